@@ -15,9 +15,10 @@ import (
 )
 
 type CycleCheckResult struct {
-	HasCycles bool                `json:"has_cycles"`
-	Cycles    [][]string          `json:"cycles"`
-	Graph     map[string][]string `json:"graph"`
+	SchemaVersion int                 `json:"schema_version"`
+	HasCycles     bool                `json:"has_cycles"`
+	Cycles        [][]string          `json:"cycles"`
+	Graph         map[string][]string `json:"graph"`
 }
 
 var checkCmd = &cobra.Command{
@@ -46,8 +47,8 @@ This command uses graph traversal algorithms to detect all cycles.`,
 			return fmt.Errorf("rqm-validator binary not found")
 		}
 
-		// Call rust-core validator with --check-cycles flag
-		validatorCmd := exec.Command(validatorPath, file, "--check-cycles")
+		// Call rust-core validator cycles subcommand
+		validatorCmd := exec.Command(validatorPath, "cycles", file)
 		output, _ := validatorCmd.CombinedOutput()
 
 		// Parse the result
@@ -55,6 +56,9 @@ This command uses graph traversal algorithms to detect all cycles.`,
 		if jsonErr := json.Unmarshal(output, &result); jsonErr != nil {
 			return fmt.Errorf("failed to parse cycle check result: %w\nOutput: %s", jsonErr, string(output))
 		}
+		if err := checkSchemaVersion(result.SchemaVersion); err != nil {
+			return err
+		}
 
 		// Display results
 		fmt.Printf("Checking %s for circular references...\n\n", file)
@@ -111,8 +115,8 @@ Useful for understanding the structure and detecting patterns.`,
 			return fmt.Errorf("rqm-validator binary not found")
 		}
 
-		// Call rust-core validator with --graph flag
-		validatorCmd := exec.Command(validatorPath, file, "--graph")
+		// Call rust-core validator graph subcommand
+		validatorCmd := exec.Command(validatorPath, "graph", file)
 		output, _ := validatorCmd.CombinedOutput()
 		if validatorPath == "" {
 			return fmt.Errorf("failed to generate graph: %s", string(output))
@@ -123,6 +127,9 @@ Useful for understanding the structure and detecting patterns.`,
 		if jsonErr := json.Unmarshal(output, &result); jsonErr != nil {
 			return fmt.Errorf("failed to parse graph result: %w\nOutput: %s", jsonErr, string(output))
 		}
+		if err := checkSchemaVersion(result.SchemaVersion); err != nil {
+			return err
+		}
 
 		// Display graph
 		fmt.Printf("Requirements Dependency Graph for %s:\n\n", file)
@@ -45,8 +45,8 @@ Displays requirements in a tree structure by default, showing:
 			return fmt.Errorf("rqm-validator binary not found")
 		}
 
-		// Call rust-core validator with --format json-full flag
-		validatorCmd := exec.Command(validatorPath, file, "--format", "json-full")
+		// Call rust-core validator export subcommand
+		validatorCmd := exec.Command(validatorPath, "export", file, "--format", "config")
 		output, _ := validatorCmd.CombinedOutput()
 		if validatorPath == "" {
 			return fmt.Errorf("failed to parse requirements: %s", string(output))
@@ -21,10 +21,29 @@ var embeddedValidator interface {
 	Available() bool
 }
 
+// validateProfile selects a rule profile (minimal, standard, strict)
+var validateProfile string
+
+// supportedSchemaVersion is the version of rqm-validator results this CLI
+// understands
+const supportedSchemaVersion = 1
+
+// checkSchemaVersion rejects results of a schema version this CLI does not
+// understand, such as those of a newer rqm-validator
+func checkSchemaVersion(version int) error {
+	if version != supportedSchemaVersion {
+		return fmt.Errorf("rqm-validator result has schema_version %d, expected %d; rebuild rqm-validator or update rqm",
+			version, supportedSchemaVersion)
+	}
+	return nil
+}
+
 type ValidationResult struct {
-	Valid    bool     `json:"valid"`
-	Errors   []string `json:"errors"`
-	Warnings []string `json:"warnings"`
+	SchemaVersion int      `json:"schema_version"`
+	Valid         bool     `json:"valid"`
+	Errors        []string `json:"errors"`
+	Warnings      []string `json:"warnings"`
+	Infos         []string `json:"infos"`
 }
 
 var validateCmd = &cobra.Command{
@@ -52,8 +71,8 @@ func runValidation(file string) error {
 		return fmt.Errorf("file does not exist: %s", file)
 	}
 
-	// Try embedded validator first (if available via CGO)
-	if embeddedValidator != nil && embeddedValidator.Available() {
+	// Try embedded validator first (if available via CGO); it has no profile support
+	if validateProfile == "" && embeddedValidator != nil && embeddedValidator.Available() {
 		return runEmbeddedValidation(file)
 	}
 
@@ -91,7 +110,11 @@ func runExternalValidation(file string) error {
 	// Call rust-core validator
 	fmt.Printf("Validating %s (using external validator)...\n", file)
 
-	validatorCmd := exec.Command(validatorPath, file)
+	validatorArgs := []string{"validate", file}
+	if validateProfile != "" {
+		validatorArgs = append(validatorArgs, "--profile", validateProfile)
+	}
+	validatorCmd := exec.Command(validatorPath, validatorArgs...)
 	output, _ := validatorCmd.CombinedOutput()
 
 	// Parse JSON output
@@ -99,6 +122,9 @@ func runExternalValidation(file string) error {
 	if jsonErr := json.Unmarshal(output, &result); jsonErr != nil {
 		return fmt.Errorf("failed to parse validator output: %w\nOutput: %s", jsonErr, string(output))
 	}
+	if err := checkSchemaVersion(result.SchemaVersion); err != nil {
+		return err
+	}
 
 	return displayValidationResult(&result)
 }
@@ -111,6 +137,8 @@ func displayValidationResult(result *ValidationResult) error {
 		fmt.Println("✓ Schema validation passed")
 		fmt.Println("✓ All summaries unique")
 		fmt.Println("✓ Owner references valid")
+		displayWarnings(result.Warnings)
+		displayInfos(result.Infos)
 		fmt.Println("\nValidation successful!")
 		return nil
 	}
@@ -121,17 +149,34 @@ func displayValidationResult(result *ValidationResult) error {
 		fmt.Printf("  - %s\n", errMsg)
 	}
 
-	// Display warnings if any
-	if len(result.Warnings) > 0 {
-		fmt.Println("\nWarnings:")
-		for _, warning := range result.Warnings {
-			fmt.Printf("  ⚠ %s\n", warning)
-		}
-	}
+	displayWarnings(result.Warnings)
+	displayInfos(result.Infos)
 
 	return fmt.Errorf("validation failed with %d error(s)", len(result.Errors))
 }
 
+// displayWarnings prints non-fatal findings, if any
+func displayWarnings(warnings []string) {
+	if len(warnings) == 0 {
+		return
+	}
+	fmt.Println("\nWarnings:")
+	for _, warning := range warnings {
+		fmt.Printf("  ⚠ %s\n", warning)
+	}
+}
+
+// displayInfos prints informational findings, if any
+func displayInfos(infos []string) {
+	if len(infos) == 0 {
+		return
+	}
+	fmt.Println("\nInfo:")
+	for _, info := range infos {
+		fmt.Printf("  ℹ %s\n", info)
+	}
+}
+
 // findValidatorBinary locates the rqm-validator binary
 func findValidatorBinary() string {
 	// Get current working directory to help construct relative paths
@@ -174,4 +219,5 @@ func findValidatorBinary() string {
 
 func init() {
 	rootCmd.AddCommand(validateCmd)
+	validateCmd.Flags().StringVar(&validateProfile, "profile", "", "Rule profile (minimal, standard, strict)")
 }
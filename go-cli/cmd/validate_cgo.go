@@ -19,9 +19,11 @@ func (v *validatorWrapper) ValidateYAML(content string) (*ValidationResult, erro
 		return nil, err
 	}
 	return &ValidationResult{
-		Valid:    result.Valid,
-		Errors:   result.Errors,
-		Warnings: result.Warnings,
+		SchemaVersion: result.SchemaVersion,
+		Valid:         result.Valid,
+		Errors:        result.Errors,
+		Warnings:      result.Warnings,
+		Infos:         result.Infos,
 	}, nil
 }
 
@@ -163,3 +163,16 @@ func findSubstring(s, substr string) bool {
 	}
 	return false
 }
+
+func TestCheckSchemaVersion(t *testing.T) {
+	if err := checkSchemaVersion(supportedSchemaVersion); err != nil {
+		t.Errorf("Expected the supported version to pass, got %v", err)
+	}
+	if err := checkSchemaVersion(supportedSchemaVersion + 1); err == nil {
+		t.Errorf("Expected a newer version to be rejected")
+	}
+	// Results without a schema_version decode as 0
+	if err := checkSchemaVersion(0); err == nil {
+		t.Errorf("Expected a missing version to be rejected")
+	}
+}
@@ -0,0 +1,253 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+package rqmcore
+
+// #include <stdlib.h>
+// #include "rqm_core.h"
+import "C"
+import (
+	"encoding/json"
+	"errors"
+	"fmt"
+	"unsafe"
+)
+
+// SchemaVersion is the version of the rqm core results this package
+// understands; results of any other version are rejected
+const SchemaVersion = 1
+
+// CycleCheckResult lists the circular references between requirements
+type CycleCheckResult struct {
+	SchemaVersion int                 `json:"schema_version"`
+	HasCycles     bool                `json:"has_cycles"`
+	Cycles        [][]string          `json:"cycles"`
+	Graph         map[string][]string `json:"graph"`
+}
+
+// QueryResult lists the requirements matching a query, with nested
+// children named by summary
+type QueryResult struct {
+	SchemaVersion int               `json:"schema_version"`
+	Requirements  []json.RawMessage `json:"requirements"`
+}
+
+// Context validates many documents with one configured validator
+type Context struct {
+	ptr *C.RqmContext
+}
+
+// NewContext creates a context validating with a rule profile such as
+// "strict"; an empty profile uses the default rules
+func NewContext(profile string) (*Context, error) {
+	var cProfile *C.char
+	if profile != "" {
+		cProfile = C.CString(profile)
+		defer C.free(unsafe.Pointer(cProfile))
+	}
+	ptr := C.rqm_context_new(cProfile)
+	if ptr == nil {
+		return nil, fmt.Errorf("failed to create validator with profile %q", profile)
+	}
+	return &Context{ptr: ptr}, nil
+}
+
+// ValidateYAML validates YAML content like the package-level ValidateYAML
+func (c *Context) ValidateYAML(yamlContent string) (*ValidationResult, error) {
+	if c.ptr == nil {
+		return nil, errors.New("context is closed")
+	}
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+
+	var result ValidationResult
+	if err := takeResult(C.rqm_context_validate_yaml(c.ptr, cYaml), &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+// Load parses YAML content once for many queries and validations. The
+// document must be closed when no longer needed.
+func (c *Context) Load(yamlContent string) (*Document, error) {
+	if c.ptr == nil {
+		return nil, errors.New("context is closed")
+	}
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+
+	ptr := C.rqm_load(c.ptr, cYaml)
+	if ptr == nil {
+		var result json.RawMessage
+		if err := takeResult(C.rqm_last_error(c.ptr), &result); err != nil {
+			return nil, err
+		}
+		return nil, errors.New("failed to load requirements")
+	}
+	return &Document{ptr: ptr}, nil
+}
+
+// Close frees the context; it must not be used afterwards
+func (c *Context) Close() {
+	if c.ptr != nil {
+		C.rqm_context_free(c.ptr)
+		c.ptr = nil
+	}
+}
+
+// Document holds requirements parsed once by Context.Load
+type Document struct {
+	ptr *C.RqmHandle
+}
+
+// Query finds the requirements matching an expression such as
+// "status==draft && tag=safety"
+func (d *Document) Query(expr string) (*QueryResult, error) {
+	if d.ptr == nil {
+		return nil, errors.New("document is closed")
+	}
+	cExpr := C.CString(expr)
+	defer C.free(unsafe.Pointer(cExpr))
+
+	var result QueryResult
+	if err := takeResult(C.rqm_query(d.ptr, cExpr), &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+// Validate validates the requirements
+func (d *Document) Validate() (*ValidationResult, error) {
+	if d.ptr == nil {
+		return nil, errors.New("document is closed")
+	}
+	var result ValidationResult
+	if err := takeResult(C.rqm_validate(d.ptr), &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+// Close frees the document; it must not be used afterwards
+func (d *Document) Close() {
+	if d.ptr != nil {
+		C.rqm_free(d.ptr)
+		d.ptr = nil
+	}
+}
+
+// ParseYAML returns the requirements as JSON, with templates applied
+func ParseYAML(yamlContent string) (json.RawMessage, error) {
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+
+	var result json.RawMessage
+	if err := takeJSON(C.rqm_parse_to_json(cYaml), &result); err != nil {
+		return nil, err
+	}
+	return result, nil
+}
+
+// CheckCycles finds circular references between requirements
+func CheckCycles(yamlContent string) (*CycleCheckResult, error) {
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+
+	var result CycleCheckResult
+	if err := takeResult(C.rqm_check_cycles(cYaml), &result); err != nil {
+		return nil, err
+	}
+	return &result, nil
+}
+
+// GraphJSON returns the requirement graph as a JSON Graph Format document
+func GraphJSON(yamlContent string) (json.RawMessage, error) {
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+
+	var result json.RawMessage
+	if err := takeJSON(C.rqm_graph_json(cYaml), &result); err != nil {
+		return nil, err
+	}
+	return result, nil
+}
+
+// DiffYAML compares two versions of the requirements
+func DiffYAML(oldYaml, newYaml string) (json.RawMessage, error) {
+	cOld := C.CString(oldYaml)
+	defer C.free(unsafe.Pointer(cOld))
+	cNew := C.CString(newYaml)
+	defer C.free(unsafe.Pointer(cNew))
+
+	var result json.RawMessage
+	if err := takeResult(C.rqm_diff_yaml(cOld, cNew), &result); err != nil {
+		return nil, err
+	}
+	return result, nil
+}
+
+// GenerateIDs assigns IDs to requirements, keeping the metadata in rqmDir,
+// and returns them by summary
+func GenerateIDs(yamlContent, rqmDir string) (map[string]string, error) {
+	cYaml := C.CString(yamlContent)
+	defer C.free(unsafe.Pointer(cYaml))
+	cDir := C.CString(rqmDir)
+	defer C.free(unsafe.Pointer(cDir))
+
+	var result struct {
+		IDs map[string]string `json:"ids"`
+	}
+	if err := takeResult(C.rqm_generate_ids(cYaml, cDir), &result); err != nil {
+		return nil, err
+	}
+	return result.IDs, nil
+}
+
+// takeJSON decodes and frees a string returned by the Rust library,
+// turning an {"error": "..."} result into an error
+func takeJSON(cResult *C.char, out interface{}) error {
+	if cResult == nil {
+		return errors.New("rqm core returned null")
+	}
+	defer C.free_string(cResult)
+	data := []byte(C.GoString(cResult))
+
+	var failure struct {
+		Error *string `json:"error"`
+	}
+	if json.Unmarshal(data, &failure) == nil && failure.Error != nil {
+		return errors.New(*failure.Error)
+	}
+	if err := json.Unmarshal(data, out); err != nil {
+		return fmt.Errorf("failed to parse rqm core result: %w", err)
+	}
+	return nil
+}
+
+// takeResult is takeJSON for the versioned results of the Rust library,
+// failing on a schema_version other than SchemaVersion
+func takeResult(cResult *C.char, out interface{}) error {
+	var data json.RawMessage
+	if err := takeJSON(cResult, &data); err != nil {
+		return err
+	}
+
+	var version struct {
+		SchemaVersion *int `json:"schema_version"`
+	}
+	if err := json.Unmarshal(data, &version); err != nil {
+		return fmt.Errorf("failed to parse rqm core result: %w", err)
+	}
+	if version.SchemaVersion == nil {
+		return errors.New("rqm core result has no schema_version")
+	}
+	if *version.SchemaVersion != SchemaVersion {
+		return fmt.Errorf("rqm core result has schema_version %d, expected %d",
+			*version.SchemaVersion, SchemaVersion)
+	}
+	if err := json.Unmarshal(data, out); err != nil {
+		return fmt.Errorf("failed to parse rqm core result: %w", err)
+	}
+	return nil
+}
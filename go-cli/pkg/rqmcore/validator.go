@@ -9,16 +9,16 @@ package rqmcore
 // #include "rqm_core.h"
 import "C"
 import (
-	"encoding/json"
-	"fmt"
 	"unsafe"
 )
 
 // ValidationResult represents the result of YAML validation
 type ValidationResult struct {
-	Valid    bool     `json:"valid"`
-	Errors   []string `json:"errors"`
-	Warnings []string `json:"warnings"`
+	SchemaVersion int      `json:"schema_version"`
+	Valid         bool     `json:"valid"`
+	Errors        []string `json:"errors"`
+	Warnings      []string `json:"warnings"`
+	Infos         []string `json:"infos"`
 }
 
 // ValidateYAML validates YAML content using the embedded Rust validator
@@ -28,20 +28,10 @@ func ValidateYAML(yamlContent string) (*ValidationResult, error) {
 	cYaml := C.CString(yamlContent)
 	defer C.free(unsafe.Pointer(cYaml))
 
-	// Call Rust validation function
-	cResult := C.validate_yaml(cYaml)
-	if cResult == nil {
-		return nil, fmt.Errorf("validation returned null")
-	}
-	defer C.free_string(cResult)
-
-	// Convert C string back to Go string
-	resultJSON := C.GoString(cResult)
-
-	// Parse JSON result
+	// Call Rust validation function and parse its JSON result
 	var result ValidationResult
-	if err := json.Unmarshal([]byte(resultJSON), &result); err != nil {
-		return nil, fmt.Errorf("failed to parse validation result: %w", err)
+	if err := takeResult(C.validate_yaml(cYaml), &result); err != nil {
+		return nil, err
 	}
 
 	return &result, nil
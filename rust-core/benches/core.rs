@@ -0,0 +1,132 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Benchmarks of the core pipeline over synthetic corpora
+//!
+//! Run with `cargo bench --bench core`. Corpora of 1k, 10k and 100k
+//! requirements are measured by default; set `RQM_BENCH_SIZES` to a comma
+//! separated list to choose others, e.g. `RQM_BENCH_SIZES=1000` for a
+//! quick run. `scripts/bench-gate.sh` compares a run against a saved
+//! baseline and fails on regressions.
+
+mod corpus;
+
+use criterion::measurement::WallTime;
+use criterion::{
+    criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
+};
+use rqm_core::graph::diff;
+use rqm_core::{Parser, RequirementGraph, Validator};
+use std::hint::black_box;
+
+const DEFAULT_SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+const SEED: u64 = 0x0052_514D;
+
+fn sizes() -> Vec<usize> {
+    match std::env::var("RQM_BENCH_SIZES") {
+        Ok(sizes) => sizes
+            .split(',')
+            .map(|size| size.trim().parse().expect("RQM_BENCH_SIZES lists numbers"))
+            .collect(),
+        Err(_) => DEFAULT_SIZES.to_vec(),
+    }
+}
+
+/// Large corpora take seconds per iteration, so fewer samples keep runs short
+fn configure(group: &mut BenchmarkGroup<'_, WallTime>, size: usize) {
+    group.sample_size(if size >= 100_000 { 10 } else { 30 });
+    group.throughput(Throughput::Elements(size as u64));
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for size in sizes() {
+        let yaml = Parser::to_yaml(&corpus::generate(size, SEED)).unwrap();
+        configure(&mut group, size);
+        group.bench_with_input(BenchmarkId::new("owned", size), &yaml, |b, yaml| {
+            b.iter(|| Parser::parse_str(black_box(yaml)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("borrowed", size), &yaml, |b, yaml| {
+            b.iter(|| Parser::parse_borrowed(black_box(yaml)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_validate(c: &mut Criterion) {
+    let validator = Validator::new().unwrap();
+    let mut group = c.benchmark_group("validate");
+    for size in sizes() {
+        let config = corpus::generate(size, SEED);
+        configure(&mut group, size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &config, |b, config| {
+            b.iter(|| validator.validate(black_box(config)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_graph_build(c: &mut Criterion) {
+    let mut group = c.benchmark_group("graph_build");
+    for size in sizes() {
+        let config = corpus::generate(size, SEED);
+        configure(&mut group, size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &config, |b, config| {
+            b.iter(|| RequirementGraph::from_config(black_box(config)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_traversal(c: &mut Criterion) {
+    let mut group = c.benchmark_group("traversal");
+    for size in sizes() {
+        let config = corpus::generate(size, SEED);
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        let root = config.requirements[0].summary.clone();
+        configure(&mut group, size);
+        group.bench_with_input(BenchmarkId::new("depth_first", size), &root, |b, root| {
+            b.iter(|| {
+                let mut visited = 0usize;
+                graph
+                    .traverse(black_box(root), |_, _| {
+                        visited += 1;
+                        Ok(())
+                    })
+                    .unwrap();
+                visited
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("cycles", size), &graph, |b, graph| {
+            b.iter(|| black_box(graph).has_cycles())
+        });
+    }
+    group.finish();
+}
+
+fn bench_diff(c: &mut Criterion) {
+    let mut group = c.benchmark_group("diff");
+    for size in sizes() {
+        let old = corpus::generate(size, SEED);
+        let new = corpus::evolve(&old, 20);
+        let old = RequirementGraph::from_config(&old).unwrap();
+        let new = RequirementGraph::from_config(&new).unwrap();
+        configure(&mut group, size);
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| diff(black_box(&old), black_box(&new)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_parse,
+    bench_validate,
+    bench_graph_build,
+    bench_traversal,
+    bench_diff
+);
+criterion_main!(benches);
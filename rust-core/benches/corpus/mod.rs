@@ -0,0 +1,155 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Synthetic requirement trees for benchmarks
+//!
+//! Corpora are generated from a seed, so every run measures the same
+//! documents. Requirements form one tree of bounded fan-out, carry the
+//! fields real files use, and some reference a later requirement elsewhere
+//! in the tree by summary.
+
+use rqm_core::types::{Priority, RequirementReference, Status};
+use rqm_core::{OwnerReference, PersonAlias, Requirement, RequirementConfig};
+
+/// Children per requirement; requirement `i` is the parent of
+/// `FAN_OUT * i + 1 ..= FAN_OUT * i + FAN_OUT`
+const FAN_OUT: usize = 5;
+
+/// Every this many requirements also reference a later one by summary
+const REFERENCE_EVERY: usize = 7;
+
+const STATUSES: [Status; 5] = [
+    Status::Draft,
+    Status::Proposed,
+    Status::Approved,
+    Status::Implemented,
+    Status::Verified,
+];
+
+const PRIORITIES: [Priority; 4] = [
+    Priority::Critical,
+    Priority::High,
+    Priority::Medium,
+    Priority::Low,
+];
+
+const TAGS: [&str; 6] = [
+    "security",
+    "performance",
+    "ui",
+    "api",
+    "storage",
+    "compliance",
+];
+
+/// Small deterministic generator, so corpora need no `rand` dependency
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+/// Generate a config holding `count` requirements
+pub fn generate(count: usize, seed: u64) -> RequirementConfig {
+    let mut rng = SplitMix(seed);
+    let mut flat: Vec<Requirement> = (0..count)
+        .map(|index| requirement(index, &mut rng))
+        .collect();
+
+    // Children and references only ever point to a later requirement, so
+    // no reference can close a cycle
+    for index in (0..count.saturating_sub(1)).step_by(REFERENCE_EVERY) {
+        let target = index + 1 + rng.below(count - index - 1);
+        let is_child = (target - 1) / FAN_OUT == index;
+        if !is_child {
+            flat[index]
+                .requirements
+                .push(RequirementReference::Reference(summary(target)));
+        }
+    }
+
+    // Attach children bottom-up so each subtree is complete when moved
+    let mut slots: Vec<Option<Requirement>> = flat.into_iter().map(Some).collect();
+    for index in (1..count).rev() {
+        let child = slots[index]
+            .take()
+            .expect("each requirement is attached once");
+        let parent = slots[(index - 1) / FAN_OUT]
+            .as_mut()
+            .expect("parents precede their children");
+        parent
+            .requirements
+            .insert(0, RequirementReference::Full(Box::new(child)));
+    }
+
+    RequirementConfig {
+        version: "1.0".to_string(),
+        aliases: vec![PersonAlias {
+            alias: "platform".to_string(),
+            name: Some("Platform Team".to_string()),
+            email: Some("platform@example.com".to_string()),
+            github: None,
+        }],
+        templates: vec![],
+        requirements: slots.into_iter().flatten().collect(),
+    }
+}
+
+/// A copy of `config` with every `every`th requirement verified and reworded
+pub fn evolve(config: &RequirementConfig, every: usize) -> RequirementConfig {
+    let mut evolved = config.clone();
+    let mut index = 0;
+    for req in &mut evolved.requirements {
+        advance(req, every, &mut index);
+    }
+    evolved
+}
+
+fn advance(req: &mut Requirement, every: usize, index: &mut usize) {
+    if index.is_multiple_of(every) {
+        req.status = Some(Status::Verified);
+        req.description = Some(format!("{} (revised)", req.summary));
+    }
+    *index += 1;
+    for child in &mut req.requirements {
+        if let RequirementReference::Full(child) = child {
+            advance(child, every, index);
+        }
+    }
+}
+
+fn summary(index: usize) -> String {
+    format!("REQ-{:06} requirement", index)
+}
+
+fn requirement(index: usize, rng: &mut SplitMix) -> Requirement {
+    let mut req = Requirement::new(summary(index));
+    req.name = Some(format!("REQ-{:06}", index));
+    req.description = Some(format!(
+        "The system shall satisfy synthetic requirement {} within the agreed limits.",
+        index
+    ));
+    req.justification = Some("Generated for benchmarking".to_string());
+    req.acceptance_test = Some(format!("Check {} under load", index));
+    req.owner = Some(OwnerReference::String(if index.is_multiple_of(3) {
+        "platform".to_string()
+    } else {
+        format!("owner{}@example.com", rng.below(50))
+    }));
+    req.tags = vec![TAGS[rng.below(TAGS.len())].to_string()];
+    req.priority = Some(PRIORITIES[rng.below(PRIORITIES.len())]);
+    req.status = Some(STATUSES[rng.below(STATUSES.len())]);
+    req.estimate = Some((1 + rng.below(13)) as f64);
+    req
+}
@@ -0,0 +1,35 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+// Regenerates rqm_core.h, the C header the Go CLI includes, from src/ffi.rs
+// with the `c-header` feature. The header is committed, so ordinary builds
+// leave the source tree alone; CI checks it is up to date.
+
+fn main() {
+    #[cfg(feature = "c-header")]
+    header::generate();
+}
+
+#[cfg(feature = "c-header")]
+mod header {
+    use std::env;
+    use std::path::PathBuf;
+
+    pub fn generate() {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+
+        let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+        let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+            .expect("cbindgen.toml should be a valid cbindgen configuration");
+        // Only the FFI module is read, so no crate metadata is needed
+        let bindings = cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(crate_dir.join("src/ffi.rs"))
+            .generate()
+            .expect("src/ffi.rs should generate a C header");
+        // Only rewrites the header when its content changes
+        bindings.write_to_file(crate_dir.join("rqm_core.h"));
+    }
+}
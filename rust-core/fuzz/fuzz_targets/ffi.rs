@@ -0,0 +1,53 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Call the C entry points the Go CLI uses with arbitrary documents
+//!
+//! Input is split at its first NUL byte into two documents, so
+//! `rqm_diff_yaml` and `rqm_query` get a second argument. Every call must
+//! return JSON, or null where the API allows it, and free cleanly.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rqm_core::ffi;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+
+/// Check a returned string is JSON and free it
+fn consume(result: *mut c_char) {
+    assert!(!result.is_null());
+    let json = unsafe { CStr::from_ptr(result) }
+        .to_str()
+        .expect("results are UTF-8");
+    serde_json::from_str::<serde_json::Value>(json).expect("results are JSON");
+    unsafe { ffi::free_string(result) };
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut parts = data.splitn(3, |&byte| byte == 0);
+    let first = CString::new(parts.next().unwrap_or_default()).unwrap();
+    let second = CString::new(parts.next().unwrap_or_default()).unwrap();
+    let (first, second) = (first.as_ptr(), second.as_ptr());
+
+    unsafe {
+        consume(ffi::validate_yaml(first));
+        consume(ffi::rqm_parse_to_json(first));
+        consume(ffi::rqm_check_cycles(first));
+        consume(ffi::rqm_graph_json(first));
+        consume(ffi::rqm_diff_yaml(first, second));
+
+        let context = ffi::rqm_context_new(std::ptr::null());
+        assert!(!context.is_null());
+        let handle = ffi::rqm_load(context, first);
+        if handle.is_null() {
+            consume(ffi::rqm_last_error(context));
+        } else {
+            consume(ffi::rqm_query(handle, second));
+            consume(ffi::rqm_validate(handle));
+            ffi::rqm_free(handle);
+        }
+        ffi::rqm_context_free(context);
+    }
+});
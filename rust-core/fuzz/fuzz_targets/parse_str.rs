@@ -0,0 +1,29 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Parse arbitrary text as a requirements document
+//!
+//! Every input must be rejected with an error, by both parse modes, or
+//! parse into requirements that serialize and parse again to the same
+//! config. Configs are compared as YAML, since a `.nan` estimate is never
+//! equal to itself.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rqm_core::Parser;
+
+fuzz_target!(|yaml: &str| {
+    let borrowed = Parser::parse_borrowed(yaml).map(|config| config.into_owned());
+    let Ok(config) = Parser::parse_str(yaml) else {
+        assert!(borrowed.is_err(), "only the borrowed parse accepted the input");
+        return;
+    };
+    let yaml = Parser::to_yaml(&config).expect("parsed configs serialize");
+    let borrowed = borrowed.expect("the borrowed parse accepts what the owned one does");
+    assert_eq!(Parser::to_yaml(&borrowed).unwrap(), yaml);
+
+    let reparsed = Parser::parse_str(&yaml).expect("serialized configs parse");
+    assert_eq!(Parser::to_yaml(&reparsed).unwrap(), yaml);
+});
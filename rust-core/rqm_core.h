@@ -1,22 +1,160 @@
 // RQM Core C Header
 // FFI interface for Go CGO integration
+//
+// Generated by cbindgen from src/ffi.rs; do not edit
 
 #ifndef RQM_CORE_H
 #define RQM_CORE_H
 
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Validator configured once and reused across calls
+//
+// Also holds why the last `rqm_load` with it failed, if it did.
+typedef struct RqmContext RqmContext;
+
+// Requirements parsed once by `rqm_load`, so they can be queried and
+// validated many times without parsing them again
+//
+// Shares the validator of the context it was loaded with.
+typedef struct RqmHandle RqmHandle;
+
 #ifdef __cplusplus
 extern "C" {
-#endif
+#endif // __cplusplus
+
+// Validate a YAML file and return JSON result
+//
+// # Safety
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *validate_yaml(const char *yaml_content);
+
+// Create a context validating with the rule profile `profile`
+//
+// A null `profile` uses the default rules. Returns null if the profile is
+// unknown or the validator cannot be created.
+//
+// # Safety
+// - `profile` must be null or a valid null-terminated C string
+// - Caller must free the returned context with `rqm_context_free`
+struct RqmContext *rqm_context_new(const char *profile);
+
+// Validate a YAML file with a context, returning the same JSON as `validate_yaml`
+//
+// # Safety
+// - `context` must be a context returned by `rqm_context_new`
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *rqm_context_validate_yaml(const struct RqmContext *context, const char *yaml_content);
+
+// Free a context created by `rqm_context_new`
+//
+// # Safety
+// - `context` must be null or a context returned by `rqm_context_new`
+// - `context` must not be used after this call
+void rqm_context_free(struct RqmContext *context);
+
+// Parse YAML and return the requirements as JSON, with templates applied
+//
+// Like the other functions below, returns an `ErrorResult`,
+// `{"schema_version": 1, "error": "..."}`, on failure.
+//
+// # Safety
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *rqm_parse_to_json(const char *yaml_content);
+
+// Find circular references, returning a `CycleCheckResult`
+//
+// # Safety
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *rqm_check_cycles(const char *yaml_content);
+
+// Return the requirement graph as a JSON Graph Format document
+//
+// # Safety
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *rqm_graph_json(const char *yaml_content);
+
+// Compare two versions of the requirements, returning a `DiffResult`
+//
+// # Safety
+// - `old_yaml` and `new_yaml` must be valid null-terminated C strings
+// - Caller must free the returned string with `free_string`
+char *rqm_diff_yaml(const char *old_yaml, const char *new_yaml);
+
+// Assign IDs to requirements, returning an `IdsResult`
+//
+// Metadata is read from and written to the `.rqm` directory `rqm_dir`, so
+// requirements keep their IDs and new ones are numbered after them.
+//
+// # Safety
+// - `yaml_content` and `rqm_dir` must be valid null-terminated C strings
+// - Caller must free the returned string with `free_string`
+char *rqm_generate_ids(const char *yaml_content, const char *rqm_dir);
+
+// Parse YAML once for use with `rqm_query` and `rqm_validate`
+//
+// Returns null if the YAML cannot be parsed; `rqm_last_error` then tells
+// why. Handles are independent of each other, so a context can load any
+// number of documents.
+//
+// # Safety
+// - `context` must be a context returned by `rqm_context_new`, not in use
+//   by another thread
+// - `yaml_content` must be a valid null-terminated C string
+// - Caller must free the returned handle with `rqm_free`
+struct RqmHandle *rqm_load(struct RqmContext *context, const char *yaml_content);
+
+// Why the last `rqm_load` with `context` failed, as an `ErrorResult`, or
+// null if it succeeded
+//
+// # Safety
+// - `context` must be a context returned by `rqm_context_new`
+// - Caller must free the returned string with `free_string`
+char *rqm_last_error(const struct RqmContext *context);
+
+// Find the loaded requirements matching a query expression such as
+// `status==draft && tag=safety`, returning a `QueryResult`
+//
+// Each match is listed once, with its nested children as references to
+// their summaries.
+//
+// # Safety
+// - `handle` must be a handle returned by `rqm_load`
+// - `expr` must be a valid null-terminated C string
+// - Caller must free the returned string with `free_string`
+char *rqm_query(const struct RqmHandle *handle, const char *expr);
+
+// Validate the loaded requirements, returning the same JSON as `validate_yaml`
+//
+// # Safety
+// - `handle` must be a handle returned by `rqm_load`
+// - Caller must free the returned string with `free_string`
+char *rqm_validate(const struct RqmHandle *handle);
 
-// Validate YAML content and return JSON result
-// Returns: JSON string (must be freed with free_string)
-char* validate_yaml(const char* yaml_content);
+// Free a handle returned by `rqm_load`
+//
+// # Safety
+// - `handle` must be null or a handle returned by `rqm_load`
+// - `handle` must not be used after this call
+void rqm_free(struct RqmHandle *handle);
 
 // Free a string allocated by Rust
-void free_string(char* s);
+//
+// # Safety
+// - `s` must be a string previously returned by a Rust FFI function
+// - `s` must not be used after this call
+void free_string(char *s);
 
 #ifdef __cplusplus
-}
-#endif
+}  // extern "C"
+#endif  // __cplusplus
 
-#endif // RQM_CORE_H
+#endif  /* RQM_CORE_H */
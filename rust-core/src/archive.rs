@@ -0,0 +1,511 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Archiving deprecated requirements
+//!
+//! Deleting deprecated requirements loses the audit trail, while keeping
+//! them inline clutters every report. [`archive_deprecated`] moves them out
+//! of a requirements file into `archive/<file name>` next to it, and their
+//! metadata into `.rqm/archive.jsonl`, so UUIDs and the change history
+//! survive. Like [`MetadataStore::add_requirement`], the file is edited as
+//! text, so comments and formatting elsewhere stay untouched.
+//!
+//! Archived requirements are left out of everything reading the file.
+//! [`include_archived`] brings them back, and [`Validator::with_archive`]
+//! lets references to them resolve without validating them; the language
+//! server, `watch` and `rqm-server` load the archives of their files for it.
+//!
+//! [`Validator::with_archive`]: crate::Validator::with_archive
+
+use crate::error::{Error, Result};
+use crate::metadata::{write_atomic, MetadataStore};
+use crate::parser::Parser;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory next to a requirements file that holds its archive
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// Result of [`archive_deprecated`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveReport {
+    /// Summaries of the archived requirements, nested ones included
+    pub archived: Vec<String>,
+
+    /// Deprecated requirements left in place because requirements nested
+    /// in them are not deprecated, or requirements left in place reference
+    /// them
+    pub skipped: Vec<String>,
+
+    /// Archive file the requirements were moved to
+    pub path: PathBuf,
+}
+
+/// Archive file of a requirements file, e.g. `archive/requirements.yml`
+/// for `requirements.yml`
+pub fn archive_path(path: &Path) -> PathBuf {
+    path.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .join(ARCHIVE_DIR)
+        .join(
+            path.file_name()
+                .unwrap_or_else(|| OsStr::new("requirements.yml")),
+        )
+}
+
+/// Requirements archived from a requirements file, if any were
+pub fn load_archive(path: &Path) -> Result<Option<RequirementConfig>> {
+    let archive = archive_path(path);
+    if !archive.exists() {
+        return Ok(None);
+    }
+    Parser::parse_file(&archive).map(Some)
+}
+
+/// Requirements archived from any of `paths`, as one configuration
+pub fn load_archives<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+) -> Result<Option<RequirementConfig>> {
+    let mut all: Option<RequirementConfig> = None;
+    for path in paths {
+        if let Some(archived) = load_archive(path.as_ref())? {
+            all = Some(match all {
+                Some(all) => include_archived(&all, &archived),
+                None => archived,
+            });
+        }
+    }
+    Ok(all)
+}
+
+/// `config` with the archived requirements appended at the top level
+pub fn include_archived(
+    config: &RequirementConfig,
+    archived: &RequirementConfig,
+) -> RequirementConfig {
+    let mut merged = config.clone();
+    merged
+        .requirements
+        .extend(archived.requirements.iter().cloned());
+    merged
+}
+
+/// Move the deprecated requirements of a file into its archive
+///
+/// A deprecated requirement is moved with everything nested in it, so it
+/// is left in place when any of that is still active, or when a
+/// requirement left in place lists any of it by reference. The metadata of
+/// moved requirements goes to the archive of `store`, if given. The file
+/// is only rewritten when the edited text parses to the old requirements
+/// minus the archived ones.
+///
+/// The archive is written first, then the file, then the metadata. Each
+/// step can be repeated, so running again after an interruption finishes
+/// the job: requirements already in the archive as they are in the file
+/// are not added twice, and metadata of archived requirements still in the
+/// store is moved.
+pub fn archive_deprecated(
+    path: &Path,
+    mut store: Option<&mut MetadataStore>,
+) -> Result<ArchiveReport> {
+    let source = fs::read_to_string(path)?;
+    let config = Parser::parse_str(&source)?;
+    let archive = archive_path(path);
+    let mut archived = load_archive(path)?;
+
+    // Finish moving the metadata of an interrupted run
+    if let (Some(store), Some(archived)) = (store.as_deref_mut(), &archived) {
+        let active: HashSet<&str> = config
+            .all_requirements()
+            .into_iter()
+            .map(|req| req.summary.as_str())
+            .collect();
+        let left: Vec<&Requirement> = archived
+            .all_requirements()
+            .into_iter()
+            .filter(|req| !active.contains(req.summary.as_str()))
+            .collect();
+        store.archive_metadata(&left)?;
+    }
+
+    let mut moved: Vec<&Requirement> = vec![];
+    let mut skipped = vec![];
+    let mut stack: Vec<&Requirement> = config.requirements.iter().rev().collect();
+    while let Some(req) = stack.pop() {
+        if req.is_deprecated() {
+            if req.flatten().iter().all(|nested| nested.is_deprecated()) {
+                moved.push(req);
+                continue;
+            }
+            skipped.push(req.summary.clone());
+        }
+        for child in req.requirements.iter().rev() {
+            if let RequirementReference::Full(child) = child {
+                stack.push(child);
+            }
+        }
+    }
+    // Moving a requirement that is still referenced would leave the
+    // reference dangling
+    while let Some(index) = referenced(&config, &moved) {
+        skipped.push(moved.remove(index).summary.clone());
+    }
+    let mut report = ArchiveReport {
+        archived: vec![],
+        skipped,
+        path: archive.clone(),
+    };
+    if moved.is_empty() {
+        return Ok(report);
+    }
+
+    let mut content = source.clone();
+    for req in &moved {
+        content = remove_requirement(&content, &req.summary).ok_or_else(|| {
+            Error::custom(format!(
+                "Could not find where '{}' is defined in {}",
+                req.summary,
+                path.display()
+            ))
+        })?;
+    }
+    let summaries: HashSet<&str> = moved.iter().map(|req| req.summary.as_str()).collect();
+    let expected = RequirementConfig {
+        requirements: without(&config.requirements, &summaries),
+        ..config.clone()
+    };
+    if Parser::parse_str(&content).ok().as_ref() != Some(&expected) {
+        return Err(Error::custom(format!(
+            "Could not archive deprecated requirements of {} without changing the others",
+            path.display()
+        )));
+    }
+
+    let mut archived = archived.take().unwrap_or_else(|| RequirementConfig {
+        version: config.version.clone(),
+        aliases: vec![],
+        templates: vec![],
+        requirements: vec![],
+    });
+    let all_moved: Vec<&Requirement> = moved.iter().flat_map(|req| req.flatten()).collect();
+    // Archived by an interrupted run, before the file was rewritten
+    let written: Vec<&Requirement> = moved
+        .iter()
+        .copied()
+        .filter(|req| archived.requirements.contains(req))
+        .collect();
+    let existing: HashSet<String> = archived
+        .requirements
+        .iter()
+        .filter(|req| !written.contains(req))
+        .flat_map(|req| req.flatten())
+        .map(|req| req.summary.clone())
+        .collect();
+    if let Some(req) = all_moved.iter().find(|req| existing.contains(&req.summary)) {
+        return Err(Error::DuplicateSummary(format!(
+            "'{}' is already archived in {}",
+            req.summary,
+            archive.display()
+        )));
+    }
+    // Aliases and templates keep the archive readable on its own
+    for alias in &config.aliases {
+        if !archived.aliases.iter().any(|a| a.alias == alias.alias) {
+            archived.aliases.push(alias.clone());
+        }
+    }
+    for template in &config.templates {
+        if !archived.templates.iter().any(|t| t.name == template.name) {
+            archived.templates.push(template.clone());
+        }
+    }
+    archived.requirements.extend(
+        moved
+            .iter()
+            .filter(|req| !written.contains(req))
+            .map(|req| (*req).clone()),
+    );
+
+    if let Some(dir) = archive.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    write_atomic(&archive, Parser::to_yaml(&archived)?.as_bytes())?;
+    write_atomic(path, content.as_bytes())?;
+    if let Some(store) = store {
+        store.archive_metadata(&all_moved)?;
+    }
+
+    report.archived = all_moved.iter().map(|req| req.summary.clone()).collect();
+    Ok(report)
+}
+
+/// Index in `moved` of a requirement that, or one nested in it, is listed
+/// by reference by a requirement that is not moved
+fn referenced(config: &RequirementConfig, moved: &[&Requirement]) -> Option<usize> {
+    let moving: HashSet<&str> = moved
+        .iter()
+        .flat_map(|req| req.flatten())
+        .map(|req| req.summary.as_str())
+        .collect();
+    let references: HashSet<&str> = config
+        .all_requirements()
+        .into_iter()
+        .filter(|req| !moving.contains(req.summary.as_str()))
+        .flat_map(|req| &req.requirements)
+        .filter_map(|child| match child {
+            RequirementReference::Reference(summary) => Some(summary.as_str()),
+            RequirementReference::Full(_) => None,
+        })
+        .collect();
+    moved.iter().position(|req| {
+        req.flatten()
+            .iter()
+            .any(|nested| references.contains(nested.summary.as_str()))
+    })
+}
+
+/// Requirements with those named in `summaries` removed, at any depth
+fn without(reqs: &[Requirement], summaries: &HashSet<&str>) -> Vec<Requirement> {
+    reqs.iter()
+        .filter(|req| !summaries.contains(req.summary.as_str()))
+        .map(|req| {
+            let mut req = req.clone();
+            req.requirements = req
+                .requirements
+                .into_iter()
+                .filter_map(|child| match child {
+                    RequirementReference::Full(child) => without(&[*child], summaries)
+                        .pop()
+                        .map(|child| RequirementReference::Full(Box::new(child))),
+                    reference => Some(reference),
+                })
+                .collect();
+            req
+        })
+        .collect()
+}
+
+/// Remove the list item of the requirement with `summary`, with everything
+/// nested in it
+///
+/// A `requirements:` list left empty becomes `[]`. Returns `None` for
+/// layouts that cannot be edited line by line, such as flow-style lists.
+fn remove_requirement(content: &str, summary: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let is_blank = |line: &str| line.trim().is_empty();
+
+    let index = Parser::summary_line(content, summary)? - 1;
+    let column = lines[index].find("summary:")?;
+    let item_indent = column.checked_sub(2)?;
+    let start = (0..=index)
+        .rev()
+        .find(|&i| indent(lines[i]) == item_indent && lines[i].trim_start().starts_with("- "))?;
+    if (start + 1..index).any(|i| !is_blank(lines[i]) && indent(lines[i]) < column) {
+        return None;
+    }
+
+    let mut end = index + 1;
+    let mut block_end = end;
+    while end < lines.len() {
+        let line = lines[end];
+        end += 1;
+        if is_blank(line) {
+            continue;
+        }
+        if indent(line) < column {
+            break;
+        }
+        block_end = end;
+    }
+
+    let mut kept: Vec<String> = lines[..start]
+        .iter()
+        .chain(&lines[block_end..])
+        .map(|line| line.to_string())
+        .collect();
+
+    // The list is empty when neither neighbour is another of its items
+    let is_item = |line: &str| indent(line) == item_indent && line.trim_start().starts_with('-');
+    let previous = (0..start).rev().find(|&i| {
+        let line = kept[i].trim_start();
+        !line.is_empty() && !line.starts_with('#')
+    });
+    let next = kept[start..].iter().find(|line| {
+        let line = line.trim_start();
+        !line.is_empty() && !line.starts_with('#')
+    });
+    if let Some(key) = previous {
+        let line = kept[key].clone();
+        let rest = line
+            .trim_start()
+            .strip_prefix("- ")
+            .unwrap_or(line.trim_start());
+        let is_key = rest.strip_prefix("requirements:").is_some_and(|value| {
+            let value = value.trim();
+            value.is_empty() || value.starts_with('#')
+        });
+        if is_key && !is_item(&line) && !next.is_some_and(|line| is_item(line)) {
+            kept[key] = line.replacen("requirements:", "requirements: []", 1);
+        }
+    }
+
+    let mut edited = kept.join("\n");
+    if content.ends_with('\n') {
+        edited.push('\n');
+    }
+    Some(edited)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"# Product requirements
+version: "1.0"
+requirements:
+  - summary: Login
+    status: approved
+    requirements:
+      # Replaced by SSO
+      - summary: Password login
+        status: deprecated
+        superseded_by: Login
+        requirements:
+          - summary: Password reset
+            status: deprecated
+            superseded_by: Login
+  - summary: Export
+    status: deprecated
+    superseded_by: Login
+    requirements:
+      - summary: CSV export
+        status: draft
+  - summary: Legacy API
+    status: deprecated
+    superseded_by: Login
+"#;
+
+    #[test]
+    fn test_remove_requirement_keeps_the_rest() {
+        let edited = remove_requirement(YAML, "Legacy API").unwrap();
+        assert!(edited.ends_with("        status: draft\n"));
+
+        let edited = remove_requirement(YAML, "Password login").unwrap();
+        assert!(edited.contains(
+            "    status: approved\n    requirements: []\n      # Replaced by SSO\n  - summary: Export"
+        ));
+        assert!(edited.starts_with("# Product requirements\n"));
+    }
+
+    #[test]
+    fn test_archive_deprecated_moves_requirements_and_metadata() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("requirements.yml");
+        fs::write(&path, YAML).unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "ARC".to_string()).unwrap();
+        let config = Parser::parse_file(&path).unwrap();
+        for req in config.all_requirements() {
+            store.get_or_create_metadata(req).unwrap();
+        }
+        let uuid = store.find_metadata("Legacy API").unwrap().unwrap().uuid;
+
+        let report = archive_deprecated(&path, Some(&mut store)).unwrap();
+        assert_eq!(
+            report.archived,
+            vec!["Password login", "Password reset", "Legacy API"]
+        );
+        assert_eq!(report.skipped, vec!["Export"]);
+        assert_eq!(report.path, temp.path().join("archive/requirements.yml"));
+
+        let active = Parser::parse_file(&path).unwrap();
+        let summaries: Vec<_> = active
+            .all_requirements()
+            .into_iter()
+            .map(|req| req.summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["Login", "Export", "CSV export"]);
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("# Product requirements"));
+
+        let archived = load_archive(&path).unwrap().unwrap();
+        assert_eq!(archived.all_requirements().len(), 3);
+        assert_eq!(
+            include_archived(&active, &archived)
+                .all_requirements()
+                .len(),
+            6
+        );
+
+        assert!(store.find_metadata("Legacy API").unwrap().is_none());
+        let prune = store
+            .prune(&include_archived(&active, &archived), Default::default())
+            .unwrap();
+        assert!(prune.id_gaps.is_empty());
+        let records = fs::read_to_string(temp.path().join(".rqm/archive.jsonl")).unwrap();
+        assert!(records.contains(&uuid.to_string()));
+
+        // Nothing is left to archive
+        let again = archive_deprecated(&path, Some(&mut store)).unwrap();
+        assert!(again.archived.is_empty());
+    }
+
+    #[test]
+    fn test_archive_deprecated_keeps_referenced_requirements() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("requirements.yml");
+        let yaml = YAML.replace(
+            "    status: approved\n    requirements:\n",
+            "    status: approved\n    requirements:\n      - Legacy API\n",
+        );
+        fs::write(&path, yaml).unwrap();
+
+        let report = archive_deprecated(&path, None).unwrap();
+        assert_eq!(report.archived, vec!["Password login", "Password reset"]);
+        assert_eq!(report.skipped, vec!["Export", "Legacy API"]);
+        assert!(Parser::parse_file(&path).unwrap().requirements[2].summary == "Legacy API");
+    }
+
+    #[test]
+    fn test_archive_deprecated_finishes_an_interrupted_run() {
+        let setup = || {
+            let temp = TempDir::new().unwrap();
+            let path = temp.path().join("requirements.yml");
+            fs::write(&path, YAML).unwrap();
+            let mut store =
+                MetadataStore::init(temp.path().join(".rqm"), "ARC".to_string()).unwrap();
+            for req in Parser::parse_file(&path).unwrap().all_requirements() {
+                store.get_or_create_metadata(req).unwrap();
+            }
+            (temp, path, store)
+        };
+
+        // Stopped after writing the archive
+        let (_temp, path, mut store) = setup();
+        archive_deprecated(&path, None).unwrap();
+        let archive = fs::read_to_string(archive_path(&path)).unwrap();
+        fs::write(&path, YAML).unwrap();
+        let report = archive_deprecated(&path, Some(&mut store)).unwrap();
+        assert_eq!(report.archived.len(), 3);
+        assert_eq!(fs::read_to_string(archive_path(&path)).unwrap(), archive);
+        assert!(store.find_metadata("Legacy API").unwrap().is_none());
+
+        // Stopped after rewriting the file
+        let (_temp, path, mut store) = setup();
+        archive_deprecated(&path, None).unwrap();
+        assert!(store.find_metadata("Legacy API").unwrap().is_some());
+        let report = archive_deprecated(&path, Some(&mut store)).unwrap();
+        assert!(report.archived.is_empty());
+        assert!(store.find_metadata("Legacy API").unwrap().is_none());
+        assert!(store.find_metadata("Password reset").unwrap().is_none());
+        assert!(store.find_metadata("Login").unwrap().is_some());
+    }
+}
@@ -0,0 +1,18 @@
+//! RQM Language Server
+//!
+//! Speaks the Language Server Protocol over standard input and output,
+//! giving editors diagnostics, hover, go-to-definition and completion for
+//! requirements YAML files.
+
+use std::io;
+use std::process;
+
+fn main() {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut writer = io::stdout().lock();
+    if let Err(e) = rqm_core::lsp::serve(&mut reader, &mut writer) {
+        eprintln!("rqm-lsp: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,49 @@
+//! RQM HTTP server
+//!
+//! Serves read-only JSON endpoints for requirements, graph queries,
+//! validation and reports, and the OSLC RM provider; see
+//! [`rqm_core::server`] for the routes.
+
+use clap::Parser;
+use rqm_core::server::{self, ServerState};
+use std::path::PathBuf;
+use std::process;
+use tokio::net::TcpListener;
+
+#[derive(Parser)]
+#[command(name = "rqm-server", about = "Serve requirements over HTTP", version)]
+struct Cli {
+    /// Requirements files to serve, read again on every request
+    #[arg(required = true)]
+    files: Vec<PathBuf>,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+
+    /// Public URL of the OSLC endpoints, e.g. https://rqm.example.com/oslc;
+    /// taken from the Host header when omitted
+    #[arg(long)]
+    oslc_base_url: Option<String>,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let listener = match TcpListener::bind(&cli.addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("rqm-server: cannot listen on {}: {}", cli.addr, e);
+            process::exit(1);
+        }
+    };
+    eprintln!("rqm-server: listening on http://{}", cli.addr);
+    let mut state = ServerState::new(cli.files);
+    if let Some(base_url) = cli.oslc_base_url {
+        state = state.with_oslc_base_url(base_url);
+    }
+    if let Err(e) = server::serve(listener, state).await {
+        eprintln!("rqm-server: {}", e);
+        process::exit(1);
+    }
+}
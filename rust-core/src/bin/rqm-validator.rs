@@ -2,156 +2,2185 @@
 //!
 //! Standalone binary for validating requirements YAML files.
 //! Designed to be called by the Go CLI and other language bindings.
+//!
+//! Exits with 0 on success, 1 when requirements are invalid, contain cycles
+//! or could not be synced cleanly, and 2 on usage and I/O errors.
 
-use rqm_core::types::RequirementReference;
-use rqm_core::{Parser, RequirementGraph, Validator};
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::env;
+use clap::{Args, CommandFactory, Parser as _, Subcommand, ValueEnum};
+use rqm_core::archive;
+use rqm_core::export::{MarkdownOptions, SiteOptions};
+use rqm_core::graph::analysis::{
+    self, AnalysisFinding, AnalysisOptions, FindingKind, DEFAULT_STALE_DAYS,
+};
+use rqm_core::graph::{diff, diff_with_metadata, GraphDiff};
+use rqm_core::hooks::{self, HookOptions};
+use rqm_core::interop::to_reqif;
+use rqm_core::locale;
+use rqm_core::merge::{self, MergeConflict, Side};
+use rqm_core::metadata::{write_atomic, IdScheme};
+use rqm_core::output::{
+    self, ArchiveResult, BaselineResult, ChangelogResult, CycleCheckResult, DiffResult,
+    HookInstallResult, InitResult, MergeResult, NewRequirementResult, NotifyResult, OutlineResult,
+    PdfResult, QueryResult, SearchResult, SignoffResult, SiteResult, SyncResult, ValidationResult,
+    WorkloadResult,
+};
+use rqm_core::query::{self, Expr};
+use rqm_core::redact::{self, Redaction};
+use rqm_core::report::{self, ReportOptions};
+use rqm_core::scaffold::{self, ScaffoldOptions};
+use rqm_core::search::{SearchHit, SearchIndex};
+use rqm_core::types::{Approval, Confidentiality, OwnerReference, RequirementConfig, Status};
+use rqm_core::validator::{
+    Baseline, Finding, Glossary, Severity, ValidationCache, ValidationReport, SCHEMA_RULE,
+};
+#[cfg(feature = "watch")]
+use rqm_core::watch::WatchSession;
+use rqm_core::workspace::{ProjectScope, WorkspaceFile};
+use rqm_core::{
+    export, DotOptions, LintConfig, MetadataStore, Monorepo, Parser, RequirementGraph, Validator,
+    Workspace,
+};
+use serde::Serialize;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process;
 
-#[derive(Debug, Serialize, Deserialize)]
-struct ValidationResult {
-    valid: bool,
-    errors: Vec<String>,
-    warnings: Vec<String>,
+/// Requirements are invalid, contain cycles or conflict with a tracker
+const EXIT_INVALID: i32 = 1;
+
+/// The command could not be carried out
+const EXIT_ERROR: i32 = 2;
+
+#[derive(clap::Parser)]
+#[command(
+    name = "rqm-validator",
+    version,
+    about = "Validate, query and export requirements YAML files"
+)]
+struct Cli {
+    /// Format of the results printed to stdout
+    #[arg(long, value_enum, global = true, default_value_t = Output::Json)]
+    output: Output,
+
+    /// Print the JSON Schemas of all results and exit
+    #[arg(long, exclusive = true)]
+    output_schema: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Output {
+    Json,
+    Text,
+    Yaml,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate requirements; several files are validated as one workspace
+    Validate(ValidateArgs),
+
+    /// Check requirements for circular references
+    Cycles { file: PathBuf },
+
+    /// Print the requirement graph
+    Graph {
+        file: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = GraphFormat::Adjacency)]
+        format: GraphFormat,
+
+        /// Add the requirements archived from the file
+        #[arg(long)]
+        include_archived: bool,
+    },
+
+    /// Render requirements in another format
+    Export {
+        file: PathBuf,
+
+        #[arg(long, value_enum)]
+        format: ExportFormat,
+
+        /// Locale to export the requirement text in, e.g. de; text without
+        /// a translation is exported as written
+        #[arg(long)]
+        lang: Option<String>,
+
+        /// Confidentiality readers are cleared for, e.g. public for external
+        /// suppliers; more confidential requirements are withheld
+        #[arg(long, value_enum)]
+        clearance: Option<Clearance>,
+
+        /// What is left of withheld requirements
+        #[arg(long, value_enum, default_value = "redact", requires = "clearance")]
+        withhold: Withhold,
+    },
+
+    /// Compare two versions of a requirements file
+    Diff {
+        /// Old and new file, or only the new file with --git
+        #[arg(required = true, num_args = 1..=2)]
+        files: Vec<PathBuf>,
+
+        /// Compare the file with its version at this git revision
+        #[arg(long)]
+        git: Option<String>,
+    },
+
+    /// Print a Markdown changelog between two baselines or git revisions
+    Changelog {
+        file: PathBuf,
+
+        /// Baseline (or git revision with --git) to compare from
+        #[arg(long)]
+        from: String,
+
+        /// Baseline (or git revision with --git) to compare to; defaults to
+        /// the working file
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Read `--from` and `--to` as git revisions instead of baselines
+        #[arg(long)]
+        git: bool,
+
+        /// Heading of the changelog; defaults to the `--to` label
+        #[arg(long)]
+        title: Option<String>,
+    },
+
+    /// Post the changes between two baselines or git revisions to the
+    /// channels in .rqm/notify.yml
+    Notify {
+        file: PathBuf,
+
+        /// Baseline (or git revision with --git) to compare from
+        #[arg(long)]
+        from: String,
+
+        /// Baseline (or git revision with --git) to compare to; defaults to
+        /// the working file
+        #[arg(long)]
+        to: Option<String>,
+
+        /// Read `--from` and `--to` as git revisions instead of baselines
+        #[arg(long)]
+        git: bool,
+
+        /// Heading of the notifications; defaults to the `--to` label
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Print the messages without posting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Three-way merge of requirement files; as a git merge driver, run
+    /// `rqm-validator merge %O %A %B --path %P`. Conflicts are left between
+    /// conflict markers and exit with 1
+    Merge {
+        base: PathBuf,
+        ours: PathBuf,
+        theirs: PathBuf,
+
+        /// File to write the merged requirements to; defaults to OURS, as
+        /// git expects
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Path of the file in the repository, git's %P, locating the
+        /// .rqm metadata that matches requirements by UUID; defaults to OURS
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Ask which side wins each conflict
+        #[arg(long)]
+        interactive: bool,
+    },
+
+    /// Freeze and sign off project baselines such as "Release 1.0"
+    #[command(subcommand)]
+    Baseline(BaselineCommand),
+
+    /// Set up git integration
+    #[command(subcommand)]
+    Hook(HookCommand),
+
+    /// Generate a static documentation website
+    #[command(subcommand)]
+    Site(SiteCommand),
+
+    /// Print a Software Requirements Specification to PDF with a headless
+    /// browser
+    Pdf {
+        file: PathBuf,
+
+        /// PDF file to write
+        #[arg(long, default_value = "srs.pdf")]
+        out: PathBuf,
+
+        /// Document title
+        #[arg(long, default_value = "Requirements")]
+        title: String,
+
+        /// Document version shown on the title page
+        #[arg(long, default_value = "1.0")]
+        doc_version: String,
+
+        /// Command printing HTML to PDF, with {input} and {output} standing
+        /// for the file paths; defaults to headless Chromium
+        #[arg(long)]
+        converter: Option<String>,
+
+        /// Confidentiality readers are cleared for; more confidential
+        /// requirements are withheld
+        #[arg(long, value_enum)]
+        clearance: Option<Clearance>,
+
+        /// What is left of withheld requirements
+        #[arg(long, value_enum, default_value = "redact", requires = "clearance")]
+        withhold: Withhold,
+    },
+
+    /// Add a requirement with a generated ID to the file defining its parent
+    New {
+        summary: String,
+
+        /// Summary of the parent requirement; top level if omitted
+        #[arg(long)]
+        parent: Option<String>,
+
+        /// Owner of the requirement; '@me' is the git user, by alias if
+        /// one has their email
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Tag of the requirement; may be repeated
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Requirements files to add to; may be repeated
+        #[arg(long = "file", default_value = "requirements.yml")]
+        files: Vec<PathBuf>,
+    },
+
+    /// Print requirements matching a filter expression, e.g.
+    /// 'status==draft && tag=safety'
+    Query { file: PathBuf, expr: String },
+
+    /// Search the text of requirements, best matches first
+    Search {
+        query: String,
+
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Move deprecated requirements and their metadata to the archive next
+    /// to the file, keeping their UUIDs and history
+    Archive { file: PathBuf },
+
+    /// Count the open requirements of every owner, by priority and status
+    Workload {
+        /// Requirements files, counted as one workspace
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// List requirements up for approval, or past it, that are missing the
+    /// approval of a role in `approval_roles` of .rqm/lint.yml
+    Signoff {
+        /// Requirements files, checked as one workspace
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+
+    /// Synchronize requirements with an issue tracker
+    #[command(subcommand)]
+    Sync(Tracker),
+
+    /// Set up a new project: the `.rqm` metadata directory, a starter
+    /// requirements.yml, lint rules, glossary and editor settings
+    Init {
+        /// Directory of the requirements file
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+
+        /// Prefix of generated requirement IDs
+        #[arg(long, default_value = "REQ")]
+        prefix: String,
+
+        /// Layout of generated IDs, e.g. '{prefix}-{category}-{seq}'
+        #[arg(long, default_value = "{prefix}-{seq}")]
+        id_template: String,
+
+        /// Zero-padded width of the ID sequence number
+        #[arg(long, default_value_t = 3)]
+        id_width: usize,
+
+        /// Number nested requirements after their parent, e.g. REQ-003.2
+        #[arg(long)]
+        hierarchical: bool,
+    },
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    #[arg(required_unless_present = "projects")]
+    files: Vec<PathBuf>,
+
+    /// Validate the projects listed in this manifest, e.g. rqm-projects.yml,
+    /// each with its own settings, and the `project:summary` references
+    /// between them
+    #[arg(long, conflicts_with_all = ["files", "watch"])]
+    projects: Option<PathBuf>,
+
+    /// Lint profile: minimal, standard or strict
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Hide findings recorded in this baseline file
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// Record the current findings in the baseline file instead
+    #[arg(long, requires = "baseline")]
+    update_baseline: bool,
+
+    /// Revalidate whenever a file changes, printing new and fixed findings
+    #[arg(long, conflicts_with_all = ["update_baseline", "sarif"])]
+    watch: bool,
+
+    /// Print SARIF for CI annotations instead of the result
+    #[arg(long)]
+    sarif: bool,
+
+    /// Validate every file again instead of reusing the findings cached in
+    /// .rqm/cache for unchanged files
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Take requirement timestamps from git history, e.g. to find stale
+    /// drafts (needs the `git` feature)
+    #[arg(long, conflicts_with = "watch")]
+    git_history: bool,
+
+    /// Validate the requirements archived from the files as well, instead
+    /// of only letting references name them
+    #[arg(long, conflicts_with = "watch")]
+    include_archived: bool,
+
+    /// Warn about requirements at least this similar to another, 0.9 if no
+    /// value is given; compares every pair of requirements
+    #[arg(
+        long,
+        value_name = "THRESHOLD",
+        num_args = 0..=1,
+        default_missing_value = "0.9",
+        conflicts_with = "watch"
+    )]
+    near_duplicates: Option<f64>,
+
+    /// Check that owners exist on GitHub, authenticating with GITHUB_TOKEN
+    /// if set (needs the `owner-directory` feature)
+    #[arg(long, conflicts_with = "watch")]
+    check_owners: bool,
+
+    /// Check that requirement links are reachable (needs the `link-check`
+    /// feature)
+    #[arg(long, conflicts_with = "watch")]
+    check_links: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GraphFormat {
+    /// Cycles and children of each requirement, in the `--output` format
+    Adjacency,
+    Dot,
+    Mermaid,
+    Graphml,
+    Jgf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    /// The parsed requirements, in the `--output` format
+    Config,
+    Html,
+    Csv,
+    Markdown,
+    /// Numbered outline, as Markdown with `--output text`
+    Outline,
+    Reqif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Clearance {
+    Public,
+    Internal,
+    Restricted,
+}
+
+impl From<Clearance> for Confidentiality {
+    fn from(clearance: Clearance) -> Self {
+        match clearance {
+            Clearance::Public => Confidentiality::Public,
+            Clearance::Internal => Confidentiality::Internal,
+            Clearance::Restricted => Confidentiality::Restricted,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Withhold {
+    /// A placeholder with the requirement's ID and children
+    Redact,
+    /// A placeholder only, dropping children and references to it
+    Drop,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct CycleCheckResult {
-    has_cycles: bool,
-    cycles: Vec<Vec<String>>,
-    graph: HashMap<String, Vec<String>>,
+#[derive(Subcommand)]
+enum HookCommand {
+    /// Install a pre-commit hook validating staged requirement files and
+    /// register the semantic merge driver for them
+    Install {
+        /// Directory in the repository
+        #[arg(default_value = ".")]
+        dir: PathBuf,
+
+        /// Validator run by the hook and merge driver; defaults to this
+        /// executable
+        #[arg(long)]
+        validator: Option<String>,
+
+        /// Requirement file pattern, as in .gitattributes; may be repeated
+        #[arg(long = "pattern", default_values_t = HookOptions::default().patterns)]
+        patterns: Vec<String>,
+
+        /// Replace an existing pre-commit hook
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum BaselineCommand {
+    /// Freeze the requirements and their metadata under a label
+    Create { file: PathBuf, label: String },
+
+    /// Record a sign-off of a baseline
+    Approve {
+        file: PathBuf,
+        label: String,
+
+        /// Person approving: an email, GitHub username or alias
+        #[arg(long)]
+        person: String,
+
+        /// Role the person approves in, e.g. product-owner
+        #[arg(long)]
+        role: String,
+
+        /// Date of the approval as YYYY-MM-DD; defaults to today
+        #[arg(long)]
+        date: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SiteCommand {
+    /// Write one page per requirement, tag, owner and status indexes, a
+    /// graph and a search page, ready for GitHub Pages
+    Build {
+        /// Requirements files, published as one site
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+
+        /// Directory to write the site to
+        #[arg(long, default_value = "_site")]
+        out: PathBuf,
+
+        /// Site name shown on every page
+        #[arg(long, default_value = "Requirements")]
+        title: String,
+
+        /// Locale to publish the requirement text in, e.g. de; text without
+        /// a translation is published as written
+        #[arg(long)]
+        lang: Option<String>,
+
+        /// Confidentiality readers of the site are cleared for; more
+        /// confidential requirements are withheld
+        #[arg(long, value_enum)]
+        clearance: Option<Clearance>,
+
+        /// What is left of withheld requirements
+        #[arg(long, value_enum, default_value = "redact", requires = "clearance")]
+        withhold: Withhold,
+    },
+}
+
+#[derive(Subcommand)]
+enum Tracker {
+    /// Jira issues; the API token is read from JIRA_USER and JIRA_TOKEN
+    Jira {
+        file: PathBuf,
+
+        /// Base URL of the Jira site
+        #[arg(long)]
+        url: String,
+
+        /// Key of the project issues are created in
+        #[arg(long)]
+        project: String,
+
+        /// Custom field holding requirement UUIDs
+        #[arg(long)]
+        uuid_field: String,
+
+        #[command(flatten)]
+        mode: SyncMode,
+    },
+
+    /// GitHub issues; the token is read from GITHUB_TOKEN
+    Github {
+        file: PathBuf,
+
+        /// Repository as owner/name
+        #[arg(long)]
+        repository: String,
+
+        /// Node ID of a project board whose status field follows the
+        /// requirements
+        #[arg(long, requires = "status_field")]
+        project: Option<String>,
+
+        /// Node ID of the project's single-select status field
+        #[arg(long, requires = "project")]
+        status_field: Option<String>,
+
+        /// Field option for a requirement status, as status=option ID;
+        /// repeat for each status
+        #[arg(long = "status-option", value_parser = parse_status_option)]
+        status_options: Vec<(Status, String)>,
+
+        #[command(flatten)]
+        mode: SyncMode,
+    },
+}
+
+#[derive(Args)]
+struct SyncMode {
+    /// Take statuses over from the tracker instead of pushing requirements
+    #[arg(long)]
+    pull: bool,
+
+    /// Report what would change without changing anything
+    #[arg(long)]
+    dry_run: bool,
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
+    let cli = Cli::parse();
+    let output = cli.output;
+    if cli.output_schema {
+        print_out(&format!(
+            "{}\n",
+            serde_json::to_string_pretty(&output::schemas()).unwrap()
+        ));
+        return;
+    }
+    let Some(command) = cli.command else {
+        Cli::command().print_help().unwrap();
+        process::exit(EXIT_ERROR);
+    };
+    let code = match command {
+        Command::Validate(args) => validate(&args, output),
+        command => match run(command, output) {
+            Ok(code) => code,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                EXIT_ERROR
+            }
+        },
+    };
+    process::exit(code);
+}
 
-    if args.len() < 2 {
-        eprintln!(
-            "Usage: {} <requirements.yml> [--format json-full | --check-cycles | --graph]",
-            args[0]
-        );
-        process::exit(1);
+// Helper function to run every command but `validate`, which reports its
+// errors as validation results
+fn run(command: Command, output: Output) -> rqm_core::Result<i32> {
+    match command {
+        Command::Validate(_) => unreachable!("validate reports its own errors"),
+        Command::Cycles { file } => {
+            let result = CycleCheckResult::from_config(&Parser::parse_file(&file)?)?;
+            emit(output, &result, |result| {
+                if !result.has_cycles {
+                    return "No circular references\n".to_string();
+                }
+                result
+                    .cycles
+                    .iter()
+                    .map(|cycle| format!("cycle: {}\n", cycle.join(" -> ")))
+                    .collect()
+            });
+            Ok(if result.has_cycles { EXIT_INVALID } else { 0 })
+        }
+        Command::Graph {
+            file,
+            format,
+            include_archived,
+        } => {
+            let mut config = Parser::parse_file(&file)?;
+            if include_archived {
+                if let Some(archived) = archive::load_archive(&file)? {
+                    config = archive::include_archived(&config, &archived);
+                }
+            }
+            let graph = RequirementGraph::from_config(&config)?;
+            match format {
+                GraphFormat::Adjacency => {
+                    emit(output, &CycleCheckResult::from_config(&config)?, |result| {
+                        result
+                            .graph
+                            .iter()
+                            .map(|(summary, children)| {
+                                format!("{} -> {}\n", summary, children.join(", "))
+                            })
+                            .collect()
+                    });
+                }
+                GraphFormat::Dot => print_out(&graph.to_dot(&DotOptions::default())),
+                GraphFormat::Mermaid => print_out(&graph.to_mermaid()),
+                GraphFormat::Graphml => print_out(&graph.to_graphml()),
+                GraphFormat::Jgf => print_out(&format!(
+                    "{}\n",
+                    serde_json::to_string_pretty(&graph.to_json_graph()).unwrap()
+                )),
+            }
+            Ok(0)
+        }
+        Command::Export {
+            file,
+            format,
+            lang,
+            clearance,
+            withhold,
+        } => {
+            let config = match format {
+                ExportFormat::Config => Parser::parse_file(&file)?,
+                _ => on_its_own(Parser::parse_file(&file)?, &file)?,
+            };
+            let store = find_store(&rqm_dir(&file))?;
+            // Redacted before translating, while summaries still match metadata
+            let config = withheld(config, clearance, withhold, store.as_ref())?;
+            let config = match lang {
+                Some(lang) => locale::translate(&config, &lang, store.as_ref())?,
+                None => config,
+            };
+            match format {
+                ExportFormat::Config => match output {
+                    Output::Json => print_out(&format!(
+                        "{}\n",
+                        serde_json::to_string_pretty(&config).unwrap()
+                    )),
+                    Output::Text | Output::Yaml => print_out(&Parser::to_yaml(&config)?),
+                },
+                ExportFormat::Html => {
+                    let options = ReportOptions {
+                        stale_after_days: Some(
+                            LintConfig::load(rqm_dir(&file))?
+                                .stale_after_days
+                                .unwrap_or(DEFAULT_STALE_DAYS),
+                        ),
+                        glossary: Glossary::load(rqm_dir(&file))?,
+                        ..ReportOptions::default()
+                    };
+                    print_out(&report::to_html(&config, store.as_ref(), &options)?)
+                }
+                ExportFormat::Csv => print_out(&export::to_csv(&config, store.as_ref())?),
+                ExportFormat::Markdown => {
+                    let options = MarkdownOptions {
+                        glossary: Glossary::load(rqm_dir(&file))?,
+                        ..MarkdownOptions::default()
+                    };
+                    print_out(&export::to_markdown(&config, store.as_ref(), &options)?)
+                }
+                ExportFormat::Outline => {
+                    let result = OutlineResult::new(export::outline(&config, store.as_ref())?);
+                    emit(output, &result, |result| {
+                        export::outline_to_markdown(&result.entries, "Requirements Outline")
+                    });
+                }
+                ExportFormat::Reqif => {
+                    print_out(&to_reqif(&config, store.as_ref(), "Requirements")?)
+                }
+            }
+            Ok(0)
+        }
+        Command::Diff { files, git } => {
+            let (old, new) = match (git, files.as_slice()) {
+                (Some(revision), [file]) => (Parser::parse_str(&file_at(file, &revision)?)?, file),
+                (None, [old, new]) => (Parser::parse_file(old)?, new),
+                _ => {
+                    return Err(rqm_core::Error::custom(
+                        "Give the old and new file, or one file and --git <revision>",
+                    ))
+                }
+            };
+            let old_graph = RequirementGraph::try_from(old)?;
+            let new_graph = RequirementGraph::try_from(Parser::parse_file(new)?)?;
+            let diff = match find_store(&rqm_dir(new))? {
+                Some(store) => diff_with_metadata(&old_graph, &new_graph, &store)?,
+                None => diff(&old_graph, &new_graph),
+            };
+            emit(output, &DiffResult::new(diff), |result| {
+                result.diff.to_text()
+            });
+            Ok(0)
+        }
+        Command::Changelog {
+            file,
+            from,
+            to,
+            git,
+            title,
+        } => {
+            let store = find_store(&rqm_dir(&file))?;
+            let (old, new, diff) =
+                changes_between(&file, &from, to.as_deref(), git, store.as_ref())?;
+            let title = title.unwrap_or_else(|| changes_title(&from, to.as_deref()));
+            let markdown = export::to_changelog(&diff, &old, &new, store.as_ref(), &title)?;
+            emit(output, &ChangelogResult::new(title, markdown), |result| {
+                result.markdown.clone()
+            });
+            Ok(0)
+        }
+        Command::Notify {
+            file,
+            from,
+            to,
+            git,
+            title,
+            dry_run,
+        } => {
+            let title = title.unwrap_or_else(|| changes_title(&from, to.as_deref()));
+            let result = notify(&file, &from, to.as_deref(), git, &title, dry_run)?;
+            emit(output, &result, |result| {
+                let verb = if result.dry_run {
+                    "would post"
+                } else {
+                    "posted"
+                };
+                let mut text = String::new();
+                for (channel, messages) in &result.channels {
+                    text.push_str(&format!("{} to {}:\n", verb, channel));
+                    for message in messages {
+                        text.push_str(&format!("  {}\n", message));
+                    }
+                }
+                if text.is_empty() {
+                    text.push_str("Nothing to notify\n");
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Archive { file } => {
+            let mut store = find_store(&rqm_dir(&file))?;
+            let report = archive::archive_deprecated(&file, store.as_mut())?;
+            emit(output, &ArchiveResult::new(report), |result| {
+                let mut text = if result.archived.is_empty() {
+                    "No deprecated requirements to archive\n".to_string()
+                } else {
+                    format!(
+                        "Archived {} requirement(s) to {}\n",
+                        result.archived.len(),
+                        result.path.display()
+                    )
+                };
+                for summary in &result.archived {
+                    text.push_str(&format!("  {}\n", summary));
+                }
+                for summary in &result.skipped {
+                    text.push_str(&format!("  kept {} for its active children\n", summary));
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Signoff { files } => {
+            let graph = RequirementGraph::try_from(Workspace::load(&files)?.merged())?;
+            let roles = LintConfig::load(rqm_dir(&files[0]))?.approval_roles;
+            let missing = graph.missing_signoffs(&roles);
+            let result = SignoffResult::new(roles, missing);
+            emit(output, &result, |result| {
+                if result.requirements.is_empty() {
+                    return "Every requirement is signed off\n".to_string();
+                }
+                let mut text = String::new();
+                for req in &result.requirements {
+                    text.push_str(&format!(
+                        "{} ({}): needs {}\n",
+                        req.summary,
+                        req.status,
+                        req.missing_roles.join(", ")
+                    ));
+                    if !req.outdated.is_empty() {
+                        text.push_str(&format!(
+                            "  signed for an earlier version: {}\n",
+                            req.outdated.join(", ")
+                        ));
+                    }
+                    text.push_str(&format!("  signature: {}\n", req.signature));
+                }
+                text
+            });
+            Ok(if result.requirements.is_empty() {
+                0
+            } else {
+                EXIT_INVALID
+            })
+        }
+        Command::Workload { files } => {
+            let graph = RequirementGraph::try_from(Workspace::load(&files)?.merged())?;
+            emit(output, &WorkloadResult::new(graph.workload()), |result| {
+                if result.owners.is_empty() {
+                    return "No open requirements\n".to_string();
+                }
+                let mut text = String::new();
+                for owner in &result.owners {
+                    let contact = [&owner.name, &owner.email, &owner.github]
+                        .into_iter()
+                        .flatten()
+                        .map(String::as_str)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    text.push_str(&owner.owner);
+                    if !contact.is_empty() && contact != owner.owner {
+                        text.push_str(&format!(" ({})", contact));
+                    }
+                    text.push_str(&format!(": {} open\n", owner.open));
+                    let counts = |counts: &std::collections::BTreeMap<String, usize>| {
+                        counts
+                            .iter()
+                            .map(|(label, count)| format!("{} {}", count, label))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    };
+                    text.push_str(&format!("  priority: {}\n", counts(&owner.by_priority)));
+                    text.push_str(&format!("  status: {}\n", counts(&owner.by_status)));
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Baseline(command) => {
+            let file = match &command {
+                BaselineCommand::Create { file, .. } | BaselineCommand::Approve { file, .. } => {
+                    file
+                }
+            };
+            let rqm_dir = rqm_dir(file);
+            let mut store = find_store(&rqm_dir)?.ok_or_else(|| {
+                rqm_core::Error::custom(
+                    "Baselines need a .rqm directory; run `rqm-validator init` first",
+                )
+            })?;
+            let snapshot = match command {
+                BaselineCommand::Create { file, label } => {
+                    store.create_baseline(&label, &Parser::parse_file(&file)?)?
+                }
+                BaselineCommand::Approve {
+                    label,
+                    person,
+                    role,
+                    date,
+                    ..
+                } => {
+                    let date =
+                        date.unwrap_or_else(|| chrono::Local::now().format("%Y-%m-%d").to_string());
+                    store.approve_baseline(&label, Approval::new(person, role, date))?
+                }
+            };
+            emit(output, &BaselineResult::new(&snapshot), |result| {
+                let mut text = format!(
+                    "Baseline '{}' of {} requirement(s), {}\n",
+                    result.label, result.requirements, result.hash
+                );
+                for approval in &result.approvals {
+                    text.push_str(&format!(
+                        "  approved by {} as {} on {}\n",
+                        approval.person, approval.role, approval.date
+                    ));
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Hook(HookCommand::Install {
+            dir,
+            validator,
+            patterns,
+            force,
+        }) => {
+            let validator = match validator {
+                Some(validator) => validator,
+                None => std::env::current_exe()?.display().to_string(),
+            };
+            let options = HookOptions {
+                validator,
+                patterns,
+                force,
+            };
+            let installed = hooks::install(&dir, &options)?;
+            let result = HookInstallResult::new(installed.work_dir, installed.changed);
+            emit(output, &result, |result| {
+                if result.changed.is_empty() {
+                    return format!("Already set up in {}\n", result.work_dir.display());
+                }
+                let mut text = format!("Set up git integration in {}\n", result.work_dir.display());
+                for path in &result.changed {
+                    text.push_str(&format!("  updated {}\n", path.display()));
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Pdf {
+            file,
+            out,
+            title,
+            doc_version,
+            converter,
+            clearance,
+            withhold,
+        } => {
+            let rqm_dir = rqm_dir(&file);
+            let store = find_store(&rqm_dir)?;
+            let config = withheld(
+                on_its_own(Parser::parse_file(&file)?, &file)?,
+                clearance,
+                withhold,
+                store.as_ref(),
+            )?;
+            write_pdf(
+                &config,
+                store.as_ref(),
+                title,
+                doc_version,
+                converter,
+                rqm_dir.parent(),
+                &out,
+            )?;
+            emit(output, &PdfResult::new(&out), |result| {
+                format!("Wrote {}\n", result.path.display())
+            });
+            Ok(0)
+        }
+        Command::Site(SiteCommand::Build {
+            files,
+            out,
+            title,
+            lang,
+            clearance,
+            withhold,
+        }) => {
+            let config = on_its_own(Workspace::load(&files)?.merged(), &files[0])?;
+            let rqm_dir = rqm_dir(&files[0]);
+            let store = find_store(&rqm_dir)?;
+            // Redacted before translating, while summaries still match metadata
+            let config = withheld(config, clearance, withhold, store.as_ref())?;
+            let config = match lang {
+                Some(lang) => locale::translate(&config, &lang, store.as_ref())?,
+                None => config,
+            };
+            let options = SiteOptions {
+                title,
+                attachment_root: rqm_dir.parent().map(Path::to_path_buf),
+            };
+            let site = export::build_site(&config, store.as_ref(), &options)?;
+            let result = SiteResult::new(&out, site.write(&out)?);
+            emit(output, &result, |result| {
+                format!(
+                    "Wrote {} files to {}\n",
+                    result.files.len(),
+                    result.out_dir.display()
+                )
+            });
+            Ok(0)
+        }
+        Command::Merge {
+            base,
+            ours,
+            theirs,
+            out,
+            path,
+            interactive,
+        } => {
+            let store = find_store(&rqm_dir(path.as_deref().unwrap_or(&ours)))?;
+            let merged = merge::merge_files(
+                &fs::read_to_string(&base)?,
+                &fs::read_to_string(&ours)?,
+                &fs::read_to_string(&theirs)?,
+                store.as_ref(),
+                |conflict| {
+                    if !interactive {
+                        return None;
+                    }
+                    eprintln!("{}", describe_conflict(conflict));
+                    eprint!("Keep [o]urs, [t]heirs or [b]ase, or leave it [u]nresolved? ");
+                    let mut answer = String::new();
+                    io::stdin().read_line(&mut answer).ok()?;
+                    match answer.trim() {
+                        "o" => Some(Side::Ours),
+                        "t" => Some(Side::Theirs),
+                        "b" => Some(Side::Base),
+                        _ => None,
+                    }
+                },
+            )?;
+
+            // Git reads the result from OURS, which must never be left half written
+            let out = out.unwrap_or(ours);
+            write_atomic(&out, merged.text.as_bytes())?;
+            let clean = merged.merge.is_clean();
+            emit(
+                output,
+                &MergeResult::new(&out, merged.merge.conflicts),
+                |result| {
+                    let mut text = format!("Merged into {}\n", result.path.display());
+                    for conflict in &result.conflicts {
+                        text.push_str(&format!("conflict: {}\n", describe_conflict(conflict)));
+                    }
+                    text
+                },
+            );
+            Ok(if clean { 0 } else { EXIT_INVALID })
+        }
+        Command::New {
+            summary,
+            parent,
+            owner,
+            tags,
+            files,
+        } => {
+            let rqm_dir = rqm_dir(&files[0]);
+            let mut store = find_store(&rqm_dir)?.ok_or_else(|| {
+                rqm_core::Error::custom(format!(
+                    "No metadata directory at {}; run `rqm-validator init` first",
+                    rqm_dir.display()
+                ))
+            })?;
+            let mut requirement = rqm_core::Requirement::new(summary);
+            requirement.owner = match owner {
+                Some(owner) if owner == "@me" => {
+                    Some(OwnerReference::String(current_user(&files)?))
+                }
+                Some(owner) => Some(OwnerReference::String(owner)),
+                None => None,
+            };
+            requirement.tags = tags;
+            let summary = requirement.summary.clone();
+
+            let validator = create_validator(&rqm_dir, None, None)?;
+            let added =
+                store.add_requirement(&files, requirement, parent.as_deref(), &validator)?;
+            let result = NewRequirementResult {
+                warnings: added.report.warnings.iter().map(Finding::message).collect(),
+                ..NewRequirementResult::new(summary, added.id, added.path, added.line)
+            };
+            emit(output, &result, |result| {
+                let mut text = format!(
+                    "Added {} '{}' at {}:{}\n",
+                    result.id,
+                    result.summary,
+                    result.path.display(),
+                    result.line
+                );
+                for warning in &result.warnings {
+                    text.push_str(&format!("warning: {}\n", warning));
+                }
+                text
+            });
+            Ok(0)
+        }
+        Command::Query { file, expr } => {
+            let expr = Expr::parse(&expr)?;
+            // Only read, so parsed without copying the text of requirements
+            // that do not match
+            let content = fs::read_to_string(&file)?;
+            let config = Parser::parse_borrowed(&content)?;
+            let matches = config.query(&expr);
+            let result = QueryResult::new(
+                matches
+                    .iter()
+                    .map(|&req| req.clone().into_owned())
+                    .collect(),
+            );
+            emit(output, &result, |_| query::to_table(&matches));
+            Ok(0)
+        }
+        Command::Search { query, files } => {
+            let index = SearchIndex::from_workspace(&Workspace::load(&files)?);
+            emit(output, &SearchResult::new(index.search(&query)), |result| {
+                result.hits.iter().map(describe_hit).collect()
+            });
+            Ok(0)
+        }
+        Command::Sync(tracker) => {
+            let result = sync(tracker)?;
+            emit(output, &result, |result| {
+                let mut text = String::new();
+                let verb = if result.dry_run { "would be " } else { "" };
+                for summary in &result.created {
+                    text.push_str(&format!("{}created: {}\n", verb, summary));
+                }
+                for issue in &result.updated {
+                    text.push_str(&format!("{}updated: {}\n", verb, issue));
+                }
+                for summary in &result.project_updates {
+                    text.push_str(&format!("{}moved on the project: {}\n", verb, summary));
+                }
+                for change in &result.status_changes {
+                    text.push_str(&format!("{}changed: {}\n", verb, change));
+                }
+                for conflict in &result.conflicts {
+                    text.push_str(&format!("conflict: {}\n", conflict));
+                }
+                text
+            });
+            Ok(if result.conflicts.is_empty() {
+                0
+            } else {
+                EXIT_INVALID
+            })
+        }
+        Command::Init {
+            dir,
+            prefix,
+            id_template,
+            id_width,
+            hierarchical,
+        } => {
+            let options = ScaffoldOptions {
+                prefix,
+                ids: IdScheme {
+                    template: id_template,
+                    width: id_width,
+                    hierarchical,
+                    ..IdScheme::default()
+                },
+            };
+            let scaffold = scaffold::scaffold(&dir, &options)?;
+            let result = InitResult {
+                created: scaffold.created,
+                skipped: scaffold.skipped,
+                ..InitResult::new(dir.join(".rqm"), &options.prefix)
+            };
+            emit(output, &result, |result| {
+                let mut text = format!(
+                    "Initialized {} with prefix {}\n",
+                    result.rqm_dir.display(),
+                    result.prefix
+                );
+                for path in &result.created {
+                    text.push_str(&format!("  created {}\n", path.display()));
+                }
+                for path in &result.skipped {
+                    text.push_str(&format!("  kept existing {}\n", path.display()));
+                }
+                text
+            });
+            Ok(0)
+        }
     }
+}
 
-    let file_path = &args[1];
+// Helper function to print a result in the requested format
+fn emit<T: Serialize + ?Sized>(output: Output, value: &T, text: impl FnOnce(&T) -> String) {
+    match output {
+        Output::Json => print_out(&format!(
+            "{}\n",
+            serde_json::to_string_pretty(value).unwrap()
+        )),
+        Output::Yaml => print_out(&serde_yaml::to_string(value).unwrap()),
+        Output::Text => print_out(&text(value)),
+    }
+}
 
-    // Check for flags
-    let output_full = args.len() > 3 && args[2] == "--format" && args[3] == "json-full";
-    let check_cycles = args.len() > 2 && args[2] == "--check-cycles";
-    let output_graph = args.len() > 2 && args[2] == "--graph";
+// Helper function to write to stdout; a reader that stops early, as
+// `head` does, is not an error
+fn print_out(text: &str) {
+    let mut stdout = io::stdout().lock();
+    if let Err(e) = stdout
+        .write_all(text.as_bytes())
+        .and_then(|()| stdout.flush())
+    {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            eprintln!("Error writing output: {}", e);
+            process::exit(EXIT_ERROR);
+        }
+    }
+}
+
+// Helper function to find the `.rqm` directory next to a requirements file
+fn rqm_dir(file: &Path) -> PathBuf {
+    file.parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+        .join(".rqm")
+}
 
-    // Parse the file
-    let config = match Parser::parse_file(file_path) {
+// Helper function to drop the references of a monorepo project into the
+// other projects, which a document of the project alone cannot link to
+fn on_its_own(config: RequirementConfig, file: &Path) -> rqm_core::Result<RequirementConfig> {
+    let root = rqm_dir(file);
+    match ProjectScope::find(root.parent().unwrap_or_else(|| Path::new(".")))? {
+        Some(scope) => Ok(scope.localize(config)),
+        None => Ok(config),
+    }
+}
+
+// Helper function to open the metadata store, if the project has one
+fn find_store(rqm_dir: &Path) -> rqm_core::Result<Option<MetadataStore>> {
+    if rqm_dir.join("config.yml").exists() {
+        MetadataStore::new(rqm_dir).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+// Helper function to validate one file, or several as one workspace
+fn validate(args: &ValidateArgs, output: Output) -> i32 {
+    let profile = args.profile.as_deref();
+    let baseline = args.baseline.as_deref();
+    if args.watch {
+        watch(&args.files, profile, baseline);
+    }
+    if args.files.len() > 1 || args.projects.is_some() {
+        return validate_workspace(args, output);
+    }
+
+    let file_path = &args.files[0];
+    let source = fs::read_to_string(file_path).unwrap_or_default();
+    let mut config = match Parser::parse_file(file_path) {
         Ok(cfg) => cfg,
         Err(e) => {
-            let result = ValidationResult {
-                valid: false,
-                errors: vec![format!("Parse error: {}", e)],
-                warnings: vec![],
+            // Values that don't fit the data model are better explained by the schema
+            let schema_errors = serde_yaml::from_str::<serde_json::Value>(&source)
+                .ok()
+                .zip(Validator::new().ok())
+                .map(|(document, validator)| validator.check_schema(&document))
+                .unwrap_or_default();
+            let mut report = ValidationReport {
+                errors: schema_errors,
+                ..ValidationReport::default()
             };
-            println!("{}", serde_json::to_string_pretty(&result).unwrap());
-            process::exit(1);
+            let result = if report.errors.is_empty() {
+                ValidationResult::error(format!("Parse error: {}", e))
+            } else {
+                ValidationResult::from_report(&report, &source)
+            };
+            if args.sarif {
+                if report.errors.is_empty() {
+                    report.errors.push(Finding {
+                        rule: SCHEMA_RULE.to_string(),
+                        severity: Severity::Error,
+                        error: e,
+                        requirement: None,
+                    });
+                }
+                print_sarif(&report, file_path, &source);
+                return EXIT_INVALID;
+            }
+            emit(output, &result, describe_result);
+            return EXIT_INVALID;
         }
     };
 
-    // If --format json-full, output the parsed config and exit
-    if output_full {
-        println!("{}", serde_json::to_string_pretty(&config).unwrap());
-        return;
+    if args.git_history {
+        if let Err(e) = git_history(&mut config, file_path) {
+            fail(output, format!("Git history error: {}", e));
+        }
+    }
+    let archived = match archive::load_archive(file_path) {
+        Ok(archived) => archived,
+        Err(e) => fail(output, format!("Archive error: {}", e)),
+    };
+    if args.include_archived {
+        if let Some(archived) = &archived {
+            config = archive::include_archived(&config, archived);
+        }
     }
 
-    // If --check-cycles or --graph, build graph and check for cycles
-    if check_cycles || output_graph {
-        let graph = match RequirementGraph::from_config(&config) {
-            Ok(g) => g,
-            Err(e) => {
-                let result = CycleCheckResult {
-                    has_cycles: false,
-                    cycles: vec![],
-                    graph: HashMap::new(),
-                };
-                println!("{}", serde_json::to_string_pretty(&result).unwrap());
-                eprintln!("Error building graph: {}", e);
-                process::exit(1);
-            }
-        };
-
-        let cycles = graph.find_cycles();
-        let has_cycles = !cycles.is_empty();
+    // Create validator, honoring a project lint config next to the file;
+    // the baseline is applied once the analyses below added their findings
+    let validator = match create_validator(&rqm_dir(file_path), profile, None) {
+        Ok(v) => v,
+        Err(e) => fail(output, format!("Validator initialization error: {}", e)),
+    };
+    let mut analysis_options = AnalysisOptions {
+        similarity_threshold: args.near_duplicates,
+        ..AnalysisOptions::default()
+    };
+    let validator = match archived.filter(|_| !args.include_archived) {
+        Some(archived) => {
+            analysis_options.archived = archived
+                .all_requirements()
+                .into_iter()
+                .map(|req| req.summary.clone())
+                .collect();
+            validator.with_archive(&archived)
+        }
+        None => validator,
+    };
 
-        // Build adjacency map for graph output
-        let mut adj_map: HashMap<String, Vec<String>> = HashMap::new();
-        for req in &config.requirements {
-            collect_graph_edges(req, &mut adj_map);
+    // Validate, reporting every finding at once
+    // Timestamps from git history and archived requirements are not part of
+    // the cached file content
+    let no_cache = args.no_cache || args.git_history || args.include_archived;
+    let mut report = match open_cache(&rqm_dir(file_path), &validator, no_cache) {
+        Some(cache) => validator.validate_cached(&config, &source, &cache),
+        None => validator.validate(&config),
+    };
+    report.warnings.extend(
+        analysis::analyze(&config, &analysis_options)
+            .iter()
+            // Already reported by the child-reference rule
+            .filter(|finding| finding.kind != FindingKind::DanglingReference)
+            .map(AnalysisFinding::to_finding),
+    );
+    match online_checks(args, &config, &rqm_dir(file_path)) {
+        Ok(online) => report.append(online),
+        Err(e) => fail(output, format!("Online check error: {}", e)),
+    }
+    if let Some(path) = baseline {
+        if args.update_baseline {
+            return record_baseline(&report, path);
         }
+        if let Err(e) = apply_baseline(&mut report, path) {
+            fail(output, format!("Baseline error: {}", e));
+        }
+    }
+    if args.sarif {
+        print_sarif(&report, file_path, &source);
+        return if report.is_valid() { 0 } else { EXIT_INVALID };
+    }
+    let result = ValidationResult::from_report(&report, &source);
+    emit(output, &result, describe_result);
+    if result.valid {
+        0
+    } else {
+        EXIT_INVALID
+    }
+}
 
-        let result = CycleCheckResult {
-            has_cycles,
-            cycles,
-            graph: adj_map,
+// Helper function to validate several files as one workspace
+fn validate_workspace(args: &ValidateArgs, output: Output) -> i32 {
+    let profile = args.profile.as_deref();
+    let baseline = args.baseline.as_deref().filter(|_| !args.update_baseline);
+    let parse_error = |e: rqm_core::Error| {
+        let result = ValidationResult::error(format!("Parse error: {}", e));
+        emit(output, &result, describe_result);
+        EXIT_INVALID
+    };
+
+    // Each project of a monorepo is validated with its own settings
+    let (mut report, mut files) = if let Some(manifest) = &args.projects {
+        let mut repo = match Monorepo::load(manifest) {
+            Ok(repo) => repo,
+            Err(e) => return parse_error(e),
+        };
+        let mut archives = vec![];
+        for project in &mut repo.projects {
+            archives.push(prepare_files(&mut project.workspace.files, args, output));
+        }
+        let mut archives = archives.into_iter();
+        let report = repo.validate(|_, validator| {
+            let validator = configure_validator(validator, profile, baseline)?;
+            Ok(match archives.next().flatten() {
+                Some(archived) => validator.with_archive(&archived),
+                None => validator,
+            })
+        });
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => fail(output, format!("Validator initialization error: {}", e)),
+        };
+        let files: Vec<WorkspaceFile> = repo
+            .projects
+            .into_iter()
+            .flat_map(|project| project.workspace.files)
+            .collect();
+        (report, files)
+    } else {
+        let mut workspace = match Workspace::load(&args.files) {
+            Ok(workspace) => workspace,
+            Err(e) => return parse_error(e),
+        };
+        let archived = prepare_files(&mut workspace.files, args, output);
+        let rqm_dir = rqm_dir(&args.files[0]);
+        let validator = match create_validator(&rqm_dir, profile, baseline) {
+            Ok(v) => v,
+            Err(e) => fail(output, format!("Validator initialization error: {}", e)),
+        };
+        let validator = match archived {
+            Some(archived) => validator.with_archive(&archived),
+            None => validator,
         };
 
-        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        let no_cache = args.no_cache || args.git_history || args.include_archived;
+        let report = match open_cache(&rqm_dir, &validator, no_cache) {
+            Some(cache) => workspace.validate_cached(&validator, &cache),
+            None => workspace.validate(&validator),
+        };
+        (report, workspace.files)
+    };
+    if args.check_owners || args.check_links || args.near_duplicates.is_some() {
+        let workspace = Workspace { files };
+        let merged = workspace.merged();
+        let options = AnalysisOptions {
+            similarity_threshold: args.near_duplicates,
+            ..AnalysisOptions::default()
+        };
+        report.unattributed.warnings.extend(
+            analysis::analyze(&merged, &options)
+                .iter()
+                .filter(|finding| finding.kind == FindingKind::NearDuplicate)
+                .map(AnalysisFinding::to_finding),
+        );
+        let rqm_dir = rqm_dir(args.projects.as_ref().unwrap_or(&args.files[0]));
+        match online_checks(args, &merged, &rqm_dir) {
+            Ok(online) => report.unattributed.append(online),
+            Err(e) => fail(output, format!("Online check error: {}", e)),
+        }
+        files = workspace.files;
+    }
+    if let Some(path) = args.baseline.as_deref() {
+        if args.update_baseline {
+            return record_baseline(&report.into_report(), path);
+        }
+        // The validators already hid the known findings of each file
+        if let Err(e) = apply_baseline(&mut report.unattributed, path) {
+            fail(output, format!("Baseline error: {}", e));
+        }
+    }
+    if args.sarif {
+        let uris: Vec<String> = files.iter().map(|file| sarif_uri(&file.path)).collect();
+        let artifacts: Vec<(&str, &str)> = uris
+            .iter()
+            .zip(&files)
+            .map(|(uri, file)| (uri.as_str(), file.source.as_str()))
+            .collect();
+        print_out(&format!(
+            "{}\n",
+            serde_json::to_string_pretty(&report.to_sarif(&artifacts)).unwrap()
+        ));
+        return if report.is_valid() { 0 } else { EXIT_INVALID };
+    }
+    let result = ValidationResult::from_workspace(&report, &files);
+    emit(output, &result, describe_result);
+    if result.valid {
+        0
+    } else {
+        EXIT_INVALID
+    }
+}
 
-        if has_cycles && check_cycles {
-            process::exit(1);
+// Helper function to take git history and archives into workspace files,
+// returning the requirements archived from them unless they were included
+fn prepare_files(
+    files: &mut [WorkspaceFile],
+    args: &ValidateArgs,
+    output: Output,
+) -> Option<RequirementConfig> {
+    if args.git_history {
+        for file in files.iter_mut() {
+            if let Err(e) = git_history(&mut file.config, &file.path) {
+                fail(output, format!("Git history error: {}", e));
+            }
+        }
+    }
+    let mut archived: Option<RequirementConfig> = None;
+    for file in files {
+        match archive::load_archive(&file.path) {
+            Ok(Some(config)) if args.include_archived => {
+                file.config = archive::include_archived(&file.config, &config);
+            }
+            Ok(Some(config)) => {
+                archived = Some(match archived {
+                    Some(all) => archive::include_archived(&all, &config),
+                    None => config,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => fail(output, format!("Archive error: {}", e)),
         }
-        return;
     }
+    archived
+}
 
-    // Create validator
-    let validator = match Validator::new() {
+// Helper function to revalidate files whenever they change, printing what
+// was found or fixed since the previous run
+#[cfg(feature = "watch")]
+fn watch(paths: &[PathBuf], profile: Option<&str>, baseline: Option<&Path>) -> ! {
+    let mut validator = match create_validator(&rqm_dir(&paths[0]), profile, baseline) {
         Ok(v) => v,
-        Err(e) => {
-            let result = ValidationResult {
-                valid: false,
-                errors: vec![format!("Validator initialization error: {}", e)],
-                warnings: vec![],
-            };
-            println!("{}", serde_json::to_string_pretty(&result).unwrap());
-            process::exit(1);
-        }
+        Err(e) => fail(
+            Output::Json,
+            format!("Validator initialization error: {}", e),
+        ),
     };
 
-    // Validate
-    let result = match validator.validate(&config) {
-        Ok(_) => ValidationResult {
-            valid: true,
-            errors: vec![],
-            warnings: vec![],
-        },
-        Err(e) => ValidationResult {
-            valid: false,
-            errors: vec![format!("{}", e)],
-            warnings: vec![],
-        },
+    let mut session = WatchSession::new(paths);
+    eprintln!("Watching {} file(s); press Ctrl+C to stop", paths.len());
+    let watched = session.run(&mut validator, |delta| {
+        println!(
+            "[{}] {} new, {} fixed, {} total ({} ms)",
+            chrono::Local::now().format("%H:%M:%S"),
+            delta.new.len(),
+            delta.fixed.len(),
+            delta.total,
+            delta.elapsed.as_millis()
+        );
+        for finding in &delta.new {
+            println!("  + {}", finding);
+        }
+        for finding in &delta.fixed {
+            println!("  - {}", finding);
+        }
+        true
+    });
+    if let Err(e) = watched {
+        fail(Output::Json, e.to_string());
+    }
+    process::exit(0);
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch(_paths: &[PathBuf], _profile: Option<&str>, _baseline: Option<&Path>) -> ! {
+    fail(
+        Output::Json,
+        "--watch needs rqm-validator built with the `watch` feature".to_string(),
+    )
+}
+
+// Helper function to create a validator from the project's `.rqm` directory
+fn create_validator(
+    rqm_dir: &Path,
+    profile: Option<&str>,
+    baseline: Option<&Path>,
+) -> rqm_core::Result<Validator> {
+    configure_validator(Validator::for_project(rqm_dir)?, profile, baseline)
+}
+
+// Helper function to apply the profile and baseline given on the command line
+fn configure_validator(
+    mut v: Validator,
+    profile: Option<&str>,
+    baseline: Option<&Path>,
+) -> rqm_core::Result<Validator> {
+    if let Some(profile) = profile {
+        v = v.with_profile(profile.parse()?);
+    }
+    if let Some(baseline) = baseline {
+        v = v.with_baseline(baseline)?;
+    }
+    Ok(v)
+}
+
+// Helper function to open the validation cache, if the project has an
+// .rqm directory and caching is enabled
+fn open_cache(rqm_dir: &Path, validator: &Validator, no_cache: bool) -> Option<ValidationCache> {
+    if no_cache || !rqm_dir.is_dir() {
+        return None;
+    }
+    ValidationCache::open(rqm_dir, validator).ok()
+}
+
+// Helper function to report a fatal error as an invalid result
+fn fail(output: Output, error: String) -> ! {
+    emit(output, &ValidationResult::error(error), describe_result);
+    process::exit(EXIT_ERROR);
+}
+
+// Helper function to render a validation result as text
+fn describe_result(result: &ValidationResult) -> String {
+    let mut text = String::new();
+    for (severity, messages) in [
+        ("error", &result.errors),
+        ("warning", &result.warnings),
+        ("info", &result.infos),
+    ] {
+        for message in messages {
+            text.push_str(&format!("{}: {}\n", severity, message));
+        }
+    }
+    if result.suppressed > 0 {
+        text.push_str(&format!(
+            "{} known findings hidden by the baseline\n",
+            result.suppressed
+        ));
+    }
+    text.push_str(if result.valid { "valid\n" } else { "invalid\n" });
+    text
+}
+
+// Helper function to render a search hit as text
+fn describe_hit(hit: &SearchHit) -> String {
+    let location = match (&hit.path, hit.line) {
+        (Some(path), Some(line)) => format!("{}:{}: ", path.display(), line),
+        (Some(path), None) => format!("{}: ", path.display()),
+        _ => String::new(),
     };
+    format!(
+        "{}{} ({:.2})\n    {}\n",
+        location, hit.summary, hit.score, hit.snippet
+    )
+}
+
+// Helper function to record a report's findings as the new baseline
+fn write_baseline(report: &ValidationReport, path: &Path) -> rqm_core::Result<usize> {
+    let rqm_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let store = MetadataStore::open(rqm_dir)?;
+    let baseline = Baseline::from_report(report, store.as_ref())?;
+    baseline.save(path)?;
+    Ok(baseline.entries.len())
+}
 
-    // Output JSON result
-    println!("{}", serde_json::to_string_pretty(&result).unwrap());
+// Helper function to hide the findings known to a baseline file
+fn apply_baseline(report: &mut ValidationReport, path: &Path) -> rqm_core::Result<()> {
+    let rqm_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let store = MetadataStore::open(rqm_dir)?;
+    report.suppressed += Baseline::load(path)?.apply(report, store.as_ref())?;
+    Ok(())
+}
 
-    if !result.valid {
-        process::exit(1);
+// Helper function to record a report's findings as the new baseline,
+// returning the exit code
+fn record_baseline(report: &ValidationReport, path: &Path) -> i32 {
+    match write_baseline(report, path) {
+        Ok(entries) => {
+            eprintln!(
+                "Recorded {} baseline entries in {}",
+                entries,
+                path.display()
+            );
+            0
+        }
+        Err(e) => {
+            eprintln!("Error writing baseline: {}", e);
+            EXIT_ERROR
+        }
     }
 }
 
-// Helper function to collect graph edges from requirements
-fn collect_graph_edges(req: &rqm_core::Requirement, adj_map: &mut HashMap<String, Vec<String>>) {
-    let mut deps = Vec::new();
+// Helper function to print a report as SARIF for CI annotations
+fn print_sarif(report: &ValidationReport, file_path: &Path, source: &str) {
+    print_out(&format!(
+        "{}\n",
+        serde_json::to_string_pretty(&report.to_sarif(&sarif_uri(file_path), source)).unwrap()
+    ));
+}
 
-    for child in &req.requirements {
-        match child {
-            RequirementReference::Full(full_req) => {
-                deps.push(full_req.summary.clone());
-                collect_graph_edges(full_req, adj_map);
-            }
-            RequirementReference::Reference(summary) => {
-                deps.push(summary.clone());
+// Helper function to name a file as SARIF consumers expect: with forward
+// slashes, relative to the repository root
+fn sarif_uri(file_path: &Path) -> String {
+    file_path
+        .to_string_lossy()
+        .trim_start_matches("./")
+        .replace('\\', "/")
+}
+
+// Helper function to compute the old and new requirements and their
+// differences, between baselines or git revisions
+fn changes_between(
+    file: &Path,
+    from: &str,
+    to: Option<&str>,
+    git: bool,
+    store: Option<&MetadataStore>,
+) -> rqm_core::Result<(RequirementConfig, RequirementConfig, GraphDiff)> {
+    if git {
+        let old = Parser::parse_str(&file_at(file, from)?)?;
+        let new = match to {
+            Some(revision) => Parser::parse_str(&file_at(file, revision)?)?,
+            None => Parser::parse_file(file)?,
+        };
+        let old_graph = RequirementGraph::from_config(&old)?;
+        let new_graph = RequirementGraph::from_config(&new)?;
+        let diff = match store {
+            Some(store) => diff_with_metadata(&old_graph, &new_graph, store)?,
+            None => diff(&old_graph, &new_graph),
+        };
+        Ok((old, new, diff))
+    } else {
+        let store = store.ok_or_else(|| {
+            rqm_core::Error::custom(
+                "Baselines need a .rqm directory; run `rqm-validator init` first",
+            )
+        })?;
+        let old = store.baseline(from)?.requirements;
+        let (new, diff) = match to {
+            Some(label) => (
+                store.baseline(label)?.requirements,
+                store.diff_baselines(from, label)?,
+            ),
+            None => {
+                let new = Parser::parse_file(file)?;
+                let diff = store.diff_baseline(from, &new)?;
+                (new, diff)
             }
+        };
+        Ok((old, new, diff))
+    }
+}
+
+// Helper function to title the changes between two baselines or revisions
+fn changes_title(from: &str, to: Option<&str>) -> String {
+    match to {
+        Some(to) => format!("Changes from {} to {}", from, to),
+        None => format!("Changes since {}", from),
+    }
+}
+
+// Helper function to post the changes to the configured channels
+#[cfg(feature = "notify")]
+fn notify(
+    file: &Path,
+    from: &str,
+    to: Option<&str>,
+    git: bool,
+    title: &str,
+    dry_run: bool,
+) -> rqm_core::Result<NotifyResult> {
+    use rqm_core::notify::{self, CurlTransport, NotifyConfig};
+
+    let rqm_dir = rqm_dir(file);
+    let config = NotifyConfig::load(&rqm_dir)?.ok_or_else(|| {
+        rqm_core::Error::custom(format!(
+            "No notification channels; add them to {}",
+            rqm_dir.join("notify.yml").display()
+        ))
+    })?;
+    let store = find_store(&rqm_dir)?;
+    let (old, new, diff) = changes_between(file, from, to, git, store.as_ref())?;
+    let changes = notify::changes(&diff, &old, &new, store.as_ref())?;
+    let transport = CurlTransport;
+    let channels = notify::notify(
+        &config,
+        &changes,
+        title,
+        (!dry_run).then_some(&transport as &dyn notify::WebhookTransport),
+    )?;
+    Ok(NotifyResult::new(dry_run, channels))
+}
+
+#[cfg(not(feature = "notify"))]
+fn notify(
+    _file: &Path,
+    _from: &str,
+    _to: Option<&str>,
+    _git: bool,
+    _title: &str,
+    _dry_run: bool,
+) -> rqm_core::Result<NotifyResult> {
+    Err(rqm_core::Error::custom(
+        "rqm-validator was built without the `notify` feature",
+    ))
+}
+
+// Helper function to run the owner and link checks asked for, which look
+// things up online
+fn online_checks(
+    args: &ValidateArgs,
+    config: &RequirementConfig,
+    rqm_dir: &Path,
+) -> rqm_core::Result<ValidationReport> {
+    let mut report = ValidationReport::default();
+    if args.check_owners {
+        report.append(check_owners(config, rqm_dir)?);
+    }
+    if args.check_links {
+        report.append(check_links(config, rqm_dir)?);
+    }
+    Ok(report)
+}
+
+// Helper function to check owners on GitHub, caching the results in the
+// .rqm directory when there is one
+#[cfg(feature = "owner-directory")]
+fn check_owners(config: &RequirementConfig, rqm_dir: &Path) -> rqm_core::Result<ValidationReport> {
+    use rqm_core::validator::{GitHubDirectory, OwnerCache, OwnerChecker};
+    let mut checker = OwnerChecker::new()
+        .with_directory(GitHubDirectory::new())
+        .with_cache(OwnerCache::load(rqm_dir)?);
+    let report = checker.check(config);
+    if rqm_dir.is_dir() {
+        checker.cache().save(rqm_dir)?;
+    }
+    Ok(report)
+}
+
+#[cfg(not(feature = "owner-directory"))]
+fn check_owners(
+    _config: &RequirementConfig,
+    _rqm_dir: &Path,
+) -> rqm_core::Result<ValidationReport> {
+    Err(rqm_core::Error::custom(
+        "--check-owners needs rqm-validator built with the `owner-directory` feature",
+    ))
+}
+
+// Helper function to check that links are reachable, caching the results in
+// the .rqm directory when there is one
+#[cfg(feature = "link-check")]
+fn check_links(config: &RequirementConfig, rqm_dir: &Path) -> rqm_core::Result<ValidationReport> {
+    use rqm_core::validator::{LinkCache, LinkChecker};
+    let mut checker = LinkChecker::new().with_cache(LinkCache::load(rqm_dir)?);
+    let report = checker.check(config);
+    if rqm_dir.is_dir() {
+        checker.cache().save(rqm_dir)?;
+    }
+    Ok(report)
+}
+
+#[cfg(not(feature = "link-check"))]
+fn check_links(_config: &RequirementConfig, _rqm_dir: &Path) -> rqm_core::Result<ValidationReport> {
+    Err(rqm_core::Error::custom(
+        "--check-links needs rqm-validator built with the `link-check` feature",
+    ))
+}
+
+// Helper function to read a file as of a git revision
+#[cfg(feature = "git")]
+fn file_at(file: &Path, revision: &str) -> rqm_core::Result<String> {
+    rqm_core::git::file_at(file, revision)
+}
+
+#[cfg(not(feature = "git"))]
+fn file_at(_file: &Path, _revision: &str) -> rqm_core::Result<String> {
+    Err(rqm_core::Error::custom(
+        "rqm-validator was built without the `git` feature",
+    ))
+}
+
+// Helper function to set requirement timestamps from git history
+#[cfg(feature = "git")]
+fn git_history(config: &mut RequirementConfig, file: &Path) -> rqm_core::Result<()> {
+    rqm_core::git::enrich(config, file).map(|_| ())
+}
+
+#[cfg(not(feature = "git"))]
+fn git_history(_config: &mut RequirementConfig, _file: &Path) -> rqm_core::Result<()> {
+    Err(rqm_core::Error::custom(
+        "--git-history needs rqm-validator built with the `git` feature",
+    ))
+}
+
+// Helper function to name the git user as an owner, by alias if one has
+// their email
+#[cfg(feature = "git")]
+fn current_user(files: &[PathBuf]) -> rqm_core::Result<String> {
+    let email = rqm_core::git::user_email(&files[0])?
+        .ok_or_else(|| rqm_core::Error::custom("git user.email is not set"))?;
+    for file in files {
+        let config = Parser::parse_file(file)?;
+        let alias = config
+            .aliases
+            .iter()
+            .find(|alias| alias.email.as_deref() == Some(email.as_str()));
+        if let Some(alias) = alias {
+            return Ok(alias.alias.clone());
         }
     }
+    Ok(email)
+}
+
+#[cfg(not(feature = "git"))]
+fn current_user(_files: &[PathBuf]) -> rqm_core::Result<String> {
+    Err(rqm_core::Error::custom(
+        "--owner @me needs rqm-validator built with the `git` feature",
+    ))
+}
+
+// Helper function to describe a merge conflict for display
+fn describe_conflict(conflict: &MergeConflict) -> String {
+    let requirement = conflict.requirement.as_deref().unwrap_or("(top level)");
+    match &conflict.field {
+        Some(field) => format!(
+            "{}: {} is {} in base, {} in ours and {} in theirs",
+            requirement, field, conflict.base, conflict.ours, conflict.theirs
+        ),
+        None => format!(
+            "{}: deleted on one side and changed on the other",
+            requirement
+        ),
+    }
+}
+
+// A project board to keep in step with requirement statuses, from the
+// options of `sync github`
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+struct GitHubBoard {
+    id: String,
+    status_field: String,
+    options: Vec<(Status, String)>,
+}
+
+// Helper function to parse a `--status-option` as status=option ID
+fn parse_status_option(value: &str) -> Result<(Status, String), String> {
+    let (status, option) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected status=option ID, got '{}'", value))?;
+    let status = serde_yaml::from_str(status.trim())
+        .map_err(|_| format!("'{}' is not a requirement status", status))?;
+    Ok((status, option.trim().to_string()))
+}
+
+// Helper function to withhold requirements above the readers' clearance
+fn withheld(
+    config: RequirementConfig,
+    clearance: Option<Clearance>,
+    withhold: Withhold,
+    store: Option<&MetadataStore>,
+) -> rqm_core::Result<RequirementConfig> {
+    let Some(clearance) = clearance else {
+        return Ok(config);
+    };
+    let mode = match withhold {
+        Withhold::Redact => Redaction::Redact,
+        Withhold::Drop => Redaction::Drop,
+    };
+    redact::redact(&config, clearance.into(), mode, store)
+}
+
+// Helper function to print the SRS document to a PDF
+#[cfg(feature = "pdf")]
+fn write_pdf(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    title: String,
+    version: String,
+    converter: Option<String>,
+    attachment_root: Option<&Path>,
+    out: &Path,
+) -> rqm_core::Result<()> {
+    let mut options = export::PdfOptions {
+        title,
+        version,
+        attachment_root: attachment_root.map(Path::to_path_buf),
+        ..export::PdfOptions::default()
+    };
+    if let Some(converter) = converter {
+        options.converter = converter.split_whitespace().map(String::from).collect();
+    }
+    export::write_pdf(config, store, &options, out)
+}
+
+#[cfg(not(feature = "pdf"))]
+fn write_pdf(
+    _config: &RequirementConfig,
+    _store: Option<&MetadataStore>,
+    _title: String,
+    _version: String,
+    _converter: Option<String>,
+    _attachment_root: Option<&Path>,
+    _out: &Path,
+) -> rqm_core::Result<()> {
+    Err(rqm_core::Error::custom(
+        "rqm-validator was built without the `pdf` feature",
+    ))
+}
+
+// Helper function to push requirements to, or pull statuses from, a tracker
+fn sync(tracker: Tracker) -> rqm_core::Result<SyncResult> {
+    match tracker {
+        Tracker::Jira {
+            file,
+            url,
+            project,
+            uuid_field,
+            mode,
+        } => sync_jira(&file, url, project, uuid_field, &mode),
+        Tracker::Github {
+            file,
+            repository,
+            project,
+            status_field,
+            status_options,
+            mode,
+        } => {
+            let project = project
+                .zip(status_field)
+                .map(|(id, status_field)| GitHubBoard {
+                    id,
+                    status_field,
+                    options: status_options,
+                });
+            sync_github(&file, repository, project, &mode)
+        }
+    }
+}
+
+#[cfg(feature = "jira")]
+fn sync_jira(
+    file: &Path,
+    url: String,
+    project: String,
+    uuid_field: String,
+    mode: &SyncMode,
+) -> rqm_core::Result<SyncResult> {
+    use rqm_core::interop::jira::{CurlTransport, JiraConfig, JiraState, JiraSync};
+
+    let rqm_dir = rqm_dir(file);
+    let mut store = MetadataStore::new(&rqm_dir)?;
+    let mut jira = JiraSync::new(
+        CurlTransport::from_env(url)?,
+        JiraConfig::new(project, uuid_field),
+    )
+    .with_state(JiraState::load(&rqm_dir)?);
+    let report = if mode.pull {
+        jira.pull_file(file, &store, mode.dry_run)?
+    } else {
+        jira.push(&Parser::parse_file(file)?, &mut store, mode.dry_run)?
+    };
+    if !mode.dry_run {
+        jira.state().save(&rqm_dir)?;
+    }
+
+    Ok(SyncResult {
+        dry_run: report.dry_run,
+        created: report.created,
+        updated: report.updated,
+        status_changes: report
+            .status_changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} ({}): {} -> {}",
+                    change.summary,
+                    change.key,
+                    change.from.map_or("none", Status::as_str),
+                    change.to.as_str()
+                )
+            })
+            .collect(),
+        conflicts: report
+            .conflicts
+            .iter()
+            .map(|conflict| {
+                format!(
+                    "{} ({}): {} locally, {} in Jira",
+                    conflict.summary,
+                    conflict.key,
+                    conflict.local.map_or("none", Status::as_str),
+                    conflict.remote
+                )
+            })
+            .collect(),
+        ..SyncResult::default()
+    })
+}
+
+#[cfg(not(feature = "jira"))]
+fn sync_jira(
+    _file: &Path,
+    _url: String,
+    _project: String,
+    _uuid_field: String,
+    _mode: &SyncMode,
+) -> rqm_core::Result<SyncResult> {
+    Err(rqm_core::Error::custom(
+        "rqm-validator was built without the `jira` feature",
+    ))
+}
+
+#[cfg(feature = "github")]
+fn sync_github(
+    file: &Path,
+    repository: String,
+    board: Option<GitHubBoard>,
+    mode: &SyncMode,
+) -> rqm_core::Result<SyncResult> {
+    use rqm_core::interop::github::{
+        CurlTransport, GitHubConfig, GitHubProject, GitHubState, GitHubSync,
+    };
+
+    let rqm_dir = rqm_dir(file);
+    let mut store = MetadataStore::new(&rqm_dir)?;
+    let mut config = GitHubConfig::new(repository);
+    if let Some(board) = board {
+        config = config.with_project(GitHubProject {
+            id: board.id,
+            status_field: board.status_field,
+            options: board.options.into_iter().collect(),
+        });
+    }
+    let mut github = GitHubSync::new(CurlTransport::from_env()?, config)
+        .with_state(GitHubState::load(&rqm_dir)?);
+    let report = if mode.pull {
+        github.pull_file(file, &store, mode.dry_run)?
+    } else {
+        github.push(&Parser::parse_file(file)?, &mut store, mode.dry_run)?
+    };
+    if !mode.dry_run {
+        github.state().save(&rqm_dir)?;
+    }
+
+    Ok(SyncResult {
+        dry_run: report.dry_run,
+        created: report.created,
+        updated: report.updated.iter().map(|n| format!("#{}", n)).collect(),
+        project_updates: report.project_updates,
+        status_changes: report
+            .status_changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} (#{}): {} -> {}",
+                    change.summary,
+                    change.number,
+                    change.from.map_or("none", Status::as_str),
+                    change.to.as_str()
+                )
+            })
+            .collect(),
+        conflicts: report
+            .conflicts
+            .iter()
+            .map(|conflict| {
+                format!(
+                    "{} (#{}): {} locally, {} in GitHub",
+                    conflict.summary,
+                    conflict.number,
+                    conflict.local.map_or("none", Status::as_str),
+                    if conflict.closed {
+                        "closed"
+                    } else {
+                        "reopened"
+                    }
+                )
+            })
+            .collect(),
+        ..SyncResult::default()
+    })
+}
 
-    adj_map.insert(req.summary.clone(), deps);
+#[cfg(not(feature = "github"))]
+fn sync_github(
+    _file: &Path,
+    _repository: String,
+    _board: Option<GitHubBoard>,
+    _mode: &SyncMode,
+) -> rqm_core::Result<SyncResult> {
+    Err(rqm_core::Error::custom(
+        "rqm-validator was built without the `github` feature",
+    ))
 }
@@ -0,0 +1,320 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Fluent builders for constructing requirements programmatically
+
+use crate::types::{
+    Approval, Attachment, Confidentiality, ExternalRef, OwnerReference, PersonAlias, Priority,
+    RequirementReference, RequirementTemplate, Status,
+};
+use crate::{Error, Requirement, RequirementConfig, Result, Validator};
+
+/// Builder for a single [`Requirement`]
+#[derive(Debug, Clone)]
+pub struct RequirementBuilder {
+    requirement: Requirement,
+}
+
+impl RequirementBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self {
+            requirement: Requirement::new(""),
+        }
+    }
+
+    /// Set the summary (required)
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.requirement.summary = summary.into();
+        self
+    }
+
+    /// Set the human-friendly name or ID
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.requirement.name = Some(name.into());
+        self
+    }
+
+    /// Set the description
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.requirement.description = Some(description.into());
+        self
+    }
+
+    /// Set the justification
+    pub fn justification(mut self, justification: impl Into<String>) -> Self {
+        self.requirement.justification = Some(justification.into());
+        self
+    }
+
+    /// Set the acceptance criteria text
+    pub fn acceptance_test(mut self, acceptance_test: impl Into<String>) -> Self {
+        self.requirement.acceptance_test = Some(acceptance_test.into());
+        self
+    }
+
+    /// Set the acceptance test URL
+    pub fn acceptance_test_link(mut self, link: impl Into<String>) -> Self {
+        self.requirement.acceptance_test_link = Some(link.into());
+        self
+    }
+
+    /// Set the owner (email, `@github` username, or alias)
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.requirement.owner = Some(OwnerReference::String(owner.into()));
+        self
+    }
+
+    /// Apply a named template
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.requirement.template = Some(template.into());
+        self
+    }
+
+    /// Add a tag
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.requirement.tags.push(tag.into());
+        self
+    }
+
+    /// Add further information
+    pub fn further_information(mut self, info: impl Into<String>) -> Self {
+        self.requirement.further_information.push(info.into());
+        self
+    }
+
+    /// Set the priority
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.requirement.priority = Some(priority);
+        self
+    }
+
+    /// Set the status
+    pub fn status(mut self, status: Status) -> Self {
+        self.requirement.status = Some(status);
+        self
+    }
+
+    /// Set who may read the requirement
+    pub fn confidentiality(mut self, confidentiality: Confidentiality) -> Self {
+        self.requirement.confidentiality = Some(confidentiality);
+        self
+    }
+
+    /// Add a reference to the same requirement in another system, linked
+    /// by the system's URL template
+    pub fn external_ref(mut self, system: impl Into<String>, id: impl Into<String>) -> Self {
+        self.requirement.external_refs.push(ExternalRef {
+            system: system.into(),
+            id: id.into(),
+            url: None,
+        });
+        self
+    }
+
+    /// Attach a file, by path relative to the project root, or a web page
+    pub fn attachment(mut self, path: impl Into<String>) -> Self {
+        self.requirement.attachments.push(Attachment::new(path));
+        self
+    }
+
+    /// Add a sign-off
+    pub fn approval(mut self, approval: Approval) -> Self {
+        self.requirement.approvals.push(approval);
+        self
+    }
+
+    /// Set the target release
+    pub fn target_release(mut self, release: impl Into<String>) -> Self {
+        self.requirement.target_release = Some(release.into());
+        self
+    }
+
+    /// Set the milestone
+    pub fn milestone(mut self, milestone: impl Into<String>) -> Self {
+        self.requirement.milestone = Some(milestone.into());
+        self
+    }
+
+    /// Set the effort estimate
+    pub fn estimate(mut self, estimate: f64) -> Self {
+        self.requirement.estimate = Some(estimate);
+        self
+    }
+
+    /// Add a nested child requirement
+    pub fn child(mut self, child: Requirement) -> Self {
+        self.requirement
+            .requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        self
+    }
+
+    /// Add a reference to another requirement by summary
+    pub fn child_ref(mut self, summary: impl Into<String>) -> Self {
+        self.requirement
+            .requirements
+            .push(RequirementReference::Reference(summary.into()));
+        self
+    }
+
+    /// Build the requirement, checking that a non-empty summary was given
+    pub fn build(self) -> Result<Requirement> {
+        if self.requirement.summary.trim().is_empty() {
+            return Err(Error::custom("Requirement summary is required"));
+        }
+        Ok(self.requirement)
+    }
+}
+
+impl Default for RequirementBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builder for a [`RequirementConfig`]
+#[derive(Debug, Clone)]
+pub struct RequirementConfigBuilder {
+    config: RequirementConfig,
+}
+
+impl RequirementConfigBuilder {
+    /// Create a builder for a version "1.0" config
+    pub fn new() -> Self {
+        Self {
+            config: RequirementConfig {
+                version: "1.0".to_string(),
+                aliases: vec![],
+                templates: vec![],
+                requirements: vec![],
+            },
+        }
+    }
+
+    /// Set the schema version
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.config.version = version.into();
+        self
+    }
+
+    /// Add a person alias
+    pub fn alias(mut self, alias: PersonAlias) -> Self {
+        self.config.aliases.push(alias);
+        self
+    }
+
+    /// Add a requirement template
+    pub fn template(mut self, template: RequirementTemplate) -> Self {
+        self.config.templates.push(template);
+        self
+    }
+
+    /// Add a top-level requirement
+    pub fn requirement(mut self, requirement: Requirement) -> Self {
+        self.config.requirements.push(requirement);
+        self
+    }
+
+    /// Build the config, resolving templates and running full validation
+    pub fn build(self) -> Result<RequirementConfig> {
+        let mut config = self.config;
+        config.apply_templates()?;
+        Validator::new()?.validate(&config).into_result()?;
+        Ok(config)
+    }
+}
+
+impl Default for RequirementConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Requirement {
+    /// Start building a requirement
+    pub fn builder() -> RequirementBuilder {
+        RequirementBuilder::new()
+    }
+}
+
+impl RequirementConfig {
+    /// Start building a requirement config
+    pub fn builder() -> RequirementConfigBuilder {
+        RequirementConfigBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requirement_builder() {
+        let req = Requirement::builder()
+            .summary("Parent")
+            .name("REQ-001")
+            .owner("@alice")
+            .priority(Priority::High)
+            .status(Status::Draft)
+            .tag("safety")
+            .child(Requirement::builder().summary("Child").build().unwrap())
+            .child_ref("Other")
+            .build()
+            .unwrap();
+
+        assert_eq!(req.summary, "Parent");
+        assert_eq!(req.name.as_deref(), Some("REQ-001"));
+        assert_eq!(req.priority, Some(Priority::High));
+        assert_eq!(req.tags, vec!["safety"]);
+        assert_eq!(req.requirements.len(), 2);
+        assert_eq!(req.flatten().len(), 2);
+    }
+
+    #[test]
+    fn test_requirement_builder_requires_summary() {
+        assert!(Requirement::builder()
+            .description("No summary")
+            .build()
+            .is_err());
+        assert!(Requirement::builder().summary("  ").build().is_err());
+    }
+
+    #[test]
+    fn test_config_builder() {
+        let config = RequirementConfig::builder()
+            .requirement(Requirement::builder().summary("A").build().unwrap())
+            .requirement(Requirement::builder().summary("B").build().unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(config.version, "1.0");
+        assert_eq!(config.requirements.len(), 2);
+    }
+
+    #[test]
+    fn test_config_builder_validates() {
+        let result = RequirementConfig::builder()
+            .requirement(Requirement::builder().summary("A").build().unwrap())
+            .requirement(Requirement::builder().summary("A").build().unwrap())
+            .build();
+
+        assert!(matches!(result, Err(Error::DuplicateSummary(_))));
+    }
+
+    #[test]
+    fn test_config_builder_unknown_owner_alias() {
+        let result = RequirementConfig::builder()
+            .requirement(
+                Requirement::builder()
+                    .summary("A")
+                    .owner("nobody")
+                    .build()
+                    .unwrap(),
+            )
+            .build();
+
+        assert!(matches!(result, Err(Error::InvalidOwner(_))));
+    }
+}
@@ -0,0 +1,188 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Rust constants for requirement IDs, checked at compile time
+//!
+//! [`rust_module`] renders a module with one constant per requirement ID
+//! and an `rqm_trace!` macro accepting exactly those IDs. Generated from a
+//! build script with [`write_rust_module`], a reference to a requirement
+//! that was removed or renamed in the YAML fails to compile:
+//!
+//! ```text
+//! // build.rs
+//! fn main() {
+//!     rqm_core::codegen::write_rust_module(
+//!         "requirements.yml",
+//!         std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("requirements.rs"),
+//!     )
+//!     .unwrap();
+//! }
+//!
+//! // src/lib.rs, before other modules so the macro is in scope there
+//! #[macro_use]
+//! #[allow(dead_code)]
+//! mod requirements {
+//!     include!(concat!(env!("OUT_DIR"), "/requirements.rs"));
+//! }
+//!
+//! // anywhere
+//! #[doc = rqm_trace!("REQ-042")]
+//! fn login() {}
+//! let id = requirements::REQ_042;
+//! ```
+//!
+//! `rqm_trace!` expands to the ID as a string literal, so the annotation
+//! stays visible in rustdoc.
+
+use crate::metadata::MetadataStore;
+use crate::types::RequirementConfig;
+use crate::{Parser, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Name of the constant for an ID, e.g. `REQ_042` for "REQ-042"
+pub fn constant_name(id: &str) -> String {
+    let mut name: String = id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Render the constants and `rqm_trace!` macro for every requirement ID
+///
+/// Requirements get a constant for their generated ID, taken from `store`
+/// where metadata exists, and one for their name. No metadata is created,
+/// so build scripts never write to `.rqm`.
+pub fn rust_module(config: &RequirementConfig, store: Option<&MetadataStore>) -> Result<String> {
+    // Constant name -> (ID, summary); the first requirement using a name wins
+    let mut constants: BTreeMap<String, (String, String)> = BTreeMap::new();
+    for req in config.all_requirements() {
+        let generated = match store {
+            Some(store) => store
+                .find_metadata(&req.summary)?
+                .map(|meta| meta.generated_id),
+            None => None,
+        };
+        for id in generated.into_iter().chain(req.name.clone()) {
+            constants
+                .entry(constant_name(&id))
+                .or_insert_with(|| (id, req.summary.clone()));
+        }
+    }
+
+    let mut out = String::from("// Requirement IDs generated by rqm. Do not edit.\n\n");
+    for (name, (id, summary)) in &constants {
+        let summary = summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        let _ = write!(
+            out,
+            "/// {}\npub const {}: &str = {:?};\n\n",
+            summary, name, id
+        );
+    }
+
+    out.push_str(
+        "/// The requirement ID as a string literal; unknown IDs fail to compile\nmacro_rules! rqm_trace {\n",
+    );
+    for (id, _) in constants.values() {
+        let _ = writeln!(out, "    ({:?}) => {{ {:?} }};", id, id);
+    }
+    out.push_str("}\n");
+    Ok(out)
+}
+
+/// Generate the module for the requirements file at `requirements` into
+/// `output`, for use in build scripts
+///
+/// Metadata is read from the `.rqm` directory next to the requirements
+/// file if there is one. Cargo is told to rerun the build script when the
+/// requirements or their metadata change. The file is only rewritten when
+/// its content changes, to avoid needless recompilation.
+pub fn write_rust_module(requirements: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<()> {
+    let requirements = requirements.as_ref();
+    let config = Parser::parse_file(requirements)?;
+    let rqm_dir = requirements
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(".rqm");
+    let store = if rqm_dir.join("config.yml").exists() {
+        println!("cargo:rerun-if-changed={}", rqm_dir.display());
+        Some(MetadataStore::new(&rqm_dir)?)
+    } else {
+        None
+    };
+    println!("cargo:rerun-if-changed={}", requirements.display());
+
+    let module = rust_module(&config, store.as_ref())?;
+    let output = output.as_ref();
+    if fs::read_to_string(output).ok().as_deref() != Some(module.as_str()) {
+        fs::write(output, module)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    name: auth.login
+    requirements:
+      - summary: Lockout
+  - summary: "Audit log"
+    name: 2FA
+"#;
+
+    #[test]
+    fn test_constant_names() {
+        assert_eq!(constant_name("REQ-042"), "REQ_042");
+        assert_eq!(constant_name("auth.login"), "AUTH_LOGIN");
+        assert_eq!(constant_name("2FA"), "_2FA");
+    }
+
+    #[test]
+    fn test_module_has_constants_and_macro() {
+        let temp = TempDir::new().unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let lockout = config.all_requirements()[1];
+        store.get_or_create_metadata(lockout).unwrap();
+
+        let module = rust_module(&config, Some(&store)).unwrap();
+        assert!(module.contains("/// Login\npub const AUTH_LOGIN: &str = \"auth.login\";\n"));
+        assert!(module.contains("/// Lockout\npub const REQ_001: &str = \"REQ-001\";\n"));
+        assert!(module.contains("pub const _2FA: &str = \"2FA\";"));
+        assert!(module.contains("    (\"REQ-001\") => { \"REQ-001\" };\n"));
+    }
+
+    #[test]
+    fn test_write_module_only_when_changed() {
+        let temp = TempDir::new().unwrap();
+        let requirements = temp.path().join("requirements.yml");
+        let output = temp.path().join("requirements.rs");
+        fs::write(&requirements, YAML).unwrap();
+
+        write_rust_module(&requirements, &output).unwrap();
+        let first = fs::metadata(&output).unwrap().modified().unwrap();
+        assert!(fs::read_to_string(&output).unwrap().contains("AUTH_LOGIN"));
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        write_rust_module(&requirements, &output).unwrap();
+        assert_eq!(fs::metadata(&output).unwrap().modified().unwrap(), first);
+    }
+}
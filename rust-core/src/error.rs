@@ -34,6 +34,12 @@ pub enum Error {
     #[error("Invalid owner reference: {0}")]
     InvalidOwner(String),
 
+    #[error("Deprecated reference: {0}")]
+    DeprecatedReference(String),
+
+    #[error("Invalid tag: {0}")]
+    InvalidTag(String),
+
     #[error("Graph error: {0}")]
     GraphError(String),
 
@@ -50,7 +56,7 @@ impl Error {
     /// Enhance YAML parsing error with helpful context
     pub fn enhance_yaml_error(err: serde_yaml::Error) -> Self {
         let msg = err.to_string();
-        
+
         // Detect common error patterns and provide helpful hints
         let enhanced = if msg.contains("RequirementReference") {
             format!(
@@ -158,6 +164,19 @@ mod tests {
         assert!(err.to_string().contains("Invalid owner"));
     }
 
+    #[test]
+    fn test_deprecated_reference_error() {
+        let err = Error::DeprecatedReference("Old Login".to_string());
+        assert!(err.to_string().contains("Deprecated reference"));
+        assert!(err.to_string().contains("Old Login"));
+    }
+
+    #[test]
+    fn test_invalid_tag_error() {
+        let err = Error::InvalidTag("safety//hv".to_string());
+        assert!(err.to_string().contains("Invalid tag"));
+    }
+
     #[test]
     fn test_graph_error() {
         let err = Error::GraphError("cycle detected".to_string());
@@ -0,0 +1,61 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Export of requirements to documents for readers outside the repository
+
+pub mod changelog;
+pub mod glossary;
+pub mod markdown;
+pub mod matrix;
+pub mod outline;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod site;
+pub mod table;
+
+pub use changelog::to_changelog;
+pub use glossary::{glossary_html, glossary_markdown, term_anchor, TermLinker};
+pub use markdown::{to_markdown, MarkdownOptions};
+pub use matrix::{matrix_rows, matrix_to_csv, matrix_to_html, matrix_to_json, MatrixRow};
+pub use outline::{outline, outline_to_markdown, OutlineEntry};
+#[cfg(feature = "pdf")]
+pub use pdf::{to_srs_html, write_pdf, PdfOptions, PdfTemplate};
+pub use site::{build_site, Site, SiteOptions};
+#[cfg(feature = "xlsx")]
+pub use table::to_xlsx;
+pub use table::{to_csv, TableRow};
+
+use std::collections::HashMap;
+
+/// Replace `{{name}}` placeholders; unknown placeholders become empty
+pub fn render_template(template: &str, values: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = rest[start + 2..start + end].trim();
+        out.push_str(values.get(name).map(String::as_str).unwrap_or_default());
+        rest = &rest[start + end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let values = HashMap::from([("name", "rqm".to_string())]);
+        assert_eq!(
+            render_template("{{ name }}/{{missing}}/{{name", &values),
+            "rqm//{{name"
+        );
+    }
+}
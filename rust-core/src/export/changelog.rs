@@ -0,0 +1,159 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Release-notes-style Markdown changelog built from a [`GraphDiff`]
+//!
+//! Requirements are grouped into "New requirements", "Changed",
+//! "Deprecated" and "Removed". A requirement deprecated in the change is
+//! only listed under "Deprecated", even if other fields changed too.
+
+use crate::error::Result;
+use crate::graph::GraphDiff;
+use crate::metadata::{display_id, MetadataStore};
+use crate::types::{RequirementConfig, Status};
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Render the changes in `diff` from `old` to `new` as a Markdown section
+/// titled `title`
+pub fn to_changelog(
+    diff: &GraphDiff,
+    old: &RequirementConfig,
+    new: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    title: &str,
+) -> Result<String> {
+    let mut out = format!("## {}\n", title);
+    if diff.added.is_empty() && diff.modified.is_empty() && diff.removed.is_empty() {
+        out.push_str("\nNo requirement changes.\n");
+        return Ok(out);
+    }
+
+    let label = |summary: &str| -> Result<String> {
+        let req = [new, old]
+            .into_iter()
+            .flat_map(|config| config.all_requirements())
+            .find(|req| req.summary == summary);
+        let id = match req {
+            Some(req) => display_id(store, req)?,
+            None => None,
+        };
+        Ok(match id {
+            Some(id) => format!("**{}** {}", id, summary),
+            None => format!("**{}**", summary),
+        })
+    };
+    let deprecated: HashSet<&str> = diff
+        .status_transitions
+        .iter()
+        .filter(|transition| transition.to == Some(Status::Deprecated))
+        .map(|transition| transition.summary.as_str())
+        .collect();
+
+    let mut new = vec![];
+    for summary in &diff.added {
+        new.push(label(summary)?);
+    }
+
+    let mut changed = vec![];
+    for change in &diff.modified {
+        if deprecated.contains(change.summary.as_str()) {
+            continue;
+        }
+        let fields: Vec<String> = change
+            .changes
+            .iter()
+            .map(|field| {
+                let transition = diff
+                    .status_transitions
+                    .iter()
+                    .find(|transition| transition.summary == change.summary);
+                match transition {
+                    Some(transition) if field.field == "status" => format!(
+                        "status {} → {}",
+                        transition.from.map_or("unset", Status::as_str),
+                        transition.to.map_or("unset", Status::as_str)
+                    ),
+                    _ => field.field.clone(),
+                }
+            })
+            .collect();
+        changed.push(format!(
+            "{} ({})",
+            label(&change.summary)?,
+            fields.join(", ")
+        ));
+    }
+
+    let mut deprecations = vec![];
+    for transition in &diff.status_transitions {
+        if deprecated.contains(transition.summary.as_str()) {
+            deprecations.push(label(&transition.summary)?);
+        }
+    }
+
+    let mut removed = vec![];
+    for summary in &diff.removed {
+        removed.push(label(summary)?);
+    }
+
+    for (heading, items) in [
+        ("New requirements", new),
+        ("Changed", changed),
+        ("Deprecated", deprecations),
+        ("Removed", removed),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "\n### {}\n", heading);
+        for item in items {
+            let _ = writeln!(out, "- {}", item);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::diff;
+    use crate::{Parser, RequirementGraph};
+
+    fn changelog(old: &str, new: &str, title: &str) -> String {
+        let old = Parser::parse_str(old).unwrap();
+        let new = Parser::parse_str(new).unwrap();
+        let diff = diff(
+            &RequirementGraph::from_config(&old).unwrap(),
+            &RequirementGraph::from_config(&new).unwrap(),
+        );
+        to_changelog(&diff, &old, &new, None, title).unwrap()
+    }
+
+    #[test]
+    fn test_changelog_sections() {
+        let changelog = changelog(
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: draft\n  - summary: Export\n    status: approved\n  - summary: Print\n",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: approved\n    description: Users log in\n  - summary: Export\n    status: deprecated\n    description: Replaced by sharing\n  - summary: Share\n    name: SHARE\n",
+            "Release 1.1",
+        );
+        assert_eq!(
+            changelog,
+            "## Release 1.1\n\
+             \n### New requirements\n\n- **SHARE** Share\n\
+             \n### Changed\n\n- **Login** (description, status draft → approved)\n\
+             \n### Deprecated\n\n- **Export**\n\
+             \n### Removed\n\n- **Print**\n"
+        );
+    }
+
+    #[test]
+    fn test_changelog_without_changes() {
+        let config = "version: \"1.0\"\nrequirements:\n  - summary: Login\n";
+        assert_eq!(
+            changelog(config, config, "1.0"),
+            "## 1.0\n\nNo requirement changes.\n"
+        );
+    }
+}
@@ -0,0 +1,242 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Glossary terms linked in exported text
+//!
+//! The first use of each term in a piece of text links to its definition,
+//! so readers of a long specification are not left guessing at jargon
+//! without every repetition turning into a link. Terms written in capitals,
+//! such as acronyms, match only in capitals; other terms and aliases match
+//! in any case.
+
+use crate::metadata::kebab_case;
+use crate::report::escape;
+use crate::validator::GlossaryEntry;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::ops::Range;
+
+/// Anchor of a glossary entry, e.g. `term-session-token`
+pub fn term_anchor(term: &str) -> String {
+    format!("term-{}", kebab_case(term))
+}
+
+/// Links the terms of a glossary in text
+#[derive(Debug, Clone)]
+pub struct TermLinker<'a> {
+    glossary: &'a [GlossaryEntry],
+
+    /// Any term or alias, longest first so phrases win over their words
+    pattern: Option<Regex>,
+
+    /// Entry index by term or alias, lowercased unless written in capitals
+    entries: HashMap<String, usize>,
+}
+
+impl<'a> TermLinker<'a> {
+    pub fn new(glossary: &'a [GlossaryEntry]) -> Self {
+        let mut entries = HashMap::new();
+        let mut names: Vec<&str> = vec![];
+        for (index, entry) in glossary.iter().enumerate() {
+            for name in entry.names().map(str::trim).filter(|name| !name.is_empty()) {
+                entries.entry(key(name)).or_insert(index);
+                names.push(name);
+            }
+        }
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let alternatives: Vec<String> = names
+            .iter()
+            .map(|name| {
+                let mut alternative = regex::escape(name);
+                if !is_capitalized(name) {
+                    alternative = format!("(?i:{})", alternative);
+                }
+                if name.starts_with(|c: char| c.is_alphanumeric()) {
+                    alternative.insert_str(0, r"\b");
+                }
+                if name.ends_with(|c: char| c.is_alphanumeric()) {
+                    alternative.push_str(r"\b");
+                }
+                alternative
+            })
+            .collect();
+        let pattern = (!alternatives.is_empty())
+            .then(|| Regex::new(&alternatives.join("|")).ok())
+            .flatten();
+        Self {
+            glossary,
+            pattern,
+            entries,
+        }
+    }
+
+    /// Plain text as HTML, with terms linked to `#term-...` anchors
+    pub fn html(&self, text: &str) -> String {
+        self.link(text, &[], escape, |found, entry| {
+            format!(
+                "<a class=\"term\" href=\"#{}\" title=\"{}\">{}</a>",
+                term_anchor(&entry.term),
+                escape(&entry.definition),
+                escape(found)
+            )
+        })
+    }
+
+    /// Markdown with terms linked to `#term-...` anchors
+    ///
+    /// Code spans, links and inline HTML are left as they are.
+    pub fn markdown(&self, text: &str) -> String {
+        let protected: Vec<Range<usize>> = Regex::new(r"`[^`]*`|\[[^\]]*\]\([^)]*\)|<[^>]*>")
+            .map(|regex| regex.find_iter(text).map(|m| m.range()).collect())
+            .unwrap_or_default();
+        self.link(text, &protected, str::to_string, |found, entry| {
+            format!("[{}](#{})", found, term_anchor(&entry.term))
+        })
+    }
+
+    fn link(
+        &self,
+        text: &str,
+        protected: &[Range<usize>],
+        plain: impl Fn(&str) -> String,
+        linked: impl Fn(&str, &GlossaryEntry) -> String,
+    ) -> String {
+        let Some(pattern) = &self.pattern else {
+            return plain(text);
+        };
+        let mut out = String::with_capacity(text.len());
+        let mut done = HashSet::new();
+        let mut last = 0;
+        for found in pattern.find_iter(text) {
+            if protected
+                .iter()
+                .any(|range| range.start < found.end() && found.start() < range.end)
+            {
+                continue;
+            }
+            let Some(&index) = self.entries.get(&key(found.as_str())) else {
+                continue;
+            };
+            if !done.insert(index) {
+                continue;
+            }
+            out.push_str(&plain(&text[last..found.start()]));
+            out.push_str(&linked(found.as_str(), &self.glossary[index]));
+            last = found.end();
+        }
+        out.push_str(&plain(&text[last..]));
+        out
+    }
+}
+
+/// Glossary as an HTML section, one definition per term
+pub fn glossary_html(glossary: &[GlossaryEntry]) -> String {
+    let mut out = String::from("<section id=\"glossary\">\n<h2>Glossary</h2>\n<dl>\n");
+    for entry in sorted(glossary) {
+        let _ = write!(
+            out,
+            "<dt id=\"{}\">{}</dt>\n<dd>{}",
+            term_anchor(&entry.term),
+            escape(&entry.term),
+            escape(&entry.definition)
+        );
+        if !entry.aliases.is_empty() {
+            let _ = write!(out, " <em>Also: {}</em>", escape(&entry.aliases.join(", ")));
+        }
+        out.push_str("</dd>\n");
+    }
+    out.push_str("</dl>\n</section>\n");
+    out
+}
+
+/// Glossary as a Markdown section, one definition per term
+pub fn glossary_markdown(glossary: &[GlossaryEntry]) -> String {
+    let mut out = String::from("\n## Glossary\n");
+    for entry in sorted(glossary) {
+        let _ = write!(
+            out,
+            "\n<a id=\"{}\"></a>\n**{}**: {}",
+            term_anchor(&entry.term),
+            entry.term,
+            entry.definition.trim_end()
+        );
+        if !entry.aliases.is_empty() {
+            let _ = write!(out, " *Also: {}*", entry.aliases.join(", "));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn sorted(glossary: &[GlossaryEntry]) -> Vec<&GlossaryEntry> {
+    let mut entries: Vec<&GlossaryEntry> = glossary.iter().collect();
+    entries.sort_by_key(|entry| entry.term.to_lowercase());
+    entries
+}
+
+fn is_capitalized(name: &str) -> bool {
+    name.chars().any(char::is_alphabetic) && !name.chars().any(char::is_lowercase)
+}
+
+fn key(name: &str) -> String {
+    if is_capitalized(name) {
+        name.to_string()
+    } else {
+        name.to_lowercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn glossary() -> Vec<GlossaryEntry> {
+        vec![
+            GlossaryEntry {
+                aliases: vec!["tokens".to_string()],
+                ..GlossaryEntry::new("Session token", "Opaque <credential> of a login")
+            },
+            GlossaryEntry::new("SSO", "Single sign-on"),
+            GlossaryEntry::new("token", "Any credential"),
+        ]
+    }
+
+    #[test]
+    fn test_first_use_of_each_term_is_linked() {
+        let glossary = glossary();
+        let linker = TermLinker::new(&glossary);
+
+        assert_eq!(
+            linker.markdown("Issue a session token after SSO; the session token and sso expire."),
+            "Issue a [session token](#term-session-token) after [SSO](#term-sso); \
+             the session token and sso expire."
+        );
+        assert_eq!(
+            linker.markdown("Rotate tokens, not `token` or [token](https://example.com)."),
+            "Rotate [tokens](#term-session-token), not `token` or [token](https://example.com)."
+        );
+        assert_eq!(
+            linker.html("A token & SSO"),
+            "A <a class=\"term\" href=\"#term-token\" title=\"Any credential\">token</a> &amp; \
+             <a class=\"term\" href=\"#term-sso\" title=\"Single sign-on\">SSO</a>"
+        );
+        assert_eq!(TermLinker::new(&[]).html("<b>"), "&lt;b&gt;");
+    }
+
+    #[test]
+    fn test_glossary_sections() {
+        let glossary = glossary();
+        let markdown = glossary_markdown(&glossary);
+        assert!(markdown.starts_with("\n## Glossary\n\n<a id=\"term-session-token\"></a>\n"));
+        assert!(
+            markdown.contains("**Session token**: Opaque <credential> of a login *Also: tokens*")
+        );
+
+        let html = glossary_html(&glossary);
+        assert!(html.contains("<dt id=\"term-sso\">SSO</dt>\n<dd>Single sign-on</dd>"));
+        assert!(html.contains("Opaque &lt;credential&gt; of a login"));
+    }
+}
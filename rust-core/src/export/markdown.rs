@@ -0,0 +1,352 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Numbered Markdown specification
+//!
+//! Requirements are numbered by position (1, 1.1, 1.2, ...) and each gets
+//! an anchor, so references between requirements become links. Levels
+//! deeper than [`MarkdownOptions::heading_depth`] are rendered as bold
+//! paragraphs instead of headings. Terms of the project glossary link to
+//! their definitions at the end of the document.
+
+use super::glossary::{glossary_markdown, TermLinker};
+use crate::error::Result;
+use crate::metadata::{display_id, kebab_case, MetadataStore};
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use crate::validator::{is_well_formed_url, Glossary};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+
+/// Options for [`to_markdown`]
+#[derive(Debug, Clone)]
+pub struct MarkdownOptions {
+    /// Document title, rendered as the level 1 heading
+    pub title: String,
+
+    /// Number of requirement levels rendered as headings, starting at `##`
+    pub heading_depth: usize,
+
+    /// Whether to add a table of status, owner and other metadata
+    pub metadata_tables: bool,
+
+    /// Glossary whose terms are linked, usually `.rqm/glossary.yml`
+    pub glossary: Option<Glossary>,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self {
+            title: "Requirements Specification".to_string(),
+            heading_depth: 3,
+            metadata_tables: true,
+            glossary: None,
+        }
+    }
+}
+
+/// Render requirements as a numbered Markdown document
+pub fn to_markdown(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    options: &MarkdownOptions,
+) -> Result<String> {
+    let glossary = options
+        .glossary
+        .as_ref()
+        .map(Glossary::entries)
+        .unwrap_or_default();
+    let mut writer = Writer {
+        out: format!("# {}\n", options.title),
+        numbers: outline_numbers(config),
+        store,
+        options,
+        terms: TermLinker::new(&glossary),
+    };
+    for req in &config.requirements {
+        writer.requirement(req, 1)?;
+    }
+    if !glossary.is_empty() {
+        writer.out.push_str(&glossary_markdown(&glossary));
+    }
+    Ok(writer.out)
+}
+
+/// Outline numbers of requirements defined in place, by summary
+pub(super) fn outline_numbers(config: &RequirementConfig) -> HashMap<String, String> {
+    let mut numbers = HashMap::new();
+    for (i, req) in config.requirements.iter().enumerate() {
+        number(req, (i + 1).to_string(), &mut numbers);
+    }
+    numbers
+}
+
+fn number(req: &Requirement, prefix: String, numbers: &mut HashMap<String, String>) {
+    let mut i = 0;
+    for child in &req.requirements {
+        if let RequirementReference::Full(child) = child {
+            i += 1;
+            number(child, format!("{}.{}", prefix, i), numbers);
+        }
+    }
+    numbers.entry(req.summary.clone()).or_insert(prefix);
+}
+
+fn anchor(summary: &str) -> String {
+    format!("req-{}", kebab_case(summary))
+}
+
+struct Writer<'a> {
+    out: String,
+    numbers: HashMap<String, String>,
+    store: Option<&'a MetadataStore>,
+    options: &'a MarkdownOptions,
+    terms: TermLinker<'a>,
+}
+
+impl Writer<'_> {
+    /// Link to another requirement, or its plain summary if not in the document
+    fn link(&self, summary: &str) -> String {
+        match self.numbers.get(summary) {
+            Some(number) => format!("[{} {}](#{})", number, summary, anchor(summary)),
+            None => summary.to_string(),
+        }
+    }
+
+    fn requirement(&mut self, req: &Requirement, depth: usize) -> Result<()> {
+        let number = self.numbers.get(&req.summary).cloned().unwrap_or_default();
+        let title = format!("{} {}", number, req.summary);
+        let _ = writeln!(self.out, "\n<a id=\"{}\"></a>", anchor(&req.summary));
+        if depth <= self.options.heading_depth && depth < 6 {
+            let _ = writeln!(self.out, "{} {}", "#".repeat(depth + 1), title);
+        } else {
+            let _ = writeln!(self.out, "**{}**", title);
+        }
+
+        if let Some(description) = &req.description {
+            let description = self.terms.markdown(description.trim_end());
+            let _ = write!(self.out, "\n{}\n", description);
+        }
+        if self.options.metadata_tables {
+            self.table(req)?;
+        }
+        if let Some(justification) = &req.justification {
+            let justification = self.terms.markdown(justification.trim_end());
+            let _ = write!(self.out, "\n*Justification:* {}\n", justification);
+        }
+        if let Some(test) = &req.acceptance_test {
+            let test = self.terms.markdown(test.trim_end());
+            let _ = write!(self.out, "\n*Acceptance test:* {}\n", test);
+        }
+
+        let references: Vec<String> = req
+            .requirements
+            .iter()
+            .filter_map(|child| match child {
+                RequirementReference::Reference(summary) => Some(self.link(summary)),
+                RequirementReference::Full(_) => None,
+            })
+            .collect();
+        if !references.is_empty() {
+            self.out.push_str("\nAlso requires:\n\n");
+            for reference in references {
+                let _ = writeln!(self.out, "- {}", reference);
+            }
+        }
+
+        for child in &req.requirements {
+            if let RequirementReference::Full(child) = child {
+                self.requirement(child, depth + 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn table(&mut self, req: &Requirement) -> Result<()> {
+        let id = display_id(self.store, req)?;
+
+        let mut rows: Vec<(&str, String)> = vec![];
+        if let Some(id) = id {
+            rows.push(("ID", format!("`{}`", id)));
+        }
+        if let Some(status) = req.status {
+            rows.push(("Status", status.to_string()));
+        }
+        if let Some(priority) = req.priority {
+            rows.push(("Priority", priority.to_string()));
+        }
+        if let Some(confidentiality) = req.confidentiality {
+            rows.push(("Confidentiality", confidentiality.to_string()));
+        }
+        if let Some(owner) = &req.owner {
+            rows.push(("Owner", owner.as_str().to_string()));
+        }
+        if !req.tags.is_empty() {
+            rows.push(("Tags", req.tags.join(", ")));
+        }
+        if let Some(release) = &req.target_release {
+            rows.push(("Target release", release.clone()));
+        }
+        if let Some(by) = &req.superseded_by {
+            rows.push(("Superseded by", self.link(by)));
+        }
+        if !req.supersedes.is_empty() {
+            let links: Vec<String> = req.supersedes.iter().map(|s| self.link(s)).collect();
+            rows.push(("Supersedes", links.join(", ")));
+        }
+        if !req.approvals.is_empty() {
+            let approvals: Vec<String> = req
+                .approvals
+                .iter()
+                .map(|a| format!("{} ({}, {})", a.person, a.role, a.date))
+                .collect();
+            rows.push(("Approved by", approvals.join(", ")));
+        }
+        if !req.external_refs.is_empty() {
+            let no_templates = BTreeMap::new();
+            let templates = self.store.map_or(&no_templates, |store| {
+                &store.project_config().external_systems
+            });
+            let links: Vec<String> = req
+                .external_refs
+                .iter()
+                .map(|reference| match reference.resolve_url(templates) {
+                    Some(url) if is_well_formed_url(&url) => format!("[{}](<{}>)", reference, url),
+                    _ => reference.to_string(),
+                })
+                .collect();
+            rows.push(("External references", links.join(", ")));
+        }
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        self.out.push_str("\n| Field | Value |\n| --- | --- |\n");
+        for (field, value) in rows {
+            let _ = writeln!(self.out, "| {} | {} |", field, cell(&value));
+        }
+        Ok(())
+    }
+}
+
+fn cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    owner: "@alice"
+    description: Users prove who they are.
+    requirements:
+      - summary: Login
+        tags: [web, sso]
+        requirements:
+          - summary: Lockout
+            acceptance_test: Five failures lock the account
+      - Audit log
+  - summary: Audit log
+    name: AUD
+    superseded_by: Authentication
+"#;
+
+    #[test]
+    fn test_markdown_numbering_and_links() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let options = MarkdownOptions {
+            heading_depth: 2,
+            ..MarkdownOptions::default()
+        };
+        let markdown = to_markdown(&config, None, &options).unwrap();
+
+        assert_eq!(
+            markdown,
+            r#"# Requirements Specification
+
+<a id="req-authentication"></a>
+## 1 Authentication
+
+Users prove who they are.
+
+| Field | Value |
+| --- | --- |
+| Status | approved |
+| Owner | @alice |
+
+Also requires:
+
+- [2 Audit log](#req-audit-log)
+
+<a id="req-login"></a>
+### 1.1 Login
+
+| Field | Value |
+| --- | --- |
+| Tags | web, sso |
+
+<a id="req-lockout"></a>
+**1.1.1 Lockout**
+
+*Acceptance test:* Five failures lock the account
+
+<a id="req-audit-log"></a>
+## 2 Audit log
+
+| Field | Value |
+| --- | --- |
+| ID | `AUD` |
+| Superseded by | [1 Authentication](#req-authentication) |
+"#
+        );
+    }
+
+    #[test]
+    fn test_markdown_without_tables() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let options = MarkdownOptions {
+            title: "Spec".to_string(),
+            metadata_tables: false,
+            ..MarkdownOptions::default()
+        };
+        let markdown = to_markdown(&config, None, &options).unwrap();
+        assert!(markdown.starts_with("# Spec\n"));
+        assert!(markdown.contains("#### 1.1.1 Lockout\n"));
+        assert!(!markdown.contains("| Field |"));
+    }
+
+    #[test]
+    fn test_markdown_links_glossary_terms() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    description: Users sign in with MFA.
+    acceptance_test: MFA is requested
+"#;
+        let config = Parser::parse_str(yaml).unwrap();
+        let options = MarkdownOptions {
+            glossary: Some(Glossary {
+                acronyms: BTreeMap::from([(
+                    "MFA".to_string(),
+                    "Multi-factor authentication".to_string(),
+                )]),
+                ..Glossary::default()
+            }),
+            ..MarkdownOptions::default()
+        };
+        let markdown = to_markdown(&config, None, &options).unwrap();
+        assert!(markdown.contains("\nUsers sign in with [MFA](#term-mfa).\n"));
+        assert!(markdown.contains("*Acceptance test:* [MFA](#term-mfa) is requested"));
+        assert!(markdown.ends_with(
+            "## Glossary\n\n<a id=\"term-mfa\"></a>\n**MFA**: Multi-factor authentication\n"
+        ));
+    }
+}
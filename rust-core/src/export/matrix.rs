@@ -0,0 +1,268 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Requirements Traceability Matrix
+//!
+//! One row per requirement, linking it to the code implementing it (from a
+//! [`TraceMap`]) and the tests verifying it (from a [`VerificationMap`]),
+//! with the verification result. Rendered as CSV for spreadsheets, HTML
+//! for auditors, or JSON for other tools.
+
+use super::table::csv_line;
+use crate::error::{Error, Result};
+use crate::metadata::{display_id, MetadataStore};
+use crate::report::escape;
+use crate::trace::{Annotation, TestOutcome, TestResult, TraceMap, Verification, VerificationMap};
+use crate::types::RequirementConfig;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Column headings of the CSV and HTML matrix
+pub const MATRIX_COLUMNS: &[&str] = &["ID", "Summary", "Status", "Code", "Tests", "Verification"];
+
+/// One requirement with its code and tests
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct MatrixRow {
+    /// ID as given by [`display_id`]
+    pub id: Option<String>,
+    pub summary: String,
+    pub status: Option<String>,
+    pub code: Vec<Annotation>,
+    pub tests: Vec<TestResult>,
+    pub verification: Verification,
+}
+
+impl MatrixRow {
+    /// The row's values, in the order of [`MATRIX_COLUMNS`]
+    pub fn cells(&self) -> Vec<String> {
+        let code: Vec<String> = self
+            .code
+            .iter()
+            .map(|a| format!("{}:{}", a.path.display(), a.line))
+            .collect();
+        let tests: Vec<String> = self
+            .tests
+            .iter()
+            .map(|t| format!("{} ({})", t.name, outcome(t.outcome)))
+            .collect();
+        vec![
+            self.id.clone().unwrap_or_default(),
+            self.summary.clone(),
+            self.status.clone().unwrap_or_default(),
+            code.join("\n"),
+            tests.join("\n"),
+            verification(self.verification).to_string(),
+        ]
+    }
+}
+
+fn outcome(outcome: TestOutcome) -> &'static str {
+    match outcome {
+        TestOutcome::Passed => "passed",
+        TestOutcome::Failed => "failed",
+        TestOutcome::Skipped => "skipped",
+    }
+}
+
+fn verification(verification: Verification) -> &'static str {
+    match verification {
+        Verification::Passing => "passing",
+        Verification::Failing => "failing",
+        Verification::Untested => "untested",
+    }
+}
+
+/// One row per requirement, in document order
+///
+/// Either map may be left out, leaving its columns empty.
+pub fn matrix_rows(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    trace: Option<&TraceMap>,
+    verification: Option<&VerificationMap>,
+) -> Result<Vec<MatrixRow>> {
+    let mut seen = HashSet::new();
+    let mut rows = vec![];
+    for req in config.all_requirements() {
+        if !seen.insert(&req.summary) {
+            continue;
+        }
+        let id = display_id(store, req)?;
+        let tests = verification
+            .and_then(|map| map.requirements.get(&req.summary))
+            .map(|entry| entry.tests.clone())
+            .unwrap_or_default();
+        rows.push(MatrixRow {
+            id,
+            summary: req.summary.clone(),
+            status: req.status.map(|s| s.to_string()),
+            code: trace
+                .map(|trace| trace.locations(&req.summary).to_vec())
+                .unwrap_or_default(),
+            tests,
+            verification: verification
+                .map_or(Verification::Untested, |map| map.verification(&req.summary)),
+        });
+    }
+    Ok(rows)
+}
+
+/// Render the matrix as CSV with a header row; code locations and tests
+/// are one per line within their cells
+pub fn matrix_to_csv(rows: &[MatrixRow]) -> String {
+    let mut out = csv_line(MATRIX_COLUMNS.iter().map(|column| column.to_string()));
+    for row in rows {
+        out.push_str(&csv_line(row.cells()));
+    }
+    out
+}
+
+/// Render the matrix as JSON
+pub fn matrix_to_json(rows: &[MatrixRow]) -> Result<String> {
+    serde_json::to_string_pretty(rows)
+        .map_err(|e| Error::custom(format!("Failed to serialize traceability matrix: {}", e)))
+}
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: left; vertical-align: top; }
+th { background: #f3f3f3; }
+td.code, td.tests { font-family: monospace; font-size: 90%; }
+.passing { background: #e6f4ea; }
+.failing { background: #fce8e6; }
+.untested { background: #fef7e0; }
+.summary span { margin-right: 1.5rem; }
+"#;
+
+/// Render the matrix as a self-contained HTML page with coverage totals
+pub fn matrix_to_html(rows: &[MatrixRow], title: &str) -> String {
+    let count = |v: Verification| rows.iter().filter(|r| r.verification == v).count();
+    let traced = rows.iter().filter(|r| !r.code.is_empty()).count();
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n<p class=\"summary\"><span>{} requirements</span><span>{traced} with code</span><span>{} passing</span><span>{} failing</span><span>{} untested</span></p>\n<table>\n<tr>",
+        rows.len(),
+        count(Verification::Passing),
+        count(Verification::Failing),
+        count(Verification::Untested),
+        title = escape(title),
+    );
+    for column in MATRIX_COLUMNS {
+        let _ = write!(out, "<th>{}</th>", column);
+    }
+    out.push_str("</tr>\n");
+    for row in rows {
+        let cells = row.cells();
+        let _ = write!(out, "<tr class=\"{}\">", verification(row.verification));
+        for (column, cell) in MATRIX_COLUMNS.iter().zip(&cells) {
+            let class = match *column {
+                "Code" => " class=\"code\"",
+                "Tests" => " class=\"tests\"",
+                _ => "",
+            };
+            let _ = write!(
+                out,
+                "<td{}>{}</td>",
+                class,
+                escape(cell).replace('\n', "<br>")
+            );
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::parse_cargo_json;
+    use crate::Parser;
+    use std::path::PathBuf;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    name: LOGIN
+    status: implemented
+    requirements:
+      - summary: Lockout
+        name: LOCKOUT
+  - summary: Audit <log>
+    name: AUDIT
+"#;
+
+    fn rows() -> Vec<MatrixRow> {
+        let config = Parser::parse_str(YAML).unwrap();
+        let annotations = vec![
+            Annotation {
+                path: PathBuf::from("src/login.rs"),
+                line: 12,
+                id: "LOGIN".to_string(),
+            },
+            Annotation {
+                path: PathBuf::from("src/lockout.rs"),
+                line: 3,
+                id: "LOCKOUT".to_string(),
+            },
+        ];
+        let trace = TraceMap::build(&config, None, annotations).unwrap();
+        let results = parse_cargo_json(
+            r#"{ "type": "test", "name": "auth::test_login", "event": "ok" }
+{ "type": "test", "name": "auth::test_lockout", "event": "failed" }"#,
+        )
+        .unwrap();
+        let verification = VerificationMap::build(&config, None, results).unwrap();
+        matrix_rows(&config, None, Some(&trace), Some(&verification)).unwrap()
+    }
+
+    #[test]
+    fn test_matrix_rows() {
+        let rows = rows();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].id.as_deref(), Some("LOGIN"));
+        assert_eq!(rows[0].verification, Verification::Passing);
+        assert_eq!(
+            rows[1].cells(),
+            vec![
+                "LOCKOUT",
+                "Lockout",
+                "",
+                "src/lockout.rs:3",
+                "auth::test_lockout (failed)",
+                "failing"
+            ]
+        );
+        assert!(rows[2].code.is_empty());
+        assert_eq!(rows[2].verification, Verification::Untested);
+
+        let config = Parser::parse_str(YAML).unwrap();
+        let bare = matrix_rows(&config, None, None, None).unwrap();
+        assert!(bare
+            .iter()
+            .all(|row| row.code.is_empty() && row.tests.is_empty()));
+    }
+
+    #[test]
+    fn test_matrix_formats() {
+        let rows = rows();
+        let csv = matrix_to_csv(&rows);
+        assert!(csv.starts_with("ID,Summary,Status,Code,Tests,Verification\r\n"));
+        assert!(csv.contains(
+            "LOGIN,Login,implemented,src/login.rs:12,auth::test_login (passed),passing\r\n"
+        ));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&matrix_to_json(&rows).unwrap()).unwrap();
+        assert_eq!(json[0]["code"][0]["line"], 12);
+        assert_eq!(json[1]["verification"], "failing");
+
+        let html = matrix_to_html(&rows, "RTM");
+        assert!(html.contains("<span>3 requirements</span><span>2 with code</span>"));
+        assert!(html.contains("<tr class=\"failing\">"));
+        assert!(html.contains("<td>Audit &lt;log&gt;</td>"));
+    }
+}
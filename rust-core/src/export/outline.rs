@@ -0,0 +1,158 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Hierarchical outline with classic specification numbering
+//!
+//! Requirements are numbered by position among their siblings (1, 1.1,
+//! 1.1.2, ...) in declaration order, exactly as in [`super::to_markdown`],
+//! so the same file always yields the same numbers. References to
+//! requirements defined elsewhere are not numbered again.
+
+use crate::error::Result;
+use crate::metadata::{display_id, MetadataStore};
+use crate::types::{Requirement, RequirementConfig, RequirementReference, Status};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A numbered requirement and the requirements defined inside it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct OutlineEntry {
+    /// Outline number, e.g. `1.2.3`
+    #[schemars(regex(pattern = r"^[0-9]+(\.[0-9]+)*$"))]
+    pub number: String,
+
+    pub summary: String,
+
+    /// Generated ID, or the requirement's name without metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineEntry>,
+}
+
+/// Number the requirements of `config` as an outline, with the IDs of
+/// [`display_id`]
+pub fn outline(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+) -> Result<Vec<OutlineEntry>> {
+    let reqs: Vec<&Requirement> = config.requirements.iter().collect();
+    entries(&reqs, "", store)
+}
+
+fn entries(
+    reqs: &[&Requirement],
+    prefix: &str,
+    store: Option<&MetadataStore>,
+) -> Result<Vec<OutlineEntry>> {
+    reqs.iter()
+        .enumerate()
+        .map(|(i, req)| {
+            let number = format!("{}{}", prefix, i + 1);
+            let id = display_id(store, req)?;
+            let children: Vec<&Requirement> = req
+                .requirements
+                .iter()
+                .filter_map(|child| match child {
+                    RequirementReference::Full(child) => Some(child.as_ref()),
+                    RequirementReference::Reference(_) => None,
+                })
+                .collect();
+            Ok(OutlineEntry {
+                children: entries(&children, &format!("{}.", number), store)?,
+                number,
+                summary: req.summary.clone(),
+                id,
+                status: req.status,
+            })
+        })
+        .collect()
+}
+
+/// Render an outline as a nested Markdown list under a level 1 heading
+pub fn outline_to_markdown(entries: &[OutlineEntry], title: &str) -> String {
+    let mut out = format!("# {}\n\n", title);
+    for entry in entries {
+        write_entry(&mut out, entry, 0);
+    }
+    out
+}
+
+fn write_entry(out: &mut String, entry: &OutlineEntry, depth: usize) {
+    let _ = write!(
+        out,
+        "{}- {} {}",
+        "  ".repeat(depth),
+        entry.number,
+        entry.summary
+    );
+    if let Some(id) = &entry.id {
+        let _ = write!(out, " `{}`", id);
+    }
+    out.push('\n');
+    for child in &entry.children {
+        write_entry(out, child, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    requirements:
+      - summary: Login
+        requirements:
+          - summary: Lockout
+      - Audit log
+      - summary: Logout
+  - summary: Audit log
+    name: AUD
+"#;
+
+    #[test]
+    fn test_outline_numbers_in_declaration_order() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let entries = outline(&config, None).unwrap();
+
+        assert_eq!(entries[0].number, "1");
+        assert_eq!(entries[0].status, Some(Status::Approved));
+        let children: Vec<_> = entries[0]
+            .children
+            .iter()
+            .map(|entry| (entry.number.as_str(), entry.summary.as_str()))
+            .collect();
+        assert_eq!(children, vec![("1.1", "Login"), ("1.2", "Logout")]);
+        assert_eq!(entries[0].children[0].children[0].number, "1.1.1");
+        assert_eq!(entries[1].id.as_deref(), Some("AUD"));
+        assert_eq!(outline(&config, None).unwrap(), entries);
+    }
+
+    #[test]
+    fn test_outline_to_markdown() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let markdown = outline_to_markdown(&outline(&config, None).unwrap(), "Outline");
+
+        assert_eq!(
+            markdown,
+            "# Outline\n\n\
+             - 1 Authentication\n  \
+               - 1.1 Login\n    \
+                 - 1.1.1 Lockout\n  \
+               - 1.2 Logout\n\
+             - 2 Audit log `AUD`\n"
+        );
+    }
+}
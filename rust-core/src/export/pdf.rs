@@ -0,0 +1,357 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Software Requirements Specification as PDF
+//!
+//! Enabled by the `pdf` feature. The document is rendered to HTML from a
+//! [`PdfTemplate`] and then printed by a headless browser, by default
+//! Chromium. Templates use `{{name}}` placeholders; values are HTML-escaped
+//! except for those holding rendered parts, such as `sections`. Attached
+//! images are embedded from the files they refer to.
+
+use super::markdown::outline_numbers;
+pub use super::render_template;
+use crate::error::{Error, Result};
+use crate::metadata::{display_id, kebab_case, write_atomic, MetadataStore};
+use crate::report::{attachments_html, escape};
+use crate::types::{Attachment, Requirement, RequirementConfig, RequirementReference};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The parts an SRS document is assembled from
+#[derive(Debug, Clone)]
+pub struct PdfTemplate {
+    /// Whole page; placeholders `title`, `style`, `title_page`, `revisions`
+    /// and `sections`
+    pub document: String,
+
+    /// Placeholders `title`, `version` and `date`
+    pub title_page: String,
+
+    /// Revision table around `rows`
+    pub revision_table: String,
+
+    /// One revision; placeholders `label`, `date` and `hash`
+    pub revision_row: String,
+
+    /// One requirement; placeholders `level`, `anchor`, `number`,
+    /// `summary`, `id`, `status`, `priority`, `owner`, `description`,
+    /// `acceptance_test`, `attachments` and `children`
+    pub section: String,
+
+    /// CSS, including `@page` rules for paper size and margins
+    pub style: String,
+}
+
+impl Default for PdfTemplate {
+    fn default() -> Self {
+        Self {
+            document: "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{{title}}</title>\n<style>{{style}}</style>\n</head>\n<body>\n{{title_page}}\n{{revisions}}\n<main>\n{{sections}}</main>\n</body>\n</html>\n".to_string(),
+            title_page: "<section class=\"title-page\">\n<h1>{{title}}</h1>\n<p class=\"subtitle\">Software Requirements Specification</p>\n<p>Version {{version}}</p>\n<p>{{date}}</p>\n</section>\n".to_string(),
+            revision_table: "<section class=\"revisions\">\n<h2>Revision history</h2>\n<table>\n<tr><th>Baseline</th><th>Date</th><th>Hash</th></tr>\n{{rows}}</table>\n</section>\n".to_string(),
+            revision_row: "<tr><td>{{label}}</td><td>{{date}}</td><td><code>{{hash}}</code></td></tr>\n".to_string(),
+            section: "<section id=\"{{anchor}}\">\n<h{{level}}>{{number}} {{summary}}</h{{level}}>\n<p class=\"meta\">{{id}} {{status}} {{priority}} {{owner}}</p>\n<p>{{description}}</p>\n<p class=\"test\">{{acceptance_test}}</p>\n{{attachments}}\n{{children}}</section>\n".to_string(),
+            style: "@page { size: A4; margin: 2cm; }\nbody { font-family: serif; font-size: 11pt; }\n.title-page { page-break-after: always; text-align: center; padding-top: 30%; }\n.revisions { page-break-after: always; }\ntable { border-collapse: collapse; width: 100%; }\nth, td { border: 1px solid #999; padding: 4px; text-align: left; }\n.meta { color: #555; font-size: 9pt; }\np:empty { display: none; }\nfigure img { max-width: 100%; }\n".to_string(),
+        }
+    }
+}
+
+/// Options for [`to_srs_html`] and [`write_pdf`]
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    /// Document title
+    pub title: String,
+
+    /// Document version shown on the title page
+    pub version: String,
+
+    pub template: PdfTemplate,
+
+    /// Command printing HTML to PDF, with `{input}` and `{output}`
+    /// replaced by file paths
+    pub converter: Vec<String>,
+
+    /// Directory attachment paths are relative to, usually the one holding
+    /// `.rqm`; attached files are only embedded when set
+    pub attachment_root: Option<PathBuf>,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            title: "Requirements".to_string(),
+            version: "1.0".to_string(),
+            template: PdfTemplate::default(),
+            converter: [
+                "chromium",
+                "--headless",
+                "--disable-gpu",
+                "--no-pdf-header-footer",
+                "--print-to-pdf={output}",
+                "{input}",
+            ]
+            .map(String::from)
+            .to_vec(),
+            attachment_root: None,
+        }
+    }
+}
+
+/// Render the SRS document as HTML, ready for printing
+///
+/// Baselines in `store` make up the revision table.
+pub fn to_srs_html(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    options: &PdfOptions,
+) -> Result<String> {
+    let template = &options.template;
+    let date = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    let title_page = render_template(
+        &template.title_page,
+        &HashMap::from([
+            ("title", escape(&options.title)),
+            ("version", escape(&options.version)),
+            ("date", date),
+        ]),
+    );
+
+    let mut rows = String::new();
+    for snapshot in store
+        .map(MetadataStore::baselines)
+        .transpose()?
+        .unwrap_or_default()
+    {
+        rows.push_str(&render_template(
+            &template.revision_row,
+            &HashMap::from([
+                ("label", escape(&snapshot.label)),
+                ("date", snapshot.created_at.format("%Y-%m-%d").to_string()),
+                ("hash", snapshot.hash.chars().take(12).collect()),
+            ]),
+        ));
+    }
+    let revisions = if rows.is_empty() {
+        String::new()
+    } else {
+        render_template(&template.revision_table, &HashMap::from([("rows", rows)]))
+    };
+
+    let numbers = outline_numbers(config);
+    let mut sections = String::new();
+    for req in &config.requirements {
+        sections.push_str(&section(req, 2, &numbers, store, options)?);
+    }
+
+    Ok(render_template(
+        &template.document,
+        &HashMap::from([
+            ("title", escape(&options.title)),
+            ("style", template.style.clone()),
+            ("title_page", title_page),
+            ("revisions", revisions),
+            ("sections", sections),
+        ]),
+    ))
+}
+
+fn section(
+    req: &Requirement,
+    level: usize,
+    numbers: &HashMap<String, String>,
+    store: Option<&MetadataStore>,
+    options: &PdfOptions,
+) -> Result<String> {
+    let template = &options.template;
+    let id = display_id(store, req)?;
+
+    let mut children = String::new();
+    for child in &req.requirements {
+        if let RequirementReference::Full(child) = child {
+            children.push_str(&section(child, level + 1, numbers, store, options)?);
+        }
+    }
+
+    let text = |value: Option<&str>| value.map(escape).unwrap_or_default();
+    // The browser printing the document loads attached files by absolute URL
+    let href = |attachment: &Attachment| {
+        let file = attachment.resolve(options.attachment_root.as_ref()?)?;
+        Some(format!("file://{}", file.display()))
+    };
+    Ok(render_template(
+        &template.section,
+        &HashMap::from([
+            ("level", level.min(6).to_string()),
+            ("anchor", format!("req-{}", kebab_case(&req.summary))),
+            (
+                "number",
+                numbers.get(&req.summary).cloned().unwrap_or_default(),
+            ),
+            ("summary", escape(&req.summary)),
+            ("id", text(id.as_deref())),
+            (
+                "status",
+                req.status.map(|s| s.to_string()).unwrap_or_default(),
+            ),
+            (
+                "priority",
+                req.priority.map(|p| p.to_string()).unwrap_or_default(),
+            ),
+            ("owner", text(req.owner.as_ref().map(|o| o.as_str()))),
+            ("description", text(req.description.as_deref())),
+            ("acceptance_test", text(req.acceptance_test.as_deref())),
+            (
+                "attachments",
+                attachments_html(req, href, true).unwrap_or_default(),
+            ),
+            ("children", children),
+        ]),
+    ))
+}
+
+/// Render the SRS document and print it to a PDF at `output`
+///
+/// The converter reads the HTML from, and prints to, a temporary
+/// directory; `output` is only replaced once it exited successfully and
+/// wrote a PDF that is newer than the run, so a failed run can neither
+/// leave files behind nor pass off an old PDF as the new one.
+pub fn write_pdf(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    options: &PdfOptions,
+    output: &Path,
+) -> Result<()> {
+    let (program, args) = options
+        .converter
+        .split_first()
+        .ok_or_else(|| Error::custom("No PDF converter configured"))?;
+
+    let temp = tempfile::tempdir()?;
+    let input = temp.path().join("srs.html");
+    let printed = temp.path().join("srs.pdf");
+    fs::write(&input, to_srs_html(config, store, options)?)?;
+    let started = fs::metadata(&input)?.modified()?;
+    let paths = |arg: &String| {
+        arg.replace("{input}", &input.to_string_lossy())
+            .replace("{output}", &printed.to_string_lossy())
+    };
+    let status = Command::new(paths(program))
+        .args(args.iter().map(paths))
+        .status()
+        .map_err(|e| Error::custom(format!("Could not run PDF converter '{}': {}", program, e)))?;
+    if !status.success() {
+        return Err(Error::custom(format!(
+            "PDF converter '{}' failed ({})",
+            program, status
+        )));
+    }
+    let written = fs::metadata(&printed).and_then(|meta| meta.modified());
+    if !written.is_ok_and(|written| written >= started) {
+        return Err(Error::custom(format!(
+            "PDF converter '{}' exited without writing a PDF",
+            program
+        )));
+    }
+
+    write_atomic(output, &fs::read(&printed)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    description: Users prove <who> they are
+    requirements:
+      - summary: Login
+        acceptance_test: SSO works
+"#;
+
+    #[test]
+    fn test_srs_html_has_title_page_revisions_and_sections() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "SRS".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        store.create_baseline("Release 1.0", &config).unwrap();
+
+        let options = PdfOptions {
+            title: "Payments".to_string(),
+            version: "2.1".to_string(),
+            ..PdfOptions::default()
+        };
+        let html = to_srs_html(&config, Some(&store), &options).unwrap();
+        assert!(html.contains("<h1>Payments</h1>"));
+        assert!(html.contains("<p>Version 2.1</p>"));
+        assert!(html.contains("<tr><td>Release 1.0</td>"));
+        assert!(html.contains("<h2>1 Authentication</h2>"));
+        assert!(html.contains("<h3>1.1 Login</h3>"));
+        assert!(html.contains("SRS-001 approved"));
+        assert!(html.contains("Users prove &lt;who&gt; they are"));
+        assert!(!html.contains("{{"));
+    }
+
+    #[test]
+    fn test_srs_html_embeds_attached_images() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("flow.svg"), "<svg/>").unwrap();
+        let mut config = Parser::parse_str(YAML).unwrap();
+        config.requirements[0].attachments = vec![
+            Attachment::new("flow.svg"),
+            Attachment::new("https://example.com/flow.svg"),
+        ];
+        let options = PdfOptions {
+            attachment_root: Some(temp.path().to_path_buf()),
+            ..PdfOptions::default()
+        };
+
+        let html = to_srs_html(&config, None, &options).unwrap();
+        let file = temp.path().join("flow.svg").canonicalize().unwrap();
+        assert!(html.contains(&format!("<img src=\"file://{}\"", file.display())));
+        assert!(html.contains("<a href=\"https://example.com/flow.svg\">"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_pdf_runs_converter() {
+        let temp = TempDir::new().unwrap();
+        let output = temp.path().join("srs.pdf");
+        let config = Parser::parse_str(YAML).unwrap();
+        let mut options = PdfOptions {
+            converter: ["cp", "{input}", "{output}"].map(String::from).to_vec(),
+            ..PdfOptions::default()
+        };
+        write_pdf(&config, None, &options, &output).unwrap();
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains("Authentication"));
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+
+        // A failing converter leaves the last PDF as it was
+        options.converter = vec!["false".to_string()];
+        let error = write_pdf(&config, None, &options, &output)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("failed"));
+        assert!(fs::read_to_string(&output)
+            .unwrap()
+            .contains("Authentication"));
+
+        // So does one that exits cleanly without printing anything
+        options.converter = vec!["true".to_string()];
+        let error = write_pdf(&config, None, &options, &temp.path().join("other.pdf"))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("without writing a PDF"));
+        assert!(!temp.path().join("other.pdf").exists());
+    }
+}
@@ -0,0 +1,702 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Static documentation website
+//!
+//! [`build_site`] renders requirements as plain HTML files that can be
+//! served from any static host such as GitHub Pages:
+//!
+//! - `index.html` with the requirement hierarchy
+//! - `requirements/<id>.html` for every requirement, linking its parents,
+//!   children, owner and tags
+//! - `tags.html`, `owners.html` and `status.html` indexes
+//! - `graph.html` with the requirement graph as an inline SVG
+//! - `search.html` with a client-side search over an inlined index
+//! - `attachments/` with the attached files, images shown on the pages of
+//!   their requirements
+//!
+//! Pages link to each other with relative URLs and load nothing from
+//! elsewhere, so the site also works when opened from disk.
+
+use crate::error::Result;
+use crate::graph::RequirementGraph;
+use crate::metadata::{display_id, kebab_case, MetadataStore};
+use crate::report::{attachments_html, escape, external_links, web_link};
+use crate::types::{Attachment, Requirement, RequirementConfig, RequirementReference, Status};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Options for [`build_site`]
+#[derive(Debug, Clone)]
+pub struct SiteOptions {
+    /// Site name, shown on every page
+    pub title: String,
+
+    /// Directory attachment paths are relative to, usually the one holding
+    /// `.rqm`; attached files are only copied into the site when set
+    pub attachment_root: Option<PathBuf>,
+}
+
+impl Default for SiteOptions {
+    fn default() -> Self {
+        Self {
+            title: "Requirements".to_string(),
+            attachment_root: None,
+        }
+    }
+}
+
+/// The files of a generated site, by path relative to its root
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Site {
+    pub files: BTreeMap<PathBuf, String>,
+
+    /// Attached files to copy into the site, by path relative to its root
+    pub assets: BTreeMap<PathBuf, PathBuf>,
+}
+
+impl Site {
+    /// Write the site below `dir`, returning the paths written
+    ///
+    /// Other files in `dir` are left alone.
+    pub fn write<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>> {
+        let mut written = vec![];
+        for (path, content) in &self.files {
+            let path = dir.as_ref().join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, content)?;
+            written.push(path);
+        }
+        for (path, source) in &self.assets {
+            let path = dir.as_ref().join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(source, &path)?;
+            written.push(path);
+        }
+        Ok(written)
+    }
+}
+
+const STYLE: &str = r#"body { font-family: system-ui, sans-serif; margin: 0; color: #222; }
+nav { background: #24292f; padding: .75rem 2rem; }
+nav a { color: white; margin-right: 1.25rem; text-decoration: none; }
+nav a.site { font-weight: bold; }
+main { margin: 2rem; max-width: 60rem; }
+ul.tree { list-style: none; padding-left: 1.25rem; }
+.id { font-family: monospace; color: #555; margin-right: .5rem; }
+.badge { font-size: .75rem; padding: 0 .4rem; border-radius: .5rem; margin-left: .3rem; background: #eee; }
+.status-draft { background: lightgray; }
+.status-proposed { background: lightyellow; }
+.status-approved { background: lightblue; }
+.status-implemented { background: palegreen; }
+.status-verified { background: green; color: white; }
+.status-deprecated { background: gray; color: white; }
+dt { font-weight: bold; margin-top: .75rem; }
+dd { margin-left: 0; white-space: pre-wrap; }
+#query { width: 100%; font-size: 1rem; padding: .4rem; }
+svg a:hover rect { fill: #dbeafe; }
+figure img { max-width: 100%; }
+"#;
+
+const SEARCH_SCRIPT: &str = r#"
+const input = document.getElementById('query');
+const results = document.getElementById('results');
+function search() {
+  const terms = input.value.toLowerCase().split(/\s+/).filter((t) => t);
+  results.innerHTML = '';
+  if (!terms.length) return;
+  for (const entry of INDEX) {
+    if (!terms.every((t) => entry.text.includes(t))) continue;
+    const li = document.createElement('li');
+    const a = document.createElement('a');
+    a.href = entry.url;
+    a.textContent = entry.id ? entry.id + ' ' + entry.summary : entry.summary;
+    li.appendChild(a);
+    results.appendChild(li);
+  }
+}
+input.addEventListener('input', search);
+search();
+"#;
+
+/// Directory of the site that attached files are copied to
+const ATTACHMENT_DIR: &str = "attachments";
+
+/// Size of a node in the graph page, and the gaps between nodes
+const NODE_WIDTH: usize = 180;
+const NODE_HEIGHT: usize = 40;
+const GAP: usize = 20;
+const LAYER_GAP: usize = 50;
+
+/// Render requirements as a static website
+///
+/// Fails if the requirements contain a cycle.
+pub fn build_site(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    options: &SiteOptions,
+) -> Result<Site> {
+    let graph = RequirementGraph::from_config(config)?;
+    let all = config.all_requirements();
+
+    let mut ids = HashMap::new();
+    let mut slugs = HashMap::new();
+    let mut taken = HashSet::new();
+    for req in &all {
+        let id = display_id(store, req)?;
+        let base = match kebab_case(id.as_deref().unwrap_or(&req.summary)) {
+            slug if slug.is_empty() => "requirement".to_string(),
+            slug => slug,
+        };
+        let mut slug = base.clone();
+        let mut n = 2;
+        while !taken.insert(slug.clone()) {
+            slug = format!("{}-{}", base, n);
+            n += 1;
+        }
+        slugs.insert(req.summary.as_str(), slug);
+        if let Some(id) = id {
+            ids.insert(req.summary.as_str(), id);
+        }
+    }
+    // Missing attachments are reported by validation, not here
+    let mut assets = BTreeMap::new();
+    if let Some(root) = &options.attachment_root {
+        for attachment in all.iter().flat_map(|req| &req.attachments) {
+            let relative = attachment.relative_path();
+            if let Some((relative, source)) = relative.zip(attachment.resolve(root)) {
+                if source.is_file() {
+                    assets.insert(Path::new(ATTACHMENT_DIR).join(relative), source);
+                }
+            }
+        }
+    }
+    let site = SiteWriter {
+        title: &options.title,
+        ids,
+        slugs,
+        assets: &assets,
+        external_systems: store
+            .map(|store| store.project_config().external_systems.clone())
+            .unwrap_or_default(),
+    };
+
+    let mut files = BTreeMap::new();
+    files.insert(PathBuf::from("style.css"), STYLE.to_string());
+    // Keeps GitHub Pages from running the site through Jekyll
+    files.insert(PathBuf::from(".nojekyll"), String::new());
+
+    let mut tree = format!("<p>{} requirements</p>\n<ul class=\"tree\">\n", all.len());
+    for req in &config.requirements {
+        site.tree(&mut tree, req);
+    }
+    tree.push_str("</ul>\n");
+    files.insert(
+        PathBuf::from("index.html"),
+        site.page(&options.title, "", &tree),
+    );
+
+    for req in &all {
+        let body = site.requirement(req, &graph)?;
+        files.insert(
+            PathBuf::from("requirements")
+                .join(format!("{}.html", site.slugs[req.summary.as_str()])),
+            site.page(&req.summary, "../", &body),
+        );
+    }
+
+    let mut tags: BTreeMap<String, Vec<&Requirement>> = BTreeMap::new();
+    let mut owners: BTreeMap<String, Vec<&Requirement>> = BTreeMap::new();
+    let mut statuses: BTreeMap<String, Vec<&Requirement>> = BTreeMap::new();
+    for req in &all {
+        for tag in &req.tags {
+            tags.entry(tag.clone()).or_default().push(req);
+        }
+        let owner = req.owner.as_ref().map(|owner| owner.as_str());
+        owners
+            .entry(owner.unwrap_or("(unassigned)").to_string())
+            .or_default()
+            .push(req);
+        let status = req.status.map(Status::as_str);
+        statuses
+            .entry(status.unwrap_or("(none)").to_string())
+            .or_default()
+            .push(req);
+    }
+    for (file, title, groups) in [
+        ("tags.html", "Tags", &tags),
+        ("owners.html", "Owners", &owners),
+        ("status.html", "Status", &statuses),
+    ] {
+        files.insert(
+            PathBuf::from(file),
+            site.page(title, "", &site.index(title, groups)),
+        );
+    }
+
+    files.insert(
+        PathBuf::from("graph.html"),
+        site.page("Graph", "", &site.graph(&graph)?),
+    );
+    files.insert(
+        PathBuf::from("search.html"),
+        site.page("Search", "", &site.search(&all)),
+    );
+
+    Ok(Site { files, assets })
+}
+
+struct SiteWriter<'a> {
+    title: &'a str,
+    ids: HashMap<&'a str, String>,
+    slugs: HashMap<&'a str, String>,
+    /// URL templates of external systems, by name
+    external_systems: BTreeMap<String, String>,
+    assets: &'a BTreeMap<PathBuf, PathBuf>,
+}
+
+impl SiteWriter<'_> {
+    /// A complete page; `root` leads from the page back to the site root
+    fn page(&self, title: &str, root: &str, body: &str) -> String {
+        let mut out = String::new();
+        let _ = write!(
+            out,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{} - {}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n<body>\n<nav>",
+            escape(title),
+            escape(self.title),
+            root
+        );
+        let _ = write!(
+            out,
+            "<a class=\"site\" href=\"{}index.html\">{}</a>",
+            root,
+            escape(self.title)
+        );
+        for (file, label) in [
+            ("tags.html", "Tags"),
+            ("owners.html", "Owners"),
+            ("status.html", "Status"),
+            ("graph.html", "Graph"),
+            ("search.html", "Search"),
+        ] {
+            let _ = write!(out, "<a href=\"{}{}\">{}</a>", root, file, label);
+        }
+        let _ = write!(out, "</nav>\n<main>\n{}</main>\n</body>\n</html>\n", body);
+        out
+    }
+
+    /// Link to a requirement's page
+    fn link(&self, root: &str, summary: &str) -> String {
+        let Some(slug) = self.slugs.get(summary) else {
+            return escape(summary);
+        };
+        let id = match self.ids.get(summary) {
+            Some(id) => format!("<span class=\"id\">{}</span>", escape(id)),
+            None => String::new(),
+        };
+        format!(
+            "<a href=\"{}requirements/{}.html\">{}{}</a>",
+            root,
+            slug,
+            id,
+            escape(summary)
+        )
+    }
+
+    fn tree(&self, out: &mut String, req: &Requirement) {
+        let _ = write!(out, "<li>{}", self.link("", &req.summary));
+        if let Some(status) = req.status.map(Status::as_str) {
+            let _ = write!(out, "<span class=\"badge status-{0}\">{0}</span>", status);
+        }
+        if !req.requirements.is_empty() {
+            out.push_str("\n<ul class=\"tree\">\n");
+            for child in &req.requirements {
+                match child {
+                    RequirementReference::Full(child) => self.tree(out, child),
+                    RequirementReference::Reference(summary) => {
+                        let _ = writeln!(out, "<li>&rarr; {}</li>", self.link("", summary));
+                    }
+                }
+            }
+            out.push_str("</ul>\n");
+        }
+        out.push_str("</li>\n");
+    }
+
+    fn requirement(&self, req: &Requirement, graph: &RequirementGraph) -> Result<String> {
+        let mut out = String::from("<h1>");
+        if let Some(id) = self.ids.get(req.summary.as_str()) {
+            let _ = write!(out, "<span class=\"id\">{}</span>", escape(id));
+        }
+        out.push_str(&escape(&req.summary));
+        if let Some(status) = req.status.map(Status::as_str) {
+            let _ = write!(
+                out,
+                "<a class=\"badge status-{0}\" href=\"../status.html#{0}\">{0}</a>",
+                status
+            );
+        }
+        if let Some(priority) = req.priority {
+            let _ = write!(out, "<span class=\"badge\">{}</span>", priority);
+        }
+        for tag in &req.tags {
+            let _ = write!(
+                out,
+                "<a class=\"badge\" href=\"../tags.html#{}\">#{}</a>",
+                kebab_case(tag),
+                escape(tag)
+            );
+        }
+        out.push_str("</h1>\n<dl>\n");
+
+        let owner = req.owner.as_ref().map(|owner| {
+            format!(
+                "<a href=\"../owners.html#{}\">{}</a>",
+                kebab_case(owner.as_str()),
+                escape(owner.as_str())
+            )
+        });
+        let acceptance_test_link = req
+            .acceptance_test_link
+            .as_ref()
+            .map(|url| web_link(url, url));
+        let superseded_by = req
+            .superseded_by
+            .as_ref()
+            .map(|summary| self.link("../", summary));
+        let fields = [
+            ("Owner", owner),
+            ("Description", req.description.as_deref().map(escape)),
+            ("Justification", req.justification.as_deref().map(escape)),
+            (
+                "Acceptance test",
+                req.acceptance_test.as_deref().map(escape),
+            ),
+            ("Acceptance test link", acceptance_test_link),
+            (
+                "External references",
+                external_links(req, &self.external_systems),
+            ),
+            ("Target release", req.target_release.as_deref().map(escape)),
+            ("Milestone", req.milestone.as_deref().map(escape)),
+            (
+                "Estimate",
+                req.estimate.map(|estimate| estimate.to_string()),
+            ),
+            ("Superseded by", superseded_by),
+        ];
+        for (label, value) in fields {
+            if let Some(value) = value {
+                let _ = writeln!(out, "<dt>{}</dt>\n<dd>{}</dd>", label, value);
+            }
+        }
+        out.push_str("</dl>\n");
+
+        if !req.further_information.is_empty() {
+            out.push_str("<h2>Further information</h2>\n<ul>\n");
+            for info in &req.further_information {
+                let _ = writeln!(out, "<li>{}</li>", escape(info));
+            }
+            out.push_str("</ul>\n");
+        }
+        let href = |attachment: &Attachment| {
+            let path = Path::new(ATTACHMENT_DIR).join(attachment.relative_path()?);
+            self.assets.contains_key(&path).then(|| {
+                let parts: Vec<_> = path.iter().map(|part| part.to_string_lossy()).collect();
+                format!("../{}", parts.join("/"))
+            })
+        };
+        if let Some(attachments) = attachments_html(req, href, true) {
+            let _ = writeln!(out, "<h2>Attachments</h2>\n{}", attachments);
+        }
+        for (heading, related) in [
+            ("Parents", graph.dependents(&req.summary)?),
+            ("Children", graph.dependencies(&req.summary)?),
+        ] {
+            if related.is_empty() {
+                continue;
+            }
+            let _ = writeln!(out, "<h2>{}</h2>\n<ul>", heading);
+            for other in related {
+                let _ = writeln!(out, "<li>{}</li>", self.link("../", &other.summary));
+            }
+            out.push_str("</ul>\n");
+        }
+        Ok(out)
+    }
+
+    /// Requirements grouped by a value, one section per value
+    fn index(&self, title: &str, groups: &BTreeMap<String, Vec<&Requirement>>) -> String {
+        let mut out = format!("<h1>{}</h1>\n", title);
+        if groups.is_empty() {
+            out.push_str("<p>None</p>\n");
+        }
+        for (value, reqs) in groups {
+            let _ = writeln!(
+                out,
+                "<h2 id=\"{}\">{} <span class=\"badge\">{}</span></h2>\n<ul>",
+                kebab_case(value),
+                escape(value),
+                reqs.len()
+            );
+            for req in reqs {
+                let _ = writeln!(out, "<li>{}</li>", self.link("", &req.summary));
+            }
+            out.push_str("</ul>\n");
+        }
+        out
+    }
+
+    /// The graph as an SVG, parents above their children
+    ///
+    /// Each requirement sits one layer below its lowest parent.
+    fn graph(&self, graph: &RequirementGraph) -> Result<String> {
+        let order = graph.topological_sort()?;
+        let mut layers: HashMap<&str, usize> = HashMap::new();
+        for req in &order {
+            let layer = layers.get(req.summary.as_str()).copied().unwrap_or(0);
+            for child in graph.dependencies(&req.summary)? {
+                let child_layer = layers.entry(child.summary.as_str()).or_insert(0);
+                *child_layer = (*child_layer).max(layer + 1);
+            }
+            layers.entry(req.summary.as_str()).or_insert(0);
+        }
+
+        let mut positions: HashMap<&str, (usize, usize)> = HashMap::new();
+        let mut counts: Vec<usize> = vec![];
+        for req in &order {
+            let layer = layers[req.summary.as_str()];
+            if counts.len() <= layer {
+                counts.resize(layer + 1, 0);
+            }
+            let x = GAP + counts[layer] * (NODE_WIDTH + GAP);
+            let y = GAP + layer * (NODE_HEIGHT + LAYER_GAP);
+            counts[layer] += 1;
+            positions.insert(req.summary.as_str(), (x, y));
+        }
+        let width = GAP + counts.iter().max().copied().unwrap_or(0) * (NODE_WIDTH + GAP);
+        let height = GAP + counts.len() * (NODE_HEIGHT + LAYER_GAP);
+
+        let mut out = String::from("<h1>Graph</h1>\n");
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-size=\"12\">",
+            width, height
+        );
+        for req in &order {
+            let (x, y) = positions[req.summary.as_str()];
+            for child in graph.dependencies(&req.summary)? {
+                let (cx, cy) = positions[child.summary.as_str()];
+                let _ = writeln!(
+                    out,
+                    "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"#888\"/>",
+                    x + NODE_WIDTH / 2,
+                    y + NODE_HEIGHT,
+                    cx + NODE_WIDTH / 2,
+                    cy
+                );
+            }
+        }
+        for req in &order {
+            let (x, y) = positions[req.summary.as_str()];
+            let label = match self.ids.get(req.summary.as_str()) {
+                Some(id) => format!("{} {}", id, req.summary),
+                None => req.summary.clone(),
+            };
+            let short: String = if label.chars().count() > 26 {
+                label.chars().take(25).chain(['…']).collect()
+            } else {
+                label.clone()
+            };
+            let fill = match req.status {
+                Some(Status::Approved) => "lightblue",
+                Some(Status::Implemented) => "palegreen",
+                Some(Status::Verified) => "#9be29b",
+                Some(Status::Deprecated) => "#ccc",
+                _ => "white",
+            };
+            let _ = writeln!(
+                out,
+                "<a href=\"requirements/{}.html\"><title>{}</title><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"6\" fill=\"{}\" stroke=\"#555\"/><text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text></a>",
+                self.slugs[req.summary.as_str()],
+                escape(&label),
+                x,
+                y,
+                NODE_WIDTH,
+                NODE_HEIGHT,
+                fill,
+                x + NODE_WIDTH / 2,
+                y + NODE_HEIGHT / 2 + 4,
+                escape(&short)
+            );
+        }
+        out.push_str("</svg>\n");
+        Ok(out)
+    }
+
+    /// Search page with the index inlined, so it works without a server
+    fn search(&self, all: &[&Requirement]) -> String {
+        let index: Vec<_> = all
+            .iter()
+            .map(|req| {
+                let id = self.ids.get(req.summary.as_str());
+                let text = [
+                    Some(req.summary.as_str()),
+                    id.map(String::as_str),
+                    req.description.as_deref(),
+                    req.acceptance_test.as_deref(),
+                    req.owner.as_ref().map(|owner| owner.as_str()),
+                ]
+                .into_iter()
+                .flatten()
+                .chain(req.tags.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ")
+                .to_lowercase();
+                json!({
+                    "id": id,
+                    "summary": req.summary,
+                    "url": format!("requirements/{}.html", self.slugs[req.summary.as_str()]),
+                    "text": text,
+                })
+            })
+            .collect();
+        // `</` would end the script element early
+        let index = json!(index).to_string().replace("</", "<\\/");
+        format!(
+            "<h1>Search</h1>\n<input id=\"query\" type=\"search\" placeholder=\"Search requirements\" autofocus>\n<ul id=\"results\"></ul>\n<script>\nconst INDEX = {};\n{}</script>\n",
+            index, SEARCH_SCRIPT
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login <SSO>
+    status: approved
+    owner: "@alice"
+    tags: [auth]
+    requirements:
+      - summary: Lockout
+        status: draft
+        tags: [auth]
+      - Export
+  - summary: Export
+    name: EXP
+    description: "</script> is escaped"
+    acceptance_test_link: "javascript://alert(1)"
+"#;
+
+    #[test]
+    fn test_site_pages_link_each_other() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let site = build_site(&config, None, &SiteOptions::default()).unwrap();
+        let page = |path: &str| site.files[Path::new(path)].as_str();
+
+        assert!(page("index.html")
+            .contains("<a href=\"requirements/login-sso.html\">Login &lt;SSO&gt;</a>"));
+        let login = page("requirements/login-sso.html");
+        assert!(login.contains("<link rel=\"stylesheet\" href=\"../style.css\">"));
+        assert!(login.contains("<a href=\"../owners.html#alice\">@alice</a>"));
+        assert!(login.contains(
+            "<li><a href=\"../requirements/exp.html\"><span class=\"id\">EXP</span>Export</a></li>"
+        ));
+        let export = page("requirements/exp.html");
+        assert!(export.contains("<h2>Parents</h2>"));
+        assert!(export.contains("requirements/login-sso.html"));
+        // Only http(s) URLs are linked
+        assert!(export.contains("<dd>javascript://alert(1)</dd>"));
+
+        assert!(
+            page("tags.html").contains("<h2 id=\"auth\">auth <span class=\"badge\">2</span></h2>")
+        );
+        assert!(page("status.html").contains("<h2 id=\"none\">(none)"));
+        assert!(page("owners.html").contains("(unassigned)"));
+        assert_eq!(page("graph.html").matches("<rect").count(), 3);
+        assert_eq!(page("graph.html").matches("<line").count(), 2);
+        let search = page("search.html");
+        assert!(search.contains("<\\/script> is escaped"));
+        assert!(!search.contains("</script> is escaped"));
+
+        // Nothing is loaded from elsewhere
+        for content in site.files.values() {
+            assert!(!content.contains("src="));
+        }
+    }
+
+    #[test]
+    fn test_site_uses_generated_ids_and_writes_files() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "WEB".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        store
+            .get_or_create_metadata(config.all_requirements()[0])
+            .unwrap();
+
+        let site = build_site(&config, Some(&store), &SiteOptions::default()).unwrap();
+        assert!(site
+            .files
+            .contains_key(Path::new("requirements/web-001.html")));
+
+        let out = temp.path().join("_site");
+        let written = site.write(&out).unwrap();
+        assert_eq!(written.len(), site.files.len());
+        assert!(out.join(".nojekyll").exists());
+        assert!(out.join("requirements/web-001.html").exists());
+        assert_eq!(store.all_metadata().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_site_copies_attachments() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("docs")).unwrap();
+        fs::write(temp.path().join("docs/flow.png"), "PNG").unwrap();
+        let mut config = Parser::parse_str(YAML).unwrap();
+        config.requirements[0].attachments = vec![
+            Attachment {
+                caption: Some("Login flow".to_string()),
+                ..Attachment::new("docs/flow.png")
+            },
+            Attachment::new("docs/missing.pdf"),
+            Attachment::new("https://example.com/mockup.png"),
+        ];
+        let options = SiteOptions {
+            attachment_root: Some(temp.path().to_path_buf()),
+            ..SiteOptions::default()
+        };
+
+        let site = build_site(&config, None, &options).unwrap();
+        let page = site
+            .files
+            .values()
+            .find(|page| page.contains("Login flow"))
+            .unwrap();
+        assert!(
+            page.contains("<figure><img src=\"../attachments/docs/flow.png\" alt=\"Login flow\">")
+        );
+        assert!(page.contains("<li>docs/missing.pdf</li>"));
+        assert!(page.contains("<a href=\"https://example.com/mockup.png\">"));
+
+        let out = temp.path().join("_site");
+        site.write(&out).unwrap();
+        assert_eq!(
+            fs::read_to_string(out.join("attachments/docs/flow.png")).unwrap(),
+            "PNG"
+        );
+    }
+}
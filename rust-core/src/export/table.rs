@@ -0,0 +1,262 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Flat tabular export for spreadsheets
+//!
+//! Every requirement defined in place becomes one row, with the summaries
+//! of its ancestors as its parent path. CSV is always available; Excel
+//! workbooks need the `xlsx` feature.
+
+use crate::error::Result;
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use serde::Serialize;
+
+/// Column headings, in the order of [`TableRow::cells`]
+pub const COLUMNS: &[&str] = &[
+    "ID",
+    "UUID",
+    "Name",
+    "Parent path",
+    "Summary",
+    "Owner",
+    "Status",
+    "Priority",
+    "Tags",
+    "Target release",
+];
+
+/// One requirement as a spreadsheet row
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct TableRow {
+    /// Generated ID, if the requirement has metadata
+    pub id: Option<String>,
+    pub uuid: Option<String>,
+    pub name: Option<String>,
+
+    /// Summaries of the ancestors, joined with " > "
+    pub parent_path: String,
+    pub summary: String,
+    pub owner: Option<String>,
+    pub status: Option<String>,
+    pub priority: Option<String>,
+    pub tags: Vec<String>,
+    pub target_release: Option<String>,
+}
+
+impl TableRow {
+    /// The row's values, in the order of [`COLUMNS`]
+    pub fn cells(&self) -> Vec<String> {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        vec![
+            text(&self.id),
+            text(&self.uuid),
+            text(&self.name),
+            self.parent_path.clone(),
+            self.summary.clone(),
+            text(&self.owner),
+            text(&self.status),
+            text(&self.priority),
+            self.tags.join(", "),
+            text(&self.target_release),
+        ]
+    }
+}
+
+/// One row per requirement, in document order
+pub fn rows(config: &RequirementConfig, store: Option<&MetadataStore>) -> Result<Vec<TableRow>> {
+    let mut rows = vec![];
+    for req in &config.requirements {
+        collect(req, &mut vec![], store, &mut rows)?;
+    }
+    Ok(rows)
+}
+
+fn collect<'a>(
+    req: &'a Requirement,
+    path: &mut Vec<&'a str>,
+    store: Option<&MetadataStore>,
+    rows: &mut Vec<TableRow>,
+) -> Result<()> {
+    let meta = match store {
+        Some(store) => store.find_metadata(&req.summary)?,
+        None => None,
+    };
+    rows.push(TableRow {
+        id: meta.as_ref().map(|meta| meta.generated_id.clone()),
+        uuid: meta.as_ref().map(|meta| meta.uuid.to_string()),
+        name: req.name.clone(),
+        parent_path: path.join(" > "),
+        summary: req.summary.clone(),
+        owner: req.owner.as_ref().map(|owner| owner.as_str().to_string()),
+        status: req.status.map(|s| s.to_string()),
+        priority: req.priority.map(|p| p.to_string()),
+        tags: req.tags.clone(),
+        target_release: req.target_release.clone(),
+    });
+
+    path.push(&req.summary);
+    for child in &req.requirements {
+        if let RequirementReference::Full(child) = child {
+            collect(child, path, store, rows)?;
+        }
+    }
+    path.pop();
+    Ok(())
+}
+
+/// Render requirements as CSV with a header row
+pub fn to_csv(config: &RequirementConfig, store: Option<&MetadataStore>) -> Result<String> {
+    let mut out = csv_line(COLUMNS.iter().map(|column| column.to_string()));
+    for row in rows(config, store)? {
+        out.push_str(&csv_line(row.cells()));
+    }
+    Ok(out)
+}
+
+/// One CSV record, quoting cells where needed
+///
+/// Spreadsheets run cells starting with `=`, `+`, `-` or `@` as formulas,
+/// so those, and cells starting with a tab or carriage return, are
+/// prefixed with `'` to be shown as text.
+pub(super) fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let cells: Vec<String> = cells
+        .into_iter()
+        .map(|cell| {
+            if cell.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+                format!("'{}", cell)
+            } else {
+                cell
+            }
+        })
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell
+            }
+        })
+        .collect();
+    format!("{}\r\n", cells.join(","))
+}
+
+/// Render requirements as an Excel workbook with a header row
+#[cfg(feature = "xlsx")]
+pub fn to_xlsx(config: &RequirementConfig, store: Option<&MetadataStore>) -> Result<Vec<u8>> {
+    use crate::error::Error;
+    use rust_xlsxwriter::{Format, Workbook};
+
+    let xlsx_error = |e: rust_xlsxwriter::XlsxError| Error::custom(format!("xlsx: {}", e));
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.set_name("Requirements").map_err(xlsx_error)?;
+
+    let bold = Format::new().set_bold();
+    for (col, heading) in COLUMNS.iter().enumerate() {
+        sheet
+            .write_string_with_format(0, col as u16, *heading, &bold)
+            .map_err(xlsx_error)?;
+    }
+    let rows = rows(config, store)?;
+    for (i, row) in rows.iter().enumerate() {
+        for (col, cell) in row.cells().iter().enumerate() {
+            sheet
+                .write_string(i as u32 + 1, col as u16, cell)
+                .map_err(xlsx_error)?;
+        }
+    }
+    sheet
+        .autofilter(0, 0, rows.len() as u32, COLUMNS.len() as u16 - 1)
+        .map_err(xlsx_error)?;
+    sheet.set_freeze_panes(1, 0).map_err(xlsx_error)?;
+
+    workbook.save_to_buffer().map_err(xlsx_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    owner: "@alice"
+    requirements:
+      - summary: Login, with "SSO"
+        priority: high
+        tags: [web, sso]
+        requirements:
+          - summary: Lockout
+      - Export
+  - summary: Export
+    name: EXP
+"#;
+
+    #[test]
+    fn test_rows_have_parent_paths() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "TAB".to_string()).unwrap();
+        let meta = store
+            .get_or_create_metadata(&config.requirements[0])
+            .unwrap();
+
+        let rows = rows(&config, Some(&store)).unwrap();
+        let paths: Vec<(&str, &str)> = rows
+            .iter()
+            .map(|row| (row.parent_path.as_str(), row.summary.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("", "Authentication"),
+                ("Authentication", "Login, with \"SSO\""),
+                ("Authentication > Login, with \"SSO\"", "Lockout"),
+                ("", "Export"),
+            ]
+        );
+        assert_eq!(rows[0].id.as_deref(), Some("TAB-001"));
+        assert_eq!(rows[0].uuid, Some(meta.uuid.to_string()));
+        assert_eq!(rows[1].id, None);
+        assert_eq!(rows[3].name.as_deref(), Some("EXP"));
+    }
+
+    #[test]
+    fn test_csv_quotes_cells() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let csv = to_csv(&config, None).unwrap();
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "ID,UUID,Name,Parent path,Summary,Owner,Status,Priority,Tags,Target release"
+        );
+        assert_eq!(lines[1], ",,,,Authentication,'@alice,approved,,,");
+        assert_eq!(
+            lines[2],
+            ",,,Authentication,\"Login, with \"\"SSO\"\"\",,,high,\"web, sso\","
+        );
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn test_csv_defuses_formulas() {
+        let cells = ["=1+1", "+1", "-2", "@SUM(A1)", "\t=x", "a=b", "-x, y"];
+        assert_eq!(
+            csv_line(cells.map(String::from)),
+            "'=1+1,'+1,'-2,'@SUM(A1),'\t=x,a=b,\"'-x, y\"\r\n"
+        );
+    }
+
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_xlsx_is_a_zip_archive() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let bytes = to_xlsx(&config, None).unwrap();
+        assert!(bytes.starts_with(b"PK"));
+    }
+}
@@ -1,65 +1,317 @@
 // FFI (Foreign Function Interface) for Go integration
 // Provides C-compatible functions that Go can call via CGO
+//
+// Panics are caught at the boundary and reported like any other error,
+// since unwinding into C is undefined behaviour.
 
+use crate::error::Result;
+use crate::graph::{self, RequirementGraph};
+use crate::metadata::{MetadataStore, RequirementMetadata};
+use crate::output::{
+    CycleCheckResult, DiffResult, ErrorResult, IdsResult, QueryResult, ValidationResult,
+};
+use crate::parser::Parser;
+use crate::query::Expr;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use crate::validator::Validator;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
-use crate::validator::Validator;
-use crate::parser::Parser;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+
+/// Validator configured once and reused across calls
+///
+/// Also holds why the last `rqm_load` with it failed, if it did.
+pub struct RqmContext {
+    validator: Arc<Validator>,
+    error: Option<String>,
+}
+
+/// Requirements parsed once by `rqm_load`, so they can be queried and
+/// validated many times without parsing them again
+///
+/// Shares the validator of the context it was loaded with.
+pub struct RqmHandle {
+    config: RequirementConfig,
+    validator: Arc<Validator>,
+}
 
 /// Validate a YAML file and return JSON result
-/// 
+///
 /// # Safety
 /// - `yaml_content` must be a valid null-terminated C string
 /// - Caller must free the returned string with `free_string`
 #[no_mangle]
 pub unsafe extern "C" fn validate_yaml(yaml_content: *const c_char) -> *mut c_char {
-    if yaml_content.is_null() {
-        return error_json("Input is null");
-    }
-
-    let c_str = unsafe { CStr::from_ptr(yaml_content) };
-    let yaml_str = match c_str.to_str() {
-        Ok(s) => s,
-        Err(_) => return error_json("Invalid UTF-8"),
-    };
-
     let validator = match Validator::new() {
         Ok(v) => v,
         Err(e) => return error_json(&format!("Failed to create validator: {}", e)),
     };
+    unsafe { validate_with(&validator, yaml_content) }
+}
 
-    let result = match Parser::parse_str(yaml_str) {
-        Ok(config) => {
-            match validator.validate(&config) {
-                Ok(_) => {
-                    serde_json::json!({
-                        "valid": true,
-                        "errors": [],
-                        "warnings": []
-                    })
-                }
-                Err(e) => {
-                    serde_json::json!({
-                        "valid": false,
-                        "errors": [e.to_string()],
-                        "warnings": []
-                    })
+/// Create a context validating with the rule profile `profile`
+///
+/// A null `profile` uses the default rules. Returns null if the profile is
+/// unknown or the validator cannot be created.
+///
+/// # Safety
+/// - `profile` must be null or a valid null-terminated C string
+/// - Caller must free the returned context with `rqm_context_free`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_context_new(profile: *const c_char) -> *mut RqmContext {
+    let create = || -> Result<Validator> {
+        let validator = Validator::new()?;
+        if profile.is_null() {
+            return Ok(validator);
+        }
+        let profile = unsafe { CStr::from_ptr(profile) }
+            .to_str()
+            .map_err(|_| crate::Error::custom("Invalid UTF-8"))?;
+        Ok(validator.with_profile(profile.parse()?))
+    };
+    match catch(create) {
+        Ok(validator) => Box::into_raw(Box::new(RqmContext {
+            validator: Arc::new(validator),
+            error: None,
+        })),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Validate a YAML file with a context, returning the same JSON as `validate_yaml`
+///
+/// # Safety
+/// - `context` must be a context returned by `rqm_context_new`
+/// - `yaml_content` must be a valid null-terminated C string
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_context_validate_yaml(
+    context: *const RqmContext,
+    yaml_content: *const c_char,
+) -> *mut c_char {
+    match unsafe { context.as_ref() } {
+        Some(context) => unsafe { validate_with(&context.validator, yaml_content) },
+        None => error_json("Context is null"),
+    }
+}
+
+/// Free a context created by `rqm_context_new`
+///
+/// # Safety
+/// - `context` must be null or a context returned by `rqm_context_new`
+/// - `context` must not be used after this call
+#[no_mangle]
+pub unsafe extern "C" fn rqm_context_free(context: *mut RqmContext) {
+    if !context.is_null() {
+        drop(unsafe { Box::from_raw(context) });
+    }
+}
+
+/// Parse YAML and return the requirements as JSON, with templates applied
+///
+/// Like the other functions below, returns an `ErrorResult`,
+/// `{"schema_version": 1, "error": "..."}`, on failure.
+///
+/// # Safety
+/// - `yaml_content` must be a valid null-terminated C string
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_parse_to_json(yaml_content: *const c_char) -> *mut c_char {
+    json_result(|| Parser::parse_str(unsafe { read_str(yaml_content) }?))
+}
+
+/// Find circular references, returning a `CycleCheckResult`
+///
+/// # Safety
+/// - `yaml_content` must be a valid null-terminated C string
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_check_cycles(yaml_content: *const c_char) -> *mut c_char {
+    json_result(|| {
+        let config = Parser::parse_str(unsafe { read_str(yaml_content) }?)?;
+        CycleCheckResult::from_config(&config)
+    })
+}
+
+/// Return the requirement graph as a JSON Graph Format document
+///
+/// # Safety
+/// - `yaml_content` must be a valid null-terminated C string
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_graph_json(yaml_content: *const c_char) -> *mut c_char {
+    json_result(|| {
+        let config = Parser::parse_str(unsafe { read_str(yaml_content) }?)?;
+        Ok(RequirementGraph::from_config(&config)?.to_json_graph())
+    })
+}
+
+/// Compare two versions of the requirements, returning a `DiffResult`
+///
+/// # Safety
+/// - `old_yaml` and `new_yaml` must be valid null-terminated C strings
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_diff_yaml(
+    old_yaml: *const c_char,
+    new_yaml: *const c_char,
+) -> *mut c_char {
+    json_result(|| {
+        let old =
+            RequirementGraph::from_config(&Parser::parse_str(unsafe { read_str(old_yaml) }?)?)?;
+        let new =
+            RequirementGraph::from_config(&Parser::parse_str(unsafe { read_str(new_yaml) }?)?)?;
+        Ok(DiffResult::new(graph::diff(&old, &new)))
+    })
+}
+
+/// Assign IDs to requirements, returning an `IdsResult`
+///
+/// Metadata is read from and written to the `.rqm` directory `rqm_dir`, so
+/// requirements keep their IDs and new ones are numbered after them.
+///
+/// # Safety
+/// - `yaml_content` and `rqm_dir` must be valid null-terminated C strings
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_generate_ids(
+    yaml_content: *const c_char,
+    rqm_dir: *const c_char,
+) -> *mut c_char {
+    json_result(|| {
+        let config = Parser::parse_str(unsafe { read_str(yaml_content) }?)?;
+        let mut store = MetadataStore::new(unsafe { read_str(rqm_dir) }?)?;
+
+        let mut ids = BTreeMap::new();
+        let mut stack: Vec<(&Requirement, Option<RequirementMetadata>)> = config
+            .requirements
+            .iter()
+            .rev()
+            .map(|req| (req, None))
+            .collect();
+        while let Some((req, parent)) = stack.pop() {
+            let meta = match &parent {
+                Some(parent) => store.get_or_create_child_metadata(req, parent)?,
+                None => store.get_or_create_metadata(req)?,
+            };
+            ids.insert(req.summary.clone(), meta.generated_id.clone());
+            for child in req.requirements.iter().rev() {
+                if let RequirementReference::Full(child) = child {
+                    stack.push((child, Some(meta.clone())));
                 }
             }
         }
+        Ok(IdsResult::new(ids))
+    })
+}
+
+/// Parse YAML once for use with `rqm_query` and `rqm_validate`
+///
+/// Returns null if the YAML cannot be parsed; `rqm_last_error` then tells
+/// why. Handles are independent of each other, so a context can load any
+/// number of documents.
+///
+/// # Safety
+/// - `context` must be a context returned by `rqm_context_new`, not in use
+///   by another thread
+/// - `yaml_content` must be a valid null-terminated C string
+/// - Caller must free the returned handle with `rqm_free`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_load(
+    context: *mut RqmContext,
+    yaml_content: *const c_char,
+) -> *mut RqmHandle {
+    let Some(context) = (unsafe { context.as_mut() }) else {
+        return std::ptr::null_mut();
+    };
+    let loaded = catch(|| Parser::parse_str(unsafe { read_str(yaml_content) }?));
+    match loaded {
+        Ok(config) => {
+            context.error = None;
+            Box::into_raw(Box::new(RqmHandle {
+                config,
+                validator: Arc::clone(&context.validator),
+            }))
+        }
         Err(e) => {
-            serde_json::json!({
-                "valid": false,
-                "errors": [e.to_string()],
-                "warnings": []
-            })
+            context.error = Some(e.to_string());
+            std::ptr::null_mut()
         }
+    }
+}
+
+/// Why the last `rqm_load` with `context` failed, as an `ErrorResult`, or
+/// null if it succeeded
+///
+/// # Safety
+/// - `context` must be a context returned by `rqm_context_new`
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_last_error(context: *const RqmContext) -> *mut c_char {
+    let error = match unsafe { context.as_ref() } {
+        Some(context) => context.error.clone(),
+        None => Some("Context is null".to_string()),
     };
+    match error {
+        Some(error) => json_result::<()>(|| Err(crate::Error::custom(error))),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Find the loaded requirements matching a query expression such as
+/// `status==draft && tag=safety`, returning a `QueryResult`
+///
+/// Each match is listed once, with its nested children as references to
+/// their summaries.
+///
+/// # Safety
+/// - `handle` must be a handle returned by `rqm_load`
+/// - `expr` must be a valid null-terminated C string
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_query(handle: *const RqmHandle, expr: *const c_char) -> *mut c_char {
+    json_result(|| {
+        let handle = unsafe { loaded(handle) }?;
+        let expr = Expr::parse(unsafe { read_str(expr) }?)?;
+        let matches = handle.config.query(&expr);
+        Ok(QueryResult::new(
+            matches
+                .into_iter()
+                .map(Requirement::shallow_clone)
+                .collect(),
+        ))
+    })
+}
 
-    let json_str = result.to_string();
-    match CString::new(json_str) {
-        Ok(c_string) => c_string.into_raw(),
-        Err(_) => error_json("Failed to create result string"),
+/// Validate the loaded requirements, returning the same JSON as `validate_yaml`
+///
+/// # Safety
+/// - `handle` must be a handle returned by `rqm_load`
+/// - Caller must free the returned string with `free_string`
+#[no_mangle]
+pub unsafe extern "C" fn rqm_validate(handle: *const RqmHandle) -> *mut c_char {
+    let validated = catch(|| {
+        let handle = unsafe { loaded(handle) }?;
+        Ok(validation_result(&handle.validator, &handle.config))
+    });
+    match validated {
+        Ok(result) => validation_json(&result),
+        Err(e) => error_json(&e.to_string()),
+    }
+}
+
+/// Free a handle returned by `rqm_load`
+///
+/// # Safety
+/// - `handle` must be null or a handle returned by `rqm_load`
+/// - `handle` must not be used after this call
+#[no_mangle]
+pub unsafe extern "C" fn rqm_free(handle: *mut RqmHandle) {
+    if !handle.is_null() {
+        drop(unsafe { Box::from_raw(handle) });
     }
 }
 
@@ -77,15 +329,88 @@ pub unsafe extern "C" fn free_string(s: *mut c_char) {
     }
 }
 
-/// Helper to create error JSON
-fn error_json(message: &str) -> *mut c_char {
-    let json = serde_json::json!({
-        "valid": false,
-        "errors": [message],
-        "warnings": []
+/// Helper to validate a C string with `validator`
+unsafe fn validate_with(validator: &Validator, yaml_content: *const c_char) -> *mut c_char {
+    let yaml_str = match unsafe { read_str(yaml_content) } {
+        Ok(s) => s,
+        Err(e) => return error_json(&e.to_string()),
+    };
+
+    let result = catch(|| {
+        Ok(match Parser::parse_str(yaml_str) {
+            Ok(config) => validation_result(validator, &config),
+            Err(e) => ValidationResult::error(e.to_string()),
+        })
+    });
+
+    match result {
+        Ok(result) => validation_json(&result),
+        Err(e) => error_json(&e.to_string()),
+    }
+}
+
+/// Helper to describe the findings of validating `config`
+fn validation_result(validator: &Validator, config: &RequirementConfig) -> ValidationResult {
+    ValidationResult::from(&validator.validate(config))
+}
+
+/// Helper to return a validation result as JSON
+fn validation_json(result: &ValidationResult) -> *mut c_char {
+    serde_json::to_string(result)
+        .ok()
+        .and_then(to_c_string)
+        .unwrap_or_else(|| error_json("Failed to create result string"))
+}
+
+/// Helper to borrow a handle returned by `rqm_load`
+unsafe fn loaded<'a>(handle: *const RqmHandle) -> Result<&'a RqmHandle> {
+    unsafe { handle.as_ref() }.ok_or_else(|| crate::Error::custom("Handle is null"))
+}
+
+/// Helper to borrow a C string as UTF-8
+unsafe fn read_str<'a>(s: *const c_char) -> Result<&'a str> {
+    if s.is_null() {
+        return Err(crate::Error::custom("Input is null"));
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|_| crate::Error::custom("Invalid UTF-8"))
+}
+
+/// Helper to return a result as JSON, or an `ErrorResult`
+fn json_result<T: Serialize>(f: impl FnOnce() -> Result<T>) -> *mut c_char {
+    let json = catch(f).and_then(|value| {
+        serde_json::to_string(&value)
+            .map_err(|e| crate::Error::custom(format!("Failed to serialize: {}", e)))
+    });
+    let json = json.unwrap_or_else(|e| {
+        serde_json::to_string(&ErrorResult::new(e.to_string()))
+            .unwrap_or_else(|_| r#"{"error":"Internal error"}"#.to_string())
     });
-    let json_str = json.to_string();
-    CString::new(json_str)
+    to_c_string(json).unwrap_or_else(|| error_json("Failed to create result string"))
+}
+
+/// Helper to run `f`, turning a panic into an error instead of unwinding
+/// into the caller
+fn catch<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(crate::Error::custom(format!("Internal error: {}", message)))
+    })
+}
+
+fn to_c_string(s: String) -> Option<*mut c_char> {
+    CString::new(s).ok().map(CString::into_raw)
+}
+
+/// Helper to create a `ValidationResult` with a single error
+fn error_json(message: &str) -> *mut c_char {
+    let json = serde_json::to_string(&ValidationResult::error(message)).unwrap_or_default();
+    CString::new(json)
         .unwrap_or_else(|_| CString::new("Internal error").unwrap())
         .into_raw()
 }
@@ -93,7 +418,29 @@ fn error_json(message: &str) -> *mut c_char {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::output::SCHEMA_VERSION;
     use std::ffi::CString;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    status: draft
+    requirements:
+      - summary: Lockout
+"#;
+
+    fn c(s: &str) -> CString {
+        CString::new(s).unwrap()
+    }
+
+    fn take_json(ptr: *mut c_char) -> serde_json::Value {
+        assert!(!ptr.is_null());
+        let json = unsafe { CStr::from_ptr(ptr) }.to_str().unwrap().to_string();
+        unsafe { free_string(ptr) };
+        serde_json::from_str(&json).unwrap()
+    }
 
     #[test]
     fn test_validate_yaml_ffi() {
@@ -108,12 +455,13 @@ requirements:
         let c_yaml = CString::new(yaml).unwrap();
         let result_ptr = unsafe { validate_yaml(c_yaml.as_ptr()) };
         assert!(!result_ptr.is_null());
-        
+
         let result_str = unsafe { CStr::from_ptr(result_ptr) };
-        let result_json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
-        
+        let result_json: serde_json::Value =
+            serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+
         assert_eq!(result_json["valid"], true);
-        
+
         unsafe { free_string(result_ptr) };
     }
 
@@ -123,12 +471,161 @@ requirements:
         let c_yaml = CString::new(yaml).unwrap();
         let result_ptr = unsafe { validate_yaml(c_yaml.as_ptr()) };
         assert!(!result_ptr.is_null());
-        
+
         let result_str = unsafe { CStr::from_ptr(result_ptr) };
-        let result_json: serde_json::Value = serde_json::from_str(result_str.to_str().unwrap()).unwrap();
-        
+        let result_json: serde_json::Value =
+            serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+
         assert_eq!(result_json["valid"], false);
-        
+        assert_eq!(result_json["schema_version"], SCHEMA_VERSION);
+
         unsafe { free_string(result_ptr) };
     }
+
+    #[test]
+    fn test_validate_yaml_reports_all_errors() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - summary: A
+  - summary: A
+  - summary: B
+    tags: ["bad//tag"]
+"#;
+        let c_yaml = CString::new(yaml).unwrap();
+        let result_ptr = unsafe { validate_yaml(c_yaml.as_ptr()) };
+        let result_str = unsafe { CStr::from_ptr(result_ptr) };
+        let result_json: serde_json::Value =
+            serde_json::from_str(result_str.to_str().unwrap()).unwrap();
+
+        assert_eq!(result_json["valid"], false);
+        assert_eq!(result_json["errors"].as_array().unwrap().len(), 2);
+        assert!(result_json["infos"].is_array());
+
+        unsafe { free_string(result_ptr) };
+    }
+
+    #[test]
+    fn test_parse_graph_and_cycles() {
+        let yaml = c(YAML);
+        let parsed = take_json(unsafe { rqm_parse_to_json(yaml.as_ptr()) });
+        assert_eq!(
+            parsed["requirements"][0]["requirements"][0]["summary"],
+            "Lockout"
+        );
+
+        let graph = take_json(unsafe { rqm_graph_json(yaml.as_ptr()) });
+        assert_eq!(graph["graph"]["edges"].as_array().unwrap().len(), 1);
+
+        let cycles = take_json(unsafe { rqm_check_cycles(yaml.as_ptr()) });
+        assert_eq!(cycles["has_cycles"], false);
+        assert_eq!(cycles["graph"]["Login"][0], "Lockout");
+
+        let cyclic = c("version: \"1.0\"\nrequirements:\n  - summary: A\n    requirements: [B]\n  - summary: B\n    requirements: [A]\n");
+        let cycles = take_json(unsafe { rqm_check_cycles(cyclic.as_ptr()) });
+        assert_eq!(cycles["has_cycles"], true);
+    }
+
+    #[test]
+    fn test_errors_are_reported_as_json() {
+        let invalid = c("requirements: [");
+        assert!(take_json(unsafe { rqm_parse_to_json(invalid.as_ptr()) })["error"].is_string());
+        assert_eq!(
+            take_json(unsafe { rqm_graph_json(std::ptr::null()) })["error"],
+            "Input is null"
+        );
+    }
+
+    #[test]
+    fn test_panics_are_reported_as_errors() {
+        let result = take_json(json_result::<()>(|| panic!("boom")));
+        assert_eq!(result["error"], "Internal error: boom");
+        assert_eq!(result["schema_version"], SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_diff_yaml() {
+        let old = c(YAML);
+        let new = c(&YAML.replace("status: draft", "status: approved"));
+        let result = take_json(unsafe { rqm_diff_yaml(old.as_ptr(), new.as_ptr()) });
+        assert_eq!(result["modified"][0]["summary"], "Login");
+        assert_eq!(result["status_transitions"][0]["to"], "approved");
+    }
+
+    #[test]
+    fn test_generate_ids_is_stable() {
+        let temp_dir = TempDir::new().unwrap();
+        let rqm_dir = c(temp_dir.path().join(".rqm").to_str().unwrap());
+        let yaml = c(YAML);
+
+        let first = take_json(unsafe { rqm_generate_ids(yaml.as_ptr(), rqm_dir.as_ptr()) });
+        assert_eq!(first["ids"]["Login"], "REQ-001");
+        assert_eq!(first["ids"]["Lockout"], "REQ-002");
+
+        let second = take_json(unsafe { rqm_generate_ids(yaml.as_ptr(), rqm_dir.as_ptr()) });
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_context_reuses_validator() {
+        let context = unsafe { rqm_context_new(std::ptr::null()) };
+        assert!(!context.is_null());
+        let yaml = c(YAML);
+        for _ in 0..2 {
+            let result = take_json(unsafe { rqm_context_validate_yaml(context, yaml.as_ptr()) });
+            assert_eq!(result["valid"], true);
+        }
+        unsafe { rqm_context_free(context) };
+
+        let profile = c("nope");
+        assert!(unsafe { rqm_context_new(profile.as_ptr()) }.is_null());
+        let result =
+            take_json(unsafe { rqm_context_validate_yaml(std::ptr::null(), yaml.as_ptr()) });
+        assert_eq!(result["valid"], false);
+    }
+
+    #[test]
+    fn test_handle_answers_many_queries() {
+        let context = unsafe { rqm_context_new(std::ptr::null()) };
+        let yaml = c(YAML);
+        let handle = unsafe { rqm_load(context, yaml.as_ptr()) };
+        assert!(!handle.is_null());
+        assert!(unsafe { rqm_last_error(context) }.is_null());
+
+        let draft = c("status==draft");
+        let result = take_json(unsafe { rqm_query(handle, draft.as_ptr()) });
+        assert_eq!(result["requirements"].as_array().unwrap().len(), 1);
+        assert_eq!(result["requirements"][0]["summary"], "Login");
+        // Children are named, not copied
+        assert_eq!(result["requirements"][0]["requirements"][0], "Lockout");
+
+        let all = c("summary~lo");
+        let result = take_json(unsafe { rqm_query(handle, all.as_ptr()) });
+        assert_eq!(result["requirements"].as_array().unwrap().len(), 2);
+
+        let invalid = c("status==");
+        assert!(take_json(unsafe { rqm_query(handle, invalid.as_ptr()) })["error"].is_string());
+        assert_eq!(take_json(unsafe { rqm_validate(handle) })["valid"], true);
+
+        // Another document loads alongside the first
+        let other = c("version: \"1.0\"\nrequirements:\n  - summary: Logout\n");
+        let second = unsafe { rqm_load(context, other.as_ptr()) };
+        let result = take_json(unsafe { rqm_query(second, all.as_ptr()) });
+        assert_eq!(result["requirements"][0]["summary"], "Logout");
+        let result = take_json(unsafe { rqm_query(handle, all.as_ptr()) });
+        assert_eq!(result["requirements"].as_array().unwrap().len(), 2);
+        unsafe { rqm_free(second) };
+        unsafe { rqm_free(handle) };
+
+        let broken = c("requirements: [");
+        assert!(unsafe { rqm_load(context, broken.as_ptr()) }.is_null());
+        let error = take_json(unsafe { rqm_last_error(context) });
+        assert!(error["error"].is_string());
+        assert_eq!(error["schema_version"], SCHEMA_VERSION);
+        assert_eq!(
+            take_json(unsafe { rqm_validate(std::ptr::null()) })["errors"][0],
+            "Handle is null"
+        );
+        unsafe { rqm_context_free(context) };
+    }
 }
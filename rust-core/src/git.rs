@@ -0,0 +1,287 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Timestamps and authorship of requirements from git history
+//!
+//! Enabled by the `git` feature. Each requirement is attributed the lines
+//! of its YAML mapping, less those of its nested requirements, and `git
+//! blame` of those lines gives when it was created, last changed, and by
+//! whom. Uncommitted lines are ignored.
+
+use crate::metadata::requirement_lines;
+use crate::types::RequirementReference;
+use crate::{Error, Parser, Requirement, RequirementConfig, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use git2::{Blame, Repository};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// What git knows about one requirement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitInfo {
+    /// Time of the oldest commit touching the requirement
+    pub created_at: DateTime<Utc>,
+
+    /// Time of the newest commit touching the requirement
+    pub updated_at: DateTime<Utc>,
+
+    /// Author of the newest commit, as `Name <email>`
+    pub last_author: String,
+}
+
+fn git_error(e: git2::Error) -> Error {
+    Error::custom(format!("git: {}", e.message()))
+}
+
+/// Path of a file relative to the working directory of its repository
+fn relative_path(repo: &Repository, path: &Path) -> Result<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| Error::custom("git: repository has no working directory"))?
+        .canonicalize()?;
+    let absolute = path.canonicalize()?;
+    absolute
+        .strip_prefix(&workdir)
+        .map(Path::to_path_buf)
+        .map_err(|_| Error::custom(format!("{} is outside the repository", path.display())))
+}
+
+/// Content of a file as of a revision such as `HEAD~1`, a branch or a tag
+pub fn file_at(path: &Path, revision: &str) -> Result<String> {
+    let repo = Repository::discover(path).map_err(git_error)?;
+    let relative = relative_path(&repo, path)?;
+    let spec = format!(
+        "{}:{}",
+        revision,
+        relative.to_string_lossy().replace('\\', "/")
+    );
+    let blob = repo
+        .revparse_single(&spec)
+        .and_then(|object| object.peel_to_blob())
+        .map_err(git_error)?;
+    String::from_utf8(blob.content().to_vec())
+        .map_err(|_| Error::custom(format!("{} at {} is not UTF-8", path.display(), revision)))
+}
+
+/// `user.email` from the git configuration of the repository containing
+/// `path`, or the global configuration outside a repository
+pub fn user_email(path: &Path) -> Result<Option<String>> {
+    let config = match Repository::discover(path) {
+        Ok(repo) => repo.config(),
+        Err(_) => git2::Config::open_default(),
+    }
+    .map_err(git_error)?;
+    match config.get_string("user.email") {
+        Ok(email) => Ok(Some(email)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(git_error(e)),
+    }
+}
+
+/// Blame a requirements file, keyed by summary
+///
+/// Requirements with no committed lines are left out.
+pub fn blame(path: &Path) -> Result<HashMap<String, GitInfo>> {
+    let content = fs::read_to_string(path)?;
+    let config = Parser::parse_str(&content)?;
+
+    let repo = Repository::discover(path).map_err(git_error)?;
+    let relative = relative_path(&repo, path)?;
+    let committed = repo.blame_file(&relative, None).map_err(git_error)?;
+    let blame = committed
+        .blame_buffer(content.as_bytes())
+        .map_err(git_error)?;
+
+    let requirements = config.all_requirements();
+    let spans: HashMap<&str, Range<usize>> = requirements
+        .iter()
+        .filter_map(|req| {
+            Some((
+                req.summary.as_str(),
+                requirement_lines(&content, &req.summary)?,
+            ))
+        })
+        .collect();
+
+    let mut info = HashMap::new();
+    for req in requirements {
+        let Some(span) = spans.get(req.summary.as_str()) else {
+            continue;
+        };
+        let nested: Vec<&Range<usize>> = req
+            .requirements
+            .iter()
+            .filter_map(|child| match child {
+                RequirementReference::Full(child) => spans.get(child.summary.as_str()),
+                RequirementReference::Reference(_) => None,
+            })
+            .collect();
+        let own = span
+            .clone()
+            .filter(|line| !nested.iter().any(|child| child.contains(line)))
+            .map(|line| line + 1);
+        if let Some(found) = blame_lines(&blame, own) {
+            info.insert(req.summary.clone(), found);
+        }
+    }
+    Ok(info)
+}
+
+/// Combine the commits behind 1-based lines
+fn blame_lines(blame: &Blame, lines: impl Iterator<Item = usize>) -> Option<GitInfo> {
+    let mut result: Option<GitInfo> = None;
+    for line in lines {
+        let Some(hunk) = blame.get_line(line) else {
+            continue;
+        };
+        if hunk.final_commit_id().is_zero() {
+            continue;
+        }
+        let signature = hunk.final_signature();
+        let Some(time) = Utc.timestamp_opt(signature.when().seconds(), 0).single() else {
+            continue;
+        };
+        let author = format!(
+            "{} <{}>",
+            signature.name().unwrap_or_default(),
+            signature.email().unwrap_or_default()
+        );
+
+        match &mut result {
+            None => {
+                result = Some(GitInfo {
+                    created_at: time,
+                    updated_at: time,
+                    last_author: author,
+                })
+            }
+            Some(info) => {
+                info.created_at = info.created_at.min(time);
+                if time > info.updated_at {
+                    info.updated_at = time;
+                    info.last_author = author;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Set `created_at`, `updated_at` and `updated_by` of requirements from
+/// git history
+///
+/// Intended to run on a parsed file before reporting, replacing manually
+/// maintained timestamps. Returns the number of requirements updated.
+pub fn enrich(config: &mut RequirementConfig, path: &Path) -> Result<usize> {
+    let info = blame(path)?;
+    let mut updated = 0;
+    let mut stack: Vec<&mut Requirement> = config.requirements.iter_mut().collect();
+    while let Some(req) = stack.pop() {
+        if let Some(found) = info.get(&req.summary) {
+            req.created_at = Some(found.created_at.to_rfc3339());
+            req.updated_at = Some(found.updated_at.to_rfc3339());
+            req.updated_by = Some(found.last_author.clone());
+            updated += 1;
+        }
+        for child in &mut req.requirements {
+            if let RequirementReference::Full(child) = child {
+                stack.push(child);
+            }
+        }
+    }
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, file: &str, name: &str, seconds: i64) {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::new(
+            name,
+            &format!("{}@example.com", name),
+            &Time::new(seconds, 0),
+        )
+        .unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            "update",
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_blame_and_enrich() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let path = temp.path().join("requirements.yml");
+
+        fs::write(
+            &path,
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements:\n      - summary: Lockout\n  - summary: Export\n",
+        )
+        .unwrap();
+        commit(&repo, "requirements.yml", "alice", 1_700_000_000);
+        // The status below the nested requirement is Login's
+        fs::write(
+            &path,
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements:\n      - summary: Lockout\n    status: draft\n  - summary: Export\n  - summary: Audit\n",
+        )
+        .unwrap();
+        commit(&repo, "requirements.yml", "bob", 1_700_100_000);
+        fs::write(
+            &path,
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements:\n      - summary: Lockout\n    status: draft\n  - summary: Export\n  - summary: Audit\n  - summary: Draft\n",
+        )
+        .unwrap();
+
+        assert!(file_at(&path, "HEAD~1")
+            .unwrap()
+            .ends_with("  - summary: Export\n"));
+        assert!(file_at(&path, "HEAD").unwrap().contains("Audit"));
+        assert!(file_at(&path, "no-such-branch").is_err());
+
+        repo.config()
+            .unwrap()
+            .set_str("user.email", "carol@example.com")
+            .unwrap();
+        assert_eq!(
+            user_email(&path).unwrap().as_deref(),
+            Some("carol@example.com")
+        );
+
+        let info = blame(&path).unwrap();
+        let login = &info["Login"];
+        assert_eq!(login.created_at.timestamp(), 1_700_000_000);
+        assert_eq!(login.updated_at.timestamp(), 1_700_100_000);
+        assert_eq!(login.last_author, "bob <bob@example.com>");
+        assert_eq!(info["Lockout"].updated_at.timestamp(), 1_700_000_000);
+        assert_eq!(info["Lockout"].last_author, "alice <alice@example.com>");
+        assert_eq!(info["Export"].last_author, "alice <alice@example.com>");
+        assert!(!info.contains_key("Draft"));
+
+        let mut config = Parser::parse_file(&path).unwrap();
+        assert_eq!(enrich(&mut config, &path).unwrap(), 4);
+        let login = &config.requirements[0];
+        assert!(login
+            .created_at
+            .as_deref()
+            .is_some_and(|time| time.starts_with("2023-11-14")));
+        assert_eq!(login.updated_by.as_deref(), Some("bob <bob@example.com>"));
+    }
+}
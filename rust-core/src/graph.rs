@@ -2,50 +2,167 @@
 // Copyright (c) 2025
 // SPDX-License-Identifier: MIT
 
-use crate::{types::RequirementReference, Error, Requirement, RequirementConfig, Result};
+use crate::metadata::tag_has_prefix;
+use crate::types::{PersonAlias, RequirementReference};
+use crate::{Error, Requirement, RequirementConfig, Result};
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{Dfs, EdgeRef};
+use serde::Serialize;
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+
+pub mod analysis;
+pub mod coverage;
+mod critical_path;
+pub mod diff;
+mod dot;
+mod graphml;
+mod jgf;
+mod mermaid;
+mod mutation;
+mod paths;
+mod priority;
+mod rollup;
+mod signoff;
+mod stats;
+mod subgraph;
+mod traversal;
+mod workload;
+
+pub use critical_path::CriticalPath;
+pub use diff::{
+    diff, diff_with_metadata, FieldChange, GraphDiff, RequirementChange, StatusTransition,
+};
+pub use dot::DotOptions;
+pub use priority::PriorityConflict;
+pub use signoff::{is_current, missing_roles, MissingSignoff, ANY_ROLE};
+pub use stats::GraphStats;
+pub use subgraph::SubgraphFilter;
+pub use traversal::{TraversalOptions, TraversalOrder};
+pub use workload::{OwnerWorkload, UNASSIGNED};
+
+/// A set of requirements that reference each other circularly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle {
+    /// Summaries of the requirements in the cycle
+    pub members: Vec<String>,
+
+    /// References between members, as `(parent, child)` summaries
+    pub edges: Vec<(String, String)>,
+}
 
-use std::collections::{HashMap, HashSet};
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let edges: Vec<String> = self
+            .edges
+            .iter()
+            .map(|(from, to)| format!("'{}' -> '{}'", from, to))
+            .collect();
+        write!(f, "cycle through {}", edges.join(", "))
+    }
+}
 
-const MAX_TRAVERSAL_DEPTH: usize = 100;
+/// Where the requirement at a node is stored: the top-level requirement
+/// it is nested in, and the index of each child on the way down to it
+///
+/// The summary is interned: the node and the lookup by summary share one
+/// allocation.
+#[derive(Debug, Clone)]
+struct Node {
+    tree: usize,
+    path: Vec<usize>,
+    summary: Arc<str>,
+}
+
+/// Every requirement in a tree with its path from the top, in pre-order
+fn paths(root: &Requirement) -> Vec<(Vec<usize>, &Requirement)> {
+    let mut result = vec![];
+    let mut stack = vec![(vec![], root)];
+    while let Some((path, req)) = stack.pop() {
+        for (i, child) in req.requirements.iter().enumerate().rev() {
+            if let RequirementReference::Full(child) = child {
+                let mut child_path = path.clone();
+                child_path.push(i);
+                stack.push((child_path, child.as_ref()));
+            }
+        }
+        result.push((path, req));
+    }
+    result
+}
 
 /// A graph representation of requirements with circular reference detection
+///
+/// Each requirement is stored once, nested in its top-level requirement as
+/// in the config, and nodes point into those trees. The trees are shared,
+/// so cloning a graph is cheap; an edit copies only the tree it changes.
+#[derive(Clone)]
 pub struct RequirementGraph {
-    graph: DiGraph<String, ()>,
-    summary_to_node: HashMap<String, NodeIndex>,
-    requirements: HashMap<String, Requirement>,
+    /// Edge weights record insertion order, which survives node removal
+    graph: DiGraph<Node, usize>,
+    /// Top-level requirements, `None` once removed so the others keep their index
+    trees: Vec<Option<Arc<Requirement>>>,
+    summary_to_node: HashMap<Arc<str>, NodeIndex>,
+    /// Parents of each node in declaration order, so upward walks avoid scanning the graph
+    parents: HashMap<NodeIndex, Vec<NodeIndex>>,
+    version: String,
+    aliases: Vec<PersonAlias>,
+    next_edge: usize,
 }
 
 impl RequirementGraph {
     /// Build a graph from a RequirementConfig
+    ///
+    /// The requirements are copied; a config that is no longer needed can
+    /// be moved in with `RequirementGraph::try_from` instead.
     pub fn from_config(config: &RequirementConfig) -> Result<Self> {
+        Self::build(
+            config.requirements.iter().cloned().map(Arc::new).collect(),
+            config.version.clone(),
+            config.aliases.clone(),
+        )
+    }
+
+    fn build(
+        trees: Vec<Arc<Requirement>>,
+        version: String,
+        aliases: Vec<PersonAlias>,
+    ) -> Result<Self> {
         let mut graph = DiGraph::new();
         let mut summary_to_node = HashMap::new();
-        let mut requirements = HashMap::new();
 
         // First pass: create all nodes
-        for req in config.all_requirements() {
-            let summary = req.summary.clone();
-            let node = graph.add_node(summary.clone());
-            summary_to_node.insert(summary.clone(), node);
-            requirements.insert(summary, req.clone());
+        for (tree, root) in trees.iter().enumerate() {
+            for (path, req) in paths(root) {
+                let summary: Arc<str> = req.summary.as_str().into();
+                let node = graph.add_node(Node {
+                    tree,
+                    path,
+                    summary: Arc::clone(&summary),
+                });
+                summary_to_node.insert(summary, node);
+            }
         }
 
         // Second pass: create edges
-        for req in config.all_requirements() {
-            let parent_node = summary_to_node[&req.summary];
+        for req in trees
+            .iter()
+            .flat_map(|root| paths(root))
+            .map(|(_, req)| req)
+        {
+            let parent_node = summary_to_node[req.summary.as_str()];
 
             for child_ref in &req.requirements {
-                match child_ref {
-                    RequirementReference::Full(child) => {
-                        if let Some(&child_node) = summary_to_node.get(&child.summary) {
-                            graph.add_edge(parent_node, child_node, ());
-                        }
+                match summary_to_node.get(child_ref.summary()) {
+                    Some(&child_node) => {
+                        let order = graph.edge_count();
+                        graph.add_edge(parent_node, child_node, order);
                     }
-                    RequirementReference::Reference(summary) => {
-                        if let Some(&child_node) = summary_to_node.get(summary) {
-                            graph.add_edge(parent_node, child_node, ());
-                        } else {
+                    None => {
+                        if let RequirementReference::Reference(summary) = child_ref {
                             return Err(Error::InvalidReference(format!(
                                 "Requirement '{}' references non-existent '{}'",
                                 req.summary, summary
@@ -56,176 +173,379 @@ impl RequirementGraph {
             }
         }
 
+        let mut parents: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        for edge in graph.edge_references() {
+            let entry = parents.entry(edge.target()).or_default();
+            if !entry.contains(&edge.source()) {
+                entry.push(edge.source());
+            }
+        }
+
         Ok(Self {
+            next_edge: graph.edge_count(),
             graph,
+            trees: trees.into_iter().map(Some).collect(),
             summary_to_node,
-            requirements,
+            parents,
+            version,
+            aliases,
         })
     }
 
     /// Get a requirement by summary
     pub fn get(&self, summary: &str) -> Option<&Requirement> {
-        self.requirements.get(summary)
+        let node = *self.summary_to_node.get(summary)?;
+        Some(self.requirement_at(node))
     }
 
-    /// Check if the graph contains cycles
-    pub fn has_cycles(&self) -> bool {
-        petgraph::algo::is_cyclic_directed(&self.graph)
+    /// Number of requirements in the graph
+    pub fn len(&self) -> usize {
+        self.graph.node_count()
     }
 
-    /// Find all cycles in the graph
-    pub fn find_cycles(&self) -> Vec<Vec<String>> {
-        if !self.has_cycles() {
-            return vec![];
-        }
+    /// Check whether the graph has no requirements
+    pub fn is_empty(&self) -> bool {
+        self.graph.node_count() == 0
+    }
 
-        let mut cycles = vec![];
-        let mut visited = HashSet::new();
+    /// Look up the node for a summary
+    fn node(&self, summary: &str) -> Result<NodeIndex> {
+        self.summary_to_node
+            .get(summary)
+            .copied()
+            .ok_or_else(|| Error::RequirementNotFound(summary.to_string()))
+    }
+
+    /// Get the parents of a node in declaration order
+    fn parents_of(&self, node: NodeIndex) -> &[NodeIndex] {
+        self.parents.get(&node).map_or(&[], Vec::as_slice)
+    }
 
+    /// Get the depth of every node reachable from a root, along its shortest route
+    fn levels(&self) -> HashMap<NodeIndex, usize> {
+        let mut levels = HashMap::new();
+        let mut queue = VecDeque::new();
         for node in self.graph.node_indices() {
-            if !visited.contains(&node) {
-                self.find_cycles_from_node(node, &mut visited, &mut vec![], &mut cycles);
+            if self.parents_of(node).is_empty() {
+                levels.insert(node, 0);
+                queue.push_back(node);
             }
         }
 
-        cycles
-    }
-
-    fn find_cycles_from_node(
-        &self,
-        node: NodeIndex,
-        visited: &mut HashSet<NodeIndex>,
-        path: &mut Vec<NodeIndex>,
-        cycles: &mut Vec<Vec<String>>,
-    ) {
-        if path.contains(&node) {
-            // Found a cycle
-            let cycle_start = path.iter().position(|&n| n == node).unwrap();
-            let cycle: Vec<String> = path[cycle_start..]
-                .iter()
-                .map(|&n| self.graph[n].clone())
-                .collect();
-            cycles.push(cycle);
-            return;
+        while let Some(node) = queue.pop_front() {
+            let depth = levels[&node];
+            for child in self.children_in_order(node) {
+                if let Entry::Vacant(entry) = levels.entry(child) {
+                    entry.insert(depth + 1);
+                    queue.push_back(child);
+                }
+            }
         }
 
-        if visited.contains(&node) {
-            return;
+        levels
+    }
+
+    /// Get the children of a node in declaration order
+    fn children_in_order(&self, node: NodeIndex) -> Vec<NodeIndex> {
+        let mut edges: Vec<_> = self.graph.edges(node).collect();
+        edges.sort_by_key(|edge| *edge.weight());
+        let mut children: Vec<NodeIndex> = vec![];
+        for edge in edges {
+            if !children.contains(&edge.target()) {
+                children.push(edge.target());
+            }
         }
+        children
+    }
 
-        path.push(node);
+    /// Get the requirement stored at a node
+    fn requirement_at(&self, node: NodeIndex) -> &Requirement {
+        let Node { tree, path, .. } = &self.graph[node];
+        let mut req = self.trees[*tree]
+            .as_deref()
+            .expect("nodes only point into stored trees");
+        for &index in path {
+            match &req.requirements[index] {
+                RequirementReference::Full(child) => req = child,
+                RequirementReference::Reference(_) => {
+                    unreachable!("paths only lead through nested children")
+                }
+            }
+        }
+        req
+    }
 
-        for neighbor in self.graph.neighbors(node) {
-            self.find_cycles_from_node(neighbor, visited, path, cycles);
+    /// Get the requirement stored at a node for changing it, copying its
+    /// tree first if another graph shares it
+    fn requirement_at_mut(&mut self, node: NodeIndex) -> &mut Requirement {
+        let Node { tree, path, .. } = &self.graph[node];
+        let mut req = Arc::make_mut(
+            self.trees[*tree]
+                .as_mut()
+                .expect("nodes only point into stored trees"),
+        );
+        for &index in path {
+            match &mut req.requirements[index] {
+                RequirementReference::Full(child) => req = child,
+                RequirementReference::Reference(_) => {
+                    unreachable!("paths only lead through nested children")
+                }
+            }
         }
+        req
+    }
 
-        path.pop();
-        visited.insert(node);
+    /// Get the summary of the requirement stored at a node
+    fn summary(&self, node: NodeIndex) -> &str {
+        &self.graph[node].summary
     }
 
-    /// Traverse from a requirement with cycle detection
-    pub fn traverse<F>(&self, start_summary: &str, mut visit: F) -> Result<()>
-    where
-        F: FnMut(&Requirement, usize) -> Result<()>,
-    {
-        let node = self
-            .summary_to_node
-            .get(start_summary)
-            .ok_or_else(|| Error::RequirementNotFound(start_summary.to_string()))?;
+    /// Every requirement, in node order
+    fn requirements(&self) -> impl Iterator<Item = &Requirement> {
+        self.graph
+            .node_indices()
+            .map(|node| self.requirement_at(node))
+    }
 
-        let mut visited = HashSet::new();
-        self.traverse_recursive(*node, &mut visited, &mut visit, 0)
-    }
-
-    fn traverse_recursive<F>(
-        &self,
-        node: NodeIndex,
-        visited: &mut HashSet<NodeIndex>,
-        visit: &mut F,
-        depth: usize,
-    ) -> Result<()>
-    where
-        F: FnMut(&Requirement, usize) -> Result<()>,
-    {
-        if depth > MAX_TRAVERSAL_DEPTH {
-            return Err(Error::GraphError(
-                "Maximum traversal depth exceeded".to_string(),
-            ));
+    /// Get all requirements carrying a tag at or below `prefix` (e.g., `safety` matches `safety/hv`)
+    pub fn with_tag_prefix(&self, prefix: &str) -> Vec<&Requirement> {
+        self.requirements()
+            .filter(|req| req.tags.iter().any(|tag| tag_has_prefix(tag, prefix)))
+            .collect()
+    }
+
+    /// Follow `superseded_by` links from a requirement to its current replacement
+    ///
+    /// The returned chain starts with the given requirement; a looping chain
+    /// stops at the first repeated requirement.
+    pub fn supersession_chain(&self, summary: &str) -> Result<Vec<&Requirement>> {
+        let mut current = self.requirement_at(self.node(summary)?);
+
+        let mut seen = HashSet::new();
+        let mut chain = vec![];
+        while seen.insert(current.summary.as_str()) {
+            chain.push(current);
+            match current
+                .superseded_by
+                .as_deref()
+                .and_then(|next| self.get(next))
+            {
+                Some(next) => current = next,
+                None => break,
+            }
         }
 
-        if visited.contains(&node) {
-            // Already visited, skip to prevent infinite loop
-            return Ok(());
+        Ok(chain)
+    }
+
+    /// Get all requirements targeting the given release
+    pub fn requirements_for_release(&self, release: &str) -> Vec<&Requirement> {
+        self.requirements()
+            .filter(|req| req.target_release.as_deref() == Some(release))
+            .collect()
+    }
+
+    /// Sum estimates over a requirement and everything reachable from it
+    ///
+    /// Each requirement is counted once, even when it has several parents.
+    pub fn rollup_estimate(&self, summary: &str) -> Result<f64> {
+        let node = self.node(summary)?;
+
+        let mut total = 0.0;
+        let mut dfs = Dfs::new(&self.graph, node);
+        while let Some(n) = dfs.next(&self.graph) {
+            if let Some(estimate) = self.requirement_at(n).estimate {
+                total += estimate;
+            }
         }
 
-        visited.insert(node);
+        Ok(total)
+    }
+
+    /// Check if the graph contains cycles
+    pub fn has_cycles(&self) -> bool {
+        // `toposort` is iterative, unlike `is_cyclic_directed`
+        petgraph::algo::toposort(&self.graph, None).is_err()
+    }
+
+    /// Find all cycles in the graph
+    ///
+    /// Each entry lists the members of one strongly connected component, so
+    /// overlapping cycles are reported once. Members are ordered by following
+    /// references from the earliest declared member, which lists a simple
+    /// cycle in cycle order.
+    pub fn find_cycles(&self) -> Vec<Vec<String>> {
+        self.cycles().into_iter().map(|c| c.members).collect()
+    }
+
+    /// Find all cycles in the graph together with the references forming them
+    pub fn cycles(&self) -> Vec<Cycle> {
+        // `kosaraju_scc` is iterative, unlike `tarjan_scc`
+        let mut components: Vec<Vec<NodeIndex>> = petgraph::algo::kosaraju_scc(&self.graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.graph.contains_edge(component[0], component[0])
+            })
+            .collect();
+        for component in &mut components {
+            component.sort();
+        }
+        components.sort();
 
-        let summary = &self.graph[node];
-        if let Some(req) = self.requirements.get(summary) {
-            visit(req, depth)?;
+        components
+            .iter()
+            .map(|component| self.describe_cycle(component))
+            .collect()
+    }
 
-            for neighbor in self.graph.neighbors(node) {
-                self.traverse_recursive(neighbor, visited, visit, depth + 1)?;
+    fn describe_cycle(&self, component: &[NodeIndex]) -> Cycle {
+        let in_component: HashSet<NodeIndex> = component.iter().copied().collect();
+        let mut order = vec![];
+        let mut visited = HashSet::new();
+        let mut stack = vec![component[0]];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+            order.push(node);
+            for child in self.children_in_order(node).into_iter().rev() {
+                if in_component.contains(&child) {
+                    stack.push(child);
+                }
             }
         }
 
-        Ok(())
+        let edges = order
+            .iter()
+            .flat_map(|&from| {
+                self.children_in_order(from)
+                    .into_iter()
+                    .filter(|to| in_component.contains(to))
+                    .map(move |to| (self.summary(from).to_string(), self.summary(to).to_string()))
+            })
+            .collect();
+
+        Cycle {
+            members: order.iter().map(|&n| self.summary(n).to_string()).collect(),
+            edges,
+        }
+    }
+
+    /// Fail with a [`Error::CircularReference`] naming every offending reference
+    pub fn check_acyclic(&self) -> Result<()> {
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            return Ok(());
+        }
+
+        let described: Vec<String> = cycles.iter().map(Cycle::to_string).collect();
+        Err(Error::CircularReference(described.join("; ")))
     }
 
     /// Get all requirements in topological order (if acyclic)
     pub fn topological_sort(&self) -> Result<Vec<&Requirement>> {
-        if self.has_cycles() {
-            return Err(Error::CircularReference(
-                "Cannot perform topological sort on cyclic graph".to_string(),
-            ));
-        }
+        self.check_acyclic()?;
 
         let sorted = petgraph::algo::toposort(&self.graph, None)
             .map_err(|_| Error::GraphError("Topological sort failed".to_string()))?;
 
         Ok(sorted
-            .iter()
-            .filter_map(|&node| {
-                let summary = &self.graph[node];
-                self.requirements.get(summary)
-            })
+            .into_iter()
+            .map(|node| self.requirement_at(node))
             .collect())
     }
 
     /// Get dependencies of a requirement
     pub fn dependencies(&self, summary: &str) -> Result<Vec<&Requirement>> {
-        let node = self
-            .summary_to_node
-            .get(summary)
-            .ok_or_else(|| Error::RequirementNotFound(summary.to_string()))?;
+        let node = self.node(summary)?;
 
         Ok(self
-            .graph
-            .neighbors(*node)
-            .filter_map(|n| {
-                let sum = &self.graph[n];
-                self.requirements.get(sum)
-            })
+            .children_in_order(node)
+            .into_iter()
+            .map(|n| self.requirement_at(n))
             .collect())
     }
 
     /// Get dependents (reverse dependencies) of a requirement
     pub fn dependents(&self, summary: &str) -> Result<Vec<&Requirement>> {
-        let node = self
-            .summary_to_node
-            .get(summary)
-            .ok_or_else(|| Error::RequirementNotFound(summary.to_string()))?;
+        let node = self.node(summary)?;
 
         Ok(self
-            .graph
-            .node_indices()
-            .filter(|&n| self.graph.neighbors(n).any(|neighbor| neighbor == *node))
-            .filter_map(|n| {
-                let sum = &self.graph[n];
-                self.requirements.get(sum)
-            })
+            .parents_of(node)
+            .iter()
+            .map(|&n| self.requirement_at(n))
             .collect())
     }
+
+    /// Get every requirement from which the given one can be reached
+    ///
+    /// Ancestors are listed nearest first. The requirement itself is excluded
+    /// even when it lies on a cycle.
+    pub fn ancestors(&self, summary: &str) -> Result<Vec<&Requirement>> {
+        let node = self.node(summary)?;
+
+        Ok(self
+            .ancestor_nodes(node)
+            .into_iter()
+            .map(|n| self.requirement_at(n))
+            .collect())
+    }
+
+    /// Get the top-level requirements from which the given one can be reached
+    ///
+    /// A requirement without parents is its own root. Requirements reachable
+    /// only through a cycle have no roots.
+    pub fn roots_of(&self, summary: &str) -> Result<Vec<&Requirement>> {
+        let node = self.node(summary)?;
+        let mut nodes = self.ancestor_nodes(node);
+        if self.parents_of(node).is_empty() {
+            nodes.insert(0, node);
+        }
+
+        Ok(nodes
+            .into_iter()
+            .filter(|&n| self.parents_of(n).is_empty())
+            .map(|n| self.requirement_at(n))
+            .collect())
+    }
+
+    /// Breadth-first walk over incoming edges, excluding the start node
+    fn ancestor_nodes(&self, node: NodeIndex) -> Vec<NodeIndex> {
+        let mut seen = HashSet::from([node]);
+        let mut order = vec![];
+        let mut queue = VecDeque::from([node]);
+
+        while let Some(current) = queue.pop_front() {
+            for &parent in self.parents_of(current) {
+                if seen.insert(parent) {
+                    order.push(parent);
+                    queue.push_back(parent);
+                }
+            }
+        }
+
+        order
+    }
+}
+
+impl TryFrom<RequirementConfig> for RequirementGraph {
+    type Error = Error;
+
+    /// Build a graph that takes over the requirements of a config
+    fn try_from(config: RequirementConfig) -> Result<Self> {
+        Self::build(
+            config.requirements.into_iter().map(Arc::new).collect(),
+            config.version,
+            config.aliases,
+        )
+    }
+}
+
+/// Get the serialized name of a unit enum variant such as a status or priority
+fn variant_name<T: Serialize>(value: T) -> Option<String> {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
 }
 
 #[cfg(test)]
@@ -245,6 +565,7 @@ mod tests {
         RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req1],
         }
     }
@@ -254,10 +575,56 @@ mod tests {
         let config = create_test_config();
         let graph = RequirementGraph::from_config(&config).unwrap();
 
-        assert_eq!(graph.requirements.len(), 3);
+        assert_eq!(graph.len(), 3);
         assert!(graph.get("Requirement 1").is_some());
     }
 
+    #[test]
+    fn test_requirements_are_stored_once() {
+        let config = create_test_config();
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        // Requirements come back with their nested children, as in the config
+        let parent = graph.get("Requirement 1").unwrap();
+        assert_eq!(parent, &config.requirements[0]);
+        let child = graph.get("Requirement 2").unwrap();
+        assert!(matches!(
+            &parent.requirements[0],
+            RequirementReference::Full(nested) if std::ptr::eq(nested.as_ref(), child)
+        ));
+        assert_eq!(graph.dependencies("Requirement 1").unwrap(), vec![child]);
+        assert_eq!(graph.trees.len(), 1);
+
+        // Summaries are interned: the lookup and the node share the text
+        let (key, &node) = graph.summary_to_node.iter().next().unwrap();
+        assert!(Arc::ptr_eq(key, &graph.graph[node].summary));
+
+        // Taking over a config moves its requirements instead of copying them
+        let owned = RequirementGraph::try_from(config.clone()).unwrap();
+        assert_eq!(owned.get("Requirement 1"), Some(parent));
+    }
+
+    #[test]
+    fn test_clones_share_trees_until_edited() {
+        let mut config = create_test_config();
+        config.requirements.push(Requirement::new("Other"));
+        let graph = RequirementGraph::try_from(config).unwrap();
+        let mut edited = graph.clone();
+        let shared = |a: &RequirementGraph, b: &RequirementGraph, tree: usize| {
+            Arc::ptr_eq(
+                a.trees[tree].as_ref().unwrap(),
+                b.trees[tree].as_ref().unwrap(),
+            )
+        };
+        assert!(shared(&graph, &edited, 0) && shared(&graph, &edited, 1));
+
+        edited.add_edge("Other", "Requirement 3").unwrap();
+        assert!(shared(&graph, &edited, 0));
+        assert!(!shared(&graph, &edited, 1));
+        assert!(graph.get("Other").unwrap().requirements.is_empty());
+        assert_eq!(edited.get("Other").unwrap().requirements.len(), 1);
+    }
+
     #[test]
     fn test_no_cycles() {
         let config = create_test_config();
@@ -311,6 +678,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req1_with_ref, req2_with_ref],
         };
 
@@ -330,6 +698,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req],
         };
 
@@ -350,7 +719,7 @@ mod tests {
     fn test_topological_sort_cyclic() {
         let mut req1 = Requirement::new("A");
         let mut req2 = Requirement::new("B");
-        
+
         req1.requirements
             .push(RequirementReference::Reference("B".to_string()));
         req2.requirements
@@ -359,6 +728,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req1, req2],
         };
 
@@ -439,6 +809,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req1, req2, req3, req4],
         };
 
@@ -466,6 +837,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req1, req2, req3],
         };
 
@@ -482,12 +854,13 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![],
         };
 
         let graph = RequirementGraph::from_config(&config).unwrap();
         assert!(!graph.has_cycles());
-        assert_eq!(graph.requirements.len(), 0);
+        assert_eq!(graph.len(), 0);
     }
 
     #[test]
@@ -496,12 +869,349 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req],
         };
 
         let graph = RequirementGraph::from_config(&config).unwrap();
         assert!(!graph.has_cycles());
-        assert_eq!(graph.requirements.len(), 1);
+        assert_eq!(graph.len(), 1);
     }
-}
 
+    #[test]
+    fn test_with_tag_prefix() {
+        let mut hv = Requirement::new("HV");
+        hv.tags = vec!["safety/electrical/hv".to_string()];
+        let mut perf = Requirement::new("Perf");
+        perf.tags = vec!["performance".to_string()];
+        let mut lv = Requirement::new("LV");
+        lv.tags = vec!["safety/electrical/lv".to_string()];
+
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![hv, perf, lv],
+        };
+
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        let safety: Vec<_> = graph
+            .with_tag_prefix("safety")
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(safety, vec!["HV", "LV"]);
+        assert_eq!(graph.with_tag_prefix("safety/electrical/hv").len(), 1);
+        assert!(graph.with_tag_prefix("safe").is_empty());
+    }
+
+    #[test]
+    fn test_supersession_chain() {
+        let mut v1 = Requirement::new("V1");
+        v1.superseded_by = Some("V2".to_string());
+        let mut v2 = Requirement::new("V2");
+        v2.superseded_by = Some("V3".to_string());
+        let v3 = Requirement::new("V3");
+
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![v1, v2, v3],
+        };
+
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        let chain: Vec<_> = graph
+            .supersession_chain("V1")
+            .unwrap()
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(chain, vec!["V1", "V2", "V3"]);
+        assert_eq!(graph.supersession_chain("V3").unwrap().len(), 1);
+        assert!(graph.supersession_chain("Missing").is_err());
+    }
+
+    #[test]
+    fn test_rollup_estimate_counts_shared_child_once() {
+        // Root -> A -> Shared, Root -> B -> Shared
+        let mut shared = Requirement::new("Shared");
+        shared.estimate = Some(5.0);
+        let mut a = Requirement::new("A");
+        a.estimate = Some(2.0);
+        a.requirements
+            .push(RequirementReference::Full(Box::new(shared)));
+        let mut b = Requirement::new("B");
+        b.estimate = Some(3.0);
+        b.requirements
+            .push(RequirementReference::Reference("Shared".to_string()));
+        let mut root = Requirement::new("Root");
+        root.estimate = Some(1.0);
+        root.requirements
+            .push(RequirementReference::Full(Box::new(a)));
+        root.requirements
+            .push(RequirementReference::Full(Box::new(b)));
+
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![root],
+        };
+
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        assert_eq!(graph.rollup_estimate("Root").unwrap(), 11.0);
+        assert_eq!(graph.rollup_estimate("B").unwrap(), 8.0);
+        assert!(graph.rollup_estimate("Missing").is_err());
+    }
+
+    #[test]
+    fn test_rollup_estimate_with_cycle() {
+        let mut a = Requirement::new("A");
+        a.estimate = Some(1.5);
+        a.requirements
+            .push(RequirementReference::Reference("B".to_string()));
+        let mut b = Requirement::new("B");
+        b.estimate = Some(2.5);
+        b.requirements
+            .push(RequirementReference::Reference("A".to_string()));
+
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![a, b],
+        };
+
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        assert_eq!(graph.rollup_estimate("A").unwrap(), 4.0);
+    }
+
+    #[test]
+    fn test_requirements_for_release() {
+        let mut a = Requirement::new("A");
+        a.target_release = Some("2.0".to_string());
+        let mut b = Requirement::new("B");
+        b.target_release = Some("1.0".to_string());
+        let mut c = Requirement::new("C");
+        c.target_release = Some("2.0".to_string());
+
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![a, b, c],
+        };
+
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        let summaries: Vec<_> = graph
+            .requirements_for_release("2.0")
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["A", "C"]);
+        assert!(graph.requirements_for_release("3.0").is_empty());
+    }
+
+    fn chain_config(len: usize) -> RequirementConfig {
+        let requirements = (0..len)
+            .map(|i| {
+                let mut req = Requirement::new(format!("R{}", i));
+                if i + 1 < len {
+                    req.requirements
+                        .push(RequirementReference::Reference(format!("R{}", i + 1)));
+                }
+                req
+            })
+            .collect();
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        }
+    }
+
+    #[test]
+    fn test_traverse_deep_chain() {
+        let graph = RequirementGraph::from_config(&chain_config(5000)).unwrap();
+
+        let mut deepest = 0;
+        graph
+            .traverse("R0", |_, depth| {
+                deepest = depth;
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(deepest, 4999);
+        assert!(!graph.has_cycles());
+    }
+
+    #[test]
+    fn test_traverse_with_max_depth() {
+        let graph = RequirementGraph::from_config(&chain_config(10)).unwrap();
+
+        assert!(graph
+            .traverse_with("R0", &TraversalOptions::with_max_depth(9), |_, _| Ok(()))
+            .is_ok());
+        let result = graph.traverse_with("R0", &TraversalOptions::with_max_depth(5), |_, _| Ok(()));
+        assert!(matches!(result, Err(Error::GraphError(_))));
+    }
+
+    #[test]
+    fn test_traverse_declaration_order() {
+        let config = create_test_config();
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let mut visited = vec![];
+        graph
+            .traverse("Requirement 1", |req, depth| {
+                visited.push((req.summary.clone(), depth));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(
+            visited,
+            vec![
+                ("Requirement 1".to_string(), 0),
+                ("Requirement 2".to_string(), 1),
+                ("Requirement 3".to_string(), 2),
+            ]
+        );
+    }
+
+    fn reference_config(links: &[(&str, &str)], summaries: &[&str]) -> RequirementConfig {
+        let requirements = summaries
+            .iter()
+            .map(|&summary| {
+                let mut req = Requirement::new(summary);
+                for (_, to) in links.iter().filter(|(from, _)| *from == summary) {
+                    req.requirements
+                        .push(RequirementReference::Reference(to.to_string()));
+                }
+                req
+            })
+            .collect();
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        }
+    }
+
+    #[test]
+    fn test_overlapping_cycles_form_one_component() {
+        let config = reference_config(
+            &[("A", "B"), ("B", "A"), ("B", "C"), ("C", "B"), ("D", "A")],
+            &["A", "B", "C", "D"],
+        );
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].members, vec!["A", "B", "C"]);
+        assert_eq!(cycles[0].edges.len(), 4);
+        assert!(!cycles[0].members.contains(&"D".to_string()));
+    }
+
+    #[test]
+    fn test_cycle_members_in_cycle_order() {
+        let config = reference_config(&[("A", "C"), ("C", "B"), ("B", "A")], &["A", "B", "C"]);
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        assert_eq!(graph.find_cycles(), vec![vec!["A", "C", "B"]]);
+    }
+
+    #[test]
+    fn test_check_acyclic_names_edges() {
+        let config = reference_config(&[("A", "B"), ("B", "A"), ("C", "C")], &["A", "B", "C"]);
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let Err(Error::CircularReference(message)) = graph.check_acyclic() else {
+            panic!("expected a circular reference error");
+        };
+        assert_eq!(
+            message,
+            "cycle through 'A' -> 'B', 'B' -> 'A'; cycle through 'C' -> 'C'"
+        );
+        assert!(matches!(
+            graph.topological_sort(),
+            Err(Error::CircularReference(_))
+        ));
+
+        let acyclic = RequirementGraph::from_config(&create_test_config()).unwrap();
+        assert!(acyclic.check_acyclic().is_ok());
+    }
+
+    #[test]
+    fn test_find_cycles_deep_cycle() {
+        let mut config = chain_config(5000);
+        config.requirements[4999]
+            .requirements
+            .push(RequirementReference::Reference("R0".to_string()));
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let cycles = graph.find_cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].len(), 5000);
+    }
+
+    #[test]
+    fn test_dependents_uses_reverse_index() {
+        let config = reference_config(&[("A", "C"), ("B", "C"), ("A", "C")], &["A", "B", "C"]);
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let dependents: Vec<_> = graph
+            .dependents("C")
+            .unwrap()
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(dependents, vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_ancestors_and_roots() {
+        // Top -> Mid -> Leaf, Other -> Leaf, Top -> Other
+        let config = reference_config(
+            &[
+                ("Top", "Mid"),
+                ("Top", "Other"),
+                ("Mid", "Leaf"),
+                ("Other", "Leaf"),
+            ],
+            &["Top", "Mid", "Other", "Leaf", "Alone"],
+        );
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let names = |reqs: Vec<&Requirement>| -> Vec<String> {
+            reqs.iter().map(|r| r.summary.clone()).collect()
+        };
+        assert_eq!(
+            names(graph.ancestors("Leaf").unwrap()),
+            vec!["Mid", "Other", "Top"]
+        );
+        assert_eq!(names(graph.roots_of("Leaf").unwrap()), vec!["Top"]);
+        assert_eq!(names(graph.roots_of("Alone").unwrap()), vec!["Alone"]);
+        assert!(graph.ancestors("Top").unwrap().is_empty());
+        assert!(graph.ancestors("Missing").is_err());
+    }
+
+    #[test]
+    fn test_ancestors_with_cycle() {
+        let config = reference_config(&[("A", "B"), ("B", "A"), ("B", "C")], &["A", "B", "C"]);
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let ancestors: Vec<_> = graph
+            .ancestors("A")
+            .unwrap()
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(ancestors, vec!["B"]);
+        assert!(graph.roots_of("C").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Structural analysis of a requirement set
+//!
+//! Reports orphaned requirements, leaves without acceptance criteria,
+//! references that resolve to nothing, near-duplicate requirements when
+//! enabled, and drafts left untouched for too long. Works on a
+//! `RequirementConfig` so that dangling references, which prevent building
+//! a `RequirementGraph`, can be reported too.
+
+pub(crate) mod similarity;
+mod stale;
+
+use crate::validator::{Finding, Severity};
+use crate::{Error, Requirement, RequirementConfig};
+use serde::{Deserialize, Serialize};
+use similarity::Text;
+use std::collections::HashSet;
+
+pub use stale::{last_updated, stale_requirements, StaleRequirement, DEFAULT_STALE_DAYS};
+
+/// Descriptions shorter than this many words are not compared
+const MIN_DESCRIPTION_WORDS: usize = 5;
+
+/// Similarity at which two requirements count as near-duplicates, for
+/// callers enabling the check
+pub const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// Kind of structural finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// Requirement has no parent and is not a declared root
+    Orphan,
+
+    /// Requirement has no children and no acceptance criteria
+    LeafWithoutAcceptance,
+
+    /// Reference to a summary that does not exist
+    DanglingReference,
+
+    /// Summary or description nearly repeats an earlier requirement's
+    NearDuplicate,
+}
+
+impl FindingKind {
+    /// ID of the rule reporting findings of this kind in validation reports
+    pub fn rule(self) -> &'static str {
+        match self {
+            FindingKind::Orphan => "orphan",
+            FindingKind::LeafWithoutAcceptance => "leaf-without-acceptance",
+            FindingKind::DanglingReference => "dangling-reference",
+            FindingKind::NearDuplicate => "near-duplicate",
+        }
+    }
+}
+
+/// A single structural finding
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnalysisFinding {
+    /// What was found
+    pub kind: FindingKind,
+
+    /// Summary of the requirement the finding is about
+    pub summary: String,
+
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Options for structural analysis
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnalysisOptions {
+    /// Summaries that are allowed to have no parent
+    ///
+    /// When unset, top-level requirements that have children count as roots.
+    pub roots: Option<Vec<String>>,
+
+    /// Similarity in `[0, 1]` at which two requirements count as near-duplicates
+    ///
+    /// Unset by default, as the check compares every pair of requirements.
+    pub similarity_threshold: Option<f64>,
+
+    /// Summaries of archived requirements, which references may still name
+    pub archived: Vec<String>,
+}
+
+impl AnalysisFinding {
+    /// The finding as a warning of a validation report
+    pub fn to_finding(&self) -> Finding {
+        Finding {
+            rule: self.kind.rule().to_string(),
+            severity: Severity::Warning,
+            error: Error::custom(self.message.clone()),
+            requirement: Some(self.summary.clone()),
+        }
+    }
+}
+
+/// Run all structural checks over a config
+pub fn analyze(config: &RequirementConfig, options: &AnalysisOptions) -> Vec<AnalysisFinding> {
+    let all = config.all_requirements();
+    let known: HashSet<&str> = all.iter().map(|req| req.summary.as_str()).collect();
+    let mut has_parent: HashSet<&str> = HashSet::new();
+    let mut findings = vec![];
+
+    for req in &all {
+        for child in &req.requirements {
+            let target = child.summary();
+            if known.contains(target) {
+                has_parent.insert(target);
+            } else if !options.archived.iter().any(|archived| archived == target) {
+                findings.push(AnalysisFinding {
+                    kind: FindingKind::DanglingReference,
+                    summary: req.summary.clone(),
+                    message: format!(
+                        "Requirement '{}' references non-existent '{}'",
+                        req.summary, target
+                    ),
+                });
+            }
+        }
+    }
+
+    let is_root = |summary: &str| match &options.roots {
+        Some(roots) => roots.iter().any(|root| root == summary),
+        None => config
+            .requirements
+            .iter()
+            .any(|req| req.summary == summary && !req.requirements.is_empty()),
+    };
+
+    for req in &all {
+        if !has_parent.contains(req.summary.as_str()) && !is_root(&req.summary) {
+            findings.push(AnalysisFinding {
+                kind: FindingKind::Orphan,
+                summary: req.summary.clone(),
+                message: format!(
+                    "Requirement '{}' has no parent and is not a root",
+                    req.summary
+                ),
+            });
+        }
+
+        if req.requirements.is_empty()
+            && req.acceptance_test.is_none()
+            && req.acceptance_test_link.is_none()
+        {
+            findings.push(AnalysisFinding {
+                kind: FindingKind::LeafWithoutAcceptance,
+                summary: req.summary.clone(),
+                message: format!(
+                    "Leaf requirement '{}' has no acceptance criteria",
+                    req.summary
+                ),
+            });
+        }
+    }
+
+    if let Some(threshold) = options.similarity_threshold {
+        findings.extend(near_duplicates(&all, threshold));
+    }
+    findings
+}
+
+/// Compare every pair of requirements, reporting the later one of each similar pair
+///
+/// Each text is tokenized once, and pairs whose lengths alone rule out
+/// reaching `threshold` are not compared further. Identical summaries are
+/// left to the duplicate-summary validation rule.
+fn near_duplicates(all: &[&Requirement], threshold: f64) -> Vec<AnalysisFinding> {
+    let texts: Vec<(Text, Option<Text>)> = all
+        .iter()
+        .map(|req| {
+            let description = req
+                .description
+                .as_deref()
+                .map(Text::new)
+                .filter(|text| text.word_count() >= MIN_DESCRIPTION_WORDS);
+            (Text::new(&req.summary), description)
+        })
+        .collect();
+    // Similarities known to be below the threshold count as 0
+    let edit = |a: &Text, b: &Text| {
+        if a.max_edit_similarity(b) < threshold {
+            0.0
+        } else {
+            a.edit_similarity(b)
+        }
+    };
+    let token = |a: &Text, b: &Text| {
+        if a.max_token_similarity(b) < threshold {
+            0.0
+        } else {
+            a.token_similarity(b)
+        }
+    };
+
+    let mut findings = vec![];
+    for (i, later) in all.iter().enumerate() {
+        let (later_summary, later_description) = &texts[i];
+        for (earlier, (earlier_summary, earlier_description)) in all[..i].iter().zip(&texts) {
+            if earlier.summary == later.summary {
+                continue;
+            }
+
+            let summary =
+                edit(earlier_summary, later_summary).max(token(earlier_summary, later_summary));
+            let description = match (earlier_description, later_description) {
+                (Some(a), Some(b)) => token(a, b),
+                _ => 0.0,
+            };
+
+            let (field, score) = if summary >= description {
+                ("summary", summary)
+            } else {
+                ("description", description)
+            };
+            if score >= threshold {
+                findings.push(AnalysisFinding {
+                    kind: FindingKind::NearDuplicate,
+                    summary: later.summary.clone(),
+                    message: format!(
+                        "Requirement '{}' is a near-duplicate of '{}' ({} {:.0}% similar)",
+                        later.summary,
+                        earlier.summary,
+                        field,
+                        score * 100.0
+                    ),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Root
+    requirements:
+      - summary: Tested Leaf
+        acceptance_test: Works
+      - summary: Untested Leaf
+      - Ghost
+  - summary: Lonely
+    acceptance_test_link: https://example.com/test
+"#;
+
+    fn kinds(findings: &[AnalysisFinding], summary: &str) -> Vec<FindingKind> {
+        findings
+            .iter()
+            .filter(|f| f.summary == summary)
+            .map(|f| f.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_analyze_default_roots() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let findings = analyze(&config, &AnalysisOptions::default());
+
+        assert_eq!(
+            kinds(&findings, "Root"),
+            vec![FindingKind::DanglingReference]
+        );
+        assert!(findings[0].message.contains("'Ghost'"));
+        let finding = findings[0].to_finding();
+        assert_eq!(finding.rule, "dangling-reference");
+        assert_eq!(finding.severity, Severity::Warning);
+        assert_eq!(finding.message(), findings[0].message);
+        assert!(kinds(&findings, "Tested Leaf").is_empty());
+        assert_eq!(
+            kinds(&findings, "Untested Leaf"),
+            vec![FindingKind::LeafWithoutAcceptance]
+        );
+        assert_eq!(kinds(&findings, "Lonely"), vec![FindingKind::Orphan]);
+    }
+
+    #[test]
+    fn test_analyze_declared_roots() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let options = AnalysisOptions {
+            roots: Some(vec!["Lonely".to_string()]),
+            ..AnalysisOptions::default()
+        };
+        let findings = analyze(&config, &options);
+
+        assert!(kinds(&findings, "Lonely").is_empty());
+        assert!(kinds(&findings, "Root").contains(&FindingKind::Orphan));
+    }
+
+    #[test]
+    fn test_analyze_near_duplicates() {
+        let config = Parser::parse_str(
+            r#"
+version: "1.0"
+requirements:
+  - summary: Root
+    requirements:
+      - summary: Users can reset their password
+        acceptance_test: Works
+      - summary: User can reset their password
+        acceptance_test: Works
+      - summary: Export monthly report
+        description: The system exports a monthly usage report as PDF
+        acceptance_test: Works
+      - summary: Generate usage summary
+        description: As PDF, the system exports a usage report monthly
+        acceptance_test: Works
+"#,
+        )
+        .unwrap();
+
+        let options = AnalysisOptions {
+            similarity_threshold: Some(DEFAULT_SIMILARITY_THRESHOLD),
+            ..AnalysisOptions::default()
+        };
+        let findings = analyze(&config, &options);
+        let duplicates: Vec<&AnalysisFinding> = findings
+            .iter()
+            .filter(|f| f.kind == FindingKind::NearDuplicate)
+            .collect();
+        assert_eq!(duplicates.len(), 2);
+        assert_eq!(duplicates[0].summary, "User can reset their password");
+        assert!(duplicates[0]
+            .message
+            .contains("of 'Users can reset their password' (summary"));
+        assert_eq!(duplicates[1].summary, "Generate usage summary");
+        assert!(duplicates[1].message.contains("(description 100% similar)"));
+
+        // Off by default
+        assert!(!analyze(&config, &AnalysisOptions::default())
+            .iter()
+            .any(|f| f.kind == FindingKind::NearDuplicate));
+    }
+}
@@ -0,0 +1,145 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Text similarity measures for near-duplicate detection
+
+use std::collections::HashSet;
+
+/// Lowercase words of a text, ignoring punctuation
+pub fn tokens(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// A text tokenized once, to be compared with many others
+pub struct Text {
+    /// The words joined by single spaces
+    normalized: Vec<char>,
+    words: HashSet<String>,
+    word_count: usize,
+}
+
+impl Text {
+    pub fn new(text: &str) -> Self {
+        let tokens = tokens(text);
+        Self {
+            normalized: tokens.join(" ").chars().collect(),
+            word_count: tokens.len(),
+            words: tokens.into_iter().collect(),
+        }
+    }
+
+    /// Number of words, repeated ones included
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    /// Similarity in `[0, 1]` from the edit distance between the texts
+    pub fn edit_similarity(&self, other: &Text) -> f64 {
+        let longest = self.normalized.len().max(other.normalized.len());
+        if longest == 0 {
+            return 1.0;
+        }
+        1.0 - levenshtein(&self.normalized, &other.normalized) as f64 / longest as f64
+    }
+
+    /// Upper bound of [`Text::edit_similarity`] from the lengths alone
+    pub fn max_edit_similarity(&self, other: &Text) -> f64 {
+        let (a, b) = (self.normalized.len(), other.normalized.len());
+        if a.max(b) == 0 {
+            return 1.0;
+        }
+        a.min(b) as f64 / a.max(b) as f64
+    }
+
+    /// Jaccard similarity in `[0, 1]` of the word sets of the texts
+    pub fn token_similarity(&self, other: &Text) -> f64 {
+        let union = self.words.union(&other.words).count();
+        if union == 0 {
+            return 1.0;
+        }
+        self.words.intersection(&other.words).count() as f64 / union as f64
+    }
+
+    /// Upper bound of [`Text::token_similarity`] from the set sizes alone
+    pub fn max_token_similarity(&self, other: &Text) -> f64 {
+        let (a, b) = (self.words.len(), other.words.len());
+        if a.max(b) == 0 {
+            return 1.0;
+        }
+        a.min(b) as f64 / a.max(b) as f64
+    }
+}
+
+/// Similarity in `[0, 1]` from the edit distance between normalized texts
+pub fn edit_similarity(a: &str, b: &str) -> f64 {
+    Text::new(a).edit_similarity(&Text::new(b))
+}
+
+/// Jaccard similarity in `[0, 1]` of the word sets of two texts
+pub fn token_similarity(a: &str, b: &str) -> f64 {
+    Text::new(a).token_similarity(&Text::new(b))
+}
+
+/// Number of single-character edits turning `a` into `b`
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(levenshtein(&chars("kitten"), &chars("sitting")), 3);
+        assert_eq!(levenshtein(&chars(""), &chars("abc")), 3);
+        assert_eq!(levenshtein(&chars("same"), &chars("same")), 0);
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(edit_similarity("User Login!", "user login"), 1.0);
+        assert!(edit_similarity("User can log in", "Users can log in") > 0.9);
+        assert!(edit_similarity("User login", "Export reports") < 0.5);
+
+        assert_eq!(
+            token_similarity("the user logs in", "in the user logs"),
+            1.0
+        );
+        assert_eq!(token_similarity("a b", "b c"), 1.0 / 3.0);
+        assert_eq!(token_similarity("", ""), 1.0);
+    }
+
+    #[test]
+    fn test_bounds_are_upper_bounds() {
+        let pairs = [
+            ("User can log in", "Users can log in"),
+            ("User login", "Export monthly reports as PDF"),
+            ("a b c d", "d c"),
+            ("", "something"),
+        ];
+        for (a, b) in pairs {
+            let (a, b) = (Text::new(a), Text::new(b));
+            assert!(a.max_edit_similarity(&b) >= a.edit_similarity(&b));
+            assert!(a.max_token_similarity(&b) >= a.token_similarity(&b));
+        }
+        assert_eq!(Text::new("a b, a").word_count(), 3);
+    }
+}
@@ -0,0 +1,159 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Draft and proposed requirements nobody has touched for a while
+
+use crate::error::Result;
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, Status};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Days without changes after which an undecided requirement is stale
+pub const DEFAULT_STALE_DAYS: u32 = 90;
+
+/// A requirement still in `draft` or `proposed` long after its last change
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StaleRequirement {
+    pub summary: String,
+
+    pub status: Status,
+
+    /// When the requirement last changed
+    pub updated_at: DateTime<Utc>,
+
+    /// Whole days since the last change
+    pub days: i64,
+}
+
+/// When a requirement last changed
+///
+/// Its own `updated_at`, as written in the file or taken from git history
+/// by `git::enrich`, wins over the one in its metadata. Timestamps are
+/// RFC 3339 or plain dates.
+pub fn last_updated(
+    req: &Requirement,
+    store: Option<&MetadataStore>,
+) -> Result<Option<DateTime<Utc>>> {
+    if let Some(time) = req.updated_at.as_deref().and_then(parse_timestamp) {
+        return Ok(Some(time));
+    }
+    match store {
+        Some(store) => Ok(store
+            .find_metadata(&req.summary)?
+            .map(|meta| meta.updated_at)),
+        None => Ok(None),
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+        })
+}
+
+/// Requirements in `draft` or `proposed` unchanged for more than `max_days` before `now`
+///
+/// Requirements without a known timestamp are not reported.
+pub fn stale_requirements(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    max_days: u32,
+    now: DateTime<Utc>,
+) -> Result<Vec<StaleRequirement>> {
+    let mut stale = vec![];
+    for req in config.all_requirements() {
+        let Some(status) = req
+            .status
+            .filter(|status| matches!(status, Status::Draft | Status::Proposed))
+        else {
+            continue;
+        };
+        let Some(updated_at) = last_updated(req, store)? else {
+            continue;
+        };
+        let days = (now - updated_at).num_days();
+        if days > i64::from(max_days) {
+            stale.push(StaleRequirement {
+                summary: req.summary.clone(),
+                status,
+                updated_at,
+                days,
+            });
+        }
+    }
+    Ok(stale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    status: draft
+    updated_at: "2025-01-01T12:00:00Z"
+  - summary: Logout
+    status: proposed
+    updated_at: 2025-03-20
+  - summary: Export
+    status: approved
+    updated_at: "2024-01-01T00:00:00Z"
+  - summary: Import
+    status: draft
+"#;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-04-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_stale_requirements_uses_threshold_and_status() {
+        let config = Parser::parse_str(YAML).unwrap();
+
+        let stale = stale_requirements(&config, None, 30, now()).unwrap();
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].summary, "Login");
+        assert_eq!(stale[0].status, Status::Draft);
+        assert_eq!(stale[0].days, 89);
+
+        let summaries: Vec<_> = stale_requirements(&config, None, 5, now())
+            .unwrap()
+            .into_iter()
+            .map(|stale| stale.summary)
+            .collect();
+        assert_eq!(summaries, vec!["Login", "Logout"]);
+        assert!(stale_requirements(&config, None, 90, now())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_last_updated_falls_back_to_metadata() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "STL".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        let import = config.all_requirements()[3];
+        assert_eq!(last_updated(import, None).unwrap(), None);
+
+        let meta = store.get_or_create_metadata(import).unwrap();
+        assert_eq!(
+            last_updated(import, Some(&store)).unwrap(),
+            Some(meta.updated_at)
+        );
+        let later = now() + chrono::Duration::days(365 * 10);
+        let stale = stale_requirements(&config, Some(&store), 90, later).unwrap();
+        assert!(stale.iter().any(|stale| stale.summary == "Import"));
+    }
+}
@@ -0,0 +1,216 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Coverage between requirement levels
+//!
+//! Compares two partitions of the graph, such as system and software
+//! requirements, and reports how they trace to each other. The result is a
+//! serializable matrix suitable as compliance evidence.
+
+use super::RequirementGraph;
+use crate::metadata::tag_has_prefix;
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+/// A set of requirements forming one level of the hierarchy
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Partition {
+    /// Requirements with a tag equal to or underneath this prefix
+    Tag(String),
+
+    /// Requirements at this depth below a root, along their shortest route
+    Depth(usize),
+}
+
+/// The downstream requirements an upper-level requirement traces to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CoverageRow {
+    /// Summary of the upper-level requirement
+    pub summary: String,
+
+    /// Summaries of the lower-level requirements reachable from it
+    pub covered_by: Vec<String>,
+}
+
+/// Traceability between an upper and a lower partition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Coverage {
+    /// One row per upper-level requirement, in declaration order
+    pub matrix: Vec<CoverageRow>,
+
+    /// Upper-level requirements with no lower-level requirement beneath them
+    pub uncovered: Vec<String>,
+
+    /// Lower-level requirements with no upper-level requirement above them
+    pub untraced: Vec<String>,
+}
+
+impl Coverage {
+    /// Check whether every requirement in both partitions traces to the other
+    pub fn is_complete(&self) -> bool {
+        self.uncovered.is_empty() && self.untraced.is_empty()
+    }
+}
+
+impl RequirementGraph {
+    /// Report coverage between an upper and a lower partition
+    ///
+    /// Links are followed through any intermediate requirements, so a system
+    /// requirement covered through an untagged grouping node still counts.
+    pub fn coverage(&self, upper: &Partition, lower: &Partition) -> Coverage {
+        let levels = self.levels();
+        let members = |partition: &Partition| -> Vec<NodeIndex> {
+            self.graph
+                .node_indices()
+                .filter(|&node| match partition {
+                    Partition::Tag(prefix) => self
+                        .requirement_at(node)
+                        .tags
+                        .iter()
+                        .any(|t| tag_has_prefix(t, prefix)),
+                    Partition::Depth(depth) => levels.get(&node) == Some(depth),
+                })
+                .collect()
+        };
+        let upper_nodes = members(upper);
+        let lower_nodes: HashSet<NodeIndex> = members(lower).into_iter().collect();
+
+        let mut traced = HashSet::new();
+        let mut matrix = vec![];
+        let mut uncovered = vec![];
+        for &node in &upper_nodes {
+            let covered: BTreeSet<NodeIndex> = self
+                .descendant_nodes(node)
+                .into_iter()
+                .filter(|n| lower_nodes.contains(n))
+                .collect();
+            if covered.is_empty() {
+                uncovered.push(self.summary(node).to_string());
+            }
+            traced.extend(covered.iter().copied());
+            matrix.push(CoverageRow {
+                summary: self.summary(node).to_string(),
+                covered_by: covered
+                    .iter()
+                    .map(|&n| self.summary(n).to_string())
+                    .collect(),
+            });
+        }
+
+        let mut untraced: Vec<NodeIndex> = lower_nodes
+            .into_iter()
+            .filter(|n| !traced.contains(n))
+            .collect();
+        untraced.sort();
+
+        Coverage {
+            matrix,
+            uncovered,
+            untraced: untraced
+                .iter()
+                .map(|&n| self.summary(n).to_string())
+                .collect(),
+        }
+    }
+
+    /// Every node reachable from `node`, excluding itself unless on a cycle
+    fn descendant_nodes(&self, node: NodeIndex) -> HashSet<NodeIndex> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([node]);
+        while let Some(current) = queue.pop_front() {
+            for child in self.children_in_order(current) {
+                if seen.insert(child) {
+                    queue.push_back(child);
+                }
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RequirementReference;
+    use crate::{Requirement, RequirementConfig};
+
+    fn tagged(summary: &str, tag: &str, children: &[&str]) -> Requirement {
+        let mut req = Requirement::new(summary);
+        if !tag.is_empty() {
+            req.tags.push(tag.to_string());
+        }
+        for child in children {
+            req.requirements
+                .push(RequirementReference::Reference(child.to_string()));
+        }
+        req
+    }
+
+    fn graph() -> RequirementGraph {
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![
+                tagged("SYS-1", "system", &["Group"]),
+                tagged("Group", "", &["SW-1", "SW-2"]),
+                tagged("SYS-2", "system/safety", &[]),
+                tagged("SW-1", "software", &[]),
+                tagged("SW-2", "software", &[]),
+                tagged("SW-3", "software", &[]),
+            ],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_coverage_by_tag() {
+        let coverage = graph().coverage(
+            &Partition::Tag("system".to_string()),
+            &Partition::Tag("software".to_string()),
+        );
+
+        assert_eq!(
+            coverage.matrix,
+            vec![
+                CoverageRow {
+                    summary: "SYS-1".to_string(),
+                    covered_by: vec!["SW-1".to_string(), "SW-2".to_string()],
+                },
+                CoverageRow {
+                    summary: "SYS-2".to_string(),
+                    covered_by: vec![],
+                },
+            ]
+        );
+        assert_eq!(coverage.uncovered, vec!["SYS-2"]);
+        assert_eq!(coverage.untraced, vec!["SW-3"]);
+        assert!(!coverage.is_complete());
+    }
+
+    #[test]
+    fn test_coverage_by_depth() {
+        let coverage = graph().coverage(&Partition::Depth(0), &Partition::Depth(1));
+
+        // Roots are SYS-1, SYS-2 and SW-3; only Group sits at depth one
+        assert_eq!(coverage.matrix[0].covered_by, vec!["Group"]);
+        assert_eq!(coverage.uncovered, vec!["SYS-2", "SW-3"]);
+        assert!(coverage.untraced.is_empty());
+    }
+
+    #[test]
+    fn test_coverage_serializes() {
+        let coverage = graph().coverage(
+            &Partition::Tag("system".to_string()),
+            &Partition::Tag("software".to_string()),
+        );
+        let json = serde_json::to_value(&coverage).unwrap();
+        assert_eq!(json["untraced"][0], "SW-3");
+
+        let partition: Partition = serde_json::from_str(r#"{"tag": "system"}"#).unwrap();
+        assert_eq!(partition, Partition::Tag("system".to_string()));
+    }
+}
@@ -0,0 +1,170 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Critical path through outstanding work
+
+use super::RequirementGraph;
+use crate::types::Status;
+use crate::{Error, Requirement, Result};
+use petgraph::graph::NodeIndex;
+use std::collections::HashMap;
+
+/// The chain of outstanding requirements with the largest combined estimate
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriticalPath<'a> {
+    /// Requirements along the path, from parent to child
+    pub requirements: Vec<&'a Requirement>,
+
+    /// Sum of the estimates along the path
+    pub total_estimate: f64,
+}
+
+impl RequirementGraph {
+    /// Find the chain of unimplemented requirements that dominates the schedule
+    ///
+    /// Only requirements that are not yet implemented, verified, or deprecated
+    /// take part, and a requirement without an estimate counts as zero. Ties
+    /// are broken in declaration order. Fails on cyclic graphs.
+    pub fn critical_path(&self) -> Result<CriticalPath<'_>> {
+        self.check_acyclic()?;
+        let sorted = petgraph::algo::toposort(&self.graph, None)
+            .map_err(|_| Error::GraphError("Topological sort failed".to_string()))?;
+
+        // Longest remaining chain starting at each outstanding node, and its next step
+        let mut longest: HashMap<NodeIndex, (f64, Option<NodeIndex>)> = HashMap::new();
+        for &node in sorted.iter().rev() {
+            let req = self.requirement_at(node);
+            if !is_outstanding(req) {
+                continue;
+            }
+
+            let mut best: (f64, Option<NodeIndex>) = (0.0, None);
+            for child in self.children_in_order(node) {
+                if let Some(&(length, _)) = longest.get(&child) {
+                    if best.1.is_none() || length > best.0 {
+                        best = (length, Some(child));
+                    }
+                }
+            }
+            longest.insert(node, (req.estimate.unwrap_or(0.0) + best.0, best.1));
+        }
+
+        let mut start: Option<(NodeIndex, f64)> = None;
+        for node in self.graph.node_indices() {
+            if let Some(&(length, _)) = longest.get(&node) {
+                match start {
+                    Some((_, best)) if length <= best => {}
+                    _ => start = Some((node, length)),
+                }
+            }
+        }
+
+        let mut requirements = vec![];
+        let mut total_estimate = 0.0;
+        if let Some((node, length)) = start {
+            total_estimate = length;
+            let mut current = Some(node);
+            while let Some(node) = current {
+                requirements.push(self.requirement_at(node));
+                current = longest[&node].1;
+            }
+        }
+
+        Ok(CriticalPath {
+            requirements,
+            total_estimate,
+        })
+    }
+}
+
+fn is_outstanding(req: &Requirement) -> bool {
+    !matches!(
+        req.status,
+        Some(Status::Implemented | Status::Verified | Status::Deprecated)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RequirementReference;
+    use crate::RequirementConfig;
+
+    fn estimated(summary: &str, estimate: f64, children: &[&str]) -> Requirement {
+        let mut req = Requirement::new(summary);
+        req.estimate = Some(estimate);
+        for child in children {
+            req.requirements
+                .push(RequirementReference::Reference(child.to_string()));
+        }
+        req
+    }
+
+    fn graph(requirements: Vec<Requirement>) -> RequirementGraph {
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        })
+        .unwrap()
+    }
+
+    fn summaries<'a>(path: &CriticalPath<'a>) -> Vec<&'a str> {
+        path.requirements
+            .iter()
+            .map(|req| req.summary.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_critical_path() {
+        let graph = graph(vec![
+            estimated("Root", 1.0, &["Short", "Long"]),
+            estimated("Short", 2.0, &[]),
+            estimated("Long", 1.0, &["Deep"]),
+            estimated("Deep", 5.0, &[]),
+        ]);
+
+        let path = graph.critical_path().unwrap();
+        assert_eq!(summaries(&path), vec!["Root", "Long", "Deep"]);
+        assert_eq!(path.total_estimate, 7.0);
+    }
+
+    #[test]
+    fn test_critical_path_skips_completed_work() {
+        let mut done = estimated("Deep", 5.0, &[]);
+        done.status = Some(Status::Implemented);
+        let graph = graph(vec![
+            estimated("Root", 1.0, &["Short", "Long"]),
+            estimated("Short", 2.0, &[]),
+            estimated("Long", 1.0, &["Deep"]),
+            done,
+        ]);
+
+        let path = graph.critical_path().unwrap();
+        assert_eq!(summaries(&path), vec!["Root", "Short"]);
+        assert_eq!(path.total_estimate, 3.0);
+    }
+
+    #[test]
+    fn test_critical_path_empty() {
+        let graph = graph(vec![]);
+        let path = graph.critical_path().unwrap();
+        assert!(path.requirements.is_empty());
+        assert_eq!(path.total_estimate, 0.0);
+    }
+
+    #[test]
+    fn test_critical_path_cyclic() {
+        let graph = graph(vec![
+            estimated("A", 1.0, &["B"]),
+            estimated("B", 1.0, &["A"]),
+        ]);
+        assert!(matches!(
+            graph.critical_path(),
+            Err(Error::CircularReference(_))
+        ));
+    }
+}
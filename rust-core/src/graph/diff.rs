@@ -0,0 +1,433 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Differences between two requirement graphs
+
+use super::RequirementGraph;
+use crate::types::Status;
+use crate::{MetadataStore, Requirement, Result};
+use petgraph::visit::EdgeRef;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Change to a single field of a requirement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct FieldChange {
+    /// Field name as it appears in YAML
+    pub field: String,
+
+    /// Previous value (`null` when unset)
+    pub old: Value,
+
+    /// New value (`null` when unset)
+    pub new: Value,
+}
+
+/// A requirement present in both graphs whose fields differ
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RequirementChange {
+    /// Summary in the new graph
+    pub summary: String,
+
+    /// Stable UUID, when metadata was available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<Uuid>,
+
+    /// Field-level changes, in field order
+    pub changes: Vec<FieldChange>,
+}
+
+/// A requirement whose status changed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct StatusTransition {
+    /// Summary in the new graph
+    pub summary: String,
+
+    /// Stable UUID, when metadata was available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<Uuid>,
+
+    pub from: Option<Status>,
+    pub to: Option<Status>,
+}
+
+/// Differences between an old and a new requirement graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct GraphDiff {
+    /// Summaries only present in the new graph
+    pub added: Vec<String>,
+
+    /// Summaries only present in the old graph
+    pub removed: Vec<String>,
+
+    /// Requirements present in both graphs with field changes
+    pub modified: Vec<RequirementChange>,
+
+    /// Parent/child links only present in the new graph
+    pub added_edges: Vec<(String, String)>,
+
+    /// Parent/child links only present in the old graph
+    pub removed_edges: Vec<(String, String)>,
+
+    /// Status changes among the modified requirements
+    #[serde(default)]
+    pub status_transitions: Vec<StatusTransition>,
+}
+
+impl GraphDiff {
+    /// Check whether the graphs are equivalent
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+
+    /// Render the differences for people, one section per kind of change
+    pub fn to_text(&self) -> String {
+        if self.is_empty() {
+            return "No changes\n".to_string();
+        }
+        let value = |value: &Value| match value {
+            Value::Null => "(unset)".to_string(),
+            value => value.to_string(),
+        };
+        let status = |status: Option<Status>| match status {
+            Some(status) => status.to_string(),
+            None => "(unset)".to_string(),
+        };
+
+        let mut out = String::new();
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                let _ = writeln!(out, "{}:", title);
+                for line in lines {
+                    let _ = writeln!(out, "  {}", line);
+                }
+            }
+        };
+        section(
+            "Added",
+            self.added.iter().map(|s| format!("+ {}", s)).collect(),
+        );
+        section(
+            "Removed",
+            self.removed.iter().map(|s| format!("- {}", s)).collect(),
+        );
+        section(
+            "Modified",
+            self.modified
+                .iter()
+                .flat_map(|change| {
+                    std::iter::once(format!("~ {}", change.summary)).chain(
+                        change.changes.iter().map(|field| {
+                            format!(
+                                "    {}: {} -> {}",
+                                field.field,
+                                value(&field.old),
+                                value(&field.new)
+                            )
+                        }),
+                    )
+                })
+                .collect(),
+        );
+        section(
+            "Status transitions",
+            self.status_transitions
+                .iter()
+                .map(|t| format!("{}: {} -> {}", t.summary, status(t.from), status(t.to)))
+                .collect(),
+        );
+        section(
+            "Links added",
+            self.added_edges
+                .iter()
+                .map(|(from, to)| format!("{} -> {}", from, to))
+                .collect(),
+        );
+        section(
+            "Links removed",
+            self.removed_edges
+                .iter()
+                .map(|(from, to)| format!("{} -> {}", from, to))
+                .collect(),
+        );
+        out
+    }
+}
+
+/// Diff two graphs, matching requirements by summary
+pub fn diff(old: &RequirementGraph, new: &RequirementGraph) -> GraphDiff {
+    compute(old, new, &|req| (req.summary.clone(), None))
+}
+
+/// Diff two graphs, matching requirements by their metadata UUID where known
+///
+/// Requirements without stored metadata fall back to matching by summary.
+/// No metadata is created.
+pub fn diff_with_metadata(
+    old: &RequirementGraph,
+    new: &RequirementGraph,
+    store: &MetadataStore,
+) -> Result<GraphDiff> {
+    let mut uuids: HashMap<String, Uuid> = HashMap::new();
+    for graph in [old, new] {
+        for req in graph.requirements() {
+            if let Some(meta) = store.find_metadata(&req.summary)? {
+                uuids.insert(req.summary.clone(), meta.uuid);
+            }
+        }
+    }
+
+    Ok(diff_with_uuids(old, new, &uuids))
+}
+
+/// Diff two graphs, matching requirements by the UUIDs of their summaries
+pub(crate) fn diff_with_uuids(
+    old: &RequirementGraph,
+    new: &RequirementGraph,
+    uuids: &HashMap<String, Uuid>,
+) -> GraphDiff {
+    compute(old, new, &|req| match uuids.get(&req.summary) {
+        Some(uuid) => (uuid.to_string(), Some(*uuid)),
+        None => (req.summary.clone(), None),
+    })
+}
+
+type KeyFn<'a> = dyn Fn(&Requirement) -> (String, Option<Uuid>) + 'a;
+
+fn compute(old: &RequirementGraph, new: &RequirementGraph, key: &KeyFn) -> GraphDiff {
+    let old_by_key = keyed(old, key);
+    let new_by_key = keyed(new, key);
+    let old_lookup: HashMap<&str, &Requirement> = old_by_key
+        .iter()
+        .map(|(k, (req, _))| (k.as_str(), *req))
+        .collect();
+    let new_lookup: HashMap<&str, &Requirement> = new_by_key
+        .iter()
+        .map(|(k, (req, _))| (k.as_str(), *req))
+        .collect();
+    let mut result = GraphDiff::default();
+
+    for (k, (old_req, _)) in &old_by_key {
+        if !new_lookup.contains_key(k.as_str()) {
+            result.removed.push(old_req.summary.clone());
+        }
+    }
+
+    for (k, (new_req, uuid)) in &new_by_key {
+        match old_lookup.get(k.as_str()) {
+            None => result.added.push(new_req.summary.clone()),
+            Some(old_req) => {
+                let changes = field_changes(old_req, new_req);
+                if let Some(change) = changes.iter().find(|c| c.field == "status") {
+                    let status = |value: &Value| serde_json::from_value(value.clone()).ok();
+                    result.status_transitions.push(StatusTransition {
+                        summary: new_req.summary.clone(),
+                        uuid: *uuid,
+                        from: status(&change.old),
+                        to: status(&change.new),
+                    });
+                }
+                if !changes.is_empty() {
+                    result.modified.push(RequirementChange {
+                        summary: new_req.summary.clone(),
+                        uuid: *uuid,
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+
+    let old_edges = edges(old, key);
+    let new_edges = edges(new, key);
+    let display = |graph_edges: &BTreeSet<(String, String)>,
+                   other: &BTreeSet<(String, String)>,
+                   graph: &RequirementGraph| {
+        let names: HashMap<String, String> = graph
+            .requirements()
+            .map(|req| (key(req).0, req.summary.clone()))
+            .collect();
+        graph_edges
+            .difference(other)
+            .map(|(from, to)| (names[from].clone(), names[to].clone()))
+            .collect::<Vec<_>>()
+    };
+    result.added_edges = display(&new_edges, &old_edges, new);
+    result.removed_edges = display(&old_edges, &new_edges, old);
+
+    result
+}
+
+/// Requirements of a graph keyed for matching, in node order
+fn keyed<'a>(
+    graph: &'a RequirementGraph,
+    key: &KeyFn,
+) -> Vec<(String, (&'a Requirement, Option<Uuid>))> {
+    graph
+        .requirements()
+        .map(|req| {
+            let (k, uuid) = key(req);
+            (k, (req, uuid))
+        })
+        .collect()
+}
+
+fn edges(graph: &RequirementGraph, key: &KeyFn) -> BTreeSet<(String, String)> {
+    graph
+        .graph
+        .edge_references()
+        .map(|edge| {
+            let from = graph.requirement_at(edge.source());
+            let to = graph.requirement_at(edge.target());
+            (key(from).0, key(to).0)
+        })
+        .collect()
+}
+
+/// Compare the serialized fields of two requirements, ignoring children
+fn field_changes(old: &Requirement, new: &Requirement) -> Vec<FieldChange> {
+    map_changes(&fields(old), &fields(new))
+}
+
+/// Serialized fields of a requirement, without its children
+pub(crate) fn fields(req: &Requirement) -> serde_json::Map<String, Value> {
+    let mut map = match serde_json::to_value(req) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    map.remove("requirements");
+    map
+}
+
+/// Fields that differ between two field maps, in field order
+pub(crate) fn map_changes(
+    old_map: &serde_json::Map<String, Value>,
+    new_map: &serde_json::Map<String, Value>,
+) -> Vec<FieldChange> {
+    let mut fields: Vec<&String> = new_map.keys().collect();
+    for field in old_map.keys() {
+        if !fields.contains(&field) {
+            fields.push(field);
+        }
+    }
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old_map.get(field).cloned().unwrap_or(Value::Null);
+            let new_value = new_map.get(field).cloned().unwrap_or(Value::Null);
+            (old_value != new_value).then(|| FieldChange {
+                field: field.clone(),
+                old: old_value,
+                new: new_value,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    fn graph(yaml: &str) -> RequirementGraph {
+        RequirementGraph::from_config(&Parser::parse_str(yaml).unwrap()).unwrap()
+    }
+
+    const OLD: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    status: draft
+    requirements:
+      - summary: Password Policy
+      - summary: Legacy SSO
+"#;
+
+    const NEW: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    status: approved
+    owner: "@alice"
+    requirements:
+      - summary: Password Policy
+      - summary: Passkeys
+"#;
+
+    #[test]
+    fn test_diff_by_summary() {
+        let result = diff(&graph(OLD), &graph(NEW));
+
+        assert_eq!(result.added, vec!["Passkeys"]);
+        assert_eq!(result.removed, vec!["Legacy SSO"]);
+        assert_eq!(result.modified.len(), 1);
+
+        let change = &result.modified[0];
+        assert_eq!(change.summary, "Login");
+        assert!(change.uuid.is_none());
+        let fields: Vec<_> = change.changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["owner", "status"]);
+        assert_eq!(change.changes[1].old, Value::String("draft".to_string()));
+        assert_eq!(change.changes[0].old, Value::Null);
+
+        assert_eq!(
+            result.added_edges,
+            vec![("Login".to_string(), "Passkeys".to_string())]
+        );
+        assert_eq!(
+            result.removed_edges,
+            vec![("Login".to_string(), "Legacy SSO".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_status_transitions_and_text() {
+        let result = diff(&graph(OLD), &graph(NEW));
+        assert_eq!(
+            result.status_transitions,
+            vec![StatusTransition {
+                summary: "Login".to_string(),
+                uuid: None,
+                from: Some(Status::Draft),
+                to: Some(Status::Approved),
+            }]
+        );
+
+        let text = result.to_text();
+        assert!(text.starts_with("Added:\n  + Passkeys\nRemoved:\n  - Legacy SSO\n"));
+        assert!(text.contains("  ~ Login\n      owner: (unset) -> \"@alice\"\n"));
+        assert!(text.contains("Status transitions:\n  Login: draft -> approved\n"));
+        assert!(text.ends_with("Links removed:\n  Login -> Legacy SSO\n"));
+        assert_eq!(diff(&graph(OLD), &graph(OLD)).to_text(), "No changes\n");
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert!(diff(&graph(OLD), &graph(OLD)).is_empty());
+    }
+
+    #[test]
+    fn test_diff_with_metadata() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let login = store
+            .get_or_create_metadata(&crate::Requirement::new("Login"))
+            .unwrap();
+
+        let result = diff_with_metadata(&graph(OLD), &graph(NEW), &store).unwrap();
+        assert_eq!(result.modified[0].uuid, Some(login.uuid));
+        assert_eq!(result.added, vec!["Passkeys"]);
+        assert!(store.find_metadata("Passkeys").unwrap().is_none());
+    }
+}
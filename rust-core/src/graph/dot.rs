@@ -0,0 +1,173 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Graphviz DOT export
+
+use super::RequirementGraph;
+use crate::types::{Priority, Status};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Styling options for DOT output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotOptions {
+    /// Fill nodes with a color derived from their status
+    pub color_by_status: bool,
+
+    /// Pick node shapes from their priority
+    pub shape_by_priority: bool,
+
+    /// Group nodes into clusters by their first tag
+    pub cluster_by_tag: bool,
+
+    /// Graph layout direction (e.g., "TB" or "LR")
+    pub rankdir: String,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            color_by_status: true,
+            shape_by_priority: true,
+            cluster_by_tag: false,
+            rankdir: "TB".to_string(),
+        }
+    }
+}
+
+impl RequirementGraph {
+    /// Render the graph in Graphviz DOT format
+    pub fn to_dot(&self, options: &DotOptions) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph requirements {{");
+        let _ = writeln!(out, "    rankdir={};", options.rankdir);
+        let _ = writeln!(out, "    node [style=filled, fillcolor=white];");
+
+        let mut clusters: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let mut unclustered = vec![];
+
+        for node in self.graph.node_indices() {
+            let summary = self.summary(node);
+            let req = self.requirement_at(node);
+
+            let mut attrs = vec![format!("label=\"{}\"", escape(summary))];
+            if options.color_by_status {
+                if let Some(status) = req.status {
+                    attrs.push(format!("fillcolor=\"{}\"", status_color(status)));
+                }
+            }
+            if options.shape_by_priority {
+                if let Some(priority) = req.priority {
+                    attrs.push(format!("shape={}", priority_shape(priority)));
+                }
+            }
+
+            let line = format!("n{} [{}];", node.index(), attrs.join(", "));
+            match req.tags.first() {
+                Some(tag) if options.cluster_by_tag => {
+                    clusters.entry(tag.as_str()).or_default().push(line)
+                }
+                _ => unclustered.push(line),
+            }
+        }
+
+        for (i, (tag, lines)) in clusters.iter().enumerate() {
+            let _ = writeln!(out, "    subgraph cluster_{} {{", i);
+            let _ = writeln!(out, "        label=\"{}\";", escape(tag));
+            for line in lines {
+                let _ = writeln!(out, "        {}", line);
+            }
+            let _ = writeln!(out, "    }}");
+        }
+        for line in &unclustered {
+            let _ = writeln!(out, "    {}", line);
+        }
+
+        for edge in self.graph.edge_indices() {
+            if let Some((from, to)) = self.graph.edge_endpoints(edge) {
+                let _ = writeln!(out, "    n{} -> n{};", from.index(), to.index());
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+pub(super) fn status_color(status: Status) -> &'static str {
+    match status {
+        Status::Draft => "lightgray",
+        Status::Proposed => "lightyellow",
+        Status::Approved => "lightblue",
+        Status::Implemented => "palegreen",
+        Status::Verified => "green",
+        Status::Deprecated => "gray",
+    }
+}
+
+fn priority_shape(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Critical => "doubleoctagon",
+        Priority::High => "box",
+        Priority::Medium => "ellipse",
+        Priority::Low => "plaintext",
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RequirementReference;
+    use crate::{Requirement, RequirementConfig};
+
+    fn graph() -> RequirementGraph {
+        let mut child = Requirement::new("Child \"quoted\"");
+        child.status = Some(Status::Verified);
+        child.tags = vec!["safety".to_string()];
+        let mut root = Requirement::new("Root");
+        root.priority = Some(Priority::Critical);
+        root.requirements
+            .push(RequirementReference::Full(Box::new(child)));
+
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![root],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_to_dot_default() {
+        let dot = graph().to_dot(&DotOptions::default());
+        assert!(dot.starts_with("digraph requirements {"));
+        assert!(dot.contains("n0 [label=\"Root\", shape=doubleoctagon];"));
+        assert!(dot.contains("label=\"Child \\\"quoted\\\"\", fillcolor=\"green\""));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(!dot.contains("subgraph"));
+    }
+
+    #[test]
+    fn test_to_dot_clusters_and_plain() {
+        let options = DotOptions {
+            color_by_status: false,
+            shape_by_priority: false,
+            cluster_by_tag: true,
+            rankdir: "LR".to_string(),
+        };
+        let dot = graph().to_dot(&options);
+        assert!(dot.contains("rankdir=LR;"));
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("label=\"safety\";"));
+        assert!(!dot.contains("fillcolor=\"green\""));
+        assert!(!dot.contains("shape="));
+    }
+}
@@ -0,0 +1,135 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! GraphML export for Gephi, Cytoscape, and graph databases
+
+use super::{variant_name, RequirementGraph};
+use crate::types::OwnerReference;
+use crate::Requirement;
+use std::fmt::Write;
+
+/// Requirement attributes exported as node data, in output order
+const NODE_ATTRIBUTES: [(&str, &str); 8] = [
+    ("summary", "string"),
+    ("name", "string"),
+    ("description", "string"),
+    ("status", "string"),
+    ("priority", "string"),
+    ("owner", "string"),
+    ("tags", "string"),
+    ("estimate", "double"),
+];
+
+impl RequirementGraph {
+    /// Render the graph as a GraphML document
+    ///
+    /// Every requirement becomes a node carrying its summary and set
+    /// attributes; tags are joined with commas.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (key, kind) in NODE_ATTRIBUTES {
+            let _ = writeln!(
+                out,
+                "  <key id=\"{0}\" for=\"node\" attr.name=\"{0}\" attr.type=\"{1}\"/>",
+                key, kind
+            );
+        }
+        let _ = writeln!(
+            out,
+            "  <graph id=\"requirements\" edgedefault=\"directed\">"
+        );
+
+        for node in self.graph.node_indices() {
+            let _ = writeln!(out, "    <node id=\"n{}\">", node.index());
+            let req = self.requirement_at(node);
+            for (key, value) in node_data(req) {
+                let _ = writeln!(out, "      <data key=\"{}\">{}</data>", key, escape(&value));
+            }
+            out.push_str("    </node>\n");
+        }
+
+        for edge in self.graph.edge_indices() {
+            if let Some((from, to)) = self.graph.edge_endpoints(edge) {
+                let _ = writeln!(
+                    out,
+                    "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\"/>",
+                    edge.index(),
+                    from.index(),
+                    to.index()
+                );
+            }
+        }
+
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+/// Get the set attributes of a requirement as `(key, value)` pairs
+pub(super) fn node_data(req: &Requirement) -> Vec<(&'static str, String)> {
+    let owner = req.owner.as_ref().map(|owner| match owner {
+        OwnerReference::String(s) => s.clone(),
+    });
+    let tags = (!req.tags.is_empty()).then(|| req.tags.join(","));
+
+    [
+        ("summary", Some(req.summary.clone())),
+        ("name", req.name.clone()),
+        ("description", req.description.clone()),
+        ("status", req.status.and_then(variant_name)),
+        ("priority", req.priority.and_then(variant_name)),
+        ("owner", owner),
+        ("tags", tags),
+        ("estimate", req.estimate.map(|e| e.to_string())),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|v| (key, v)))
+    .collect()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{RequirementReference, Status};
+    use crate::{Requirement, RequirementConfig, RequirementGraph};
+
+    #[test]
+    fn test_to_graphml() {
+        let mut child = Requirement::new("Child");
+        child.status = Some(Status::Draft);
+        child.tags = vec!["ui".to_string(), "web".to_string()];
+        child.estimate = Some(2.5);
+        let mut root = Requirement::new("Root <A> & B");
+        root.requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        let graph = RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![root],
+        })
+        .unwrap();
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.starts_with("<?xml"));
+        assert!(graphml.contains(
+            "<key id=\"estimate\" for=\"node\" attr.name=\"estimate\" attr.type=\"double\"/>"
+        ));
+        assert!(graphml.contains("<data key=\"summary\">Root &lt;A&gt; &amp; B</data>"));
+        assert!(graphml.contains("<data key=\"status\">draft</data>"));
+        assert!(graphml.contains("<data key=\"tags\">ui,web</data>"));
+        assert!(graphml.contains("<data key=\"estimate\">2.5</data>"));
+        assert!(graphml.contains("<edge id=\"e0\" source=\"n0\" target=\"n1\"/>"));
+        assert!(!graphml.contains("<data key=\"owner\">"));
+        assert!(graphml.ends_with("</graphml>\n"));
+    }
+}
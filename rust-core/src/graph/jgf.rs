@@ -0,0 +1,103 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! JSON Graph Format export
+//!
+//! Follows version 2 of the JSON Graph specification
+//! (<https://jsongraphformat.info>), with nodes keyed by id.
+
+use super::graphml::node_data;
+use super::RequirementGraph;
+use serde_json::{json, Map, Value};
+
+impl RequirementGraph {
+    /// Render the graph as a JSON Graph Format document
+    pub fn to_json_graph(&self) -> Value {
+        let mut nodes = Map::new();
+        for node in self.graph.node_indices() {
+            let summary = self.summary(node);
+            let mut metadata = Map::new();
+            let req = self.requirement_at(node);
+            for (key, value) in node_data(req) {
+                let value = match (key, req.estimate) {
+                    ("estimate", Some(estimate)) => json!(estimate),
+                    ("tags", _) => json!(req.tags),
+                    _ => Value::String(value),
+                };
+                metadata.insert(key.to_string(), value);
+            }
+            nodes.insert(
+                format!("n{}", node.index()),
+                json!({ "label": summary, "metadata": metadata }),
+            );
+        }
+
+        let edges: Vec<Value> = self
+            .graph
+            .edge_indices()
+            .filter_map(|edge| self.graph.edge_endpoints(edge))
+            .map(|(from, to)| {
+                json!({
+                    "source": format!("n{}", from.index()),
+                    "target": format!("n{}", to.index()),
+                    "relation": "requires",
+                })
+            })
+            .collect();
+
+        json!({
+            "graph": {
+                "id": "requirements",
+                "directed": true,
+                "metadata": { "version": self.version },
+                "nodes": nodes,
+                "edges": edges,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{Priority, RequirementReference};
+    use crate::{Requirement, RequirementConfig, RequirementGraph};
+    use serde_json::json;
+
+    #[test]
+    fn test_to_json_graph() {
+        let mut child = Requirement::new("Child");
+        child.priority = Some(Priority::High);
+        child.tags = vec!["ui".to_string()];
+        child.estimate = Some(3.0);
+        let mut root = Requirement::new("Root");
+        root.requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        let graph = RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![root],
+        })
+        .unwrap();
+
+        let jgf = graph.to_json_graph();
+        let g = &jgf["graph"];
+        assert_eq!(g["directed"], json!(true));
+        assert_eq!(g["metadata"]["version"], json!("1.0"));
+        assert_eq!(g["nodes"]["n0"]["label"], json!("Root"));
+        assert_eq!(
+            g["nodes"]["n1"]["metadata"],
+            json!({
+                "summary": "Child",
+                "priority": "high",
+                "tags": ["ui"],
+                "estimate": 3.0,
+            })
+        );
+        assert_eq!(
+            g["edges"],
+            json!([{ "source": "n0", "target": "n1", "relation": "requires" }])
+        );
+    }
+}
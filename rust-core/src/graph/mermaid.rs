@@ -0,0 +1,186 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Mermaid flowchart and mindmap export
+
+use super::dot::status_color;
+use super::RequirementGraph;
+use crate::types::Status;
+use petgraph::graph::NodeIndex;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write;
+
+impl RequirementGraph {
+    /// Render the graph as a Mermaid flowchart, with nodes styled by status
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TD\n");
+        let mut by_status: BTreeMap<&'static str, Vec<String>> = BTreeMap::new();
+
+        for node in self.graph.node_indices() {
+            let summary = self.summary(node);
+            let _ = writeln!(out, "    n{}[\"{}\"]", node.index(), escape(summary));
+            if let Some(status) = self.requirement_at(node).status {
+                by_status
+                    .entry(status_class(status))
+                    .or_default()
+                    .push(format!("n{}", node.index()));
+            }
+        }
+
+        for edge in self.graph.edge_indices() {
+            if let Some((from, to)) = self.graph.edge_endpoints(edge) {
+                let _ = writeln!(out, "    n{} --> n{}", from.index(), to.index());
+            }
+        }
+
+        for status in [
+            Status::Draft,
+            Status::Proposed,
+            Status::Approved,
+            Status::Implemented,
+            Status::Verified,
+            Status::Deprecated,
+        ] {
+            if let Some(nodes) = by_status.get(status_class(status)) {
+                let _ = writeln!(
+                    out,
+                    "    classDef {} fill:{}",
+                    status_class(status),
+                    status_color(status)
+                );
+                let _ = writeln!(
+                    out,
+                    "    class {} {}",
+                    nodes.join(","),
+                    status_class(status)
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Render the requirement hierarchy as a Mermaid mindmap
+    ///
+    /// Requirements reachable through several parents are expanded only once.
+    pub fn to_mermaid_mindmap(&self) -> String {
+        let mut roots: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&n| self.parents_of(n).is_empty())
+            .collect();
+        if roots.is_empty() {
+            roots = self.graph.node_indices().collect();
+        }
+
+        let mut out = String::from("mindmap\n");
+        let mut visited = HashSet::new();
+        let base_depth = if roots.len() == 1 {
+            0
+        } else {
+            out.push_str("  root((Requirements))\n");
+            1
+        };
+
+        for root in roots {
+            let mut stack = vec![(root, base_depth)];
+            while let Some((node, depth)) = stack.pop() {
+                if !visited.insert(node) {
+                    continue;
+                }
+                let _ = writeln!(
+                    out,
+                    "{}{}",
+                    "  ".repeat(depth + 1),
+                    mindmap_text(self.summary(node))
+                );
+                let mut children: Vec<_> = self.graph.neighbors(node).collect();
+                children.sort();
+                for child in children.into_iter().rev() {
+                    stack.push((child, depth + 1));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+fn status_class(status: Status) -> &'static str {
+    match status {
+        Status::Draft => "draft",
+        Status::Proposed => "proposed",
+        Status::Approved => "approved",
+        Status::Implemented => "implemented",
+        Status::Verified => "verified",
+        Status::Deprecated => "deprecated",
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('"', "#quot;").replace('\n', " ")
+}
+
+/// Mindmap nodes are plain text where brackets denote shapes, so drop them
+fn mindmap_text(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            '(' | ')' | '[' | ']' | '{' | '}' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::{RequirementReference, Status};
+    use crate::{Requirement, RequirementConfig, RequirementGraph};
+
+    fn graph(roots: Vec<Requirement>) -> RequirementGraph {
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: roots,
+        })
+        .unwrap()
+    }
+
+    fn tree() -> Vec<Requirement> {
+        let mut child = Requirement::new("Child (v2)");
+        child.status = Some(Status::Draft);
+        let mut root = Requirement::new("Root \"A\"");
+        root.requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        root.requirements
+            .push(RequirementReference::Reference("Child (v2)".to_string()));
+        vec![root]
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = graph(tree()).to_mermaid();
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("n0[\"Root #quot;A#quot;\"]"));
+        assert!(mermaid.contains("n0 --> n1"));
+        assert!(mermaid.contains("classDef draft fill:lightgray"));
+        assert!(mermaid.contains("class n1 draft"));
+    }
+
+    #[test]
+    fn test_to_mermaid_mindmap_single_root() {
+        let mindmap = graph(tree()).to_mermaid_mindmap();
+        assert_eq!(mindmap, "mindmap\n  Root \"A\"\n    Child v2\n");
+    }
+
+    #[test]
+    fn test_to_mermaid_mindmap_multiple_roots() {
+        let mindmap =
+            graph(vec![Requirement::new("A"), Requirement::new("B")]).to_mermaid_mindmap();
+        assert_eq!(mindmap, "mindmap\n  root((Requirements))\n    A\n    B\n");
+    }
+}
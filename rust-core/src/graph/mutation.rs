@@ -0,0 +1,475 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! In-place graph edits with incremental cycle checking
+//!
+//! Each edit only inspects the region it touches: adding a reference
+//! searches for a path back from the child to the parent, so interactive
+//! tools can keep one graph alive instead of rebuilding it per change.
+
+use super::{paths, Cycle, Node, RequirementGraph};
+use crate::types::RequirementReference;
+use crate::{Error, Requirement, Result};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+impl RequirementGraph {
+    /// Add a requirement, including any nested children, to the graph
+    ///
+    /// Fails without changing the graph if a summary already exists, a
+    /// reference is unknown, or the new requirements would form a cycle.
+    pub fn add_requirement(&mut self, requirement: Requirement) -> Result<()> {
+        let added = requirement.flatten();
+
+        let mut new_summaries = HashSet::new();
+        for req in &added {
+            if self.summary_to_node.contains_key(req.summary.as_str())
+                || !new_summaries.insert(req.summary.as_str())
+            {
+                return Err(Error::DuplicateSummary(req.summary.clone()));
+            }
+        }
+        for req in &added {
+            for child in &req.requirements {
+                let summary = child.summary();
+                if !self.summary_to_node.contains_key(summary) && !new_summaries.contains(summary) {
+                    return Err(Error::InvalidReference(format!(
+                        "Requirement '{}' references non-existent '{}'",
+                        req.summary, summary
+                    )));
+                }
+            }
+        }
+
+        let links: Vec<(String, Vec<String>)> = added
+            .iter()
+            .map(|req| {
+                let children = req.requirements.iter().map(|c| c.summary().to_string());
+                (req.summary.clone(), children.collect())
+            })
+            .collect();
+        let tree = self.trees.len();
+        let mut nodes = vec![];
+        for (path, req) in paths(&requirement) {
+            let summary: Arc<str> = req.summary.as_str().into();
+            let node = self.graph.add_node(Node {
+                tree,
+                path,
+                summary: Arc::clone(&summary),
+            });
+            self.summary_to_node.insert(summary, node);
+            nodes.push(node);
+        }
+        self.trees.push(Some(Arc::new(requirement)));
+
+        for (summary, children) in &links {
+            let parent = self.summary_to_node[summary.as_str()];
+            for child in children {
+                let child = self.summary_to_node[child.as_str()];
+                if let Err(e) = self.link(parent, child) {
+                    // Undo in reverse so node indices stay valid
+                    for (&node, (summary, _)) in nodes.iter().zip(&links).rev() {
+                        self.remove_node(node, summary);
+                    }
+                    self.trees.pop();
+                    return Err(e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add a reference from `parent` to `child`
+    ///
+    /// The parent's stored requirement gains a reference to the child. Fails
+    /// without changing the graph if the reference would close a cycle.
+    pub fn add_edge(&mut self, parent: &str, child: &str) -> Result<()> {
+        let parent_node = self.node(parent)?;
+        let child_node = self.node(child)?;
+
+        self.link(parent_node, child_node)?;
+        self.requirement_at_mut(parent_node)
+            .requirements
+            .push(RequirementReference::Reference(child.to_string()));
+
+        Ok(())
+    }
+
+    /// Remove a requirement and every reference to or from it
+    ///
+    /// Nested children stay in the graph as separate top-level requirements,
+    /// while the one returned keeps them as well. References to the removed
+    /// requirement are dropped from its parents.
+    pub fn remove(&mut self, summary: &str) -> Result<Requirement> {
+        let node = self.node(summary)?;
+        let Node { tree, path, .. } = self.graph[node].clone();
+
+        // Trees whose requirements shift when it is cut out
+        let mut touched: Vec<usize> = self
+            .parents_of(node)
+            .iter()
+            .map(|&parent| self.graph[parent].tree)
+            .collect();
+        touched.push(tree);
+        touched.sort_unstable();
+        touched.dedup();
+        let nodes_at: HashMap<(usize, Vec<usize>), NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&n| touched.contains(&self.graph[n].tree))
+            .map(|n| ((self.graph[n].tree, self.graph[n].path.clone()), n))
+            .collect();
+
+        let mut moves = vec![];
+        let mut removed = None;
+        for &t in &touched {
+            let target = (t == tree).then_some(path.as_slice());
+            if target == Some(&[]) {
+                removed = self.trees[t]
+                    .take()
+                    .map(|root| Arc::try_unwrap(root).unwrap_or_else(|root| root.as_ref().clone()));
+            } else if let Some(root) = self.trees[t].as_mut() {
+                let mut cut = Cut::new(summary, target);
+                cut.walk(Arc::make_mut(root));
+                moves.extend(cut.moves.into_iter().map(|(old, new)| ((t, old), (t, new))));
+                removed = removed.or(cut.removed);
+            }
+        }
+        let mut removed = removed.ok_or_else(|| Error::RequirementNotFound(summary.to_string()))?;
+
+        // References to itself from its own children go as well; the
+        // children then stay in the graph on their own
+        let mut cut = Cut::new(summary, None);
+        cut.walk(&mut removed);
+        let old_paths: HashMap<Vec<usize>, Vec<usize>> =
+            cut.moves.into_iter().map(|(old, new)| (new, old)).collect();
+        for (i, child) in removed.requirements.iter().enumerate() {
+            if let RequirementReference::Full(child) = child {
+                let new_tree = self.trees.len();
+                for (rest, _) in paths(child) {
+                    let within: Vec<usize> = [i].into_iter().chain(rest.iter().copied()).collect();
+                    let old = path.iter().chain(&old_paths[&within]).copied().collect();
+                    moves.push(((tree, old), (new_tree, rest)));
+                }
+                self.trees.push(Some(Arc::new(child.as_ref().clone())));
+            }
+        }
+        for (old, (tree, path)) in moves {
+            if let Some(&moved) = nodes_at.get(&old) {
+                let node = &mut self.graph[moved];
+                node.tree = tree;
+                node.path = path;
+            }
+        }
+
+        self.remove_node(node, summary);
+        Ok(removed)
+    }
+
+    /// Remove a node named `summary`, keeping the lookup and reverse indices consistent
+    fn remove_node(&mut self, node: NodeIndex, summary: &str) {
+        for child in self.children_in_order(node) {
+            if let Some(parents) = self.parents.get_mut(&child) {
+                parents.retain(|&p| p != node);
+            }
+        }
+        self.parents.remove(&node);
+
+        // Removing a node moves the last node into its index
+        let last = NodeIndex::new(self.graph.node_count() - 1);
+        self.graph.remove_node(node);
+        if self.summary_to_node.get(summary) == Some(&node) {
+            self.summary_to_node.remove(summary);
+        }
+
+        if last != node {
+            let moved = Arc::clone(&self.graph[node].summary);
+            if let Some(index) = self
+                .summary_to_node
+                .get_mut(&moved)
+                .filter(|index| **index == last)
+            {
+                *index = node;
+            }
+            if let Some(parents) = self.parents.remove(&last) {
+                self.parents.insert(node, parents);
+            }
+            for parents in self.parents.values_mut() {
+                for parent in parents.iter_mut().filter(|p| **p == last) {
+                    *parent = node;
+                }
+            }
+        }
+    }
+
+    /// Add an edge unless it would close a cycle
+    fn link(&mut self, parent: NodeIndex, child: NodeIndex) -> Result<()> {
+        if let Some(path) = self.path(child, parent) {
+            let name = |node: NodeIndex| self.summary(node).to_string();
+            let mut members: Vec<String> = path.iter().map(|&n| name(n)).collect();
+            members.rotate_right(1);
+            let mut edges = vec![(name(parent), name(child))];
+            edges.extend(path.windows(2).map(|w| (name(w[0]), name(w[1]))));
+            return Err(Error::CircularReference(
+                Cycle { members, edges }.to_string(),
+            ));
+        }
+
+        self.graph.add_edge(parent, child, self.next_edge);
+        self.next_edge += 1;
+        let parents = self.parents.entry(child).or_default();
+        if !parents.contains(&parent) {
+            parents.push(parent);
+        }
+
+        Ok(())
+    }
+
+    /// Find a path of nodes from `from` to `to`, searching only what `from` reaches
+    fn path(&self, from: NodeIndex, to: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let mut previous: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut seen = HashSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                let mut current = to;
+                while let Some(&prev) = previous.get(&current) {
+                    path.push(prev);
+                    current = prev;
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for child in self.children_in_order(node) {
+                if seen.insert(child) {
+                    previous.insert(child, node);
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Cutting a requirement out of a tree, together with references to it
+struct Cut<'a> {
+    summary: &'a str,
+
+    /// Path of the requirement to take out, if it is nested in this tree
+    target: Option<&'a [usize]>,
+
+    /// Paths of the requirement being walked, before and after the cut
+    old: Vec<usize>,
+    new: Vec<usize>,
+
+    /// Old and new path of every nested requirement left in the tree
+    moves: Vec<(Vec<usize>, Vec<usize>)>,
+
+    removed: Option<Requirement>,
+}
+
+impl<'a> Cut<'a> {
+    fn new(summary: &'a str, target: Option<&'a [usize]>) -> Self {
+        Self {
+            summary,
+            target,
+            old: vec![],
+            new: vec![],
+            moves: vec![],
+            removed: None,
+        }
+    }
+
+    fn walk(&mut self, req: &mut Requirement) {
+        for (i, child) in std::mem::take(&mut req.requirements)
+            .into_iter()
+            .enumerate()
+        {
+            self.old.push(i);
+            match child {
+                RequirementReference::Reference(reference) if reference == self.summary => {}
+                RequirementReference::Full(child) if Some(self.old.as_slice()) == self.target => {
+                    self.removed = Some(*child);
+                }
+                RequirementReference::Full(mut child) => {
+                    self.new.push(req.requirements.len());
+                    self.moves.push((self.old.clone(), self.new.clone()));
+                    self.walk(&mut child);
+                    self.new.pop();
+                    req.requirements.push(RequirementReference::Full(child));
+                }
+                reference => req.requirements.push(reference),
+            }
+            self.old.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RequirementConfig;
+
+    fn graph() -> RequirementGraph {
+        let mut a = Requirement::new("A");
+        a.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("B"))));
+        a.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("C"))));
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![a],
+        })
+        .unwrap()
+    }
+
+    fn summaries(reqs: Vec<&Requirement>) -> Vec<&str> {
+        reqs.iter().map(|r| r.summary.as_str()).collect()
+    }
+
+    #[test]
+    fn test_add_requirement() {
+        let mut graph = graph();
+        let mut d = Requirement::new("D");
+        d.requirements
+            .push(RequirementReference::Reference("B".to_string()));
+        d.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("E"))));
+
+        graph.add_requirement(d).unwrap();
+        assert!(graph.get("E").is_some());
+        assert_eq!(summaries(graph.dependencies("D").unwrap()), vec!["B", "E"]);
+        assert_eq!(summaries(graph.dependents("B").unwrap()), vec!["A", "D"]);
+    }
+
+    #[test]
+    fn test_add_requirement_rejects_duplicates_and_unknown_references() {
+        let mut graph = graph();
+        assert!(matches!(
+            graph.add_requirement(Requirement::new("B")),
+            Err(Error::DuplicateSummary(_))
+        ));
+
+        let mut d = Requirement::new("D");
+        d.requirements
+            .push(RequirementReference::Reference("Missing".to_string()));
+        assert!(matches!(
+            graph.add_requirement(d),
+            Err(Error::InvalidReference(_))
+        ));
+        assert!(graph.get("D").is_none());
+    }
+
+    #[test]
+    fn test_add_requirement_rolls_back_cycle() {
+        let mut graph = graph();
+        let mut d = Requirement::new("D");
+        let mut e = Requirement::new("E");
+        e.requirements
+            .push(RequirementReference::Reference("D".to_string()));
+        d.requirements.push(RequirementReference::Full(Box::new(e)));
+
+        assert!(matches!(
+            graph.add_requirement(d),
+            Err(Error::CircularReference(_))
+        ));
+        assert!(graph.get("D").is_none());
+        assert!(graph.get("E").is_none());
+        assert_eq!(graph.stats().requirements, 3);
+        assert!(graph.get("C").is_some());
+        assert_eq!(summaries(graph.dependencies("A").unwrap()), vec!["B", "C"]);
+    }
+
+    #[test]
+    fn test_add_edge() {
+        let mut graph = graph();
+        graph.add_edge("B", "C").unwrap();
+
+        assert_eq!(summaries(graph.dependencies("B").unwrap()), vec!["C"]);
+        assert_eq!(summaries(graph.dependents("C").unwrap()), vec!["A", "B"]);
+        assert_eq!(graph.get("B").unwrap().requirements.len(), 1);
+        assert!(!graph.has_cycles());
+    }
+
+    #[test]
+    fn test_add_edge_rejects_cycle() {
+        let mut graph = graph();
+        graph.add_edge("B", "C").unwrap();
+
+        let Err(Error::CircularReference(message)) = graph.add_edge("C", "A") else {
+            panic!("expected a circular reference error");
+        };
+        assert_eq!(message, "cycle through 'C' -> 'A', 'A' -> 'C'");
+        let Err(Error::CircularReference(message)) = graph.add_edge("C", "B") else {
+            panic!("expected a circular reference error");
+        };
+        assert_eq!(message, "cycle through 'C' -> 'B', 'B' -> 'C'");
+        assert!(graph.add_edge("A", "A").is_err());
+        assert!(!graph.has_cycles());
+        assert!(graph.get("C").unwrap().requirements.is_empty());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut graph = graph();
+        graph.add_edge("B", "C").unwrap();
+
+        let removed = graph.remove("A").unwrap();
+        assert_eq!(removed.summary, "A");
+        assert!(graph.get("A").is_none());
+        assert!(graph.dependents("B").unwrap().is_empty());
+        assert_eq!(summaries(graph.dependents("C").unwrap()), vec!["B"]);
+        assert_eq!(summaries(graph.roots_of("C").unwrap()), vec!["B"]);
+        assert!(graph.remove("A").is_err());
+    }
+
+    #[test]
+    fn test_remove_updates_parents() {
+        let mut graph = graph();
+        graph.remove("B").unwrap();
+
+        assert_eq!(summaries(graph.dependencies("A").unwrap()), vec!["C"]);
+        let a = graph.get("A").unwrap();
+        assert_eq!(a.requirements.len(), 1);
+        assert_eq!(a.requirements[0].summary(), "C");
+    }
+
+    #[test]
+    fn test_remove_keeps_nested_children() {
+        let mut graph = graph();
+        let mut d = Requirement::new("D");
+        d.requirements
+            .push(RequirementReference::Reference("B".to_string()));
+        graph
+            .add_requirement(Requirement {
+                requirements: vec![RequirementReference::Full(Box::new(d))],
+                ..Requirement::new("E")
+            })
+            .unwrap();
+        graph.add_edge("D", "C").unwrap();
+
+        let removed = graph.remove("E").unwrap();
+        assert_eq!(removed.requirements[0].summary(), "D");
+        assert!(matches!(
+            &removed.requirements[0],
+            RequirementReference::Full(d) if d.requirements.len() == 2
+        ));
+        // D now stands on its own, still referencing B and C
+        assert_eq!(summaries(graph.dependencies("D").unwrap()), vec!["B", "C"]);
+        assert_eq!(graph.get("D").unwrap().requirements.len(), 2);
+
+        graph.remove("B").unwrap();
+        assert_eq!(summaries(graph.dependencies("D").unwrap()), vec!["C"]);
+        let a = graph.get("A").unwrap();
+        assert!(matches!(&a.requirements[0], RequirementReference::Full(c) if c.summary == "C"));
+        assert_eq!(graph.get("C").unwrap().summary, "C");
+    }
+}
@@ -0,0 +1,156 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Path queries between requirements
+
+use super::RequirementGraph;
+use crate::{Requirement, Result};
+use petgraph::algo::all_simple_paths;
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, VecDeque};
+
+impl RequirementGraph {
+    /// Find all simple paths from `from` down to `to` with at most `max_depth` edges
+    ///
+    /// Each path lists the requirements in order, starting with `from` and ending with `to`.
+    pub fn paths_between(
+        &self,
+        from: &str,
+        to: &str,
+        max_depth: usize,
+    ) -> Result<Vec<Vec<&Requirement>>> {
+        let start = self.node(from)?;
+        let end = self.node(to)?;
+
+        if start == end {
+            return Ok(vec![self.resolve_path(&[start])]);
+        }
+
+        let max_intermediate = max_depth.saturating_sub(1);
+        let mut paths: Vec<Vec<NodeIndex>> =
+            all_simple_paths(&self.graph, start, end, 0, Some(max_intermediate)).collect();
+        paths.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        paths.dedup();
+
+        Ok(paths.iter().map(|path| self.resolve_path(path)).collect())
+    }
+
+    /// Find a shortest path from `from` down to `to`, if one exists
+    pub fn shortest_path(&self, from: &str, to: &str) -> Result<Option<Vec<&Requirement>>> {
+        let start = self.node(from)?;
+        let end = self.node(to)?;
+
+        let mut predecessors: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        let mut found = start == end;
+
+        while let Some(node) = queue.pop_front() {
+            if found {
+                break;
+            }
+            for next in self.graph.neighbors(node) {
+                if next == start || predecessors.contains_key(&next) {
+                    continue;
+                }
+                predecessors.insert(next, node);
+                if next == end {
+                    found = true;
+                    break;
+                }
+                queue.push_back(next);
+            }
+        }
+
+        if !found {
+            return Ok(None);
+        }
+
+        let mut path = vec![end];
+        let mut current = end;
+        while current != start {
+            current = predecessors[&current];
+            path.push(current);
+        }
+        path.reverse();
+
+        Ok(Some(self.resolve_path(&path)))
+    }
+
+    fn resolve_path(&self, path: &[NodeIndex]) -> Vec<&Requirement> {
+        path.iter().map(|&n| self.requirement_at(n)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::RequirementReference;
+    use crate::{Requirement, RequirementConfig, RequirementGraph};
+
+    fn summaries(path: &[&Requirement]) -> Vec<String> {
+        path.iter().map(|r| r.summary.clone()).collect()
+    }
+
+    /// System -> A -> Leaf, System -> B -> C -> Leaf, C -> System (cycle)
+    fn graph() -> RequirementGraph {
+        let reqs = [
+            ("System", vec!["A", "B"]),
+            ("A", vec!["Leaf"]),
+            ("B", vec!["C"]),
+            ("C", vec!["Leaf", "System"]),
+            ("Leaf", vec![]),
+        ]
+        .into_iter()
+        .map(|(summary, children)| {
+            let mut req = Requirement::new(summary);
+            req.requirements = children
+                .into_iter()
+                .map(|c| RequirementReference::Reference(c.to_string()))
+                .collect();
+            req
+        })
+        .collect();
+
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: reqs,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_paths_between() {
+        let graph = graph();
+        let paths = graph.paths_between("System", "Leaf", 10).unwrap();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(summaries(&paths[0]), vec!["System", "A", "Leaf"]);
+        assert_eq!(summaries(&paths[1]), vec!["System", "B", "C", "Leaf"]);
+    }
+
+    #[test]
+    fn test_paths_between_depth_limit() {
+        let graph = graph();
+        let paths = graph.paths_between("System", "Leaf", 2).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert!(graph
+            .paths_between("Leaf", "System", 10)
+            .unwrap()
+            .is_empty());
+        assert!(graph.paths_between("System", "Missing", 10).is_err());
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let graph = graph();
+        let path = graph.shortest_path("B", "Leaf").unwrap().unwrap();
+        assert_eq!(summaries(&path), vec!["B", "C", "Leaf"]);
+
+        let around = graph.shortest_path("C", "A").unwrap().unwrap();
+        assert_eq!(summaries(&around), vec!["C", "System", "A"]);
+
+        assert!(graph.shortest_path("Leaf", "A").unwrap().is_none());
+        assert_eq!(graph.shortest_path("A", "A").unwrap().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Children that are less urgent than their critical parents
+
+use super::RequirementGraph;
+use crate::types::Priority;
+use serde::{Deserialize, Serialize};
+
+/// A child declaring a lower priority than its critical parent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriorityConflict {
+    /// Summary of the critical parent
+    pub parent: String,
+
+    /// Summary of the less urgent child
+    pub child: String,
+
+    pub parent_priority: Priority,
+
+    pub child_priority: Priority,
+}
+
+impl RequirementGraph {
+    /// Children, nested or referenced, with a lower priority than a
+    /// critical parent, in node order
+    ///
+    /// Children without a priority are not conflicts; use
+    /// [`crate::RequirementConfig::inherit_priorities`] to give them their
+    /// parent's instead.
+    pub fn priority_conflicts(&self) -> Vec<PriorityConflict> {
+        let mut conflicts = vec![];
+        for node in self.graph.node_indices() {
+            let parent = self.requirement_at(node);
+            if parent.priority != Some(Priority::Critical) {
+                continue;
+            }
+            for child in self.children_in_order(node) {
+                let child = self.requirement_at(child);
+                if let Some(priority) = child.priority {
+                    if priority.urgency() < Priority::Critical.urgency() {
+                        conflicts.push(PriorityConflict {
+                            parent: parent.summary.clone(),
+                            child: child.summary.clone(),
+                            parent_priority: Priority::Critical,
+                            child_priority: priority,
+                        });
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Braking
+    priority: critical
+    requirements:
+      - summary: Sensors
+        requirements:
+          - summary: Redundancy
+            priority: medium
+      - summary: Logging
+        priority: low
+      - summary: Actuators
+        priority: critical
+      - Telemetry
+  - summary: Telemetry
+    priority: high
+"#;
+
+    #[test]
+    fn test_priority_conflicts_under_critical_parents() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        let pairs = |conflicts: Vec<PriorityConflict>| -> Vec<(String, String)> {
+            conflicts
+                .into_iter()
+                .map(|conflict| (conflict.parent, conflict.child))
+                .collect()
+        };
+
+        let conflicts = graph.priority_conflicts();
+        assert_eq!(conflicts[0].child_priority, Priority::Low);
+        assert_eq!(
+            pairs(conflicts),
+            vec![
+                ("Braking".to_string(), "Logging".to_string()),
+                ("Braking".to_string(), "Telemetry".to_string()),
+            ]
+        );
+
+        // Inherited, Sensors is critical too and Redundancy falls below it
+        let mut inherited = config.clone();
+        inherited.inherit_priorities();
+        let graph = RequirementGraph::from_config(&inherited).unwrap();
+        assert!(pairs(graph.priority_conflicts())
+            .contains(&("Sensors".to_string(), "Redundancy".to_string())));
+    }
+}
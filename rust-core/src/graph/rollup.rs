@@ -0,0 +1,160 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Status rolled up from children, so a parent is only as far along as
+//! its least advanced child
+
+use super::RequirementGraph;
+use crate::types::Status;
+use crate::{Requirement, Result};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
+
+impl RequirementGraph {
+    /// Status a requirement has by its children
+    ///
+    /// A parent is as far along as its least advanced child that is not
+    /// deprecated, with children without a status counting as drafts: it is
+    /// implemented only when all of its children are. Requirements without
+    /// such children, and deprecated ones, keep their declared status.
+    /// References leading back into a cycle are ignored.
+    pub fn derived_status(&self, summary: &str) -> Result<Option<Status>> {
+        let node = self.node(summary)?;
+        Ok(self.derive(node, &mut HashMap::new()))
+    }
+
+    /// Requirements whose declared status is ahead of their derived status,
+    /// paired with the latter, in node order
+    pub fn statuses_ahead_of_children(&self) -> Vec<(&Requirement, Status)> {
+        let mut memo = HashMap::new();
+        self.graph
+            .node_indices()
+            .filter_map(|node| {
+                let req = self.requirement_at(node);
+                let declared = req.status.filter(|&status| status != Status::Deprecated)?;
+                let derived = self.derive(node, &mut memo)?;
+                (declared > derived).then_some((req, derived))
+            })
+            .collect()
+    }
+
+    /// Derive the status of `start` and everything below it into `memo`
+    ///
+    /// Iterative, so deeply nested requirements cannot overflow the stack.
+    fn derive(
+        &self,
+        start: NodeIndex,
+        memo: &mut HashMap<NodeIndex, Option<Status>>,
+    ) -> Option<Status> {
+        let mut in_progress = HashSet::new();
+        let mut stack = vec![(start, false)];
+        while let Some((node, expanded)) = stack.pop() {
+            if memo.contains_key(&node) {
+                continue;
+            }
+            let declared = self.requirement_at(node).status;
+            let children = self.children_in_order(node);
+            if declared == Some(Status::Deprecated) || children.is_empty() {
+                memo.insert(node, declared);
+            } else if !expanded {
+                in_progress.insert(node);
+                stack.push((node, true));
+                stack.extend(
+                    children
+                        .into_iter()
+                        .filter(|child| !in_progress.contains(child))
+                        .map(|child| (child, false)),
+                );
+            } else {
+                in_progress.remove(&node);
+                // Children still in progress close a cycle and have no entry
+                let least = children
+                    .iter()
+                    .filter_map(|child| memo.get(child).copied())
+                    .filter(|&status| status != Some(Status::Deprecated))
+                    .map(|status| status.unwrap_or(Status::Draft))
+                    .min();
+                memo.insert(node, least.or(declared));
+            }
+        }
+        memo[&start]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::types::Status;
+    use crate::{Parser, RequirementGraph};
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: implemented
+    requirements:
+      - summary: Login
+        status: approved
+        requirements:
+          - summary: Password
+            status: verified
+          - summary: SSO
+            status: implemented
+      - summary: Logout
+        status: verified
+      - summary: Legacy login
+        status: deprecated
+  - summary: Reporting
+    status: approved
+    requirements:
+      - summary: Export
+      - summary: Old export
+        status: deprecated
+  - summary: Audit
+    status: verified
+    requirements:
+      - summary: Retired audit
+        status: deprecated
+"#;
+
+    #[test]
+    fn test_derived_status_is_least_advanced_active_child() {
+        let graph = RequirementGraph::from_config(&Parser::parse_str(YAML).unwrap()).unwrap();
+        let derived = |summary| graph.derived_status(summary).unwrap();
+
+        assert_eq!(derived("Login"), Some(Status::Implemented));
+        assert_eq!(derived("Authentication"), Some(Status::Implemented));
+        assert_eq!(derived("Logout"), Some(Status::Verified));
+        assert_eq!(derived("Export"), None);
+        assert_eq!(derived("Reporting"), Some(Status::Draft));
+        assert_eq!(derived("Audit"), Some(Status::Verified));
+        assert!(graph.derived_status("Missing").is_err());
+
+        let ahead: Vec<_> = graph
+            .statuses_ahead_of_children()
+            .into_iter()
+            .map(|(req, derived)| (req.summary.as_str(), derived))
+            .collect();
+        assert_eq!(ahead, vec![("Reporting", Status::Draft)]);
+    }
+
+    #[test]
+    fn test_derived_status_cuts_cycles() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - summary: A
+    status: approved
+    requirements:
+      - summary: B
+        status: implemented
+        requirements: [A]
+"#;
+        let graph = RequirementGraph::from_config(&Parser::parse_str(yaml).unwrap()).unwrap();
+        assert_eq!(
+            graph.derived_status("A").unwrap(),
+            Some(Status::Implemented)
+        );
+        assert_eq!(graph.derived_status("B").unwrap(), Some(Status::Approved));
+    }
+}
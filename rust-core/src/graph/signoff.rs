@@ -0,0 +1,150 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Requirements awaiting sign-off
+//!
+//! Projects list the roles that must approve a requirement, such as
+//! `product-owner` and `safety`; without a list, one approval in any role
+//! will do. An approval with a signature only counts while the requirement
+//! still says what was signed.
+
+use super::RequirementGraph;
+use crate::metadata::signature;
+use crate::types::{Approval, Status};
+use crate::Requirement;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Role reported as missing when no roles are required and nobody approved
+pub const ANY_ROLE: &str = "any";
+
+/// Whether an approval still holds: it has no signature, or was signed for
+/// what the requirement says now
+pub fn is_current(approval: &Approval, req: &Requirement) -> bool {
+    approval
+        .signature
+        .as_deref()
+        .is_none_or(|signed| signed.eq_ignore_ascii_case(&signature(req)))
+}
+
+/// Roles in `roles` without a current approval of `req`, or [`ANY_ROLE`]
+/// when no roles are given and the requirement has no current approval
+pub fn missing_roles(req: &Requirement, roles: &[String]) -> Vec<String> {
+    let approved: Vec<&str> = req
+        .approvals
+        .iter()
+        .filter(|approval| is_current(approval, req))
+        .map(|approval| approval.role.as_str())
+        .collect();
+    if roles.is_empty() && approved.is_empty() {
+        return vec![ANY_ROLE.to_string()];
+    }
+    roles
+        .iter()
+        .filter(|role| !approved.contains(&role.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// A requirement up for approval, or past it, without every sign-off
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct MissingSignoff {
+    /// Summary of the requirement
+    pub summary: String,
+
+    /// Current status
+    pub status: Status,
+
+    /// Roles still to approve, or `any`
+    pub missing_roles: Vec<String>,
+
+    /// Approvals signed for an earlier version, as `person (role)`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outdated: Vec<String>,
+
+    /// Signature of the requirement as it is now, for new approvals
+    #[schemars(regex(pattern = r"^sha256:[0-9a-f]{64}$"))]
+    pub signature: String,
+}
+
+impl RequirementGraph {
+    /// Proposed, approved, implemented and verified requirements missing an
+    /// approval from any of `roles`, in node order
+    pub fn missing_signoffs(&self, roles: &[String]) -> Vec<MissingSignoff> {
+        self.requirements()
+            .filter_map(|req| {
+                let status = req
+                    .status
+                    .filter(|status| (Status::Proposed..=Status::Verified).contains(status))?;
+                let missing_roles = missing_roles(req, roles);
+                if missing_roles.is_empty() {
+                    return None;
+                }
+                Some(MissingSignoff {
+                    summary: req.summary.clone(),
+                    status,
+                    missing_roles,
+                    outdated: req
+                        .approvals
+                        .iter()
+                        .filter(|approval| !is_current(approval, req))
+                        .map(|approval| format!("{} ({})", approval.person, approval.role))
+                        .collect(),
+                    signature: signature(req),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn test_missing_signoffs() {
+        let config = Parser::parse_str(
+            r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    status: approved
+    approvals:
+      - person: "@alice"
+        role: product-owner
+        date: 2025-03-01
+  - summary: Lockout
+    status: proposed
+  - summary: Audit
+    status: draft
+  - summary: Export
+    status: verified
+    approvals:
+      - person: "@bob"
+        role: safety
+        date: 2025-03-02
+        signature: sha256:0000000000000000000000000000000000000000000000000000000000000000
+"#,
+        )
+        .unwrap();
+        let graph = RequirementGraph::from_config(&config).unwrap();
+
+        let missing = graph.missing_signoffs(&[]);
+        let summaries: Vec<&str> = missing.iter().map(|m| m.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["Lockout", "Export"]);
+        assert_eq!(missing[0].missing_roles, vec![ANY_ROLE]);
+        assert_eq!(missing[1].outdated, vec!["@bob (safety)"]);
+        assert_eq!(
+            missing[1].signature,
+            signature(config.all_requirements()[3])
+        );
+
+        let roles = vec!["product-owner".to_string(), "safety".to_string()];
+        let missing = graph.missing_signoffs(&roles);
+        assert_eq!(missing[0].summary, "Login");
+        assert_eq!(missing[0].missing_roles, vec!["safety"]);
+        assert_eq!(missing.len(), 3);
+    }
+}
@@ -0,0 +1,189 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Graph metrics for dashboards and health checks
+
+use super::{variant_name, RequirementGraph};
+use petgraph::graph::NodeIndex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// Label used for requirements without a status or priority
+const UNSET: &str = "unset";
+
+/// Summary metrics of a requirement graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GraphStats {
+    /// Number of requirements
+    pub requirements: usize,
+
+    /// Number of parent-child references
+    pub references: usize,
+
+    /// Requirements not referenced by any other requirement
+    pub roots: usize,
+
+    /// Requirements without children
+    pub leaves: usize,
+
+    /// Deepest level below a root, following the shortest route to each requirement
+    pub max_depth: usize,
+
+    /// Average number of children per non-leaf requirement
+    pub average_fan_out: f64,
+
+    /// Requirements in the longest chain of references; `None` if the graph is cyclic
+    pub longest_chain: Option<usize>,
+
+    /// Requirement counts keyed by status, with `unset` for requirements without one
+    pub by_status: BTreeMap<String, usize>,
+
+    /// Requirement counts keyed by priority, with `unset` for requirements without one
+    pub by_priority: BTreeMap<String, usize>,
+}
+
+impl RequirementGraph {
+    /// Compute metrics describing the size and shape of the graph
+    pub fn stats(&self) -> GraphStats {
+        let roots: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&n| self.parents_of(n).is_empty())
+            .collect();
+        let parents: Vec<usize> = self
+            .graph
+            .node_indices()
+            .map(|n| self.children_in_order(n).len())
+            .filter(|&count| count > 0)
+            .collect();
+
+        let mut by_status = BTreeMap::new();
+        let mut by_priority = BTreeMap::new();
+        for node in self.graph.node_indices() {
+            let req = self.requirement_at(node);
+            *by_status.entry(label(req.status)).or_insert(0) += 1;
+            *by_priority.entry(label(req.priority)).or_insert(0) += 1;
+        }
+
+        GraphStats {
+            requirements: self.graph.node_count(),
+            references: self.graph.edge_count(),
+            roots: roots.len(),
+            leaves: self.graph.node_count() - parents.len(),
+            max_depth: self.levels().into_values().max().unwrap_or(0),
+            average_fan_out: if parents.is_empty() {
+                0.0
+            } else {
+                parents.iter().sum::<usize>() as f64 / parents.len() as f64
+            },
+            longest_chain: self.longest_chain(),
+            by_status,
+            by_priority,
+        }
+    }
+
+    fn longest_chain(&self) -> Option<usize> {
+        let sorted = petgraph::algo::toposort(&self.graph, None).ok()?;
+        let mut lengths: HashMap<NodeIndex, usize> = HashMap::new();
+
+        // Walk children before parents so each chain length is known when needed
+        for &node in sorted.iter().rev() {
+            let longest_child = self
+                .graph
+                .neighbors(node)
+                .map(|child| lengths[&child])
+                .max()
+                .unwrap_or(0);
+            lengths.insert(node, longest_child + 1);
+        }
+
+        Some(lengths.values().copied().max().unwrap_or(0))
+    }
+}
+
+pub(super) fn label<T: Serialize>(value: Option<T>) -> String {
+    value
+        .and_then(variant_name)
+        .unwrap_or_else(|| UNSET.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Priority, RequirementReference, Status};
+    use crate::{Requirement, RequirementConfig};
+
+    fn graph(requirements: Vec<Requirement>) -> RequirementGraph {
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_stats() {
+        // Root -> A -> C, Root -> B -> C, Root -> C
+        let mut c = Requirement::new("C");
+        c.status = Some(Status::Draft);
+        let mut a = Requirement::new("A");
+        a.status = Some(Status::Draft);
+        a.priority = Some(Priority::High);
+        a.requirements.push(RequirementReference::Full(Box::new(c)));
+        let mut b = Requirement::new("B");
+        b.requirements
+            .push(RequirementReference::Reference("C".to_string()));
+        let mut root = Requirement::new("Root");
+        root.status = Some(Status::Approved);
+        for child in [a, b] {
+            root.requirements
+                .push(RequirementReference::Full(Box::new(child)));
+        }
+        root.requirements
+            .push(RequirementReference::Reference("C".to_string()));
+
+        let stats = graph(vec![root, Requirement::new("Lonely")]).stats();
+        assert_eq!(stats.requirements, 5);
+        assert_eq!(stats.references, 5);
+        assert_eq!(stats.roots, 2);
+        assert_eq!(stats.leaves, 2);
+        assert_eq!(stats.max_depth, 1);
+        assert_eq!(stats.average_fan_out, 5.0 / 3.0);
+        assert_eq!(stats.longest_chain, Some(3));
+        assert_eq!(stats.by_status["draft"], 2);
+        assert_eq!(stats.by_status["approved"], 1);
+        assert_eq!(stats.by_status["unset"], 2);
+        assert_eq!(stats.by_priority["high"], 1);
+        assert_eq!(stats.by_priority["unset"], 4);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["longest_chain"], 3);
+        assert_eq!(json["by_status"]["draft"], 2);
+    }
+
+    #[test]
+    fn test_stats_cyclic() {
+        let mut a = Requirement::new("A");
+        a.requirements
+            .push(RequirementReference::Reference("B".to_string()));
+        let mut b = Requirement::new("B");
+        b.requirements
+            .push(RequirementReference::Reference("A".to_string()));
+
+        let stats = graph(vec![a, b]).stats();
+        assert_eq!(stats.roots, 0);
+        assert_eq!(stats.max_depth, 0);
+        assert_eq!(stats.longest_chain, None);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        let stats = graph(vec![]).stats();
+        assert_eq!(stats.requirements, 0);
+        assert_eq!(stats.average_fan_out, 0.0);
+        assert_eq!(stats.longest_chain, Some(0));
+    }
+}
@@ -0,0 +1,228 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Subgraph extraction and slicing
+
+use super::RequirementGraph;
+use crate::metadata::tag_has_prefix;
+use crate::types::{RequirementReference, Status};
+use crate::{Requirement, RequirementConfig, Result};
+use petgraph::graph::NodeIndex;
+use std::collections::HashSet;
+
+/// Criteria for selecting requirements in a subgraph
+///
+/// Unset criteria match everything; set criteria must all match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubgraphFilter {
+    /// Keep requirements tagged at or below this tag prefix
+    pub tag_prefix: Option<String>,
+
+    /// Keep requirements with this status
+    pub status: Option<Status>,
+
+    /// Keep requirements owned by this owner reference
+    pub owner: Option<String>,
+}
+
+impl SubgraphFilter {
+    /// Check whether a requirement satisfies the filter
+    pub fn matches(&self, req: &Requirement) -> bool {
+        if let Some(prefix) = &self.tag_prefix {
+            if !req.tags.iter().any(|tag| tag_has_prefix(tag, prefix)) {
+                return false;
+            }
+        }
+        if let Some(status) = self.status {
+            if req.status != Some(status) {
+                return false;
+            }
+        }
+        if let Some(owner) = &self.owner {
+            if req.owner.as_ref().map(|o| o.as_str()) != Some(owner.as_str()) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl RequirementGraph {
+    /// Extract the given roots and everything reachable from them as a new config
+    ///
+    /// Requirements rejected by the filter are dropped, and their matching
+    /// descendants are attached to the nearest kept ancestor (or become
+    /// top-level). Each requirement is nested once; further occurrences are
+    /// emitted as references.
+    pub fn subgraph(&self, roots: &[&str], filter: &SubgraphFilter) -> Result<RequirementConfig> {
+        let root_nodes = roots
+            .iter()
+            .map(|summary| self.node(summary))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut expanded = HashSet::new();
+        let mut requirements = vec![];
+        for root in root_nodes {
+            for child in self.extract(root, filter, &mut expanded, &mut HashSet::new()) {
+                match child {
+                    RequirementReference::Full(req) => requirements.push(*req),
+                    RequirementReference::Reference(_) => {}
+                }
+            }
+        }
+
+        let included: HashSet<String> = requirements
+            .iter()
+            .flat_map(|req| req.flatten())
+            .map(|req| req.summary.clone())
+            .collect();
+        for req in &mut requirements {
+            prune_links(req, &included);
+        }
+
+        Ok(RequirementConfig {
+            version: self.version.clone(),
+            aliases: self.aliases.clone(),
+            templates: vec![],
+            requirements,
+        })
+    }
+
+    /// Build the references contributed by `node` to its nearest kept ancestor
+    fn extract(
+        &self,
+        node: NodeIndex,
+        filter: &SubgraphFilter,
+        expanded: &mut HashSet<NodeIndex>,
+        path: &mut HashSet<NodeIndex>,
+    ) -> Vec<RequirementReference> {
+        let req = self.requirement_at(node);
+        let keep = filter.matches(req);
+
+        if expanded.contains(&node) || path.contains(&node) {
+            return if keep {
+                vec![RequirementReference::Reference(req.summary.clone())]
+            } else {
+                vec![]
+            };
+        }
+        if keep {
+            expanded.insert(node);
+        }
+
+        path.insert(node);
+        let mut seen = HashSet::new();
+        let children: Vec<_> = self
+            .children_in_order(node)
+            .into_iter()
+            .flat_map(|child| self.extract(child, filter, expanded, path))
+            .filter(|child| seen.insert(child.summary().to_string()))
+            .collect();
+        path.remove(&node);
+
+        if keep {
+            let mut req = req.clone();
+            req.template = None;
+            req.requirements = children;
+            vec![RequirementReference::Full(Box::new(req))]
+        } else {
+            children
+        }
+    }
+}
+
+/// Drop supersession links to requirements outside the extracted set
+fn prune_links(req: &mut Requirement, included: &HashSet<String>) {
+    req.superseded_by = req.superseded_by.take().filter(|s| included.contains(s));
+    req.supersedes.retain(|s| included.contains(s));
+    for child in &mut req.requirements {
+        if let RequirementReference::Full(child) = child {
+            prune_links(child, included);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OwnerReference;
+    use crate::{Parser, Validator};
+
+    fn graph() -> RequirementGraph {
+        let yaml = r#"
+version: "1.0"
+aliases:
+  - alias: acme
+    email: supplier@acme.example
+requirements:
+  - summary: Vehicle
+    owner: "@oem"
+    requirements:
+      - summary: Brakes
+        owner: acme
+        tags: [safety/brakes]
+        requirements:
+          - summary: Brake Pads
+            owner: acme
+          - summary: Brake Sensor
+            owner: "@oem"
+            requirements:
+              - Brake Pads
+      - summary: Infotainment
+        owner: "@oem"
+  - summary: Unrelated
+"#;
+        RequirementGraph::from_config(&Parser::parse_str(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_subgraph_reachable_only() {
+        let config = graph()
+            .subgraph(&["Brakes"], &SubgraphFilter::default())
+            .unwrap();
+
+        let summaries: Vec<_> = config
+            .all_requirements()
+            .iter()
+            .map(|r| r.summary.as_str())
+            .collect();
+        assert_eq!(summaries, vec!["Brakes", "Brake Pads", "Brake Sensor"]);
+        assert_eq!(config.aliases.len(), 1);
+        assert!(Validator::new().unwrap().validate(&config).is_valid());
+        assert!(RequirementGraph::from_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_subgraph_owner_filter_lifts_descendants() {
+        let filter = SubgraphFilter {
+            owner: Some("acme".to_string()),
+            ..Default::default()
+        };
+        let config = graph().subgraph(&["Vehicle"], &filter).unwrap();
+
+        assert_eq!(config.requirements.len(), 1);
+        let brakes = &config.requirements[0];
+        assert_eq!(brakes.summary, "Brakes");
+        assert_eq!(
+            brakes.owner,
+            Some(OwnerReference::String("acme".to_string()))
+        );
+        // Brake Pads is nested once and not duplicated through Brake Sensor
+        assert_eq!(brakes.requirements.len(), 1);
+        assert_eq!(config.all_requirements().len(), 2);
+    }
+
+    #[test]
+    fn test_subgraph_tag_filter_and_missing_root() {
+        let filter = SubgraphFilter {
+            tag_prefix: Some("safety".to_string()),
+            ..Default::default()
+        };
+        let graph = graph();
+        let config = graph.subgraph(&["Vehicle"], &filter).unwrap();
+        assert_eq!(config.all_requirements().len(), 1);
+
+        assert!(graph.subgraph(&["Missing"], &filter).is_err());
+    }
+}
@@ -0,0 +1,375 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Configurable graph traversal
+
+use super::RequirementGraph;
+use crate::{Error, Requirement, Result};
+use petgraph::graph::NodeIndex;
+use std::collections::{HashSet, VecDeque};
+
+/// Order in which a traversal visits requirements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraversalOrder {
+    /// Depth-first, visiting a requirement before its children (the default)
+    #[default]
+    DepthFirstPre,
+
+    /// Depth-first, visiting a requirement after all of its children
+    DepthFirstPost,
+
+    /// Level by level, visiting every requirement at one depth before the next
+    BreadthFirst,
+}
+
+/// Options controlling graph traversal
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TraversalOptions {
+    /// Fail once a requirement is reached deeper than this; `None` means unlimited
+    pub max_depth: Option<usize>,
+
+    /// Order in which requirements are visited
+    pub order: TraversalOrder,
+
+    /// Visit a requirement once for every path reaching it instead of once overall
+    ///
+    /// Cycles are still cut where they close. Expanding a densely shared
+    /// hierarchy can visit a very large number of paths.
+    pub expand: bool,
+}
+
+impl TraversalOptions {
+    /// Limit traversal to the given depth
+    pub fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            max_depth: Some(max_depth),
+            ..Self::default()
+        }
+    }
+
+    /// Traverse in the given order
+    pub fn with_order(order: TraversalOrder) -> Self {
+        Self {
+            order,
+            ..Self::default()
+        }
+    }
+
+    fn check_depth(&self, depth: usize) -> Result<()> {
+        match self.max_depth {
+            Some(max_depth) if depth > max_depth => Err(Error::GraphError(format!(
+                "Maximum traversal depth of {} exceeded",
+                max_depth
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl RequirementGraph {
+    /// Traverse from a requirement with cycle detection
+    ///
+    /// Requirements are visited depth-first in declaration order, each at
+    /// most once, together with their depth below the start.
+    pub fn traverse<F>(&self, start_summary: &str, visit: F) -> Result<()>
+    where
+        F: FnMut(&Requirement, usize) -> Result<()>,
+    {
+        self.traverse_with(start_summary, &TraversalOptions::default(), visit)
+    }
+
+    /// Traverse from a requirement using the given options
+    pub fn traverse_with<F>(
+        &self,
+        start_summary: &str,
+        options: &TraversalOptions,
+        mut visit: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&Requirement, usize) -> Result<()>,
+    {
+        let start = self.node(start_summary)?;
+        match options.order {
+            TraversalOrder::DepthFirstPre => self.depth_first(start, options, false, &mut visit),
+            TraversalOrder::DepthFirstPost => self.depth_first(start, options, true, &mut visit),
+            TraversalOrder::BreadthFirst => self.breadth_first(start, options, &mut visit),
+        }
+    }
+
+    fn depth_first<F>(
+        &self,
+        start: NodeIndex,
+        options: &TraversalOptions,
+        post_order: bool,
+        visit: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(&Requirement, usize) -> Result<()>,
+    {
+        let mut visited = HashSet::from([start]);
+        let mut on_path = HashSet::from([start]);
+        if !post_order {
+            self.visit_node(start, 0, visit)?;
+        }
+
+        // Each frame holds a node on the current path, its depth, and its unexplored children
+        let mut stack = vec![(start, 0, self.children_in_order(start).into_iter())];
+
+        while let Some((node, depth, children)) = stack.last_mut() {
+            let (node, depth) = (*node, *depth);
+            let Some(child) = children.next() else {
+                if post_order {
+                    self.visit_node(node, depth, visit)?;
+                }
+                on_path.remove(&node);
+                stack.pop();
+                continue;
+            };
+
+            options.check_depth(depth + 1)?;
+            let seen = if options.expand {
+                on_path.contains(&child)
+            } else {
+                !visited.insert(child)
+            };
+            if seen {
+                // Already visited, skip to prevent infinite loop
+                continue;
+            }
+
+            on_path.insert(child);
+            if !post_order {
+                self.visit_node(child, depth + 1, visit)?;
+            }
+            stack.push((child, depth + 1, self.children_in_order(child).into_iter()));
+        }
+
+        Ok(())
+    }
+
+    fn breadth_first<F>(
+        &self,
+        start: NodeIndex,
+        options: &TraversalOptions,
+        visit: &mut F,
+    ) -> Result<()>
+    where
+        F: FnMut(&Requirement, usize) -> Result<()>,
+    {
+        let mut visited = HashSet::from([start]);
+        // Each entry records the entry it was reached from, so expansion can detect cycles
+        let mut entries: Vec<(NodeIndex, Option<usize>)> = vec![(start, None)];
+        let mut queue = VecDeque::from([(0, 0)]);
+
+        while let Some((entry, depth)) = queue.pop_front() {
+            let node = entries[entry].0;
+            self.visit_node(node, depth, visit)?;
+
+            for child in self.children_in_order(node) {
+                options.check_depth(depth + 1)?;
+                let seen = if options.expand {
+                    self.is_ancestor_entry(&entries, entry, child)
+                } else {
+                    !visited.insert(child)
+                };
+                if !seen {
+                    entries.push((child, Some(entry)));
+                    queue.push_back((entries.len() - 1, depth + 1));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether `node` lies on the path leading to `entry`, inclusive
+    fn is_ancestor_entry(
+        &self,
+        entries: &[(NodeIndex, Option<usize>)],
+        entry: usize,
+        node: NodeIndex,
+    ) -> bool {
+        let mut current = Some(entry);
+        while let Some(index) = current {
+            if entries[index].0 == node {
+                return true;
+            }
+            current = entries[index].1;
+        }
+        false
+    }
+
+    fn visit_node<F>(&self, node: NodeIndex, depth: usize, visit: &mut F) -> Result<()>
+    where
+        F: FnMut(&Requirement, usize) -> Result<()>,
+    {
+        visit(self.requirement_at(node), depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::RequirementReference;
+    use crate::RequirementConfig;
+
+    /// Root -> A, B; A -> Shared, A1; B -> Shared; Shared -> Leaf
+    fn diamond() -> RequirementGraph {
+        let mut shared = Requirement::new("Shared");
+        shared
+            .requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new(
+                "Leaf",
+            ))));
+        let mut a = Requirement::new("A");
+        a.requirements
+            .push(RequirementReference::Full(Box::new(shared)));
+        a.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("A1"))));
+        let mut b = Requirement::new("B");
+        b.requirements
+            .push(RequirementReference::Reference("Shared".to_string()));
+        let mut root = Requirement::new("Root");
+        root.requirements
+            .push(RequirementReference::Full(Box::new(a)));
+        root.requirements
+            .push(RequirementReference::Full(Box::new(b)));
+
+        RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![root],
+        })
+        .unwrap()
+    }
+
+    fn collect(graph: &RequirementGraph, options: TraversalOptions) -> Vec<(String, usize)> {
+        let mut visited = vec![];
+        graph
+            .traverse_with("Root", &options, |req, depth| {
+                visited.push((req.summary.clone(), depth));
+                Ok(())
+            })
+            .unwrap();
+        visited
+    }
+
+    fn names(visited: &[(String, usize)]) -> Vec<&str> {
+        visited.iter().map(|(s, _)| s.as_str()).collect()
+    }
+
+    #[test]
+    fn test_depth_first_pre_order() {
+        let visited = collect(&diamond(), TraversalOptions::default());
+        assert_eq!(
+            names(&visited),
+            vec!["Root", "A", "Shared", "Leaf", "A1", "B"]
+        );
+    }
+
+    #[test]
+    fn test_depth_first_post_order() {
+        let visited = collect(
+            &diamond(),
+            TraversalOptions::with_order(TraversalOrder::DepthFirstPost),
+        );
+        assert_eq!(
+            names(&visited),
+            vec!["Leaf", "Shared", "A1", "A", "B", "Root"]
+        );
+        assert_eq!(visited[5], ("Root".to_string(), 0));
+    }
+
+    #[test]
+    fn test_breadth_first_order() {
+        let visited = collect(
+            &diamond(),
+            TraversalOptions::with_order(TraversalOrder::BreadthFirst),
+        );
+        assert_eq!(
+            visited,
+            vec![
+                ("Root".to_string(), 0),
+                ("A".to_string(), 1),
+                ("B".to_string(), 1),
+                ("Shared".to_string(), 2),
+                ("A1".to_string(), 2),
+                ("Leaf".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_shared_requirements() {
+        let options = TraversalOptions {
+            expand: true,
+            ..TraversalOptions::default()
+        };
+        let visited = collect(&diamond(), options);
+        assert_eq!(
+            names(&visited),
+            vec!["Root", "A", "Shared", "Leaf", "A1", "B", "Shared", "Leaf"]
+        );
+
+        let options = TraversalOptions {
+            expand: true,
+            order: TraversalOrder::BreadthFirst,
+            ..TraversalOptions::default()
+        };
+        let visited = collect(&diamond(), options);
+        assert_eq!(
+            names(&visited),
+            vec!["Root", "A", "B", "Shared", "A1", "Shared", "Leaf", "Leaf"]
+        );
+    }
+
+    #[test]
+    fn test_expand_cuts_cycles() {
+        let mut a = Requirement::new("Root");
+        a.requirements
+            .push(RequirementReference::Reference("B".to_string()));
+        let mut b = Requirement::new("B");
+        b.requirements
+            .push(RequirementReference::Reference("Root".to_string()));
+        let graph = RequirementGraph::from_config(&RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![a, b],
+        })
+        .unwrap();
+
+        for order in [
+            TraversalOrder::DepthFirstPre,
+            TraversalOrder::DepthFirstPost,
+            TraversalOrder::BreadthFirst,
+        ] {
+            let options = TraversalOptions {
+                expand: true,
+                order,
+                ..TraversalOptions::default()
+            };
+            assert_eq!(collect(&graph, options).len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_max_depth_applies_to_all_orders() {
+        let graph = diamond();
+        for order in [
+            TraversalOrder::DepthFirstPre,
+            TraversalOrder::DepthFirstPost,
+            TraversalOrder::BreadthFirst,
+        ] {
+            let options = TraversalOptions {
+                max_depth: Some(2),
+                order,
+                ..TraversalOptions::default()
+            };
+            let result = graph.traverse_with("Root", &options, |_, _| Ok(()));
+            assert!(matches!(result, Err(Error::GraphError(_))));
+        }
+    }
+}
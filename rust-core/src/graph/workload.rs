@@ -0,0 +1,188 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Open requirements per owner, so leads can see who is overloaded
+
+use super::stats::label;
+use super::RequirementGraph;
+use crate::types::{PersonAlias, Status};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Owner reported for open requirements without one
+pub const UNASSIGNED: &str = "unassigned";
+
+/// Open requirements of one owner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OwnerWorkload {
+    /// Alias, email or `@user` as written on the requirements, or `unassigned`
+    pub owner: String,
+
+    /// Name of the person behind an alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Email of the person behind an alias, or the owner itself if an email
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    /// GitHub user of the person behind an alias, or the owner itself if one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<String>,
+
+    /// Number of open requirements
+    pub open: usize,
+
+    /// Open requirement counts keyed by priority, with `unset` for none
+    pub by_priority: BTreeMap<String, usize>,
+
+    /// Open requirement counts keyed by status, with `unset` for none
+    pub by_status: BTreeMap<String, usize>,
+
+    /// Summaries of the open requirements, in declaration order
+    pub requirements: Vec<String>,
+}
+
+impl OwnerWorkload {
+    fn new(owner: &str, alias: Option<&PersonAlias>) -> Self {
+        let (name, email, github) = match alias {
+            Some(alias) => (
+                alias.name.clone(),
+                alias.email.clone(),
+                alias
+                    .github
+                    .as_ref()
+                    .map(|user| format!("@{}", user.trim_start_matches('@'))),
+            ),
+            None if owner.starts_with('@') => (None, None, Some(owner.to_string())),
+            None if owner.contains('@') => (None, Some(owner.to_string()), None),
+            None => (None, None, None),
+        };
+        Self {
+            owner: owner.to_string(),
+            name,
+            email,
+            github,
+            open: 0,
+            by_priority: BTreeMap::new(),
+            by_status: BTreeMap::new(),
+            requirements: vec![],
+        }
+    }
+}
+
+impl RequirementGraph {
+    /// Open requirements grouped by owner, most loaded first
+    ///
+    /// A requirement is open until it is verified; deprecated requirements
+    /// are not counted. Owners written as the email or GitHub user of an
+    /// alias count towards that alias.
+    pub fn workload(&self) -> Vec<OwnerWorkload> {
+        let mut workloads: BTreeMap<String, OwnerWorkload> = BTreeMap::new();
+        for req in self.requirements() {
+            if matches!(req.status, Some(Status::Verified | Status::Deprecated)) {
+                continue;
+            }
+
+            let written = req.owner.as_ref().map(|owner| owner.as_str());
+            let alias = written.and_then(|owner| self.alias_for(owner));
+            let owner = match (alias, written) {
+                (Some(alias), _) => alias.alias.as_str(),
+                (None, Some(owner)) => owner,
+                (None, None) => UNASSIGNED,
+            };
+
+            let workload = workloads
+                .entry(owner.to_string())
+                .or_insert_with(|| OwnerWorkload::new(owner, alias));
+            workload.open += 1;
+            *workload.by_priority.entry(label(req.priority)).or_insert(0) += 1;
+            *workload.by_status.entry(label(req.status)).or_insert(0) += 1;
+            workload.requirements.push(req.summary.clone());
+        }
+
+        let mut workloads: Vec<OwnerWorkload> = workloads.into_values().collect();
+        workloads.sort_by(|a, b| b.open.cmp(&a.open).then_with(|| a.owner.cmp(&b.owner)));
+        workloads
+    }
+
+    /// Find the alias an owner names, directly or by its email or GitHub user
+    fn alias_for(&self, owner: &str) -> Option<&PersonAlias> {
+        let user = owner.strip_prefix('@');
+        self.aliases.iter().find(|alias| {
+            alias.alias == owner
+                || alias
+                    .email
+                    .as_deref()
+                    .is_some_and(|email| email.eq_ignore_ascii_case(owner))
+                || user.is_some_and(|user| {
+                    alias.github.as_deref().map(|g| g.trim_start_matches('@')) == Some(user)
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+    use crate::RequirementGraph;
+
+    const YAML: &str = r#"
+version: "1.0"
+aliases:
+  - alias: alice
+    name: Alice Smith
+    email: alice@example.com
+    github: alicedev
+requirements:
+  - summary: Login
+    owner: alice
+    priority: high
+    status: draft
+    requirements:
+      - summary: Lockout
+        owner: "@alicedev"
+        priority: high
+        status: approved
+      - summary: Session timeout
+        owner: Alice@Example.com
+        status: verified
+  - summary: Audit log
+    owner: bob@example.com
+    priority: low
+  - summary: Legacy export
+    owner: bob@example.com
+    status: deprecated
+  - summary: Metrics
+"#;
+
+    #[test]
+    fn test_workload_groups_open_requirements_by_resolved_owner() {
+        let graph = RequirementGraph::from_config(&Parser::parse_str(YAML).unwrap()).unwrap();
+        let workload = graph.workload();
+
+        let owners: Vec<_> = workload
+            .iter()
+            .map(|w| (w.owner.as_str(), w.open))
+            .collect();
+        assert_eq!(
+            owners,
+            vec![("alice", 2), ("bob@example.com", 1), ("unassigned", 1)]
+        );
+
+        let alice = &workload[0];
+        assert_eq!(alice.name.as_deref(), Some("Alice Smith"));
+        assert_eq!(alice.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(alice.github.as_deref(), Some("@alicedev"));
+        assert_eq!(alice.by_priority["high"], 2);
+        assert_eq!(alice.by_status["draft"], 1);
+        assert_eq!(alice.by_status["approved"], 1);
+        assert_eq!(alice.requirements, vec!["Login", "Lockout"]);
+
+        let bob = &workload[1];
+        assert_eq!(bob.email.as_deref(), Some("bob@example.com"));
+        assert_eq!(bob.by_status["unset"], 1);
+    }
+}
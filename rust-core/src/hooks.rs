@@ -0,0 +1,318 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Git integration: a pre-commit validation hook and the semantic merge
+//! driver
+//!
+//! [`install`] writes a pre-commit hook, which validates the staged
+//! versions of requirement files, registers `rqm-validator merge` as the
+//! `rqm` merge driver with `git config`, and assigns it to requirement
+//! files in `.gitattributes`. Git is asked where hooks and configuration
+//! live, so `core.hooksPath` and worktrees are respected. Installing again
+//! only changes what is missing or outdated.
+
+use crate::error::{Error, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Marks a pre-commit hook written by [`install`], so it may be replaced
+const HOOK_MARKER: &str = "# Installed by rqm-validator hook install";
+
+/// Options for [`install`]
+#[derive(Debug, Clone)]
+pub struct HookOptions {
+    /// Command running the validator, e.g. `rqm-validator` or an absolute path
+    pub validator: String,
+
+    /// Requirement file patterns, as in `.gitattributes`
+    pub patterns: Vec<String>,
+
+    /// Replace a pre-commit hook not written by [`install`]
+    pub force: bool,
+}
+
+impl Default for HookOptions {
+    fn default() -> Self {
+        Self {
+            validator: "rqm-validator".to_string(),
+            patterns: vec!["requirements.yml".to_string(), "*.rqm.yml".to_string()],
+            force: false,
+        }
+    }
+}
+
+/// Files changed by [`install`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HookInstall {
+    /// Root of the working tree
+    pub work_dir: PathBuf,
+
+    /// Files that were created or updated; empty if everything was in place
+    pub changed: Vec<PathBuf>,
+}
+
+/// Install the hook and merge driver in the repository containing `dir`
+pub fn install<P: AsRef<Path>>(dir: P, options: &HookOptions) -> Result<HookInstall> {
+    let dir = dir.as_ref();
+    let work_dir = PathBuf::from(
+        git(dir, &["rev-parse", "--show-toplevel"])
+            .map_err(|_| Error::custom(format!("{} is not in a git repository", dir.display())))?,
+    );
+    let mut result = HookInstall {
+        work_dir: work_dir.clone(),
+        changed: vec![],
+    };
+    let validator = shell_quote(&options.validator);
+
+    let hooks = dir.join(git(dir, &["rev-parse", "--git-path", "hooks"])?);
+    let hook = hooks.join("pre-commit");
+    let script = hook_script(&validator, &options.patterns);
+    let existing = fs::read_to_string(&hook).ok();
+    if existing.as_deref() != Some(script.as_str()) {
+        if existing
+            .as_deref()
+            .is_some_and(|content| !content.contains(HOOK_MARKER))
+            && !options.force
+        {
+            return Err(Error::custom(format!(
+                "{} already exists; use --force to replace it",
+                hook.display()
+            )));
+        }
+        fs::create_dir_all(&hooks)?;
+        fs::write(&hook, script)?;
+        make_executable(&hook)?;
+        result.changed.push(hook);
+    }
+
+    // Also updates drivers installed before git passed the path along
+    let driver = format!("{} merge %O %A %B --path %P", validator);
+    let installed = git(dir, &["config", "--get", "merge.rqm.driver"]).ok();
+    if installed.as_deref() != Some(driver.as_str()) {
+        git(dir, &["config", "merge.rqm.name", "RQM requirements merge"])?;
+        git(dir, &["config", "merge.rqm.driver", &driver])?;
+        result
+            .changed
+            .push(dir.join(git(dir, &["rev-parse", "--git-path", "config"])?));
+    }
+
+    let attributes = work_dir.join(".gitattributes");
+    let content = fs::read_to_string(&attributes).unwrap_or_default();
+    let missing: Vec<String> = options
+        .patterns
+        .iter()
+        .map(|pattern| format!("{} merge=rqm", pattern))
+        .filter(|line| !content.lines().any(|existing| existing.trim() == line))
+        .collect();
+    if !missing.is_empty() {
+        let mut content = content;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        for line in missing {
+            content.push_str(&line);
+            content.push('\n');
+        }
+        fs::write(&attributes, content)?;
+        result.changed.push(attributes);
+    }
+
+    Ok(result)
+}
+
+/// The pre-commit hook, validating what is staged rather than the working
+/// tree
+///
+/// The staged requirement files are checked out to a temporary directory
+/// with the staged `.rqm` settings beside them. Paths are split on newlines
+/// only, so they may contain spaces.
+fn hook_script(validator: &str, patterns: &[String]) -> String {
+    let pathspecs: Vec<String> = patterns.iter().map(|p| shell_quote(&pathspec(p))).collect();
+    format!(
+        "#!/bin/sh\n\
+         {}\n\
+         # Validates the staged requirement files before each commit\n\
+         files=$(git -c core.quotePath=false diff --cached --name-only --diff-filter=ACMR -- {})\n\
+         [ -z \"$files\" ] && exit 0\n\
+         settings=$(git -c core.quotePath=false ls-files -- ':(glob)**/.rqm/*.yml')\n\
+         tmp=$(mktemp -d) || exit 1\n\
+         trap 'rm -rf \"$tmp\"' EXIT\n\
+         set -f\n\
+         IFS='\n\
+         '\n\
+         git checkout-index --prefix=\"$tmp/\" -- $files $settings || exit 1\n\
+         set --\n\
+         for file in $files; do set -- \"$@\" \"$tmp/$file\"; done\n\
+         {} --output text validate \"$@\"\n",
+        HOOK_MARKER,
+        pathspecs.join(" "),
+        validator
+    )
+}
+
+/// A `.gitattributes` pattern as a pathspec matching the same files
+///
+/// Like in `.gitattributes`, a pattern without a slash matches in every
+/// directory, one with a slash relative to the root.
+fn pathspec(pattern: &str) -> String {
+    let pattern = pattern.trim_start_matches('/');
+    if pattern.contains('/') {
+        format!(":(glob){}", pattern)
+    } else {
+        format!(":(glob)**/{}", pattern)
+    }
+}
+
+/// Run git in `dir`, returning its output without the final newline
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| Error::custom(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(Error::custom(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end_matches(['\n', '\r'])
+        .to_string())
+}
+
+/// Quote a word for the shell, unless it is made of safe characters only
+fn shell_quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:@%+".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn repository() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]).unwrap();
+        fs::create_dir(temp.path().join("docs")).unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_install_is_idempotent() {
+        let temp = repository();
+        fs::write(temp.path().join(".gitattributes"), "*.png binary").unwrap();
+
+        let options = HookOptions {
+            validator: "/opt/rqm tools/rqm-validator".to_string(),
+            ..HookOptions::default()
+        };
+        let result = install(temp.path().join("docs"), &options).unwrap();
+        assert_eq!(result.changed.len(), 3);
+
+        let hook = fs::read_to_string(temp.path().join(".git/hooks/pre-commit")).unwrap();
+        assert!(hook.contains("-- ':(glob)**/requirements.yml' ':(glob)**/*.rqm.yml')\n"));
+        assert!(hook.contains("'/opt/rqm tools/rqm-validator' --output text validate \"$@\"\n"));
+        assert_eq!(
+            git(temp.path(), &["config", "--get", "merge.rqm.driver"]).unwrap(),
+            "'/opt/rqm tools/rqm-validator' merge %O %A %B --path %P"
+        );
+        assert_eq!(
+            fs::read_to_string(temp.path().join(".gitattributes")).unwrap(),
+            "*.png binary\nrequirements.yml merge=rqm\n*.rqm.yml merge=rqm\n"
+        );
+
+        let again = install(temp.path(), &options).unwrap();
+        assert!(again.changed.is_empty());
+
+        // A driver installed without the path is brought up to date
+        git(
+            temp.path(),
+            &["config", "merge.rqm.driver", "rqm-validator merge %O %A %B"],
+        )
+        .unwrap();
+        let updated = install(temp.path(), &options).unwrap();
+        assert_eq!(updated.changed.len(), 1);
+    }
+
+    #[test]
+    fn test_install_respects_hooks_path() {
+        let temp = repository();
+        git(temp.path(), &["config", "core.hooksPath", "tools/hooks"]).unwrap();
+        install(temp.path(), &HookOptions::default()).unwrap();
+        assert!(temp.path().join("tools/hooks/pre-commit").exists());
+        assert!(!temp.path().join(".git/hooks/pre-commit").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_validates_staged_files() {
+        let temp = repository();
+        let validator = temp.path().join("fake validator");
+        fs::write(
+            &validator,
+            "#!/bin/sh\nshift 3\nfor f in \"$@\"; do cat \"$f\"; done\n[ -f \"$(dirname \"$1\")/.rqm/lint.yml\" ]\n",
+        )
+        .unwrap();
+        make_executable(&validator).unwrap();
+        let options = HookOptions {
+            validator: validator.to_str().unwrap().to_string(),
+            ..HookOptions::default()
+        };
+        install(temp.path(), &options).unwrap();
+
+        let dir = temp.path().join("my docs");
+        fs::create_dir_all(dir.join(".rqm")).unwrap();
+        fs::write(dir.join(".rqm/lint.yml"), "rules: {}\n").unwrap();
+        fs::write(dir.join("requirements.yml"), "staged\n").unwrap();
+        git(temp.path(), &["add", "."]).unwrap();
+        fs::write(dir.join("requirements.yml"), "unstaged\n").unwrap();
+
+        let output = Command::new(temp.path().join(".git/hooks/pre-commit"))
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "staged\n");
+    }
+
+    #[test]
+    fn test_install_keeps_foreign_hook() {
+        let temp = repository();
+        let hook = temp.path().join(".git/hooks/pre-commit");
+        fs::write(&hook, "#!/bin/sh\nnpm test\n").unwrap();
+
+        assert!(install(temp.path(), &HookOptions::default()).is_err());
+        assert_eq!(fs::read_to_string(&hook).unwrap(), "#!/bin/sh\nnpm test\n");
+
+        let options = HookOptions {
+            force: true,
+            ..HookOptions::default()
+        };
+        install(temp.path(), &options).unwrap();
+        assert!(fs::read_to_string(&hook).unwrap().contains(HOOK_MARKER));
+
+        let outside = TempDir::new().unwrap();
+        assert!(install(outside.path(), &HookOptions::default()).is_err());
+    }
+}
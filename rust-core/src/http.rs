@@ -0,0 +1,159 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! JSON requests through `curl`, shared by the tracker syncs and the
+//! notification webhooks
+//!
+//! The whole request goes to curl as a config on standard input: URL,
+//! headers, credentials and body. Secrets never appear in the process list,
+//! and large bodies are not limited by the length of the command line.
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Quote a value for a curl config
+pub(crate) fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// A request to send with [`send`]
+pub(crate) struct Request<'a> {
+    pub(crate) method: &'a str,
+    pub(crate) url: &'a str,
+
+    /// Headers as `Name: value`
+    pub(crate) headers: Vec<String>,
+
+    /// `user:password` for basic authentication
+    pub(crate) user: Option<String>,
+
+    pub(crate) body: Option<&'a Value>,
+}
+
+impl<'a> Request<'a> {
+    pub(crate) fn new(method: &'a str, url: &'a str) -> Self {
+        Self {
+            method,
+            url,
+            headers: vec![],
+            user: None,
+            body: None,
+        }
+    }
+
+    /// The curl config sending this request
+    fn config(&self) -> String {
+        let mut config = String::new();
+        let _ = writeln!(config, "url = {}", curl_quote(self.url));
+        let _ = writeln!(config, "request = {}", curl_quote(self.method));
+        for header in &self.headers {
+            let _ = writeln!(config, "header = {}", curl_quote(header));
+        }
+        if let Some(user) = &self.user {
+            let _ = writeln!(config, "user = {}", curl_quote(user));
+        }
+        if let Some(body) = self.body {
+            let _ = writeln!(config, "header = \"Content-Type: application/json\"");
+            let _ = writeln!(config, "data-binary = {}", curl_quote(&body.to_string()));
+        }
+        config
+    }
+}
+
+/// HTTP status and body of a response
+pub(crate) struct Response {
+    pub(crate) status: String,
+    pub(crate) body: String,
+}
+
+impl Response {
+    pub(crate) fn is_success(&self) -> bool {
+        self.status.starts_with('2')
+    }
+
+    /// The response, or an error naming `service` and `what` was requested
+    /// unless the status is 2xx
+    pub(crate) fn check(self, service: &str, what: &str) -> Result<Self> {
+        if !self.is_success() {
+            return Err(Error::custom(format!(
+                "{} {} failed with HTTP status '{}': {}",
+                service,
+                what,
+                self.status,
+                self.body.chars().take(200).collect::<String>()
+            )));
+        }
+        Ok(self)
+    }
+
+    /// The body as JSON, or `Null` if empty, as [`Response::check`]ed
+    #[cfg(any(feature = "jira", feature = "github"))]
+    pub(crate) fn json(self, service: &str, what: &str) -> Result<Value> {
+        let response = self.check(service, what)?;
+        if response.body.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&response.body)
+            .map_err(|e| Error::custom(format!("Invalid {} response: {}", service, e)))
+    }
+}
+
+/// Send a request and wait for the response
+pub(crate) fn send(request: &Request) -> Result<Response> {
+    let mut child = Command::new("curl")
+        .args(["-s", "-K", "-", "-w", "\n%{http_code}"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(request.config().as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = output.rsplit_once('\n').unwrap_or(("", &output));
+    Ok(Response {
+        status: status.trim().to_string(),
+        body: body.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_carries_the_whole_request() {
+        let body = json!({ "text": "Line \"one\"\nC:\\path" });
+        let mut request = Request::new("POST", "https://example.com/hook?key=secret");
+        request
+            .headers
+            .push("Authorization: Bearer token".to_string());
+        request.body = Some(&body);
+        assert_eq!(
+            request.config(),
+            "url = \"https://example.com/hook?key=secret\"\n\
+             request = \"POST\"\n\
+             header = \"Authorization: Bearer token\"\n\
+             header = \"Content-Type: application/json\"\n\
+             data-binary = \"{\\\"text\\\":\\\"Line \\\\\\\"one\\\\\\\"\\\\nC:\\\\\\\\path\\\"}\"\n"
+        );
+    }
+}
@@ -0,0 +1,16 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Exchange of requirements with other requirements management tools
+
+#[cfg(feature = "github")]
+pub mod github;
+#[cfg(feature = "jira")]
+pub mod jira;
+pub mod oslc;
+pub mod reqif;
+#[cfg(any(feature = "jira", feature = "github"))]
+mod sync;
+
+pub use reqif::{from_reqif, to_reqif};
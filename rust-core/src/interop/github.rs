@@ -0,0 +1,904 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Synchronization with GitHub issues
+//!
+//! Enabled by the `github` feature. Each requirement maps to one issue,
+//! found through a marker with the requirement's UUID at the end of the
+//! issue body. Pushing writes the summary, description, tags (as labels),
+//! owner (as assignee) and a checklist of child requirements to the issue;
+//! pulling takes over issues being closed or reopened as status changes.
+//! With a [`GitHubProject`], pushing also adds the issues to a project
+//! board and sets its status field from the requirement's status.
+//! The YAML stays the source of truth for everything but the status. The
+//! state of the last sync is kept in `.rqm/github-sync.json`, so a status
+//! changed on both sides since then is reported as a conflict instead of
+//! being overwritten.
+
+use super::sync::{self, Pull};
+use crate::http::{self, Request};
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference, Status};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Prefix of the marker linking an issue to a requirement
+const MARKER: &str = "<!-- rqm:uuid=";
+
+/// File in `.rqm` holding the state of the last sync
+const STATE_FILE: &str = "github-sync.json";
+
+/// Sends requests to the GitHub REST API
+pub trait GitHubTransport {
+    /// Send a request to `path` below the API root and return the JSON
+    /// response, or `Null` for empty responses
+    fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value>;
+}
+
+/// Talks to GitHub through `curl`
+///
+/// The token is sent on standard input so it never appears in the process
+/// list.
+#[derive(Debug, Clone)]
+pub struct CurlTransport {
+    api_url: String,
+    token: String,
+}
+
+impl CurlTransport {
+    /// Use the public GitHub API with `token`
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            api_url: "https://api.github.com".to_string(),
+            token: token.into(),
+        }
+    }
+
+    /// Take the token from `GITHUB_TOKEN`
+    pub fn from_env() -> Result<Self> {
+        let token =
+            std::env::var("GITHUB_TOKEN").map_err(|_| Error::custom("GITHUB_TOKEN is not set"))?;
+        Ok(Self::new(token))
+    }
+
+    /// Use a GitHub Enterprise API endpoint instead
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+}
+
+impl GitHubTransport for CurlTransport {
+    fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let url = format!("{}/{}", self.api_url.trim_end_matches('/'), path);
+        let mut request = Request::new(method, &url);
+        request.headers = vec![
+            "Accept: application/vnd.github+json".to_string(),
+            format!("Authorization: Bearer {}", self.token),
+        ];
+        request.body = body;
+        http::send(&request)?.json("GitHub", &format!("{} {}", method, path))
+    }
+}
+
+/// Which repository requirements map to, and what issue states mean
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitHubConfig {
+    /// Repository as "owner/name"
+    pub repository: String,
+
+    /// Status of requirements whose issue is closed
+    #[serde(default = "default_closed_status")]
+    pub closed_status: Status,
+
+    /// Status of implemented or verified requirements whose issue is reopened
+    #[serde(default = "default_reopened_status")]
+    pub reopened_status: Status,
+
+    /// Project board whose status field follows the requirements
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<GitHubProject>,
+}
+
+/// A GitHub project board, whose status field is set from requirement
+/// statuses
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitHubProject {
+    /// Node ID of the project, e.g. "PVT_kwDOBQfyNM4AAfu4"
+    pub id: String,
+
+    /// Node ID of the project's single-select status field
+    pub status_field: String,
+
+    /// ID of the field's option for each requirement status; requirements
+    /// in other statuses leave the field alone
+    pub options: BTreeMap<Status, String>,
+}
+
+fn default_closed_status() -> Status {
+    Status::Implemented
+}
+
+fn default_reopened_status() -> Status {
+    Status::Approved
+}
+
+impl GitHubConfig {
+    /// Map requirements to issues of `repository`
+    pub fn new(repository: impl Into<String>) -> Self {
+        Self {
+            repository: repository.into(),
+            closed_status: default_closed_status(),
+            reopened_status: default_reopened_status(),
+            project: None,
+        }
+    }
+
+    /// Also keep the status field of a project board up to date
+    pub fn with_project(mut self, project: GitHubProject) -> Self {
+        self.project = Some(project);
+        self
+    }
+}
+
+/// An issue as seen at the last sync
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncedIssue {
+    pub number: u64,
+
+    /// Requirement status at the last sync
+    pub status: Option<Status>,
+
+    /// Whether the issue was closed at the last sync
+    pub closed: bool,
+
+    /// Requirement status last set on the project board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_status: Option<Status>,
+}
+
+/// State of the last sync, stored in `.rqm/github-sync.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GitHubState {
+    #[serde(default)]
+    pub issues: BTreeMap<Uuid, SyncedIssue>,
+}
+
+impl GitHubState {
+    /// Load the state from an `.rqm` directory, starting empty if it is absent
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Self> {
+        sync::load_state(rqm_dir.as_ref(), STATE_FILE, "GitHub")
+    }
+
+    /// Save the state to an `.rqm` directory
+    pub fn save<P: AsRef<Path>>(&self, rqm_dir: P) -> Result<()> {
+        sync::save_state(self, rqm_dir.as_ref(), STATE_FILE, "GitHub")
+    }
+}
+
+/// A requirement status taken over from GitHub
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub summary: String,
+    pub number: u64,
+    pub from: Option<Status>,
+    pub to: Status,
+}
+
+/// A status changed both in the requirements and in GitHub since the last
+/// sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubConflict {
+    pub summary: String,
+    pub number: u64,
+    pub local: Option<Status>,
+    pub closed: bool,
+}
+
+/// What a push or pull did, or would do in a dry run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GitHubReport {
+    pub dry_run: bool,
+
+    /// Summaries of requirements that got a new issue
+    pub created: Vec<String>,
+
+    /// Numbers of issues that were updated
+    pub updated: Vec<u64>,
+
+    /// Summaries of requirements whose status was set on the project board
+    pub project_updates: Vec<String>,
+
+    pub status_changes: Vec<StatusChange>,
+    pub conflicts: Vec<GitHubConflict>,
+}
+
+/// The fields of an issue that take part in syncing
+struct Issue {
+    number: u64,
+    node_id: String,
+    title: String,
+    body: String,
+    labels: Vec<String>,
+    assignees: Vec<String>,
+    closed: bool,
+}
+
+/// The marker linking an issue body to the requirement with `uuid`
+fn marker(uuid: &Uuid) -> String {
+    format!("{}{} -->", MARKER, uuid)
+}
+
+/// The requirement UUID in an issue body, if any
+fn body_uuid(body: &str) -> Option<Uuid> {
+    let start = body.rfind(MARKER)? + MARKER.len();
+    let end = body[start..].find("-->")?;
+    Uuid::parse_str(body[start..start + end].trim()).ok()
+}
+
+/// GitHub user for an owner: `@user` directly, aliases through their
+/// GitHub account
+fn assignee(owner: &str, config: &RequirementConfig) -> Option<String> {
+    if let Some(user) = owner.strip_prefix('@') {
+        return Some(user.to_string());
+    }
+    config
+        .aliases
+        .iter()
+        .find(|alias| alias.alias == owner)?
+        .github
+        .as_ref()
+        .map(|user| user.trim_start_matches('@').to_string())
+}
+
+/// Pushes requirements to GitHub issues and pulls their state back
+pub struct GitHubSync<T: GitHubTransport> {
+    transport: T,
+    config: GitHubConfig,
+    state: GitHubState,
+}
+
+impl<T: GitHubTransport> GitHubSync<T> {
+    /// Sync through `transport` with no record of earlier syncs
+    pub fn new(transport: T, config: GitHubConfig) -> Self {
+        Self {
+            transport,
+            config,
+            state: GitHubState::default(),
+        }
+    }
+
+    /// Continue from the state of an earlier sync
+    pub fn with_state(mut self, state: GitHubState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// State after the syncs made so far
+    pub fn state(&self) -> &GitHubState {
+        &self.state
+    }
+
+    /// All issues of the repository that are linked to a requirement
+    fn issues(&self) -> Result<HashMap<Uuid, Issue>> {
+        let mut issues = HashMap::new();
+        for page in 1.. {
+            let path = format!(
+                "repos/{}/issues?state=all&per_page=100&page={}",
+                self.config.repository, page
+            );
+            let found = self.transport.send("GET", &path, None)?;
+            let found = found.as_array().cloned().unwrap_or_default();
+            for issue in &found {
+                // The issues endpoint lists pull requests, too
+                if issue.get("pull_request").is_some() {
+                    continue;
+                }
+                let body = issue["body"].as_str().unwrap_or_default();
+                let Some(uuid) = body_uuid(body) else {
+                    continue;
+                };
+                let names = |list: &Value, field: &str| -> Vec<String> {
+                    let mut names: Vec<String> = list
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|item| item[field].as_str().map(String::from))
+                        .collect();
+                    names.sort();
+                    names
+                };
+                issues.insert(
+                    uuid,
+                    Issue {
+                        number: issue["number"].as_u64().unwrap_or_default(),
+                        node_id: issue["node_id"].as_str().unwrap_or_default().to_string(),
+                        title: issue["title"].as_str().unwrap_or_default().to_string(),
+                        body: body.to_string(),
+                        labels: names(&issue["labels"], "name"),
+                        assignees: names(&issue["assignees"], "login"),
+                        closed: issue["state"] == "closed",
+                    },
+                );
+            }
+            if found.len() < 100 {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Issue body: description, checklist of children and the marker
+    ///
+    /// Children are checked once implemented or verified, and link to their
+    /// issue where one is known.
+    fn body(
+        &self,
+        req: &Requirement,
+        uuid: &Uuid,
+        requirements: &HashMap<&str, &Requirement>,
+        numbers: &HashMap<&str, u64>,
+    ) -> String {
+        let mut body = String::new();
+        if let Some(description) = &req.description {
+            let _ = write!(body, "{}\n\n", description.trim());
+        }
+        if !req.requirements.is_empty() {
+            body.push_str("### Requirements\n\n");
+            for child in &req.requirements {
+                let summary = match child {
+                    RequirementReference::Full(child) => child.summary.as_str(),
+                    RequirementReference::Reference(summary) => summary.as_str(),
+                };
+                let done = requirements
+                    .get(summary)
+                    .and_then(|child| child.status)
+                    .is_some_and(|status| matches!(status, Status::Implemented | Status::Verified));
+                let _ = write!(body, "- [{}] ", if done { "x" } else { " " });
+                if let Some(number) = numbers.get(summary) {
+                    let _ = write!(body, "#{} ", number);
+                }
+                let _ = writeln!(body, "{}", summary);
+            }
+            body.push('\n');
+        }
+        body.push_str(&marker(uuid));
+        body
+    }
+
+    /// Create or update an issue for every requirement
+    ///
+    /// Titles, bodies, labels and assignees in GitHub are overwritten; issue
+    /// states are left to [`GitHubSync::pull`]. Children get their issues
+    /// first, so the checklists of their parents can link to them. A dry run
+    /// only reads from GitHub.
+    pub fn push(
+        &mut self,
+        requirements: &RequirementConfig,
+        store: &mut MetadataStore,
+        dry_run: bool,
+    ) -> Result<GitHubReport> {
+        let mut issues = self.issues()?;
+        let mut report = GitHubReport {
+            dry_run,
+            ..GitHubReport::default()
+        };
+
+        let all = requirements.all_requirements();
+        let mut by_summary: HashMap<&str, &Requirement> = HashMap::new();
+        for req in &all {
+            by_summary.entry(req.summary.as_str()).or_insert(req);
+        }
+        let mut uuids = HashMap::new();
+        for req in by_summary.values() {
+            if let Some(meta) = sync::push_metadata(store, req, dry_run)? {
+                uuids.insert(req.summary.as_str(), meta.uuid);
+            }
+        }
+        let mut numbers: HashMap<&str, u64> = uuids
+            .iter()
+            .filter_map(|(&summary, uuid)| Some((summary, issues.get(uuid)?.number)))
+            .collect();
+
+        let mut done = HashSet::new();
+        for req in all.iter().rev() {
+            if !done.insert(req.summary.as_str()) {
+                continue;
+            }
+            let Some(&uuid) = uuids.get(req.summary.as_str()) else {
+                report.created.push(req.summary.clone());
+                continue;
+            };
+            let body = self.body(req, &uuid, &by_summary, &numbers);
+            let mut labels = req.tags.clone();
+            labels.sort();
+            labels.dedup();
+            let assignees: Vec<String> = req
+                .owner
+                .as_ref()
+                .and_then(|owner| assignee(owner.as_str(), requirements))
+                .into_iter()
+                .collect();
+            let fields = json!({
+                "title": req.summary,
+                "body": body,
+                "labels": labels,
+                "assignees": assignees,
+            });
+
+            let node_id = match issues.get(&uuid) {
+                None => {
+                    report.created.push(req.summary.clone());
+                    if dry_run {
+                        self.plan_project_status(req, &uuid, &mut report);
+                        continue;
+                    }
+                    let path = format!("repos/{}/issues", self.config.repository);
+                    let created = self.transport.send("POST", &path, Some(&fields))?;
+                    let number = created["number"].as_u64().unwrap_or_default();
+                    numbers.insert(req.summary.as_str(), number);
+                    self.remember(uuid, number, req.status, false);
+                    created["node_id"].as_str().unwrap_or_default().to_string()
+                }
+                Some(issue) => {
+                    if issue.title != req.summary
+                        || issue.body.trim() != body.trim()
+                        || issue.labels != labels
+                        || issue.assignees != assignees
+                    {
+                        report.updated.push(issue.number);
+                        if !dry_run {
+                            let path =
+                                format!("repos/{}/issues/{}", self.config.repository, issue.number);
+                            self.transport.send("PATCH", &path, Some(&fields))?;
+                        }
+                    }
+                    if !dry_run && !self.state.issues.contains_key(&uuid) {
+                        self.remember(uuid, issue.number, req.status, issue.closed);
+                    }
+                    issue.node_id.clone()
+                }
+            };
+            if let Some(option) = self.plan_project_status(req, &uuid, &mut report) {
+                if !dry_run {
+                    self.set_project_status(&node_id, &option)?;
+                    if let Some(synced) = self.state.issues.get_mut(&uuid) {
+                        synced.project_status = req.status;
+                    }
+                }
+            }
+            issues.remove(&uuid);
+        }
+        report.created.reverse();
+        report.updated.sort_unstable();
+        report.project_updates.reverse();
+        Ok(report)
+    }
+
+    /// The project board option for the status of `req`, if the board is
+    /// to be updated, noting the update in `report`
+    fn plan_project_status(
+        &self,
+        req: &Requirement,
+        uuid: &Uuid,
+        report: &mut GitHubReport,
+    ) -> Option<String> {
+        let project = self.config.project.as_ref()?;
+        let option = project.options.get(&req.status?)?;
+        let synced = self.state.issues.get(uuid);
+        if synced.is_some_and(|synced| synced.project_status == req.status) {
+            return None;
+        }
+        report.project_updates.push(req.summary.clone());
+        Some(option.clone())
+    }
+
+    /// Add an issue to the project board, if it is not on it yet, and set
+    /// its status field to `option`
+    fn set_project_status(&self, node_id: &str, option: &str) -> Result<()> {
+        let Some(project) = &self.config.project else {
+            return Ok(());
+        };
+        let added = self.graphql(
+            "mutation($project: ID!, $content: ID!) { \
+             addProjectV2ItemById(input: {projectId: $project, contentId: $content}) \
+             { item { id } } }",
+            json!({ "project": project.id, "content": node_id }),
+        )?;
+        let item = added["addProjectV2ItemById"]["item"]["id"]
+            .as_str()
+            .ok_or_else(|| Error::custom("GitHub did not return the project item"))?;
+        self.graphql(
+            "mutation($project: ID!, $item: ID!, $field: ID!, $option: String!) { \
+             updateProjectV2ItemFieldValue(input: {projectId: $project, itemId: $item, \
+             fieldId: $field, value: {singleSelectOptionId: $option}}) { projectV2Item { id } } }",
+            json!({
+                "project": project.id,
+                "item": item,
+                "field": project.status_field,
+                "option": option,
+            }),
+        )?;
+        Ok(())
+    }
+
+    /// Run a GraphQL query, which Projects are only available through
+    fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        let body = json!({ "query": query, "variables": variables });
+        let response = self.transport.send("POST", "graphql", Some(&body))?;
+        if let Some(error) = response["errors"]
+            .as_array()
+            .and_then(|errors| errors.first())
+        {
+            return Err(Error::custom(format!(
+                "GitHub GraphQL request failed: {}",
+                error["message"].as_str().unwrap_or_default()
+            )));
+        }
+        Ok(response["data"].clone())
+    }
+
+    /// Take over issues being closed or reopened as status changes
+    ///
+    /// A closed issue sets [`GitHubConfig::closed_status`] unless the
+    /// requirement is already implemented or verified; a reopened one sets
+    /// [`GitHubConfig::reopened_status`] if it is. Requirements whose status
+    /// changed locally while their issue was closed or reopened are left
+    /// alone and reported as conflicts. A dry run changes neither
+    /// `requirements` nor the sync state.
+    pub fn pull(
+        &mut self,
+        requirements: &mut RequirementConfig,
+        store: &MetadataStore,
+        dry_run: bool,
+    ) -> Result<GitHubReport> {
+        let issues = self.issues()?;
+        let mut report = GitHubReport {
+            dry_run,
+            ..GitHubReport::default()
+        };
+
+        sync::for_each_with_metadata(requirements, store, |req, meta| {
+            let Some(issue) = issues.get(&meta.uuid) else {
+                return Ok(());
+            };
+
+            let done = matches!(req.status, Some(Status::Implemented | Status::Verified));
+            let remote = match (issue.closed, done) {
+                (true, false) => Some(self.config.closed_status),
+                (false, true) => Some(self.config.reopened_status),
+                _ => None,
+            };
+            let synced = self.state.issues.get(&meta.uuid);
+            let local_changed = synced.is_some_and(|synced| synced.status != req.status);
+            let remote_changed = match synced {
+                Some(synced) => synced.closed != issue.closed,
+                None => issue.closed,
+            };
+
+            match sync::reconcile(req.status, remote, local_changed, remote_changed) {
+                Pull::Conflict => {
+                    report.conflicts.push(GitHubConflict {
+                        summary: req.summary.clone(),
+                        number: issue.number,
+                        local: req.status,
+                        closed: issue.closed,
+                    });
+                    return Ok(());
+                }
+                Pull::Take(remote) => {
+                    report.status_changes.push(StatusChange {
+                        summary: req.summary.clone(),
+                        number: issue.number,
+                        from: req.status,
+                        to: remote,
+                    });
+                    if !dry_run {
+                        req.status = Some(remote);
+                    }
+                }
+                Pull::Keep => {}
+            }
+            if !dry_run {
+                self.remember(meta.uuid, issue.number, req.status, issue.closed);
+            }
+            Ok(())
+        })?;
+
+        report.status_changes.sort_by_key(|change| change.number);
+        report.conflicts.sort_by_key(|conflict| conflict.number);
+        Ok(report)
+    }
+
+    /// [`GitHubSync::pull`] into a requirements file, writing the new
+    /// statuses back with [`write_statuses`](crate::metadata::write_statuses)
+    /// so the rest of the file stays as it is
+    pub fn pull_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        store: &MetadataStore,
+        dry_run: bool,
+    ) -> Result<GitHubReport> {
+        sync::pull_file(
+            path.as_ref(),
+            dry_run,
+            |requirements| self.pull(requirements, store, dry_run),
+            |report| {
+                report
+                    .status_changes
+                    .iter()
+                    .map(|change| (change.summary.clone(), change.to))
+                    .collect()
+            },
+        )
+    }
+
+    fn remember(&mut self, uuid: Uuid, number: u64, status: Option<Status>, closed: bool) {
+        let project_status = self
+            .state
+            .issues
+            .get(&uuid)
+            .and_then(|synced| synced.project_status);
+        self.state.issues.insert(
+            uuid,
+            SyncedIssue {
+                number,
+                status,
+                closed,
+                project_status,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    /// An in-memory GitHub repository
+    #[derive(Default)]
+    struct FakeGitHub {
+        issues: RefCell<Vec<Value>>,
+        writes: RefCell<usize>,
+
+        /// Status option of each project item, by issue node ID
+        board: RefCell<BTreeMap<String, String>>,
+    }
+
+    impl FakeGitHub {
+        fn issue(&self, number: u64) -> Value {
+            self.issues.borrow()[number as usize - 1].clone()
+        }
+
+        fn set_state(&self, number: u64, state: &str) {
+            self.issues.borrow_mut()[number as usize - 1]["state"] = json!(state);
+        }
+    }
+
+    impl GitHubTransport for &FakeGitHub {
+        fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+            let body = body.cloned().unwrap_or(Value::Null);
+            let to_objects = |names: &Value, field: &str| -> Value {
+                names
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|name| json!({ field: name }))
+                    .collect()
+            };
+            match method {
+                "POST" if path == "graphql" => {
+                    let variables = &body["variables"];
+                    let query = body["query"].as_str().unwrap();
+                    if query.contains("addProjectV2ItemById") {
+                        assert_eq!(variables["project"], "PVT_1");
+                        let item = variables["content"].as_str().unwrap();
+                        return Ok(json!({ "data": {
+                            "addProjectV2ItemById": { "item": { "id": item } }
+                        }}));
+                    }
+                    assert_eq!(variables["field"], "PVTSSF_status");
+                    self.board.borrow_mut().insert(
+                        variables["item"].as_str().unwrap().to_string(),
+                        variables["option"].as_str().unwrap().to_string(),
+                    );
+                    Ok(json!({ "data": {} }))
+                }
+                "GET" => {
+                    assert!(path.starts_with("repos/acme/pay/issues?state=all"));
+                    let mut issues = self.issues.borrow().clone();
+                    // A pull request, which must be ignored
+                    issues.push(json!({ "number": 99, "pull_request": {}, "body": "" }));
+                    if path.ends_with("page=1") {
+                        Ok(Value::Array(issues))
+                    } else {
+                        Ok(json!([]))
+                    }
+                }
+                "POST" => {
+                    *self.writes.borrow_mut() += 1;
+                    let mut issues = self.issues.borrow_mut();
+                    let number = issues.len() as u64 + 1;
+                    let mut issue = body.clone();
+                    issue["number"] = json!(number);
+                    issue["node_id"] = json!(format!("I_{}", number));
+                    issue["state"] = json!("open");
+                    issue["labels"] = to_objects(&body["labels"], "name");
+                    issue["assignees"] = to_objects(&body["assignees"], "login");
+                    issues.push(issue);
+                    Ok(json!({ "number": number, "node_id": format!("I_{}", number) }))
+                }
+                "PATCH" => {
+                    *self.writes.borrow_mut() += 1;
+                    let number: usize = path.rsplit('/').next().unwrap().parse().unwrap();
+                    let issue = &mut self.issues.borrow_mut()[number - 1];
+                    issue["title"] = body["title"].clone();
+                    issue["body"] = body["body"].clone();
+                    issue["labels"] = to_objects(&body["labels"], "name");
+                    issue["assignees"] = to_objects(&body["assignees"], "login");
+                    Ok(Value::Null)
+                }
+                _ => Err(Error::custom(format!("unexpected {} {}", method, path))),
+            }
+        }
+    }
+
+    const YAML: &str = r#"
+version: "1.0"
+aliases:
+  - alias: bob
+    github: bobby
+requirements:
+  - summary: Refunds
+    status: approved
+    owner: "@alice"
+    tags: [payments, web]
+    description: Customers can get refunds
+    requirements:
+      - summary: Partial refunds
+        status: implemented
+        owner: bob
+      - Receipts
+  - summary: Receipts
+    status: draft
+"#;
+
+    #[test]
+    fn test_body_marker_roundtrip() {
+        let uuid = Uuid::new_v4();
+        let body = format!("Text\n\n{}", marker(&uuid));
+        assert_eq!(body_uuid(&body), Some(uuid));
+        assert_eq!(body_uuid("<!-- rqm:uuid=nonsense -->"), None);
+        assert_eq!(body_uuid("Plain issue"), None);
+    }
+
+    #[test]
+    fn test_push_creates_issues_with_checklists() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let github = FakeGitHub::default();
+        let mut sync = GitHubSync::new(&github, GitHubConfig::new("acme/pay"));
+        let mut config = Parser::parse_str(YAML).unwrap();
+
+        let dry = sync.push(&config, &mut store, true).unwrap();
+        assert_eq!(dry.created, vec!["Refunds", "Partial refunds", "Receipts"]);
+        assert_eq!(*github.writes.borrow(), 0);
+        assert!(store.all_metadata().unwrap().is_empty());
+
+        sync.push(&config, &mut store, false).unwrap();
+        assert_eq!(github.issues.borrow().len(), 3);
+        let refunds = github.issue(3);
+        assert_eq!(refunds["title"], "Refunds");
+        assert_eq!(
+            refunds["labels"],
+            json!([{ "name": "payments" }, { "name": "web" }])
+        );
+        assert_eq!(refunds["assignees"], json!([{ "login": "alice" }]));
+        let uuid = store.find_metadata("Refunds").unwrap().unwrap().uuid;
+        assert_eq!(
+            refunds["body"],
+            format!(
+                "Customers can get refunds\n\n### Requirements\n\n- [x] #2 Partial refunds\n- [ ] #1 Receipts\n\n{}",
+                marker(&uuid)
+            )
+        );
+        assert_eq!(github.issue(2)["assignees"], json!([{ "login": "bobby" }]));
+
+        let unchanged = sync.push(&config, &mut store, false).unwrap();
+        assert!(unchanged.created.is_empty() && unchanged.updated.is_empty());
+
+        config.requirements[0].tags.push("api".to_string());
+        let updated = sync.push(&config, &mut store, false).unwrap();
+        assert_eq!(updated.updated, vec![3]);
+        assert_eq!(github.issue(3)["labels"][0], json!({ "name": "api" }));
+    }
+
+    #[test]
+    fn test_push_sets_project_status() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let github = FakeGitHub::default();
+        let project = GitHubProject {
+            id: "PVT_1".to_string(),
+            status_field: "PVTSSF_status".to_string(),
+            options: BTreeMap::from([
+                (Status::Approved, "todo".to_string()),
+                (Status::Implemented, "done".to_string()),
+            ]),
+        };
+        let mut sync =
+            GitHubSync::new(&github, GitHubConfig::new("acme/pay").with_project(project));
+        let mut config = Parser::parse_str(YAML).unwrap();
+
+        sync.push(&config, &mut store, false).unwrap();
+        // Receipts is a draft, which has no column
+        assert_eq!(
+            *github.board.borrow(),
+            BTreeMap::from([
+                ("I_2".to_string(), "done".to_string()),
+                ("I_3".to_string(), "todo".to_string()),
+            ])
+        );
+        assert!(sync
+            .push(&config, &mut store, false)
+            .unwrap()
+            .project_updates
+            .is_empty());
+
+        config.requirements[0].status = Some(Status::Implemented);
+        let dry = sync.push(&config, &mut store, true).unwrap();
+        assert_eq!(dry.project_updates, vec!["Refunds"]);
+        assert_eq!(github.board.borrow()["I_3"], "todo");
+        sync.push(&config, &mut store, false).unwrap();
+        assert_eq!(github.board.borrow()["I_3"], "done");
+    }
+
+    #[test]
+    fn test_pull_takes_closed_and_reopened_issues() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "REQ".to_string()).unwrap();
+        let github = FakeGitHub::default();
+        let mut sync = GitHubSync::new(&github, GitHubConfig::new("acme/pay"));
+        let mut config = Parser::parse_str(YAML).unwrap();
+        sync.push(&config, &mut store, false).unwrap();
+        sync.state().save(&rqm_dir).unwrap();
+
+        // Refunds is closed and Receipts too, but Receipts changed locally
+        github.set_state(3, "closed");
+        github.set_state(1, "closed");
+        config.requirements[1].status = Some(Status::Proposed);
+
+        let mut sync = GitHubSync::new(&github, GitHubConfig::new("acme/pay"))
+            .with_state(GitHubState::load(&rqm_dir).unwrap());
+        let report = sync.pull(&mut config, &store, false).unwrap();
+        assert_eq!(
+            report.status_changes,
+            vec![StatusChange {
+                summary: "Refunds".to_string(),
+                number: 3,
+                from: Some(Status::Approved),
+                to: Status::Implemented,
+            }]
+        );
+        assert_eq!(config.requirements[0].status, Some(Status::Implemented));
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].number, 1);
+
+        github.set_state(3, "open");
+        let dry = sync.pull(&mut config, &store, true).unwrap();
+        assert_eq!(dry.status_changes[0].to, Status::Approved);
+        assert_eq!(config.requirements[0].status, Some(Status::Implemented));
+        sync.pull(&mut config, &store, false).unwrap();
+        assert_eq!(config.requirements[0].status, Some(Status::Approved));
+    }
+}
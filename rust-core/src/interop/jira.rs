@@ -0,0 +1,659 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Synchronization with Jira issues
+//!
+//! Enabled by the `jira` feature. Each requirement maps to one issue, found
+//! through a custom field holding the requirement's UUID. Pushing creates
+//! and updates issues from the requirements; pulling brings issue statuses
+//! back into the requirements. The state of the last sync is kept in
+//! `.rqm/jira-sync.json`, so a status changed on both sides since then is
+//! reported as a conflict instead of being overwritten.
+
+use super::sync::{self, Pull};
+use crate::http::{self, Request};
+use crate::metadata::MetadataStore;
+use crate::types::{RequirementConfig, Status};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use uuid::Uuid;
+
+/// File in `.rqm` holding the state of the last sync
+const STATE_FILE: &str = "jira-sync.json";
+
+/// Sends requests to the Jira REST API
+pub trait JiraTransport {
+    /// Send a request to `path` below the API root and return the JSON
+    /// response, or `Null` for empty responses
+    fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value>;
+}
+
+/// Talks to Jira through `curl`
+///
+/// Credentials are passed to curl on standard input, so they never appear
+/// in the process list. With a user, the token is sent as basic
+/// authentication (Jira Cloud API tokens); without, as a bearer token (Data
+/// Center personal access tokens).
+#[derive(Debug, Clone)]
+pub struct CurlTransport {
+    base_url: String,
+    user: Option<String>,
+    token: String,
+}
+
+impl CurlTransport {
+    /// Connect to the Jira instance at `base_url`
+    pub fn new(
+        base_url: impl Into<String>,
+        user: Option<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        Self {
+            base_url: base_url.into(),
+            user,
+            token: token.into(),
+        }
+    }
+
+    /// Take credentials from `JIRA_USER` and `JIRA_TOKEN`
+    pub fn from_env(base_url: impl Into<String>) -> Result<Self> {
+        let token =
+            std::env::var("JIRA_TOKEN").map_err(|_| Error::custom("JIRA_TOKEN is not set"))?;
+        Ok(Self::new(base_url, std::env::var("JIRA_USER").ok(), token))
+    }
+}
+
+/// Quote a value as a JQL string
+fn jql_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl JiraTransport for CurlTransport {
+    fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let url = format!(
+            "{}/rest/api/2/{}",
+            self.base_url.trim_end_matches('/'),
+            path
+        );
+        let mut request = Request::new(method, &url);
+        request.headers.push("Accept: application/json".to_string());
+        match &self.user {
+            Some(user) => request.user = Some(format!("{}:{}", user, self.token)),
+            None => request
+                .headers
+                .push(format!("Authorization: Bearer {}", self.token)),
+        }
+        request.body = body;
+        http::send(&request)?.json("Jira", &format!("{} {}", method, path))
+    }
+}
+
+/// Which Jira project and fields requirements map to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JiraConfig {
+    /// Key of the project issues are created in, e.g. "PAY"
+    pub project: String,
+
+    /// Issue type of created issues
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+
+    /// Custom field holding the requirement UUID, e.g. "customfield_10100"
+    pub uuid_field: String,
+
+    /// Requirement status for each Jira status name; issues in other
+    /// statuses leave the requirement's status alone
+    #[serde(default = "default_statuses")]
+    pub statuses: BTreeMap<String, Status>,
+}
+
+fn default_issue_type() -> String {
+    "Story".to_string()
+}
+
+fn default_statuses() -> BTreeMap<String, Status> {
+    BTreeMap::from([("Done".to_string(), Status::Implemented)])
+}
+
+impl JiraConfig {
+    /// Map requirements to `project`, with the UUID in `uuid_field`
+    pub fn new(project: impl Into<String>, uuid_field: impl Into<String>) -> Self {
+        Self {
+            project: project.into(),
+            issue_type: default_issue_type(),
+            uuid_field: uuid_field.into(),
+            statuses: default_statuses(),
+        }
+    }
+}
+
+/// An issue as seen at the last sync
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SyncedIssue {
+    pub key: String,
+
+    /// Requirement status at the last sync
+    pub status: Option<Status>,
+
+    /// Jira status name at the last sync, if known
+    pub jira_status: Option<String>,
+}
+
+/// State of the last sync, stored in `.rqm/jira-sync.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JiraState {
+    #[serde(default)]
+    pub issues: BTreeMap<Uuid, SyncedIssue>,
+}
+
+impl JiraState {
+    /// Load the state from an `.rqm` directory, starting empty if it is absent
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Self> {
+        sync::load_state(rqm_dir.as_ref(), STATE_FILE, "Jira")
+    }
+
+    /// Save the state to an `.rqm` directory
+    pub fn save<P: AsRef<Path>>(&self, rqm_dir: P) -> Result<()> {
+        sync::save_state(self, rqm_dir.as_ref(), STATE_FILE, "Jira")
+    }
+}
+
+/// A requirement status taken over from Jira
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub summary: String,
+    pub key: String,
+    pub from: Option<Status>,
+    pub to: Status,
+}
+
+/// A status changed both in the requirements and in Jira since the last sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct JiraConflict {
+    pub summary: String,
+    pub key: String,
+    pub local: Option<Status>,
+    pub remote: String,
+}
+
+/// What a push or pull did, or would do in a dry run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JiraReport {
+    pub dry_run: bool,
+
+    /// Summaries of requirements that got a new issue
+    pub created: Vec<String>,
+
+    /// Keys of issues whose summary or description was updated
+    pub updated: Vec<String>,
+
+    pub status_changes: Vec<StatusChange>,
+    pub conflicts: Vec<JiraConflict>,
+}
+
+/// The fields of an issue that take part in syncing
+struct Issue {
+    key: String,
+    summary: String,
+    description: String,
+    status: String,
+}
+
+/// Pushes requirements to Jira and pulls statuses back
+pub struct JiraSync<T: JiraTransport> {
+    transport: T,
+    config: JiraConfig,
+    state: JiraState,
+}
+
+impl<T: JiraTransport> JiraSync<T> {
+    /// Sync through `transport` with no record of earlier syncs
+    pub fn new(transport: T, config: JiraConfig) -> Self {
+        Self {
+            transport,
+            config,
+            state: JiraState::default(),
+        }
+    }
+
+    /// Continue from the state of an earlier sync
+    pub fn with_state(mut self, state: JiraState) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// State after the syncs made so far
+    pub fn state(&self) -> &JiraState {
+        &self.state
+    }
+
+    /// All issues of the project that are linked to a requirement
+    fn issues(&self) -> Result<HashMap<Uuid, Issue>> {
+        let field = &self.config.uuid_field;
+        let field_id = field
+            .strip_prefix("customfield_")
+            .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+            .ok_or_else(|| {
+                Error::custom(format!(
+                    "Jira UUID field '{}' is not a custom field ID like customfield_10100",
+                    field
+                ))
+            })?;
+        let jql = format!(
+            "project = {} AND cf[{}] is not EMPTY",
+            jql_string(&self.config.project),
+            field_id
+        );
+
+        let mut issues = HashMap::new();
+        let mut start = 0;
+        loop {
+            let body = json!({
+                "jql": jql,
+                "startAt": start,
+                "maxResults": 100,
+                "fields": ["summary", "description", "status", field],
+            });
+            let page = self.transport.send("POST", "search", Some(&body))?;
+            let found = page["issues"].as_array().cloned().unwrap_or_default();
+            for issue in &found {
+                let fields = &issue["fields"];
+                let Some(uuid) = fields[field]
+                    .as_str()
+                    .and_then(|s| Uuid::parse_str(s.trim()).ok())
+                else {
+                    continue;
+                };
+                let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+                issues.insert(
+                    uuid,
+                    Issue {
+                        key: text(&issue["key"]),
+                        summary: text(&fields["summary"]),
+                        description: text(&fields["description"]),
+                        status: text(&fields["status"]["name"]),
+                    },
+                );
+            }
+
+            start += found.len();
+            let total = page["total"].as_u64().unwrap_or(0) as usize;
+            if found.is_empty() || start >= total {
+                return Ok(issues);
+            }
+        }
+    }
+
+    /// Create or update an issue for every requirement
+    ///
+    /// Summaries and descriptions in Jira are overwritten; statuses are
+    /// left to [`JiraSync::pull`]. A dry run only reads from Jira.
+    pub fn push(
+        &mut self,
+        requirements: &RequirementConfig,
+        store: &mut MetadataStore,
+        dry_run: bool,
+    ) -> Result<JiraReport> {
+        let issues = self.issues()?;
+        let mut report = JiraReport {
+            dry_run,
+            ..JiraReport::default()
+        };
+        let mut seen = HashSet::new();
+        for req in requirements.all_requirements() {
+            if !seen.insert(&req.summary) {
+                continue;
+            }
+            let Some(meta) = sync::push_metadata(store, req, dry_run)? else {
+                report.created.push(req.summary.clone());
+                continue;
+            };
+            let description = req.description.clone().unwrap_or_default();
+
+            match issues.get(&meta.uuid) {
+                None => {
+                    report.created.push(req.summary.clone());
+                    if dry_run {
+                        continue;
+                    }
+                    let body = json!({ "fields": {
+                        "project": { "key": self.config.project },
+                        "issuetype": { "name": self.config.issue_type },
+                        "summary": req.summary,
+                        "description": description,
+                        self.config.uuid_field.as_str(): meta.uuid.to_string(),
+                    }});
+                    let created = self.transport.send("POST", "issue", Some(&body))?;
+                    let key = created["key"].as_str().unwrap_or_default().to_string();
+                    self.remember(meta.uuid, key, req.status, None);
+                }
+                Some(issue) => {
+                    if issue.summary != req.summary
+                        || issue.description.trim() != description.trim()
+                    {
+                        report.updated.push(issue.key.clone());
+                        if !dry_run {
+                            let body = json!({ "fields": {
+                                "summary": req.summary,
+                                "description": description,
+                            }});
+                            self.transport.send(
+                                "PUT",
+                                &format!("issue/{}", issue.key),
+                                Some(&body),
+                            )?;
+                        }
+                    }
+                    if !dry_run && !self.state.issues.contains_key(&meta.uuid) {
+                        self.remember(
+                            meta.uuid,
+                            issue.key.clone(),
+                            req.status,
+                            Some(issue.status.clone()),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Take over requirement statuses from Jira
+    ///
+    /// Only statuses listed in [`JiraConfig::statuses`] are taken over.
+    /// Requirements whose status changed locally while their issue's status
+    /// changed in Jira are left alone and reported as conflicts. A dry run
+    /// changes neither `requirements` nor the sync state.
+    pub fn pull(
+        &mut self,
+        requirements: &mut RequirementConfig,
+        store: &MetadataStore,
+        dry_run: bool,
+    ) -> Result<JiraReport> {
+        let issues = self.issues()?;
+        let mut report = JiraReport {
+            dry_run,
+            ..JiraReport::default()
+        };
+
+        sync::for_each_with_metadata(requirements, store, |req, meta| {
+            let Some(issue) = issues.get(&meta.uuid) else {
+                return Ok(());
+            };
+            let Some(&remote) = self.config.statuses.get(&issue.status) else {
+                return Ok(());
+            };
+
+            let synced = self.state.issues.get(&meta.uuid);
+            let local_changed = synced.is_some_and(|synced| synced.status != req.status);
+            let remote_changed = match synced {
+                Some(synced) => synced.jira_status.as_deref() != Some(issue.status.as_str()),
+                None => true,
+            };
+
+            match sync::reconcile(req.status, Some(remote), local_changed, remote_changed) {
+                Pull::Conflict => {
+                    report.conflicts.push(JiraConflict {
+                        summary: req.summary.clone(),
+                        key: issue.key.clone(),
+                        local: req.status,
+                        remote: issue.status.clone(),
+                    });
+                    return Ok(());
+                }
+                Pull::Take(remote) => {
+                    report.status_changes.push(StatusChange {
+                        summary: req.summary.clone(),
+                        key: issue.key.clone(),
+                        from: req.status,
+                        to: remote,
+                    });
+                    if !dry_run {
+                        req.status = Some(remote);
+                    }
+                }
+                Pull::Keep => {}
+            }
+            if !dry_run {
+                self.remember(
+                    meta.uuid,
+                    issue.key.clone(),
+                    req.status,
+                    Some(issue.status.clone()),
+                );
+            }
+            Ok(())
+        })?;
+
+        report.status_changes.sort_by(|a, b| a.key.cmp(&b.key));
+        report.conflicts.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(report)
+    }
+
+    /// [`JiraSync::pull`] into a requirements file, writing the new statuses
+    /// back with [`write_statuses`](crate::metadata::write_statuses) so the
+    /// rest of the file stays as it is
+    pub fn pull_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        store: &MetadataStore,
+        dry_run: bool,
+    ) -> Result<JiraReport> {
+        sync::pull_file(
+            path.as_ref(),
+            dry_run,
+            |requirements| self.pull(requirements, store, dry_run),
+            |report| {
+                report
+                    .status_changes
+                    .iter()
+                    .map(|change| (change.summary.clone(), change.to))
+                    .collect()
+            },
+        )
+    }
+
+    fn remember(
+        &mut self,
+        uuid: Uuid,
+        key: String,
+        status: Option<Status>,
+        jira_status: Option<String>,
+    ) {
+        self.state.issues.insert(
+            uuid,
+            SyncedIssue {
+                key,
+                status,
+                jira_status,
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use std::cell::RefCell;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const FIELD: &str = "customfield_10100";
+
+    /// An in-memory Jira project
+    #[derive(Default)]
+    struct FakeJira {
+        issues: RefCell<Vec<Value>>,
+        writes: RefCell<usize>,
+    }
+
+    impl FakeJira {
+        fn set_status(&self, key: &str, status: &str) {
+            for issue in self.issues.borrow_mut().iter_mut() {
+                if issue["key"] == key {
+                    issue["fields"]["status"]["name"] = json!(status);
+                }
+            }
+        }
+    }
+
+    impl JiraTransport for &FakeJira {
+        fn send(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+            let body = body.cloned().unwrap_or(Value::Null);
+            match (method, path) {
+                ("POST", "search") => {
+                    let issues = self.issues.borrow();
+                    let start = body["startAt"].as_u64().unwrap() as usize;
+                    // Small pages to exercise paging
+                    let page: Vec<Value> = issues.iter().skip(start).take(1).cloned().collect();
+                    Ok(json!({ "issues": page, "total": issues.len() }))
+                }
+                ("POST", "issue") => {
+                    *self.writes.borrow_mut() += 1;
+                    let mut issues = self.issues.borrow_mut();
+                    let key = format!("PAY-{}", issues.len() + 1);
+                    let mut fields = body["fields"].clone();
+                    fields["status"] = json!({ "name": "To Do" });
+                    issues.push(json!({ "key": key, "fields": fields }));
+                    Ok(json!({ "key": key }))
+                }
+                ("PUT", path) => {
+                    *self.writes.borrow_mut() += 1;
+                    let key = path.trim_start_matches("issue/");
+                    for issue in self.issues.borrow_mut().iter_mut() {
+                        if issue["key"] == key {
+                            for (name, value) in body["fields"].as_object().unwrap() {
+                                issue["fields"][name] = value.clone();
+                            }
+                        }
+                    }
+                    Ok(Value::Null)
+                }
+                _ => Err(Error::custom(format!("unexpected {} {}", method, path))),
+            }
+        }
+    }
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Refunds
+    status: approved
+    description: Customers can get refunds
+    requirements:
+      - summary: Partial refunds
+        status: draft
+"#;
+
+    #[test]
+    fn test_push_creates_then_updates() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let jira = FakeJira::default();
+        let mut sync = JiraSync::new(&jira, JiraConfig::new("PAY", FIELD));
+        let mut config = Parser::parse_str(YAML).unwrap();
+
+        let dry = sync.push(&config, &mut store, true).unwrap();
+        assert_eq!(dry.created, vec!["Refunds", "Partial refunds"]);
+        assert_eq!(*jira.writes.borrow(), 0);
+        // Nor is any ID assigned
+        assert!(store.all_metadata().unwrap().is_empty());
+
+        let pushed = sync.push(&config, &mut store, false).unwrap();
+        assert_eq!(pushed.created.len(), 2);
+        assert_eq!(jira.issues.borrow().len(), 2);
+        let uuid = store.find_metadata("Refunds").unwrap().unwrap().uuid;
+        assert_eq!(
+            jira.issues.borrow()[0]["fields"][FIELD],
+            json!(uuid.to_string())
+        );
+
+        config.requirements[0].description = Some("Refunds within 30 days".to_string());
+        let updated = sync.push(&config, &mut store, false).unwrap();
+        assert!(updated.created.is_empty());
+        assert_eq!(updated.updated, vec!["PAY-1"]);
+        assert_eq!(
+            jira.issues.borrow()[0]["fields"]["description"],
+            json!("Refunds within 30 days")
+        );
+        assert_eq!(sync.state().issues[&uuid].key, "PAY-1");
+    }
+
+    #[test]
+    fn test_pull_takes_statuses_and_reports_conflicts() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "REQ".to_string()).unwrap();
+        let jira = FakeJira::default();
+        let mut sync = JiraSync::new(&jira, JiraConfig::new("PAY", FIELD));
+        let mut config = Parser::parse_str(YAML).unwrap();
+        sync.push(&config, &mut store, false).unwrap();
+        sync.pull(&mut config, &store, false).unwrap();
+        sync.state().save(&rqm_dir).unwrap();
+
+        // Jira finishes both; locally the second one is reworked meanwhile
+        jira.set_status("PAY-1", "Done");
+        jira.set_status("PAY-2", "Done");
+        let partial = &mut config.requirements[0].requirements[0];
+        if let crate::types::RequirementReference::Full(req) = partial {
+            req.status = Some(Status::Proposed);
+        }
+
+        let mut sync = JiraSync::new(&jira, JiraConfig::new("PAY", FIELD))
+            .with_state(JiraState::load(&rqm_dir).unwrap());
+        let dry = sync.pull(&mut config, &store, true).unwrap();
+        assert_eq!(dry.status_changes.len(), 1);
+        assert_eq!(config.requirements[0].status, Some(Status::Approved));
+
+        let report = sync.pull(&mut config, &store, false).unwrap();
+        assert_eq!(
+            report.status_changes,
+            vec![StatusChange {
+                summary: "Refunds".to_string(),
+                key: "PAY-1".to_string(),
+                from: Some(Status::Approved),
+                to: Status::Implemented,
+            }]
+        );
+        assert_eq!(config.requirements[0].status, Some(Status::Implemented));
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].key, "PAY-2");
+        assert_eq!(report.conflicts[0].local, Some(Status::Proposed));
+    }
+
+    #[test]
+    fn test_pull_file_writes_statuses_back() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let path = temp.path().join("requirements.yml");
+        fs::write(&path, format!("# Payments\n{}", YAML.trim_start())).unwrap();
+        let mut store = MetadataStore::init(&rqm_dir, "REQ".to_string()).unwrap();
+        let jira = FakeJira::default();
+        let mut sync = JiraSync::new(&jira, JiraConfig::new("PAY", FIELD));
+        sync.push(&Parser::parse_file(&path).unwrap(), &mut store, false)
+            .unwrap();
+
+        jira.set_status("PAY-1", "Done");
+        let report = sync.pull_file(&path, &store, false).unwrap();
+        assert_eq!(report.status_changes.len(), 1);
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Payments\n"));
+        assert!(written.contains("  - summary: Refunds\n    status: implemented\n"));
+    }
+
+    #[test]
+    fn test_jql_is_quoted() {
+        assert_eq!(
+            jql_string(r#"PAY" OR project = "X\"#),
+            r#""PAY\" OR project = \"X\\""#
+        );
+        let jira = FakeJira::default();
+        let sync = JiraSync::new(&jira, JiraConfig::new("PAY", "summary"));
+        assert!(sync.issues().is_err());
+    }
+}
@@ -0,0 +1,620 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! OSLC Requirements Management 2.0 provider
+//!
+//! Renders the RDF/XML documents an OSLC RM consumer expects: a service
+//! provider catalog, a service provider with a query capability, the
+//! resource shape of requirements, single requirements and query results.
+//! The module does no networking itself; [`OslcProvider::handle`] maps a
+//! request path and query string to a response for whatever HTTP server
+//! hosts it.
+//!
+//! Requirements are addressed by UUID where metadata exists, so links from
+//! other tools survive renames, and by the kebab-case summary otherwise.
+//! Children are linked with `oslc_rm:decomposedBy`, parents with
+//! `oslc_rm:decomposes`.
+
+use crate::error::Result;
+use crate::metadata::{kebab_case, MetadataStore};
+use crate::percent::decode_query;
+use crate::report::escape;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Media type of all documents
+pub const RDF_XML: &str = "application/rdf+xml";
+
+/// Page size when paging without `oslc.pageSize`
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Largest page size served, whatever `oslc.pageSize` asks for
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+const NAMESPACES: &str = concat!(
+    " xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"",
+    " xmlns:dcterms=\"http://purl.org/dc/terms/\"",
+    " xmlns:oslc=\"http://open-services.net/ns/core#\"",
+    " xmlns:oslc_rm=\"http://open-services.net/ns/rm#\"",
+    " xmlns:rdfs=\"http://www.w3.org/2000/01/rdf-schema#\"",
+    " xmlns:rqm=\"https://rqm.dev/ns#\""
+);
+
+/// Properties of requirements, as (prefix:name, value type, occurs, title)
+const PROPERTIES: &[(&str, &str, &str, &str)] = &[
+    ("dcterms:identifier", "String", "Exactly-one", "Identifier"),
+    ("dcterms:title", "XMLLiteral", "Exactly-one", "Title"),
+    (
+        "dcterms:description",
+        "XMLLiteral",
+        "Zero-or-one",
+        "Description",
+    ),
+    ("dcterms:subject", "String", "Zero-or-many", "Tags"),
+    ("dcterms:contributor", "String", "Zero-or-one", "Owner"),
+    ("rqm:status", "String", "Zero-or-one", "Status"),
+    ("rqm:priority", "String", "Zero-or-one", "Priority"),
+    (
+        "oslc_rm:decomposedBy",
+        "Resource",
+        "Zero-or-many",
+        "Children",
+    ),
+    ("oslc_rm:decomposes", "Resource", "Zero-or-many", "Parents"),
+];
+
+/// A document to send back
+#[derive(Debug, Clone, PartialEq)]
+pub struct OslcResponse {
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+/// Serves requirements as OSLC RM resources below `base_url`
+pub struct OslcProvider<'a> {
+    config: &'a RequirementConfig,
+    store: Option<&'a MetadataStore>,
+    base_url: String,
+    title: String,
+}
+
+/// A requirement with its resource path segment and identifier
+struct Resource<'a> {
+    req: &'a Requirement,
+    segment: String,
+    identifier: String,
+}
+
+/// Every requirement as a resource, with the links between them
+struct Resources<'a> {
+    list: Vec<Resource<'a>>,
+
+    /// Index in `list` of each summary
+    index: HashMap<&'a str, usize>,
+
+    /// Summaries of the requirements listing each summary as a child
+    parents: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> Resources<'a> {
+    fn get(&self, summary: &str) -> Option<&Resource<'a>> {
+        self.index.get(summary).map(|&i| &self.list[i])
+    }
+}
+
+impl<'a> OslcProvider<'a> {
+    /// Serve `config` at `base_url`, e.g. `https://rqm.example.com/oslc`
+    pub fn new(
+        config: &'a RequirementConfig,
+        store: Option<&'a MetadataStore>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            config,
+            store,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            title: "Requirements".to_string(),
+        }
+    }
+
+    /// Title of the service provider
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Answer a GET of `path` below the base URL, with the raw query string
+    ///
+    /// Returns `None` for paths that are not OSLC resources.
+    pub fn handle(&self, path: &str, query: &str) -> Result<Option<OslcResponse>> {
+        let body = match path.trim_matches('/') {
+            "catalog" => self.catalog(),
+            "provider" => self.service_provider(),
+            "shapes/requirement" => self.resource_shape(),
+            "requirements" => self.query(query)?,
+            path => match path.strip_prefix("requirements/") {
+                Some(id) => match self.requirement(id)? {
+                    Some(body) => body,
+                    None => return Ok(None),
+                },
+                None => return Ok(None),
+            },
+        };
+        Ok(Some(OslcResponse {
+            content_type: RDF_XML,
+            body,
+        }))
+    }
+
+    /// The service provider catalog, listing the one service provider
+    pub fn catalog(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF{ns}>\n<oslc:ServiceProviderCatalog rdf:about=\"{base}/catalog\">\n<dcterms:title>{title} catalog</dcterms:title>\n<oslc:domain rdf:resource=\"http://open-services.net/ns/rm#\"/>\n<oslc:serviceProvider rdf:resource=\"{base}/provider\"/>\n</oslc:ServiceProviderCatalog>\n</rdf:RDF>\n",
+            ns = NAMESPACES,
+            base = escape(&self.base_url),
+            title = escape(&self.title),
+        )
+    }
+
+    /// The service provider with its requirements query capability
+    pub fn service_provider(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF{ns}>\n<oslc:ServiceProvider rdf:about=\"{base}/provider\">\n<dcterms:title>{title}</dcterms:title>\n<oslc:service>\n<oslc:Service>\n<oslc:domain rdf:resource=\"http://open-services.net/ns/rm#\"/>\n<oslc:queryCapability>\n<oslc:QueryCapability>\n<dcterms:title>Requirements</dcterms:title>\n<oslc:queryBase rdf:resource=\"{base}/requirements\"/>\n<oslc:resourceShape rdf:resource=\"{base}/shapes/requirement\"/>\n<oslc:resourceType rdf:resource=\"http://open-services.net/ns/rm#Requirement\"/>\n</oslc:QueryCapability>\n</oslc:queryCapability>\n</oslc:Service>\n</oslc:service>\n</oslc:ServiceProvider>\n</rdf:RDF>\n",
+            ns = NAMESPACES,
+            base = escape(&self.base_url),
+            title = escape(&self.title),
+        )
+    }
+
+    /// The resource shape describing requirement properties
+    pub fn resource_shape(&self) -> String {
+        let base = escape(&self.base_url);
+        let mut out = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF{}>\n<oslc:ResourceShape rdf:about=\"{}/shapes/requirement\">\n<dcterms:title>Requirement</dcterms:title>\n<oslc:describes rdf:resource=\"http://open-services.net/ns/rm#Requirement\"/>\n",
+            NAMESPACES, base
+        );
+        for (name, value_type, occurs, title) in PROPERTIES {
+            let (prefix, local) = name.split_once(':').unwrap_or_default();
+            let namespace = match prefix {
+                "dcterms" => "http://purl.org/dc/terms/",
+                "oslc_rm" => "http://open-services.net/ns/rm#",
+                _ => "https://rqm.dev/ns#",
+            };
+            let value_type = match *value_type {
+                "Resource" => "http://open-services.net/ns/core#Resource",
+                "XMLLiteral" => "http://www.w3.org/1999/02/22-rdf-syntax-ns#XMLLiteral",
+                _ => "http://www.w3.org/2001/XMLSchema#string",
+            };
+            let _ = write!(
+                out,
+                "<oslc:property>\n<oslc:Property>\n<oslc:name>{local}</oslc:name>\n<dcterms:title>{title}</dcterms:title>\n<oslc:propertyDefinition rdf:resource=\"{namespace}{local}\"/>\n<oslc:valueType rdf:resource=\"{value_type}\"/>\n<oslc:occurs rdf:resource=\"http://open-services.net/ns/core#{occurs}\"/>\n</oslc:Property>\n</oslc:property>\n"
+            );
+        }
+        out.push_str("</oslc:ResourceShape>\n</rdf:RDF>\n");
+        out
+    }
+
+    /// A single requirement, by UUID or kebab-case summary
+    pub fn requirement(&self, id: &str) -> Result<Option<String>> {
+        let resources = self.resources()?;
+        let Some(resource) = resources.list.iter().find(|r| r.segment == id) else {
+            return Ok(None);
+        };
+        Ok(Some(format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF{}>\n{}</rdf:RDF>\n",
+            NAMESPACES,
+            self.describe(resource, &resources)
+        )))
+    }
+
+    /// Requirements matching an OSLC query string
+    ///
+    /// Supports `oslc.where` with `=` and `!=` comparisons of
+    /// `dcterms:identifier`, `dcterms:title`, `dcterms:subject`,
+    /// `dcterms:contributor`, `rqm:status` and `rqm:priority` joined by
+    /// `and`, `oslc.searchTerms` matched against titles and descriptions,
+    /// and paging with `oslc.paging`, `oslc.pageSize` and `page`. Pages
+    /// hold at most [`MAX_PAGE_SIZE`] requirements.
+    pub fn query(&self, query: &str) -> Result<String> {
+        let params: HashMap<String, String> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode_query(name), decode_query(value))
+            })
+            .collect();
+        let clauses = params
+            .get("oslc.where")
+            .map(|w| parse_where(w))
+            .unwrap_or_default();
+        let terms: Vec<String> = params
+            .get("oslc.searchTerms")
+            .map(|terms| {
+                terms
+                    .split(',')
+                    .map(|term| term.trim().trim_matches('"').to_lowercase())
+                    .filter(|term| !term.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let resources = self.resources()?;
+        let matches: Vec<&Resource> = resources
+            .list
+            .iter()
+            .filter(|resource| {
+                clauses.iter().all(|(property, equal, value)| {
+                    self.values(resource, property).contains(value) == *equal
+                })
+            })
+            .filter(|resource| {
+                let text = format!(
+                    "{} {}",
+                    resource.req.summary,
+                    resource.req.description.as_deref().unwrap_or_default()
+                )
+                .to_lowercase();
+                terms.iter().all(|term| text.contains(term.as_str()))
+            })
+            .collect();
+
+        let paging = params.get("oslc.paging").map(String::as_str) == Some("true")
+            || params.contains_key("oslc.pageSize");
+        let page_size = params
+            .get("oslc.pageSize")
+            .and_then(|size| size.parse().ok())
+            .filter(|&size: &usize| size > 0)
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .min(MAX_PAGE_SIZE);
+        let page = params
+            .get("page")
+            .and_then(|page| page.parse().ok())
+            .filter(|&page: &usize| page > 0)
+            .unwrap_or(1);
+        let (shown, next) = if paging {
+            let start = (page - 1).saturating_mul(page_size);
+            let shown: Vec<&Resource> = matches
+                .iter()
+                .skip(start)
+                .take(page_size)
+                .copied()
+                .collect();
+            let next = page
+                .checked_add(1)
+                .filter(|_| start.saturating_add(page_size) < matches.len());
+            (shown, next)
+        } else {
+            (matches.clone(), None)
+        };
+
+        let base = escape(&self.base_url);
+        let this = if query.is_empty() {
+            format!("{}/requirements", base)
+        } else {
+            format!("{}/requirements?{}", base, escape(query))
+        };
+        let mut out = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF{}>\n<rdf:Description rdf:about=\"{}/requirements\">\n",
+            NAMESPACES, base
+        );
+        for resource in &shown {
+            let _ = writeln!(
+                out,
+                "<rdfs:member rdf:resource=\"{}\"/>",
+                self.uri(resource)
+            );
+        }
+        out.push_str("</rdf:Description>\n");
+        let _ = write!(
+            out,
+            "<oslc:ResponseInfo rdf:about=\"{}\">\n<oslc:totalCount>{}</oslc:totalCount>\n",
+            this,
+            matches.len()
+        );
+        if let Some(next) = next {
+            let mut next_params: Vec<String> = query
+                .split('&')
+                .filter(|pair| !pair.is_empty() && !pair.starts_with("page="))
+                .map(String::from)
+                .collect();
+            next_params.push(format!("page={}", next));
+            let _ = writeln!(
+                out,
+                "<oslc:nextPage rdf:resource=\"{}/requirements?{}\"/>",
+                base,
+                escape(&next_params.join("&"))
+            );
+        }
+        out.push_str("</oslc:ResponseInfo>\n");
+        for resource in &shown {
+            out.push_str(&self.describe(resource, &resources));
+        }
+        out.push_str("</rdf:RDF>\n");
+        Ok(out)
+    }
+
+    /// Every requirement defined in place, in document order
+    fn resources(&self) -> Result<Resources<'a>> {
+        let mut list = vec![];
+        let mut index = HashMap::new();
+        let mut seen = HashSet::new();
+        for req in self.config.all_requirements() {
+            if !seen.insert(req.summary.as_str()) {
+                continue;
+            }
+            let meta = match self.store {
+                Some(store) => store.find_metadata(&req.summary)?,
+                None => None,
+            };
+            let segment = match &meta {
+                Some(meta) => meta.uuid.to_string(),
+                None => kebab_case(&req.summary),
+            };
+            let identifier = meta
+                .map(|meta| meta.generated_id)
+                .or_else(|| req.name.clone())
+                .unwrap_or_else(|| segment.clone());
+            index.insert(req.summary.as_str(), list.len());
+            list.push(Resource {
+                req,
+                segment,
+                identifier,
+            });
+        }
+
+        let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+        for resource in &list {
+            for child in &resource.req.requirements {
+                parents
+                    .entry(child_summary(child))
+                    .or_default()
+                    .push(&resource.req.summary);
+            }
+        }
+        Ok(Resources {
+            list,
+            index,
+            parents,
+        })
+    }
+
+    fn uri(&self, resource: &Resource) -> String {
+        format!(
+            "{}/requirements/{}",
+            escape(&self.base_url),
+            escape(&resource.segment)
+        )
+    }
+
+    /// Values of a queryable property, as strings
+    fn values(&self, resource: &Resource, property: &str) -> Vec<String> {
+        let req = resource.req;
+        match property {
+            "dcterms:identifier" => vec![resource.identifier.clone()],
+            "dcterms:title" => vec![req.summary.clone()],
+            "dcterms:subject" => req.tags.clone(),
+            "dcterms:contributor" => req.owner.iter().map(|o| o.as_str().to_string()).collect(),
+            "rqm:status" => req.status.iter().map(|s| s.to_string()).collect(),
+            "rqm:priority" => req.priority.iter().map(|p| p.to_string()).collect(),
+            _ => vec![],
+        }
+    }
+
+    fn describe(&self, resource: &Resource, resources: &Resources) -> String {
+        let req = resource.req;
+        let mut out = format!(
+            "<oslc_rm:Requirement rdf:about=\"{}\">\n<dcterms:identifier>{}</dcterms:identifier>\n<dcterms:title rdf:parseType=\"Literal\">{}</dcterms:title>\n",
+            self.uri(resource),
+            escape(&resource.identifier),
+            escape(&req.summary)
+        );
+        if let Some(description) = &req.description {
+            let _ = writeln!(
+                out,
+                "<dcterms:description rdf:parseType=\"Literal\">{}</dcterms:description>",
+                escape(description.trim())
+            );
+        }
+        for property in [
+            "dcterms:subject",
+            "dcterms:contributor",
+            "rqm:status",
+            "rqm:priority",
+        ] {
+            for value in self.values(resource, property) {
+                let _ = writeln!(out, "<{0}>{1}</{0}>", property, escape(&value));
+            }
+        }
+        let link = |out: &mut String, property: &str, summary: &str| {
+            if let Some(target) = resources.get(summary) {
+                let _ = writeln!(out, "<{} rdf:resource=\"{}\"/>", property, self.uri(target));
+            }
+        };
+        for child in &req.requirements {
+            link(&mut out, "oslc_rm:decomposedBy", child_summary(child));
+        }
+        for parent in resources
+            .parents
+            .get(req.summary.as_str())
+            .into_iter()
+            .flatten()
+        {
+            link(&mut out, "oslc_rm:decomposes", parent);
+        }
+        out.push_str("</oslc_rm:Requirement>\n");
+        out
+    }
+}
+
+fn child_summary(child: &RequirementReference) -> &str {
+    match child {
+        RequirementReference::Full(child) => &child.summary,
+        RequirementReference::Reference(summary) => summary,
+    }
+}
+
+/// Parse `prop="value" and prop!="value"` into (property, equal, value)
+fn parse_where(clause: &str) -> Vec<(String, bool, String)> {
+    let mut clauses = vec![];
+    let mut rest = clause.trim();
+    while !rest.is_empty() {
+        let Some(op) = rest.find('=') else {
+            break;
+        };
+        let equal = !rest[..op].ends_with('!');
+        let property = rest[..op].trim_end_matches('!').trim().to_string();
+        let value_part = rest[op + 1..].trim_start();
+        let (value, after) = match value_part.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => {
+                let end = value_part.find(' ').unwrap_or(value_part.len());
+                (&value_part[..end], &value_part[end..])
+            }
+        };
+        clauses.push((property, equal, value.to_string()));
+        rest = after.trim_start();
+        rest = rest.strip_prefix("and").unwrap_or(rest).trim_start();
+    }
+    clauses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    owner: "@alice"
+    description: Users prove <who> they are
+    requirements:
+      - summary: Login
+        tags: [web]
+        status: draft
+      - Audit log
+  - summary: Audit log
+    name: AUD
+    tags: [web, security]
+"#;
+
+    fn provider(config: &RequirementConfig) -> OslcProvider<'_> {
+        OslcProvider::new(config, None, "https://rqm.example.com/oslc/")
+    }
+
+    #[test]
+    fn test_discovery_documents() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let provider = provider(&config).with_title("Payments");
+
+        let catalog = provider.handle("/catalog", "").unwrap().unwrap();
+        assert_eq!(catalog.content_type, RDF_XML);
+        assert!(catalog.body.contains(
+            "<oslc:serviceProvider rdf:resource=\"https://rqm.example.com/oslc/provider\"/>"
+        ));
+
+        let service = provider.service_provider();
+        assert!(service.contains("<dcterms:title>Payments</dcterms:title>"));
+        assert!(service.contains(
+            "<oslc:queryBase rdf:resource=\"https://rqm.example.com/oslc/requirements\"/>"
+        ));
+
+        let shape = provider.resource_shape();
+        assert!(shape.contains("<oslc:name>decomposedBy</oslc:name>"));
+        assert!(shape.contains(
+            "<oslc:valueType rdf:resource=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#XMLLiteral\"/>"
+        ));
+        assert!(provider.handle("/elsewhere", "").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_requirement_resource_links_by_uuid() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        let auth = store
+            .get_or_create_metadata(&config.requirements[0])
+            .unwrap();
+        let provider = OslcProvider::new(&config, Some(&store), "http://host/oslc");
+
+        let body = provider
+            .requirement(&auth.uuid.to_string())
+            .unwrap()
+            .unwrap();
+        let about = format!("rdf:about=\"http://host/oslc/requirements/{}\"", auth.uuid);
+        assert!(body.contains(&about));
+        assert!(body.contains("<dcterms:identifier>REQ-001</dcterms:identifier>"));
+        assert!(body.contains("Users prove &lt;who&gt; they are"));
+        assert!(body.contains("<rqm:status>approved</rqm:status>"));
+        assert!(body.contains(
+            "<oslc_rm:decomposedBy rdf:resource=\"http://host/oslc/requirements/login\"/>"
+        ));
+        assert!(body.contains(
+            "<oslc_rm:decomposedBy rdf:resource=\"http://host/oslc/requirements/audit-log\"/>"
+        ));
+
+        let audit = provider.requirement("audit-log").unwrap().unwrap();
+        assert!(audit.contains("<dcterms:identifier>AUD</dcterms:identifier>"));
+        assert!(audit.contains(&format!(
+            "<oslc_rm:decomposes rdf:resource=\"http://host/oslc/requirements/{}\"/>",
+            auth.uuid
+        )));
+        assert!(provider.requirement("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_query_where_search_and_paging() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let provider = provider(&config);
+        let count = |body: &str| body.matches("<oslc_rm:Requirement ").count();
+
+        assert_eq!(count(&provider.query("").unwrap()), 3);
+
+        let web = provider
+            .query("oslc.where=dcterms%3Asubject%3D%22web%22%20and%20rqm:status!=%22draft%22")
+            .unwrap();
+        assert_eq!(count(&web), 1);
+        assert!(web.contains("<oslc:totalCount>1</oslc:totalCount>"));
+        assert!(web.contains("<dcterms:identifier>AUD</dcterms:identifier>"));
+
+        let search = provider.query("oslc.searchTerms=%22prove%22").unwrap();
+        assert_eq!(count(&search), 1);
+
+        let page = provider.query("oslc.paging=true&oslc.pageSize=2").unwrap();
+        assert_eq!(count(&page), 2);
+        assert!(page.contains("<oslc:totalCount>3</oslc:totalCount>"));
+        assert!(page.contains("oslc.pageSize=2&amp;page=2\"/>"));
+        let last = provider
+            .query("oslc.paging=true&oslc.pageSize=2&page=2")
+            .unwrap();
+        assert_eq!(count(&last), 1);
+        assert!(!last.contains("oslc:nextPage"));
+
+        // Out-of-range pages are empty rather than overflowing
+        let far = format!("oslc.pageSize={}&page={}", usize::MAX, usize::MAX);
+        let far = provider.query(&far).unwrap();
+        assert_eq!(count(&far), 0);
+        assert!(far.contains("<oslc:totalCount>3</oslc:totalCount>"));
+        assert!(!far.contains("oslc:nextPage"));
+    }
+
+    #[test]
+    fn test_parse_where() {
+        assert_eq!(
+            parse_where("dcterms:title=\"A and B\" and rqm:priority!=high"),
+            vec![
+                ("dcterms:title".to_string(), true, "A and B".to_string()),
+                ("rqm:priority".to_string(), false, "high".to_string()),
+            ]
+        );
+    }
+}
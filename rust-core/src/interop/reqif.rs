@@ -0,0 +1,573 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! OMG ReqIF 1.2 import and export
+//!
+//! Requirements become `SPEC-OBJECT`s with string attributes, the nesting
+//! of requirements becomes the `SPEC-HIERARCHY` of one `SPECIFICATION`, and
+//! references to requirements defined elsewhere become `SPEC-RELATION`s.
+//!
+//! On import, attributes are matched by their `LONG-NAME`, so files from
+//! DOORS, Polarion or Jama work as long as they use the standard
+//! `ReqIF.Name`, `ReqIF.Text` and `ReqIF.ForeignID` names. String, XHTML,
+//! number and enumeration values are read; XHTML is reduced to its text.
+
+use crate::error::{Error, Result};
+use crate::metadata::{kebab_case, MetadataStore};
+use crate::report::escape;
+use crate::types::{OwnerReference, Requirement, RequirementConfig, RequirementReference};
+use crate::xml::{parse_xml, Element};
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+const NAME: &str = "ReqIF.Name";
+const TEXT: &str = "ReqIF.Text";
+const FOREIGN_ID: &str = "ReqIF.ForeignID";
+const CHAPTER_NAME: &str = "ReqIF.ChapterName";
+
+/// Attribute definitions written on export, as identifier and long name
+const ATTRIBUTES: &[(&str, &str)] = &[
+    ("rqm-name", NAME),
+    ("rqm-text", TEXT),
+    ("rqm-foreign-id", FOREIGN_ID),
+    ("rqm-status", "Status"),
+    ("rqm-priority", "Priority"),
+    ("rqm-owner", "Owner"),
+    ("rqm-tags", "Tags"),
+    ("rqm-justification", "Justification"),
+    ("rqm-acceptance-test", "Acceptance test"),
+    ("rqm-target-release", "Target release"),
+];
+
+/// Attribute values of a requirement, in the order of [`ATTRIBUTES`]
+fn attribute_values(req: &Requirement, id: Option<String>) -> [Option<String>; 10] {
+    [
+        Some(req.summary.clone()),
+        req.description.clone(),
+        id.or_else(|| req.name.clone()),
+        req.status.map(|s| s.to_string()),
+        req.priority.map(|p| p.to_string()),
+        req.owner.as_ref().map(|owner| owner.as_str().to_string()),
+        Some(req.tags.join(", ")).filter(|tags| !tags.is_empty()),
+        req.justification.clone(),
+        req.acceptance_test.clone(),
+        req.target_release.clone(),
+    ]
+}
+
+/// Render requirements as a ReqIF document
+///
+/// Spec objects are identified by the requirement's UUID where `store` has
+/// metadata, so repeated exports update the same objects in the receiving
+/// tool. No metadata is created.
+pub fn to_reqif(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    title: &str,
+) -> Result<String> {
+    let now = Utc::now().to_rfc3339();
+    let mut identifiers = HashMap::new();
+    let mut objects = String::new();
+    for req in config.all_requirements() {
+        let meta = match store {
+            Some(store) => store.find_metadata(&req.summary)?,
+            None => None,
+        };
+        let identifier = match &meta {
+            Some(meta) => format!("_{}", meta.uuid),
+            None => format!("rqm-{}", kebab_case(&req.summary)),
+        };
+        if identifiers
+            .insert(req.summary.clone(), identifier.clone())
+            .is_some()
+        {
+            continue;
+        }
+
+        let last_change = meta
+            .as_ref()
+            .map_or(now.clone(), |m| m.updated_at.to_rfc3339());
+        let _ = writeln!(
+            objects,
+            "        <SPEC-OBJECT IDENTIFIER=\"{}\" LAST-CHANGE=\"{}\">\n          <VALUES>",
+            escape(&identifier),
+            last_change
+        );
+        let values = attribute_values(req, meta.map(|m| m.generated_id));
+        for ((definition, _), value) in ATTRIBUTES.iter().zip(values) {
+            if let Some(value) = value {
+                let _ = writeln!(
+                    objects,
+                    "            <ATTRIBUTE-VALUE-STRING THE-VALUE=\"{}\"><DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>{}</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION></ATTRIBUTE-VALUE-STRING>",
+                    xml_attr(&value),
+                    definition
+                );
+            }
+        }
+        objects.push_str("          </VALUES>\n          <TYPE><SPEC-OBJECT-TYPE-REF>rqm-requirement</SPEC-OBJECT-TYPE-REF></TYPE>\n        </SPEC-OBJECT>\n");
+    }
+
+    let mut relations = String::new();
+    for req in config.all_requirements() {
+        for child in &req.requirements {
+            let RequirementReference::Reference(target) = child else {
+                continue;
+            };
+            let (Some(source), Some(target)) =
+                (identifiers.get(&req.summary), identifiers.get(target))
+            else {
+                continue;
+            };
+            let _ = writeln!(
+                relations,
+                "        <SPEC-RELATION IDENTIFIER=\"{0}-requires-{1}\" LAST-CHANGE=\"{2}\">\n          <TYPE><SPEC-RELATION-TYPE-REF>rqm-requires</SPEC-RELATION-TYPE-REF></TYPE>\n          <SOURCE><SPEC-OBJECT-REF>{0}</SPEC-OBJECT-REF></SOURCE>\n          <TARGET><SPEC-OBJECT-REF>{1}</SPEC-OBJECT-REF></TARGET>\n        </SPEC-RELATION>",
+                escape(source),
+                escape(target),
+                now
+            );
+        }
+    }
+
+    let mut hierarchy = String::new();
+    for req in &config.requirements {
+        write_hierarchy(&mut hierarchy, req, &identifiers, &now, 10);
+    }
+
+    let mut definitions = String::new();
+    for (identifier, long_name) in ATTRIBUTES {
+        let _ = writeln!(
+            definitions,
+            "            <ATTRIBUTE-DEFINITION-STRING IDENTIFIER=\"{}\" LONG-NAME=\"{}\" LAST-CHANGE=\"{}\"><TYPE><DATATYPE-DEFINITION-STRING-REF>rqm-string</DATATYPE-DEFINITION-STRING-REF></TYPE></ATTRIBUTE-DEFINITION-STRING>",
+            identifier, long_name, now
+        );
+    }
+
+    let title = escape(title);
+    Ok(format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<REQ-IF xmlns="http://www.omg.org/spec/ReqIF/20110401/reqif.xsd">
+  <THE-HEADER>
+    <REQ-IF-HEADER IDENTIFIER="rqm-header">
+      <CREATION-TIME>{now}</CREATION-TIME>
+      <REQ-IF-TOOL-ID>rqm</REQ-IF-TOOL-ID>
+      <REQ-IF-VERSION>1.0</REQ-IF-VERSION>
+      <SOURCE-TOOL-ID>rqm {version}</SOURCE-TOOL-ID>
+      <TITLE>{title}</TITLE>
+    </REQ-IF-HEADER>
+  </THE-HEADER>
+  <CORE-CONTENT>
+    <REQ-IF-CONTENT>
+      <DATATYPES>
+        <DATATYPE-DEFINITION-STRING IDENTIFIER="rqm-string" LONG-NAME="String" MAX-LENGTH="65535" LAST-CHANGE="{now}"/>
+      </DATATYPES>
+      <SPEC-TYPES>
+        <SPEC-OBJECT-TYPE IDENTIFIER="rqm-requirement" LONG-NAME="Requirement" LAST-CHANGE="{now}">
+          <SPEC-ATTRIBUTES>
+{definitions}          </SPEC-ATTRIBUTES>
+        </SPEC-OBJECT-TYPE>
+        <SPEC-RELATION-TYPE IDENTIFIER="rqm-requires" LONG-NAME="Requires" LAST-CHANGE="{now}"/>
+        <SPECIFICATION-TYPE IDENTIFIER="rqm-specification" LONG-NAME="Specification" LAST-CHANGE="{now}"/>
+      </SPEC-TYPES>
+      <SPEC-OBJECTS>
+{objects}      </SPEC-OBJECTS>
+      <SPEC-RELATIONS>
+{relations}      </SPEC-RELATIONS>
+      <SPECIFICATIONS>
+        <SPECIFICATION IDENTIFIER="rqm-specification-1" LONG-NAME="{title}" LAST-CHANGE="{now}">
+          <TYPE><SPECIFICATION-TYPE-REF>rqm-specification</SPECIFICATION-TYPE-REF></TYPE>
+          <CHILDREN>
+{hierarchy}          </CHILDREN>
+        </SPECIFICATION>
+      </SPECIFICATIONS>
+    </REQ-IF-CONTENT>
+  </CORE-CONTENT>
+</REQ-IF>
+"#,
+        version = crate::VERSION,
+    ))
+}
+
+fn write_hierarchy(
+    out: &mut String,
+    req: &Requirement,
+    identifiers: &HashMap<String, String>,
+    now: &str,
+    indent: usize,
+) {
+    let identifier = escape(&identifiers[&req.summary]);
+    let pad = " ".repeat(indent);
+    let _ = writeln!(
+        out,
+        "{pad}<SPEC-HIERARCHY IDENTIFIER=\"{identifier}-h\" LAST-CHANGE=\"{now}\">\n{pad}  <OBJECT><SPEC-OBJECT-REF>{identifier}</SPEC-OBJECT-REF></OBJECT>"
+    );
+    let children: Vec<&Requirement> = req
+        .requirements
+        .iter()
+        .filter_map(|child| match child {
+            RequirementReference::Full(child) => Some(child.as_ref()),
+            RequirementReference::Reference(_) => None,
+        })
+        .collect();
+    if !children.is_empty() {
+        let _ = writeln!(out, "{pad}  <CHILDREN>");
+        for child in children {
+            write_hierarchy(out, child, identifiers, now, indent + 4);
+        }
+        let _ = writeln!(out, "{pad}  </CHILDREN>");
+    }
+    let _ = writeln!(out, "{pad}</SPEC-HIERARCHY>");
+}
+
+/// Escape a value for an XML attribute, keeping line breaks
+fn xml_attr(value: &str) -> String {
+    escape(value).replace('\n', "&#10;")
+}
+
+/// Text of the first `*-REF` child, as used for references
+fn reference(element: &Element) -> Option<String> {
+    element
+        .elements()
+        .next()
+        .map(|child| child.text_content().trim().to_string())
+}
+
+/// Read requirements from a ReqIF document
+///
+/// The hierarchies of all specifications are merged; objects placed more
+/// than once are defined at their first place and referenced elsewhere.
+/// Objects outside any hierarchy become top-level requirements.
+pub fn from_reqif(xml: &str) -> Result<RequirementConfig> {
+    let root = parse_xml(xml)?
+        .children
+        .into_iter()
+        .find(|e| e.name == "REQ-IF")
+        .ok_or_else(|| Error::custom("Invalid ReqIF: no REQ-IF element"))?;
+    let content = root
+        .path(&["CORE-CONTENT", "REQ-IF-CONTENT"])
+        .ok_or_else(|| Error::custom("Invalid ReqIF: no REQ-IF-CONTENT"))?;
+
+    let mut long_names = HashMap::new();
+    content.visit(&mut |element| {
+        let is_definition =
+            element.name.starts_with("ATTRIBUTE-DEFINITION-") && !element.name.ends_with("-REF");
+        if is_definition || element.name == "ENUM-VALUE" {
+            if let (Some(id), Some(name)) = (element.attr("IDENTIFIER"), element.attr("LONG-NAME"))
+            {
+                long_names.insert(id.to_string(), name.to_string());
+            }
+        }
+    });
+
+    let mut objects: HashMap<String, Requirement> = HashMap::new();
+    let mut order = vec![];
+    for object in content
+        .child("SPEC-OBJECTS")
+        .into_iter()
+        .flat_map(|objects| objects.children_named("SPEC-OBJECT"))
+    {
+        let Some(identifier) = object.attr("IDENTIFIER") else {
+            continue;
+        };
+        let values = object_values(object, &long_names);
+        objects.insert(identifier.to_string(), requirement(identifier, &values));
+        order.push(identifier.to_string());
+    }
+
+    let mut relations: HashMap<String, Vec<String>> = HashMap::new();
+    for relation in content
+        .child("SPEC-RELATIONS")
+        .into_iter()
+        .flat_map(|relations| relations.children_named("SPEC-RELATION"))
+    {
+        let source = relation.child("SOURCE").and_then(reference);
+        let target = relation.child("TARGET").and_then(reference);
+        if let (Some(source), Some(target)) = (source, target) {
+            relations.entry(source).or_default().push(target);
+        }
+    }
+
+    let mut builder = TreeBuilder {
+        objects: &objects,
+        relations: &relations,
+        placed: HashSet::new(),
+    };
+    let mut requirements = vec![];
+    for specification in content
+        .child("SPECIFICATIONS")
+        .into_iter()
+        .flat_map(|specs| specs.children_named("SPECIFICATION"))
+    {
+        for hierarchy in specification
+            .child("CHILDREN")
+            .into_iter()
+            .flat_map(|children| children.children_named("SPEC-HIERARCHY"))
+        {
+            if let Some(RequirementReference::Full(req)) = builder.build(hierarchy) {
+                requirements.push(*req);
+            }
+        }
+    }
+    for identifier in &order {
+        if !builder.placed.contains(identifier) {
+            if let Some(RequirementReference::Full(req)) = builder.place(identifier, None) {
+                requirements.push(*req);
+            }
+        }
+    }
+
+    Ok(RequirementConfig {
+        version: "1.0".to_string(),
+        aliases: vec![],
+        templates: vec![],
+        requirements,
+    })
+}
+
+/// Attribute values of a spec object, by attribute long name
+fn object_values(
+    object: &Element,
+    long_names: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    for value in object
+        .child("VALUES")
+        .into_iter()
+        .flat_map(Element::elements)
+    {
+        let Some(definition) = value.child("DEFINITION").and_then(reference) else {
+            continue;
+        };
+        let name = long_names.get(&definition).cloned().unwrap_or(definition);
+        let text = if let Some(text) = value.attr("THE-VALUE") {
+            text.to_string()
+        } else if let Some(xhtml) = value.child("THE-VALUE") {
+            // Only the indentation around the XHTML is not part of the text
+            xhtml.text_content().trim().to_string()
+        } else if let Some(enums) = value.child("VALUES") {
+            enums
+                .elements()
+                .map(|reference| {
+                    let id = reference.text_content().trim().to_string();
+                    long_names.get(&id).cloned().unwrap_or(id)
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        } else {
+            continue;
+        };
+        values.insert(name, text);
+    }
+    values
+}
+
+fn requirement(identifier: &str, values: &HashMap<String, String>) -> Requirement {
+    let value = |name: &str| {
+        values
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .filter(|value| !value.is_empty())
+    };
+    let summary = value(NAME)
+        .or_else(|| value(CHAPTER_NAME))
+        .or_else(|| {
+            value(TEXT).and_then(|text| text.lines().next().map(|line| line.trim().to_string()))
+        })
+        .unwrap_or_else(|| identifier.to_string());
+
+    let mut req = Requirement::new(summary);
+    req.description = value(TEXT).filter(|text| *text != req.summary);
+    req.name = value(FOREIGN_ID);
+    req.status = value("Status").and_then(|s| serde_yaml::from_str(&s.to_lowercase()).ok());
+    req.priority = value("Priority").and_then(|p| serde_yaml::from_str(&p.to_lowercase()).ok());
+    req.owner = value("Owner").map(OwnerReference::String);
+    req.tags = value("Tags")
+        .map(|tags| tags.split(',').map(|tag| tag.trim().to_string()).collect())
+        .unwrap_or_default();
+    req.justification = value("Justification");
+    req.acceptance_test = value("Acceptance test");
+    req.target_release = value("Target release");
+    req
+}
+
+struct TreeBuilder<'a> {
+    objects: &'a HashMap<String, Requirement>,
+    relations: &'a HashMap<String, Vec<String>>,
+    placed: HashSet<String>,
+}
+
+impl TreeBuilder<'_> {
+    fn build(&mut self, hierarchy: &Element) -> Option<RequirementReference> {
+        let identifier = hierarchy.child("OBJECT").and_then(reference)?;
+        self.place(&identifier, hierarchy.child("CHILDREN"))
+    }
+
+    /// Define an object here, or refer to it if it was defined before
+    fn place(
+        &mut self,
+        identifier: &str,
+        children: Option<&Element>,
+    ) -> Option<RequirementReference> {
+        let object = self.objects.get(identifier)?;
+        if !self.placed.insert(identifier.to_string()) {
+            return Some(RequirementReference::Reference(object.summary.clone()));
+        }
+
+        let mut req = object.clone();
+        for child in children
+            .into_iter()
+            .flat_map(|children| children.children_named("SPEC-HIERARCHY"))
+        {
+            req.requirements.extend(self.build(child));
+        }
+        for target in self.relations.get(identifier).into_iter().flatten() {
+            let Some(target) = self.objects.get(target) else {
+                continue;
+            };
+            if !req
+                .requirements
+                .iter()
+                .any(|child| child.summary() == target.summary)
+            {
+                req.requirements
+                    .push(RequirementReference::Reference(target.summary.clone()));
+            }
+        }
+        Some(RequirementReference::Full(Box::new(req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    status: approved
+    owner: "@alice"
+    description: |
+      Users prove who they are
+      before using <any> feature.
+    requirements:
+      - summary: Login
+        priority: high
+        tags: [web, sso]
+        acceptance_test: SSO works
+      - Audit log
+  - summary: Audit log
+    name: AUD
+"#;
+
+    #[test]
+    fn test_roundtrip() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let xml = to_reqif(&config, None, "Product").unwrap();
+        assert!(xml.contains("<TITLE>Product</TITLE>"));
+        assert!(xml.contains("&lt;any&gt; feature.&#10;"));
+
+        let imported = from_reqif(&xml).unwrap();
+        assert_eq!(imported.requirements, config.requirements);
+    }
+
+    #[test]
+    fn test_export_uses_uuids() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "RIF".to_string()).unwrap();
+        let meta = store
+            .get_or_create_metadata(&config.requirements[0])
+            .unwrap();
+
+        let xml = to_reqif(&config, Some(&store), "Product").unwrap();
+        assert!(xml.contains(&format!("<SPEC-OBJECT IDENTIFIER=\"_{}\"", meta.uuid)));
+        let imported = from_reqif(&xml).unwrap();
+        assert_eq!(imported.requirements[0].name.as_deref(), Some("RIF-001"));
+    }
+
+    #[test]
+    fn test_import_foreign_tool() {
+        // Shaped like a DOORS export: XHTML text and enumerated status
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<REQ-IF xmlns="http://www.omg.org/spec/ReqIF/20110401/reqif.xsd" xmlns:xhtml="http://www.w3.org/1999/xhtml">
+  <CORE-CONTENT><REQ-IF-CONTENT>
+    <DATATYPES>
+      <DATATYPE-DEFINITION-ENUMERATION IDENTIFIER="dt-status" LONG-NAME="Status">
+        <SPECIFIED-VALUES>
+          <ENUM-VALUE IDENTIFIER="ev-draft" LONG-NAME="Draft"/>
+        </SPECIFIED-VALUES>
+      </DATATYPE-DEFINITION-ENUMERATION>
+    </DATATYPES>
+    <SPEC-TYPES><SPEC-OBJECT-TYPE IDENTIFIER="t"><SPEC-ATTRIBUTES>
+      <ATTRIBUTE-DEFINITION-XHTML IDENTIFIER="a-text" LONG-NAME="ReqIF.Text"/>
+      <ATTRIBUTE-DEFINITION-STRING IDENTIFIER="a-id" LONG-NAME="ReqIF.ForeignID"/>
+      <ATTRIBUTE-DEFINITION-ENUMERATION IDENTIFIER="a-status" LONG-NAME="Status"/>
+    </SPEC-ATTRIBUTES></SPEC-OBJECT-TYPE></SPEC-TYPES>
+    <SPEC-OBJECTS>
+      <SPEC-OBJECT IDENTIFIER="o1"><VALUES>
+        <ATTRIBUTE-VALUE-XHTML>
+          <DEFINITION><ATTRIBUTE-DEFINITION-XHTML-REF>a-text</ATTRIBUTE-DEFINITION-XHTML-REF></DEFINITION>
+          <THE-VALUE><xhtml:div>The system <xhtml:b>shall</xhtml:b> log in</xhtml:div></THE-VALUE>
+        </ATTRIBUTE-VALUE-XHTML>
+        <ATTRIBUTE-VALUE-STRING THE-VALUE="DOORS-17">
+          <DEFINITION><ATTRIBUTE-DEFINITION-STRING-REF>a-id</ATTRIBUTE-DEFINITION-STRING-REF></DEFINITION>
+        </ATTRIBUTE-VALUE-STRING>
+        <ATTRIBUTE-VALUE-ENUMERATION>
+          <DEFINITION><ATTRIBUTE-DEFINITION-ENUMERATION-REF>a-status</ATTRIBUTE-DEFINITION-ENUMERATION-REF></DEFINITION>
+          <VALUES><ENUM-VALUE-REF>ev-draft</ENUM-VALUE-REF></VALUES>
+        </ATTRIBUTE-VALUE-ENUMERATION>
+      </VALUES></SPEC-OBJECT>
+      <SPEC-OBJECT IDENTIFIER="o2"><VALUES/></SPEC-OBJECT>
+    </SPEC-OBJECTS>
+  </REQ-IF-CONTENT></CORE-CONTENT>
+</REQ-IF>"#;
+
+        let config = from_reqif(xml).unwrap();
+        assert_eq!(config.requirements.len(), 2);
+        let req = &config.requirements[0];
+        assert_eq!(req.summary, "The system shall log in");
+        assert_eq!(req.description, None);
+        assert_eq!(req.name.as_deref(), Some("DOORS-17"));
+        assert_eq!(req.status, Some(crate::types::Status::Draft));
+        assert_eq!(config.requirements[1].summary, "o2");
+    }
+
+    #[test]
+    fn test_import_keeps_whitespace_of_text() {
+        let xml = r#"<REQ-IF xmlns:xhtml="http://www.w3.org/1999/xhtml">
+  <CORE-CONTENT><REQ-IF-CONTENT><SPEC-OBJECTS>
+    <SPEC-OBJECT IDENTIFIER="o1"><VALUES>
+      <ATTRIBUTE-VALUE-XHTML>
+        <DEFINITION>
+          <ATTRIBUTE-DEFINITION-XHTML-REF> ReqIF.Text </ATTRIBUTE-DEFINITION-XHTML-REF>
+        </DEFINITION>
+        <THE-VALUE>
+          <xhtml:div>Log in
+with <xhtml:b>two</xhtml:b>-factor   codes</xhtml:div>
+        </THE-VALUE>
+      </ATTRIBUTE-VALUE-XHTML>
+    </VALUES></SPEC-OBJECT>
+  </SPEC-OBJECTS></REQ-IF-CONTENT></CORE-CONTENT>
+</REQ-IF>"#;
+
+        let config = from_reqif(xml).unwrap();
+        let req = &config.requirements[0];
+        assert_eq!(req.summary, "Log in");
+        assert_eq!(
+            req.description.as_deref(),
+            Some("Log in\nwith two-factor   codes")
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_other_xml() {
+        assert!(from_reqif("<graphml/>").is_err());
+        assert!(from_reqif("<REQ-IF><unclosed></REQ-IF>").is_err());
+    }
+}
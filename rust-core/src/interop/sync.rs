@@ -0,0 +1,165 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! What syncing with Jira and with GitHub have in common
+//!
+//! Both keep the state of the last sync in a JSON file in `.rqm`, and take
+//! statuses over the same way: a status changed in the tracker since the
+//! last sync replaces the requirement's, unless that changed as well, which
+//! is a conflict. Dry runs neither assign IDs nor write any file.
+
+use crate::metadata::{write_atomic, write_statuses, MetadataStore, RequirementMetadata};
+use crate::types::{Requirement, RequirementConfig, RequirementReference, Status};
+use crate::{Error, Parser, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Load the state of the last sync with `tracker` from `file` in an `.rqm`
+/// directory, starting empty if it is absent
+pub(crate) fn load_state<S: DeserializeOwned + Default>(
+    rqm_dir: &Path,
+    file: &str,
+    tracker: &str,
+) -> Result<S> {
+    let path = rqm_dir.join(file);
+    if !path.exists() {
+        return Ok(S::default());
+    }
+
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content)
+        .map_err(|e| Error::custom(format!("Failed to parse {} sync state: {}", tracker, e)))
+}
+
+/// Save the state of a sync with `tracker` to `file` in an `.rqm` directory
+pub(crate) fn save_state<S: Serialize>(
+    state: &S,
+    rqm_dir: &Path,
+    file: &str,
+    tracker: &str,
+) -> Result<()> {
+    let content = serde_json::to_string_pretty(state)
+        .map_err(|e| Error::custom(format!("Failed to serialize {} sync state: {}", tracker, e)))?;
+    write_atomic(&rqm_dir.join(file), content.as_bytes())?;
+    Ok(())
+}
+
+/// Metadata of a requirement being pushed
+///
+/// A dry run only looks it up, so that no ID is assigned; a requirement
+/// without metadata then has no issue yet.
+pub(crate) fn push_metadata(
+    store: &mut MetadataStore,
+    req: &Requirement,
+    dry_run: bool,
+) -> Result<Option<RequirementMetadata>> {
+    if dry_run {
+        store.find_metadata(&req.summary)
+    } else {
+        store.get_or_create_metadata(req).map(Some)
+    }
+}
+
+/// Call `visit` with every requirement defined in `config` that has
+/// metadata, parents before their children
+pub(crate) fn for_each_with_metadata(
+    config: &mut RequirementConfig,
+    store: &MetadataStore,
+    mut visit: impl FnMut(&mut Requirement, RequirementMetadata) -> Result<()>,
+) -> Result<()> {
+    fn walk(
+        req: &mut Requirement,
+        store: &MetadataStore,
+        visit: &mut impl FnMut(&mut Requirement, RequirementMetadata) -> Result<()>,
+    ) -> Result<()> {
+        if let Some(meta) = store.find_metadata(&req.summary)? {
+            visit(req, meta)?;
+        }
+        for child in &mut req.requirements {
+            if let RequirementReference::Full(child) = child {
+                walk(child, store, visit)?;
+            }
+        }
+        Ok(())
+    }
+    for req in &mut config.requirements {
+        walk(req, store, &mut visit)?;
+    }
+    Ok(())
+}
+
+/// What a pull does with a requirement's status
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Pull {
+    Keep,
+    Take(Status),
+    Conflict,
+}
+
+/// Decide on the status `remote` the tracker implies for a requirement
+/// whose status is `local`, given which sides changed since the last sync
+pub(crate) fn reconcile(
+    local: Option<Status>,
+    remote: Option<Status>,
+    local_changed: bool,
+    remote_changed: bool,
+) -> Pull {
+    match remote {
+        Some(remote) if local != Some(remote) && remote_changed => {
+            if local_changed {
+                Pull::Conflict
+            } else {
+                Pull::Take(remote)
+            }
+        }
+        _ => Pull::Keep,
+    }
+}
+
+/// Pull into the requirements file at `path` and write the statuses taken
+/// over back with [`write_statuses`], leaving the rest of the file as it is
+pub(crate) fn pull_file<R>(
+    path: &Path,
+    dry_run: bool,
+    pull: impl FnOnce(&mut RequirementConfig) -> Result<R>,
+    taken: impl Fn(&R) -> Vec<(String, Status)>,
+) -> Result<R> {
+    let mut requirements = Parser::parse_file(path)?;
+    let report = pull(&mut requirements)?;
+    let statuses = taken(&report);
+    if !dry_run && !statuses.is_empty() {
+        write_statuses(path, &statuses)?;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile() {
+        use Status::*;
+        assert_eq!(
+            reconcile(Some(Approved), Some(Implemented), false, true),
+            Pull::Take(Implemented)
+        );
+        assert_eq!(
+            reconcile(Some(Proposed), Some(Implemented), true, true),
+            Pull::Conflict
+        );
+        // Unchanged in the tracker, or already in sync
+        assert_eq!(
+            reconcile(Some(Approved), Some(Implemented), false, false),
+            Pull::Keep
+        );
+        assert_eq!(
+            reconcile(Some(Implemented), Some(Implemented), true, true),
+            Pull::Keep
+        );
+        assert_eq!(reconcile(Some(Approved), None, false, true), Pull::Keep);
+    }
+}
@@ -10,25 +10,112 @@
 //! ## Features
 //!
 //! - Parse YAML requirement files with full validation
+//! - Borrow requirement text from the source for read-only workflows
 //! - Build requirement graphs with circular reference detection
 //! - Query and traverse requirement trees
+//! - Per-owner workload of open requirements by priority and status
+//! - Flag draft and proposed requirements left unchanged past a threshold
+//! - Status roll-up from children, flagging parents ahead of them
+//! - Priority conflicts under critical parents, with optional inheritance
+//! - Archiving of deprecated requirements with their metadata and history
+//! - Filter requirements with expressions like `status==draft && tag=safety`
+//! - Ranked full-text search with phrase queries across files
 //! - Export to various formats
 //! - Automatic ID generation with metadata management
+//! - Project scaffolding with starter requirements, lint rules and glossary
+//! - Fluent builders for constructing requirements programmatically
+//! - Validate requirements spread over several files as one workspace, on
+//!   every core with the `parallel` feature
+//! - Monorepos of several projects, each with its own `.rqm` directory and
+//!   ID prefix, referencing each other as `project:summary` or `project:REQ-ID`
+//! - Revalidate files as they change, reporting new and fixed findings
+//! - Derive timestamps and authors from git history (`git` feature)
+//! - Interactive HTML reports for stakeholders
+//! - Static documentation websites for GitHub Pages
+//! - Numbered Markdown specification documents and outlines
+//! - Spreadsheet exports as CSV, or Excel workbooks (`xlsx` feature)
+//! - Formal specifications as PDF from templates (`pdf` feature)
+//! - ReqIF import and export for DOORS, Polarion and Jama
+//! - OSLC RM 2.0 provider documents for ALM tools to link requirements
+//! - Traceability from requirements to `rqm:` annotations in source code
+//! - Links to the same requirement in other systems, from per-system URL templates
+//! - Glossary of terms, linked from exported text and checked for undefined acronyms
+//! - Attached files and pages, checked against checksums and copied into exports
+//! - Translations of requirement text, checked per locale and picked for exports
+//! - Confidentiality levels, with redacted exports for external readers
+//! - Signed approvals by role, required before approval and listed when missing
+//! - Verification status from JUnit XML and `cargo test` JSON results
+//! - Requirements Traceability Matrix as CSV, HTML or JSON
+//! - Compile-time checked requirement ID constants for Rust code
+//! - C bindings for the Go CLI, with a generated `rqm_core.h` header
+//! - WebAssembly bindings for validating in the browser (`wasm` feature)
+//! - Language server with diagnostics, hover and completion for editors
+//! - Three-way semantic merge of requirement files, usable as a git merge driver
+//! - Change notifications for Slack, Teams and webhooks (`notify` feature)
+//! - Installation of a pre-commit validation hook and the merge driver
+//! - Read-only HTTP and GraphQL APIs over requirements and reports (`server` feature)
+//! - Versioned JSON results with published JSON Schemas for tools
 
+pub mod archive;
+pub mod builder;
+pub mod codegen;
 pub mod error;
+pub mod export;
 pub mod ffi;
+#[cfg(feature = "git")]
+pub mod git;
 pub mod graph;
+pub mod hooks;
+#[cfg(any(feature = "jira", feature = "github", feature = "notify"))]
+mod http;
+pub mod interop;
+pub mod locale;
+pub mod lsp;
+pub mod merge;
 pub mod metadata;
+#[cfg(feature = "notify")]
+pub mod notify;
+pub mod output;
 pub mod parser;
+mod percent;
+pub mod query;
+pub mod redact;
+pub mod report;
+pub mod scaffold;
+pub mod search;
+pub mod serialize;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod trace;
 pub mod types;
 pub mod validator;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+pub mod watch;
+pub mod workspace;
+mod xml;
 
+pub use builder::{RequirementBuilder, RequirementConfigBuilder};
 pub use error::{Error, Result};
-pub use graph::RequirementGraph;
-pub use metadata::{kebab_case, MetadataStore, ProjectConfig, RequirementMetadata};
+pub use graph::{
+    CriticalPath, Cycle, DotOptions, GraphStats, RequirementGraph, SubgraphFilter,
+    TraversalOptions, TraversalOrder,
+};
+pub use metadata::{
+    kebab_case, FileSync, HistoryEntry, IdRange, IdScheme, MetadataStore, ProjectConfig,
+    PruneAction, PruneReport, Release, Rename, RenameMatch, RequirementMetadata,
+    SharedMetadataStore, Snapshot, StorageBackend, TagRegistry,
+};
 pub use parser::Parser;
-pub use types::{OwnerReference, PersonAlias, Requirement, RequirementConfig};
-pub use validator::Validator;
+pub use query::Expr;
+pub use serialize::{EmptyFields, SerializeOptions};
+pub use types::{
+    Approval, Attachment, Confidentiality, ExternalRef, OwnerReference, PersonAlias, Requirement,
+    RequirementConfig, RequirementConfigRef, RequirementRef, RequirementTemplate, Translation,
+};
+pub use validator::{GlossaryEntry, LintConfig, ValidationReport, Validator};
+pub use workspace::{Monorepo, Workspace};
 
 /// Version of the library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -0,0 +1,178 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Requirement text in several languages
+//!
+//! Requirements carry translations of their summary and description next
+//! to the original text:
+//!
+//! ```yaml
+//! - summary: Login
+//!   description: Users sign in with their email address
+//!   translations:
+//!     de:
+//!       summary: Anmeldung
+//!       description: Benutzer melden sich mit ihrer E-Mail-Adresse an
+//! ```
+//!
+//! The original summary stays the identity of a requirement, so references
+//! and metadata never depend on a language. [`translate`] produces a copy
+//! in one language for exports.
+
+use crate::error::{Error, Result};
+use crate::metadata::{display_id, MetadataStore};
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use std::collections::{BTreeSet, HashMap};
+
+/// Locales any requirement in `config` is translated to
+pub fn locales(config: &RequirementConfig) -> BTreeSet<String> {
+    config
+        .all_requirements()
+        .into_iter()
+        .flat_map(|req| req.translations.keys().cloned())
+        .collect()
+}
+
+/// Copy of `config` with summaries and descriptions in `locale`
+///
+/// Text without a translation is kept as it is, and references follow the
+/// translated summaries. Generated IDs from `store` are kept as `name`,
+/// since metadata is looked up by the original summary. Fails if two
+/// requirements translate to the same summary.
+pub fn translate(
+    config: &RequirementConfig,
+    locale: &str,
+    store: Option<&MetadataStore>,
+) -> Result<RequirementConfig> {
+    let mut summaries: HashMap<&str, &str> = HashMap::new();
+    let mut originals: HashMap<&str, &str> = HashMap::new();
+    for req in config.all_requirements() {
+        let translated = req
+            .translations
+            .get(locale)
+            .and_then(|translation| translation.summary.as_deref())
+            .unwrap_or(&req.summary);
+        if let Some(other) = originals.insert(translated, &req.summary) {
+            if other != req.summary {
+                return Err(Error::DuplicateSummary(format!(
+                    "'{}' and '{}' are both '{}' in '{}'",
+                    other, req.summary, translated, locale
+                )));
+            }
+        }
+        summaries.insert(&req.summary, translated);
+    }
+
+    let mut translated = config.clone();
+    for req in &mut translated.requirements {
+        translate_requirement(req, locale, &summaries, store)?;
+    }
+    Ok(translated)
+}
+
+fn translate_requirement(
+    req: &mut Requirement,
+    locale: &str,
+    summaries: &HashMap<&str, &str>,
+    store: Option<&MetadataStore>,
+) -> Result<()> {
+    let rename = |summary: &str| summaries.get(summary).unwrap_or(&summary).to_string();
+
+    if req.name.is_none() {
+        req.name = display_id(store, req)?;
+    }
+    let translation = req.translations.remove(locale).unwrap_or_default();
+    req.translations.clear();
+    req.summary = rename(&req.summary);
+    if let Some(description) = translation.description {
+        req.description = Some(description);
+    }
+    req.superseded_by = req.superseded_by.as_deref().map(rename);
+    req.supersedes = req.supersedes.iter().map(|s| rename(s)).collect();
+    for child in &mut req.requirements {
+        match child {
+            RequirementReference::Full(child) => {
+                translate_requirement(child, locale, summaries, store)?
+            }
+            RequirementReference::Reference(summary) => *summary = rename(summary),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    description: Users sign in
+    translations:
+      de:
+        summary: Anmeldung
+        description: Benutzer melden sich an
+      fr:
+        summary: Connexion
+    requirements:
+      - summary: Lockout
+      - Audit
+  - summary: Audit
+    superseded_by: Login
+"#;
+
+    #[test]
+    fn test_translate() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "LOC".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        store
+            .get_or_create_metadata(config.all_requirements()[0])
+            .unwrap();
+        assert_eq!(
+            locales(&config).into_iter().collect::<Vec<_>>(),
+            vec!["de", "fr"]
+        );
+
+        let german = translate(&config, "de", Some(&store)).unwrap();
+        let login = &german.requirements[0];
+        assert_eq!(login.summary, "Anmeldung");
+        assert_eq!(login.name.as_deref(), Some("LOC-001"));
+        assert_eq!(
+            login.description.as_deref(),
+            Some("Benutzer melden sich an")
+        );
+        assert!(login.translations.is_empty());
+        assert_eq!(
+            german.requirements[1].superseded_by.as_deref(),
+            Some("Anmeldung")
+        );
+        // Untranslated text is kept
+        assert_eq!(german.all_requirements()[1].summary, "Lockout");
+
+        let french = translate(&config, "fr", None).unwrap();
+        assert_eq!(french.requirements[0].summary, "Connexion");
+        assert_eq!(
+            french.requirements[0].description.as_deref(),
+            Some("Users sign in")
+        );
+    }
+
+    #[test]
+    fn test_translations_must_not_collide() {
+        let mut config = Parser::parse_str(YAML).unwrap();
+        config.requirements[1].translations.insert(
+            "de".to_string(),
+            crate::Translation {
+                summary: Some("Anmeldung".to_string()),
+                description: None,
+            },
+        );
+        let error = translate(&config, "de", None).unwrap_err().to_string();
+        assert!(error.contains("'Login' and 'Audit' are both 'Anmeldung' in 'de'"));
+    }
+}
@@ -0,0 +1,704 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Language Server Protocol support for requirements files
+//!
+//! [`LanguageServer`] answers JSON-RPC messages with diagnostics from the
+//! [`Validator`], go-to-definition for references in `requirements:` lists,
+//! hover cards with a requirement's description, owner and status, and
+//! completion of summaries, names and generated IDs. The `rqm-lsp` binary
+//! runs it over standard input and output with [`serve`], which answers
+//! malformed messages and failed requests with JSON-RPC errors rather than
+//! stopping.
+//!
+//! Documents are synchronized in full on every change; requirements files
+//! are small enough that incremental sync is not worth its complexity.
+//! Positions are treated at line granularity, so UTF-16 column offsets
+//! never need converting.
+
+use crate::archive::load_archive;
+use crate::percent;
+use crate::types::{Requirement, RequirementConfig};
+use crate::validator::Validator;
+use crate::{Error, Parser, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Read, Write};
+use std::path::PathBuf;
+
+/// Largest message body accepted, far above any requirements file
+pub const MAX_MESSAGE_LENGTH: usize = 64 * 1024 * 1024;
+
+/// JSON-RPC error code for a message that is not valid JSON
+const PARSE_ERROR: i64 = -32700;
+
+/// JSON-RPC error code for a request that failed
+const INTERNAL_ERROR: i64 = -32603;
+
+/// An open document and what could be parsed from it
+struct Document {
+    text: String,
+    config: Option<RequirementConfig>,
+}
+
+/// Answers LSP requests for open requirements files
+pub struct LanguageServer {
+    validator: Validator,
+    documents: BTreeMap<String, Document>,
+    shutdown: bool,
+}
+
+/// What is written on a line: a `summary:` or a reference list entry
+fn line_value(line: &str) -> Option<String> {
+    let line = line.trim_start();
+    let item = line.strip_prefix('-').map(str::trim_start);
+    let value = match line
+        .trim_start_matches('-')
+        .trim_start()
+        .strip_prefix("summary:")
+    {
+        Some(value) => value,
+        None => item.filter(|item| !item.contains(": ") && !item.ends_with(':'))?,
+    };
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value);
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+/// A file path from a `file://` URI
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let path = path.strip_prefix("localhost").unwrap_or(path);
+    let path = percent::decode(path);
+    // `file:///C:/project` names `C:/project`
+    let drive = path.as_bytes().get(1..3).filter(|_| path.starts_with('/'));
+    match drive {
+        Some([letter, b':']) if letter.is_ascii_alphabetic() => Some(PathBuf::from(&path[1..])),
+        _ => Some(PathBuf::from(path)),
+    }
+}
+
+fn line_range(line: usize, text: &str) -> Value {
+    let length = text
+        .lines()
+        .nth(line)
+        .map_or(0, |l| l.encode_utf16().count());
+    json!({
+        "start": { "line": line, "character": 0 },
+        "end": { "line": line, "character": length },
+    })
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn error_response(id: &Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+impl LanguageServer {
+    /// Validate with the default rules until `initialize` names a project,
+    /// then with its settings
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            validator: Validator::new()?,
+            documents: BTreeMap::new(),
+            shutdown: false,
+        })
+    }
+
+    /// Whether the client asked the server to exit
+    pub fn is_shut_down(&self) -> bool {
+        self.shutdown
+    }
+
+    /// Handle one message, returning the responses and notifications to send
+    pub fn handle(&mut self, message: &Value) -> Result<Vec<Value>> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let id = &message["id"];
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        Ok(match method {
+            "initialize" => {
+                let root = params["rootUri"].as_str().and_then(uri_path);
+                if let Some(rqm_dir) = root.map(|root| root.join(".rqm")) {
+                    self.validator = Validator::for_project(&rqm_dir)?;
+                }
+                vec![response(
+                    id,
+                    json!({
+                        "capabilities": {
+                            "textDocumentSync": 1,
+                            "definitionProvider": true,
+                            "hoverProvider": true,
+                            "completionProvider": { "triggerCharacters": ["-", " "] },
+                        },
+                        "serverInfo": { "name": "rqm-lsp", "version": env!("CARGO_PKG_VERSION") },
+                    }),
+                )]
+            }
+            "shutdown" => {
+                self.shutdown = true;
+                vec![response(id, Value::Null)]
+            }
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.update(uri, text.to_string())
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                match changes
+                    .and_then(|c| c.last())
+                    .and_then(|c| c["text"].as_str())
+                {
+                    Some(text) => self.update(uri, text.to_string()),
+                    None => vec![],
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                vec![json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": { "uri": uri, "diagnostics": [] },
+                })]
+            }
+            "textDocument/definition" => vec![response(id, self.definition(uri, params))],
+            "textDocument/hover" => vec![response(id, self.hover(uri, params))],
+            "textDocument/completion" => vec![response(id, self.completion(uri, params))],
+            _ if !id.is_null() => vec![error_response(
+                id,
+                -32601,
+                format!("Unsupported method '{}'", method),
+            )],
+            // Other notifications need no answer
+            _ => vec![],
+        })
+    }
+
+    /// Store a document's new text and publish its diagnostics
+    ///
+    /// References may name requirements archived from the document's file.
+    fn update(&mut self, uri: &str, text: String) -> Vec<Value> {
+        let mut diagnostics = vec![];
+        let archived = uri_path(uri).map(|path| load_archive(&path)).transpose();
+        match archived {
+            Ok(archived) => self.validator.set_archive(archived.flatten().as_ref()),
+            Err(e) => {
+                self.validator.set_archive(None);
+                diagnostics.push(json!({
+                    "range": line_range(0, &text),
+                    "severity": 2,
+                    "source": "rqm",
+                    "message": format!("Could not read the archive: {}", e),
+                }));
+            }
+        }
+        let config = match Parser::parse_str(&text) {
+            Ok(config) => {
+                let report = self.validator.validate(&config);
+                for (severity, findings) in [
+                    (1, &report.errors),
+                    (2, &report.warnings),
+                    (3, &report.infos),
+                ] {
+                    for finding in findings {
+                        let line = finding
+                            .requirement
+                            .as_deref()
+                            .and_then(|summary| Parser::summary_line(&text, summary))
+                            .map_or(0, |line| line - 1);
+                        diagnostics.push(json!({
+                            "range": line_range(line, &text),
+                            "severity": severity,
+                            "source": "rqm",
+                            "code": finding.rule,
+                            "message": finding.message(),
+                        }));
+                    }
+                }
+                Some(config)
+            }
+            Err(e) => {
+                diagnostics.push(json!({
+                    "range": line_range(error_line(&e), &text),
+                    "severity": 1,
+                    "source": "rqm",
+                    "message": e.to_string(),
+                }));
+                // Keep the last good parse for navigation while editing
+                self.documents.get(uri).and_then(|doc| doc.config.clone())
+            }
+        };
+        self.documents
+            .insert(uri.to_string(), Document { text, config });
+        vec![json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        })]
+    }
+
+    /// The summary or reference on the line at the request's position
+    fn value_at(&self, uri: &str, params: &Value) -> Option<String> {
+        let line = params["position"]["line"].as_u64()? as usize;
+        line_value(self.documents.get(uri)?.text.lines().nth(line)?)
+    }
+
+    /// A requirement by summary or name in any open document
+    fn find(&self, value: &str) -> Option<(&str, &Document, &Requirement)> {
+        self.documents.iter().find_map(|(uri, doc)| {
+            let req = doc
+                .config
+                .as_ref()?
+                .all_requirements()
+                .into_iter()
+                .find(|req| req.summary == value || req.name.as_deref() == Some(value))?;
+            Some((uri.as_str(), doc, req))
+        })
+    }
+
+    fn definition(&self, uri: &str, params: &Value) -> Value {
+        let Some((target, doc, req)) = self
+            .value_at(uri, params)
+            .and_then(|value| self.find(&value))
+        else {
+            return Value::Null;
+        };
+        match Parser::summary_line(&doc.text, &req.summary) {
+            Some(line) => json!({ "uri": target, "range": line_range(line - 1, &doc.text) }),
+            None => Value::Null,
+        }
+    }
+
+    fn hover(&self, uri: &str, params: &Value) -> Value {
+        let Some((_, _, req)) = self
+            .value_at(uri, params)
+            .and_then(|value| self.find(&value))
+        else {
+            return Value::Null;
+        };
+        let mut text = format!("**{}**", req.summary);
+        if let Some(name) = &req.name {
+            text.push_str(&format!(" `{}`", name));
+        }
+        let mut facts = vec![];
+        if let Some(status) = req.status {
+            facts.push(format!("Status: {}", status));
+        }
+        if let Some(owner) = &req.owner {
+            facts.push(format!("Owner: {}", owner.as_str()));
+        }
+        if !facts.is_empty() {
+            text.push_str(&format!("\n\n{}", facts.join(" · ")));
+        }
+        if let Some(description) = &req.description {
+            text.push_str(&format!("\n\n{}", description.trim()));
+        }
+        json!({ "contents": { "kind": "markdown", "value": text } })
+    }
+
+    /// Summaries when the cursor is in a nested `requirements:` list entry
+    ///
+    /// Names and generated IDs complete to the summary they stand for.
+    fn completion(&self, uri: &str, params: &Value) -> Value {
+        let Some(doc) = self.documents.get(uri) else {
+            return Value::Null;
+        };
+        let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+        let lines: Vec<&str> = doc.text.lines().collect();
+        let current = lines.get(line).copied().unwrap_or_default();
+        let indent = current.len() - current.trim_start().len();
+        if !current.trim_start().starts_with('-') {
+            return Value::Null;
+        }
+        let list_key = lines[..line.min(lines.len())]
+            .iter()
+            .rev()
+            .find(|l| !l.trim().is_empty() && l.len() - l.trim_start().len() < indent);
+        let in_requirements = match list_key {
+            // The top-level list holds definitions, not references
+            Some(key) => {
+                key.starts_with(' ') && key.trim_start().trim_start_matches("- ") == "requirements:"
+            }
+            None => false,
+        };
+        if !in_requirements {
+            return Value::Null;
+        }
+
+        let mut items = vec![];
+        for doc in self.documents.values() {
+            for req in doc.config.iter().flat_map(|c| c.all_requirements()) {
+                let id = match self.validator.metadata() {
+                    Some(store) => store
+                        .find_metadata(&req.summary)
+                        .ok()
+                        .flatten()
+                        .map(|meta| meta.generated_id),
+                    None => None,
+                };
+                items.push(json!({
+                    "label": req.summary,
+                    "kind": 18,
+                    "detail": id.as_ref().or(req.name.as_ref()),
+                    "documentation": req.description,
+                }));
+                for alias in id.iter().chain(&req.name) {
+                    items.push(json!({
+                        "label": alias,
+                        "kind": 18,
+                        "detail": req.summary,
+                        "insertText": req.summary,
+                        "documentation": req.description,
+                    }));
+                }
+            }
+        }
+        json!({ "isIncomplete": false, "items": items })
+    }
+}
+
+/// 0-based line of a parse error, or the first line if unknown
+fn error_line(error: &Error) -> usize {
+    let message = error.to_string();
+    message
+        .find("line ")
+        .and_then(|start| {
+            let digits: String = message[start + 5..]
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+            digits.parse::<usize>().ok()
+        })
+        .map_or(0, |line| line.saturating_sub(1))
+}
+
+/// Read one message framed with a `Content-Length` header
+///
+/// Returns `None` at the end of input. A body that is not JSON, or longer
+/// than [`MAX_MESSAGE_LENGTH`], is consumed before the error is returned,
+/// so the next message can still be read.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length.ok_or_else(|| Error::custom("LSP message without Content-Length"))?;
+    if length > MAX_MESSAGE_LENGTH {
+        io::copy(&mut reader.take(length as u64), &mut io::sink())?;
+        return Err(Error::custom(format!(
+            "LSP message of {} bytes exceeds the limit of {} bytes",
+            length, MAX_MESSAGE_LENGTH
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| Error::custom(format!("Invalid LSP message: {}", e)))
+}
+
+/// Write one message with its `Content-Length` header
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<()> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Run the server until the client sends `exit` or closes the input
+///
+/// Only failing to read or write the streams ends it early: a malformed
+/// message is answered with a parse error, a failed request with an
+/// internal error, and a failed notification is logged to the client.
+pub fn serve(reader: &mut impl BufRead, writer: &mut impl Write) -> Result<()> {
+    let mut server = LanguageServer::new()?;
+    loop {
+        let message = match read_message(reader) {
+            Ok(Some(message)) => message,
+            Ok(None) => break,
+            Err(e @ Error::IoError(_)) => return Err(e),
+            Err(e) => {
+                write_message(
+                    writer,
+                    &error_response(&Value::Null, PARSE_ERROR, e.to_string()),
+                )?;
+                continue;
+            }
+        };
+        if message["method"] == "exit" {
+            break;
+        }
+        let replies = match server.handle(&message) {
+            Ok(replies) => replies,
+            Err(e) if !message["id"].is_null() => {
+                vec![error_response(
+                    &message["id"],
+                    INTERNAL_ERROR,
+                    e.to_string(),
+                )]
+            }
+            Err(e) => vec![json!({
+                "jsonrpc": "2.0",
+                "method": "window/logMessage",
+                "params": { "type": 1, "message": e.to_string() },
+            })],
+        };
+        for reply in replies {
+            write_message(writer, &reply)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "file:///project/requirements.yml";
+    const YAML: &str = r#"version: "1.0"
+requirements:
+  - summary: Authentication
+    owner: "@alice"
+    status: approved
+    description: Users prove who they are
+    requirements:
+      - Audit log
+      - Missing
+  - summary: Audit log
+    name: AUDIT
+"#;
+
+    fn open(server: &mut LanguageServer, text: &str) -> Value {
+        let replies = server
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": URI, "text": text } },
+            }))
+            .unwrap();
+        replies[0]["params"]["diagnostics"].clone()
+    }
+
+    fn request(server: &mut LanguageServer, method: &str, line: usize) -> Value {
+        let replies = server
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": method,
+                "params": {
+                    "textDocument": { "uri": URI },
+                    "position": { "line": line, "character": 9 },
+                },
+            }))
+            .unwrap();
+        assert_eq!(replies[0]["id"], 7);
+        replies[0]["result"].clone()
+    }
+
+    #[test]
+    fn test_line_value() {
+        assert_eq!(
+            line_value("  - summary: \"Login\" # note").as_deref(),
+            Some("Login")
+        );
+        assert_eq!(
+            line_value("      - Audit log").as_deref(),
+            Some("Audit log")
+        );
+        assert_eq!(line_value("    owner: \"@alice\""), None);
+        assert_eq!(
+            line_value("  - summary: Login\n"),
+            Some("Login".to_string())
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_for_findings_and_parse_errors() {
+        let mut server = LanguageServer::new().unwrap();
+        let diagnostics = open(&mut server, YAML);
+        let missing = diagnostics
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["message"].as_str().unwrap().contains("Missing"))
+            .unwrap();
+        assert_eq!(missing["severity"], 1);
+        assert_eq!(missing["range"]["start"]["line"], 2);
+
+        let diagnostics = open(&mut server, &format!("{}  - summary: [\n", YAML));
+        assert_eq!(diagnostics[0]["severity"], 1);
+        // The last good parse is kept for navigation
+        assert!(!request(&mut server, "textDocument/hover", 7).is_null());
+    }
+
+    #[test]
+    fn test_definition_hover_and_completion() {
+        let mut server = LanguageServer::new().unwrap();
+        open(&mut server, YAML);
+
+        let definition = request(&mut server, "textDocument/definition", 7);
+        assert_eq!(definition["uri"], URI);
+        assert_eq!(definition["range"]["start"]["line"], 9);
+        assert!(request(&mut server, "textDocument/definition", 8).is_null());
+
+        let hover = request(&mut server, "textDocument/hover", 2);
+        let text = hover["contents"]["value"].as_str().unwrap();
+        assert!(text.starts_with("**Authentication**"));
+        assert!(text.contains("Status: approved · Owner: @alice"));
+        assert!(text.contains("Users prove who they are"));
+
+        let completion = request(&mut server, "textDocument/completion", 8);
+        let labels: Vec<&str> = completion["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, vec!["Authentication", "Audit log", "AUDIT"]);
+        assert_eq!(completion["items"][2]["insertText"], "Audit log");
+        assert!(request(&mut server, "textDocument/completion", 2).is_null());
+    }
+
+    #[test]
+    fn test_completion_offers_generated_ids() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut store =
+            crate::MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        store
+            .get_or_create_metadata(&config.requirements[0])
+            .unwrap();
+
+        let mut server = LanguageServer::new().unwrap();
+        let root = format!("file://{}", temp.path().display()).replace(' ', "%20");
+        server
+            .handle(&json!({ "id": 1, "method": "initialize", "params": { "rootUri": root } }))
+            .unwrap();
+        open(&mut server, YAML);
+        let completion = request(&mut server, "textDocument/completion", 8);
+        let id = completion["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|item| item["label"] == "REQ-001")
+            .unwrap();
+        assert_eq!(id["insertText"], "Authentication");
+    }
+
+    #[test]
+    fn test_references_to_archived_requirements() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("archive")).unwrap();
+        std::fs::write(
+            temp.path().join("archive/requirements.yml"),
+            "version: \"1.0\"\nrequirements:\n  - summary: Missing\n    status: deprecated\n",
+        )
+        .unwrap();
+        let uri = format!("file://{}/requirements.yml", temp.path().display());
+
+        let mut server = LanguageServer::new().unwrap();
+        let replies = server
+            .handle(&json!({
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": uri.replace(' ', "%20"), "text": YAML } },
+            }))
+            .unwrap();
+        let messages: Vec<&str> = replies[0]["params"]["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["message"].as_str().unwrap())
+            .collect();
+        assert!(messages.iter().all(|m| !m.contains("non-existent")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("depends on archived 'Missing'")));
+    }
+
+    #[test]
+    fn test_uri_path() {
+        assert_eq!(
+            uri_path("file:///home/me/My%20Project/%C3%A4+b.yml"),
+            Some(PathBuf::from("/home/me/My Project/ä+b.yml"))
+        );
+        assert_eq!(
+            uri_path("file:///c%3A/project"),
+            Some(PathBuf::from("c:/project"))
+        );
+        assert_eq!(uri_path("https://example.com/x"), None);
+    }
+
+    #[test]
+    fn test_framing_and_serve() {
+        let mut input = vec![];
+        for message in [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+        let mut output = vec![];
+        serve(&mut input.as_slice(), &mut output).unwrap();
+
+        let mut reader = output.as_slice();
+        let initialized = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(initialized["result"]["capabilities"]["hoverProvider"], true);
+        let shutdown = read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(shutdown["id"], 2);
+        assert!(read_message(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_serve_survives_bad_messages() {
+        let mut input = b"Content-Length: 9\r\n\r\nnot json!".to_vec();
+        input.extend(format!("Content-Length: {}\r\n\r\n", MAX_MESSAGE_LENGTH + 1).as_bytes());
+        input.resize(input.len() + MAX_MESSAGE_LENGTH + 1, b' ');
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".rqm")).unwrap();
+        std::fs::write(temp.path().join(".rqm/lint.yml"), "rules: [").unwrap();
+        let root = format!("file://{}", temp.path().display()).replace(' ', "%20");
+        for message in [
+            // The root's lint.yml cannot be parsed
+            json!({ "id": 1, "method": "initialize", "params": { "rootUri": root } }),
+            json!({ "id": 2, "method": "shutdown" }),
+        ] {
+            write_message(&mut input, &message).unwrap();
+        }
+        let mut output = vec![];
+        serve(&mut input.as_slice(), &mut output).unwrap();
+
+        let mut reader = output.as_slice();
+        let mut next = || read_message(&mut reader).unwrap().unwrap();
+        assert_eq!(next()["error"]["code"], PARSE_ERROR);
+        let too_long = next();
+        assert_eq!(too_long["error"]["code"], PARSE_ERROR);
+        assert!(too_long["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("exceeds"));
+        let failed = next();
+        assert_eq!(failed["id"], 1);
+        assert_eq!(failed["error"]["code"], INTERNAL_ERROR);
+        assert_eq!(next()["id"], 2);
+    }
+}
@@ -0,0 +1,795 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Three-way semantic merge of requirement files
+//!
+//! Requirements are matched by the UUID of their metadata, so a requirement
+//! renamed on one side and edited on the other is still one requirement;
+//! without metadata they are matched by summary. They are merged field by
+//! field: a field changed on one side only takes that side's value, and a
+//! field changed differently on both sides is a [`MergeConflict`]. A
+//! requirement's parent is merged like a field, so moving a requirement on
+//! one side and editing it on the other merges cleanly. Child lists keep the
+//! order of ours, with the children added by theirs placed after their
+//! predecessor.
+//!
+//! `rqm-validator merge` runs as a git merge driver:
+//!
+//! ```text
+//! # .gitattributes
+//! *.rqm.yml merge=rqm
+//!
+//! # .git/config
+//! [merge "rqm"]
+//!     name = RQM requirements merge
+//!     driver = rqm-validator merge %O %A %B --path %P
+//! ```
+//!
+//! [`merge_files`] writes the merged file as text: top-level requirements
+//! the merge leaves as they are in ours or theirs are copied byte for byte,
+//! with their comments and formatting, and only changed ones are serialized
+//! again. Unresolved conflicts are written between git's conflict markers.
+
+use crate::graph::diff::fields;
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use crate::{Error, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+mod text;
+
+/// Version of a conflicting value to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Base,
+    Ours,
+    Theirs,
+}
+
+/// A change made differently on both sides
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MergeConflict {
+    /// Summary of the requirement, or `None` for top-level fields such as
+    /// `aliases`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+
+    /// Conflicting field, `parent` for conflicting moves, or `None` when one
+    /// side deleted a requirement the other changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    /// Value on each side (`null` when unset or deleted)
+    pub base: Value,
+    pub ours: Value,
+    pub theirs: Value,
+}
+
+/// Result of a merge
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge {
+    /// Merged requirements, using ours for every unresolved conflict
+    pub config: RequirementConfig,
+
+    /// Conflicts left unresolved
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl Merge {
+    /// Check whether the merge needs no manual resolution
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merge the changes from `base` to `ours` and to `theirs`
+pub fn merge(
+    base: &RequirementConfig,
+    ours: &RequirementConfig,
+    theirs: &RequirementConfig,
+) -> Result<Merge> {
+    merge_with(base, ours, theirs, |_| None)
+}
+
+/// Merge requirement files, without resolving templates
+pub fn merge_str(base: &str, ours: &str, theirs: &str) -> Result<Merge> {
+    merge(&parse(base)?, &parse(ours)?, &parse(theirs)?)
+}
+
+fn parse(content: &str) -> Result<RequirementConfig> {
+    serde_yaml::from_str(content).map_err(Error::enhance_yaml_error)
+}
+
+/// Merge like [`merge`], asking `resolve` which side wins each conflict
+///
+/// Conflicts for which `resolve` returns `None` stay unresolved.
+pub fn merge_with(
+    base: &RequirementConfig,
+    ours: &RequirementConfig,
+    theirs: &RequirementConfig,
+    mut resolve: impl FnMut(&MergeConflict) -> Option<Side>,
+) -> Result<Merge> {
+    let keys = Keys::default();
+    let trees = [
+        Tree::new(base, &keys),
+        Tree::new(ours, &keys),
+        Tree::new(theirs, &keys),
+    ];
+    Ok(merge_trees(&trees, &mut resolve)?.merge)
+}
+
+/// A merged requirements file
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedFile {
+    /// Content of the merged file, with conflict markers around the
+    /// requirements of unresolved conflicts
+    pub text: String,
+
+    pub merge: Merge,
+}
+
+/// Merge the content of requirement files, matching requirements by the
+/// UUIDs in `store`
+///
+/// Templates are not resolved. Conflicts for which `resolve` returns `None`
+/// stay unresolved and are marked in [`MergedFile::text`].
+pub fn merge_files(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    store: Option<&MetadataStore>,
+    mut resolve: impl FnMut(&MergeConflict) -> Option<Side>,
+) -> Result<MergedFile> {
+    let configs = [parse(base)?, parse(ours)?, parse(theirs)?];
+    let keys = match store {
+        Some(store) => Keys::from_store(&configs, store)?,
+        None => Keys::default(),
+    };
+    let trees = [
+        Tree::new(&configs[0], &keys),
+        Tree::new(&configs[1], &keys),
+        Tree::new(&configs[2], &keys),
+    ];
+    let merged = merge_trees(&trees, &mut resolve)?;
+    let text = text::render([base, ours, theirs], &configs, &trees, &merged)?;
+    Ok(MergedFile {
+        text,
+        merge: merged.merge,
+    })
+}
+
+/// Keys matching requirements across the sides of a merge
+#[derive(Default)]
+struct Keys {
+    /// UUID of every summary with metadata
+    uuids: HashMap<String, String>,
+}
+
+impl Keys {
+    /// Look up every requirement by its name, summary, or previous summary
+    fn from_store(configs: &[RequirementConfig; 3], store: &MetadataStore) -> Result<Self> {
+        let mut uuids = HashMap::new();
+        for config in configs {
+            for req in config.all_requirements() {
+                if uuids.contains_key(&req.summary) {
+                    continue;
+                }
+                let mut meta = match &req.name {
+                    Some(name) => store.find_by_generated_id(name)?,
+                    None => None,
+                };
+                if meta.is_none() {
+                    meta = store.find_metadata(&req.summary)?;
+                }
+                if let (None, Some(from)) = (&meta, &req.renamed_from) {
+                    meta = store.find_metadata(from)?;
+                }
+                if let Some(meta) = meta {
+                    uuids.insert(req.summary.clone(), meta.uuid.to_string());
+                }
+            }
+        }
+        Ok(Self { uuids })
+    }
+
+    fn key(&self, summary: &str) -> String {
+        self.uuids
+            .get(summary)
+            .cloned()
+            .unwrap_or_else(|| summary.to_string())
+    }
+}
+
+/// A merge and where its requirements ended up
+struct Merged {
+    merge: Merge,
+
+    /// Keys of the merged top-level requirements
+    roots: Vec<String>,
+
+    /// Key of the requirement of each unresolved conflict, `None` for
+    /// top-level fields
+    conflict_keys: Vec<Option<String>>,
+}
+
+fn merge_trees(
+    [base, ours, theirs]: &[Tree; 3],
+    resolve: &mut dyn FnMut(&MergeConflict) -> Option<Side>,
+) -> Result<Merged> {
+    let mut merger = Merger {
+        resolve,
+        conflicts: vec![],
+        conflict_keys: vec![],
+    };
+
+    let top = merger.merge_fields(None, &base.top, &ours.top, &theirs.top);
+
+    let mut keys: Vec<&String> = vec![];
+    let mut seen = HashSet::new();
+    for key in ours.order.iter().chain(&theirs.order).chain(&base.order) {
+        if seen.insert(key) {
+            keys.push(key);
+        }
+    }
+
+    // Summary of a requirement, as ours has it
+    let summary_of = |key: &str| -> String {
+        [ours, theirs, base]
+            .into_iter()
+            .find_map(|tree| tree.nodes.get(key)?.fields.get("summary")?.as_str())
+            .unwrap_or(key)
+            .to_string()
+    };
+    let empty = Node::default();
+    let mut nodes: HashMap<&str, Node> = HashMap::new();
+    for key in &keys {
+        let (b, o, t) = (
+            base.nodes.get(*key),
+            ours.nodes.get(*key),
+            theirs.nodes.get(*key),
+        );
+        let summary = summary_of(key);
+        let node = match (o, t) {
+            (Some(o), Some(t)) => {
+                let b = b.unwrap_or(&empty);
+                let parent = match merge3(&b.parent, &o.parent, &t.parent) {
+                    Some(parent) => parent.clone(),
+                    None => {
+                        let parent = |node: &Node| match node.parent.as_deref() {
+                            Some(parent) => Value::String(summary_of(parent)),
+                            None => Value::Null,
+                        };
+                        let side = merger.conflict(
+                            Some(key),
+                            MergeConflict {
+                                requirement: Some(summary.clone()),
+                                field: Some("parent".to_string()),
+                                base: parent(b),
+                                ours: parent(o),
+                                theirs: parent(t),
+                            },
+                        );
+                        pick(side, b, o, t).parent.clone()
+                    }
+                };
+                Some(Node {
+                    fields: merger.merge_fields(
+                        Some((key, &summary)),
+                        &b.fields,
+                        &o.fields,
+                        &t.fields,
+                    ),
+                    parent,
+                    children: merge_lists(&b.children, &o.children, &t.children),
+                })
+            }
+            (Some(changed), None) | (None, Some(changed)) => match b {
+                None => Some(changed.clone()),
+                Some(b) if b == changed => None,
+                Some(b) => {
+                    let json = |node: Option<&Node>| match node {
+                        Some(node) => Value::Object(node.fields.clone()),
+                        None => Value::Null,
+                    };
+                    let side = merger.conflict(
+                        Some(key),
+                        MergeConflict {
+                            requirement: Some(summary),
+                            field: None,
+                            base: json(Some(b)),
+                            ours: json(o),
+                            theirs: json(t),
+                        },
+                    );
+                    match side {
+                        Side::Base => Some(b.clone()),
+                        Side::Ours => o.cloned(),
+                        Side::Theirs => t.cloned(),
+                    }
+                }
+            },
+            (None, None) => None,
+        };
+        if let Some(node) = node {
+            nodes.insert(key.as_str(), node);
+        }
+    }
+
+    // Children of deleted parents move to the top level
+    let kept: HashSet<String> = nodes.keys().map(|s| s.to_string()).collect();
+    for node in nodes.values_mut() {
+        if node.parent.as_ref().is_some_and(|p| !kept.contains(p)) {
+            node.parent = None;
+        }
+    }
+
+    let mut builder = Builder {
+        nodes: &nodes,
+        order: &keys,
+        built: HashSet::new(),
+    };
+    let mut requirements = vec![];
+    let mut roots = vec![];
+    for entry in builder.children(None, merge_lists(&base.roots, &ours.roots, &theirs.roots)) {
+        if let Some(req) = builder.build(&entry) {
+            requirements.push(req);
+            roots.push(entry.key);
+        }
+    }
+    // Requirements whose merged parents form a cycle are not reachable
+    for key in &keys {
+        if nodes.contains_key(key.as_str()) && !builder.built.contains(key.as_str()) {
+            let entry = Entry {
+                key: key.to_string(),
+                reference: false,
+            };
+            if let Some(req) = builder.build(&entry) {
+                requirements.push(req);
+                roots.push(entry.key);
+            }
+        }
+    }
+
+    let mut config = top;
+    config.insert("requirements".to_string(), Value::Array(requirements));
+    let config = serde_json::from_value(Value::Object(config))
+        .map_err(|e| Error::SchemaValidation(e.to_string()))?;
+    Ok(Merged {
+        merge: Merge {
+            config,
+            conflicts: merger.conflicts,
+        },
+        roots,
+        conflict_keys: merger.conflict_keys,
+    })
+}
+
+/// The merged value if at most one side changed it, or both the same way
+fn merge3<'a, T: PartialEq>(base: &'a T, ours: &'a T, theirs: &'a T) -> Option<&'a T> {
+    if ours == theirs || theirs == base {
+        Some(ours)
+    } else if ours == base {
+        Some(theirs)
+    } else {
+        None
+    }
+}
+
+fn pick<T>(side: Side, base: T, ours: T, theirs: T) -> T {
+    match side {
+        Side::Base => base,
+        Side::Ours => ours,
+        Side::Theirs => theirs,
+    }
+}
+
+/// Merge child lists: ours without the entries theirs removed, plus the
+/// entries theirs added
+fn merge_lists(base: &[Entry], ours: &[Entry], theirs: &[Entry]) -> Vec<Entry> {
+    let mut merged: Vec<Entry> = ours
+        .iter()
+        .filter(|entry| theirs.contains(entry) || !base.contains(entry))
+        .cloned()
+        .collect();
+    let mut position = 0;
+    for entry in theirs {
+        match merged.iter().position(|merged| merged == entry) {
+            Some(index) => position = index + 1,
+            None if !base.contains(entry) => {
+                merged.insert(position, entry.clone());
+                position += 1;
+            }
+            None => {}
+        }
+    }
+    merged
+}
+
+/// A child in a `requirements:` list
+#[derive(Debug, Clone, PartialEq)]
+struct Entry {
+    /// Key of an inline child, or the summary a reference names
+    key: String,
+
+    /// Whether the child is a reference by summary rather than defined inline
+    reference: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Node {
+    fields: Map<String, Value>,
+
+    /// Key of the requirement defining this one inline
+    parent: Option<String>,
+
+    children: Vec<Entry>,
+}
+
+/// One side of a merge, flattened
+#[derive(Default)]
+struct Tree {
+    /// Top-level fields other than `requirements`
+    top: Map<String, Value>,
+
+    /// Requirements by key
+    nodes: HashMap<String, Node>,
+
+    /// Keys in document order
+    order: Vec<String>,
+
+    roots: Vec<Entry>,
+}
+
+impl Tree {
+    fn new(config: &RequirementConfig, keys: &Keys) -> Self {
+        let mut tree = Tree::default();
+        if let Ok(Value::Object(mut top)) = serde_json::to_value(config) {
+            top.remove("requirements");
+            tree.top = top;
+        }
+        for req in &config.requirements {
+            let entry = tree.add(req, None, keys);
+            tree.roots.push(entry);
+        }
+        tree
+    }
+
+    fn add(&mut self, req: &Requirement, parent: Option<&str>, keys: &Keys) -> Entry {
+        let key = keys.key(&req.summary);
+        self.order.push(key.clone());
+        let mut children = vec![];
+        for child in &req.requirements {
+            children.push(match child {
+                RequirementReference::Full(child) => self.add(child, Some(&key), keys),
+                RequirementReference::Reference(summary) => Entry {
+                    key: summary.clone(),
+                    reference: true,
+                },
+            });
+        }
+        self.nodes.insert(
+            key.clone(),
+            Node {
+                fields: fields(req),
+                parent: parent.map(str::to_string),
+                children,
+            },
+        );
+        Entry {
+            key,
+            reference: false,
+        }
+    }
+
+    /// Key of the top-level requirement containing the one with `key`
+    fn root_of<'a>(&'a self, mut key: &'a str) -> Option<&'a str> {
+        let mut seen = HashSet::new();
+        loop {
+            let node = self.nodes.get(key)?;
+            match node.parent.as_deref() {
+                Some(parent) if seen.insert(parent) => key = parent,
+                Some(_) => return None,
+                None => return Some(key),
+            }
+        }
+    }
+}
+
+struct Merger<'a> {
+    resolve: &'a mut dyn FnMut(&MergeConflict) -> Option<Side>,
+    conflicts: Vec<MergeConflict>,
+    conflict_keys: Vec<Option<String>>,
+}
+
+impl Merger<'_> {
+    /// Resolve a conflict about the requirement with `key`, keeping ours if
+    /// it stays unresolved
+    fn conflict(&mut self, key: Option<&str>, conflict: MergeConflict) -> Side {
+        match (self.resolve)(&conflict) {
+            Some(side) => side,
+            None => {
+                self.conflicts.push(conflict);
+                self.conflict_keys.push(key.map(str::to_string));
+                Side::Ours
+            }
+        }
+    }
+
+    /// Merge the fields of the requirement with the given key and summary,
+    /// or the top-level fields
+    fn merge_fields(
+        &mut self,
+        requirement: Option<(&str, &str)>,
+        base: &Map<String, Value>,
+        ours: &Map<String, Value>,
+        theirs: &Map<String, Value>,
+    ) -> Map<String, Value> {
+        let mut keys: Vec<&String> = ours.keys().collect();
+        for key in theirs.keys().chain(base.keys()) {
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+
+        let mut merged = Map::new();
+        for key in keys {
+            let get = |map: &Map<String, Value>| map.get(key).cloned().unwrap_or(Value::Null);
+            let (b, o, t) = (get(base), get(ours), get(theirs));
+            let value = match merge3(&b, &o, &t) {
+                Some(value) => value.clone(),
+                None => {
+                    let side = self.conflict(
+                        requirement.map(|(key, _)| key),
+                        MergeConflict {
+                            requirement: requirement.map(|(_, summary)| summary.to_string()),
+                            field: Some(key.clone()),
+                            base: b.clone(),
+                            ours: o.clone(),
+                            theirs: t.clone(),
+                        },
+                    );
+                    pick(side, b, o, t)
+                }
+            };
+            if !value.is_null() {
+                merged.insert(key.clone(), value);
+            }
+        }
+        merged
+    }
+}
+
+/// Assembles merged nodes back into nested requirements
+struct Builder<'a> {
+    nodes: &'a HashMap<&'a str, Node>,
+    order: &'a [&'a String],
+    built: HashSet<&'a str>,
+}
+
+impl<'a> Builder<'a> {
+    /// Merged children of `owner`: inline children whose merged parent is
+    /// `owner`, appending any the merged list misses, and references
+    fn children(&self, owner: Option<&str>, merged: Vec<Entry>) -> Vec<Entry> {
+        let is_child = |key: &str| {
+            self.nodes
+                .get(key)
+                .is_some_and(|node| node.parent.as_deref() == owner)
+        };
+        let mut children: Vec<Entry> = merged
+            .into_iter()
+            .filter(|entry| entry.reference || is_child(&entry.key))
+            .collect();
+        for key in self.order {
+            let listed = children
+                .iter()
+                .any(|entry| !entry.reference && entry.key == **key);
+            if is_child(key) && !listed {
+                children.push(Entry {
+                    key: key.to_string(),
+                    reference: false,
+                });
+            }
+        }
+        children
+    }
+
+    fn build(&mut self, entry: &Entry) -> Option<Value> {
+        if entry.reference {
+            return Some(Value::String(entry.key.clone()));
+        }
+        let nodes = self.nodes;
+        let (key, node) = nodes.get_key_value(entry.key.as_str())?;
+        if !self.built.insert(key) {
+            return None;
+        }
+        let mut fields = node.fields.clone();
+        let children: Vec<Value> = self
+            .children(Some(key), node.children.clone())
+            .iter()
+            .filter_map(|child| self.build(child))
+            .collect();
+        if !children.is_empty() {
+            fields.insert("requirements".to_string(), Value::Array(children));
+        }
+        Some(Value::Object(fields))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Status;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const BASE: &str = r#"version: "1.0"
+requirements:
+  - summary: Login
+    status: draft
+    requirements:
+      - summary: Password policy
+      - summary: Lockout
+  - summary: Export
+    description: CSV export
+"#;
+
+    fn summaries(config: &RequirementConfig) -> Vec<&str> {
+        config
+            .all_requirements()
+            .iter()
+            .map(|req| req.summary.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_non_conflicting_changes_merge() {
+        let ours = BASE
+            .replace("status: draft", "status: approved")
+            .replace("      - summary: Lockout\n", "");
+        let theirs = BASE.replace("CSV export", "CSV and JSON export") + "  - summary: Audit\n";
+        let merge = merge_str(BASE, &ours, &theirs).unwrap();
+
+        assert!(merge.is_clean());
+        assert_eq!(
+            summaries(&merge.config),
+            vec!["Login", "Password policy", "Export", "Audit"]
+        );
+        assert_eq!(merge.config.requirements[0].status, Some(Status::Approved));
+        assert_eq!(
+            merge.config.requirements[1].description.as_deref(),
+            Some("CSV and JSON export")
+        );
+    }
+
+    #[test]
+    fn test_move_and_edit_merge() {
+        // Ours moves Lockout to the top level, theirs describes it
+        let ours = BASE.replace("      - summary: Lockout\n", "") + "  - summary: Lockout\n";
+        let theirs = BASE.replace(
+            "      - summary: Lockout\n",
+            "      - summary: Lockout\n        description: After 5 attempts\n",
+        );
+        let merge = merge_str(BASE, &ours, &theirs).unwrap();
+
+        assert!(merge.is_clean());
+        assert_eq!(merge.config.requirements.len(), 3);
+        assert_eq!(merge.config.requirements[0].requirements.len(), 1);
+        assert_eq!(
+            merge.config.requirements[2].description.as_deref(),
+            Some("After 5 attempts")
+        );
+    }
+
+    #[test]
+    fn test_conflicts_are_reported_and_resolved() {
+        let ours = BASE.replace("status: draft", "status: approved");
+        let theirs = BASE
+            .replace("status: draft", "status: deprecated")
+            .replace("  - summary: Export\n    description: CSV export\n", "");
+        let ours = ours.replace("CSV export", "CSV export with headers");
+
+        let merge = merge_str(BASE, &ours, &theirs).unwrap();
+        assert_eq!(merge.conflicts.len(), 2);
+        assert_eq!(merge.conflicts[0].requirement.as_deref(), Some("Login"));
+        assert_eq!(merge.conflicts[0].field.as_deref(), Some("status"));
+        assert_eq!(merge.conflicts[0].theirs, Value::from("deprecated"));
+        assert_eq!(merge.conflicts[1].requirement.as_deref(), Some("Export"));
+        assert_eq!(merge.conflicts[1].field, None);
+        assert_eq!(merge.conflicts[1].theirs, Value::Null);
+        // Unresolved conflicts keep ours
+        assert_eq!(merge.config.requirements[0].status, Some(Status::Approved));
+        assert_eq!(merge.config.requirements.len(), 2);
+
+        let parse = |content: &str| serde_yaml::from_str(content).unwrap();
+        let resolved = merge_with(&parse(BASE), &parse(&ours), &parse(&theirs), |_| {
+            Some(Side::Theirs)
+        })
+        .unwrap();
+        assert!(resolved.is_clean());
+        assert_eq!(resolved.config.requirements.len(), 1);
+        assert_eq!(
+            resolved.config.requirements[0].status,
+            Some(Status::Deprecated)
+        );
+        Parser::parse_str(&Parser::to_yaml(&resolved.config).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_merged_file_keeps_untouched_text() {
+        let base = "# Product requirements\nversion: \"1.0\"\nrequirements:\n  # Sign-in flow\n  - summary: Login\n    status: draft   # until review\n  - summary: Export\n    description: >-\n      CSV export\n";
+        let ours = base.replace(
+            "status: draft   # until review",
+            "status: approved  # reviewed",
+        );
+        let theirs = base.replace(
+            "      CSV export\n",
+            "      CSV export\n  - summary: Audit\n",
+        );
+        let merged = merge_files(base, &ours, &theirs, None, |_| None).unwrap();
+
+        assert!(merged.merge.is_clean());
+        assert_eq!(
+            merged.text,
+            ours.clone() + "  - summary: Audit\n",
+            "untouched requirements keep their comments and formatting"
+        );
+        assert_eq!(merged.merge.config, parse(&merged.text).unwrap());
+
+        // Nothing changed on theirs: ours byte for byte
+        assert_eq!(
+            merge_files(base, &ours, base, None, |_| None).unwrap().text,
+            ours
+        );
+    }
+
+    #[test]
+    fn test_conflicts_are_marked() {
+        let ours = BASE.replace("status: draft", "status: approved");
+        let theirs = BASE.replace("status: draft", "status: deprecated");
+        let merged = merge_files(BASE, &ours, &theirs, None, |_| None).unwrap();
+
+        assert_eq!(merged.merge.conflicts.len(), 1);
+        assert!(merged
+            .text
+            .contains("<<<<<<< ours\n  - summary: Login\n    status: approved\n"));
+        assert!(merged
+            .text
+            .contains("=======\n  - summary: Login\n    status: deprecated\n"));
+        assert!(merged
+            .text
+            .contains(">>>>>>> theirs\n  - summary: Export\n    description: CSV export\n"));
+        assert!(parse(&merged.text).is_err());
+    }
+
+    #[test]
+    fn test_requirements_match_by_uuid() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        for req in parse(BASE).unwrap().all_requirements() {
+            store.get_or_create_metadata(req).unwrap();
+        }
+
+        // Ours renames Export, theirs describes it differently
+        let ours = BASE.replace(
+            "  - summary: Export\n",
+            "  - summary: CSV export\n    renamed_from: Export\n",
+        );
+        let theirs = BASE.replace("CSV export", "CSV and JSON export");
+        let merged = merge_files(BASE, &ours, &theirs, Some(&store), |_| None).unwrap();
+
+        assert!(merged.merge.is_clean());
+        let export = &merged.merge.config.requirements[1];
+        assert_eq!(export.summary, "CSV export");
+        assert_eq!(export.description.as_deref(), Some("CSV and JSON export"));
+        assert_eq!(merged.merge.config.requirements.len(), 2);
+
+        // By summary, the rename is a deletion the other side conflicts with
+        let by_summary = merge_files(BASE, &ours, &theirs, None, |_| None).unwrap();
+        assert!(!by_summary.merge.is_clean());
+    }
+}
@@ -0,0 +1,250 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Writing a merge back as text
+//!
+//! Each side's file is split into the text before its top-level
+//! requirements, one block per top-level requirement, and the text after
+//! them. The merged file reuses those blocks wherever the merge left a
+//! requirement as one side has it.
+
+use super::{Merged, Tree};
+use crate::types::{Requirement, RequirementConfig};
+use crate::{Parser, Result};
+
+/// Length of git's conflict markers
+const MARKER: usize = 7;
+
+/// A requirements file split around its top-level requirements
+struct Layout<'a> {
+    /// Everything up to the first requirement, including `requirements:`
+    head: &'a str,
+
+    /// The text of each top-level requirement, with the comments after it
+    items: Vec<&'a str>,
+
+    /// Everything after the requirements
+    tail: &'a str,
+
+    /// Indentation of the `-` of each requirement
+    indent: &'a str,
+}
+
+impl<'a> Layout<'a> {
+    /// Split a file whose top-level `requirements:` is a block sequence
+    /// of `count` items
+    fn new(source: &'a str, count: usize) -> Option<Self> {
+        let mut lines = vec![];
+        let mut offset = 0;
+        for line in source.split_inclusive('\n') {
+            lines.push((offset, line));
+            offset += line.len();
+        }
+
+        let list = lines.iter().position(|(_, line)| {
+            line.strip_prefix("requirements:")
+                .map(str::trim)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('#'))
+        })?;
+        let is_content = |line: &str| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        };
+        let first = (list + 1..lines.len()).find(|&i| is_content(lines[i].1))?;
+        let first_line = lines[first].1;
+        let indent = &first_line[..first_line.len() - first_line.trim_start_matches(' ').len()];
+        let is_item = |line: &str| {
+            line.strip_prefix(indent)
+                .is_some_and(|rest| rest.trim_end() == "-" || rest.starts_with("- "))
+        };
+        if !is_item(first_line) {
+            return None;
+        }
+
+        let mut starts = vec![];
+        let mut end = source.len();
+        for &(start, line) in &lines[first..] {
+            if is_item(line) {
+                starts.push(start);
+            } else if is_content(line) {
+                let width = line.len() - line.trim_start_matches(' ').len();
+                if width <= indent.len() {
+                    end = start;
+                    break;
+                }
+            }
+        }
+        if starts.len() != count {
+            return None;
+        }
+        let items = starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| &source[start..starts.get(i + 1).copied().unwrap_or(end)])
+            .collect();
+        Some(Self {
+            head: &source[..starts[0]],
+            items,
+            tail: &source[end..],
+            indent,
+        })
+    }
+}
+
+/// Render the merged file, falling back to serializing it in full when
+/// ours cannot be split or a clean result does not read back as merged
+pub(super) fn render(
+    sources: [&str; 3],
+    configs: &[RequirementConfig; 3],
+    trees: &[Tree; 3],
+    merged: &Merged,
+) -> Result<String> {
+    let merge = &merged.merge;
+    let Some(ours) = Layout::new(sources[1], configs[1].requirements.len()) else {
+        return full(merged);
+    };
+    if merge.config.requirements.is_empty() {
+        return full(merged);
+    }
+    // Blocks of theirs are only reused at the same indentation
+    let theirs = Layout::new(sources[2], configs[2].requirements.len())
+        .filter(|theirs| theirs.indent == ours.indent);
+    let sides = [(1, Some(&ours)), (2, theirs.as_ref())];
+    let block = |side: usize, key: &str| -> Result<String> {
+        let Some(index) = trees[side].roots.iter().position(|root| root.key == key) else {
+            return Ok(String::new());
+        };
+        match sides[side - 1].1 {
+            Some(layout) => Ok(layout.items[index].to_string()),
+            None => item(&configs[side].requirements[index], ours.indent),
+        }
+    };
+
+    let mut top_conflict = false;
+    let mut conflicted: Vec<&str> = vec![];
+    for key in &merged.conflict_keys {
+        match key {
+            None => top_conflict = true,
+            Some(key) => {
+                let root = trees[1].root_of(key).or_else(|| trees[2].root_of(key));
+                if let Some(root) = root.filter(|root| !conflicted.contains(root)) {
+                    conflicted.push(root);
+                }
+            }
+        }
+    }
+
+    let mut text = String::new();
+    let top = top_fields(&merge.config);
+    let tail = if top_conflict {
+        let theirs_head = match &theirs {
+            Some(theirs) => theirs.head.to_string(),
+            None => head(&configs[2])?,
+        };
+        text.push_str(&conflict(ours.head, &theirs_head));
+        ours.tail
+    } else if top == trees[1].top {
+        text.push_str(ours.head);
+        ours.tail
+    } else if let Some(theirs) = theirs.as_ref().filter(|_| top == trees[2].top) {
+        text.push_str(theirs.head);
+        theirs.tail
+    } else {
+        text.push_str(&head(&merge.config)?);
+        ""
+    };
+
+    let mut marked = vec![];
+    for (req, key) in merge.config.requirements.iter().zip(&merged.roots) {
+        if conflicted.contains(&key.as_str()) {
+            text.push_str(&conflict(&block(1, key)?, &block(2, key)?));
+            marked.push(key.as_str());
+            continue;
+        }
+        let unchanged = sides.iter().find_map(|&(side, layout)| {
+            let index = trees[side].roots.iter().position(|root| &root.key == key)?;
+            (configs[side].requirements[index] == *req).then_some(layout?.items[index])
+        });
+        match unchanged {
+            Some(block) => push_block(&mut text, block),
+            None => text.push_str(&item(req, ours.indent)?),
+        }
+    }
+    // Requirements deleted by ours but changed by theirs
+    for key in conflicted.iter().filter(|key| !marked.contains(key)) {
+        text.push_str(&conflict(&block(1, key)?, &block(2, key)?));
+    }
+    text.push_str(tail);
+
+    if merge.is_clean() && super::parse(&text).ok().as_ref() != Some(&merge.config) {
+        return full(merged);
+    }
+    Ok(text)
+}
+
+/// The merged file serialized in full
+fn full(merged: &Merged) -> Result<String> {
+    Parser::to_yaml(&merged.merge.config)
+}
+
+/// Top-level fields other than `requirements`, as in [`Tree::top`]
+fn top_fields(config: &RequirementConfig) -> serde_json::Map<String, serde_json::Value> {
+    match serde_json::to_value(config) {
+        Ok(serde_json::Value::Object(mut top)) => {
+            top.remove("requirements");
+            top
+        }
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// The top-level fields of `config` followed by `requirements:`
+fn head(config: &RequirementConfig) -> Result<String> {
+    let mut value = serde_yaml::to_value(config)?;
+    if let serde_yaml::Value::Mapping(map) = &mut value {
+        map.remove("requirements");
+    }
+    let mut text = serde_yaml::to_string(&value)?;
+    if text.trim() == "{}" {
+        text.clear();
+    }
+    text.push_str("requirements:\n");
+    Ok(text)
+}
+
+fn push_block(text: &mut String, block: &str) {
+    text.push_str(block);
+    if !block.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// Both sides of a conflict between git's conflict markers
+fn conflict(ours: &str, theirs: &str) -> String {
+    let mut text = format!("{} ours\n", "<".repeat(MARKER));
+    if !ours.is_empty() {
+        push_block(&mut text, ours);
+    }
+    text.push_str(&format!("{}\n", "=".repeat(MARKER)));
+    if !theirs.is_empty() {
+        push_block(&mut text, theirs);
+    }
+    text.push_str(&format!("{} theirs\n", ">".repeat(MARKER)));
+    text
+}
+
+/// A requirement as an item of a block sequence indented by `indent`
+fn item(req: &Requirement, indent: &str) -> Result<String> {
+    let yaml = serde_yaml::to_string(req)?;
+    let mut text = String::new();
+    for (i, line) in yaml.lines().enumerate() {
+        if !line.is_empty() {
+            text.push_str(indent);
+            text.push_str(if i == 0 { "- " } else { "  " });
+            text.push_str(line);
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
@@ -4,8 +4,10 @@
 //! including UUID generation, ID assignment, and tracking changes.
 
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
@@ -13,8 +15,44 @@ use uuid::Uuid;
 use crate::error::Error;
 use crate::types::Requirement;
 
+mod append;
+mod atomic;
+mod backend;
+pub(crate) mod canonical;
+mod history;
+mod ids;
+mod jsonl;
+mod prune;
+mod ranges;
+mod rename;
+mod shared;
+mod snapshot;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod writeback;
+
+pub use append::AddedRequirement;
+pub use atomic::write_atomic;
+pub use atomic::StoreLock;
+pub use backend::StorageBackend;
+use backend::{FileBackend, MetadataBackend};
+pub use canonical::{canonical_json, content_hash, signature};
+pub use history::HistoryEntry;
+pub use ids::IdScheme;
+use jsonl::JsonlBackend;
+pub use prune::{PruneAction, PruneReport};
+pub use ranges::IdRange;
+pub use rename::{Rename, RenameMatch, RENAME_THRESHOLD};
+pub use shared::SharedMetadataStore;
+pub use snapshot::Snapshot;
+#[cfg(feature = "sqlite")]
+use sqlite::SqliteBackend;
+#[cfg(feature = "git")]
+pub(crate) use writeback::requirement_lines;
+pub use writeback::{write_statuses, FileSync};
+
 /// Metadata for a single requirement
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct RequirementMetadata {
     /// Stable UUID for tracking across refactors
     pub uuid: Uuid,
@@ -22,8 +60,10 @@ pub struct RequirementMetadata {
     /// Auto-generated ID (e.g., "RQM-001")
     pub generated_id: String,
 
-    /// Hash of the summary for change detection
-    pub summary_hash: String,
+    /// [`content_hash`] of the requirement when last seen, for change
+    /// detection
+    #[serde(alias = "summary_hash")]
+    pub content_hash: String,
 
     /// When this requirement was first created
     pub created_at: DateTime<Utc>,
@@ -43,6 +83,39 @@ pub struct ProjectConfig {
 
     /// Next sequential ID number
     pub next_id: u32,
+
+    /// Format of generated IDs
+    #[serde(default, skip_serializing_if = "IdScheme::is_default")]
+    pub ids: IdScheme,
+
+    /// Next sequence numbers of ID categories and hierarchical parents
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub counters: BTreeMap<String, u32>,
+
+    /// Blocks of sequence numbers reserved for teams
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub id_ranges: Vec<IdRange>,
+
+    /// Registry of known hierarchical tags
+    #[serde(default, skip_serializing_if = "TagRegistry::is_empty")]
+    pub tags: TagRegistry,
+
+    /// Planned releases, in chronological order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub releases: Vec<Release>,
+
+    /// Where requirement metadata is stored
+    #[serde(default, skip_serializing_if = "StorageBackend::is_default")]
+    pub storage: StorageBackend,
+
+    /// URL templates of external systems by name, with `{id}` standing for
+    /// the ID in `external_refs`, e.g. `https://example.atlassian.net/browse/{id}`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub external_systems: BTreeMap<String, String>,
+
+    /// Languages every requirement must be translated to, e.g. `de`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locales: Vec<String>,
 }
 
 impl ProjectConfig {
@@ -51,55 +124,182 @@ impl ProjectConfig {
         Self {
             project_prefix: prefix,
             next_id: 1,
+            ids: IdScheme::default(),
+            counters: BTreeMap::new(),
+            id_ranges: Vec::new(),
+            tags: TagRegistry::default(),
+            releases: Vec::new(),
+            storage: StorageBackend::default(),
+            external_systems: BTreeMap::new(),
+            locales: Vec::new(),
         }
     }
 
     /// Generate the next ID and increment the counter
     pub fn next_id(&mut self) -> String {
-        let id = format!("{}-{:03}", self.project_prefix, self.next_id);
+        let id = self.ids.format(&self.project_prefix, None, self.next_id);
         self.next_id += 1;
         id
     }
 }
 
+/// Project-level registry of hierarchical tags (e.g., `safety/electrical/hv`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TagRegistry {
+    /// Reject tags that are not registered
+    #[serde(default)]
+    pub strict: bool,
+
+    /// Registered tags; every parent namespace of a registered tag is implicitly known
+    #[serde(default)]
+    pub registered: Vec<String>,
+}
+
+impl TagRegistry {
+    /// Check whether the registry has no configuration
+    pub fn is_empty(&self) -> bool {
+        !self.strict && self.registered.is_empty()
+    }
+
+    /// Check whether a tag is registered, either directly or as a parent namespace
+    pub fn is_known(&self, tag: &str) -> bool {
+        self.registered
+            .iter()
+            .any(|registered| tag_has_prefix(registered, tag))
+    }
+}
+
+/// Check whether a tag is well-formed (non-empty `/`-separated segments)
+pub fn is_valid_tag(tag: &str) -> bool {
+    !tag.is_empty()
+        && tag
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment.trim() == segment)
+}
+
+/// Check whether `tag` equals `prefix` or lives underneath it in the tag hierarchy
+pub fn tag_has_prefix(tag: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    tag == prefix || (tag.starts_with(prefix) && tag[prefix.len()..].starts_with('/'))
+}
+
+/// A planned release in the project's release registry
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Release {
+    /// Release name as used in `target_release` (e.g., "2.0")
+    pub name: String,
+
+    /// Planned release date
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+/// Order two release names
+///
+/// With a registry, releases are ordered by their position in it and unknown
+/// names are incomparable. Without one, dotted numeric versions are compared.
+pub fn compare_releases(releases: &[Release], a: &str, b: &str) -> Option<Ordering> {
+    if !releases.is_empty() {
+        let rank = |name: &str| releases.iter().position(|r| r.name == name);
+        return Some(rank(a)?.cmp(&rank(b)?));
+    }
+
+    let parse = |name: &str| -> Option<Vec<u64>> {
+        name.trim_start_matches('v')
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    };
+    Some(parse(a)?.cmp(&parse(b)?))
+}
+
 /// Metadata store for managing requirement metadata
+///
+/// Files are replaced atomically, and creating metadata holds a lock on the
+/// `.rqm` directory so concurrent processes never assign the same ID.
+///
+/// Metadata lives in one file per requirement unless the project config
+/// selects another [`StorageBackend`]; existing per-file metadata is then
+/// migrated into it when the store is opened.
 pub struct MetadataStore {
-    metadata_dir: PathBuf,
+    backend: Box<dyn MetadataBackend>,
     config_path: PathBuf,
+    lock_path: PathBuf,
     metadata_cache: HashMap<String, RequirementMetadata>,
     project_config: ProjectConfig,
+    team: Option<String>,
 }
 
 impl MetadataStore {
     /// Create a new metadata store
     pub fn new<P: AsRef<Path>>(rqm_dir: P) -> Result<Self, Error> {
         let rqm_path = rqm_dir.as_ref();
-        let metadata_dir = rqm_path.join(".metadata");
+        let metadata_dir = StorageBackend::Files.path(rqm_path);
         let config_path = rqm_path.join("config.yml");
-
-        // Load or create project config
-        let project_config = if config_path.exists() {
-            let content = fs::read_to_string(&config_path)?;
-            serde_yaml::from_str(&content).map_err(|e| Error::SchemaValidation(e.to_string()))?
-        } else {
-            ProjectConfig::new("REQ".to_string())
+        let lock_path = rqm_path.join(".lock");
+        let project_config = Self::load_config(&config_path)?;
+
+        let storage = project_config.storage;
+        let path = storage.path(rqm_path);
+        let mut backend: Box<dyn MetadataBackend> = match storage {
+            StorageBackend::Files => Box::new(FileBackend::open(path)?),
+            StorageBackend::Jsonl => Box::new(JsonlBackend::open(path)?),
+            #[cfg(feature = "sqlite")]
+            StorageBackend::Sqlite => Box::new(SqliteBackend::open(&path)?),
+            #[cfg(not(feature = "sqlite"))]
+            StorageBackend::Sqlite => {
+                return Err(Error::custom(
+                    "SQLite metadata storage needs rqm built with the `sqlite` feature",
+                ))
+            }
         };
 
-        // Create metadata directory if it doesn't exist
-        if !metadata_dir.exists() {
-            fs::create_dir_all(&metadata_dir)?;
+        // Move records left in the per-file layout into the configured backend
+        if storage != StorageBackend::Files && metadata_dir.is_dir() {
+            let _lock = StoreLock::acquire(&lock_path)?;
+            backend.refresh()?;
+            if metadata_dir.is_dir() {
+                backend.import(FileBackend::open(metadata_dir.clone())?.entries()?)?;
+                fs::remove_dir_all(&metadata_dir)?;
+            }
         }
 
         Ok(Self {
-            metadata_dir,
+            backend,
             config_path,
+            lock_path,
             metadata_cache: HashMap::new(),
             project_config,
+            team: None,
         })
     }
 
+    /// Open the store of `rqm_dir` if it is a project directory, with a
+    /// `config.yml` or metadata in any backend, or `None` otherwise
+    pub fn open<P: AsRef<Path>>(rqm_dir: P) -> Result<Option<Self>, Error> {
+        let rqm_path = rqm_dir.as_ref();
+        let config_path = rqm_path.join("config.yml");
+        let storage = Self::load_config(&config_path)?.storage;
+        let exists = config_path.is_file()
+            || [StorageBackend::Files, storage]
+                .iter()
+                .any(|backend| backend.path(rqm_path).exists());
+        if !exists {
+            return Ok(None);
+        }
+        Self::new(rqm_path).map(Some)
+    }
+
     /// Initialize a new project with the given prefix
     pub fn init<P: AsRef<Path>>(rqm_dir: P, prefix: String) -> Result<Self, Error> {
+        Self::init_with_config(rqm_dir, ProjectConfig::new(prefix))
+    }
+
+    /// Initialize a new .rqm directory with the given project configuration
+    pub fn init_with_config<P: AsRef<Path>>(
+        rqm_dir: P,
+        config: ProjectConfig,
+    ) -> Result<Self, Error> {
         let rqm_path = rqm_dir.as_ref();
 
         // Create .rqm directory if needed
@@ -108,77 +308,208 @@ impl MetadataStore {
         }
 
         let config_path = rqm_path.join("config.yml");
-        let config = ProjectConfig::new(prefix);
 
         // Write config
         let yaml =
             serde_yaml::to_string(&config).map_err(|e| Error::SchemaValidation(e.to_string()))?;
-        fs::write(&config_path, yaml)?;
+        write_atomic(&config_path, yaml.as_bytes())?;
 
         Self::new(rqm_dir)
     }
 
+    /// Load a project config, or the default one if the file does not exist
+    fn load_config(config_path: &Path) -> Result<ProjectConfig, Error> {
+        if !config_path.exists() {
+            return Ok(ProjectConfig::new("REQ".to_string()));
+        }
+        let content = fs::read_to_string(config_path)?;
+        serde_yaml::from_str(&content).map_err(|e| Error::SchemaValidation(e.to_string()))
+    }
+
+    /// Get the project configuration
+    pub fn project_config(&self) -> &ProjectConfig {
+        &self.project_config
+    }
+
     /// Save the project configuration
     pub fn save_config(&self) -> Result<(), Error> {
         let yaml = serde_yaml::to_string(&self.project_config)
             .map_err(|e| Error::SchemaValidation(e.to_string()))?;
-        fs::write(&self.config_path, yaml)?;
+        write_atomic(&self.config_path, yaml.as_bytes())?;
         Ok(())
     }
 
+    /// Drop the cached record of a kebab-case key
+    pub(crate) fn forget(&mut self, key: &str) {
+        self.metadata_cache.remove(key);
+    }
+
+    /// Drop all cached records and re-read the configuration and backend
+    pub fn reload(&mut self) -> Result<(), Error> {
+        self.metadata_cache.clear();
+        self.project_config = Self::load_config(&self.config_path)?;
+        self.backend.refresh()
+    }
+
+    /// Look up existing metadata for a summary without creating any
+    pub fn find_metadata(&self, summary: &str) -> Result<Option<RequirementMetadata>, Error> {
+        let kebab_id = kebab_case(summary);
+        if let Some(meta) = self.metadata_cache.get(&kebab_id) {
+            return Ok(Some(meta.clone()));
+        }
+
+        self.backend.get(&kebab_id)
+    }
+
+    /// All stored metadata, in key order
+    pub fn all_metadata(&self) -> Result<Vec<RequirementMetadata>, Error> {
+        Ok(self
+            .backend
+            .entries()?
+            .into_iter()
+            .map(|(_, meta)| meta)
+            .collect())
+    }
+
+    /// Look up metadata by its stable UUID
+    pub fn find_by_uuid(&self, uuid: &Uuid) -> Result<Option<RequirementMetadata>, Error> {
+        self.backend.find_by_uuid(uuid)
+    }
+
+    /// Look up metadata by generated ID, such as `REQ-042`
+    pub fn find_by_generated_id(&self, id: &str) -> Result<Option<RequirementMetadata>, Error> {
+        self.backend.find_by_generated_id(id)
+    }
+
+    /// Every recorded version of a requirement's metadata, oldest first
+    ///
+    /// Only the SQLite backend keeps history; the others return the current
+    /// version alone.
+    pub fn revisions(&self, uuid: &Uuid) -> Result<Vec<RequirementMetadata>, Error> {
+        self.backend.revisions(uuid)
+    }
+
     /// Get or create metadata for a requirement
     pub fn get_or_create_metadata(
         &mut self,
         req: &Requirement,
+    ) -> Result<RequirementMetadata, Error> {
+        self.get_or_create(req, None)
+    }
+
+    /// Get or create metadata for a requirement nested under `parent`
+    ///
+    /// With [`IdScheme::hierarchical`] IDs, a new requirement is numbered
+    /// after its parent's generated ID.
+    pub fn get_or_create_child_metadata(
+        &mut self,
+        req: &Requirement,
+        parent: &RequirementMetadata,
+    ) -> Result<RequirementMetadata, Error> {
+        self.get_or_create(req, Some(&parent.generated_id))
+    }
+
+    fn get_or_create(
+        &mut self,
+        req: &Requirement,
+        parent: Option<&str>,
     ) -> Result<RequirementMetadata, Error> {
         let kebab_id = kebab_case(&req.summary);
 
         // Check cache first
         if let Some(meta) = self.metadata_cache.get(&kebab_id) {
-            return Ok(meta.clone());
+            if meta.content_hash == content_hash(req) {
+                return Ok(meta.clone());
+            }
         }
 
         // Try to load from disk
-        let meta_path = self.metadata_dir.join(format!("{}.json", kebab_id));
-
-        if meta_path.exists() {
-            let content = fs::read_to_string(&meta_path)?;
-            let mut meta: RequirementMetadata = serde_json::from_str(&content)
-                .map_err(|e| Error::SchemaValidation(e.to_string()))?;
-
-            // Check if summary changed
-            let current_hash = hash_string(&req.summary);
-            if meta.summary_hash != current_hash {
-                meta.summary = req.summary.clone();
-                meta.summary_hash = current_hash;
-                meta.updated_at = Utc::now();
+        if let Some(meta) = self.backend.get(&kebab_id)? {
+            self.record_content(kebab_id, meta, req)
+        } else {
+            // Hold the lock from reading next_id until it is saved; the
+            // backend also holds off writers that do not share the lock file
+            let lock = StoreLock::acquire(&self.lock_path)?;
+            self.backend.begin()?;
+            let created = self.create(kebab_id, req, parent);
+            self.backend.finish(created.is_ok())?;
+            drop(lock);
+            match created? {
+                Some(meta) => Ok(meta),
+                // Another process created it meanwhile
+                None => self.get_or_create(req, parent),
             }
+        }
+    }
 
-            self.metadata_cache.insert(kebab_id, meta.clone());
-            Ok(meta)
-        } else {
-            // Create new metadata
-            let generated_id = self.project_config.next_id();
-            let meta = RequirementMetadata {
-                uuid: Uuid::new_v4(),
-                generated_id,
-                summary_hash: hash_string(&req.summary),
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-                summary: req.summary.clone(),
-            };
-
-            // Save to disk
-            let json = serde_json::to_string_pretty(&meta)
-                .map_err(|e| Error::SchemaValidation(e.to_string()))?;
-            fs::write(&meta_path, json)?;
-
-            // Update config with new next_id
-            self.save_config()?;
-
-            self.metadata_cache.insert(kebab_id, meta.clone());
-            Ok(meta)
+    /// Create metadata for a requirement not stored yet, or `None` if it
+    /// is stored by now; called within [`MetadataBackend::begin`]
+    fn create(
+        &mut self,
+        kebab_id: String,
+        req: &Requirement,
+        parent: Option<&str>,
+    ) -> Result<Option<RequirementMetadata>, Error> {
+        self.backend.refresh()?;
+        if self.backend.get(&kebab_id)?.is_some() {
+            return Ok(None);
+        }
+        if let Some(from) = &req.renamed_from {
+            let old_key = kebab_case(from);
+            if old_key != kebab_id && self.backend.get(&old_key)?.is_some() {
+                let meta = self.move_record(&old_key, &req.summary)?;
+                return self.record_content(kebab_id, meta, req).map(Some);
+            }
+        }
+        self.project_config = Self::load_config(&self.config_path)?;
+
+        // Create new metadata
+        let generated_id = match self.team.clone() {
+            Some(team) if self.project_config.uses_sequence(&req.tags, parent) => {
+                self.next_team_id(&team)?
+            }
+            _ => self.project_config.next_id_for(&req.tags, parent),
+        };
+        let meta = RequirementMetadata {
+            uuid: Uuid::new_v4(),
+            generated_id,
+            content_hash: content_hash(req),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            summary: req.summary.clone(),
+        };
+
+        // Persist the new next_id before the record, so that a failed write
+        // leaves a gap in the IDs rather than handing this one out twice
+        self.save_config()?;
+        self.backend.put(&kebab_id, &meta)?;
+
+        self.metadata_cache.insert(kebab_id, meta.clone());
+        Ok(Some(meta))
+    }
+
+    /// Store the current content hash of a requirement if it changed
+    ///
+    /// Hashes written by older versions are replaced without touching
+    /// `updated_at`, since they cannot tell whether the content changed.
+    fn record_content(
+        &mut self,
+        kebab_id: String,
+        mut meta: RequirementMetadata,
+        req: &Requirement,
+    ) -> Result<RequirementMetadata, Error> {
+        let current_hash = content_hash(req);
+        if meta.content_hash != current_hash {
+            if !is_legacy_hash(&meta.content_hash) {
+                meta.updated_at = Utc::now();
+            }
+            meta.summary = req.summary.clone();
+            meta.content_hash = current_hash;
+            self.backend.put(&kebab_id, &meta)?;
         }
+
+        self.metadata_cache.insert(kebab_id, meta.clone());
+        Ok(meta)
     }
 
     /// Get the generated ID for a requirement
@@ -188,6 +519,24 @@ impl MetadataStore {
     }
 }
 
+/// The ID shown for a requirement in exports and reports
+///
+/// This is the generated ID where `store` has metadata for the requirement,
+/// or else its `name`. Metadata is only looked up, never created, so that
+/// exporting never writes to `.rqm`.
+pub fn display_id(
+    store: Option<&MetadataStore>,
+    req: &Requirement,
+) -> Result<Option<String>, Error> {
+    let generated = match store {
+        Some(store) => store
+            .find_metadata(&req.summary)?
+            .map(|meta| meta.generated_id),
+        None => None,
+    };
+    Ok(generated.or_else(|| req.name.clone()))
+}
+
 /// Convert a string to kebab-case
 pub fn kebab_case(s: &str) -> String {
     s.to_lowercase()
@@ -200,14 +549,14 @@ pub fn kebab_case(s: &str) -> String {
         .join("-")
 }
 
-/// Simple hash function for change detection
+/// Stable hash of a string
 fn hash_string(s: &str) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
+    canonical::sha256_hex(s)
+}
 
-    let mut hasher = DefaultHasher::new();
-    s.hash(&mut hasher);
-    format!("{:x}", hasher.finish())
+/// Whether a stored hash predates SHA-256 content hashes
+fn is_legacy_hash(hash: &str) -> bool {
+    hash.len() != 64
 }
 
 #[cfg(test)]
@@ -234,6 +583,79 @@ mod tests {
         assert_eq!(config.next_id(), "TEST-003");
     }
 
+    #[test]
+    fn test_tag_has_prefix() {
+        assert!(tag_has_prefix("safety/electrical/hv", "safety"));
+        assert!(tag_has_prefix("safety/electrical/hv", "safety/electrical/"));
+        assert!(tag_has_prefix("safety", "safety"));
+        assert!(!tag_has_prefix("safetynet", "safety"));
+        assert!(!tag_has_prefix("safety", "safety/electrical"));
+    }
+
+    #[test]
+    fn test_tag_registry_is_known() {
+        let registry = TagRegistry {
+            strict: true,
+            registered: vec!["safety/electrical/hv".to_string(), "perf".to_string()],
+        };
+        assert!(registry.is_known("safety"));
+        assert!(registry.is_known("safety/electrical"));
+        assert!(registry.is_known("safety/electrical/hv"));
+        assert!(registry.is_known("perf"));
+        assert!(!registry.is_known("perf/latency"));
+        assert!(!registry.is_known("security"));
+    }
+
+    #[test]
+    fn test_is_valid_tag() {
+        assert!(is_valid_tag("safety/electrical"));
+        assert!(!is_valid_tag(""));
+        assert!(!is_valid_tag("safety//hv"));
+        assert!(!is_valid_tag("/safety"));
+        assert!(!is_valid_tag("safety/"));
+    }
+
+    #[test]
+    fn test_project_config_tags_roundtrip() {
+        let yaml = "project_prefix: REQ\nnext_id: 3\ntags:\n  strict: true\n  registered:\n    - safety/hv\n";
+        let config: ProjectConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.tags.strict);
+        assert_eq!(config.tags.registered, vec!["safety/hv"]);
+
+        let legacy: ProjectConfig =
+            serde_yaml::from_str("project_prefix: REQ\nnext_id: 1\n").unwrap();
+        assert!(legacy.tags.is_empty());
+    }
+
+    #[test]
+    fn test_compare_releases_registry() {
+        let releases = vec![
+            Release {
+                name: "alpha".to_string(),
+                date: None,
+            },
+            Release {
+                name: "beta".to_string(),
+                date: Some("2025-06-01".to_string()),
+            },
+        ];
+        assert_eq!(
+            compare_releases(&releases, "alpha", "beta"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_releases(&releases, "alpha", "gamma"), None);
+    }
+
+    #[test]
+    fn test_compare_releases_versions() {
+        assert_eq!(
+            compare_releases(&[], "1.10", "1.9"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_releases(&[], "v2.0", "2.0"), Some(Ordering::Equal));
+        assert_eq!(compare_releases(&[], "next", "2.0"), None);
+    }
+
     #[test]
     fn test_metadata_store_init() {
         let temp = TempDir::new().unwrap();
@@ -286,4 +708,224 @@ mod tests {
             assert_eq!(meta.generated_id, "PERS-001");
         }
     }
+
+    #[test]
+    fn test_content_changes_update_timestamp() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut req = Requirement::new("Login");
+        let created = MetadataStore::init(&rqm_dir, "HASH".to_string())
+            .unwrap()
+            .get_or_create_metadata(&req)
+            .unwrap();
+        assert_eq!(created.content_hash, content_hash(&req));
+
+        // Records from older versions carry a `summary_hash`
+        let path = rqm_dir.join(".metadata/login.json");
+        let legacy = fs::read_to_string(&path)
+            .unwrap()
+            .replace("content_hash", "summary_hash")
+            .replace(&created.content_hash, "5f3e2a1b");
+        fs::write(&path, legacy).unwrap();
+        let migrated = MetadataStore::new(&rqm_dir)
+            .unwrap()
+            .get_or_create_metadata(&req)
+            .unwrap();
+        assert_eq!(migrated.content_hash, created.content_hash);
+        assert_eq!(migrated.updated_at, created.updated_at);
+
+        let mut store = MetadataStore::new(&rqm_dir).unwrap();
+        req.description = Some("Users sign in with SSO".to_string());
+        let edited = store.get_or_create_metadata(&req).unwrap();
+        assert_eq!(edited.uuid, created.uuid);
+        assert_ne!(edited.content_hash, created.content_hash);
+        assert!(edited.updated_at > created.updated_at);
+    }
+
+    #[test]
+    fn test_display_id_prefers_generated_ids() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "DIS".to_string()).unwrap();
+        let mut login = Requirement::new("Login");
+        login.name = Some("LOGIN".to_string());
+        store.get_or_create_metadata(&login).unwrap();
+        let mut export = Requirement::new("Export");
+        export.name = Some("EXPORT".to_string());
+
+        assert_eq!(
+            display_id(Some(&store), &login).unwrap().as_deref(),
+            Some("DIS-001")
+        );
+        assert_eq!(
+            display_id(Some(&store), &export).unwrap().as_deref(),
+            Some("EXPORT")
+        );
+        assert_eq!(display_id(None, &login).unwrap().as_deref(), Some("LOGIN"));
+        // Looking up never creates metadata
+        assert!(store.find_metadata("Export").unwrap().is_none());
+    }
+
+    /// Stores nothing, as if the disk were full
+    struct FailingPut(FileBackend);
+
+    impl MetadataBackend for FailingPut {
+        fn get(&self, key: &str) -> Result<Option<RequirementMetadata>, Error> {
+            self.0.get(key)
+        }
+
+        fn put(&mut self, _key: &str, _meta: &RequirementMetadata) -> Result<(), Error> {
+            Err(Error::custom("disk full"))
+        }
+
+        fn remove(&mut self, key: &str) -> Result<(), Error> {
+            self.0.remove(key)
+        }
+
+        fn entries(&self) -> Result<Vec<(String, RequirementMetadata)>, Error> {
+            self.0.entries()
+        }
+    }
+
+    #[test]
+    fn test_failed_write_does_not_reuse_id() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "GAP".to_string()).unwrap();
+        let metadata_dir = StorageBackend::Files.path(&rqm_dir);
+        store.backend = Box::new(FailingPut(FileBackend::open(metadata_dir).unwrap()));
+
+        assert!(store
+            .get_or_create_metadata(&Requirement::new("Lost"))
+            .is_err());
+        assert!(!rqm_dir.join(".lock").exists());
+
+        let mut store = MetadataStore::new(&rqm_dir).unwrap();
+        let meta = store
+            .get_or_create_metadata(&Requirement::new("Kept"))
+            .unwrap();
+        assert_eq!(meta.generated_id, "GAP-002");
+    }
+
+    #[test]
+    fn test_concurrent_stores_claim_distinct_ids() {
+        claim_ids_concurrently(StorageBackend::default());
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_concurrent_sqlite_stores_claim_distinct_ids() {
+        claim_ids_concurrently(StorageBackend::Sqlite);
+    }
+
+    fn claim_ids_concurrently(storage: StorageBackend) {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "CONC".to_string()).unwrap();
+        store.project_config.storage = storage;
+        store.save_config().unwrap();
+        drop(store);
+
+        let ids = std::sync::Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for t in 0..4 {
+                let (rqm_dir, ids) = (&rqm_dir, &ids);
+                scope.spawn(move || {
+                    // Each thread stands in for a separate CLI invocation
+                    let mut store = MetadataStore::new(rqm_dir).unwrap();
+                    for i in 0..5 {
+                        let req = Requirement::new(format!("Requirement {} {}", t, i));
+                        let id = store.get_generated_id(&req).unwrap();
+                        ids.lock().unwrap().push(id);
+                    }
+                });
+            }
+        });
+
+        let mut ids = ids.into_inner().unwrap();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 20);
+        let store = MetadataStore::new(&rqm_dir).unwrap();
+        assert_eq!(store.project_config().next_id, 21);
+        assert!(!rqm_dir.join(".lock").exists());
+    }
+
+    #[test]
+    fn test_jsonl_storage_migrates_per_file_metadata() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let login = Requirement::new("Login");
+
+        let mut store = MetadataStore::init(&rqm_dir, "MIG".to_string()).unwrap();
+        let uuid = store.get_or_create_metadata(&login).unwrap().uuid;
+        store.project_config.storage = StorageBackend::Jsonl;
+        store.save_config().unwrap();
+
+        let mut store = MetadataStore::new(&rqm_dir).unwrap();
+        assert!(!rqm_dir.join(".metadata").exists());
+        assert!(rqm_dir.join("metadata.jsonl").exists());
+        assert_eq!(store.get_or_create_metadata(&login).unwrap().uuid, uuid);
+
+        // Found without the per-file directory, as for baselines
+        fs::remove_file(rqm_dir.join("config.yml")).unwrap();
+        assert!(MetadataStore::open(&rqm_dir).unwrap().is_none());
+        store.save_config().unwrap();
+        let opened = MetadataStore::open(&rqm_dir).unwrap().unwrap();
+        assert_eq!(opened.find_metadata("Login").unwrap().unwrap().uuid, uuid);
+
+        let logout = store
+            .get_or_create_metadata(&Requirement::new("Logout"))
+            .unwrap();
+        assert_eq!(logout.generated_id, "MIG-002");
+
+        let store = MetadataStore::new(&rqm_dir).unwrap();
+        let ids: Vec<String> = store
+            .all_metadata()
+            .unwrap()
+            .into_iter()
+            .map(|meta| meta.generated_id)
+            .collect();
+        assert_eq!(ids, vec!["MIG-001", "MIG-002"]);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_storage_lookups_and_revisions() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+
+        let mut store = MetadataStore::init(&rqm_dir, "SQL".to_string()).unwrap();
+        let login = store
+            .get_or_create_metadata(&Requirement::new("Login"))
+            .unwrap();
+        store.project_config.storage = StorageBackend::Sqlite;
+        store.save_config().unwrap();
+
+        let mut store = MetadataStore::new(&rqm_dir).unwrap();
+        assert!(!rqm_dir.join(".metadata").exists());
+        assert_eq!(
+            store.find_by_generated_id("SQL-001").unwrap().unwrap().uuid,
+            login.uuid
+        );
+
+        // Same key, new summary text: the update is recorded as a revision
+        let renamed = store
+            .get_or_create_metadata(&Requirement::new("login"))
+            .unwrap();
+        assert_eq!(renamed.uuid, login.uuid);
+
+        let store = MetadataStore::new(&rqm_dir).unwrap();
+        assert_eq!(
+            store.find_by_uuid(&login.uuid).unwrap().unwrap().summary,
+            "login"
+        );
+        let summaries: Vec<String> = store
+            .revisions(&login.uuid)
+            .unwrap()
+            .into_iter()
+            .map(|meta| meta.summary)
+            .collect();
+        assert_eq!(summaries, vec!["Login", "login"]);
+        assert!(store.find_by_generated_id("SQL-404").unwrap().is_none());
+    }
 }
@@ -0,0 +1,329 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Adding new requirements to requirement files
+//!
+//! Like [`MetadataStore::sync_file`], the file is edited as text: the new
+//! requirement is appended to the end of the right `requirements:` list, so
+//! comments and formatting elsewhere stay untouched.
+
+use super::{write_atomic, MetadataStore};
+use crate::error::Error;
+use crate::parser::Parser;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use crate::validator::{ValidationReport, Validator};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Result of [`MetadataStore::add_requirement`]
+#[derive(Debug)]
+pub struct AddedRequirement {
+    /// File the requirement was written to
+    pub path: PathBuf,
+
+    /// Line of the new requirement's summary, 1-based
+    pub line: usize,
+
+    /// Generated ID, also written as its `name:`
+    pub id: String,
+
+    /// Findings of validating the edited file, which has no errors
+    pub report: ValidationReport,
+}
+
+impl MetadataStore {
+    /// Add a new requirement to one of `paths`, nested under `parent` if given
+    ///
+    /// The requirement goes into the file defining `parent`, or the first
+    /// file without a parent, and gets a generated ID as its `name`. The file
+    /// is only rewritten when the edited text parses to the old requirements
+    /// plus the new one and `validator` finds no errors. The ID stays
+    /// allocated if that fails, so a retry with the same summary reuses it.
+    pub fn add_requirement<P: AsRef<Path>>(
+        &mut self,
+        paths: &[P],
+        mut requirement: Requirement,
+        parent: Option<&str>,
+        validator: &Validator,
+    ) -> Result<AddedRequirement, Error> {
+        let mut target = None;
+        for path in paths {
+            let path = path.as_ref();
+            let source = fs::read_to_string(path)?;
+            let config = Parser::parse_str(&source)?;
+            let all = config.all_requirements();
+            if all.iter().any(|req| req.summary == requirement.summary) {
+                return Err(Error::DuplicateSummary(requirement.summary));
+            }
+            let defines_parent = match parent {
+                Some(parent) => all.iter().any(|req| req.summary == parent),
+                None => target.is_none(),
+            };
+            if defines_parent && target.is_none() {
+                target = Some((path.to_path_buf(), source, config));
+            }
+        }
+        let Some((path, source, config)) = target else {
+            return Err(match parent {
+                Some(parent) => Error::custom(format!("No requirement '{}'", parent)),
+                None => Error::custom("No requirements file given"),
+            });
+        };
+
+        let meta = match parent {
+            Some(parent) => {
+                let parent = config
+                    .all_requirements()
+                    .into_iter()
+                    .find(|req| req.summary == parent)
+                    .cloned()
+                    .ok_or_else(|| Error::custom(format!("No requirement '{}'", parent)))?;
+                let parent_meta = self.get_or_create_metadata(&parent)?;
+                self.get_or_create_child_metadata(&requirement, &parent_meta)?
+            }
+            None => self.get_or_create_metadata(&requirement)?,
+        };
+        requirement.name = Some(meta.generated_id.clone());
+        self.get_or_create_metadata(&requirement)?;
+
+        let content = insert_requirement(&source, &requirement, parent).ok_or_else(|| {
+            Error::custom(format!(
+                "Could not find where to add '{}' in {}",
+                requirement.summary,
+                path.display()
+            ))
+        })?;
+        let edited = Parser::parse_str(&content)?;
+        if !is_added(&config, &edited, &requirement, parent) {
+            return Err(Error::custom(format!(
+                "Could not add '{}' to {} without changing its requirements",
+                requirement.summary,
+                path.display()
+            )));
+        }
+        let report = validator.validate(&edited).into_result()?;
+
+        write_atomic(&path, content.as_bytes())?;
+        Ok(AddedRequirement {
+            line: Parser::summary_line(&content, &requirement.summary).unwrap_or(1),
+            path,
+            id: meta.generated_id,
+            report,
+        })
+    }
+}
+
+/// Check that `edited` is `old` with `requirement` added below `parent`
+fn is_added(
+    old: &RequirementConfig,
+    edited: &RequirementConfig,
+    requirement: &Requirement,
+    parent: Option<&str>,
+) -> bool {
+    let mut expected = old.clone();
+    let reference = RequirementReference::Full(Box::new(requirement.clone()));
+    match parent {
+        Some(parent) => {
+            fn push(reqs: &mut [Requirement], parent: &str, child: &RequirementReference) -> bool {
+                for req in reqs {
+                    if req.summary == parent {
+                        req.requirements.push(child.clone());
+                        return true;
+                    }
+                    for nested in &mut req.requirements {
+                        if let RequirementReference::Full(nested) = nested {
+                            if push(std::slice::from_mut(nested), parent, child) {
+                                return true;
+                            }
+                        }
+                    }
+                }
+                false
+            }
+            if !push(&mut expected.requirements, parent, &reference) {
+                return false;
+            }
+        }
+        None => expected.requirements.push(requirement.clone()),
+    }
+    *edited == expected
+}
+
+/// Append a requirement to the `requirements:` list of `parent`, or the
+/// top-level list
+///
+/// A parent without children gets a new `requirements:` key. Returns `None`
+/// for layouts that cannot be edited line by line, such as flow-style lists.
+fn insert_requirement(
+    content: &str,
+    requirement: &Requirement,
+    parent: Option<&str>,
+) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let indent = |line: &str| line.len() - line.trim_start().len();
+    let is_key = |line: &str, column: usize| {
+        indent(line) == column
+            && line
+                .trim_start()
+                .strip_prefix("requirements:")
+                .is_some_and(|rest| {
+                    let rest = rest.trim();
+                    rest.is_empty() || rest.starts_with('#')
+                })
+    };
+
+    // The lines of the parent's mapping, and the column of its keys
+    let (start, end, column) = match parent {
+        Some(parent) => {
+            let index = Parser::summary_line(content, parent)? - 1;
+            let column = lines[index].find("summary:")?;
+            let mut end = index + 1;
+            let mut block_end = end;
+            while end < lines.len() {
+                let line = lines[end];
+                end += 1;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                if indent(line) < column {
+                    break;
+                }
+                block_end = end;
+            }
+            (index + 1, block_end, column)
+        }
+        None => (0, lines.len(), 0),
+    };
+
+    let (insert_at, item_indent, key) = match (start..end).find(|&i| is_key(lines[i], column)) {
+        Some(key_index) => {
+            let mut item_indent = None;
+            let mut insert_at = key_index + 1;
+            for (i, line) in lines.iter().enumerate().take(end).skip(key_index + 1) {
+                let trimmed = line.trim_start();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                let item = *item_indent.get_or_insert(indent(line));
+                if item < column
+                    || indent(line) < item
+                    || (indent(line) == item
+                        && !trimmed.starts_with('-')
+                        && !trimmed.starts_with('#'))
+                {
+                    break;
+                }
+                insert_at = i + 1;
+            }
+            (insert_at, item_indent.unwrap_or(column + 2), false)
+        }
+        // Top-level requirements always exist in a valid file
+        None if parent.is_none() => return None,
+        None => (end, column + 2, true),
+    };
+
+    let yaml = serde_yaml::to_string(&vec![requirement]).ok()?;
+    let mut edited: Vec<String> = lines[..insert_at].iter().map(|l| l.to_string()).collect();
+    if key {
+        edited.push(format!("{}requirements:", " ".repeat(column)));
+    }
+    for line in yaml.lines() {
+        edited.push(format!("{}{}", " ".repeat(item_indent), line));
+    }
+    edited.extend(lines[insert_at..].iter().map(|l| l.to_string()));
+    let mut text = edited.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OwnerReference;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = r#"# Product requirements
+version: "1.0"
+requirements:
+  # Authentication
+  - summary: Login
+    description: Users sign in
+    requirements:
+      - summary: Password policy   # NIST
+        name: PWD
+
+  - summary: Export
+    tags: [data]
+"#;
+
+    fn requirement(summary: &str) -> Requirement {
+        let mut req = Requirement::new(summary);
+        req.owner = Some(OwnerReference::String("@alice".to_string()));
+        req.tags = vec!["safety".to_string()];
+        req
+    }
+
+    #[test]
+    fn test_insert_requirement() {
+        let edited = insert_requirement(SOURCE, &requirement("Lockout"), Some("Login")).unwrap();
+        assert!(edited.contains(
+            "        name: PWD\n      - summary: Lockout\n        owner: '@alice'\n        tags:\n        - safety\n\n  - summary: Export\n"
+        ));
+
+        let edited = insert_requirement(SOURCE, &requirement("Audit"), Some("Export")).unwrap();
+        assert!(edited.ends_with(
+            "    tags: [data]\n    requirements:\n      - summary: Audit\n        owner: '@alice'\n        tags:\n        - safety\n"
+        ));
+
+        let edited = insert_requirement(SOURCE, &requirement("Search"), None).unwrap();
+        assert!(edited.ends_with(
+            "    tags: [data]\n  - summary: Search\n    owner: '@alice'\n    tags:\n    - safety\n"
+        ));
+        assert!(edited.starts_with("# Product requirements\n"));
+
+        let flow = "version: \"1.0\"\nrequirements:\n  - {summary: Login, requirements: []}\n";
+        assert!(insert_requirement(flow, &requirement("Lockout"), Some("Login")).is_none());
+    }
+
+    #[test]
+    fn test_add_requirement_allocates_id_and_keeps_comments() {
+        let temp = TempDir::new().unwrap();
+        let other = temp.path().join("other.yml");
+        let path = temp.path().join("requirements.yml");
+        fs::write(
+            &other,
+            "version: \"1.0\"\nrequirements:\n  - summary: Billing\n",
+        )
+        .unwrap();
+        fs::write(&path, SOURCE).unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let validator = Validator::new().unwrap();
+
+        let added = store
+            .add_requirement(
+                &[&other, &path],
+                requirement("Lockout"),
+                Some("Login"),
+                &validator,
+            )
+            .unwrap();
+        assert_eq!(added.path, path);
+        assert_eq!(added.id, "REQ-002");
+        assert_eq!(added.line, 10);
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Product requirements\n"));
+        assert!(written.contains("      - summary: Password policy   # NIST\n"));
+        assert!(written.contains("      - summary: Lockout\n        name: REQ-002\n"));
+        let config = Parser::parse_str(&written).unwrap();
+        assert_eq!(config.requirements[0].requirements.len(), 2);
+
+        let duplicate = store.add_requirement(&[&path], requirement("Lockout"), None, &validator);
+        assert!(matches!(duplicate, Err(Error::DuplicateSummary(_))));
+        let unknown =
+            store.add_requirement(&[&path], requirement("Audit"), Some("Nope"), &validator);
+        assert!(unknown.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), written);
+    }
+}
@@ -0,0 +1,161 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Crash-safe file writes and an inter-process lock for the metadata store
+
+use crate::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Replace a file's content so readers see either the old or the new version
+///
+/// The content goes to a temporary file in the same directory, which is
+/// synced and then renamed over the target.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    // Unique per process and call, so concurrent writers never share a temp file
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let temp = dir.join(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&temp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+        return result;
+    }
+
+    sync_dir(dir);
+    Ok(())
+}
+
+/// Persist a rename by syncing its directory, where the platform allows it
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(dir) {
+        let _ = dir.sync_all();
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+/// An exclusive lock on a metadata directory, released when dropped
+///
+/// The lock is a file created with `O_EXCL`, which also works on network
+/// filesystems where advisory locks are unreliable. A lock older than
+/// [`StoreLock::STALE_AFTER`] is assumed to belong to a crashed process and
+/// is taken over.
+#[derive(Debug)]
+pub struct StoreLock {
+    path: PathBuf,
+}
+
+impl StoreLock {
+    /// How long to wait for another process before giving up
+    pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Age after which a lock is considered abandoned
+    pub const STALE_AFTER: Duration = Duration::from_secs(60);
+
+    /// Acquire the lock, waiting up to [`StoreLock::TIMEOUT`]
+    pub fn acquire(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let started = SystemTime::now();
+        let mut delay = Duration::from_millis(5);
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    // The owner is informational, for whoever finds a stuck lock
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            if Self::is_stale(&path) {
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+            if started.elapsed().unwrap_or_default() >= Self::TIMEOUT {
+                return Err(Error::custom(format!(
+                    "Timed out waiting for metadata lock {}; remove it if no other rqm process is running",
+                    path.display()
+                )));
+            }
+            thread::sleep(delay);
+            delay = (delay * 2).min(Duration::from_millis(200));
+        }
+    }
+
+    fn is_stale(path: &Path) -> bool {
+        fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > Self::STALE_AFTER)
+    }
+}
+
+impl Drop for StoreLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_atomic_replaces_content() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.yml");
+        write_atomic(&path, b"next_id: 1\n").unwrap();
+        write_atomic(&path, b"next_id: 2\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "next_id: 2\n");
+        // No temporary files are left behind
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".lock");
+        let inside = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..5 {
+                        let _lock = StoreLock::acquire(&path).unwrap();
+                        assert_eq!(inside.fetch_add(1, Ordering::SeqCst), 0);
+                        thread::sleep(Duration::from_millis(1));
+                        inside.fetch_sub(1, Ordering::SeqCst);
+                    }
+                });
+            }
+        });
+        assert!(!path.exists());
+    }
+}
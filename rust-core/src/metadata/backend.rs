@@ -0,0 +1,178 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Storage backends for requirement metadata
+
+use super::{write_atomic, RequirementMetadata};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// How a project stores requirement metadata, set by `storage` in `config.yml`
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    /// One JSON file per requirement in `.rqm/.metadata/`
+    #[default]
+    Files,
+
+    /// A single `.rqm/metadata.jsonl` with one record per line
+    Jsonl,
+
+    /// An SQLite database, `.rqm/metadata.db`; needs the `sqlite` feature
+    Sqlite,
+}
+
+impl StorageBackend {
+    pub fn is_default(&self) -> bool {
+        *self == StorageBackend::default()
+    }
+
+    /// Where the backend keeps its records in a `.rqm` directory
+    pub fn path(&self, rqm_dir: &Path) -> PathBuf {
+        match self {
+            StorageBackend::Files => rqm_dir.join(".metadata"),
+            StorageBackend::Jsonl => rqm_dir.join("metadata.jsonl"),
+            StorageBackend::Sqlite => rqm_dir.join("metadata.db"),
+        }
+    }
+}
+
+/// Reads and writes metadata records keyed by kebab-case summary
+pub(crate) trait MetadataBackend: Send + Sync {
+    /// Look up a record
+    fn get(&self, key: &str) -> Result<Option<RequirementMetadata>, Error>;
+
+    /// Store a record, replacing any with the same key
+    fn put(&mut self, key: &str, meta: &RequirementMetadata) -> Result<(), Error>;
+
+    /// Delete a record; deleting a missing key is not an error
+    fn remove(&mut self, key: &str) -> Result<(), Error>;
+
+    /// Every record, sorted by key
+    fn entries(&self) -> Result<Vec<(String, RequirementMetadata)>, Error>;
+
+    /// Add records for keys not stored yet, returning how many were added
+    fn import(&mut self, entries: Vec<(String, RequirementMetadata)>) -> Result<usize, Error> {
+        let mut imported = 0;
+        for (key, meta) in entries {
+            if self.get(&key)?.is_none() {
+                self.put(&key, &meta)?;
+                imported += 1;
+            }
+        }
+        Ok(imported)
+    }
+
+    /// Look up a record by UUID
+    fn find_by_uuid(&self, uuid: &Uuid) -> Result<Option<RequirementMetadata>, Error> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|(_, meta)| meta)
+            .find(|meta| meta.uuid == *uuid))
+    }
+
+    /// Look up a record by generated ID
+    fn find_by_generated_id(&self, id: &str) -> Result<Option<RequirementMetadata>, Error> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|(_, meta)| meta)
+            .find(|meta| meta.generated_id == id))
+    }
+
+    /// Every stored version of a record, oldest first
+    ///
+    /// Backends without history only know the current version.
+    fn revisions(&self, uuid: &Uuid) -> Result<Vec<RequirementMetadata>, Error> {
+        Ok(self.find_by_uuid(uuid)?.into_iter().collect())
+    }
+
+    /// Pick up changes made by other processes; called while holding the store lock
+    fn refresh(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Hold off other writers until [`MetadataBackend::finish`], so that a
+    /// new ID is allocated and stored as one; called while holding the
+    /// store lock
+    fn begin(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Commit what was written since [`MetadataBackend::begin`], or roll
+    /// it back
+    fn finish(&mut self, _commit: bool) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// One JSON file per requirement
+pub(crate) struct FileBackend {
+    dir: PathBuf,
+}
+
+impl FileBackend {
+    /// Open the backend, creating its directory if needed
+    pub(crate) fn open(dir: PathBuf) -> Result<Self, Error> {
+        if !dir.exists() {
+            fs::create_dir_all(&dir)?;
+        }
+        Ok(Self { dir })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// Read one metadata file
+fn read_file(path: &Path) -> Result<RequirementMetadata, Error> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| Error::SchemaValidation(e.to_string()))
+}
+
+impl MetadataBackend for FileBackend {
+    fn get(&self, key: &str) -> Result<Option<RequirementMetadata>, Error> {
+        let path = self.path(key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_file(&path).map(Some)
+    }
+
+    fn put(&mut self, key: &str, meta: &RequirementMetadata) -> Result<(), Error> {
+        let json = serde_json::to_string_pretty(meta)
+            .map_err(|e| Error::SchemaValidation(e.to_string()))?;
+        write_atomic(&self.path(key), json.as_bytes())?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), Error> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn entries(&self) -> Result<Vec<(String, RequirementMetadata)>, Error> {
+        let mut entries = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let key = match (path.file_stem(), path.extension()) {
+                (Some(stem), Some(ext)) if ext == "json" => stem.to_string_lossy().into_owned(),
+                _ => continue,
+            };
+            if key.starts_with('.') {
+                continue;
+            }
+            entries.push((key, read_file(&path)?));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+}
@@ -0,0 +1,218 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Stable content hashes of requirements
+//!
+//! A requirement is serialized to canonical JSON before hashing: keys are
+//! sorted, empty fields dropped, whitespace in text collapsed and tags
+//! sorted, so reformatting the YAML does not count as a change. Nested
+//! requirements contribute only their summaries; editing a child changes
+//! the child's hash, not the parent's.
+
+use crate::types::{Requirement, RequirementReference};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Fields that record bookkeeping rather than content
+const IGNORED: &[&str] = &[
+    "name",
+    "renamed_from",
+    "created_at",
+    "updated_at",
+    "updated_by",
+];
+
+/// Hex SHA-256 of a string
+pub(crate) fn sha256_hex(s: &str) -> String {
+    Sha256::digest(s.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Hash of everything a requirement says, stable across machines and
+/// Rust versions
+pub fn content_hash(req: &Requirement) -> String {
+    sha256_hex(&canonical_json(req))
+}
+
+/// Signature of a requirement, as recorded by approvals: `sha256:<hex>`
+///
+/// The status and approvals are left out of the hash, so approving a
+/// requirement does not change what was approved.
+pub fn signature(req: &Requirement) -> String {
+    let mut signed = req.shallow_clone();
+    signed.status = None;
+    signed.approvals.clear();
+    format!("sha256:{}", content_hash(&signed))
+}
+
+/// Canonical JSON form of a requirement, as hashed by [`content_hash`]
+pub fn canonical_json(req: &Requirement) -> String {
+    let mut value = serde_json::to_value(req).unwrap_or(Value::Null);
+    if let Value::Object(map) = &mut value {
+        for key in IGNORED {
+            map.remove(*key);
+        }
+        if !req.requirements.is_empty() {
+            let children = req
+                .requirements
+                .iter()
+                .map(RequirementReference::summary)
+                .map(|summary| Value::String(summary.to_string()))
+                .collect();
+            map.insert("requirements".to_string(), Value::Array(children));
+        }
+        if let Some(Value::Array(tags)) = map.get_mut("tags") {
+            tags.sort_by_key(|tag| tag.as_str().map(str::to_string));
+            tags.dedup();
+        }
+    }
+
+    let mut out = String::new();
+    write_value(&normalize(value), &mut out);
+    out
+}
+
+/// Collapse whitespace and drop empty values
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.split_whitespace().collect::<Vec<_>>().join(" ")),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(normalize)
+                .filter(|item| !is_empty(item))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, normalize(value)))
+                .filter(|(_, value)| !is_empty(value))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Write JSON with sorted keys and no whitespace
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| key.as_str());
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_value(value, out);
+            }
+            out.push('}');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    fn first(yaml: &str) -> Requirement {
+        Parser::parse_str(&format!("version: \"1.0\"\nrequirements:\n{}", yaml))
+            .unwrap()
+            .requirements
+            .remove(0)
+    }
+
+    #[test]
+    fn test_sha256_is_stable() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_formatting_does_not_change_hash() {
+        let plain = first(
+            "  - summary: Login\n    description: Users sign in with SSO\n    tags: [auth, web]\n",
+        );
+        let reformatted = first(
+            "  - tags:\n      - web\n      - auth\n    description: >\n      Users sign in\n      with SSO\n    summary: Login\n    name: REQ-001\n    further_information: []\n",
+        );
+        assert_eq!(content_hash(&plain), content_hash(&reformatted));
+        assert_eq!(
+            canonical_json(&plain),
+            r#"{"description":"Users sign in with SSO","summary":"Login","tags":["auth","web"]}"#
+        );
+    }
+
+    #[test]
+    fn test_content_edits_change_hash() {
+        let base = first("  - summary: Login\n    description: Users sign in\n");
+        let edited = first("  - summary: Login\n    description: Users sign in with SSO\n");
+        let criteria = first(
+            "  - summary: Login\n    description: Users sign in\n    acceptance_test: SSO works\n",
+        );
+        assert_ne!(content_hash(&base), content_hash(&edited));
+        assert_ne!(content_hash(&base), content_hash(&criteria));
+
+        // Only the summaries of children count
+        let parent = first("  - summary: Login\n    requirements:\n      - summary: Lockout\n");
+        let child_edited = first(
+            "  - summary: Login\n    requirements:\n      - summary: Lockout\n        status: draft\n",
+        );
+        assert_eq!(content_hash(&parent), content_hash(&child_edited));
+    }
+
+    #[test]
+    fn test_approving_does_not_change_signature() {
+        let proposed = first(
+            "  - summary: Login
+    status: proposed
+",
+        );
+        let approved = first(
+            "  - summary: Login
+    status: approved
+    approvals:
+      - person: \"@alice\"
+        role: product-owner
+        date: 2025-03-01
+",
+        );
+        let edited = first(
+            "  - summary: Login
+    description: Users sign in
+",
+        );
+        assert_eq!(signature(&proposed), signature(&approved));
+        assert_ne!(signature(&proposed), signature(&edited));
+        assert!(signature(&proposed).starts_with("sha256:"));
+    }
+}
@@ -0,0 +1,239 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Append-only change history of requirements
+//!
+//! Every [`MetadataStore::sync`] compares each requirement with the state
+//! recorded so far and appends its changed fields to `.rqm/history.jsonl`.
+//! The file is never rewritten, so it doubles as an audit log.
+
+use super::{MetadataStore, RequirementMetadata, StoreLock};
+use crate::error::Error;
+use crate::graph::diff::{fields, map_changes};
+use crate::graph::FieldChange;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+/// Changes to one requirement recorded by a sync
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Stable UUID of the requirement
+    pub uuid: Uuid,
+
+    /// Summary at the time of the change
+    pub summary: String,
+
+    /// When the change was recorded
+    pub timestamp: DateTime<Utc>,
+
+    /// Who recorded it, from `git config user.name` and `user.email`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    /// Changed fields; the first entry of a requirement lists every field
+    pub changes: Vec<FieldChange>,
+}
+
+impl MetadataStore {
+    /// Record what changed in each requirement since the last sync
+    ///
+    /// Creates metadata for new requirements, numbering nested ones after
+    /// their parent under hierarchical ID schemes, and returns the entries
+    /// that were appended to the history.
+    pub fn sync(&mut self, config: &RequirementConfig) -> Result<Vec<HistoryEntry>, Error> {
+        let mut current = vec![];
+        let mut stack: Vec<(&Requirement, Option<RequirementMetadata>)> = config
+            .requirements
+            .iter()
+            .rev()
+            .map(|req| (req, None))
+            .collect();
+        while let Some((req, parent)) = stack.pop() {
+            let meta = match &parent {
+                Some(parent) => self.get_or_create_child_metadata(req, parent)?,
+                None => self.get_or_create_metadata(req)?,
+            };
+            current.push((meta.uuid, req.summary.clone(), fields(req)));
+            for child in req.requirements.iter().rev() {
+                if let RequirementReference::Full(child) = child {
+                    stack.push((child, Some(meta.clone())));
+                }
+            }
+        }
+
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        let mut recorded = replay(&self.read_history()?);
+        let author = git_author(self.config_path.parent().unwrap_or(Path::new(".")));
+        let timestamp = Utc::now();
+
+        let mut entries = vec![];
+        for (uuid, summary, fields) in current {
+            let state = recorded.entry(uuid).or_default();
+            let changes = map_changes(state, &fields);
+            if changes.is_empty() {
+                continue;
+            }
+            *state = fields;
+            entries.push(HistoryEntry {
+                uuid,
+                summary,
+                timestamp,
+                author: author.clone(),
+                changes,
+            });
+        }
+
+        if !entries.is_empty() {
+            let mut content = String::new();
+            for entry in &entries {
+                let json = serde_json::to_string(entry)
+                    .map_err(|e| Error::SchemaValidation(e.to_string()))?;
+                content.push_str(&json);
+                content.push('\n');
+            }
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.history_path())?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+        }
+        Ok(entries)
+    }
+
+    /// Every recorded change of a requirement, oldest first
+    pub fn history(&self, uuid: &Uuid) -> Result<Vec<HistoryEntry>, Error> {
+        Ok(self
+            .read_history()?
+            .into_iter()
+            .filter(|entry| entry.uuid == *uuid)
+            .collect())
+    }
+
+    fn history_path(&self) -> PathBuf {
+        self.config_path.with_file_name("history.jsonl")
+    }
+
+    fn read_history(&self) -> Result<Vec<HistoryEntry>, Error> {
+        let path = self.history_path();
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        let content = fs::read_to_string(&path)?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).map_err(|e| {
+                    Error::SchemaValidation(format!("{} line {}: {}", path.display(), i + 1, e))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Field values of each requirement as of the last recorded change
+fn replay(entries: &[HistoryEntry]) -> HashMap<Uuid, Map<String, Value>> {
+    let mut state: HashMap<Uuid, Map<String, Value>> = HashMap::new();
+    for entry in entries {
+        let fields = state.entry(entry.uuid).or_default();
+        for change in &entry.changes {
+            if change.new.is_null() {
+                fields.remove(&change.field);
+            } else {
+                fields.insert(change.field.clone(), change.new.clone());
+            }
+        }
+    }
+    state
+}
+
+/// `Name <email>` from the git configuration, if git is available
+fn git_author(dir: &Path) -> Option<String> {
+    let config = |key: &str| {
+        let output = Command::new("git")
+            .args(["config", "--get", key])
+            .current_dir(dir)
+            .output()
+            .ok()?;
+        let value = String::from_utf8(output.stdout).ok()?.trim().to_string();
+        (output.status.success() && !value.is_empty()).then_some(value)
+    };
+
+    match (config("user.name"), config("user.email")) {
+        (Some(name), Some(email)) => Some(format!("{} <{}>", name, email)),
+        (name, email) => name.or(email),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Requirement, Status};
+    use tempfile::TempDir;
+
+    fn config(requirements: Vec<Requirement>) -> RequirementConfig {
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        }
+    }
+
+    #[test]
+    fn test_sync_records_field_changes() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "HIS".to_string()).unwrap();
+
+        let mut login = Requirement::new("Login");
+        login.status = Some(Status::Draft);
+        let first = store.sync(&config(vec![login.clone()])).unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(first[0].changes.iter().any(|c| c.field == "status"));
+
+        // Nothing changed, nothing recorded
+        assert!(store.sync(&config(vec![login.clone()])).unwrap().is_empty());
+
+        login.status = Some(Status::Approved);
+        login.description = Some("Users sign in".to_string());
+        store.sync(&config(vec![login])).unwrap();
+
+        let uuid = store.find_metadata("Login").unwrap().unwrap().uuid;
+        let history = store.history(&uuid).unwrap();
+        assert_eq!(history.len(), 2);
+        let fields: Vec<&str> = history[1]
+            .changes
+            .iter()
+            .map(|c| c.field.as_str())
+            .collect();
+        assert_eq!(fields, vec!["description", "status"]);
+        assert_eq!(history[1].changes[1].old, Value::from("draft"));
+        assert_eq!(history[1].changes[1].new, Value::from("approved"));
+    }
+
+    #[test]
+    fn test_removed_field_is_recorded_as_null() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "HIS".to_string()).unwrap();
+
+        let mut login = Requirement::new("Login");
+        login.milestone = Some("M1".to_string());
+        store.sync(&config(vec![login.clone()])).unwrap();
+        login.milestone = None;
+        let entries = store.sync(&config(vec![login])).unwrap();
+
+        assert_eq!(entries[0].changes.len(), 1);
+        assert_eq!(entries[0].changes[0].new, Value::Null);
+    }
+}
@@ -0,0 +1,206 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Format of generated requirement IDs
+
+use super::{tag_has_prefix, ProjectConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How generated IDs are formatted, set by `ids` in `config.yml`
+///
+/// ```yaml
+/// ids:
+///   template: "{prefix}-{category}-{seq}"
+///   width: 4
+///   categories:
+///     safety: SAFE
+///     performance: PERF
+///   hierarchical: true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IdScheme {
+    /// Layout of an ID with `{prefix}`, `{category}` and `{seq}` placeholders
+    ///
+    /// Without a matching category, `{category}` is dropped together with one
+    /// adjacent separator. A template without `{category}` uses the category
+    /// in place of `{prefix}`, so `safety: SAFE` yields `SAFE-001`.
+    #[serde(default = "default_template")]
+    pub template: String,
+
+    /// Zero-padded width of the sequence number
+    #[serde(default = "default_width")]
+    pub width: usize,
+
+    /// Category codes by tag; each category counts separately
+    ///
+    /// A requirement takes the code of its most specific matching tag, where
+    /// `safety` also matches `safety/electrical`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub categories: BTreeMap<String, String>,
+
+    /// Number nested requirements after their parent, e.g. `REQ-003.2`
+    #[serde(default)]
+    pub hierarchical: bool,
+}
+
+fn default_template() -> String {
+    "{prefix}-{seq}".to_string()
+}
+
+fn default_width() -> usize {
+    3
+}
+
+impl Default for IdScheme {
+    fn default() -> Self {
+        Self {
+            template: default_template(),
+            width: default_width(),
+            categories: BTreeMap::new(),
+            hierarchical: false,
+        }
+    }
+}
+
+impl IdScheme {
+    pub fn is_default(&self) -> bool {
+        *self == IdScheme::default()
+    }
+
+    /// Category code of the most specific category matching one of `tags`
+    pub fn category(&self, tags: &[String]) -> Option<&str> {
+        self.categories
+            .iter()
+            .filter(|(tag, _)| tags.iter().any(|t| tag_has_prefix(t, tag)))
+            .max_by_key(|(tag, _)| tag.len())
+            .map(|(_, code)| code.as_str())
+    }
+
+    /// Render an ID
+    pub fn format(&self, prefix: &str, category: Option<&str>, seq: u32) -> String {
+        let mut template = self.template.clone();
+        let prefix = match category {
+            Some(code) if !template.contains("{category}") => code,
+            _ => prefix,
+        };
+        if category.is_none() {
+            for placeholder in ["{category}-", "-{category}", "{category}"] {
+                if template.contains(placeholder) {
+                    template = template.replacen(placeholder, "", 1);
+                    break;
+                }
+            }
+        }
+
+        template
+            .replace("{prefix}", prefix)
+            .replace("{category}", category.unwrap_or_default())
+            .replace("{seq}", &format!("{:0width$}", seq, width = self.width))
+    }
+}
+
+impl ProjectConfig {
+    /// Generate the ID of a requirement with the given tags and parent ID
+    ///
+    /// The parent only matters for [`IdScheme::hierarchical`] schemes. IDs
+    /// without a category use `next_id`; every category and parent has its
+    /// own counter in `counters`.
+    pub fn next_id_for(&mut self, tags: &[String], parent: Option<&str>) -> String {
+        if let (true, Some(parent)) = (self.ids.hierarchical, parent) {
+            let counter = self.counters.entry(format!("{}.", parent)).or_insert(1);
+            let id = format!("{}.{}", parent, counter);
+            *counter += 1;
+            return id;
+        }
+
+        let Some(code) = self.ids.category(tags).map(str::to_string) else {
+            return self.next_id();
+        };
+        let counter = self.counters.entry(code.clone()).or_insert(1);
+        let seq = *counter;
+        *counter += 1;
+        self.ids.format(&self.project_prefix, Some(&code), seq)
+    }
+
+    /// Check whether an ID for these tags and parent comes from `next_id`
+    pub fn uses_sequence(&self, tags: &[String], parent: Option<&str>) -> bool {
+        !(self.ids.hierarchical && parent.is_some()) && self.ids.category(tags).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MetadataStore, Parser};
+    use tempfile::TempDir;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_template_and_width() {
+        let mut config = ProjectConfig::new("REQ".to_string());
+        config.ids.template = "{prefix}{seq}".to_string();
+        config.ids.width = 5;
+        assert_eq!(config.next_id(), "REQ00001");
+    }
+
+    #[test]
+    fn test_category_counters() {
+        let mut config = ProjectConfig::new("REQ".to_string());
+        config.ids.categories = [("safety", "SAFE"), ("performance", "PERF")]
+            .into_iter()
+            .map(|(tag, code)| (tag.to_string(), code.to_string()))
+            .collect();
+
+        assert_eq!(config.next_id_for(&tags(&["safety/hv"]), None), "SAFE-001");
+        assert_eq!(
+            config.next_id_for(&tags(&["performance"]), None),
+            "PERF-001"
+        );
+        assert_eq!(config.next_id_for(&tags(&["safety"]), None), "SAFE-002");
+        assert_eq!(config.next_id_for(&tags(&["ui"]), None), "REQ-001");
+
+        config.ids.template = "{prefix}-{category}-{seq}".to_string();
+        assert_eq!(config.next_id_for(&tags(&["safety"]), None), "REQ-SAFE-003");
+        assert_eq!(config.next_id_for(&[], None), "REQ-002");
+    }
+
+    #[test]
+    fn test_hierarchical_ids() {
+        let mut config = ProjectConfig::new("REQ".to_string());
+        config.ids.hierarchical = true;
+        let parent = config.next_id_for(&[], None);
+        assert_eq!(config.next_id_for(&[], Some(&parent)), "REQ-001.1");
+        assert_eq!(config.next_id_for(&[], Some(&parent)), "REQ-001.2");
+        assert_eq!(config.next_id_for(&[], Some("REQ-001.2")), "REQ-001.2.1");
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        let restored: ProjectConfig = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(restored.counters, config.counters);
+        assert!(restored.ids.hierarchical);
+    }
+
+    #[test]
+    fn test_sync_numbers_children_after_parent() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "REQ".to_string()).unwrap();
+        store.project_config.ids.hierarchical = true;
+        store.save_config().unwrap();
+
+        let config = Parser::parse_str(
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements:\n      - summary: Password policy\n  - summary: Export\n",
+        )
+        .unwrap();
+        store.sync(&config).unwrap();
+
+        let id = |summary: &str| store.find_metadata(summary).unwrap().unwrap().generated_id;
+        assert_eq!(id("Login"), "REQ-001");
+        assert_eq!(id("Password policy"), "REQ-001.1");
+        assert_eq!(id("Export"), "REQ-002");
+    }
+}
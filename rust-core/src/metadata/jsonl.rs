@@ -0,0 +1,218 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Metadata in a single append-only JSON Lines file
+//!
+//! Each line is one record; when a key appears more than once, the last line
+//! wins. New records are appended, so concurrent branches usually merge
+//! without conflicts, and [`JsonlBackend::compact`] rewrites the file with
+//! one sorted line per key. A final line cut short by a crash while
+//! appending is ignored, and dropped by the next write.
+
+use super::backend::MetadataBackend;
+use super::{write_atomic, RequirementMetadata};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    key: String,
+    #[serde(flatten)]
+    meta: RequirementMetadata,
+}
+
+pub(crate) struct JsonlBackend {
+    path: PathBuf,
+    records: BTreeMap<String, RequirementMetadata>,
+
+    /// Whether the file ends in a partly written line
+    torn: bool,
+}
+
+impl JsonlBackend {
+    /// Open the file, which need not exist yet
+    pub(crate) fn open(path: PathBuf) -> Result<Self, Error> {
+        let mut backend = Self {
+            path,
+            records: BTreeMap::new(),
+            torn: false,
+        };
+        backend.refresh()?;
+        Ok(backend)
+    }
+
+    /// Rewrite the file with one line per key
+    pub(crate) fn compact(&self) -> Result<(), Error> {
+        let mut content = String::new();
+        for (key, meta) in &self.records {
+            content.push_str(&line(key, meta)?);
+        }
+        write_atomic(&self.path, content.as_bytes())?;
+        Ok(())
+    }
+}
+
+fn line(key: &str, meta: &RequirementMetadata) -> Result<String, Error> {
+    let record = Record {
+        key: key.to_string(),
+        meta: meta.clone(),
+    };
+    let json =
+        serde_json::to_string(&record).map_err(|e| Error::SchemaValidation(e.to_string()))?;
+    Ok(format!("{}\n", json))
+}
+
+impl MetadataBackend for JsonlBackend {
+    fn get(&self, key: &str) -> Result<Option<RequirementMetadata>, Error> {
+        Ok(self.records.get(key).cloned())
+    }
+
+    fn put(&mut self, key: &str, meta: &RequirementMetadata) -> Result<(), Error> {
+        // Appending to a torn line would corrupt the new record as well
+        if self.torn {
+            self.compact()?;
+            self.torn = false;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line(key, meta)?.as_bytes())?;
+        file.sync_all()?;
+        self.records.insert(key.to_string(), meta.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), Error> {
+        if self.records.remove(key).is_some() {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn import(&mut self, entries: Vec<(String, RequirementMetadata)>) -> Result<usize, Error> {
+        let mut imported = 0;
+        for (key, meta) in entries {
+            if let Entry::Vacant(entry) = self.records.entry(key) {
+                entry.insert(meta);
+                imported += 1;
+            }
+        }
+        self.compact()?;
+        Ok(imported)
+    }
+
+    fn entries(&self) -> Result<Vec<(String, RequirementMetadata)>, Error> {
+        Ok(self
+            .records
+            .iter()
+            .map(|(key, meta)| (key.clone(), meta.clone()))
+            .collect())
+    }
+
+    fn refresh(&mut self) -> Result<(), Error> {
+        self.records.clear();
+        self.torn = false;
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        let count = content.lines().count();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<Record>(line) {
+                Ok(record) => {
+                    self.records.insert(record.key, record.meta);
+                }
+                Err(_) if i + 1 == count && !content.ends_with('\n') => self.torn = true,
+                Err(e) => {
+                    return Err(Error::SchemaValidation(format!(
+                        "{} line {}: {}",
+                        self.path.display(),
+                        i + 1,
+                        e
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn meta(id: &str, summary: &str) -> RequirementMetadata {
+        RequirementMetadata {
+            uuid: Uuid::new_v4(),
+            generated_id: id.to_string(),
+            content_hash: String::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_last_record_wins() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("metadata.jsonl");
+        let mut backend = JsonlBackend::open(path.clone()).unwrap();
+        backend.put("login", &meta("REQ-001", "Login")).unwrap();
+        backend.put("login", &meta("REQ-002", "Login")).unwrap();
+        backend.put("logout", &meta("REQ-003", "Logout")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        let reopened = JsonlBackend::open(path.clone()).unwrap();
+        assert_eq!(
+            reopened.get("login").unwrap().unwrap().generated_id,
+            "REQ-002"
+        );
+
+        reopened.compact().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 2);
+        assert_eq!(
+            JsonlBackend::open(path).unwrap().entries().unwrap().len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_corrupt_line_is_reported() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("metadata.jsonl");
+        fs::write(&path, "{not json}\n").unwrap();
+        let error = JsonlBackend::open(path).err().unwrap().to_string();
+        assert!(error.contains("line 1"));
+    }
+
+    #[test]
+    fn test_torn_final_line_is_skipped() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("metadata.jsonl");
+        let login = line("login", &meta("REQ-001", "Login")).unwrap();
+        let torn = line("logout", &meta("REQ-002", "Logout")).unwrap();
+        fs::write(&path, format!("{}{}", login, &torn[..20])).unwrap();
+
+        let mut backend = JsonlBackend::open(path.clone()).unwrap();
+        assert_eq!(backend.entries().unwrap().len(), 1);
+        backend.put("audit", &meta("REQ-003", "Audit")).unwrap();
+        assert_eq!(
+            JsonlBackend::open(path).unwrap().entries().unwrap().len(),
+            2
+        );
+    }
+}
@@ -0,0 +1,236 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Garbage collection of metadata records
+
+use super::backend::MetadataBackend;
+use super::jsonl::JsonlBackend;
+use super::{
+    content_hash, is_legacy_hash, kebab_case, MetadataStore, RequirementMetadata, StoreLock,
+};
+use crate::error::Error;
+use crate::types::{Requirement, RequirementConfig};
+use std::collections::{BTreeSet, HashMap};
+
+/// What [`MetadataStore::prune`] does with orphaned records
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PruneAction {
+    /// Only report orphans
+    #[default]
+    Report,
+
+    /// Move orphans to `.rqm/archive.jsonl`, from which they can be restored
+    Archive,
+
+    /// Delete orphans
+    Delete,
+}
+
+/// Findings of [`MetadataStore::prune`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PruneReport {
+    /// Records whose requirement no longer exists, sorted by key
+    pub orphans: Vec<RequirementMetadata>,
+
+    /// Generated IDs below `next_id`, outside team ranges, that no record holds
+    pub id_gaps: Vec<String>,
+
+    /// Records whose content hash no longer matches the requirement
+    pub drifted: Vec<RequirementMetadata>,
+}
+
+impl PruneReport {
+    /// Check whether nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.orphans.is_empty() && self.id_gaps.is_empty() && self.drifted.is_empty()
+    }
+}
+
+impl MetadataStore {
+    /// Find metadata records that no requirement in `config` uses
+    ///
+    /// `config` should hold every requirement of the project, e.g.
+    /// [`Workspace::merged`](crate::Workspace::merged) for multi-file projects;
+    /// records of requirements missing from it are treated as orphans and
+    /// handled according to `action`. ID gaps and content-hash drift are
+    /// reported only.
+    pub fn prune(
+        &mut self,
+        config: &RequirementConfig,
+        action: PruneAction,
+    ) -> Result<PruneReport, Error> {
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.backend.refresh()?;
+        let records: HashMap<String, RequirementMetadata> =
+            self.backend.entries()?.into_iter().collect();
+
+        let archived = self.open_archive()?.entries()?;
+        let mut report = PruneReport {
+            id_gaps: self.id_gaps(
+                records
+                    .values()
+                    .chain(archived.iter().map(|(_, meta)| meta)),
+            ),
+            ..PruneReport::default()
+        };
+
+        let mut live = BTreeSet::new();
+        for req in config.all_requirements() {
+            let key = kebab_case(&req.summary);
+            if let Some(meta) = records.get(&key) {
+                if !is_legacy_hash(&meta.content_hash) && meta.content_hash != content_hash(req) {
+                    report.drifted.push(meta.clone());
+                }
+            }
+            live.insert(key);
+        }
+        report
+            .drifted
+            .sort_by(|a, b| a.generated_id.cmp(&b.generated_id));
+        report.drifted.dedup_by(|a, b| a.uuid == b.uuid);
+
+        let mut orphans: Vec<(String, RequirementMetadata)> = records
+            .into_iter()
+            .filter(|(key, _)| !live.contains(key))
+            .collect();
+        orphans.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if action == PruneAction::Archive && !orphans.is_empty() {
+            let mut archive = self.open_archive()?;
+            for (key, meta) in &orphans {
+                archive.put(key, meta)?;
+            }
+        }
+        for (key, meta) in orphans {
+            if action != PruneAction::Report {
+                self.backend.remove(&key)?;
+                self.metadata_cache.remove(&key);
+            }
+            report.orphans.push(meta);
+        }
+
+        Ok(report)
+    }
+
+    /// Move the records of archived requirements to `.rqm/archive.jsonl`
+    ///
+    /// Records keep their UUID and generated ID, so history entries still
+    /// resolve and the IDs are not handed out again. Returns the number of
+    /// records moved; requirements without one are skipped.
+    pub fn archive_metadata(&mut self, reqs: &[&Requirement]) -> Result<usize, Error> {
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.backend.refresh()?;
+        let mut records: HashMap<String, RequirementMetadata> =
+            self.backend.entries()?.into_iter().collect();
+
+        let mut archive = self.open_archive()?;
+        let mut moved = 0;
+        for req in reqs {
+            let key = kebab_case(&req.summary);
+            if let Some(meta) = records.remove(&key) {
+                archive.put(&key, &meta)?;
+                self.backend.remove(&key)?;
+                self.metadata_cache.remove(&key);
+                moved += 1;
+            }
+        }
+        Ok(moved)
+    }
+
+    fn open_archive(&self) -> Result<JsonlBackend, Error> {
+        JsonlBackend::open(self.config_path.with_file_name("archive.jsonl"))
+    }
+
+    /// Generated IDs that were handed out but are held by no record
+    fn id_gaps<'a>(&self, records: impl Iterator<Item = &'a RequirementMetadata>) -> Vec<String> {
+        let config = &self.project_config;
+        let used: BTreeSet<&str> = records.map(|meta| meta.generated_id.as_str()).collect();
+        (1..config.next_id)
+            .filter(|n| {
+                !config
+                    .id_ranges
+                    .iter()
+                    .any(|r| (r.start..r.end).contains(n))
+            })
+            .map(|n| config.ids.format(&config.project_prefix, None, n))
+            .filter(|id| !used.contains(id.as_str()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Requirement;
+    use tempfile::TempDir;
+
+    fn config(summaries: &[&str]) -> RequirementConfig {
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: summaries.iter().map(|s| Requirement::new(*s)).collect(),
+        }
+    }
+
+    fn store_with(temp: &TempDir, summaries: &[&str]) -> MetadataStore {
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "GC".to_string()).unwrap();
+        for summary in summaries {
+            store
+                .get_or_create_metadata(&Requirement::new(*summary))
+                .unwrap();
+        }
+        store
+    }
+
+    #[test]
+    fn test_report_leaves_orphans_in_place() {
+        let temp = TempDir::new().unwrap();
+        let mut store = store_with(&temp, &["Login", "Logout", "Signup"]);
+
+        let report = store
+            .prune(&config(&["Login", "signup"]), PruneAction::Report)
+            .unwrap();
+        let orphans: Vec<&str> = report
+            .orphans
+            .iter()
+            .map(|meta| meta.generated_id.as_str())
+            .collect();
+        assert_eq!(orphans, vec!["GC-002"]);
+        assert_eq!(report.drifted.len(), 1);
+        assert_eq!(report.drifted[0].generated_id, "GC-003");
+        assert!(report.id_gaps.is_empty());
+        assert_eq!(store.all_metadata().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_delete_leaves_id_gap() {
+        let temp = TempDir::new().unwrap();
+        let mut store = store_with(&temp, &["Login", "Logout", "Signup"]);
+        let live = config(&["Login", "Signup"]);
+
+        store.prune(&live, PruneAction::Delete).unwrap();
+        assert!(store.find_metadata("Logout").unwrap().is_none());
+
+        let report = store.prune(&live, PruneAction::Report).unwrap();
+        assert!(report.orphans.is_empty());
+        assert_eq!(report.id_gaps, vec!["GC-002"]);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_archive_moves_orphans() {
+        let temp = TempDir::new().unwrap();
+        let mut store = store_with(&temp, &["Login", "Logout"]);
+
+        store
+            .prune(&config(&["Login"]), PruneAction::Archive)
+            .unwrap();
+        assert_eq!(store.all_metadata().unwrap().len(), 1);
+
+        let archive = JsonlBackend::open(temp.path().join(".rqm/archive.jsonl")).unwrap();
+        let archived = archive.get("logout").unwrap().unwrap();
+        assert_eq!(archived.generated_id, "GC-002");
+    }
+}
@@ -0,0 +1,198 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Blocks of sequence numbers reserved for teams
+//!
+//! A team reserves a block once; afterwards new requirements on its
+//! branches take numbers from the block and never touch `next_id`, so
+//! branches of different teams merge without conflicts in `config.yml`.
+//! Each team only records the last number it handed out in its own block.
+
+use super::{MetadataStore, StoreLock};
+use crate::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+/// Sequence numbers `start..end` reserved for a team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IdRange {
+    pub team: String,
+    pub start: u32,
+    pub end: u32,
+
+    /// Number after the last one handed out from the block, so that the
+    /// IDs of pruned requirements are not handed out again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<u32>,
+}
+
+impl MetadataStore {
+    /// Take the next `count` sequence numbers out of `next_id`
+    pub fn reserve_range(&mut self, count: u32) -> Result<Range<u32>, Error> {
+        self.reserve(count, None)
+    }
+
+    /// Reserve `count` sequence numbers and record them as `team`'s block
+    pub fn reserve_team_range(&mut self, team: &str, count: u32) -> Result<Range<u32>, Error> {
+        self.reserve(count, Some(team))
+    }
+
+    fn reserve(&mut self, count: u32, team: Option<&str>) -> Result<Range<u32>, Error> {
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.project_config = Self::load_config(&self.config_path)?;
+        let start = self.project_config.next_id;
+        let end = start
+            .checked_add(count)
+            .ok_or_else(|| Error::custom("ID range exceeds the largest sequence number"))?;
+        self.project_config.next_id = end;
+        if let Some(team) = team {
+            self.project_config.id_ranges.push(IdRange {
+                team: team.to_string(),
+                start,
+                end,
+                next: None,
+            });
+        }
+        self.save_config()?;
+        Ok(start..end)
+    }
+
+    /// Allocate new sequence IDs from `team`'s reserved blocks
+    ///
+    /// Categorized and hierarchical IDs keep their own counters.
+    pub fn set_team(&mut self, team: impl Into<String>) {
+        self.team = Some(team.into());
+    }
+
+    /// Next free ID in the team's blocks, after the highest one in use or
+    /// ever handed out, which is recorded in the block
+    pub(super) fn next_team_id(&mut self, team: &str) -> Result<String, Error> {
+        let config = &self.project_config;
+        let format = |n| config.ids.format(&config.project_prefix, None, n);
+        let numbers: Vec<u32> = config
+            .id_ranges
+            .iter()
+            .filter(|range| range.team == team)
+            .flat_map(|range| range.start..range.end)
+            .collect();
+        let handed_out = |n: u32| {
+            config.id_ranges.iter().any(|range| {
+                range.team == team
+                    && (range.start..range.end).contains(&n)
+                    && range.next.is_some_and(|next| n < next)
+            })
+        };
+        if numbers.is_empty() {
+            return Err(Error::custom(format!(
+                "Team '{}' has no reserved ID range",
+                team
+            )));
+        }
+
+        let used: BTreeSet<String> = self
+            .backend
+            .entries()?
+            .into_iter()
+            .map(|(_, meta)| meta.generated_id)
+            .collect();
+        let first_free = numbers
+            .iter()
+            .rposition(|&n| used.contains(&format(n)) || handed_out(n))
+            .map_or(0, |i| i + 1);
+        let Some(&number) = numbers.get(first_free) else {
+            return Err(Error::custom(format!(
+                "Team '{}' has used up its reserved IDs; reserve another range",
+                team
+            )));
+        };
+        let id = format(number);
+
+        let range = self
+            .project_config
+            .id_ranges
+            .iter_mut()
+            .find(|range| range.team == team && (range.start..range.end).contains(&number));
+        if let Some(range) = range {
+            range.next = Some(number + 1);
+        }
+        Ok(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Requirement;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_reserve_range_advances_next_id() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        assert_eq!(store.reserve_range(10).unwrap(), 1..11);
+        assert_eq!(store.reserve_range(5).unwrap(), 11..16);
+
+        let meta = store
+            .get_or_create_metadata(&Requirement::new("Login"))
+            .unwrap();
+        assert_eq!(meta.generated_id, "REQ-016");
+    }
+
+    #[test]
+    fn test_teams_allocate_from_their_blocks() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut store = MetadataStore::init(&rqm_dir, "REQ".to_string()).unwrap();
+        store.reserve_team_range("web", 2).unwrap();
+        store.reserve_team_range("mobile", 2).unwrap();
+
+        let mut web = MetadataStore::new(&rqm_dir).unwrap();
+        web.set_team("web");
+        let mut mobile = MetadataStore::new(&rqm_dir).unwrap();
+        mobile.set_team("mobile");
+
+        let id = |store: &mut MetadataStore, summary: &str| {
+            store
+                .get_or_create_metadata(&Requirement::new(summary))
+                .map(|meta| meta.generated_id)
+        };
+        assert_eq!(id(&mut web, "Login").unwrap(), "REQ-001");
+        assert_eq!(id(&mut mobile, "Push").unwrap(), "REQ-003");
+        assert_eq!(id(&mut web, "Logout").unwrap(), "REQ-002");
+
+        let error = id(&mut web, "Signup").unwrap_err().to_string();
+        assert!(error.contains("used up"));
+        assert_eq!(web.project_config().next_id, 5);
+    }
+
+    #[test]
+    fn test_pruned_team_ids_are_not_reused() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        store.reserve_team_range("web", 3).unwrap();
+        store.set_team("web");
+        for summary in ["Login", "Logout"] {
+            store
+                .get_or_create_metadata(&Requirement::new(summary))
+                .unwrap();
+        }
+
+        let config = crate::types::RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![Requirement::new("Login")],
+        };
+        store
+            .prune(&config, crate::metadata::PruneAction::Delete)
+            .unwrap();
+        assert_eq!(store.project_config().id_ranges[0].next, Some(3));
+
+        let meta = store
+            .get_or_create_metadata(&Requirement::new("Signup"))
+            .unwrap();
+        assert_eq!(meta.generated_id, "REQ-003");
+    }
+}
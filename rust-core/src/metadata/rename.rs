@@ -0,0 +1,313 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Carrying metadata over when a requirement's summary is reworded
+//!
+//! Metadata is keyed by the kebab-cased summary, so rewording a summary
+//! would otherwise give the requirement a new UUID and generated ID.
+
+use super::{kebab_case, MetadataStore, RequirementMetadata, StoreLock};
+use crate::error::Error;
+use crate::graph::analysis::similarity::{edit_similarity, token_similarity, tokens};
+use crate::types::{Requirement, RequirementConfig};
+use chrono::Utc;
+use std::collections::HashSet;
+
+/// Descriptions shorter than this many words are not compared
+const MIN_DESCRIPTION_WORDS: usize = 5;
+
+/// Similarity in `[0, 1]` at which a removed and an added requirement are
+/// taken to be the same one
+pub const RENAME_THRESHOLD: f64 = 0.75;
+
+/// How a rename was recognized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenameMatch {
+    /// The new requirement names the old summary in `renamed_from`
+    Hint,
+
+    /// The summaries or descriptions are this similar
+    Similarity(f64),
+}
+
+/// A requirement whose summary changed, with the metadata to carry over
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    /// Previous summary
+    pub from: String,
+
+    /// New summary
+    pub to: String,
+
+    /// Metadata stored under the previous summary
+    pub metadata: RequirementMetadata,
+
+    pub matched_by: RenameMatch,
+}
+
+impl MetadataStore {
+    /// Find requirements of `old` that appear reworded in `new`
+    ///
+    /// A `renamed_from` hint on a new requirement always wins. Otherwise each
+    /// requirement that disappeared is paired with the most similar one that
+    /// appeared, if they reach [`RENAME_THRESHOLD`]. Nothing is changed until
+    /// [`MetadataStore::apply_renames`] is called.
+    pub fn detect_renames(
+        &self,
+        old: &RequirementConfig,
+        new: &RequirementConfig,
+    ) -> Result<Vec<Rename>, Error> {
+        let new_keys: HashSet<String> = new
+            .all_requirements()
+            .iter()
+            .map(|req| kebab_case(&req.summary))
+            .collect();
+        let old_keys: HashSet<String> = old
+            .all_requirements()
+            .iter()
+            .map(|req| kebab_case(&req.summary))
+            .collect();
+
+        let mut added = vec![];
+        for req in new.all_requirements() {
+            let key = kebab_case(&req.summary);
+            if !old_keys.contains(&key) && self.find_metadata(&req.summary)?.is_none() {
+                added.push(req);
+            }
+        }
+
+        let mut renames = vec![];
+        let mut claimed = HashSet::new();
+        let mut unhinted = vec![];
+        for req in added {
+            let hinted = match &req.renamed_from {
+                Some(from) if !new_keys.contains(&kebab_case(from)) => {
+                    self.find_metadata(from)?.map(|meta| (from, meta))
+                }
+                _ => None,
+            };
+            match hinted {
+                Some((from, metadata)) if claimed.insert(metadata.uuid) => renames.push(Rename {
+                    from: from.clone(),
+                    to: req.summary.clone(),
+                    metadata,
+                    matched_by: RenameMatch::Hint,
+                }),
+                _ => unhinted.push(req),
+            }
+        }
+
+        let mut removed = vec![];
+        for req in old.all_requirements() {
+            if new_keys.contains(&kebab_case(&req.summary)) {
+                continue;
+            }
+            if let Some(meta) = self.find_metadata(&req.summary)? {
+                if !claimed.contains(&meta.uuid) {
+                    removed.push((req, meta));
+                }
+            }
+        }
+
+        let mut pairs = vec![];
+        for (i, new_req) in unhinted.iter().enumerate() {
+            for (j, (old_req, _)) in removed.iter().enumerate() {
+                let score = similarity(old_req, new_req);
+                if score >= RENAME_THRESHOLD {
+                    pairs.push((score, i, j));
+                }
+            }
+        }
+        // Best matches first, so each requirement pairs with its closest counterpart
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut used_added = HashSet::new();
+        let mut used_removed = HashSet::new();
+        for (score, i, j) in pairs {
+            if used_added.contains(&i) || used_removed.contains(&j) {
+                continue;
+            }
+            used_added.insert(i);
+            used_removed.insert(j);
+            let (old_req, metadata) = &removed[j];
+            renames.push(Rename {
+                from: old_req.summary.clone(),
+                to: unhinted[i].summary.clone(),
+                metadata: metadata.clone(),
+                matched_by: RenameMatch::Similarity(score),
+            });
+        }
+
+        renames.sort_by(|a, b| a.to.cmp(&b.to));
+        Ok(renames)
+    }
+
+    /// Move metadata to the new summaries, keeping UUIDs and generated IDs
+    pub fn apply_renames(&mut self, renames: &[Rename]) -> Result<(), Error> {
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.backend.refresh()?;
+        for rename in renames {
+            let key = kebab_case(&rename.from);
+            match self.backend.get(&key)? {
+                Some(meta) if meta.uuid == rename.metadata.uuid => {}
+                _ => {
+                    return Err(Error::custom(format!(
+                        "Metadata for '{}' changed since the rename to '{}' was detected",
+                        rename.from, rename.to
+                    )))
+                }
+            }
+            self.move_record(&key, &rename.to)?;
+        }
+        Ok(())
+    }
+
+    /// Re-key a record under a new summary; the caller holds the store lock
+    ///
+    /// The content hash is kept, so the next lookup records the change.
+    pub(super) fn move_record(
+        &mut self,
+        old_key: &str,
+        summary: &str,
+    ) -> Result<RequirementMetadata, Error> {
+        let key = kebab_case(summary);
+        if self.backend.get(&key)?.is_some() {
+            return Err(Error::custom(format!(
+                "Cannot rename to '{}': it already has metadata",
+                summary
+            )));
+        }
+        let mut meta = self
+            .backend
+            .get(old_key)?
+            .ok_or_else(|| Error::custom(format!("No metadata stored for '{}'", old_key)))?;
+
+        meta.summary = summary.to_string();
+        meta.updated_at = Utc::now();
+        self.backend.put(&key, &meta)?;
+        self.backend.remove(old_key)?;
+
+        self.metadata_cache.remove(old_key);
+        self.metadata_cache.insert(key, meta.clone());
+        Ok(meta)
+    }
+}
+
+/// How alike two requirements are, by summary or by description
+fn similarity(old: &Requirement, new: &Requirement) -> f64 {
+    let summary = edit_similarity(&old.summary, &new.summary)
+        .max(token_similarity(&old.summary, &new.summary));
+    let description = match (&old.description, &new.description) {
+        (Some(a), Some(b))
+            if tokens(a).len() >= MIN_DESCRIPTION_WORDS
+                && tokens(b).len() >= MIN_DESCRIPTION_WORDS =>
+        {
+            token_similarity(a, b)
+        }
+        _ => 0.0,
+    };
+    summary.max(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config(requirements: Vec<Requirement>) -> RequirementConfig {
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements,
+        }
+    }
+
+    fn store(temp: &TempDir, old: &RequirementConfig) -> MetadataStore {
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REN".to_string()).unwrap();
+        for req in old.all_requirements() {
+            store.get_or_create_metadata(req).unwrap();
+        }
+        store
+    }
+
+    #[test]
+    fn test_reworded_summary_keeps_id() {
+        let temp = TempDir::new().unwrap();
+        let old = config(vec![
+            Requirement::new("User can log in"),
+            Requirement::new("Export reports"),
+        ]);
+        let mut store = store(&temp, &old);
+        let new = config(vec![
+            Requirement::new("Users can log in"),
+            Requirement::new("Export reports"),
+            Requirement::new("Audit trail"),
+        ]);
+
+        let renames = store.detect_renames(&old, &new).unwrap();
+        assert_eq!(renames.len(), 1);
+        assert_eq!(renames[0].from, "User can log in");
+        assert_eq!(renames[0].to, "Users can log in");
+        assert!(matches!(renames[0].matched_by, RenameMatch::Similarity(_)));
+
+        store.apply_renames(&renames).unwrap();
+        let meta = store
+            .get_or_create_metadata(&Requirement::new("Users can log in"))
+            .unwrap();
+        assert_eq!(meta.generated_id, "REN-001");
+        assert!(store.find_metadata("User can log in").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_hints_and_similarity_combine() {
+        let temp = TempDir::new().unwrap();
+        let old = config(vec![
+            Requirement::new("User can log in"),
+            Requirement::new("Logins are rate limited"),
+        ]);
+        let store = store(&temp, &old);
+
+        let mut throttling = Requirement::new("Throttle sign-in attempts");
+        throttling.renamed_from = Some("Logins are rate limited".to_string());
+        let new = config(vec![Requirement::new("Users can log in"), throttling]);
+
+        let renames = store.detect_renames(&old, &new).unwrap();
+        let pairs: Vec<(&str, &str)> = renames
+            .iter()
+            .map(|r| (r.from.as_str(), r.to.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("Logins are rate limited", "Throttle sign-in attempts"),
+                ("User can log in", "Users can log in"),
+            ]
+        );
+        assert_eq!(renames[0].matched_by, RenameMatch::Hint);
+    }
+
+    #[test]
+    fn test_hint_is_applied_on_lookup() {
+        let temp = TempDir::new().unwrap();
+        let mut store = store(&temp, &config(vec![Requirement::new("Login")]));
+
+        let mut renamed = Requirement::new("Sign in");
+        renamed.renamed_from = Some("Login".to_string());
+        assert_eq!(
+            store.get_or_create_metadata(&renamed).unwrap().generated_id,
+            "REN-001"
+        );
+        assert_eq!(store.project_config().next_id, 2);
+    }
+
+    #[test]
+    fn test_unrelated_requirements_are_not_renames() {
+        let temp = TempDir::new().unwrap();
+        let old = config(vec![Requirement::new("Export reports")]);
+        let store = store(&temp, &old);
+        let new = config(vec![Requirement::new("Audit trail")]);
+        assert!(store.detect_renames(&old, &new).unwrap().is_empty());
+    }
+}
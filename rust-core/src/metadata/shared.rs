@@ -0,0 +1,256 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! A metadata store that can be shared between threads
+//!
+//! Cached records sit behind their own `RwLock`, so lookups of known
+//! requirements only take read locks and never wait for each other. Cache
+//! misses read the underlying [`MetadataStore`] under a shared lock as
+//! well; only writes wait for exclusive access.
+
+use super::{content_hash, kebab_case, MetadataStore, ProjectConfig, RequirementMetadata};
+use crate::error::Error;
+use crate::types::Requirement;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+type Entry = Arc<RwLock<RequirementMetadata>>;
+
+/// [`MetadataStore`] for use behind an `Arc`, e.g. in a server
+///
+/// ```no_run
+/// use rqm_core::metadata::SharedMetadataStore;
+/// use std::sync::Arc;
+///
+/// let store = Arc::new(SharedMetadataStore::new(".rqm").unwrap());
+/// let worker = Arc::clone(&store);
+/// std::thread::spawn(move || worker.find_metadata("Login"));
+/// ```
+pub struct SharedMetadataStore {
+    store: RwLock<MetadataStore>,
+    entries: RwLock<HashMap<String, Entry>>,
+}
+
+impl SharedMetadataStore {
+    /// Open the metadata store in an `.rqm` directory
+    pub fn new<P: AsRef<Path>>(rqm_dir: P) -> Result<Self, Error> {
+        Ok(Self::from(MetadataStore::new(rqm_dir)?))
+    }
+
+    // A panic elsewhere cannot leave the store half-written: every write
+    // goes to disk atomically before the cache is touched
+    fn store(&self) -> RwLockReadGuard<'_, MetadataStore> {
+        self.store.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn store_mut(&self) -> RwLockWriteGuard<'_, MetadataStore> {
+        self.store.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn cached(&self, key: &str) -> Option<Entry> {
+        let entries = self.entries.read().unwrap_or_else(PoisonError::into_inner);
+        entries.get(key).cloned()
+    }
+
+    // Callers hold the store lock, so no write can land between reading a
+    // record and caching it
+    fn remember(&self, key: String, meta: &RequirementMetadata) {
+        let mut entries = self.entries.write().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(&key) {
+            Some(entry) => *entry.write().unwrap_or_else(PoisonError::into_inner) = meta.clone(),
+            None => {
+                entries.insert(key, Arc::new(RwLock::new(meta.clone())));
+            }
+        }
+    }
+
+    /// Look up existing metadata for a summary without creating any
+    pub fn find_metadata(&self, summary: &str) -> Result<Option<RequirementMetadata>, Error> {
+        let key = kebab_case(summary);
+        if let Some(entry) = self.cached(&key) {
+            let meta = entry.read().unwrap_or_else(PoisonError::into_inner);
+            return Ok(Some(meta.clone()));
+        }
+
+        let store = self.store();
+        let found = store.find_metadata(summary)?;
+        if let Some(meta) = &found {
+            self.remember(key, meta);
+        }
+        Ok(found)
+    }
+
+    /// Get or create metadata for a requirement
+    pub fn get_or_create_metadata(&self, req: &Requirement) -> Result<RequirementMetadata, Error> {
+        let key = kebab_case(&req.summary);
+        if let Some(entry) = self.cached(&key) {
+            let meta = entry.read().unwrap_or_else(PoisonError::into_inner);
+            if meta.content_hash == content_hash(req) {
+                return Ok(meta.clone());
+            }
+        }
+
+        let mut store = self.store_mut();
+        let meta = store.get_or_create_metadata(req)?;
+        self.remember(key, &meta);
+        Ok(meta)
+    }
+
+    /// Drop the cached record of a summary, e.g. after another process changed it
+    pub fn invalidate(&self, summary: &str) {
+        let key = kebab_case(summary);
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&key);
+        self.store_mut().forget(&key);
+    }
+
+    /// Drop every cached record and reload the project configuration
+    pub fn invalidate_all(&self) -> Result<(), Error> {
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.store_mut().reload()
+    }
+
+    /// A copy of the project configuration
+    pub fn project_config(&self) -> ProjectConfig {
+        self.store().project_config().clone()
+    }
+
+    /// Run an operation that only reads the store, e.g. rendering a report
+    pub fn read<T>(&self, f: impl FnOnce(&MetadataStore) -> T) -> T {
+        f(&self.store())
+    }
+
+    /// Run an operation that needs exclusive access to the store
+    ///
+    /// The cache is cleared afterwards, since the operation may have
+    /// changed any record.
+    pub fn with_store<T>(&self, f: impl FnOnce(&mut MetadataStore) -> T) -> T {
+        let result = f(&mut self.store_mut());
+        self.entries
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        result
+    }
+}
+
+impl From<MetadataStore> for SharedMetadataStore {
+    fn from(store: MetadataStore) -> Self {
+        Self {
+            store: RwLock::new(store),
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Barrier;
+    use std::thread;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shared_between_threads() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<SharedMetadataStore>();
+
+        let temp = TempDir::new().unwrap();
+        let store = Arc::new(SharedMetadataStore::from(
+            MetadataStore::init(temp.path().join(".rqm"), "SHR".to_string()).unwrap(),
+        ));
+
+        let results: Vec<Vec<RequirementMetadata>> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| {
+                    let store = Arc::clone(&store);
+                    scope.spawn(move || {
+                        (0..5)
+                            .map(|i| {
+                                let req = Requirement::new(format!("Requirement {}", i));
+                                store.get_or_create_metadata(&req).unwrap()
+                            })
+                            .collect()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        // Every thread sees the same record for the same summary
+        for metas in &results[1..] {
+            assert_eq!(metas, &results[0]);
+        }
+        let ids: HashSet<&str> = results[0]
+            .iter()
+            .map(|meta| meta.generated_id.as_str())
+            .collect();
+        assert_eq!(ids.len(), 5);
+        assert_eq!(store.project_config().next_id, 6);
+        assert_eq!(store.read(|store| store.all_metadata().unwrap().len()), 5);
+    }
+
+    #[test]
+    fn test_lookups_racing_a_writer_cache_the_latest_record() {
+        let temp = TempDir::new().unwrap();
+        let store = SharedMetadataStore::from(
+            MetadataStore::init(temp.path().join(".rqm"), "SHR".to_string()).unwrap(),
+        );
+        let edit = |i: usize| {
+            let mut req = Requirement::new("Login");
+            req.description = Some(format!("Revision {}", i));
+            req
+        };
+        store.get_or_create_metadata(&edit(0)).unwrap();
+
+        for i in 1..=100 {
+            store.invalidate("Login");
+            let start = Barrier::new(2);
+            thread::scope(|scope| {
+                scope.spawn(|| {
+                    start.wait();
+                    store.find_metadata("Login").unwrap();
+                });
+                start.wait();
+                store.get_or_create_metadata(&edit(i)).unwrap();
+            });
+
+            // Whichever ran first, the cache ends up with the written record
+            let cached = store.find_metadata("Login").unwrap().unwrap();
+            assert_eq!(cached.content_hash, content_hash(&edit(i)));
+        }
+    }
+
+    #[test]
+    fn test_invalidate_rereads_record() {
+        let temp = TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let store =
+            SharedMetadataStore::from(MetadataStore::init(&rqm_dir, "SHR".to_string()).unwrap());
+        let login = store
+            .get_or_create_metadata(&Requirement::new("Login"))
+            .unwrap();
+
+        // Another process renames the requirement
+        let mut other = MetadataStore::new(&rqm_dir).unwrap();
+        let mut renamed = Requirement::new("Sign in");
+        renamed.renamed_from = Some("Login".to_string());
+        other.get_or_create_metadata(&renamed).unwrap();
+
+        assert!(store.find_metadata("Login").unwrap().is_some());
+        store.invalidate("Login");
+        assert!(store.find_metadata("Login").unwrap().is_none());
+        assert_eq!(
+            store.find_metadata("Sign in").unwrap().unwrap().uuid,
+            login.uuid
+        );
+    }
+}
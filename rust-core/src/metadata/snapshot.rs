@@ -0,0 +1,439 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Frozen project baselines such as "Release 1.0"
+//!
+//! A baseline is written once to `.rqm/baselines/<label>.json` with a hash
+//! of its content, which is checked whenever it is read back. Labels are
+//! stored in kebab case, so two labels with the same kebab case cannot
+//! both be used.
+
+use super::{hash_string, kebab_case, write_atomic, MetadataStore, RequirementMetadata, StoreLock};
+use crate::error::Error;
+use crate::graph::diff::diff_with_uuids;
+use crate::graph::{GraphDiff, RequirementGraph};
+use crate::types::{Approval, RequirementConfig};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The requirements and their metadata as of a baseline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Snapshot {
+    /// Name of the baseline, e.g. "Release 1.0"
+    pub label: String,
+
+    /// When the baseline was taken
+    pub created_at: DateTime<Utc>,
+
+    /// Hash of the requirements and metadata, for tamper detection
+    pub hash: String,
+
+    /// Algorithm of `hash`: 2 for SHA-256, or 1 for the hashes of
+    /// baselines taken before it was recorded
+    #[serde(default = "legacy_hash_version")]
+    pub hash_version: u32,
+
+    /// All requirements
+    pub requirements: RequirementConfig,
+
+    /// Metadata of every requirement in `requirements`
+    pub metadata: Vec<RequirementMetadata>,
+
+    /// Sign-offs of the baseline as a whole; not covered by `hash`, so
+    /// they can be added after the baseline was taken
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<Approval>,
+}
+
+/// Version of the hash of new baselines
+const HASH_VERSION: u32 = 2;
+
+fn legacy_hash_version() -> u32 {
+    1
+}
+
+impl Snapshot {
+    /// Hash of the requirements and metadata with the algorithm `version`
+    fn content_hash(
+        requirements: &RequirementConfig,
+        metadata: &[RequirementMetadata],
+        version: u32,
+    ) -> Result<String, Error> {
+        let json = serde_json::to_string(&(requirements, metadata))
+            .map_err(|e| Error::SchemaValidation(e.to_string()))?;
+        match version {
+            HASH_VERSION => Ok(hash_string(&json)),
+            // Metadata called content hashes `summary_hash` back then
+            1 => Ok(legacy_hash(
+                &json.replace("\"content_hash\":", "\"summary_hash\":"),
+            )),
+            _ => Err(Error::custom(format!(
+                "Baseline hash version {} is not supported",
+                version
+            ))),
+        }
+    }
+}
+
+/// The hash of baselines before SHA-256 content hashes
+fn legacy_hash(s: &str) -> String {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    s.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+impl MetadataStore {
+    /// Freeze `config` and its metadata as a baseline named `label`
+    ///
+    /// Metadata is created for requirements that have none yet. A baseline
+    /// cannot be overwritten, nor share its kebab-case label with another.
+    pub fn create_baseline(
+        &mut self,
+        label: &str,
+        config: &RequirementConfig,
+    ) -> Result<Snapshot, Error> {
+        let path = self.baseline_path(label);
+        self.check_new_baseline(label, &path)?;
+
+        // Before locking, since creating metadata takes the lock itself
+        let mut metadata = vec![];
+        for req in config.all_requirements() {
+            metadata.push(self.get_or_create_metadata(req)?);
+        }
+        let snapshot = Snapshot {
+            label: label.to_string(),
+            created_at: Utc::now(),
+            hash: Snapshot::content_hash(config, &metadata, HASH_VERSION)?,
+            hash_version: HASH_VERSION,
+            requirements: config.clone(),
+            metadata,
+            approvals: vec![],
+        };
+
+        // Held from the check until written, so no concurrent baseline
+        // with the same label is overwritten
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        self.check_new_baseline(label, &path)?;
+        fs::create_dir_all(self.baselines_dir())?;
+        write_snapshot(&path, &snapshot)?;
+        Ok(snapshot)
+    }
+
+    fn check_new_baseline(&self, label: &str, path: &Path) -> Result<(), Error> {
+        if !path.exists() {
+            return Ok(());
+        }
+        let existing = read_snapshot(path)?.label;
+        Err(Error::custom(if existing == label {
+            format!("Baseline '{}' already exists", label)
+        } else {
+            format!(
+                "Baseline label '{}' collides with the existing baseline '{}'",
+                label, existing
+            )
+        }))
+    }
+
+    /// Record a sign-off of the baseline named `label`
+    ///
+    /// The approval's `baseline` is set to the label. Each person can
+    /// approve a baseline once per role.
+    pub fn approve_baseline(&self, label: &str, approval: Approval) -> Result<Snapshot, Error> {
+        // Held until written, so concurrent approvals are not lost
+        let _lock = StoreLock::acquire(&self.lock_path)?;
+        let mut snapshot = self.baseline(label)?;
+        if approval
+            .baseline
+            .as_deref()
+            .is_some_and(|baseline| baseline != snapshot.label)
+        {
+            return Err(Error::custom(format!(
+                "Approval for baseline '{}' cannot be recorded on '{}'",
+                approval.baseline.unwrap_or_default(),
+                snapshot.label
+            )));
+        }
+        if snapshot
+            .approvals
+            .iter()
+            .any(|a| a.person == approval.person && a.role == approval.role)
+        {
+            return Err(Error::custom(format!(
+                "Baseline '{}' is already approved by {} as {}",
+                snapshot.label, approval.person, approval.role
+            )));
+        }
+        snapshot.approvals.push(Approval {
+            baseline: Some(snapshot.label.clone()),
+            ..approval
+        });
+        write_snapshot(&self.baseline_path(label), &snapshot)?;
+        Ok(snapshot)
+    }
+
+    /// Load a baseline, checking that it was not modified
+    pub fn baseline(&self, label: &str) -> Result<Snapshot, Error> {
+        let path = self.baseline_path(label);
+        if !path.exists() {
+            return Err(Error::custom(format!("No baseline named '{}'", label)));
+        }
+        let snapshot = read_snapshot(&path)?;
+        if snapshot.label != label {
+            return Err(Error::custom(format!(
+                "No baseline named '{}'; did you mean '{}'?",
+                label, snapshot.label
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    /// All baselines, oldest first
+    pub fn baselines(&self) -> Result<Vec<Snapshot>, Error> {
+        let dir = self.baselines_dir();
+        if !dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut snapshots = vec![];
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                snapshots.push(read_snapshot(&path)?);
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.created_at);
+        Ok(snapshots)
+    }
+
+    /// Changes from a baseline to `config`, matching requirements by UUID
+    pub fn diff_baseline(
+        &self,
+        label: &str,
+        config: &RequirementConfig,
+    ) -> Result<GraphDiff, Error> {
+        let snapshot = self.baseline(label)?;
+        let old = RequirementGraph::from_config(&snapshot.requirements)?;
+        let new = RequirementGraph::from_config(config)?;
+
+        let mut uuids: HashMap<String, _> = snapshot
+            .metadata
+            .iter()
+            .map(|meta| (meta.summary.clone(), meta.uuid))
+            .collect();
+        for req in config.all_requirements() {
+            if let Some(meta) = self.find_metadata(&req.summary)? {
+                uuids.insert(req.summary.clone(), meta.uuid);
+            }
+        }
+        Ok(diff_with_uuids(&old, &new, &uuids))
+    }
+
+    /// Changes from the baseline `from` to the baseline `to`, matching
+    /// requirements by UUID
+    pub fn diff_baselines(&self, from: &str, to: &str) -> Result<GraphDiff, Error> {
+        let from = self.baseline(from)?;
+        let to = self.baseline(to)?;
+        let old = RequirementGraph::from_config(&from.requirements)?;
+        let new = RequirementGraph::from_config(&to.requirements)?;
+
+        let uuids: HashMap<String, _> = from
+            .metadata
+            .iter()
+            .chain(&to.metadata)
+            .map(|meta| (meta.summary.clone(), meta.uuid))
+            .collect();
+        Ok(diff_with_uuids(&old, &new, &uuids))
+    }
+
+    fn baselines_dir(&self) -> PathBuf {
+        self.config_path.with_file_name("baselines")
+    }
+
+    fn baseline_path(&self, label: &str) -> PathBuf {
+        self.baselines_dir()
+            .join(format!("{}.json", kebab_case(label)))
+    }
+}
+
+fn write_snapshot(path: &Path, snapshot: &Snapshot) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(snapshot)
+        .map_err(|e| Error::SchemaValidation(e.to_string()))?;
+    write_atomic(path, json.as_bytes())?;
+    Ok(())
+}
+
+fn read_snapshot(path: &Path) -> Result<Snapshot, Error> {
+    let content = fs::read_to_string(path)?;
+    let snapshot: Snapshot =
+        serde_json::from_str(&content).map_err(|e| Error::SchemaValidation(e.to_string()))?;
+    let hash = Snapshot::content_hash(
+        &snapshot.requirements,
+        &snapshot.metadata,
+        snapshot.hash_version,
+    )?;
+    if hash != snapshot.hash {
+        return Err(Error::custom(format!(
+            "Baseline '{}' does not match its hash; {} was modified",
+            snapshot.label,
+            path.display()
+        )));
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const V1: &str = "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: draft\n  - summary: Export\n";
+    const V2: &str = "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: approved\n  - summary: Audit\n";
+
+    #[test]
+    fn test_baseline_roundtrip_and_diff() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+
+        let created = store
+            .create_baseline("Release 1.0", &Parser::parse_str(V1).unwrap())
+            .unwrap();
+        assert_eq!(created.metadata.len(), 2);
+        assert_eq!(store.baseline("Release 1.0").unwrap(), created);
+        assert!(store
+            .create_baseline("Release 1.0", &Parser::parse_str(V2).unwrap())
+            .is_err());
+
+        let diff = store
+            .diff_baseline("Release 1.0", &Parser::parse_str(V2).unwrap())
+            .unwrap();
+        assert_eq!(diff.added, vec!["Audit"]);
+        assert_eq!(diff.removed, vec!["Export"]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].uuid, Some(created.metadata[0].uuid));
+
+        store
+            .create_baseline("Release 1.1", &Parser::parse_str(V2).unwrap())
+            .unwrap();
+        assert_eq!(
+            store.diff_baselines("Release 1.0", "Release 1.1").unwrap(),
+            diff
+        );
+        assert!(store.diff_baselines("Release 1.0", "Release 2.0").is_err());
+    }
+
+    #[test]
+    fn test_modified_baseline_is_rejected() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        store
+            .create_baseline("Release 1.0", &Parser::parse_str(V1).unwrap())
+            .unwrap();
+        store
+            .create_baseline("Release 1.1", &Parser::parse_str(V2).unwrap())
+            .unwrap();
+        let labels: Vec<String> = store
+            .baselines()
+            .unwrap()
+            .into_iter()
+            .map(|snapshot| snapshot.label)
+            .collect();
+        assert_eq!(labels, vec!["Release 1.0", "Release 1.1"]);
+
+        let path = temp.path().join(".rqm/baselines/release-1-0.json");
+        let tampered = fs::read_to_string(&path)
+            .unwrap()
+            .replace("draft", "approved");
+        fs::write(&path, tampered).unwrap();
+        let error = store.baseline("Release 1.0").unwrap_err().to_string();
+        assert!(error.contains("does not match its hash"));
+    }
+
+    #[test]
+    fn test_approve_baseline() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        store
+            .create_baseline("Release 1.0", &Parser::parse_str(V1).unwrap())
+            .unwrap();
+
+        let approval = Approval::new("@alice", "product-owner", "2025-03-01");
+        let approved = store
+            .approve_baseline("Release 1.0", approval.clone())
+            .unwrap();
+        assert_eq!(
+            approved.approvals[0].baseline.as_deref(),
+            Some("Release 1.0")
+        );
+        // Approvals leave the content hash intact
+        assert_eq!(store.baseline("Release 1.0").unwrap(), approved);
+
+        let error = store
+            .approve_baseline("Release 1.0", approval.clone())
+            .unwrap_err();
+        assert!(error.to_string().contains("already approved by @alice"));
+        let elsewhere = Approval {
+            baseline: Some("Release 2.0".to_string()),
+            ..approval
+        };
+        assert!(store.approve_baseline("Release 1.0", elsewhere).is_err());
+    }
+
+    #[test]
+    fn test_colliding_labels_are_rejected() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        store
+            .create_baseline("Release 1.0", &Parser::parse_str(V1).unwrap())
+            .unwrap();
+
+        let error = store
+            .create_baseline("release-1-0", &Parser::parse_str(V2).unwrap())
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("collides with the existing baseline 'Release 1.0'"));
+        assert!(store.baseline("release-1-0").is_err());
+        assert_eq!(
+            store.baseline("Release 1.0").unwrap().requirements,
+            Parser::parse_str(V1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_baselines_with_legacy_hashes_are_read() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let created = store
+            .create_baseline("Release 1.0", &Parser::parse_str(V1).unwrap())
+            .unwrap();
+        assert_eq!(created.hash_version, HASH_VERSION);
+
+        // As written before hash versions, with the old metadata field name
+        let path = temp.path().join(".rqm/baselines/release-1-0.json");
+        let mut legacy: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let legacy_json = serde_json::to_string(&(&created.requirements, &created.metadata))
+            .unwrap()
+            .replace("\"content_hash\":", "\"summary_hash\":");
+        legacy["hash"] = legacy_hash(&legacy_json).into();
+        legacy.as_object_mut().unwrap().remove("hash_version");
+        for meta in legacy["metadata"].as_array_mut().unwrap() {
+            let meta = meta.as_object_mut().unwrap();
+            let hash = meta.remove("content_hash").unwrap();
+            meta.insert("summary_hash".to_string(), hash);
+        }
+        fs::write(&path, legacy.to_string()).unwrap();
+
+        let read = store.baseline("Release 1.0").unwrap();
+        assert_eq!(read.hash_version, 1);
+        assert_eq!(read.requirements, created.requirements);
+    }
+}
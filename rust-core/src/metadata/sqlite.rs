@@ -0,0 +1,310 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Metadata in an SQLite database, enabled by the `sqlite` feature
+//!
+//! Records are indexed by key, UUID and generated ID, and every write is
+//! also appended to a `revisions` table in the same transaction. New IDs
+//! are allocated in a `BEGIN IMMEDIATE` transaction, so that concurrent
+//! writers wait for each other instead of allocating the same ID.
+//!
+//! The schema version is kept in `PRAGMA user_version`; databases written
+//! by older versions are migrated when opened.
+
+use super::backend::MetadataBackend;
+use super::{RequirementMetadata, StoreLock};
+use crate::error::Error;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Row, Savepoint, TransactionBehavior};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use uuid::Uuid;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS metadata (
+        key TEXT PRIMARY KEY,
+        uuid TEXT NOT NULL UNIQUE,
+        generated_id TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        summary TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS metadata_generated_id ON metadata (generated_id);
+    CREATE TABLE IF NOT EXISTS revisions (
+        revision INTEGER PRIMARY KEY AUTOINCREMENT,
+        key TEXT NOT NULL,
+        uuid TEXT NOT NULL,
+        generated_id TEXT NOT NULL,
+        content_hash TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        summary TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS revisions_uuid ON revisions (uuid);
+";
+
+const COLUMNS: &str = "uuid, generated_id, content_hash, created_at, updated_at, summary";
+
+/// Version of [`SCHEMA`]; bump it with a step in [`migrate`] when the
+/// tables change
+const SCHEMA_VERSION: i32 = 1;
+
+fn sql_error(e: rusqlite::Error) -> Error {
+    Error::custom(format!("SQLite metadata error: {}", e))
+}
+
+/// The connection is behind a mutex, as a store shared between threads
+/// needs its backend to be `Sync`
+pub(crate) struct SqliteBackend {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteBackend {
+    /// Open the database, creating or migrating its tables if needed
+    pub(crate) fn open(path: &Path) -> Result<Self, Error> {
+        let mut conn = Connection::open(path).map_err(sql_error)?;
+        conn.busy_timeout(StoreLock::TIMEOUT).map_err(sql_error)?;
+        // Immediate, so that processes opening the database at once do not
+        // both migrate it
+        let tx = conn
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(sql_error)?;
+        migrate(&tx)?;
+        tx.execute_batch(SCHEMA).map_err(sql_error)?;
+        tx.pragma_update(None, "user_version", SCHEMA_VERSION)
+            .map_err(sql_error)?;
+        tx.commit().map_err(sql_error)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        // Every write is a transaction, which a panic rolls back
+        self.conn.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn query_one(&self, filter: &str, value: &str) -> Result<Option<RequirementMetadata>, Error> {
+        let sql = format!("SELECT {} FROM metadata WHERE {} = ?1", COLUMNS, filter);
+        self.conn()
+            .query_row(&sql, [value], from_row)
+            .optional()
+            .map_err(sql_error)
+    }
+}
+
+/// Bring tables written by an older version up to [`SCHEMA_VERSION`]
+fn migrate(conn: &Connection) -> Result<(), Error> {
+    let version: i32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(sql_error)?;
+    // Version 0 named the content hash after the summary it once hashed;
+    // new databases start out at version 0 as well, without tables
+    if version < 1 && has_table(conn, "metadata")? {
+        conn.execute_batch(
+            "ALTER TABLE metadata RENAME COLUMN summary_hash TO content_hash;
+             ALTER TABLE revisions RENAME COLUMN summary_hash TO content_hash;",
+        )
+        .map_err(sql_error)?;
+    }
+    Ok(())
+}
+
+fn has_table(conn: &Connection, name: &str) -> Result<bool, Error> {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [name],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|found| found.is_some())
+    .map_err(sql_error)
+}
+
+fn from_row(row: &Row) -> rusqlite::Result<RequirementMetadata> {
+    from_columns(row, 0)
+}
+
+/// Read a record from the columns in [`COLUMNS`], starting at `first`
+fn from_columns(row: &Row, first: usize) -> rusqlite::Result<RequirementMetadata> {
+    let text = |i: usize| row.get::<_, String>(first + i);
+    let invalid = |i: usize, e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(i, rusqlite::types::Type::Text, e)
+    };
+    let timestamp = |i: usize| -> rusqlite::Result<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&text(i)?)
+            .map(|t| t.with_timezone(&Utc))
+            .map_err(|e| invalid(first + i, Box::new(e)))
+    };
+
+    Ok(RequirementMetadata {
+        uuid: Uuid::parse_str(&text(0)?).map_err(|e| invalid(first, Box::new(e)))?,
+        generated_id: text(1)?,
+        content_hash: text(2)?,
+        created_at: timestamp(3)?,
+        updated_at: timestamp(4)?,
+        summary: text(5)?,
+    })
+}
+
+/// Store a record and append it to its revisions
+fn insert(tx: &Savepoint, key: &str, meta: &RequirementMetadata) -> Result<(), Error> {
+    let uuid = meta.uuid.to_string();
+    let created_at = meta.created_at.to_rfc3339();
+    let updated_at = meta.updated_at.to_rfc3339();
+    let values = params![
+        key,
+        uuid,
+        meta.generated_id,
+        meta.content_hash,
+        created_at,
+        updated_at,
+        meta.summary,
+    ];
+    for table in ["INSERT OR REPLACE INTO metadata", "INSERT INTO revisions"] {
+        let sql = format!(
+            "{} (key, {}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            table, COLUMNS
+        );
+        tx.execute(&sql, values).map_err(sql_error)?;
+    }
+    Ok(())
+}
+
+impl MetadataBackend for SqliteBackend {
+    fn get(&self, key: &str) -> Result<Option<RequirementMetadata>, Error> {
+        self.query_one("key", key)
+    }
+
+    fn put(&mut self, key: &str, meta: &RequirementMetadata) -> Result<(), Error> {
+        // A savepoint, as this may run within the transaction of `begin`
+        let mut conn = self.conn();
+        let tx = conn.savepoint().map_err(sql_error)?;
+        insert(&tx, key, meta)?;
+        tx.commit().map_err(sql_error)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), Error> {
+        // Revisions are kept so the history of removed records stays queryable
+        self.conn()
+            .execute("DELETE FROM metadata WHERE key = ?1", [key])
+            .map_err(sql_error)?;
+        Ok(())
+    }
+
+    fn import(&mut self, entries: Vec<(String, RequirementMetadata)>) -> Result<usize, Error> {
+        let mut conn = self.conn();
+        let tx = conn.savepoint().map_err(sql_error)?;
+        let mut imported = 0;
+        for (key, meta) in entries {
+            let exists = tx
+                .query_row("SELECT 1 FROM metadata WHERE key = ?1", [&key], |_| Ok(()))
+                .optional()
+                .map_err(sql_error)?
+                .is_some();
+            if !exists {
+                insert(&tx, &key, &meta)?;
+                imported += 1;
+            }
+        }
+        tx.commit().map_err(sql_error)?;
+        Ok(imported)
+    }
+
+    fn entries(&self) -> Result<Vec<(String, RequirementMetadata)>, Error> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT key, {} FROM metadata ORDER BY key",
+                COLUMNS
+            ))
+            .map_err(sql_error)?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, from_columns(row, 1)?)))
+            .map_err(sql_error)?;
+        rows.collect::<rusqlite::Result<_>>().map_err(sql_error)
+    }
+
+    fn find_by_uuid(&self, uuid: &Uuid) -> Result<Option<RequirementMetadata>, Error> {
+        self.query_one("uuid", &uuid.to_string())
+    }
+
+    fn find_by_generated_id(&self, id: &str) -> Result<Option<RequirementMetadata>, Error> {
+        self.query_one("generated_id", id)
+    }
+
+    fn begin(&mut self) -> Result<(), Error> {
+        self.conn()
+            .execute_batch("BEGIN IMMEDIATE")
+            .map_err(sql_error)
+    }
+
+    fn finish(&mut self, commit: bool) -> Result<(), Error> {
+        let conn = self.conn();
+        if commit {
+            if let Err(e) = conn.execute_batch("COMMIT") {
+                let _ = conn.execute_batch("ROLLBACK");
+                return Err(sql_error(e));
+            }
+            Ok(())
+        } else {
+            conn.execute_batch("ROLLBACK").map_err(sql_error)
+        }
+    }
+
+    fn revisions(&self, uuid: &Uuid) -> Result<Vec<RequirementMetadata>, Error> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {} FROM revisions WHERE uuid = ?1 ORDER BY revision",
+                COLUMNS
+            ))
+            .map_err(sql_error)?;
+        let rows = stmt
+            .query_map([uuid.to_string()], from_row)
+            .map_err(sql_error)?;
+        rows.collect::<rusqlite::Result<_>>().map_err(sql_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrates_summary_hash_column() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("metadata.db");
+        let uuid = Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        {
+            // Tables as written before the schema was versioned
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(&SCHEMA.replace("content_hash", "summary_hash"))
+                .unwrap();
+            let columns = COLUMNS.replace("content_hash", "summary_hash");
+            for table in ["metadata", "revisions"] {
+                let sql = format!(
+                    "INSERT INTO {} (key, {}) VALUES ('login', ?1, 'REQ-001', 'abc', ?2, ?2, 'Login')",
+                    table, columns
+                );
+                conn.execute(&sql, params![uuid, now]).unwrap();
+            }
+        }
+
+        for _ in 0..2 {
+            let backend = SqliteBackend::open(&path).unwrap();
+            let meta = backend.get("login").unwrap().unwrap();
+            assert_eq!(meta.content_hash, "abc");
+            assert_eq!(backend.revisions(&meta.uuid).unwrap().len(), 1);
+            let version: i32 = backend
+                .conn()
+                .pragma_query_value(None, "user_version", |row| row.get(0))
+                .unwrap();
+            assert_eq!(version, SCHEMA_VERSION);
+        }
+    }
+}
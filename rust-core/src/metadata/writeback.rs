@@ -0,0 +1,417 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Writing generated IDs and statuses back into requirement files
+//!
+//! The file is edited as text, adding a `name:` line after each summary or
+//! replacing a `status:` value, so comments and formatting elsewhere stay
+//! untouched.
+
+use super::{write_atomic, HistoryEntry, MetadataStore};
+use crate::error::Error;
+use crate::parser::Parser;
+use crate::types::{RequirementConfig, Status};
+use serde_json::Value;
+use std::fs;
+#[cfg(feature = "git")]
+use std::ops::Range;
+use std::path::Path;
+
+/// Result of [`MetadataStore::sync_file`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileSync {
+    /// Changes recorded in the history
+    pub history: Vec<HistoryEntry>,
+
+    /// Summaries that were given a `name:`, with the ID written
+    pub named: Vec<(String, String)>,
+
+    /// Summaries without a `name:` whose line could not be edited, such as
+    /// flow-style mappings
+    pub skipped: Vec<String>,
+}
+
+impl MetadataStore {
+    /// Sync a requirements file and write generated IDs into it
+    ///
+    /// Runs [`MetadataStore::sync`] and then adds `name: <generated ID>` to
+    /// every requirement without a name. The file is only rewritten when
+    /// the edited text parses to the same requirements.
+    pub fn sync_file<P: AsRef<Path>>(&mut self, path: P) -> Result<FileSync, Error> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)?;
+        let config = Parser::parse_str(&source)?;
+        let mut result = FileSync {
+            history: self.sync(&config)?,
+            ..FileSync::default()
+        };
+
+        let mut content = source.clone();
+        for req in config.all_requirements() {
+            if req.name.is_some() {
+                continue;
+            }
+            let Some(meta) = self.find_metadata(&req.summary)? else {
+                continue;
+            };
+            match insert_name(&content, &req.summary, &meta.generated_id) {
+                Some(edited) => {
+                    content = edited;
+                    result.named.push((req.summary.clone(), meta.generated_id));
+                }
+                None => result.skipped.push(req.summary.clone()),
+            }
+        }
+        if result.named.is_empty() {
+            return Ok(result);
+        }
+
+        let edited = Parser::parse_str(&content)?;
+        let expected: Vec<(String, Option<String>)> = config
+            .all_requirements()
+            .iter()
+            .map(|req| {
+                let named = result.named.iter().find(|(s, _)| *s == req.summary);
+                let name = named.map(|(_, id)| id.clone()).or(req.name.clone());
+                (req.summary.clone(), name)
+            })
+            .collect();
+        let actual: Vec<(String, Option<String>)> = edited
+            .all_requirements()
+            .iter()
+            .map(|req| (req.summary.clone(), req.name.clone()))
+            .collect();
+        if actual != expected {
+            return Err(Error::custom(format!(
+                "Could not add IDs to {} without changing its requirements",
+                path.display()
+            )));
+        }
+
+        write_atomic(path, content.as_bytes())?;
+        Ok(result)
+    }
+}
+
+/// Set the status of requirements in a requirements file
+///
+/// Each requirement's `status:` value is replaced, or a `status:` line
+/// added below its summary. The file is only rewritten when the edited
+/// text parses to the same requirements with the new statuses.
+pub fn write_statuses<P: AsRef<Path>>(path: P, statuses: &[(String, Status)]) -> Result<(), Error> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path)?;
+    let config = Parser::parse_str(&source)?;
+
+    let mut content = source.clone();
+    for (summary, status) in statuses {
+        let value = status.to_string();
+        content = set_status(&content, summary, &value).ok_or_else(|| {
+            Error::custom(format!(
+                "Could not set the status of '{}' in {}",
+                summary,
+                path.display()
+            ))
+        })?;
+    }
+
+    let edited = Parser::parse_str(&content)?;
+    let expected: Vec<(&str, Option<Status>)> = config
+        .all_requirements()
+        .into_iter()
+        .map(|req| {
+            let set = statuses.iter().find(|(summary, _)| *summary == req.summary);
+            (
+                req.summary.as_str(),
+                set.map(|(_, status)| *status).or(req.status),
+            )
+        })
+        .collect();
+    let actual: Vec<(&str, Option<Status>)> = edited
+        .all_requirements()
+        .into_iter()
+        .map(|req| (req.summary.as_str(), req.status))
+        .collect();
+    if actual != expected || without_statuses(&edited)? != without_statuses(&config)? {
+        return Err(Error::custom(format!(
+            "Could not set statuses in {} without changing its requirements",
+            path.display()
+        )));
+    }
+
+    write_atomic(path, content.as_bytes())?;
+    Ok(())
+}
+
+/// A config as JSON with every requirement's status removed
+fn without_statuses(config: &RequirementConfig) -> Result<Value, Error> {
+    fn strip(requirements: &mut Value) {
+        for req in requirements.as_array_mut().into_iter().flatten() {
+            if let Value::Object(fields) = req {
+                fields.remove("status");
+                if let Some(children) = fields.get_mut("requirements") {
+                    strip(children);
+                }
+            }
+        }
+    }
+    let mut value = serde_json::to_value(config).map_err(|e| Error::custom(e.to_string()))?;
+    strip(&mut value["requirements"]);
+    Ok(value)
+}
+
+/// 0-based lines of the mapping of the requirement with `summary`,
+/// including those of its nested requirements
+#[cfg(feature = "git")]
+pub(crate) fn requirement_lines(content: &str, summary: &str) -> Option<Range<usize>> {
+    let block = Block::find(content, summary)?;
+    Some(block.start..block.end)
+}
+
+/// Lines of a requirement's mapping around its `summary:` line
+struct Block<'a> {
+    lines: Vec<&'a str>,
+
+    /// Column of the requirement's keys
+    column: usize,
+
+    /// Index of the first line of the requirement, its `- ` item
+    start: usize,
+
+    /// Index of the summary line
+    summary: usize,
+
+    /// Index after the last line of the requirement
+    end: usize,
+}
+
+impl<'a> Block<'a> {
+    fn find(content: &'a str, summary: &str) -> Option<Self> {
+        let lines: Vec<&str> = content.lines().collect();
+        let index = Parser::summary_line(content, summary)? - 1;
+        let column = lines[index].find("summary:")?;
+        let indent = |line: &str| line.len() - line.trim_start().len();
+
+        let is_item = |line: &str| {
+            line.get(..column)
+                .is_some_and(|prefix| prefix.trim() == "-")
+        };
+        let is_content = |line: &str| {
+            let trimmed = line.trim();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        };
+
+        let mut start = index;
+        while !is_item(lines[start]) {
+            start = start.checked_sub(1)?;
+            let line = lines[start];
+            if is_content(line) && indent(line) < column && !is_item(line) {
+                return None;
+            }
+        }
+        let end = (index + 1..lines.len())
+            .find(|&i| is_content(lines[i]) && indent(lines[i]) < column)
+            .unwrap_or(lines.len());
+        Some(Self {
+            lines,
+            column,
+            start,
+            summary: index,
+            end,
+        })
+    }
+
+    /// Index of the line holding `key:` of this requirement
+    fn key(&self, key: &str) -> Option<usize> {
+        let key = format!("{}:", key);
+        (self.start..self.end).find(|&i| {
+            let line = self.lines[i];
+            line.get(..self.column)
+                .is_some_and(|prefix| matches!(prefix.trim(), "" | "-"))
+                && line[self.column..].starts_with(&key)
+        })
+    }
+
+    /// Index below the summary value, including any continuation lines
+    fn below_summary(&self) -> usize {
+        let indent = |line: &str| line.len() - line.trim_start().len();
+        let mut insert_at = self.summary + 1;
+        for i in self.summary + 1..self.end {
+            let line = self.lines[i];
+            if line.trim().is_empty() {
+                continue;
+            }
+            if indent(line) <= self.column {
+                break;
+            }
+            insert_at = i + 1;
+        }
+        insert_at
+    }
+
+    /// The content with `edit` applied to its lines
+    fn edit(&self, content: &str, edit: impl FnOnce(&mut Vec<String>)) -> String {
+        let mut edited: Vec<String> = self.lines.iter().map(|line| line.to_string()).collect();
+        edit(&mut edited);
+        let mut text = edited.join("\n");
+        if content.ends_with('\n') {
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Add a `name:` line below a requirement's summary
+///
+/// The line goes after the summary value, including any continuation
+/// lines, at the indentation of the `summary:` key.
+fn insert_name(content: &str, summary: &str, id: &str) -> Option<String> {
+    let block = Block::find(content, summary)?;
+    let at = block.below_summary();
+    let line = format!("{}name: {}", " ".repeat(block.column), id);
+    Some(block.edit(content, |lines| lines.insert(at, line)))
+}
+
+/// Replace the `status:` value of a requirement, keeping any comment after
+/// it, or add a `status:` line below its summary
+fn set_status(content: &str, summary: &str, status: &str) -> Option<String> {
+    let block = Block::find(content, summary)?;
+    match block.key("status") {
+        Some(index) => {
+            let line = block.lines[index];
+            let value_at = block.column + "status:".len();
+            let comment = line[value_at..].find(" #").map(|i| &line[value_at + i..]);
+            let edited = format!(
+                "{}status: {}{}",
+                &line[..block.column],
+                status,
+                comment.unwrap_or_default()
+            );
+            Some(block.edit(content, |lines| lines[index] = edited))
+        }
+        None => {
+            let at = block.below_summary();
+            let line = format!("{}status: {}", " ".repeat(block.column), status);
+            Some(block.edit(content, |lines| lines.insert(at, line)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = r#"# Product requirements
+version: "1.0"
+requirements:
+  # Authentication
+  - summary: Login
+    description: Users sign in
+    requirements:
+      - summary: >
+          Password policy
+        name: PWD
+      - summary: Lockout   # after 5 attempts
+  - summary: Export
+"#;
+
+    #[test]
+    fn test_sync_file_writes_names_and_keeps_comments() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("requirements.yml");
+        fs::write(&path, SOURCE).unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+
+        let result = store.sync_file(&path).unwrap();
+        assert_eq!(result.named.len(), 3);
+        assert!(result.skipped.is_empty());
+
+        let written = fs::read_to_string(&path).unwrap();
+        assert!(written.starts_with("# Product requirements\n"));
+        assert!(written.contains("  # Authentication\n  - summary: Login\n    name: REQ-001\n"));
+        assert!(written.contains("  - summary: Export\n    name: REQ-004\n"));
+        assert!(written.contains("name: PWD"));
+
+        assert!(written
+            .contains("      - summary: Lockout   # after 5 attempts\n        name: REQ-003\n"));
+        let config = Parser::parse_str(&written).unwrap();
+        let names: Vec<Option<&str>> = config
+            .all_requirements()
+            .iter()
+            .map(|req| req.name.as_deref())
+            .collect();
+        assert_eq!(names[0], Some("REQ-001"));
+        assert_eq!(names[1], Some("PWD"));
+        assert_eq!(names[2], Some("REQ-003"));
+
+        // A second sync has nothing left to write
+        let again = store.sync_file(&path).unwrap();
+        assert!(again.named.is_empty());
+        assert_eq!(fs::read_to_string(&path).unwrap(), written);
+    }
+
+    #[test]
+    fn test_write_statuses_keeps_comments() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("requirements.yml");
+        let source = "\
+version: \"1.0\"
+requirements:
+  - status: draft # until review
+    summary: Login
+    requirements:
+      - summary: Lockout
+        status: draft
+  - summary: Export   # CSV only
+    tags: [data]
+";
+        fs::write(&path, source).unwrap();
+        write_statuses(
+            &path,
+            &[
+                ("Login".to_string(), Status::Approved),
+                ("Lockout".to_string(), Status::Implemented),
+                ("Export".to_string(), Status::Proposed),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "\
+version: \"1.0\"
+requirements:
+  - status: approved # until review
+    summary: Login
+    requirements:
+      - summary: Lockout
+        status: implemented
+  - summary: Export   # CSV only
+    status: proposed
+    tags: [data]
+"
+        );
+
+        fs::write(
+            &path,
+            "version: \"1.0\"\nrequirements: [{summary: Login}]\n",
+        )
+        .unwrap();
+        assert!(write_statuses(&path, &[("Login".to_string(), Status::Approved)]).is_err());
+    }
+
+    #[test]
+    fn test_insert_name() {
+        let source = "requirements:\n  - summary: |\n      Long\n      text\n    tags: [a]\n";
+        let edited = insert_name(source, "Long\ntext\n", "REQ-001");
+        // Block scalars are not matched by summary_line
+        assert!(edited.is_none());
+
+        let source = "requirements:\n  - summary: Short\n    tags: [a]\n";
+        assert_eq!(
+            insert_name(source, "Short", "REQ-007").unwrap(),
+            "requirements:\n  - summary: Short\n    name: REQ-007\n    tags: [a]\n"
+        );
+    }
+}
@@ -0,0 +1,531 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Change notifications for Slack, Microsoft Teams and generic webhooks
+//!
+//! Enabled by the `notify` feature. A [`GraphDiff`] is turned into one
+//! [`Change`] per added, removed, modified or re-statused requirement, and
+//! each channel configured in `.rqm/notify.yml` gets one post with a line
+//! per change it subscribes to:
+//!
+//! ```yaml
+//! templates:
+//!   status: "{{id}} moved to {{to}}, owner {{owner}}"
+//! channels:
+//!   - kind: slack
+//!     url_env: SLACK_WEBHOOK_URL
+//!     events: [added, status]
+//!   - name: audit
+//!     kind: webhook
+//!     url: https://ci.example.com/hooks/rqm
+//! ```
+//!
+//! Templates use `{{name}}` placeholders: `id`, `summary`, `owner`, `from`,
+//! `to`, `status` and `fields`. Webhook URLs usually embed a secret, so they
+//! are best taken from an environment variable with `url_env`.
+
+use crate::error::{Error, Result};
+use crate::export::render_template;
+use crate::graph::GraphDiff;
+use crate::http::{self, Request};
+use crate::metadata::{display_id, MetadataStore};
+use crate::types::{Requirement, RequirementConfig, Status};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Kind of change a notification is sent for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Event {
+    /// A new requirement
+    Added,
+
+    /// A requirement whose status changed
+    Status,
+
+    /// A requirement with other field changes
+    Changed,
+
+    /// A requirement that no longer exists
+    Removed,
+}
+
+impl Event {
+    /// Template used unless the configuration overrides it
+    pub fn default_template(self) -> &'static str {
+        match self {
+            Event::Added => "{{id}} added: {{summary}}, owner {{owner}}",
+            Event::Status => "{{id}} moved to {{to}}, owner {{owner}}",
+            Event::Changed => "{{id}} changed ({{fields}}), owner {{owner}}",
+            Event::Removed => "{{id}} removed: {{summary}}",
+        }
+    }
+}
+
+/// Service a channel posts to, which determines the payload format
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// Slack incoming webhook
+    Slack,
+
+    /// Microsoft Teams incoming webhook
+    Teams,
+
+    /// Any endpoint accepting the changes as JSON
+    Webhook,
+}
+
+impl ChannelKind {
+    /// Name as written in `notify.yml`, e.g. `slack`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChannelKind::Slack => "slack",
+            ChannelKind::Teams => "teams",
+            ChannelKind::Webhook => "webhook",
+        }
+    }
+}
+
+/// Where notifications are posted
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Channel {
+    /// Name in results and errors; defaults to the kind
+    #[serde(default)]
+    pub name: Option<String>,
+
+    pub kind: ChannelKind,
+
+    /// Webhook URL
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Environment variable holding the webhook URL
+    #[serde(default)]
+    pub url_env: Option<String>,
+
+    /// Events to notify about; all when empty
+    #[serde(default)]
+    pub events: Vec<Event>,
+
+    /// Templates overriding the project's for this channel
+    #[serde(default)]
+    pub templates: BTreeMap<Event, String>,
+}
+
+impl Channel {
+    /// A channel of `kind` posting to `url`, for all events
+    pub fn new(kind: ChannelKind, url: impl Into<String>) -> Self {
+        Self {
+            name: None,
+            kind,
+            url: Some(url.into()),
+            url_env: None,
+            events: vec![],
+            templates: BTreeMap::new(),
+        }
+    }
+
+    /// Name of the channel, defaulting to its kind
+    pub fn name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => self.kind.as_str().to_string(),
+        }
+    }
+
+    /// Resolve the webhook URL, reading `url_env` if set
+    pub fn url(&self) -> Result<String> {
+        if let Some(var) = &self.url_env {
+            return std::env::var(var).map_err(|_| {
+                Error::custom(format!(
+                    "{} is not set for notification channel '{}'",
+                    var,
+                    self.name()
+                ))
+            });
+        }
+        self.url.clone().ok_or_else(|| {
+            Error::custom(format!(
+                "Notification channel '{}' has neither url nor url_env",
+                self.name()
+            ))
+        })
+    }
+
+    /// Whether the channel wants notifications for `event`
+    pub fn wants(&self, event: Event) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+
+    /// One message per change the channel subscribes to
+    ///
+    /// Templates are taken from the channel, then from `templates`, then
+    /// from [`Event::default_template`].
+    pub fn messages(&self, changes: &[Change], templates: &BTreeMap<Event, String>) -> Vec<String> {
+        changes
+            .iter()
+            .filter(|change| self.wants(change.event))
+            .map(|change| {
+                let template = self
+                    .templates
+                    .get(&change.event)
+                    .or_else(|| templates.get(&change.event))
+                    .map(String::as_str)
+                    .unwrap_or_else(|| change.event.default_template());
+                change.render(template)
+            })
+            .collect()
+    }
+
+    /// The request body posted to the channel
+    pub fn payload(&self, title: &str, messages: &[String], changes: &[Change]) -> Value {
+        match self.kind {
+            ChannelKind::Slack => {
+                let mut text = format!("*{}*", title);
+                for message in messages {
+                    text.push_str("\n• ");
+                    text.push_str(message);
+                }
+                json!({ "text": text })
+            }
+            ChannelKind::Teams => {
+                let items: Vec<String> = messages.iter().map(|m| format!("- {}", m)).collect();
+                json!({
+                    "@type": "MessageCard",
+                    "@context": "https://schema.org/extensions",
+                    "summary": title,
+                    "title": title,
+                    "text": items.join("\n"),
+                })
+            }
+            ChannelKind::Webhook => {
+                let changes: Vec<&Change> = changes
+                    .iter()
+                    .filter(|change| self.wants(change.event))
+                    .collect();
+                json!({ "title": title, "messages": messages, "changes": changes })
+            }
+        }
+    }
+}
+
+/// Notification settings, stored in `.rqm/notify.yml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct NotifyConfig {
+    /// Templates overriding the defaults for all channels
+    #[serde(default)]
+    pub templates: BTreeMap<Event, String>,
+
+    #[serde(default)]
+    pub channels: Vec<Channel>,
+}
+
+impl NotifyConfig {
+    /// Load the settings from an `.rqm` directory, if present
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Option<Self>> {
+        let path = rqm_dir.as_ref().join("notify.yml");
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let config: Self = serde_yaml::from_str(&content)
+            .map_err(|e| Error::custom(format!("Failed to parse {}: {}", path.display(), e)))?;
+        let mut names = HashSet::new();
+        for channel in &config.channels {
+            if !names.insert(channel.name()) {
+                return Err(Error::custom(format!(
+                    "Duplicate notification channel '{}'; give channels distinct names",
+                    channel.name()
+                )));
+            }
+        }
+        Ok(Some(config))
+    }
+}
+
+/// One change to notify about, with the values its template may use
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Change {
+    pub event: Event,
+    pub summary: String,
+
+    /// Placeholder values: `id`, `summary`, `owner`, `from`, `to`, `status`
+    /// and `fields`
+    pub values: BTreeMap<String, String>,
+}
+
+impl Change {
+    /// Fill `template` with the change's values
+    pub fn render(&self, template: &str) -> String {
+        let values: HashMap<&str, String> = self
+            .values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.clone()))
+            .collect();
+        render_template(template, &values)
+    }
+}
+
+/// Changes in `diff` between `old` and `new`, added first and removed last
+///
+/// Requirements are identified by [`display_id`], falling back to their
+/// summary. A status change is reported as [`Event::Status`], and other
+/// field changes to the same requirement as a separate [`Event::Changed`].
+pub fn changes(
+    diff: &GraphDiff,
+    old: &RequirementConfig,
+    new: &RequirementConfig,
+    store: Option<&MetadataStore>,
+) -> Result<Vec<Change>> {
+    let find = |config: &RequirementConfig, summary: &str| -> Option<Requirement> {
+        config
+            .all_requirements()
+            .into_iter()
+            .find(|req| req.summary == summary)
+            .cloned()
+    };
+    let change = |event: Event, summary: &str, config: &RequirementConfig| -> Result<Change> {
+        let req = find(config, summary);
+        let id = match &req {
+            Some(req) => display_id(store, req)?,
+            None => None,
+        }
+        .unwrap_or_else(|| summary.to_string());
+        let owner = req
+            .as_ref()
+            .and_then(|req| req.owner.as_ref())
+            .map(|owner| owner.as_str().to_string())
+            .unwrap_or_else(|| "unassigned".to_string());
+        let status = req
+            .as_ref()
+            .and_then(|req| req.status)
+            .map_or("unset", Status::as_str)
+            .to_string();
+        Ok(Change {
+            event,
+            summary: summary.to_string(),
+            values: BTreeMap::from([
+                ("id".to_string(), id),
+                ("summary".to_string(), summary.to_string()),
+                ("owner".to_string(), owner),
+                ("status".to_string(), status),
+            ]),
+        })
+    };
+
+    let mut result = vec![];
+    for summary in &diff.added {
+        result.push(change(Event::Added, summary, new)?);
+    }
+    for transition in &diff.status_transitions {
+        let mut status = change(Event::Status, &transition.summary, new)?;
+        status.values.insert(
+            "from".to_string(),
+            transition.from.map_or("unset", Status::as_str).to_string(),
+        );
+        status.values.insert(
+            "to".to_string(),
+            transition.to.map_or("unset", Status::as_str).to_string(),
+        );
+        result.push(status);
+    }
+    for modified in &diff.modified {
+        let fields: Vec<&str> = modified
+            .changes
+            .iter()
+            .map(|field| field.field.as_str())
+            .filter(|field| *field != "status")
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+        let mut changed = change(Event::Changed, &modified.summary, new)?;
+        changed
+            .values
+            .insert("fields".to_string(), fields.join(", "));
+        result.push(changed);
+    }
+    for summary in &diff.removed {
+        result.push(change(Event::Removed, summary, old)?);
+    }
+    Ok(result)
+}
+
+/// Posts JSON payloads to webhook URLs
+pub trait WebhookTransport {
+    fn post(&self, url: &str, body: &Value) -> Result<()>;
+}
+
+/// Posts through `curl`, like the tracker syncs
+///
+/// The URL, which usually contains a secret, and the body go to curl on
+/// standard input rather than in the process list.
+#[derive(Debug, Clone, Default)]
+pub struct CurlTransport;
+
+impl WebhookTransport for CurlTransport {
+    fn post(&self, url: &str, body: &Value) -> Result<()> {
+        let mut request = Request::new("POST", url);
+        request.body = Some(body);
+        http::send(&request)?.check("Webhook", "POST")?;
+        Ok(())
+    }
+}
+
+/// Post `changes` to every channel with at least one message
+///
+/// Returns the messages of each channel by name. Without a transport
+/// nothing is posted, which previews the notifications.
+pub fn notify(
+    config: &NotifyConfig,
+    changes: &[Change],
+    title: &str,
+    transport: Option<&dyn WebhookTransport>,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut sent = BTreeMap::new();
+    for channel in &config.channels {
+        let messages = channel.messages(changes, &config.templates);
+        if messages.is_empty() {
+            continue;
+        }
+        if let Some(transport) = transport {
+            let payload = channel.payload(title, &messages, changes);
+            transport.post(&channel.url()?, &payload).map_err(|e| {
+                Error::custom(format!("Notification channel '{}': {}", channel.name(), e))
+            })?;
+        }
+        sent.insert(channel.name(), messages);
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{diff, RequirementGraph};
+    use crate::Parser;
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    /// Records posted payloads instead of sending them
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<(String, Value)>>);
+
+    impl WebhookTransport for Recorder {
+        fn post(&self, url: &str, body: &Value) -> Result<()> {
+            self.0.borrow_mut().push((url.to_string(), body.clone()));
+            Ok(())
+        }
+    }
+
+    fn sample_changes() -> Vec<Change> {
+        let old = Parser::parse_str(
+            r#"version: "1.0"
+requirements:
+  - summary: Login
+    name: REQ-042
+    owner: "@bob"
+    status: draft
+  - summary: Export
+"#,
+        )
+        .unwrap();
+        let new = Parser::parse_str(
+            r#"version: "1.0"
+requirements:
+  - summary: Login
+    name: REQ-042
+    owner: "@bob"
+    status: approved
+    description: Users sign in
+  - summary: Audit log
+    owner: ops@example.com
+"#,
+        )
+        .unwrap();
+        let graph = |config| RequirementGraph::from_config(config).unwrap();
+        changes(&diff(&graph(&old), &graph(&new)), &old, &new, None).unwrap()
+    }
+
+    #[test]
+    fn test_changes_render_default_templates() {
+        let channel = Channel::new(ChannelKind::Slack, "https://hooks.example.com/1");
+        assert_eq!(
+            channel.messages(&sample_changes(), &BTreeMap::new()),
+            vec![
+                "Audit log added: Audit log, owner ops@example.com",
+                "REQ-042 moved to approved, owner @bob",
+                "REQ-042 changed (description), owner @bob",
+                "Export removed: Export",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_notify_filters_events_and_formats_payloads() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("notify.yml"),
+            r#"templates:
+  status: "{{id}}: {{from}} → {{to}}"
+channels:
+  - kind: slack
+    url: https://hooks.slack.example/T1
+    events: [status]
+  - kind: teams
+    url: https://teams.example/hook
+    events: [removed]
+    templates:
+      removed: "Gone: {{summary}}"
+  - name: audit
+    kind: webhook
+    url: https://ci.example/hook
+    events: [added]
+"#,
+        )
+        .unwrap();
+        let config = NotifyConfig::load(temp.path()).unwrap().unwrap();
+        let recorder = Recorder::default();
+        let sent = notify(&config, &sample_changes(), "Release 2", Some(&recorder)).unwrap();
+        assert_eq!(sent["slack"], vec!["REQ-042: draft → approved"]);
+        assert_eq!(sent["teams"], vec!["Gone: Export"]);
+
+        let posted = recorder.0.borrow();
+        assert_eq!(posted.len(), 3);
+        assert_eq!(posted[0].0, "https://hooks.slack.example/T1");
+        assert_eq!(
+            posted[0].1["text"],
+            "*Release 2*\n• REQ-042: draft → approved"
+        );
+        assert_eq!(posted[1].1["@type"], "MessageCard");
+        assert_eq!(posted[2].1["changes"][0]["summary"], "Audit log");
+        assert_eq!(posted[2].1["changes"].as_array().unwrap().len(), 1);
+
+        let preview = notify(&config, &[], "Release 2", None).unwrap();
+        assert!(preview.is_empty());
+    }
+
+    #[test]
+    fn test_load_rejects_duplicate_channels() {
+        let temp = TempDir::new().unwrap();
+        assert!(NotifyConfig::load(temp.path()).unwrap().is_none());
+        fs::write(
+            temp.path().join("notify.yml"),
+            "channels:\n  - kind: slack\n    url: a\n  - kind: slack\n    url: b\n",
+        )
+        .unwrap();
+        assert!(NotifyConfig::load(temp.path()).is_err());
+
+        let channel = Channel {
+            url_env: Some("RQM_TEST_UNSET_WEBHOOK".to_string()),
+            ..Channel::new(ChannelKind::Webhook, "unused")
+        };
+        assert!(channel.url().is_err());
+    }
+}
@@ -0,0 +1,859 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Versioned JSON results printed by the `rqm-validator` CLI and returned
+//! by the C API
+//!
+//! Every result carries a `schema_version`. Adding a field keeps the
+//! version; renaming or removing one, or changing its meaning, increments
+//! [`SCHEMA_VERSION`], so the Go CLI and scripts can detect results they do
+//! not understand. [`schemas`] returns the JSON Schema of every result,
+//! generated from its type and printed by `rqm-validator --output-schema`.
+//!
+//! Requirement exports (`export --format config`) follow the requirements
+//! schema instead, and JGF graph dumps follow the JSON Graph Format.
+
+use crate::archive::ArchiveReport;
+use crate::error::Result;
+use crate::export::OutlineEntry;
+use crate::graph::{GraphDiff, MissingSignoff, OwnerWorkload, RequirementGraph};
+use crate::merge::MergeConflict;
+use crate::metadata::{RequirementMetadata, Snapshot};
+use crate::parser::Parser;
+use crate::search::SearchHit;
+use crate::types::{Approval, Requirement, RequirementConfig, RequirementReference};
+use crate::validator::{Finding, ValidationReport};
+use crate::workspace::{WorkspaceFile, WorkspaceReport};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Version of the result types in this module
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Findings of `validate`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ValidationResult {
+    pub schema_version: u32,
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    #[serde(default)]
+    pub infos: Vec<String>,
+
+    /// Known findings hidden by a baseline
+    #[serde(default)]
+    pub suppressed: usize,
+}
+
+impl Default for ValidationResult {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            valid: true,
+            errors: vec![],
+            warnings: vec![],
+            infos: vec![],
+            suppressed: 0,
+        }
+    }
+}
+
+impl ValidationResult {
+    /// An invalid result with a single error
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            valid: false,
+            errors: vec![message.into()],
+            ..Self::default()
+        }
+    }
+
+    /// The findings of one document, located by line in its `source`
+    pub fn from_report(report: &ValidationReport, source: &str) -> Self {
+        let mut result = Self::default();
+        result.extend(report, |finding| {
+            finding.located(|summary| Parser::summary_line(source, summary))
+        });
+        result
+    }
+
+    /// The findings of a workspace, prefixed by their file and located by
+    /// line where their requirement is known
+    pub fn from_workspace(report: &WorkspaceReport, files: &[WorkspaceFile]) -> Self {
+        let mut result = Self::default();
+        result.extend(&report.unattributed, Finding::message);
+        for (file, parsed) in report.files.iter().zip(files) {
+            result.extend(&file.report, |finding| parsed.locate(finding));
+        }
+        result
+    }
+
+    fn extend(&mut self, report: &ValidationReport, message: impl Fn(&Finding) -> String) {
+        self.valid &= report.is_valid();
+        self.errors.extend(report.errors.iter().map(&message));
+        self.warnings.extend(report.warnings.iter().map(&message));
+        self.infos.extend(report.infos.iter().map(&message));
+        self.suppressed += report.suppressed;
+    }
+}
+
+impl From<&ValidationReport> for ValidationResult {
+    /// The findings without their location, for callers without the source
+    fn from(report: &ValidationReport) -> Self {
+        let mut result = Self::default();
+        result.extend(report, Finding::message);
+        result
+    }
+}
+
+/// Cycles and children of every requirement, from `cycles` and `graph`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct CycleCheckResult {
+    pub schema_version: u32,
+    pub has_cycles: bool,
+
+    /// Summaries along each cycle
+    pub cycles: Vec<Vec<String>>,
+
+    /// Summaries of the children of each requirement
+    pub graph: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for CycleCheckResult {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            has_cycles: false,
+            cycles: vec![],
+            graph: BTreeMap::new(),
+        }
+    }
+}
+
+impl CycleCheckResult {
+    /// Find the cycles and the children of every requirement in `config`
+    pub fn from_config(config: &RequirementConfig) -> Result<Self> {
+        let cycles = RequirementGraph::from_config(config)?.find_cycles();
+
+        let mut graph = BTreeMap::new();
+        for req in &config.requirements {
+            collect_children(req, &mut graph);
+        }
+
+        Ok(Self {
+            has_cycles: !cycles.is_empty(),
+            cycles,
+            graph,
+            ..Self::default()
+        })
+    }
+}
+
+fn collect_children(req: &Requirement, graph: &mut BTreeMap<String, Vec<String>>) {
+    let mut children = Vec::new();
+    for child in &req.requirements {
+        match child {
+            RequirementReference::Full(full_req) => {
+                children.push(full_req.summary.clone());
+                collect_children(full_req, graph);
+            }
+            RequirementReference::Reference(summary) => children.push(summary.clone()),
+        }
+    }
+    graph.insert(req.summary.clone(), children);
+}
+
+/// Requirements matching a `query` expression
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct QueryResult {
+    pub schema_version: u32,
+    pub requirements: Vec<Requirement>,
+}
+
+impl QueryResult {
+    pub fn new(requirements: Vec<Requirement>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            requirements,
+        }
+    }
+}
+
+/// Ranked hits of `search`
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+pub struct SearchResult {
+    pub schema_version: u32,
+    pub hits: Vec<SearchHit>,
+}
+
+impl SearchResult {
+    pub fn new(hits: Vec<SearchHit>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            hits,
+        }
+    }
+}
+
+/// One requirement with its metadata and neighbours, from `rqm-server`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RequirementResult {
+    pub schema_version: u32,
+    pub requirement: Requirement,
+
+    /// Generated ID and timestamps, if the requirement has metadata
+    pub metadata: Option<RequirementMetadata>,
+
+    /// Summaries of the requirements listing this one as a child
+    pub parents: Vec<String>,
+
+    /// Summaries of the requirement's children
+    pub children: Vec<String>,
+}
+
+impl RequirementResult {
+    pub fn new(requirement: Requirement) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            requirement,
+            metadata: None,
+            parents: vec![],
+            children: vec![],
+        }
+    }
+}
+
+/// What `sync` did, with changes described for display
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct SyncResult {
+    pub schema_version: u32,
+    pub dry_run: bool,
+
+    /// Summaries of requirements that got a new issue
+    pub created: Vec<String>,
+
+    /// Issues that were updated
+    pub updated: Vec<String>,
+
+    /// Summaries of requirements whose status was set on a project board
+    pub project_updates: Vec<String>,
+
+    pub status_changes: Vec<String>,
+    pub conflicts: Vec<String>,
+}
+
+impl Default for SyncResult {
+    fn default() -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            dry_run: false,
+            created: vec![],
+            updated: vec![],
+            project_updates: vec![],
+            status_changes: vec![],
+            conflicts: vec![],
+        }
+    }
+}
+
+/// Changes between two versions of the requirements, from `diff`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct DiffResult {
+    pub schema_version: u32,
+
+    #[serde(flatten)]
+    pub diff: GraphDiff,
+}
+
+impl DiffResult {
+    pub fn new(diff: GraphDiff) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            diff,
+        }
+    }
+}
+
+/// Markdown changelog between two versions of the requirements, from
+/// `changelog`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ChangelogResult {
+    pub schema_version: u32,
+    pub title: String,
+    pub markdown: String,
+}
+
+impl ChangelogResult {
+    pub fn new(title: impl Into<String>, markdown: impl Into<String>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            title: title.into(),
+            markdown: markdown.into(),
+        }
+    }
+}
+
+/// Conflicts left by `merge`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct MergeResult {
+    pub schema_version: u32,
+
+    /// File the merged requirements were written to
+    pub path: PathBuf,
+
+    /// Conflicts left unresolved, for which ours was kept
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    pub fn new(path: impl Into<PathBuf>, conflicts: Vec<MergeConflict>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            path: path.into(),
+            conflicts,
+        }
+    }
+}
+
+/// Git integration set up by `hook install`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct HookInstallResult {
+    pub schema_version: u32,
+
+    /// Root of the working tree
+    pub work_dir: PathBuf,
+
+    /// Files that were created or updated
+    pub changed: Vec<PathBuf>,
+}
+
+impl HookInstallResult {
+    pub fn new(work_dir: impl Into<PathBuf>, changed: Vec<PathBuf>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            work_dir: work_dir.into(),
+            changed,
+        }
+    }
+}
+
+/// Files written by `site build`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct SiteResult {
+    pub schema_version: u32,
+    pub out_dir: PathBuf,
+    pub files: Vec<PathBuf>,
+}
+
+impl SiteResult {
+    pub fn new(out_dir: impl Into<PathBuf>, files: Vec<PathBuf>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            out_dir: out_dir.into(),
+            files,
+        }
+    }
+}
+
+/// PDF written by `pdf`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct PdfResult {
+    pub schema_version: u32,
+    pub path: PathBuf,
+}
+
+impl PdfResult {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            path: path.into(),
+        }
+    }
+}
+
+/// A requirement added by `new`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct NewRequirementResult {
+    pub schema_version: u32,
+    pub summary: String,
+
+    /// Generated ID, written as the requirement's `name`
+    pub id: String,
+
+    pub path: PathBuf,
+
+    /// Line of the requirement's summary, 1-based
+    #[schemars(range(min = 1))]
+    pub line: usize,
+
+    /// Warnings from validating the edited file
+    pub warnings: Vec<String>,
+}
+
+impl NewRequirementResult {
+    pub fn new(
+        summary: impl Into<String>,
+        id: impl Into<String>,
+        path: impl Into<PathBuf>,
+        line: usize,
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            summary: summary.into(),
+            id: id.into(),
+            path: path.into(),
+            line,
+            warnings: vec![],
+        }
+    }
+}
+
+/// Messages posted by `notify`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct NotifyResult {
+    pub schema_version: u32,
+
+    /// Whether the messages were only previewed
+    pub dry_run: bool,
+
+    /// Messages of each channel that had any, by channel name
+    pub channels: BTreeMap<String, Vec<String>>,
+}
+
+impl NotifyResult {
+    pub fn new(dry_run: bool, channels: BTreeMap<String, Vec<String>>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            dry_run,
+            channels,
+        }
+    }
+}
+
+/// Numbered outline of the requirements, from `export --format outline`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct OutlineResult {
+    pub schema_version: u32,
+    pub entries: Vec<OutlineEntry>,
+}
+
+impl OutlineResult {
+    pub fn new(entries: Vec<OutlineEntry>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            entries,
+        }
+    }
+}
+
+/// Open requirements per owner, from `workload`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct WorkloadResult {
+    pub schema_version: u32,
+
+    /// Owners with open requirements, most loaded first
+    pub owners: Vec<OwnerWorkload>,
+}
+
+impl WorkloadResult {
+    pub fn new(owners: Vec<OwnerWorkload>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            owners,
+        }
+    }
+}
+
+/// Requirements missing sign-off, from `signoff`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct SignoffResult {
+    pub schema_version: u32,
+
+    /// Roles that must approve, empty if any one approval will do
+    pub roles: Vec<String>,
+
+    /// Requirements without every approval, in declaration order
+    pub requirements: Vec<MissingSignoff>,
+}
+
+impl SignoffResult {
+    pub fn new(roles: Vec<String>, requirements: Vec<MissingSignoff>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            roles,
+            requirements,
+        }
+    }
+}
+
+/// A baseline taken or approved by `baseline`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct BaselineResult {
+    pub schema_version: u32,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    pub hash: String,
+
+    /// Number of requirements in the baseline
+    pub requirements: usize,
+
+    /// Sign-offs of the baseline
+    #[serde(default)]
+    pub approvals: Vec<Approval>,
+}
+
+impl BaselineResult {
+    pub fn new(snapshot: &Snapshot) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            label: snapshot.label.clone(),
+            created_at: snapshot.created_at,
+            hash: snapshot.hash.clone(),
+            requirements: snapshot.requirements.all_requirements().len(),
+            approvals: snapshot.approvals.clone(),
+        }
+    }
+}
+
+/// Deprecated requirements moved out of a file by `archive`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ArchiveResult {
+    pub schema_version: u32,
+
+    /// Archive file the requirements were moved to
+    pub path: PathBuf,
+
+    /// Summaries of the archived requirements, nested ones included
+    pub archived: Vec<String>,
+
+    /// Deprecated requirements left in place for their active children
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+impl ArchiveResult {
+    pub fn new(report: ArchiveReport) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            path: report.path,
+            archived: report.archived,
+            skipped: report.skipped,
+        }
+    }
+}
+
+/// The metadata directory and starter files set up by `init`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct InitResult {
+    pub schema_version: u32,
+    pub rqm_dir: PathBuf,
+    pub prefix: String,
+
+    /// Files that were created
+    #[serde(default)]
+    pub created: Vec<PathBuf>,
+
+    /// Starter files left alone because they already existed
+    #[serde(default)]
+    pub skipped: Vec<PathBuf>,
+}
+
+impl InitResult {
+    pub fn new(rqm_dir: impl Into<PathBuf>, prefix: impl Into<String>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            rqm_dir: rqm_dir.into(),
+            prefix: prefix.into(),
+            created: vec![],
+            skipped: vec![],
+        }
+    }
+}
+
+/// Generated ID of each requirement by summary, from the C API's
+/// `rqm_generate_ids`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct IdsResult {
+    pub schema_version: u32,
+    pub ids: BTreeMap<String, String>,
+}
+
+impl IdsResult {
+    pub fn new(ids: BTreeMap<String, String>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            ids,
+        }
+    }
+}
+
+/// A failed call of the C API; failed validations are a [`ValidationResult`]
+/// instead
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ErrorResult {
+    pub schema_version: u32,
+    pub error: String,
+}
+
+impl ErrorResult {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            error: error.into(),
+        }
+    }
+}
+
+/// Schema generated from `T`, with `schema_version` fixed to
+/// [`SCHEMA_VERSION`]
+fn result_schema<T: JsonSchema>() -> Value {
+    let mut schema =
+        serde_json::to_value(schemars::schema_for!(T)).expect("schemas serialize to JSON");
+    schema["properties"]["schema_version"] = json!({ "type": "integer", "const": SCHEMA_VERSION });
+    schema
+}
+
+/// JSON Schemas of every result, by type name
+pub fn schemas() -> BTreeMap<&'static str, Value> {
+    BTreeMap::from([
+        ("ValidationResult", result_schema::<ValidationResult>()),
+        ("CycleCheckResult", result_schema::<CycleCheckResult>()),
+        ("QueryResult", result_schema::<QueryResult>()),
+        ("SearchResult", result_schema::<SearchResult>()),
+        ("RequirementResult", result_schema::<RequirementResult>()),
+        ("SyncResult", result_schema::<SyncResult>()),
+        ("DiffResult", result_schema::<DiffResult>()),
+        ("ChangelogResult", result_schema::<ChangelogResult>()),
+        ("MergeResult", result_schema::<MergeResult>()),
+        ("HookInstallResult", result_schema::<HookInstallResult>()),
+        ("SiteResult", result_schema::<SiteResult>()),
+        ("PdfResult", result_schema::<PdfResult>()),
+        (
+            "NewRequirementResult",
+            result_schema::<NewRequirementResult>(),
+        ),
+        ("NotifyResult", result_schema::<NotifyResult>()),
+        ("OutlineResult", result_schema::<OutlineResult>()),
+        ("WorkloadResult", result_schema::<WorkloadResult>()),
+        ("SignoffResult", result_schema::<SignoffResult>()),
+        ("BaselineResult", result_schema::<BaselineResult>()),
+        ("ArchiveResult", result_schema::<ArchiveResult>()),
+        ("InitResult", result_schema::<InitResult>()),
+        ("IdsResult", result_schema::<IdsResult>()),
+        ("ErrorResult", result_schema::<ErrorResult>()),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::SearchIndex;
+    use crate::Parser;
+    use jsonschema::JSONSchema;
+
+    fn assert_valid(name: &str, value: impl Serialize) {
+        let schema = JSONSchema::compile(&schemas()[name]).unwrap();
+        let instance = serde_json::to_value(value).unwrap();
+        let errors: Vec<String> = match schema.validate(&instance) {
+            Ok(()) => vec![],
+            Err(errors) => errors.map(|e| e.to_string()).collect(),
+        };
+        assert!(
+            errors.is_empty(),
+            "{} does not match its schema: {:?}",
+            name,
+            errors
+        );
+    }
+
+    #[test]
+    fn test_validation_results_locate_findings() {
+        use crate::validator::Severity;
+        use crate::workspace::FileReport;
+
+        let source = "version: \"1.0\"\nrequirements:\n  - summary: Login\n";
+        let report = ValidationReport {
+            warnings: vec![Finding {
+                rule: "test".to_string(),
+                severity: Severity::Warning,
+                error: crate::Error::custom("Too short"),
+                requirement: Some("Login".to_string()),
+            }],
+            suppressed: 1,
+            ..ValidationReport::default()
+        };
+        let result = ValidationResult::from_report(&report, source);
+        assert!(result.valid);
+        assert_eq!(result.warnings, vec!["Too short (line 3)"]);
+        assert_eq!(result.suppressed, 1);
+        assert_eq!(ValidationResult::from(&report).warnings, vec!["Too short"]);
+
+        let file = WorkspaceFile::parse("auth.yml", source.to_string()).unwrap();
+        let workspace = WorkspaceReport {
+            files: vec![FileReport {
+                path: file.path.clone(),
+                report,
+            }],
+            unattributed: ValidationReport::default(),
+        };
+        let result = ValidationResult::from_workspace(&workspace, &[file]);
+        assert_eq!(result.warnings, vec!["auth.yml: Too short (line 3)"]);
+    }
+
+    #[test]
+    fn test_results_match_schemas() {
+        let config = Parser::parse_str(
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    description: Users log in\n",
+        )
+        .unwrap();
+        let hits = SearchIndex::from_config(&config).search("log");
+
+        assert_valid("ValidationResult", ValidationResult::error("Parse error"));
+        assert_valid(
+            "CycleCheckResult",
+            CycleCheckResult {
+                has_cycles: true,
+                cycles: vec![vec!["A".to_string(), "B".to_string()]],
+                graph: BTreeMap::from([("A".to_string(), vec!["B".to_string()])]),
+                ..CycleCheckResult::default()
+            },
+        );
+        assert_valid("QueryResult", QueryResult::new(config.requirements.clone()));
+        assert_valid("SearchResult", SearchResult::new(hits));
+        assert_valid(
+            "RequirementResult",
+            RequirementResult {
+                children: vec!["Password policy".to_string()],
+                ..RequirementResult::new(config.requirements[0].clone())
+            },
+        );
+        assert_valid("SyncResult", SyncResult::default());
+        assert_valid(
+            "SiteResult",
+            SiteResult::new("_site", vec![PathBuf::from("_site/index.html")]),
+        );
+        assert_valid("PdfResult", PdfResult::new("srs.pdf"));
+        assert_valid(
+            "ChangelogResult",
+            ChangelogResult::new("v1.1", "# v1.1\n\n## Added\n\n- REQ-042 Login\n"),
+        );
+        assert_valid(
+            "NotifyResult",
+            NotifyResult::new(
+                true,
+                BTreeMap::from([(
+                    "slack".to_string(),
+                    vec!["REQ-042 moved to approved, owner @bob".to_string()],
+                )]),
+            ),
+        );
+        assert_valid("InitResult", InitResult::new(".rqm", "REQ"));
+        assert_valid(
+            "ArchiveResult",
+            ArchiveResult::new(crate::archive::ArchiveReport {
+                archived: vec!["Legacy login".to_string()],
+                skipped: vec![],
+                path: PathBuf::from("archive/requirements.yml"),
+            }),
+        );
+        assert_valid(
+            "WorkloadResult",
+            WorkloadResult::new(
+                crate::RequirementGraph::from_config(&config)
+                    .unwrap()
+                    .workload(),
+            ),
+        );
+        assert_valid(
+            "SignoffResult",
+            SignoffResult::new(
+                vec![],
+                crate::RequirementGraph::from_config(&Parser::parse_str(
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: approved\n",
+                ).unwrap())
+                .unwrap()
+                .missing_signoffs(&[]),
+            ),
+        );
+        assert_valid(
+            "BaselineResult",
+            BaselineResult::new(&Snapshot {
+                label: "Release 1.0".to_string(),
+                created_at: Utc::now(),
+                hash: "0f3a".to_string(),
+                hash_version: 2,
+                requirements: config.clone(),
+                metadata: vec![],
+                approvals: vec![Approval {
+                    baseline: Some("Release 1.0".to_string()),
+                    ..Approval::new("@alice", "product-owner", "2025-03-01")
+                }],
+            }),
+        );
+        assert_valid(
+            "OutlineResult",
+            OutlineResult::new(crate::export::outline(&config, None).unwrap()),
+        );
+        assert_valid(
+            "HookInstallResult",
+            HookInstallResult::new(".", vec![PathBuf::from(".gitattributes")]),
+        );
+        assert_valid(
+            "MergeResult",
+            MergeResult::new(
+                "requirements.yml",
+                vec![MergeConflict {
+                    requirement: Some("Login".to_string()),
+                    field: Some("status".to_string()),
+                    base: json!("draft"),
+                    ours: json!("approved"),
+                    theirs: Value::Null,
+                }],
+            ),
+        );
+        assert_valid(
+            "NewRequirementResult",
+            NewRequirementResult::new("Logout", "REQ-002", "requirements.yml", 5),
+        );
+
+        let mut changed = config.clone();
+        changed.requirements[0].status = Some(crate::types::Status::Approved);
+        changed.requirements.push(Requirement::new("Logout"));
+        let graph = |config| crate::RequirementGraph::from_config(config).unwrap();
+        let diff = crate::graph::diff(&graph(&config), &graph(&changed));
+        assert_valid("DiffResult", DiffResult::new(diff));
+
+        assert_valid(
+            "IdsResult",
+            IdsResult::new(BTreeMap::from([(
+                "Login".to_string(),
+                "REQ-001".to_string(),
+            )])),
+        );
+        assert_valid("ErrorResult", ErrorResult::new("Input is null"));
+    }
+
+    #[test]
+    fn test_schema_version_is_required() {
+        for (name, schema) in schemas() {
+            assert!(
+                schema["required"]
+                    .as_array()
+                    .is_some_and(|required| required.contains(&json!("schema_version"))),
+                "{} does not require schema_version",
+                name
+            );
+        }
+        let schema = JSONSchema::compile(&schemas()["ValidationResult"]).unwrap();
+        let mut result = serde_json::to_value(ValidationResult::default()).unwrap();
+        result["schema_version"] = json!(SCHEMA_VERSION + 1);
+        assert!(!schema.is_valid(&result));
+        result.as_object_mut().unwrap().remove("schema_version");
+        assert!(!schema.is_valid(&result));
+    }
+}
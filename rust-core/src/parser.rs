@@ -2,6 +2,8 @@
 // Copyright (c) 2025
 // SPDX-License-Identifier: MIT
 
+use crate::serialize::{self, SerializeOptions};
+use crate::types::RequirementConfigRef;
 use crate::{Error, RequirementConfig, Result};
 use std::fs;
 use std::path::Path;
@@ -17,16 +19,68 @@ impl Parser {
     }
 
     /// Parse a YAML string into a RequirementConfig
+    ///
+    /// Requirement templates are resolved as part of parsing.
     pub fn parse_str(content: &str) -> Result<RequirementConfig> {
-        serde_yaml::from_str(content).map_err(Error::enhance_yaml_error)
+        let mut config: RequirementConfig =
+            serde_yaml::from_str(content).map_err(Error::enhance_yaml_error)?;
+        config.apply_templates()?;
+        Ok(config)
+    }
+
+    /// Parse a YAML string into a RequirementConfigRef borrowing from it
+    ///
+    /// For read-only work such as validation and reporting: strings point into
+    /// `content` instead of being copied, unless YAML escapes or folding
+    /// change them. Requirement templates are resolved as part of parsing.
+    pub fn parse_borrowed(content: &str) -> Result<RequirementConfigRef<'_>> {
+        let mut config: RequirementConfigRef<'_> =
+            serde_yaml::from_str(content).map_err(Error::enhance_yaml_error)?;
+        config.apply_templates()?;
+        Ok(config)
     }
 
     /// Serialize a RequirementConfig to YAML string
+    ///
+    /// Output is deterministic: an unchanged config always yields identical bytes.
     pub fn to_yaml(config: &RequirementConfig) -> Result<String> {
         let yaml = serde_yaml::to_string(config)?;
         Ok(yaml)
     }
 
+    /// Serialize a RequirementConfig to YAML string with explicit options
+    pub fn to_yaml_with(config: &RequirementConfig, options: &SerializeOptions) -> Result<String> {
+        let value = serialize::to_value(config, options)?;
+        let yaml = serde_yaml::to_string(&value)?;
+        Ok(yaml)
+    }
+
+    /// Normalize a YAML document into canonical form, e.g. for a pre-commit hook
+    ///
+    /// Templates are left unresolved so the output stays equivalent to the input.
+    pub fn normalize_str(content: &str, options: &SerializeOptions) -> Result<String> {
+        let config: RequirementConfig =
+            serde_yaml::from_str(content).map_err(Error::enhance_yaml_error)?;
+        Self::to_yaml_with(&config, options)
+    }
+
+    /// Find the 1-based line declaring a requirement's summary
+    ///
+    /// This is a textual search for `summary: <value>`, quoted or not and
+    /// optionally followed by a comment, so it also works on documents that
+    /// fail to parse.
+    pub fn summary_line(content: &str, summary: &str) -> Option<usize> {
+        content
+            .lines()
+            .position(|line| {
+                let line = line.trim_start().trim_start_matches("- ").trim_start();
+                line.strip_prefix("summary:")
+                    .map(|value| scalar_text(value.trim()))
+                    .is_some_and(|value| value == summary)
+            })
+            .map(|index| index + 1)
+    }
+
     /// Write a RequirementConfig to a YAML file
     pub fn write_file<P: AsRef<Path>>(path: P, config: &RequirementConfig) -> Result<()> {
         let yaml = Self::to_yaml(config)?;
@@ -35,6 +89,16 @@ impl Parser {
     }
 }
 
+/// Text of a single-line YAML scalar, without quotes or a trailing comment
+fn scalar_text(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(rest) = value.strip_prefix(quote) {
+            return rest.find(quote).map_or(rest, |end| &rest[..end]);
+        }
+    }
+    value.split(" #").next().unwrap_or(value).trim_end()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,6 +141,7 @@ requirements:
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![Requirement::new("Test")],
         };
 
@@ -120,12 +185,15 @@ requirements:
     #[test]
     fn test_to_yaml() {
         let mut req = Requirement::new("Test Requirement");
-        req.owner = Some(crate::types::OwnerReference::String("test@example.com".to_string()));
+        req.owner = Some(crate::types::OwnerReference::String(
+            "test@example.com".to_string(),
+        ));
         req.status = Some(crate::types::Status::Draft);
 
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![req],
         };
 
@@ -164,7 +232,7 @@ requirements:
 
         let config = Parser::parse_str(yaml).unwrap();
         let req = &config.requirements[0];
-        
+
         assert_eq!(req.summary, "Complete Requirement");
         assert_eq!(req.name, Some("REQ-001".to_string()));
         assert!(req.description.is_some());
@@ -176,5 +244,109 @@ requirements:
         assert_eq!(req.tags.len(), 2);
         assert_eq!(req.further_information.len(), 1);
     }
-}
 
+    #[test]
+    fn test_parse_with_templates() {
+        let yaml = r#"
+version: "1.0"
+templates:
+  - name: security-default
+    owner: security@example.com
+    priority: high
+    tags:
+      - security
+requirements:
+  - summary: Encrypt data at rest
+    template: security-default
+    tags:
+      - storage
+"#;
+
+        let config = Parser::parse_str(yaml).unwrap();
+        let req = &config.requirements[0];
+        assert_eq!(req.priority, Some(crate::types::Priority::High));
+        assert_eq!(req.owner.as_ref().unwrap().as_str(), "security@example.com");
+        assert_eq!(req.tags, vec!["security", "storage"]);
+    }
+
+    #[test]
+    fn test_parse_unknown_template() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - summary: Test
+    template: missing
+"#;
+
+        let result = Parser::parse_str(yaml);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_yaml_is_deterministic() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - tags: [b, a]
+    owner: test@example.com
+    summary: Parent
+    requirements:
+      - Child
+      - summary: Child
+        priority: low
+"#;
+
+        let config = Parser::parse_str(yaml).unwrap();
+        let first = Parser::to_yaml(&config).unwrap();
+        let second = Parser::to_yaml(&Parser::parse_str(&first).unwrap()).unwrap();
+        assert_eq!(first, second);
+        assert!(first.find("summary").unwrap() < first.find("owner").unwrap());
+    }
+
+    #[test]
+    fn test_normalize_explicit_roundtrip() {
+        use crate::serialize::EmptyFields;
+
+        let yaml = r#"
+version: "1.0"
+templates:
+  - name: base
+    priority: high
+requirements:
+  - summary: Test
+    template: base
+"#;
+        let options = SerializeOptions {
+            empty_fields: EmptyFields::Explicit,
+        };
+
+        let normalized = Parser::normalize_str(yaml, &options).unwrap();
+        assert!(normalized.contains("description: null"));
+        assert!(normalized.contains("tags: []"));
+        // Template defaults are not inlined by normalization
+        assert!(!normalized.contains("priority: high\n  requirements"));
+
+        let again = Parser::normalize_str(&normalized, &options).unwrap();
+        assert_eq!(normalized, again);
+
+        let stripped = Parser::normalize_str(&normalized, &SerializeOptions::default()).unwrap();
+        assert!(!stripped.contains("null"));
+        assert_eq!(
+            Parser::parse_str(&stripped).unwrap(),
+            Parser::parse_str(yaml).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_summary_line() {
+        let yaml = "version: \"1.0\"\nrequirements:\n  - summary: First\n    requirements:\n      - summary: \"Second\"\n      - name: x\n        summary: 'Third'\n";
+        assert_eq!(Parser::summary_line(yaml, "First"), Some(3));
+        assert_eq!(Parser::summary_line(yaml, "Second"), Some(5));
+        assert_eq!(Parser::summary_line(yaml, "Third"), Some(7));
+        assert_eq!(Parser::summary_line(yaml, "Missing"), None);
+
+        let yaml = "requirements:\n  - summary: First # why\n  - summary: 'Second' # it's\n";
+        assert_eq!(Parser::summary_line(yaml, "First"), Some(2));
+        assert_eq!(Parser::summary_line(yaml, "Second"), Some(3));
+    }
+}
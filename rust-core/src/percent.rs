@@ -0,0 +1,71 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Percent-encoding of URL components (RFC 3986)
+
+use std::fmt::Write;
+
+/// Encode every byte of `value` other than the unreserved characters
+/// `A-Z a-z 0-9 - . _ ~`, so it can be placed in any part of a URL
+pub(crate) fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{:02X}", byte);
+        }
+    }
+    out
+}
+
+/// Decode the `%XX` escapes of a URL component, such as the path of a
+/// `file://` URI, leaving malformed escapes as they are
+pub(crate) fn decode(value: &str) -> String {
+    decode_bytes(value, false)
+}
+
+/// Decode the `%XX` escapes and `+` of a query string component, leaving
+/// malformed escapes as they are
+pub(crate) fn decode_query(value: &str) -> String {
+    decode_bytes(value, true)
+}
+
+fn decode_bytes(value: &str, plus_is_space: bool) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' if plus_is_space => out.push(b' '),
+            b'%' if i + 2 < bytes.len()
+                && bytes[i + 1].is_ascii_hexdigit()
+                && bytes[i + 2].is_ascii_hexdigit() =>
+            {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                out.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                i += 2;
+            }
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_and_decode() {
+        assert_eq!(encode("Login page/ä?x=1"), "Login%20page%2F%C3%A4%3Fx%3D1");
+        assert_eq!(
+            decode_query(&encode("Login page/ä?x=1")),
+            "Login page/ä?x=1"
+        );
+        assert_eq!(decode_query("a%20b+c%2"), "a b c%2");
+        assert_eq!(decode("C++%20notes/%E2%9C%93"), "C++ notes/✓");
+    }
+}
@@ -0,0 +1,546 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Filter expressions for selecting requirements
+//!
+//! An expression compares requirement fields with values and combines the
+//! comparisons with `&&`, `||`, `!` and parentheses:
+//!
+//! ```text
+//! status==draft && priority==critical && tag=safety && owner=@alice
+//! (status==approved || status==implemented) && !tag=legacy
+//! summary~"audit log"
+//! ```
+//!
+//! `==` and `=` test equality, `!=` inequality and `~` a case-insensitive
+//! substring. For `tag` a comparison holds if it holds for any of the tags.
+//! Values may be quoted to include spaces or operator characters.
+//!
+//! Expressions match [`Requirement`]s and the [`RequirementRef`]s of
+//! [`Parser::parse_borrowed`](crate::Parser::parse_borrowed) alike, so a
+//! query can run without copying the text of every requirement.
+
+use crate::types::{
+    Confidentiality, Priority, Requirement, RequirementConfig, RequirementConfigRef,
+    RequirementRef, Status,
+};
+use crate::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// A requirement field that can be compared in an expression
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Summary,
+    Name,
+    Description,
+    Status,
+    Priority,
+    Confidentiality,
+    Tag,
+    Owner,
+    Release,
+    Milestone,
+    Template,
+}
+
+impl Field {
+    /// Names accepted in expressions, in the order of the variants
+    pub const NAMES: &'static [&'static str] = &[
+        "summary",
+        "name",
+        "description",
+        "status",
+        "priority",
+        "confidentiality",
+        "tag",
+        "owner",
+        "release",
+        "milestone",
+        "template",
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "summary" => Field::Summary,
+            "name" => Field::Name,
+            "description" => Field::Description,
+            "status" => Field::Status,
+            "priority" => Field::Priority,
+            "confidentiality" => Field::Confidentiality,
+            "tag" | "tags" => Field::Tag,
+            "owner" => Field::Owner,
+            "release" | "target_release" => Field::Release,
+            "milestone" => Field::Milestone,
+            "template" => Field::Template,
+            _ => return None,
+        })
+    }
+}
+
+/// A requirement whose fields an [`Expr`] can compare, owned or borrowed
+pub trait Queryable {
+    /// The requirement's values for `field`; several only for tags
+    fn values(&self, field: Field) -> Vec<&str>;
+}
+
+impl Queryable for Requirement {
+    fn values(&self, field: Field) -> Vec<&str> {
+        match field {
+            Field::Summary => vec![self.summary.as_str()],
+            Field::Name => self.name.as_deref().into_iter().collect(),
+            Field::Description => self.description.as_deref().into_iter().collect(),
+            Field::Status => self.status.map(Status::as_str).into_iter().collect(),
+            Field::Priority => self.priority.map(Priority::as_str).into_iter().collect(),
+            Field::Confidentiality => self
+                .confidentiality
+                .map(Confidentiality::as_str)
+                .into_iter()
+                .collect(),
+            Field::Tag => self.tags.iter().map(String::as_str).collect(),
+            Field::Owner => self.owner.iter().map(|o| o.as_str()).collect(),
+            Field::Release => self.target_release.as_deref().into_iter().collect(),
+            Field::Milestone => self.milestone.as_deref().into_iter().collect(),
+            Field::Template => self.template.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+impl Queryable for RequirementRef<'_> {
+    fn values(&self, field: Field) -> Vec<&str> {
+        match field {
+            Field::Summary => vec![self.summary.as_ref()],
+            Field::Name => self.name.as_deref().into_iter().collect(),
+            Field::Description => self.description.as_deref().into_iter().collect(),
+            Field::Status => self.status.map(Status::as_str).into_iter().collect(),
+            Field::Priority => self.priority.map(Priority::as_str).into_iter().collect(),
+            Field::Confidentiality => self
+                .confidentiality
+                .map(Confidentiality::as_str)
+                .into_iter()
+                .collect(),
+            Field::Tag => self.tags.iter().map(AsRef::as_ref).collect(),
+            Field::Owner => self.owner.as_deref().into_iter().collect(),
+            Field::Release => self.target_release.as_deref().into_iter().collect(),
+            Field::Milestone => self.milestone.as_deref().into_iter().collect(),
+            Field::Template => self.template.as_deref().into_iter().collect(),
+        }
+    }
+}
+
+/// How a field is compared with a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Eq,
+    Ne,
+    Contains,
+}
+
+/// A parsed filter expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Compare { field: Field, op: Op, value: String },
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+
+impl Expr {
+    /// Parse an expression, e.g. `status==draft && tag=safety`
+    pub fn parse(input: &str) -> Result<Self> {
+        let invalid = |msg: String| Error::custom(format!("Invalid query '{}': {}", input, msg));
+        let tokens = tokenize(input).map_err(invalid)?;
+        let mut parser = ExprParser { tokens, pos: 0 };
+        let expr = parser.or().map_err(invalid)?;
+        match parser.tokens.get(parser.pos) {
+            None => Ok(expr),
+            Some(token) => Err(invalid(format!("unexpected {}", token))),
+        }
+    }
+
+    /// Check whether a requirement satisfies the expression
+    pub fn matches<R: Queryable + ?Sized>(&self, req: &R) -> bool {
+        match self {
+            Expr::Compare { field, op, value } => {
+                let values = req.values(*field);
+                match op {
+                    Op::Eq => values.iter().any(|v| v == value),
+                    Op::Ne => !values.iter().any(|v| v == value),
+                    Op::Contains => {
+                        let value = value.to_lowercase();
+                        values.iter().any(|v| v.to_lowercase().contains(&value))
+                    }
+                }
+            }
+            Expr::And(left, right) => left.matches(req) && right.matches(req),
+            Expr::Or(left, right) => left.matches(req) || right.matches(req),
+            Expr::Not(expr) => !expr.matches(req),
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Expr::parse(s)
+    }
+}
+
+impl RequirementConfig {
+    /// Requirements matching `expr`, in document order
+    pub fn query(&self, expr: &Expr) -> Vec<&Requirement> {
+        self.all_requirements()
+            .into_iter()
+            .filter(|&req| expr.matches(req))
+            .collect()
+    }
+}
+
+impl<'a> RequirementConfigRef<'a> {
+    /// Requirements matching `expr`, in document order
+    pub fn query(&self, expr: &Expr) -> Vec<&RequirementRef<'a>> {
+        self.all_requirements()
+            .into_iter()
+            .filter(|&req| expr.matches(req))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "'{}'", word),
+            Token::Op(Op::Eq) => f.write_str("'=='"),
+            Token::Op(Op::Ne) => f.write_str("'!='"),
+            Token::Op(Op::Contains) => f.write_str("'~'"),
+            Token::And => f.write_str("'&&'"),
+            Token::Or => f.write_str("'||'"),
+            Token::Not => f.write_str("'!'"),
+            Token::Open => f.write_str("'('"),
+            Token::Close => f.write_str("')'"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '~' => Token::Op(Op::Contains),
+            '=' => {
+                chars.next_if_eq(&'=');
+                Token::Op(Op::Eq)
+            }
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Op(Op::Ne),
+            '!' => Token::Not,
+            '&' | '|' => {
+                if chars.next_if_eq(&c).is_none() {
+                    return Err(format!("expected '{}{}'", c, c));
+                }
+                if c == '&' {
+                    Token::And
+                } else {
+                    Token::Or
+                }
+            }
+            '"' | '\'' => {
+                let mut word = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some('\\') => word.extend(chars.next()),
+                        Some(other) => word.push(other),
+                        None => return Err("unterminated quote".to_string()),
+                    }
+                }
+                Token::Word(word)
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || "()~=!&|\"'".contains(next) {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                Token::Word(word)
+            }
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens; `&&` binds tighter than `||`
+struct ExprParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl ExprParser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn or(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> std::result::Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> std::result::Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                if !self.eat(&Token::Close) {
+                    return Err("missing ')'".to_string());
+                }
+                Ok(expr)
+            }
+            Some(Token::Word(name)) => {
+                let field = Field::parse(&name).ok_or_else(|| {
+                    format!(
+                        "unknown field '{}', expected one of {}",
+                        name,
+                        Field::NAMES.join(", ")
+                    )
+                })?;
+                let op = match self.next() {
+                    Some(Token::Op(op)) => op,
+                    _ => return Err(format!("expected an operator after '{}'", name)),
+                };
+                let value = match self.next() {
+                    Some(Token::Word(value)) => value,
+                    _ => return Err(format!("expected a value after '{}'", name)),
+                };
+                check_value(field, op, &value)?;
+                Ok(Expr::Compare { field, op, value })
+            }
+            Some(token) => Err(format!("unexpected {}", token)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+}
+
+/// Reject status and priority values that could never match
+fn check_value(field: Field, op: Op, value: &str) -> std::result::Result<(), String> {
+    if op == Op::Contains {
+        return Ok(());
+    }
+    let valid = match field {
+        Field::Status => serde_yaml::from_str::<Status>(value).is_ok(),
+        Field::Priority => serde_yaml::from_str::<Priority>(value).is_ok(),
+        _ => true,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' is not a valid {}",
+            value,
+            Field::NAMES[field as usize]
+        ))
+    }
+}
+
+/// Render requirements as a plain text table for terminals
+pub fn to_table<R: Queryable>(requirements: &[&R]) -> String {
+    let headers = ["Summary", "Name", "Status", "Priority", "Owner", "Tags"];
+    let columns = [
+        Field::Summary,
+        Field::Name,
+        Field::Status,
+        Field::Priority,
+        Field::Owner,
+        Field::Tag,
+    ];
+    let rows: Vec<[String; 6]> = requirements
+        .iter()
+        .map(|req| columns.map(|field| req.values(field).join(", ")))
+        .collect();
+    let mut widths = headers.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        out.push_str(padded.join("  ").trim_end());
+        out.push('\n');
+    };
+    line(&headers.map(String::from));
+    line(&widths.map(|w| "-".repeat(w)));
+    for row in &rows {
+        line(row);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Braking
+    status: draft
+    priority: critical
+    owner: "@alice"
+    tags: [safety, hardware]
+    requirements:
+      - summary: Emergency stop
+        status: approved
+        priority: critical
+        owner: "@bob"
+        tags: [safety]
+  - summary: Audit log
+    name: AUDIT
+    status: draft
+    priority: low
+    tags: [legacy]
+"#;
+
+    fn summaries(query: &str) -> Vec<String> {
+        let config = Parser::parse_str(YAML).unwrap();
+        let expr = Expr::parse(query).unwrap();
+        config
+            .query(&expr)
+            .into_iter()
+            .map(|req| req.summary.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let expr: Expr = "status==draft || tag=safety && !owner=@bob"
+            .parse()
+            .unwrap();
+        let compare = |field, op, value: &str| Expr::Compare {
+            field,
+            op,
+            value: value.to_string(),
+        };
+        assert_eq!(
+            expr,
+            Expr::Or(
+                Box::new(compare(Field::Status, Op::Eq, "draft")),
+                Box::new(Expr::And(
+                    Box::new(compare(Field::Tag, Op::Eq, "safety")),
+                    Box::new(Expr::Not(Box::new(compare(Field::Owner, Op::Eq, "@bob")))),
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn test_query() {
+        assert_eq!(
+            summaries("status==draft && priority==critical && tag=safety && owner=@alice"),
+            vec!["Braking"]
+        );
+        assert_eq!(
+            summaries("tag=safety && status!=draft"),
+            vec!["Emergency stop"]
+        );
+        assert_eq!(
+            summaries("(priority==low || owner=@bob) && !tag=hardware"),
+            vec!["Emergency stop", "Audit log"]
+        );
+        assert_eq!(summaries("summary~\"STOP\""), vec!["Emergency stop"]);
+        assert_eq!(summaries("name==AUDIT"), vec!["Audit log"]);
+        assert!(summaries("release==2.0").is_empty());
+    }
+
+    #[test]
+    fn test_borrowed_query_matches_owned() {
+        let owned = Parser::parse_str(YAML).unwrap();
+        let borrowed = Parser::parse_borrowed(YAML).unwrap();
+        for query in ["tag=safety && status!=draft", "owner=@alice", "name==AUDIT"] {
+            let expr = Expr::parse(query).unwrap();
+            let owned = owned.query(&expr);
+            let borrowed = borrowed.query(&expr);
+            assert_eq!(
+                borrowed.iter().map(|req| req.id()).collect::<Vec<_>>(),
+                owned.iter().map(|req| req.id()).collect::<Vec<_>>()
+            );
+            assert_eq!(to_table(&borrowed), to_table(&owned));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (query, message) in [
+            ("colour==red", "unknown field 'colour'"),
+            ("status==finished", "'finished' is not a valid status"),
+            ("status==draft &", "expected '&&'"),
+            ("(tag=a", "missing ')'"),
+            ("tag=a tag=b", "unexpected 'tag'"),
+            ("status", "expected an operator"),
+            ("summary=\"open", "unterminated quote"),
+        ] {
+            let err = Expr::parse(query).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", query, err);
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let expr = Expr::parse("name==AUDIT").unwrap();
+        let table = to_table(&config.query(&expr));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Summary    Name   Status  Priority  Owner  Tags");
+        assert_eq!(
+            lines[2],
+            "Audit log  AUDIT  draft   low              legacy"
+        );
+    }
+}
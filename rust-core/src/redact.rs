@@ -0,0 +1,274 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Exports for readers cleared for part of the requirements
+//!
+//! Requirements are `public`, `internal` or `restricted`:
+//!
+//! ```yaml
+//! - summary: Payment
+//!   requirements:
+//!     - summary: Fraud scoring
+//!       confidentiality: restricted
+//! ```
+//!
+//! A requirement without a level inherits its parent's, and is never less
+//! confidential than its parent. [`redact`] withholds the requirements above
+//! a clearance, such as for documents sent to external suppliers. Withheld
+//! requirements leave a placeholder in their place, so the numbering of the
+//! rest of the document stays the same as in the full export.
+
+use crate::error::Result;
+use crate::metadata::{display_id, MetadataStore};
+use crate::types::{Confidentiality, Requirement, RequirementConfig, RequirementReference};
+use std::collections::{HashMap, HashSet};
+
+/// What is left of a withheld requirement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Redaction {
+    /// A placeholder keeping the requirement's ID and children, which are
+    /// withheld as well
+    #[default]
+    Redact,
+
+    /// A placeholder without ID or children; references to the requirement
+    /// are removed
+    Drop,
+}
+
+/// Confidentiality of every requirement defined in `config`, with inherited
+/// levels resolved, by summary
+pub fn levels(config: &RequirementConfig) -> HashMap<&str, Confidentiality> {
+    let mut levels = HashMap::new();
+    for req in &config.requirements {
+        resolve(req, Confidentiality::Public, &mut levels);
+    }
+    levels
+}
+
+fn resolve<'a>(
+    req: &'a Requirement,
+    inherited: Confidentiality,
+    levels: &mut HashMap<&'a str, Confidentiality>,
+) {
+    let level = req.confidentiality.unwrap_or_default().max(inherited);
+    levels.insert(&req.summary, level);
+    for child in &req.requirements {
+        if let RequirementReference::Full(child) = child {
+            resolve(child, level, levels);
+        }
+    }
+}
+
+/// Copy of `config` for readers cleared for `clearance`
+///
+/// Requirements above the clearance are replaced by placeholders named
+/// `Redacted requirement 1`, `Redacted requirement 2` and so on in document
+/// order. With [`Redaction::Redact`] they keep their generated ID from
+/// `store`, or their `name`.
+pub fn redact(
+    config: &RequirementConfig,
+    clearance: Confidentiality,
+    mode: Redaction,
+    store: Option<&MetadataStore>,
+) -> Result<RequirementConfig> {
+    let taken: HashSet<&str> = config
+        .all_requirements()
+        .into_iter()
+        .map(|req| req.summary.as_str())
+        .collect();
+    let mut placeholders = HashMap::new();
+    let mut count = 0;
+    for req in &config.requirements {
+        name_withheld(
+            req,
+            clearance,
+            Confidentiality::Public,
+            &taken,
+            &mut count,
+            &mut placeholders,
+        );
+    }
+
+    let redactor = Redactor {
+        placeholders,
+        mode,
+        store,
+    };
+    let mut redacted = config.clone();
+    for req in &mut redacted.requirements {
+        redactor.requirement(req)?;
+    }
+    Ok(redacted)
+}
+
+/// Pick placeholder summaries for withheld requirements in document order
+fn name_withheld(
+    req: &Requirement,
+    clearance: Confidentiality,
+    inherited: Confidentiality,
+    taken: &HashSet<&str>,
+    count: &mut usize,
+    placeholders: &mut HashMap<String, String>,
+) {
+    let level = req.confidentiality.unwrap_or_default().max(inherited);
+    if level > clearance && !placeholders.contains_key(&req.summary) {
+        let placeholder = loop {
+            *count += 1;
+            let placeholder = format!("Redacted requirement {}", count);
+            if !taken.contains(placeholder.as_str()) {
+                break placeholder;
+            }
+        };
+        placeholders.insert(req.summary.clone(), placeholder);
+    }
+    for child in &req.requirements {
+        if let RequirementReference::Full(child) = child {
+            name_withheld(child, clearance, level, taken, count, placeholders);
+        }
+    }
+}
+
+struct Redactor<'a> {
+    /// Placeholder summary by summary of each withheld requirement
+    placeholders: HashMap<String, String>,
+    mode: Redaction,
+    store: Option<&'a MetadataStore>,
+}
+
+impl Redactor<'_> {
+    fn requirement(&self, req: &mut Requirement) -> Result<()> {
+        if let Some(placeholder) = self.placeholders.get(&req.summary) {
+            let mut withheld = Requirement::new(placeholder.clone());
+            if self.mode == Redaction::Redact {
+                withheld.name = display_id(self.store, req)?;
+                withheld.requirements = std::mem::take(&mut req.requirements);
+            }
+            *req = withheld;
+        }
+
+        req.superseded_by = req
+            .superseded_by
+            .take()
+            .and_then(|summary| self.reference(summary));
+        req.supersedes = std::mem::take(&mut req.supersedes)
+            .into_iter()
+            .filter_map(|summary| self.reference(summary))
+            .collect();
+        let mut children = vec![];
+        for child in std::mem::take(&mut req.requirements) {
+            match child {
+                RequirementReference::Full(mut child) => {
+                    self.requirement(&mut child)?;
+                    children.push(RequirementReference::Full(child));
+                }
+                RequirementReference::Reference(summary) => {
+                    if let Some(summary) = self.reference(summary) {
+                        children.push(RequirementReference::Reference(summary));
+                    }
+                }
+            }
+        }
+        req.requirements = children;
+        Ok(())
+    }
+
+    /// A reference to `summary` as it reads after redaction, if it is kept
+    fn reference(&self, summary: String) -> Option<String> {
+        match (self.placeholders.get(&summary), self.mode) {
+            (None, _) => Some(summary),
+            (Some(placeholder), Redaction::Redact) => Some(placeholder.clone()),
+            (Some(_), Redaction::Drop) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Checkout
+    description: Customers pay for their cart
+    requirements:
+      - summary: Fraud scoring
+        name: PAY-7
+        description: Orders are scored by the model in vault 3
+        confidentiality: restricted
+        requirements:
+          - summary: Score threshold
+            confidentiality: public
+      - summary: Receipts
+        requirements:
+          - Fraud scoring
+  - summary: Payment reports
+    confidentiality: internal
+    supersedes:
+      - Fraud scoring
+"#;
+
+    fn summaries(config: &RequirementConfig) -> Vec<&str> {
+        config
+            .all_requirements()
+            .into_iter()
+            .map(|req| req.summary.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_levels_are_inherited() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let levels = levels(&config);
+        assert_eq!(levels["Checkout"], Confidentiality::Public);
+        assert_eq!(levels["Fraud scoring"], Confidentiality::Restricted);
+        // Not less confidential than its parent
+        assert_eq!(levels["Score threshold"], Confidentiality::Restricted);
+        assert_eq!(levels["Payment reports"], Confidentiality::Internal);
+    }
+
+    #[test]
+    fn test_redact() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let redacted = redact(&config, Confidentiality::Internal, Redaction::Redact, None).unwrap();
+        let all = redacted.all_requirements();
+        let fraud = all[1];
+        assert_eq!(fraud.summary, "Redacted requirement 1");
+        assert_eq!(fraud.name.as_deref(), Some("PAY-7"));
+        assert_eq!(fraud.description, None);
+        assert_eq!(fraud.confidentiality, None);
+        assert_eq!(fraud.requirements[0].summary(), "Redacted requirement 2");
+        assert_eq!(all[3].summary, "Receipts");
+        assert_eq!(all[3].requirements[0].summary(), "Redacted requirement 1");
+        assert_eq!(
+            redacted.requirements[1].supersedes,
+            vec!["Redacted requirement 1"]
+        );
+
+        let public = redact(&config, Confidentiality::Public, Redaction::Redact, None).unwrap();
+        assert_eq!(public.requirements[1].summary, "Redacted requirement 3");
+    }
+
+    #[test]
+    fn test_drop_keeps_numbering() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let dropped = redact(&config, Confidentiality::Internal, Redaction::Drop, None).unwrap();
+        assert_eq!(
+            summaries(&dropped),
+            vec![
+                "Checkout",
+                "Redacted requirement 1",
+                "Receipts",
+                "Payment reports"
+            ]
+        );
+        let all = dropped.all_requirements();
+        assert_eq!(all[1].name, None);
+        assert!(all[1].requirements.is_empty());
+        assert!(all[2].requirements.is_empty());
+        assert!(dropped.requirements[1].supersedes.is_empty());
+    }
+}
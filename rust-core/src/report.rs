@@ -0,0 +1,520 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Interactive HTML report
+//!
+//! Renders requirements as a single self-contained HTML page, with styles
+//! and scripts inlined so it can be published as a CI artifact. The page
+//! shows the hierarchy as a collapsible tree and filters it by status,
+//! owner, tag and free text. Terms of the project glossary link to their
+//! definitions below the tree.
+
+use crate::error::Result;
+use crate::export::glossary::{glossary_html, TermLinker};
+use crate::graph::analysis::stale_requirements;
+use crate::metadata::{display_id, kebab_case, MetadataStore};
+use crate::types::{Attachment, Requirement, RequirementConfig, RequirementReference, Status};
+use crate::validator::Glossary;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+
+/// Options for [`to_html`]
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Page title and heading
+    pub title: String,
+
+    /// Days after which unchanged draft and proposed requirements are
+    /// marked stale, or `None` to not mark them
+    pub stale_after_days: Option<u32>,
+
+    /// The time staleness is measured up to
+    pub now: DateTime<Utc>,
+
+    /// Glossary whose terms are linked, usually `.rqm/glossary.yml`
+    pub glossary: Option<Glossary>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            title: "Requirements".to_string(),
+            stale_after_days: None,
+            now: Utc::now(),
+            glossary: None,
+        }
+    }
+}
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+.filters { display: flex; gap: .5rem; flex-wrap: wrap; margin-bottom: 1rem; }
+.filters input { flex: 1; min-width: 12rem; }
+ul.tree { list-style: none; padding-left: 1.25rem; }
+li.req { margin: .25rem 0; }
+li.req.hidden { display: none; }
+summary { cursor: pointer; }
+.id { font-family: monospace; color: #555; margin-right: .5rem; }
+.badge { font-size: .75rem; padding: 0 .4rem; border-radius: .5rem; margin-left: .3rem; background: #eee; }
+.status-draft { background: lightgray; }
+.status-proposed { background: lightyellow; }
+.status-approved { background: lightblue; }
+.status-implemented { background: palegreen; }
+.status-verified { background: green; color: white; }
+.status-deprecated { background: gray; color: white; }
+.stale { background: orange; }
+.details { margin: .25rem 0 .5rem 1rem; font-size: .9rem; }
+.details p { margin: .2rem 0; white-space: pre-wrap; }
+a.term { color: inherit; text-decoration: underline dotted; }
+"#;
+
+const SCRIPT: &str = r#"
+function applyFilters() {
+  const status = document.getElementById('status').value;
+  const owner = document.getElementById('owner').value;
+  const tag = document.getElementById('tag').value;
+  const text = document.getElementById('search').value.toLowerCase();
+  const matches = (li) =>
+    (!status || li.dataset.status === status) &&
+    (!owner || li.dataset.owner === owner) &&
+    (!tag || li.dataset.tags.split(' ').includes(tag)) &&
+    (!text || li.dataset.search.includes(text));
+  const filtering = status || owner || tag || text;
+  const visit = (li) => {
+    let visible = matches(li);
+    for (const child of li.querySelectorAll(':scope > details > ul > li.req')) {
+      if (visit(child)) visible = true;
+    }
+    li.classList.toggle('hidden', !visible);
+    const details = li.querySelector(':scope > details');
+    if (filtering && visible) details.open = true;
+    return visible;
+  };
+  document.querySelectorAll('#tree > li.req').forEach(visit);
+}
+document.querySelectorAll('.filters select, .filters input')
+  .forEach((el) => el.addEventListener('input', applyFilters));
+"#;
+
+/// Render requirements as a self-contained interactive HTML page
+pub fn to_html(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+    options: &ReportOptions,
+) -> Result<String> {
+    let all = config.all_requirements();
+    let mut statuses = BTreeSet::new();
+    let mut owners = BTreeSet::new();
+    let mut tags = BTreeSet::new();
+    for req in &all {
+        statuses.extend(req.status.map(|status| status.to_string()));
+        owners.extend(req.owner.as_ref().map(|owner| owner.as_str().to_string()));
+        tags.extend(req.tags.iter().cloned());
+    }
+    let stale: HashMap<String, i64> = match options.stale_after_days {
+        Some(days) => stale_requirements(config, store, days, options.now)?
+            .into_iter()
+            .map(|stale| (stale.summary, stale.days))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</h1>\n<p>{} requirements",
+        escape(&options.title),
+        STYLE,
+        escape(&options.title),
+        all.len()
+    );
+    if !stale.is_empty() {
+        let _ = write!(out, ", {} stale", stale.len());
+    }
+    out.push_str("</p>\n");
+
+    out.push_str("<div class=\"filters\">\n");
+    filter(&mut out, "status", "All statuses", &statuses);
+    filter(&mut out, "owner", "All owners", &owners);
+    filter(&mut out, "tag", "All tags", &tags);
+    out.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search\">\n</div>\n");
+
+    let glossary = options
+        .glossary
+        .as_ref()
+        .map(Glossary::entries)
+        .unwrap_or_default();
+    let terms = TermLinker::new(&glossary);
+    out.push_str("<ul class=\"tree\" id=\"tree\">\n");
+    for req in &config.requirements {
+        render(&mut out, req, store, &stale, &terms)?;
+    }
+    out.push_str("</ul>\n");
+    if !glossary.is_empty() {
+        out.push_str(&glossary_html(&glossary));
+    }
+    let _ = write!(out, "<script>{}</script>\n</body>\n</html>\n", SCRIPT);
+    Ok(out)
+}
+
+fn filter(out: &mut String, id: &str, all: &str, values: &BTreeSet<String>) {
+    let _ = writeln!(out, "<select id=\"{}\">", id);
+    let _ = writeln!(out, "<option value=\"\">{}</option>", all);
+    for value in values {
+        let _ = writeln!(out, "<option>{}</option>", escape(value));
+    }
+    out.push_str("</select>\n");
+}
+
+fn render(
+    out: &mut String,
+    req: &Requirement,
+    store: Option<&MetadataStore>,
+    stale: &HashMap<String, i64>,
+    terms: &TermLinker,
+) -> Result<()> {
+    let id = display_id(store, req)?;
+    let status = req.status.map(Status::as_str).unwrap_or_default();
+    let owner = req.owner.as_ref().map(|o| o.as_str()).unwrap_or_default();
+    let search = [
+        Some(req.summary.as_str()),
+        id.as_deref(),
+        req.description.as_deref(),
+        req.acceptance_test.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ")
+    .to_lowercase();
+
+    let _ = writeln!(
+        out,
+        "<li class=\"req\" id=\"req-{}\" data-status=\"{}\" data-owner=\"{}\" data-tags=\"{}\" data-search=\"{}\">",
+        kebab_case(&req.summary),
+        status,
+        escape(owner),
+        escape(&req.tags.join(" ")),
+        escape(&search)
+    );
+    out.push_str("<details>\n<summary>");
+    if let Some(id) = &id {
+        let _ = write!(out, "<span class=\"id\">{}</span>", escape(id));
+    }
+    out.push_str(&escape(&req.summary));
+    if !status.is_empty() {
+        let _ = write!(out, "<span class=\"badge status-{0}\">{0}</span>", status);
+    }
+    if let Some(days) = stale.get(&req.summary) {
+        let _ = write!(
+            out,
+            "<span class=\"badge stale\" title=\"Unchanged for {} days\">stale</span>",
+            days
+        );
+    }
+    if let Some(priority) = req.priority {
+        let _ = write!(out, "<span class=\"badge\">{}</span>", priority);
+    }
+    for tag in &req.tags {
+        let _ = write!(out, "<span class=\"badge\">#{}</span>", escape(tag));
+    }
+    out.push_str("</summary>\n<div class=\"details\">\n");
+
+    let fields = [
+        ("Owner", Some(owner).filter(|o| !o.is_empty()).map(escape)),
+        (
+            "Description",
+            req.description.as_deref().map(|d| terms.html(d)),
+        ),
+        (
+            "Justification",
+            req.justification.as_deref().map(|j| terms.html(j)),
+        ),
+        (
+            "Acceptance test",
+            req.acceptance_test.as_deref().map(|t| terms.html(t)),
+        ),
+        ("Target release", req.target_release.as_deref().map(escape)),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value {
+            let _ = writeln!(out, "<p><strong>{}:</strong> {}</p>", label, value);
+        }
+    }
+    let no_templates = BTreeMap::new();
+    let templates = store.map_or(&no_templates, |store| {
+        &store.project_config().external_systems
+    });
+    if let Some(links) = external_links(req, templates) {
+        let _ = writeln!(
+            out,
+            "<p><strong>External references:</strong> {}</p>",
+            links
+        );
+    }
+    // Linked rather than shown, so the page stays self-contained
+    let href = |attachment: &Attachment| {
+        let parts: Vec<_> = attachment
+            .relative_path()?
+            .iter()
+            .map(|part| part.to_string_lossy().into_owned())
+            .collect();
+        Some(parts.join("/"))
+    };
+    if let Some(attachments) = attachments_html(req, href, false) {
+        let _ = writeln!(out, "<p><strong>Attachments:</strong></p>\n{}", attachments);
+    }
+    out.push_str("</div>\n");
+
+    if !req.requirements.is_empty() {
+        out.push_str("<ul class=\"tree\">\n");
+        for child in &req.requirements {
+            match child {
+                RequirementReference::Full(child) => render(out, child, store, stale, terms)?,
+                RequirementReference::Reference(summary) => {
+                    let _ = writeln!(
+                        out,
+                        "<li>&rarr; <a href=\"#req-{}\">{}</a></li>",
+                        kebab_case(summary),
+                        escape(summary)
+                    );
+                }
+            }
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</details>\n</li>\n");
+    Ok(())
+}
+
+/// External references of a requirement as HTML, linked where they
+/// resolve to an http(s) URL
+pub(crate) fn external_links(
+    req: &Requirement,
+    templates: &BTreeMap<String, String>,
+) -> Option<String> {
+    if req.external_refs.is_empty() {
+        return None;
+    }
+    let links: Vec<String> = req
+        .external_refs
+        .iter()
+        .map(|reference| match reference.resolve_url(templates) {
+            Some(url) => web_link(&url, &reference.to_string()),
+            None => escape(&reference.to_string()),
+        })
+        .collect();
+    Some(links.join(", "))
+}
+
+/// Attachments of a requirement as HTML, or `None` if it has none
+///
+/// `href` gives the address of an attached file, or `None` if there is
+/// none to link to. With `figures`, attached image files are shown rather
+/// than linked. Web pages are linked only when they are http(s) URLs.
+pub(crate) fn attachments_html(
+    req: &Requirement,
+    href: impl Fn(&Attachment) -> Option<String>,
+    figures: bool,
+) -> Option<String> {
+    if req.attachments.is_empty() {
+        return None;
+    }
+    let mut shown = String::new();
+    let mut listed = String::new();
+    for attachment in &req.attachments {
+        let label = escape(attachment.label());
+        let href = if attachment.is_url() {
+            Some(attachment.path.trim().to_string())
+        } else if attachment.has_scheme() {
+            None
+        } else {
+            href(attachment)
+        };
+        match href {
+            Some(href) if figures && attachment.is_image() && !attachment.is_url() => {
+                let _ = writeln!(
+                    shown,
+                    "<figure><img src=\"{}\" alt=\"{}\"><figcaption>{}</figcaption></figure>",
+                    escape(&href),
+                    label,
+                    label
+                );
+            }
+            Some(href) => {
+                let _ = writeln!(
+                    listed,
+                    "<li><a href=\"{}\">{}</a></li>",
+                    escape(&href),
+                    label
+                );
+            }
+            None => {
+                let _ = writeln!(listed, "<li>{}</li>", label);
+            }
+        }
+    }
+    if !listed.is_empty() {
+        let _ = write!(shown, "<ul class=\"attachments\">\n{}</ul>", listed);
+    }
+    Some(shown.trim_end().to_string())
+}
+
+/// `text` linked to `url` if it is an http(s) URL, or else just escaped
+pub(crate) fn web_link(url: &str, text: &str) -> String {
+    let url = url.trim();
+    if crate::validator::is_well_formed_url(url) {
+        format!("<a href=\"{}\">{}</a>", escape(url), escape(text))
+    } else {
+        escape(text)
+    }
+}
+
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::analysis::DEFAULT_STALE_DAYS;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login <SSO>
+    status: approved
+    owner: "@alice"
+    tags: [auth]
+    description: Users sign in & stay signed in
+    requirements:
+      - summary: Lockout
+        status: draft
+        updated_at: "2020-01-01T00:00:00Z"
+      - Export
+  - summary: Export
+    name: EXP
+"#;
+
+    #[test]
+    fn test_report_renders_tree_and_filters() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let options = ReportOptions {
+            stale_after_days: Some(DEFAULT_STALE_DAYS),
+            now: DateTime::parse_from_rfc3339("2020-06-01T00:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            ..ReportOptions::default()
+        };
+        let html = to_html(&config, None, &options).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Login &lt;SSO&gt;"));
+        assert!(html.contains("Users sign in &amp; stay signed in"));
+        assert!(html.contains("<option>approved</option>"));
+        assert!(html.contains("<option>draft</option>"));
+        assert!(html.contains("<option>@alice</option>"));
+        assert!(html.contains("<option>auth</option>"));
+        assert!(html.contains("<a href=\"#req-export\">Export</a>"));
+        assert!(html.contains("<span class=\"id\">EXP</span>"));
+        assert!(html.contains("<p>3 requirements, 1 stale</p>"));
+        assert!(html.contains("<span class=\"badge stale\" title=\"Unchanged for 152 days\""));
+
+        // Stale requirements are only marked on request
+        let html = to_html(&config, None, &ReportOptions::default()).unwrap();
+        assert!(html.contains("<p>3 requirements</p>"));
+        assert!(!html.contains("badge stale"));
+        // Nothing is loaded from elsewhere
+        assert!(!html.contains("src="));
+    }
+
+    #[test]
+    fn test_report_links_glossary_terms() {
+        let config = Parser::parse_str(YAML).unwrap();
+        let options = ReportOptions {
+            glossary: Some(Glossary {
+                terms: vec![crate::GlossaryEntry::new("Sign in", "Prove who you are")],
+                ..Glossary::default()
+            }),
+            ..ReportOptions::default()
+        };
+        let html = to_html(&config, None, &options).unwrap();
+
+        assert!(html.contains(
+            "Users <a class=\"term\" href=\"#term-sign-in\" title=\"Prove who you are\">sign in</a> &amp; stay signed in"
+        ));
+        assert!(html.contains("<dt id=\"term-sign-in\">Sign in</dt>"));
+    }
+
+    #[test]
+    fn test_external_links_only_link_http() {
+        let mut req = Requirement::new("Login");
+        req.external_refs = vec![
+            crate::types::ExternalRef {
+                system: "doors".to_string(),
+                id: "42".to_string(),
+                url: Some("javascript://alert(1)".to_string()),
+            },
+            crate::types::ExternalRef {
+                system: "jira".to_string(),
+                id: "AUTH 1".to_string(),
+                url: None,
+            },
+        ];
+        let templates = BTreeMap::from([(
+            "jira".to_string(),
+            "https://example.atlassian.net/browse/{id}".to_string(),
+        )]);
+        assert_eq!(
+            external_links(&req, &templates).unwrap(),
+            "doors:42, <a href=\"https://example.atlassian.net/browse/AUTH%201\">jira:AUTH 1</a>"
+        );
+    }
+
+    #[test]
+    fn test_report_links_attachments() {
+        let mut config = Parser::parse_str(YAML).unwrap();
+        config.requirements[1].attachments = vec![
+            crate::Attachment {
+                caption: Some("Export <flow>".to_string()),
+                ..crate::Attachment::new("docs/export.png")
+            },
+            crate::Attachment::new("https://example.com/spec"),
+            crate::Attachment::new("javascript://alert(1)"),
+        ];
+        let html = to_html(&config, None, &ReportOptions::default()).unwrap();
+        assert!(html.contains(
+            "<ul class=\"attachments\">\n<li><a href=\"docs/export.png\">Export &lt;flow&gt;</a></li>\n\
+             <li><a href=\"https://example.com/spec\">https://example.com/spec</a></li>\n\
+             <li>javascript://alert(1)</li>\n</ul>"
+        ));
+        assert!(!html.contains("<img"));
+    }
+
+    #[test]
+    fn test_report_uses_generated_ids() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REP".to_string()).unwrap();
+        let config = Parser::parse_str(YAML).unwrap();
+        store
+            .get_or_create_metadata(config.all_requirements()[0])
+            .unwrap();
+
+        let options = ReportOptions {
+            title: "Product".to_string(),
+            ..ReportOptions::default()
+        };
+        let html = to_html(&config, Some(&store), &options).unwrap();
+        assert!(html.contains("<title>Product</title>"));
+        assert!(html.contains("<span class=\"id\">REP-001</span>"));
+        assert_eq!(store.all_metadata().unwrap().len(), 1);
+    }
+}
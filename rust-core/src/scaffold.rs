@@ -0,0 +1,191 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Starter files for a new project
+//!
+//! [`scaffold`] sets up the `.rqm` metadata directory together with a
+//! starter `requirements.yml`, `lint.yml`, glossary and editor settings, so
+//! a new project validates and gets schema completion from the start.
+//! Files that already exist are left alone.
+
+use crate::error::{Error, Result};
+use crate::metadata::{IdScheme, MetadataStore, ProjectConfig};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const REQUIREMENTS: &str = r#"# yaml-language-server: $schema=.rqm/schema.json
+version: "1.0"
+
+requirements:
+  - summary: Example system
+    description: |
+      Describe the system and its purpose.
+    status: draft
+    requirements:
+      - summary: Example requirement
+        description: |
+          Describe what the system shall do.
+        justification: |
+          Explain why the system needs it.
+        acceptance_test: |
+          Describe how to verify the requirement.
+        status: draft
+"#;
+
+const LINT: &str = r#"# Lint rules of this project; rule levels are off, info, warning or error
+profile: standard
+rules: {}
+"#;
+
+const GLOSSARY: &str = r#"# Project terminology, checked by the glossary lint rules
+banned:
+  should: shall
+acronyms: {}
+# Terms linked to their definitions in HTML and Markdown exports
+terms: []
+"#;
+
+const EDITORCONFIG: &str = r#"root = true
+
+[*.{yml,yaml}]
+indent_style = space
+indent_size = 2
+insert_final_newline = true
+trim_trailing_whitespace = true
+"#;
+
+const VSCODE_SETTINGS: &str = r#"{
+  "yaml.schemas": {
+    ".rqm/schema.json": ["requirements.yml", "requirements/**/*.yml"]
+  }
+}
+"#;
+
+/// Options for [`scaffold`]
+#[derive(Debug, Clone)]
+pub struct ScaffoldOptions {
+    /// Prefix of generated IDs
+    pub prefix: String,
+
+    /// Format of generated IDs
+    pub ids: IdScheme,
+}
+
+impl Default for ScaffoldOptions {
+    fn default() -> Self {
+        Self {
+            prefix: "REQ".to_string(),
+            ids: IdScheme::default(),
+        }
+    }
+}
+
+/// Files written by [`scaffold`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scaffold {
+    /// Files that were created
+    pub created: Vec<PathBuf>,
+
+    /// Starter files left alone because they already existed
+    pub skipped: Vec<PathBuf>,
+}
+
+/// Set up a new project in `dir`
+///
+/// Fails if `dir/.rqm` is already initialized.
+pub fn scaffold<P: AsRef<Path>>(dir: P, options: &ScaffoldOptions) -> Result<Scaffold> {
+    let dir = dir.as_ref();
+    let rqm_dir = dir.join(".rqm");
+    let config_path = rqm_dir.join("config.yml");
+    if config_path.exists() {
+        return Err(Error::custom(format!(
+            "{} is already initialized",
+            rqm_dir.display()
+        )));
+    }
+
+    let mut config = ProjectConfig::new(options.prefix.clone());
+    config.ids = options.ids.clone();
+    MetadataStore::init_with_config(&rqm_dir, config)?;
+
+    let mut result = Scaffold {
+        created: vec![config_path],
+        skipped: vec![],
+    };
+    let files = [
+        (dir.join("requirements.yml"), REQUIREMENTS),
+        (
+            rqm_dir.join("schema.json"),
+            include_str!("../../schema.json"),
+        ),
+        (rqm_dir.join("lint.yml"), LINT),
+        (rqm_dir.join("glossary.yml"), GLOSSARY),
+        (dir.join(".editorconfig"), EDITORCONFIG),
+        (dir.join(".vscode").join("settings.json"), VSCODE_SETTINGS),
+    ];
+    for (path, content) in files {
+        if path.exists() {
+            result.skipped.push(path);
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+        result.created.push(path);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::analysis::{self, AnalysisOptions};
+    use crate::validator::{Glossary, LintConfig, Validator};
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scaffold_is_valid() {
+        let temp = TempDir::new().unwrap();
+        let options = ScaffoldOptions {
+            prefix: "APP".to_string(),
+            ids: IdScheme {
+                hierarchical: true,
+                ..IdScheme::default()
+            },
+        };
+        let result = scaffold(temp.path(), &options).unwrap();
+        assert_eq!(result.created.len(), 7);
+        assert!(result.skipped.is_empty());
+
+        let rqm_dir = temp.path().join(".rqm");
+        let store = MetadataStore::new(&rqm_dir).unwrap();
+        assert_eq!(store.project_config().project_prefix, "APP");
+        assert!(store.project_config().ids.hierarchical);
+
+        let config = Parser::parse_file(temp.path().join("requirements.yml")).unwrap();
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(LintConfig::load(&rqm_dir).unwrap())
+            .with_glossary(Glossary::load(&rqm_dir).unwrap().unwrap());
+        assert!(validator.validate(&config).is_valid());
+        assert!(analysis::analyze(&config, &AnalysisOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_scaffold_keeps_existing_files() {
+        let temp = TempDir::new().unwrap();
+        let requirements = temp.path().join("requirements.yml");
+        fs::write(&requirements, "version: \"1.0\"\nrequirements: []\n").unwrap();
+
+        let result = scaffold(temp.path(), &ScaffoldOptions::default()).unwrap();
+        assert_eq!(result.skipped, vec![requirements.clone()]);
+        assert_eq!(
+            fs::read_to_string(&requirements).unwrap(),
+            "version: \"1.0\"\nrequirements: []\n"
+        );
+        assert!(scaffold(temp.path(), &ScaffoldOptions::default()).is_err());
+    }
+}
@@ -0,0 +1,456 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Full-text search over requirements
+//!
+//! A [`SearchIndex`] is an inverted index of the words in summaries,
+//! descriptions and justifications, with the positions of each word so
+//! quoted phrases can be matched. Hits are ranked with BM25, weighting
+//! summary matches above the longer text fields.
+//!
+//! Queries are words and `"quoted phrases"`; a requirement must contain all
+//! of them to match. Case and punctuation are ignored.
+
+use crate::types::{Requirement, RequirementConfig};
+use crate::workspace::Workspace;
+use crate::Parser;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// BM25 term frequency saturation
+const K1: f64 = 1.2;
+
+/// BM25 length normalization
+const B: f64 = 0.75;
+
+/// Words around a match shown in a snippet, before and after
+const SNIPPET_CONTEXT: usize = 6;
+
+/// A text field of a requirement that is indexed
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchField {
+    Summary,
+    Description,
+    Justification,
+}
+
+impl SearchField {
+    const ALL: [SearchField; 3] = [
+        SearchField::Summary,
+        SearchField::Description,
+        SearchField::Justification,
+    ];
+
+    /// Relative weight of a match in this field
+    fn weight(self) -> f64 {
+        match self {
+            SearchField::Summary => 3.0,
+            SearchField::Description | SearchField::Justification => 1.0,
+        }
+    }
+
+    fn text(self, req: &Requirement) -> Option<&str> {
+        match self {
+            SearchField::Summary => Some(&req.summary),
+            SearchField::Description => req.description.as_deref(),
+            SearchField::Justification => req.justification.as_deref(),
+        }
+    }
+}
+
+/// A requirement matching a query
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+pub struct SearchHit {
+    pub summary: String,
+
+    /// File the requirement is defined in, when indexed from a workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+
+    /// 1-based line of the requirement's summary in that file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1))]
+    pub line: Option<usize>,
+
+    pub score: f64,
+
+    /// Field contributing most to the score
+    pub field: SearchField,
+
+    /// Text around the first match in that field
+    pub snippet: String,
+}
+
+/// An indexed requirement
+struct Document {
+    summary: String,
+    path: Option<PathBuf>,
+    line: Option<usize>,
+    texts: BTreeMap<SearchField, String>,
+    lengths: BTreeMap<SearchField, usize>,
+}
+
+/// Positions of a word in one field of one document
+struct Posting {
+    doc: usize,
+    field: SearchField,
+    positions: Vec<usize>,
+}
+
+/// Lowercased words with their byte ranges in `text`
+fn words(text: &str) -> Vec<(String, usize, usize)> {
+    let mut words = vec![];
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push((text[s..i].to_lowercase(), s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Inverted index over requirement text
+#[derive(Default)]
+pub struct SearchIndex {
+    documents: Vec<Document>,
+    postings: HashMap<String, Vec<Posting>>,
+}
+
+impl SearchIndex {
+    /// An empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index every requirement of a file
+    pub fn from_config(config: &RequirementConfig) -> Self {
+        let mut index = Self::new();
+        index.add_config(config, None, None);
+        index
+    }
+
+    /// Index every requirement of every file in a workspace, keeping
+    /// their locations
+    pub fn from_workspace(workspace: &Workspace) -> Self {
+        let mut index = Self::new();
+        for file in &workspace.files {
+            index.add_config(&file.config, Some(&file.path), Some(&file.source));
+        }
+        index
+    }
+
+    /// Number of indexed requirements
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Check whether nothing is indexed
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Index the requirements of `config`, found at `path` with `source`
+    pub fn add_config(
+        &mut self,
+        config: &RequirementConfig,
+        path: Option<&Path>,
+        source: Option<&str>,
+    ) {
+        for req in config.all_requirements() {
+            let line = source.and_then(|source| Parser::summary_line(source, &req.summary));
+            self.add(req, path, line);
+        }
+    }
+
+    fn add(&mut self, req: &Requirement, path: Option<&Path>, line: Option<usize>) {
+        let doc = self.documents.len();
+        let mut texts = BTreeMap::new();
+        let mut lengths = BTreeMap::new();
+        for field in SearchField::ALL {
+            let Some(text) = field.text(req) else {
+                continue;
+            };
+            let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+            let words = words(text);
+            for (position, (word, _, _)) in words.iter().enumerate() {
+                positions.entry(word.clone()).or_default().push(position);
+            }
+            for (word, positions) in positions {
+                self.postings.entry(word).or_default().push(Posting {
+                    doc,
+                    field,
+                    positions,
+                });
+            }
+            texts.insert(field, text.to_string());
+            lengths.insert(field, words.len());
+        }
+        self.documents.push(Document {
+            summary: req.summary.clone(),
+            path: path.map(Path::to_path_buf),
+            line,
+            texts,
+            lengths,
+        });
+    }
+
+    /// Requirements containing every word and phrase of `query`, best
+    /// first
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms = parse_query(query);
+        if terms.is_empty() || self.documents.is_empty() {
+            return vec![];
+        }
+        let average = self.average_lengths();
+
+        // Per document: score so far, score per field and first match
+        let mut scores: HashMap<usize, (f64, BTreeMap<SearchField, f64>)> = HashMap::new();
+        let mut first_match: HashMap<(usize, SearchField), usize> = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            let occurrences = self.occurrences(term);
+            let df = occurrences
+                .keys()
+                .map(|(doc, _)| doc)
+                .collect::<HashSet<_>>()
+                .len() as f64;
+            let n = self.documents.len() as f64;
+            let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+
+            let mut term_scores: HashMap<usize, BTreeMap<SearchField, f64>> = HashMap::new();
+            for (&(doc, field), positions) in &occurrences {
+                let tf = positions.len() as f64;
+                let length = self.documents[doc].lengths[&field] as f64;
+                let norm = 1.0 - B + B * length / average[&field];
+                let score = field.weight() * idf * tf * (K1 + 1.0) / (tf + K1 * norm);
+                *term_scores
+                    .entry(doc)
+                    .or_default()
+                    .entry(field)
+                    .or_default() += score;
+                let first = first_match.entry((doc, field)).or_insert(positions[0]);
+                *first = (*first).min(positions[0]);
+            }
+
+            // Every term must match, so later terms only narrow the hits
+            if i == 0 {
+                for (doc, fields) in term_scores {
+                    scores.insert(doc, (fields.values().sum(), fields));
+                }
+            } else {
+                scores.retain(|doc, _| term_scores.contains_key(doc));
+                for (doc, (total, fields)) in &mut scores {
+                    for (field, score) in &term_scores[doc] {
+                        *total += score;
+                        *fields.entry(*field).or_default() += score;
+                    }
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(doc, (score, fields))| {
+                let field = fields
+                    .iter()
+                    .max_by(|a, b| a.1.total_cmp(b.1))
+                    .map_or(SearchField::Summary, |(field, _)| *field);
+                let document = &self.documents[doc];
+                SearchHit {
+                    summary: document.summary.clone(),
+                    path: document.path.clone(),
+                    line: document.line,
+                    score,
+                    field,
+                    snippet: snippet(&document.texts[&field], first_match[&(doc, field)]),
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.summary.cmp(&b.summary))
+        });
+        hits
+    }
+
+    /// Average number of words per field over the documents having it
+    fn average_lengths(&self) -> BTreeMap<SearchField, f64> {
+        let mut totals: BTreeMap<SearchField, (usize, usize)> = BTreeMap::new();
+        for doc in &self.documents {
+            for (field, length) in &doc.lengths {
+                let total = totals.entry(*field).or_default();
+                total.0 += length;
+                total.1 += 1;
+            }
+        }
+        totals
+            .into_iter()
+            .map(|(field, (words, docs))| (field, (words as f64 / docs as f64).max(1.0)))
+            .collect()
+    }
+
+    /// Start positions of a term or phrase per document and field
+    fn occurrences(&self, term: &[String]) -> HashMap<(usize, SearchField), Vec<usize>> {
+        let mut found = HashMap::new();
+        let Some(first) = term.first().and_then(|word| self.postings.get(word)) else {
+            return found;
+        };
+        for posting in first {
+            let starts: Vec<usize> = posting
+                .positions
+                .iter()
+                .copied()
+                .filter(|&start| {
+                    term[1..].iter().enumerate().all(|(offset, word)| {
+                        self.positions(word, posting.doc, posting.field)
+                            .is_some_and(|positions| positions.contains(&(start + offset + 1)))
+                    })
+                })
+                .collect();
+            if !starts.is_empty() {
+                found.insert((posting.doc, posting.field), starts);
+            }
+        }
+        found
+    }
+
+    fn positions(&self, word: &str, doc: usize, field: SearchField) -> Option<&[usize]> {
+        self.postings
+            .get(word)?
+            .iter()
+            .find(|posting| posting.doc == doc && posting.field == field)
+            .map(|posting| posting.positions.as_slice())
+    }
+}
+
+/// Words and quoted phrases of a query, each as its list of words
+fn parse_query(query: &str) -> Vec<Vec<String>> {
+    let mut terms = vec![];
+    for (i, part) in query.split('"').enumerate() {
+        let words: Vec<String> = words(part).into_iter().map(|(word, _, _)| word).collect();
+        if i % 2 == 1 {
+            // Inside quotes: one phrase
+            if !words.is_empty() {
+                terms.push(words);
+            }
+        } else {
+            terms.extend(words.into_iter().map(|word| vec![word]));
+        }
+    }
+    terms
+}
+
+/// The words around position `at` of `text`
+fn snippet(text: &str, at: usize) -> String {
+    let words = words(text);
+    let first = at.saturating_sub(SNIPPET_CONTEXT);
+    let last = (at + SNIPPET_CONTEXT).min(words.len().saturating_sub(1));
+    let (Some(start), Some(end)) = (words.get(first), words.get(last)) else {
+        return text.to_string();
+    };
+    // Keep punctuation at either end of the text
+    let start = if first > 0 { start.1 } else { 0 };
+    let end = if last + 1 < words.len() {
+        end.2
+    } else {
+        text.len()
+    };
+    let mut snippet = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if start > 0 {
+        snippet.insert(0, '…');
+    }
+    if end < text.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::WorkspaceFile;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Audit log
+    description: Every change to an account is written to the audit log.
+    requirements:
+      - summary: Log retention
+        description: Audit entries are kept for seven years.
+        justification: Required by the financial regulator.
+  - summary: Login
+    description: Users log in with a password. The log of failed attempts is kept.
+"#;
+
+    fn summaries(hits: &[SearchHit]) -> Vec<&str> {
+        hits.iter().map(|hit| hit.summary.as_str()).collect()
+    }
+
+    #[test]
+    fn test_ranking() {
+        let index = SearchIndex::from_config(&Parser::parse_str(YAML).unwrap());
+        assert_eq!(index.len(), 3);
+
+        // Summary matches rank above description matches
+        let hits = index.search("log");
+        assert_eq!(
+            summaries(&hits),
+            vec!["Audit log", "Log retention", "Login"]
+        );
+        assert!(hits[0].score > hits[2].score);
+        assert_eq!(hits[2].field, SearchField::Description);
+
+        // All words must match, in any field
+        assert_eq!(
+            summaries(&index.search("audit regulator")),
+            vec!["Log retention"]
+        );
+        assert!(index.search("audit unicorn").is_empty());
+        assert!(index.search("  ").is_empty());
+    }
+
+    #[test]
+    fn test_phrases() {
+        let index = SearchIndex::from_config(&Parser::parse_str(YAML).unwrap());
+        assert_eq!(summaries(&index.search("\"audit log\"")), vec!["Audit log"]);
+        assert_eq!(
+            summaries(&index.search("\"log in\" PASSWORD")),
+            vec!["Login"]
+        );
+        assert!(index.search("\"log audit\"").is_empty());
+    }
+
+    #[test]
+    fn test_snippets_and_locations() {
+        let file = WorkspaceFile::parse("auth.yml", YAML.to_string()).unwrap();
+        let workspace = Workspace { files: vec![file] };
+        let index = SearchIndex::from_workspace(&workspace);
+
+        let hit = &index.search("regulator")[0];
+        assert_eq!(hit.path.as_deref(), Some(Path::new("auth.yml")));
+        assert_eq!(hit.line, Some(7));
+        assert_eq!(hit.field, SearchField::Justification);
+        assert_eq!(hit.snippet, "Required by the financial regulator.");
+
+        assert_eq!(
+            snippet("one two three four five six seven eight nine ten", 8),
+            "…three four five six seven eight nine ten"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Deterministic YAML serialization
+//!
+//! `Requirement` and `RequirementConfig` always serialize their fields in
+//! declaration order, and any map-valued output is sorted by key, so an
+//! unchanged config produces byte-identical YAML. This module adds control
+//! over how unset fields are rendered.
+
+use crate::types::{PersonAlias, Requirement, RequirementTemplate};
+use crate::{Error, RequirementConfig, Result};
+use serde::de::{self, DeserializeOwned, Deserializer, Visitor};
+use serde::{Serialize, Serializer};
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// How unset optional fields and empty lists are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyFields {
+    /// Omit `None` fields and empty lists (the default)
+    #[default]
+    Strip,
+
+    /// Emit every known field, using `null` for unset values and `[]` for empty lists
+    Explicit,
+}
+
+/// Options for YAML serialization
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SerializeOptions {
+    /// Rendering of unset fields
+    pub empty_fields: EmptyFields,
+}
+
+/// Serialize a map with its keys in sorted order
+///
+/// Use with `#[serde(serialize_with = "crate::serialize::sorted_map")]` on
+/// `HashMap` fields so output does not depend on hash iteration order.
+pub fn sorted_map<S, K, V>(
+    map: &HashMap<K, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    K: Serialize + Ord,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+/// Convert a config into a YAML value honoring the serialization options
+pub fn to_value(config: &RequirementConfig, options: &SerializeOptions) -> Result<Value> {
+    let mut value = serde_yaml::to_value(config)?;
+
+    if options.empty_fields == EmptyFields::Explicit {
+        let schema: serde_json::Value = serde_json::from_str(include_str!("../../schema.json"))
+            .map_err(|e| Error::custom(format!("Failed to parse schema: {}", e)))?;
+        let defs = &schema["$defs"];
+
+        if let Value::Mapping(map) = &mut value {
+            *map = explicit_mapping(map, struct_fields::<RequirementConfig>(), &schema);
+            for (key, fields, def) in [
+                (
+                    "aliases",
+                    struct_fields::<PersonAlias>(),
+                    &defs["person_alias"],
+                ),
+                (
+                    "templates",
+                    struct_fields::<RequirementTemplate>(),
+                    &defs["requirement_template"],
+                ),
+            ] {
+                if let Some(Value::Sequence(items)) = map.get_mut(key) {
+                    for item in items {
+                        if let Value::Mapping(m) = item {
+                            *m = explicit_mapping(m, fields, def);
+                        }
+                    }
+                }
+            }
+            if let Some(Value::Sequence(reqs)) = map.get_mut("requirements") {
+                explicit_requirements(reqs, &defs["requirement"]);
+            }
+        }
+    }
+
+    Ok(value)
+}
+
+fn explicit_requirements(reqs: &mut [Value], def: &serde_json::Value) {
+    for req in reqs {
+        if let Value::Mapping(map) = req {
+            *map = explicit_mapping(map, struct_fields::<Requirement>(), def);
+            if let Some(Value::Sequence(children)) = map.get_mut("requirements") {
+                explicit_requirements(children, def);
+            }
+        }
+    }
+}
+
+/// Rebuild a mapping in declaration order with every field present
+fn explicit_mapping(map: &Mapping, fields: &[&str], def: &serde_json::Value) -> Mapping {
+    let mut result = Mapping::new();
+    for &field in fields {
+        let value = map.get(field).cloned().unwrap_or_else(|| {
+            match def["properties"][field]["type"].as_str() {
+                Some("array") => Value::Sequence(vec![]),
+                Some("object") => Value::Mapping(Mapping::new()),
+                _ => Value::Null,
+            }
+        });
+        result.insert(Value::String(field.to_string()), value);
+    }
+    result
+}
+
+/// Get the serde field names of a struct in declaration order
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldCollector<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for FieldCollector<'_> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            Err(de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(de::Error::custom("fields collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldCollector(&mut fields));
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_struct_fields_order() {
+        let fields = struct_fields::<Requirement>();
+        assert_eq!(fields[0], "summary");
+        assert_eq!(fields[1], "name");
+        assert!(fields.contains(&"requirements"));
+    }
+
+    #[test]
+    fn test_sorted_map() {
+        #[derive(Serialize)]
+        struct Wrapper {
+            #[serde(serialize_with = "sorted_map")]
+            map: HashMap<String, u32>,
+        }
+
+        let map: HashMap<String, u32> = ["zeta", "alpha", "mid"]
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.to_string(), i as u32))
+            .collect();
+        let json = serde_json::to_string(&Wrapper { map }).unwrap();
+        assert_eq!(json, r#"{"map":{"alpha":1,"mid":2,"zeta":0}}"#);
+    }
+
+    #[test]
+    fn test_explicit_fields() {
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![Requirement::new("Test")],
+        };
+        let options = SerializeOptions {
+            empty_fields: EmptyFields::Explicit,
+        };
+
+        let value = to_value(&config, &options).unwrap();
+        let req = &value["requirements"][0];
+        assert_eq!(req["summary"], Value::String("Test".to_string()));
+        assert_eq!(req["description"], Value::Null);
+        assert_eq!(req["tags"], Value::Sequence(vec![]));
+        assert_eq!(value["aliases"], Value::Sequence(vec![]));
+    }
+}
@@ -0,0 +1,493 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Read-only HTTP API over a set of requirements files
+//!
+//! Enabled by the `server` feature and served by `rqm-server`. Files are
+//! read again for every request, on the blocking thread pool, so responses
+//! follow edits without a restart. Metadata is read through one
+//! [`SharedMetadataStore`], opened on first use, whose records are cached
+//! for the life of the server. Results use the versioned types of
+//! [`crate::output`]; the graph is a JSON Graph Format document and reports
+//! are returned as rendered.
+//!
+//! | Endpoint | Response |
+//! |----------|----------|
+//! | `GET /api/health` | `{"status": "ok"}` |
+//! | `GET /api/requirements?query=<expr>` | [`QueryResult`], optionally filtered |
+//! | `GET /api/requirements/:summary` | [`RequirementResult`] |
+//! | `GET /api/requirements/:summary/ancestors` | [`QueryResult`] |
+//! | `GET /api/requirements/:summary/dependencies` | [`QueryResult`] of children |
+//! | `GET /api/requirements/:summary/dependents` | [`QueryResult`] of parents |
+//! | `GET /api/graph` | JSON Graph Format |
+//! | `GET /api/validate` | [`ValidationResult`] |
+//! | `GET /api/search?q=<text>` | [`SearchResult`] |
+//! | `GET /api/reports/stats` | [`GraphStats`](crate::graph::GraphStats) |
+//! | `GET /api/reports/matrix` | Traceability matrix rows |
+//! | `GET /api/reports/html` | Interactive HTML report |
+//! | `POST /api/graphql` | GraphQL response; see [`graphql`] |
+//! | `GET /api/graphql` | GraphQL schema in SDL |
+//! | `GET /oslc/...` | OSLC RM documents; see [`OslcProvider`] |
+//!
+//! Errors are returned as `{"error": "<message>"}` with status 404 for
+//! unknown requirements, 400 for invalid queries and 500 otherwise. OSLC
+//! resources are linked below the base URL set with
+//! [`ServerState::with_oslc_base_url`], or below `http://<Host>/oslc`.
+
+pub mod graphql;
+
+use crate::archive::load_archives;
+use crate::export::matrix_rows;
+use crate::graph::analysis::DEFAULT_STALE_DAYS;
+use crate::interop::oslc::OslcProvider;
+use crate::output::{QueryResult, RequirementResult, SearchResult, ValidationResult};
+use crate::query::Expr;
+use crate::report::{self, ReportOptions};
+use crate::search::SearchIndex;
+use crate::validator::{Glossary, LintConfig};
+use crate::{
+    Error, MetadataStore, Requirement, RequirementGraph, SharedMetadataStore, Validator, Workspace,
+};
+use axum::extract::{Path as UrlPath, Query, RawQuery, State};
+use axum::http::header::{CONTENT_TYPE, HOST};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use graphql::{RqmSchema, Snapshot};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use tokio::net::TcpListener;
+
+/// The requirements files served and their metadata directory
+#[derive(Clone)]
+pub struct ServerState {
+    files: Vec<PathBuf>,
+    rqm_dir: PathBuf,
+    store: Arc<OnceLock<SharedMetadataStore>>,
+    schema: RqmSchema,
+    oslc_base_url: Option<String>,
+}
+
+impl ServerState {
+    /// Serve `files`, with metadata from the `.rqm` directory next to the
+    /// first one
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        let rqm_dir = files
+            .first()
+            .and_then(|file| file.parent())
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| std::path::Path::new("."))
+            .join(".rqm");
+        Self {
+            files,
+            rqm_dir,
+            store: Arc::new(OnceLock::new()),
+            schema: graphql::schema(),
+            oslc_base_url: None,
+        }
+    }
+
+    /// Link OSLC resources below `base_url`, the public URL of `/oslc`
+    pub fn with_oslc_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.oslc_base_url = Some(base_url.into());
+        self
+    }
+
+    fn workspace(&self) -> crate::Result<Workspace> {
+        Workspace::load(&self.files)
+    }
+
+    /// The metadata store, once the project has one
+    fn store(&self) -> crate::Result<Option<&SharedMetadataStore>> {
+        if let Some(store) = self.store.get() {
+            return Ok(Some(store));
+        }
+        if !self.rqm_dir.join("config.yml").exists() {
+            return Ok(None);
+        }
+        let store = SharedMetadataStore::new(&self.rqm_dir)?;
+        Ok(Some(self.store.get_or_init(|| store)))
+    }
+
+    /// Run `f` with read access to the metadata store, if there is one
+    fn with_store<T>(
+        &self,
+        f: impl FnOnce(Option<&MetadataStore>) -> crate::Result<T>,
+    ) -> crate::Result<T> {
+        match self.store()? {
+            Some(shared) => shared.read(|store| f(Some(store))),
+            None => f(None),
+        }
+    }
+}
+
+/// An error response
+struct ApiError(StatusCode, String);
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let status = match error {
+            Error::RequirementNotFound(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        ApiError(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<T, ApiError>;
+type Shared = State<Arc<ServerState>>;
+
+/// Routes of the API
+pub fn router(state: ServerState) -> Router {
+    Router::new()
+        .route("/api/health", get(health))
+        .route("/api/requirements", get(requirements))
+        .route("/api/requirements/:summary", get(requirement))
+        .route("/api/requirements/:summary/:relation", get(related))
+        .route("/api/graph", get(graph))
+        .route("/api/validate", get(validate))
+        .route("/api/search", get(search))
+        .route("/api/reports/:report", get(report))
+        .route("/api/graphql", get(graphql_sdl).post(graphql))
+        .route("/oslc/*path", get(oslc))
+        .with_state(Arc::new(state))
+}
+
+/// Serve the API on `listener` until the process ends
+pub async fn serve(listener: TcpListener, state: ServerState) -> crate::Result<()> {
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+#[derive(Deserialize)]
+struct RequirementsParams {
+    query: Option<String>,
+}
+
+async fn requirements(
+    State(state): Shared,
+    Query(params): Query<RequirementsParams>,
+) -> ApiResult<Json<QueryResult>> {
+    blocking(move || {
+        let config = state.workspace()?.merged();
+        let requirements = match params.query {
+            Some(query) => {
+                let expr: Expr = query
+                    .parse()
+                    .map_err(|e: Error| ApiError(StatusCode::BAD_REQUEST, e.to_string()))?;
+                config.query(&expr).into_iter().cloned().collect()
+            }
+            None => config.all_requirements().into_iter().cloned().collect(),
+        };
+        Ok(Json(QueryResult::new(requirements)))
+    })
+    .await
+}
+
+async fn requirement(
+    State(state): Shared,
+    UrlPath(summary): UrlPath<String>,
+) -> ApiResult<Json<RequirementResult>> {
+    blocking(move || {
+        let graph = RequirementGraph::from_config(&state.workspace()?.merged())?;
+        let requirement = graph
+            .get(&summary)
+            .ok_or_else(|| Error::RequirementNotFound(summary.clone()))?;
+        let summaries =
+            |reqs: Vec<&Requirement>| reqs.iter().map(|req| req.summary.clone()).collect();
+        let metadata = match state.store()? {
+            Some(store) => store.find_metadata(&summary)?,
+            None => None,
+        };
+        Ok(Json(RequirementResult {
+            metadata,
+            parents: summaries(graph.dependents(&summary)?),
+            children: summaries(graph.dependencies(&summary)?),
+            ..RequirementResult::new(requirement.clone())
+        }))
+    })
+    .await
+}
+
+async fn related(
+    State(state): Shared,
+    UrlPath((summary, relation)): UrlPath<(String, String)>,
+) -> ApiResult<Json<QueryResult>> {
+    blocking(move || {
+        let graph = RequirementGraph::from_config(&state.workspace()?.merged())?;
+        let related = match relation.as_str() {
+            "ancestors" => graph.ancestors(&summary)?,
+            "dependencies" => graph.dependencies(&summary)?,
+            "dependents" => graph.dependents(&summary)?,
+            _ => {
+                return Err(ApiError(
+                    StatusCode::NOT_FOUND,
+                    format!("Unknown relation '{}'", relation),
+                ))
+            }
+        };
+        Ok(Json(QueryResult::new(
+            related.into_iter().cloned().collect(),
+        )))
+    })
+    .await
+}
+
+async fn graph(State(state): Shared) -> ApiResult<Json<Value>> {
+    blocking(move || {
+        let graph = RequirementGraph::from_config(&state.workspace()?.merged())?;
+        Ok(Json(graph.to_json_graph()))
+    })
+    .await
+}
+
+async fn validate(State(state): Shared) -> ApiResult<Json<ValidationResult>> {
+    blocking(move || {
+        let workspace = state.workspace()?;
+        let mut validator = Validator::for_project(&state.rqm_dir)?;
+        if let Some(archived) = load_archives(&state.files)? {
+            validator = validator.with_archive(&archived);
+        }
+        let report = workspace.validate(&validator);
+        Ok(Json(ValidationResult::from_workspace(
+            &report,
+            &workspace.files,
+        )))
+    })
+    .await
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+}
+
+async fn search(
+    State(state): Shared,
+    Query(params): Query<SearchParams>,
+) -> ApiResult<Json<SearchResult>> {
+    blocking(move || {
+        let index = SearchIndex::from_workspace(&state.workspace()?);
+        Ok(Json(SearchResult::new(index.search(&params.q))))
+    })
+    .await
+}
+
+async fn report(State(state): Shared, UrlPath(report): UrlPath<String>) -> ApiResult<Response> {
+    blocking(move || {
+        let config = state.workspace()?.merged();
+        Ok(match report.as_str() {
+            "stats" => Json(RequirementGraph::from_config(&config)?.stats()).into_response(),
+            "matrix" => Json(state.with_store(|store| matrix_rows(&config, store, None, None))?)
+                .into_response(),
+            "html" => {
+                let options = ReportOptions {
+                    stale_after_days: Some(
+                        LintConfig::load(&state.rqm_dir)?
+                            .stale_after_days
+                            .unwrap_or(DEFAULT_STALE_DAYS),
+                    ),
+                    glossary: Glossary::load(&state.rqm_dir)?,
+                    ..ReportOptions::default()
+                };
+                Html(state.with_store(|store| report::to_html(&config, store, &options))?)
+                    .into_response()
+            }
+            _ => {
+                return Err(ApiError(
+                    StatusCode::NOT_FOUND,
+                    format!("Unknown report '{}'", report),
+                ))
+            }
+        })
+    })
+    .await
+}
+
+async fn graphql(
+    State(state): Shared,
+    Json(request): Json<async_graphql::Request>,
+) -> ApiResult<Json<async_graphql::Response>> {
+    let snapshot = {
+        let state = Arc::clone(&state);
+        blocking(move || {
+            let config = state.workspace()?.merged();
+            Ok(state.with_store(|store| Snapshot::new(config, store))?)
+        })
+        .await?
+    };
+    let request = request.data(Arc::new(snapshot));
+    Ok(Json(state.schema.execute(request).await))
+}
+
+async fn graphql_sdl(State(state): Shared) -> String {
+    state.schema.sdl()
+}
+
+async fn oslc(
+    State(state): Shared,
+    UrlPath(path): UrlPath<String>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let base_url = match &state.oslc_base_url {
+        Some(base_url) => base_url.clone(),
+        None => {
+            let host = headers.get(HOST).and_then(|host| host.to_str().ok());
+            format!("http://{}/oslc", host.unwrap_or("localhost"))
+        }
+    };
+    blocking(move || {
+        let config = state.workspace()?.merged();
+        let response = state.with_store(|store| {
+            OslcProvider::new(&config, store, base_url)
+                .handle(&path, query.as_deref().unwrap_or_default())
+        })?;
+        match response {
+            Some(response) => {
+                Ok(([(CONTENT_TYPE, response.content_type)], response.body).into_response())
+            }
+            None => Err(ApiError(
+                StatusCode::NOT_FOUND,
+                format!("Unknown OSLC resource '{}'", path),
+            )),
+        }
+    })
+    .await
+}
+
+/// Run a handler's work, which reads files and may take a while, on the
+/// blocking thread pool instead of the async runtime
+async fn blocking<T: Send + 'static>(
+    work: impl FnOnce() -> ApiResult<T> + Send + 'static,
+) -> ApiResult<T> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpStream};
+    use tempfile::TempDir;
+
+    const REQUIREMENTS: &str = r#"version: "1.0"
+requirements:
+  - summary: Login
+    status: approved
+    requirements:
+      - summary: Password policy
+        status: draft
+"#;
+
+    /// Start a server on a free port, returning its address
+    fn start(files: Vec<PathBuf>) -> (tokio::runtime::Runtime, SocketAddr) {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0")).unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(serve(listener, ServerState::new(files)));
+        (runtime, addr)
+    }
+
+    fn get(addr: SocketAddr, path: &str) -> (u16, String) {
+        request(addr, "GET", path, "")
+    }
+
+    fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let status = response[9..12].parse().unwrap();
+        let body = response.split_once("\r\n\r\n").unwrap().1.to_string();
+        (status, body)
+    }
+
+    fn json(body: &str) -> Value {
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("requirements.yml");
+        fs::write(&path, REQUIREMENTS).unwrap();
+        let (_runtime, addr) = start(vec![path]);
+
+        assert_eq!(get(addr, "/api/health").0, 200);
+
+        let (status, body) = get(addr, "/api/requirements");
+        assert_eq!(status, 200);
+        assert_eq!(json(&body)["requirements"].as_array().unwrap().len(), 2);
+        let (_, body) = get(addr, "/api/requirements?query=status%3D%3Ddraft");
+        assert_eq!(json(&body)["requirements"][0]["summary"], "Password policy");
+        assert_eq!(get(addr, "/api/requirements?query=status%3D%3D").0, 400);
+
+        let (status, body) = get(addr, "/api/requirements/Password%20policy");
+        assert_eq!(status, 200);
+        assert_eq!(json(&body)["parents"], json!(["Login"]));
+        let (_, body) = get(addr, "/api/requirements/Login/dependencies");
+        assert_eq!(json(&body)["requirements"][0]["summary"], "Password policy");
+        let (status, body) = get(addr, "/api/requirements/Nope");
+        assert_eq!(status, 404);
+        assert!(json(&body)["error"].as_str().unwrap().contains("Nope"));
+
+        let (_, body) = get(addr, "/api/validate");
+        assert_eq!(json(&body)["valid"], true);
+        let (_, body) = get(addr, "/api/search?q=password");
+        assert_eq!(json(&body)["hits"][0]["summary"], "Password policy");
+        let (_, body) = get(addr, "/api/graph");
+        assert!(json(&body)["graph"]["nodes"].is_object());
+        let (_, body) = get(addr, "/api/reports/stats");
+        assert_eq!(json(&body)["requirements"], 2);
+        let (status, body) = get(addr, "/api/reports/html");
+        assert_eq!(status, 200);
+        assert!(body.contains("<html"));
+        assert_eq!(get(addr, "/api/reports/nope").0, 404);
+
+        let query =
+            json!({ "query": r#"{ requirement(summary: "Login") { children { summary } } }"# });
+        let (status, body) = request(addr, "POST", "/api/graphql", &query.to_string());
+        assert_eq!(status, 200);
+        assert_eq!(
+            json(&body)["data"]["requirement"]["children"],
+            json!([{ "summary": "Password policy" }])
+        );
+        assert!(get(addr, "/api/graphql").1.contains("type Requirement"));
+
+        let (status, body) = get(addr, "/oslc/catalog");
+        assert_eq!(status, 200);
+        assert!(body.contains("rdf:resource=\"http://localhost/oslc/provider\""));
+        let (_, body) = get(addr, "/oslc/requirements?oslc.where=rqm:status=%22draft%22");
+        assert!(body.contains("<oslc:totalCount>1</oslc:totalCount>"));
+        let (_, body) = get(addr, "/oslc/requirements/login");
+        assert!(body.contains("http://localhost/oslc/requirements/password-policy"));
+        assert_eq!(get(addr, "/oslc/nope").0, 404);
+    }
+}
@@ -0,0 +1,393 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! GraphQL schema over the requirement graph
+//!
+//! Served at `/api/graphql`: `POST` executes a query, `GET` returns the
+//! schema in SDL. Every request sees a [`Snapshot`] of the files taken when
+//! it arrived, so nested fields such as `children { dependents { id } }` are
+//! resolved against one consistent graph. Queries nested deeper than
+//! [`MAX_DEPTH`] or more complex than [`MAX_COMPLEXITY`] are rejected before
+//! they run, since relations can be followed back and forth without end.
+//!
+//! ```graphql
+//! {
+//!   requirements(filter: "status==draft && tag=safety") {
+//!     id
+//!     summary
+//!     owner
+//!     children { summary status }
+//!   }
+//! }
+//! ```
+
+use crate::metadata::{MetadataStore, RequirementMetadata};
+use crate::query::Expr;
+use crate::search::SearchIndex;
+use crate::types::Status;
+use crate::{Requirement, RequirementConfig, RequirementGraph};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, SimpleObject};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The executable schema
+pub type RqmSchema = async_graphql::Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Deepest nesting of fields a query may have
+pub const MAX_DEPTH: usize = 12;
+
+/// Most fields a query may select, counting each nested field once
+pub const MAX_COMPLEXITY: usize = 500;
+
+/// Build the schema; requests must carry an `Arc<Snapshot>` as data
+pub fn schema() -> RqmSchema {
+    async_graphql::Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Requirements, their graph and metadata as of one request
+pub struct Snapshot {
+    config: RequirementConfig,
+    graph: RequirementGraph,
+    metadata: HashMap<String, RequirementMetadata>,
+}
+
+impl Snapshot {
+    /// Take a snapshot of `config`, with metadata from `store` if given
+    pub fn new(config: RequirementConfig, store: Option<&MetadataStore>) -> crate::Result<Self> {
+        let graph = RequirementGraph::from_config(&config)?;
+        let mut metadata = HashMap::new();
+        if let Some(store) = store {
+            for req in config.all_requirements() {
+                if let Some(meta) = store.find_metadata(&req.summary)? {
+                    metadata.insert(req.summary.clone(), meta);
+                }
+            }
+        }
+        Ok(Self {
+            config,
+            graph,
+            metadata,
+        })
+    }
+
+    fn requirement(&self, summary: &str) -> &Requirement {
+        self.graph
+            .get(summary)
+            .expect("nodes are only made for requirements in the graph")
+    }
+
+    /// Generated ID, or the requirement's own name
+    fn id(&self, requirement: &Requirement) -> Option<String> {
+        self.metadata
+            .get(&requirement.summary)
+            .map(|meta| meta.generated_id.clone())
+            .or_else(|| requirement.name.clone())
+    }
+}
+
+fn snapshot<'a>(ctx: &Context<'a>) -> &'a Arc<Snapshot> {
+    ctx.data_unchecked::<Arc<Snapshot>>()
+}
+
+/// A node for `requirement`, which must be in the snapshot's graph
+fn node(snapshot: &Arc<Snapshot>, requirement: &Requirement) -> RequirementNode {
+    RequirementNode {
+        snapshot: snapshot.clone(),
+        summary: requirement.summary.clone(),
+    }
+}
+
+fn nodes(snapshot: &Arc<Snapshot>, requirements: Vec<&Requirement>) -> Vec<RequirementNode> {
+    requirements
+        .into_iter()
+        .map(|req| node(snapshot, req))
+        .collect()
+}
+
+/// Entry points of the schema
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Requirements in document order, optionally filtered by a query
+    /// expression such as `status==draft && tag=safety`
+    async fn requirements(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<String>,
+        status: Option<String>,
+        tag: Option<String>,
+    ) -> async_graphql::Result<Vec<RequirementNode>> {
+        let snapshot = snapshot(ctx);
+        let mut requirements = match filter {
+            Some(filter) => snapshot.config.query(&filter.parse::<Expr>()?),
+            None => snapshot.config.all_requirements(),
+        };
+        if let Some(status) = status {
+            requirements.retain(|req| req.status.map(Status::as_str) == Some(&*status));
+        }
+        if let Some(tag) = tag {
+            requirements.retain(|req| req.tags.contains(&tag));
+        }
+        Ok(nodes(snapshot, requirements))
+    }
+
+    /// One requirement, by summary or by ID
+    async fn requirement(
+        &self,
+        ctx: &Context<'_>,
+        summary: Option<String>,
+        id: Option<String>,
+    ) -> Option<RequirementNode> {
+        let snapshot = snapshot(ctx);
+        let requirement = match (summary, id) {
+            (Some(summary), _) => snapshot.graph.get(&summary),
+            (None, Some(id)) => snapshot
+                .config
+                .all_requirements()
+                .into_iter()
+                .find(|req| snapshot.id(req).as_deref() == Some(&*id)),
+            (None, None) => None,
+        };
+        requirement.map(|req| node(snapshot, req))
+    }
+
+    /// Top-level requirements of every file
+    async fn roots(&self, ctx: &Context<'_>) -> Vec<RequirementNode> {
+        let snapshot = snapshot(ctx);
+        nodes(snapshot, snapshot.config.requirements.iter().collect())
+    }
+
+    /// Ranked full-text search, best first
+    async fn search(&self, ctx: &Context<'_>, text: String) -> Vec<SearchHitNode> {
+        let snapshot = snapshot(ctx);
+        SearchIndex::from_config(&snapshot.config)
+            .search(&text)
+            .into_iter()
+            .filter_map(|hit| {
+                let requirement = snapshot.graph.get(&hit.summary)?;
+                Some(SearchHitNode {
+                    score: hit.score,
+                    requirement: node(snapshot, requirement),
+                })
+            })
+            .collect()
+    }
+}
+
+/// A requirement; relations resolve against the request's snapshot
+///
+/// Nodes share the snapshot and name their requirement by summary, so
+/// following relations copies no requirements.
+pub struct RequirementNode {
+    snapshot: Arc<Snapshot>,
+    summary: String,
+}
+
+impl RequirementNode {
+    fn req(&self) -> &Requirement {
+        self.snapshot.requirement(&self.summary)
+    }
+}
+
+#[Object(name = "Requirement")]
+impl RequirementNode {
+    async fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Generated ID, or the requirement's `name` if it has no metadata
+    async fn id(&self) -> Option<String> {
+        self.snapshot.id(self.req())
+    }
+
+    async fn name(&self) -> Option<&str> {
+        self.req().name.as_deref()
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.req().description.as_deref()
+    }
+
+    async fn justification(&self) -> Option<&str> {
+        self.req().justification.as_deref()
+    }
+
+    async fn acceptance_test(&self) -> Option<&str> {
+        self.req().acceptance_test.as_deref()
+    }
+
+    async fn owner(&self) -> Option<String> {
+        self.req()
+            .owner
+            .as_ref()
+            .map(|owner| owner.as_str().to_string())
+    }
+
+    /// Status in lower case, e.g. `draft`
+    async fn status(&self) -> Option<String> {
+        self.req().status.map(|status| status.to_string())
+    }
+
+    /// Priority in lower case, e.g. `high`
+    async fn priority(&self) -> Option<String> {
+        self.req().priority.map(|priority| priority.to_string())
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.req().tags
+    }
+
+    async fn target_release(&self) -> Option<&str> {
+        self.req().target_release.as_deref()
+    }
+
+    async fn milestone(&self) -> Option<&str> {
+        self.req().milestone.as_deref()
+    }
+
+    async fn estimate(&self) -> Option<f64> {
+        self.req().estimate
+    }
+
+    /// Generated ID and timestamps, if the requirement has metadata
+    async fn metadata(&self) -> Option<Metadata> {
+        self.snapshot
+            .metadata
+            .get(&self.summary)
+            .map(Metadata::from)
+    }
+
+    /// Direct children
+    async fn children(&self) -> async_graphql::Result<Vec<RequirementNode>> {
+        let snapshot = &self.snapshot;
+        Ok(nodes(snapshot, snapshot.graph.dependencies(&self.summary)?))
+    }
+
+    /// Requirements listing this one as a child
+    async fn dependents(&self) -> async_graphql::Result<Vec<RequirementNode>> {
+        let snapshot = &self.snapshot;
+        Ok(nodes(snapshot, snapshot.graph.dependents(&self.summary)?))
+    }
+
+    /// All requirements above this one
+    async fn ancestors(&self) -> async_graphql::Result<Vec<RequirementNode>> {
+        let snapshot = &self.snapshot;
+        Ok(nodes(snapshot, snapshot.graph.ancestors(&self.summary)?))
+    }
+}
+
+/// Metadata of a requirement, with RFC 3339 timestamps
+#[derive(SimpleObject)]
+pub struct Metadata {
+    uuid: String,
+    generated_id: String,
+    created_at: String,
+    updated_at: String,
+}
+
+impl From<&RequirementMetadata> for Metadata {
+    fn from(meta: &RequirementMetadata) -> Self {
+        Self {
+            uuid: meta.uuid.to_string(),
+            generated_id: meta.generated_id.clone(),
+            created_at: meta.created_at.to_rfc3339(),
+            updated_at: meta.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// A search hit with its requirement
+#[derive(SimpleObject)]
+#[graphql(name = "SearchHit")]
+pub struct SearchHitNode {
+    score: f64,
+    requirement: RequirementNode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use serde_json::{json, Value};
+
+    fn execute(query: &str) -> Value {
+        let config = Parser::parse_str(
+            r#"version: "1.0"
+requirements:
+  - summary: Login
+    name: AUTH
+    status: approved
+    tags: [safety]
+    requirements:
+      - summary: Password policy
+        status: draft
+      - summary: Lockout
+        status: draft
+        tags: [safety]
+"#,
+        )
+        .unwrap();
+        let snapshot = Arc::new(Snapshot::new(config, None).unwrap());
+        let request = async_graphql::Request::new(query).data(snapshot);
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let response = runtime.block_on(schema().execute(request));
+        serde_json::to_value(response).unwrap()
+    }
+
+    #[test]
+    fn test_nested_query() {
+        let response = execute(
+            r#"{ requirement(id: "AUTH") { summary status children { summary dependents { id } } } }"#,
+        );
+        assert_eq!(
+            response["data"]["requirement"],
+            json!({
+                "summary": "Login",
+                "status": "approved",
+                "children": [
+                    { "summary": "Password policy", "dependents": [{ "id": "AUTH" }] },
+                    { "summary": "Lockout", "dependents": [{ "id": "AUTH" }] },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let response =
+            execute(r#"{ requirements(filter: "status==draft", tag: "safety") { summary } }"#);
+        assert_eq!(
+            response["data"]["requirements"],
+            json!([{ "summary": "Lockout" }])
+        );
+
+        let response = execute(r#"{ requirements(filter: "status==") { summary } }"#);
+        assert!(response["errors"][0]["message"].is_string());
+
+        let response =
+            execute(r#"{ search(text: "password") { requirement { ancestors { summary } } } }"#);
+        assert_eq!(
+            response["data"]["search"][0]["requirement"]["ancestors"],
+            json!([{ "summary": "Login" }])
+        );
+    }
+
+    #[test]
+    fn test_deep_queries_are_rejected() {
+        let mut query = "summary".to_string();
+        for _ in 0..MAX_DEPTH {
+            query = format!("children {{ dependents {{ {} }} }}", query);
+        }
+        let response = execute(&format!("{{ roots {{ {} }} }}", query));
+        assert!(response["data"].is_null());
+        assert!(response["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("nested too deep"));
+    }
+}
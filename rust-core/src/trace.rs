@@ -0,0 +1,479 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Traceability from requirements to source code
+//!
+//! Code states which requirements it implements with structured comments,
+//! such as `// rqm: REQ-042` in Rust or `# rqm: REQ-042, AUTH` in Python.
+//! A comment may list several IDs; an ID is a generated ID, a requirement
+//! name or a UUID. [`TraceScanner`] finds these annotations in a source
+//! tree, and [`TraceMap`] matches them to requirements.
+//!
+//! Test results from JUnit XML or `cargo test` JSON output are matched to
+//! requirements the same way, giving each requirement a [`Verification`]
+//! status in a [`VerificationMap`].
+
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference, Status};
+use crate::validator::{Finding, Severity, ValidationReport};
+use crate::{Error, Result};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod results;
+
+pub use results::{
+    parse_cargo_json, parse_junit, RequirementVerification, TestOutcome, TestResult, Verification,
+    VerificationMap, VERIFICATION_FAILING_RULE, VERIFICATION_UNTESTED_RULE,
+};
+
+/// Rule ID reported for requirements without implementing code
+pub const TRACE_UNTRACED_RULE: &str = "trace-untraced";
+
+/// Rule ID reported for annotations naming no known requirement
+pub const TRACE_UNKNOWN_ID_RULE: &str = "trace-unknown-id";
+
+/// IDs after the `rqm:` marker, separated by commas
+///
+/// An ID starts and ends with a letter or digit, and the list ends at the
+/// end of the line, at whitespace, at a full stop or semicolon, or at a
+/// comment's closing delimiter, so neither prose after it nor `-->` is
+/// taken for an ID.
+const IDS: &str = r"\brqm:\s*(\w(?:[\w.-]*\w)?(?:\s*,\s*\w(?:[\w.-]*\w)?)*)(?:$|[\s.;]|\*/|-->)";
+
+/// Comment syntaxes of the default patterns, with their file extensions
+const LANGUAGES: &[(&str, &[&str])] = &[
+    (
+        r"(?://|/\*)",
+        &[
+            "rs", "c", "h", "cc", "cpp", "hpp", "cs", "go", "java", "js", "jsx", "ts", "tsx", "kt",
+            "swift", "scala", "dart", "php",
+        ],
+    ),
+    (
+        "#",
+        &[
+            "py", "rb", "sh", "bash", "pl", "r", "yml", "yaml", "toml", "cmake",
+        ],
+    ),
+    ("--", &["sql", "lua", "hs"]),
+    ("<!--", &["html", "xml", "md", "vue", "svelte"]),
+];
+
+/// How annotations look in files with certain extensions
+#[derive(Debug, Clone)]
+pub struct TracePattern {
+    /// File extensions without the dot, e.g. `rs`
+    pub extensions: Vec<String>,
+
+    /// Pattern whose first capture group holds the IDs
+    pub pattern: Regex,
+}
+
+impl TracePattern {
+    /// Create a pattern; `pattern` must have a capture group for the IDs
+    pub fn new(extensions: &[&str], pattern: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern)
+            .map_err(|e| Error::custom(format!("Invalid trace pattern '{}': {}", pattern, e)))?;
+        if pattern.captures_len() < 2 {
+            return Err(Error::custom(format!(
+                "Trace pattern '{}' has no capture group for IDs",
+                pattern
+            )));
+        }
+        Ok(Self {
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            pattern,
+        })
+    }
+
+    fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+    }
+}
+
+/// One requirement ID mentioned in a source file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Annotation {
+    /// Path relative to the scanned root
+    pub path: PathBuf,
+
+    /// 1-based line number
+    pub line: usize,
+
+    pub id: String,
+}
+
+/// Finds annotations in source trees
+#[derive(Debug, Clone)]
+pub struct TraceScanner {
+    patterns: Vec<TracePattern>,
+    exclude: Vec<String>,
+}
+
+impl Default for TraceScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TraceScanner {
+    /// Scan common languages, skipping hidden directories, `target` and
+    /// `node_modules`
+    pub fn new() -> Self {
+        let patterns = LANGUAGES
+            .iter()
+            .map(|(comment, extensions)| {
+                TracePattern::new(extensions, &format!(r"{}\s*{}", comment, IDS))
+                    .expect("default trace patterns are valid")
+            })
+            .collect();
+        Self {
+            patterns,
+            exclude: vec!["target".to_string(), "node_modules".to_string()],
+        }
+    }
+
+    /// Add a pattern, taking precedence over earlier ones for its extensions
+    pub fn with_pattern(mut self, pattern: TracePattern) -> Self {
+        self.patterns.insert(0, pattern);
+        self
+    }
+
+    /// Skip directories with this name
+    pub fn with_exclude(mut self, name: impl Into<String>) -> Self {
+        self.exclude.push(name.into());
+        self
+    }
+
+    /// Every annotation below `root`, ordered by path and line
+    ///
+    /// Files that are not UTF-8 are skipped, and so are symbolic links,
+    /// which could lead out of the tree or around in circles.
+    pub fn scan(&self, root: &Path) -> Result<Vec<Annotation>> {
+        let mut annotations = vec![];
+        let mut dirs = vec![root.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                if file_type.is_symlink() {
+                    continue;
+                }
+                let path = entry.path();
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or_default();
+                if file_type.is_dir() {
+                    if !name.starts_with('.') && !self.exclude.iter().any(|e| e == name) {
+                        dirs.push(path);
+                    }
+                    continue;
+                }
+                let Some(pattern) = self.patterns.iter().find(|p| p.handles(&path)) else {
+                    continue;
+                };
+                let Ok(content) = fs::read_to_string(&path) else {
+                    continue;
+                };
+                let relative = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+                annotations.extend(scan_text(&content, &pattern.pattern, &relative));
+            }
+        }
+        annotations.sort();
+        Ok(annotations)
+    }
+}
+
+fn scan_text(content: &str, pattern: &Regex, path: &Path) -> Vec<Annotation> {
+    let mut annotations = vec![];
+    for (i, line) in content.lines().enumerate() {
+        for captures in pattern.captures_iter(line) {
+            let Some(ids) = captures.get(1) else {
+                continue;
+            };
+            for id in ids.as_str().split([',', ' ', '\t']) {
+                if !id.is_empty() {
+                    annotations.push(Annotation {
+                        path: path.to_path_buf(),
+                        line: i + 1,
+                        id: id.to_string(),
+                    });
+                }
+            }
+        }
+    }
+    annotations
+}
+
+/// Summaries of requirements by every ID they can be referred to with:
+/// name, generated ID and UUID
+fn requirement_ids(
+    config: &RequirementConfig,
+    store: Option<&MetadataStore>,
+) -> Result<HashMap<String, String>> {
+    let mut ids = HashMap::new();
+    for req in config.all_requirements() {
+        if let Some(name) = &req.name {
+            ids.insert(name.clone(), req.summary.clone());
+        }
+        if let Some(meta) = match store {
+            Some(store) => store.find_metadata(&req.summary)?,
+            None => None,
+        } {
+            ids.insert(meta.generated_id, req.summary.clone());
+            ids.insert(meta.uuid.to_string(), req.summary.clone());
+        }
+    }
+    Ok(ids)
+}
+
+/// Annotations matched to requirements
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TraceMap {
+    /// Code locations implementing each requirement, by summary
+    pub locations: BTreeMap<String, Vec<Annotation>>,
+
+    /// Annotations whose ID names no requirement
+    pub unknown: Vec<Annotation>,
+
+    /// Summaries of requirements with no implementing code, in document
+    /// order
+    pub untraced: Vec<String>,
+}
+
+impl TraceMap {
+    /// Match `annotations` to the requirements in `config`
+    ///
+    /// Annotations may name a requirement by name, generated ID or UUID. A
+    /// requirement counts as traced when it or one of the requirements
+    /// defined below it is annotated, so parents are covered by their
+    /// children. Deprecated requirements are never reported as untraced.
+    pub fn build(
+        config: &RequirementConfig,
+        store: Option<&MetadataStore>,
+        annotations: Vec<Annotation>,
+    ) -> Result<Self> {
+        let ids = requirement_ids(config, store)?;
+        let mut map = TraceMap::default();
+        for annotation in annotations {
+            match ids.get(&annotation.id) {
+                Some(summary) => map
+                    .locations
+                    .entry(summary.clone())
+                    .or_default()
+                    .push(annotation),
+                None => map.unknown.push(annotation),
+            }
+        }
+        for req in &config.requirements {
+            map.collect_untraced(req);
+        }
+        Ok(map)
+    }
+
+    /// Record untraced requirements below `req`; returns whether `req` is
+    /// traced
+    fn collect_untraced(&mut self, req: &Requirement) -> bool {
+        let mut traced = self.locations.contains_key(&req.summary);
+        let position = self.untraced.len();
+        for child in &req.requirements {
+            if let RequirementReference::Full(child) = child {
+                traced |= self.collect_untraced(child);
+            }
+        }
+        if !traced && req.status != Some(Status::Deprecated) {
+            self.untraced.insert(position, req.summary.clone());
+        }
+        traced
+    }
+
+    /// Code locations implementing a requirement
+    pub fn locations(&self, summary: &str) -> &[Annotation] {
+        self.locations
+            .get(summary)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Findings for untraced requirements (warnings) and unknown IDs
+    /// (errors)
+    pub fn report(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for annotation in &self.unknown {
+            report.push(Finding {
+                rule: TRACE_UNKNOWN_ID_RULE.to_string(),
+                severity: Severity::Error,
+                error: Error::InvalidReference(format!(
+                    "{}:{}: annotation references unknown requirement '{}'",
+                    annotation.path.display(),
+                    annotation.line,
+                    annotation.id
+                )),
+                requirement: None,
+            });
+        }
+        for summary in &self.untraced {
+            report.push(Finding {
+                rule: TRACE_UNTRACED_RULE.to_string(),
+                severity: Severity::Warning,
+                error: Error::custom(format!(
+                    "Requirement '{}' has no implementing code",
+                    summary
+                )),
+                requirement: Some(summary.clone()),
+            });
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    requirements:
+      - summary: Login
+        name: LOGIN
+      - summary: Logout
+  - summary: Legacy export
+    status: deprecated
+  - summary: Audit log
+"#;
+
+    fn tree() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(
+            src.join("login.rs"),
+            "// rqm: LOGIN\nfn login() {}\n/* rqm: REQ-001, NOPE */\n",
+        )
+        .unwrap();
+        fs::write(
+            src.join("audit.py"),
+            "def audit():  # rqm: REQ-002\n    pass\n",
+        )
+        .unwrap();
+        fs::write(src.join("notes.txt"), "// rqm: LOGIN\n").unwrap();
+        fs::create_dir_all(temp.path().join("target")).unwrap();
+        fs::write(temp.path().join("target/gen.rs"), "// rqm: GHOST\n").unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_scan_finds_annotations() {
+        let temp = tree();
+        let annotations = TraceScanner::new().scan(temp.path()).unwrap();
+        let found: Vec<(String, usize, &str)> = annotations
+            .iter()
+            .map(|a| (a.path.display().to_string(), a.line, a.id.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("src/audit.py".to_string(), 1, "REQ-002"),
+                ("src/login.rs".to_string(), 1, "LOGIN"),
+                ("src/login.rs".to_string(), 3, "NOPE"),
+                ("src/login.rs".to_string(), 3, "REQ-001"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ids_end_at_a_delimiter() {
+        let scanner = TraceScanner::new();
+        let ids = |extension: &str, line: &str| -> Vec<String> {
+            let pattern = scanner
+                .patterns
+                .iter()
+                .find(|p| p.extensions.iter().any(|e| e == extension))
+                .unwrap();
+            scan_text(line, &pattern.pattern, Path::new("file"))
+                .into_iter()
+                .map(|a| a.id)
+                .collect()
+        };
+        assert_eq!(ids("rs", "// rqm: REQ-1 handles login"), ["REQ-1"]);
+        assert_eq!(ids("rs", "/* rqm: REQ-1,REQ-2.*/"), ["REQ-1", "REQ-2"]);
+        assert_eq!(ids("md", "<!-- rqm: REQ-3-->"), ["REQ-3"]);
+        assert_eq!(ids("py", "# rqm: REQ-4."), ["REQ-4"]);
+        assert_eq!(ids("py", "# rqm: REQ-4/legacy"), Vec::<String>::new());
+        assert_eq!(ids("py", "# warqm: REQ-5"), Vec::<String>::new());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_skips_symlinks() {
+        let temp = tree();
+        std::os::unix::fs::symlink(temp.path(), temp.path().join("src/loop")).unwrap();
+        let annotations = TraceScanner::new().scan(temp.path()).unwrap();
+        assert_eq!(annotations.len(), 4);
+    }
+
+    #[test]
+    fn test_custom_pattern() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("spec.txt"), "Implements [REQ-9]\n").unwrap();
+        let scanner = TraceScanner::new()
+            .with_pattern(TracePattern::new(&["txt"], r"Implements \[([\w-]+)\]").unwrap());
+        let annotations = scanner.scan(temp.path()).unwrap();
+        assert_eq!(annotations[0].id, "REQ-9");
+        assert!(TracePattern::new(&["txt"], "no group").is_err());
+    }
+
+    #[test]
+    fn test_trace_map_reports_gaps() {
+        let temp = tree();
+        let config = Parser::parse_str(YAML).unwrap();
+        let mut store = MetadataStore::init(temp.path().join(".rqm"), "REQ".to_string()).unwrap();
+        let logout = &config.all_requirements()[2];
+        assert_eq!(logout.summary, "Logout");
+        store.get_or_create_metadata(logout).unwrap();
+        store
+            .get_or_create_metadata(&config.requirements[2])
+            .unwrap();
+
+        let annotations = TraceScanner::new().scan(temp.path()).unwrap();
+        let map = TraceMap::build(&config, Some(&store), annotations).unwrap();
+        assert_eq!(map.locations("Login").len(), 1);
+        assert_eq!(map.locations("Logout")[0].line, 3);
+        assert_eq!(
+            map.locations("Audit log")[0].path,
+            Path::new("src/audit.py")
+        );
+        assert_eq!(map.unknown.len(), 1);
+        assert!(map.untraced.is_empty());
+
+        let map = TraceMap::build(&config, None, vec![]).unwrap();
+        assert_eq!(
+            map.untraced,
+            vec!["Authentication", "Login", "Logout", "Audit log"]
+        );
+
+        let map = TraceMap::build(
+            &config,
+            Some(&store),
+            TraceScanner::new().scan(temp.path()).unwrap(),
+        )
+        .unwrap();
+        let report = map.report();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, TRACE_UNKNOWN_ID_RULE);
+        assert!(report.errors[0].message().contains("src/login.rs:3"));
+        assert!(report.warnings.is_empty());
+    }
+}
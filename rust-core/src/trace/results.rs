@@ -0,0 +1,458 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Test results as evidence of verification
+//!
+//! A test verifies a requirement when it names one of the requirement's
+//! IDs: either explicitly, through a JUnit `requirement` property or an
+//! `rqm: REQ-042` marker in its name, or as a word of its name without
+//! module or class path, as in `auth::test_req_042_login`.
+
+use super::requirement_ids;
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference, Status};
+use crate::validator::{Finding, Severity, ValidationReport};
+use crate::xml::{parse_xml, Element};
+use crate::{Error, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Rule ID reported for requirements with failing tests
+pub const VERIFICATION_FAILING_RULE: &str = "verification-failing";
+
+/// Rule ID reported for requirements without tests
+pub const VERIFICATION_UNTESTED_RULE: &str = "verification-untested";
+
+/// JUnit property names whose values list requirement IDs
+const PROPERTIES: &[&str] = &["requirement", "requirements", "rqm"];
+
+/// Outcome of a single test
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// A test and the requirement IDs it names explicitly
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TestResult {
+    pub name: String,
+    pub outcome: TestOutcome,
+
+    /// IDs from properties or `rqm:` markers
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<String>,
+}
+
+impl TestResult {
+    fn new(name: String, outcome: TestOutcome) -> Self {
+        let mut ids = vec![];
+        if let Some((_, marked)) = name.split_once("rqm:") {
+            ids.extend(split_ids(marked));
+        }
+        Self { name, outcome, ids }
+    }
+
+    /// Whether the test names `id`, explicitly or as a word of its name
+    ///
+    /// Module and class paths are left out, so `auth::test_login` does not
+    /// name `AUTH`.
+    fn names(&self, id: &str) -> bool {
+        self.ids.iter().any(|i| i == id) || {
+            let own_name = self.name.rsplit(['.', ':']).next().unwrap_or_default();
+            let name = format!("_{}_", normalize(own_name));
+            name.contains(&format!("_{}_", normalize(id)))
+        }
+    }
+}
+
+/// Lowercase with every run of other characters than letters and digits
+/// replaced by one underscore
+fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_alphanumeric() {
+            out.extend(c.to_lowercase());
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
+
+fn split_ids(s: &str) -> impl Iterator<Item = String> + '_ {
+    s.split(|c: char| c == ',' || c.is_whitespace() || c == ']' || c == ')')
+        .map(|id| id.trim_end_matches('.'))
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+}
+
+/// Read test results from a JUnit XML report
+///
+/// `classname` and `name` of each `testcase` are joined with a dot. Cases
+/// with a `failure` or `error` failed; cases with `skipped` were skipped.
+pub fn parse_junit(xml: &str) -> Result<Vec<TestResult>> {
+    let document = parse_xml(xml)?;
+    let mut results = vec![];
+    document.visit(&mut |element: &Element| {
+        if element.name != "testcase" {
+            return;
+        }
+        let name = match (element.attr("classname"), element.attr("name")) {
+            (Some(class), Some(name)) if !class.is_empty() => format!("{}.{}", class, name),
+            (_, name) => name.unwrap_or_default().to_string(),
+        };
+        let outcome = if element.child("failure").is_some() || element.child("error").is_some() {
+            TestOutcome::Failed
+        } else if element.child("skipped").is_some() {
+            TestOutcome::Skipped
+        } else {
+            TestOutcome::Passed
+        };
+
+        let mut result = TestResult::new(name, outcome);
+        for property in element
+            .children_named("properties")
+            .flat_map(|properties| properties.children_named("property"))
+            .filter(|property| {
+                property
+                    .attr("name")
+                    .is_some_and(|name| PROPERTIES.contains(&name))
+            })
+        {
+            let value = property
+                .attr("value")
+                .map(String::from)
+                .unwrap_or_else(|| property.text_content());
+            result.ids.extend(split_ids(&value));
+        }
+        results.push(result);
+    });
+
+    if results.is_empty()
+        && !document
+            .children
+            .iter()
+            .any(|e| e.name.starts_with("testsuite"))
+    {
+        return Err(Error::custom("Not a JUnit report: no testsuite element"));
+    }
+    Ok(results)
+}
+
+/// Read test results from `cargo test -- -Z unstable-options --format json`
+///
+/// Lines that are not JSON test events, such as cargo's own output, are
+/// skipped.
+pub fn parse_cargo_json(output: &str) -> Result<Vec<TestResult>> {
+    let mut results = vec![];
+    for line in output.lines() {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            continue;
+        };
+        if event["type"] != "test" {
+            continue;
+        }
+        let outcome = match event["event"].as_str() {
+            Some("ok") => TestOutcome::Passed,
+            Some("failed") | Some("timeout") => TestOutcome::Failed,
+            Some("ignored") => TestOutcome::Skipped,
+            _ => continue,
+        };
+        let name = event["name"].as_str().unwrap_or_default().to_string();
+        results.push(TestResult::new(name, outcome));
+    }
+    Ok(results)
+}
+
+/// Whether a requirement's tests pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verification {
+    /// At least one test passed and none failed
+    Passing,
+    /// At least one test failed
+    Failing,
+    /// No test ran
+    Untested,
+}
+
+/// A requirement's tests and the verification they add up to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RequirementVerification {
+    pub verification: Verification,
+    pub tests: Vec<TestResult>,
+}
+
+/// Test results matched to requirements
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerificationMap {
+    /// Verification of every requirement, by summary
+    pub requirements: BTreeMap<String, RequirementVerification>,
+
+    /// Tests naming no requirement
+    pub unmatched: Vec<TestResult>,
+}
+
+impl VerificationMap {
+    /// Match test results to the requirements in `config`
+    ///
+    /// Tests may name a requirement by name, generated ID or UUID. Only a
+    /// requirement's own tests count, not those of the requirements below
+    /// it.
+    pub fn build(
+        config: &RequirementConfig,
+        store: Option<&MetadataStore>,
+        results: Vec<TestResult>,
+    ) -> Result<Self> {
+        let mut ids: Vec<(String, String)> = requirement_ids(config, store)?.into_iter().collect();
+        ids.sort();
+
+        let mut map = VerificationMap::default();
+        for req in config.all_requirements() {
+            map.requirements
+                .entry(req.summary.clone())
+                .or_insert(RequirementVerification {
+                    verification: Verification::Untested,
+                    tests: vec![],
+                });
+        }
+        for result in results {
+            let mut matched = false;
+            for (id, summary) in &ids {
+                if !result.names(id) {
+                    continue;
+                }
+                let entry = map.requirements.get_mut(summary).expect("known summary");
+                if !entry.tests.contains(&result) {
+                    entry.tests.push(result.clone());
+                }
+                matched = true;
+            }
+            if !matched {
+                map.unmatched.push(result);
+            }
+        }
+
+        for entry in map.requirements.values_mut() {
+            let outcomes: Vec<TestOutcome> = entry.tests.iter().map(|t| t.outcome).collect();
+            entry.verification = if outcomes.contains(&TestOutcome::Failed) {
+                Verification::Failing
+            } else if outcomes.contains(&TestOutcome::Passed) {
+                Verification::Passing
+            } else {
+                Verification::Untested
+            };
+        }
+        Ok(map)
+    }
+
+    /// Verification of a requirement; untested if it is unknown
+    pub fn verification(&self, summary: &str) -> Verification {
+        self.requirements
+            .get(summary)
+            .map_or(Verification::Untested, |entry| entry.verification)
+    }
+
+    /// Mark implemented requirements with passing tests as verified, and
+    /// verified ones with failing tests as implemented again
+    ///
+    /// Returns the summaries of changed requirements with their old and new
+    /// status.
+    pub fn update_statuses(&self, config: &mut RequirementConfig) -> Vec<(String, Status, Status)> {
+        let mut changes = vec![];
+        let mut stack: Vec<&mut Requirement> = config.requirements.iter_mut().collect();
+        while let Some(req) = stack.pop() {
+            let new = match (req.status, self.verification(&req.summary)) {
+                (Some(Status::Implemented), Verification::Passing) => Some(Status::Verified),
+                (Some(Status::Verified), Verification::Failing) => Some(Status::Implemented),
+                _ => None,
+            };
+            if let (Some(old), Some(new)) = (req.status, new) {
+                changes.push((req.summary.clone(), old, new));
+                req.status = Some(new);
+            }
+            for child in &mut req.requirements {
+                if let RequirementReference::Full(child) = child {
+                    stack.push(child);
+                }
+            }
+        }
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes
+    }
+
+    /// Findings for requirements with failing tests (errors) and for
+    /// untested requirements with nothing defined below them (warnings)
+    ///
+    /// Deprecated requirements are never reported as untested.
+    pub fn report(&self, config: &RequirementConfig) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for req in config.all_requirements() {
+            let Some(entry) = self.requirements.get(&req.summary) else {
+                continue;
+            };
+            match entry.verification {
+                Verification::Failing => {
+                    let failed: Vec<&str> = entry
+                        .tests
+                        .iter()
+                        .filter(|test| test.outcome == TestOutcome::Failed)
+                        .map(|test| test.name.as_str())
+                        .collect();
+                    report.push(Finding {
+                        rule: VERIFICATION_FAILING_RULE.to_string(),
+                        severity: Severity::Error,
+                        error: Error::custom(format!(
+                            "Requirement '{}' has failing tests: {}",
+                            req.summary,
+                            failed.join(", ")
+                        )),
+                        requirement: Some(req.summary.clone()),
+                    });
+                }
+                Verification::Untested
+                    if req.status != Some(Status::Deprecated)
+                        && !req
+                            .requirements
+                            .iter()
+                            .any(|child| matches!(child, RequirementReference::Full(_))) =>
+                {
+                    report.push(Finding {
+                        rule: VERIFICATION_UNTESTED_RULE.to_string(),
+                        severity: Severity::Warning,
+                        error: Error::custom(format!(
+                            "Requirement '{}' has no passing or failing tests",
+                            req.summary
+                        )),
+                        requirement: Some(req.summary.clone()),
+                    });
+                }
+                _ => {}
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Authentication
+    name: AUTH
+    status: implemented
+    requirements:
+      - summary: Login
+        name: LOGIN-1
+        status: verified
+      - summary: Logout
+        name: LOGOUT
+  - summary: Audit log
+    name: AUDIT
+    status: implemented
+"#;
+
+    const JUNIT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="auth" tests="4">
+    <testcase classname="auth.LoginTest" name="test_login_1_with_password"/>
+    <testcase classname="auth.LoginTest" name="rejects bad passwords [rqm: LOGIN-1]">
+      <failure message="expected 401">stack</failure>
+    </testcase>
+    <testcase classname="audit" name="writes entries">
+      <properties>
+        <property name="requirement" value="AUDIT, AUTH"/>
+      </properties>
+    </testcase>
+    <testcase classname="misc" name="unrelated"><skipped/></testcase>
+  </testsuite>
+</testsuites>
+"#;
+
+    #[test]
+    fn test_parse_junit() {
+        let results = parse_junit(JUNIT).unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].name, "auth.LoginTest.test_login_1_with_password");
+        assert_eq!(results[1].outcome, TestOutcome::Failed);
+        assert_eq!(results[1].ids, vec!["LOGIN-1"]);
+        assert_eq!(results[2].ids, vec!["AUDIT", "AUTH"]);
+        assert_eq!(results[3].outcome, TestOutcome::Skipped);
+        assert!(parse_junit("<html/>").is_err());
+        assert!(parse_junit("<testsuites/>").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_cargo_json() {
+        let output = r#"   Compiling rqm v0.1.0
+{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "auth::test_logout" }
+{ "type": "test", "name": "auth::test_logout", "event": "ok" }
+{ "type": "test", "name": "auth::test_login_1", "event": "failed", "stdout": "" }
+{ "type": "test", "name": "slow", "event": "ignored" }
+"#;
+        let results = parse_cargo_json(output).unwrap();
+        let outcomes: Vec<(&str, TestOutcome)> = results
+            .iter()
+            .map(|r| (r.name.as_str(), r.outcome))
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("auth::test_logout", TestOutcome::Passed),
+                ("auth::test_login_1", TestOutcome::Failed),
+                ("slow", TestOutcome::Skipped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verification_and_status_updates() {
+        let mut config = Parser::parse_str(YAML).unwrap();
+        let map = VerificationMap::build(&config, None, parse_junit(JUNIT).unwrap()).unwrap();
+        assert_eq!(map.verification("Login"), Verification::Failing);
+        assert_eq!(map.requirements["Login"].tests.len(), 2);
+        assert_eq!(map.verification("Audit log"), Verification::Passing);
+        assert_eq!(map.verification("Authentication"), Verification::Passing);
+        assert_eq!(map.verification("Logout"), Verification::Untested);
+        assert_eq!(map.unmatched.len(), 1);
+
+        let report = map.report(&config);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, VERIFICATION_FAILING_RULE);
+        assert!(report.errors[0].message().contains("rejects bad passwords"));
+        let untested: Vec<_> = report
+            .warnings
+            .iter()
+            .filter_map(|w| w.requirement.as_deref())
+            .collect();
+        assert_eq!(untested, vec!["Logout"]);
+
+        let changes = map.update_statuses(&mut config);
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "Audit log".to_string(),
+                    Status::Implemented,
+                    Status::Verified
+                ),
+                (
+                    "Authentication".to_string(),
+                    Status::Implemented,
+                    Status::Verified
+                ),
+                ("Login".to_string(), Status::Verified, Status::Implemented),
+            ]
+        );
+        assert_eq!(config.requirements[1].status, Some(Status::Verified));
+    }
+}
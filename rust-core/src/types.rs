@@ -2,8 +2,19 @@
 // Copyright (c) 2025
 // SPDX-License-Identifier: MIT
 
+use crate::{Error, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+
+mod borrowed;
+
+pub use borrowed::{
+    PersonAliasRef, RequirementConfigRef, RequirementRef, RequirementReferenceRef,
+    RequirementTemplateRef,
+};
 
 /// Top-level configuration for a requirements file
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -15,6 +26,10 @@ pub struct RequirementConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub aliases: Vec<PersonAlias>,
 
+    /// Named partial requirements that can be applied via `template:`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<RequirementTemplate>,
+
     /// Top-level requirements
     pub requirements: Vec<Requirement>,
 }
@@ -36,6 +51,124 @@ impl RequirementConfig {
         }
         all
     }
+
+    /// Resolve `template:` references by merging template defaults into requirements
+    ///
+    /// Fields set on the requirement win over the template, tags are merged.
+    /// Templates may `extend` other templates; cycles are rejected.
+    pub fn apply_templates(&mut self) -> Result<()> {
+        let mut resolved: HashMap<String, RequirementTemplate> = HashMap::new();
+        for template in &self.templates {
+            let merged = self.resolve_template(&template.name, &mut Vec::new())?;
+            resolved.insert(template.name.clone(), merged);
+        }
+
+        for req in &mut self.requirements {
+            req.apply_template(&resolved)?;
+        }
+
+        Ok(())
+    }
+
+    /// Give nested requirements without a priority their parent's
+    ///
+    /// Inherited priorities are passed on further down. Requirements
+    /// referenced by summary keep their own. Returns the number of
+    /// requirements that inherited a priority.
+    pub fn inherit_priorities(&mut self) -> usize {
+        let mut inherited = 0;
+        let mut stack: Vec<&mut Requirement> = self.requirements.iter_mut().collect();
+        while let Some(req) = stack.pop() {
+            let priority = req.priority;
+            for child in &mut req.requirements {
+                if let RequirementReference::Full(child) = child {
+                    if child.priority.is_none() && priority.is_some() {
+                        child.priority = priority;
+                        inherited += 1;
+                    }
+                    stack.push(child);
+                }
+            }
+        }
+        inherited
+    }
+
+    fn resolve_template(&self, name: &str, chain: &mut Vec<String>) -> Result<RequirementTemplate> {
+        if chain.iter().any(|n| n == name) {
+            chain.push(name.to_string());
+            return Err(Error::CircularReference(format!(
+                "Template inheritance cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        let template = self
+            .templates
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| Error::InvalidReference(format!("Unknown template '{}'", name)))?;
+
+        match &template.extends {
+            Some(parent) => {
+                chain.push(name.to_string());
+                let base = self.resolve_template(parent, chain)?;
+                chain.pop();
+                Ok(template.merged_over(&base))
+            }
+            None => Ok(template.clone()),
+        }
+    }
+}
+
+/// Reusable defaults that requirements can inherit via `template:`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementTemplate {
+    /// Template identifier referenced by requirements
+    pub name: String,
+
+    /// Parent template to inherit defaults from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+
+    /// Default owner reference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<OwnerReference>,
+
+    /// Tags added to every requirement using this template
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Default priority level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+
+    /// Default status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+}
+
+impl RequirementTemplate {
+    /// Merge this template over a base template, with this template's values winning
+    fn merged_over(&self, base: &RequirementTemplate) -> RequirementTemplate {
+        RequirementTemplate {
+            name: self.name.clone(),
+            extends: self.extends.clone(),
+            owner: self.owner.clone().or_else(|| base.owner.clone()),
+            tags: merge_tags(&base.tags, &self.tags),
+            priority: self.priority.or(base.priority),
+            status: self.status.or(base.status),
+        }
+    }
+}
+
+/// Union two tag lists, keeping first-seen order
+fn merge_tags<T: AsRef<str> + Clone>(base: &[T], extra: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+    base.iter()
+        .chain(extra)
+        .filter(|&tag| seen.insert(tag.as_ref()))
+        .cloned()
+        .collect()
 }
 
 /// Person alias for requirement ownership
@@ -58,7 +191,7 @@ pub struct PersonAlias {
 }
 
 /// Owner reference (email, GitHub username, or alias)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(untagged)]
 pub enum OwnerReference {
     String(String),
@@ -88,7 +221,7 @@ impl OwnerReference {
 }
 
 /// Priority level for requirements
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Priority {
     Critical,
@@ -97,8 +230,38 @@ pub enum Priority {
     Low,
 }
 
+impl Priority {
+    /// Name as written in requirement files, e.g. `high`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Priority::Critical => "critical",
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+
+    /// Rank for comparisons, higher for more urgent priorities
+    pub fn urgency(self) -> u8 {
+        match self {
+            Priority::Critical => 3,
+            Priority::High => 2,
+            Priority::Medium => 1,
+            Priority::Low => 0,
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Status of a requirement
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, JsonSchema,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
     Draft,
@@ -109,8 +272,253 @@ pub enum Status {
     Deprecated,
 }
 
+impl Status {
+    /// Name as written in requirement files, e.g. `draft`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Draft => "draft",
+            Status::Proposed => "proposed",
+            Status::Approved => "approved",
+            Status::Implemented => "implemented",
+            Status::Verified => "verified",
+            Status::Deprecated => "deprecated",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Who may read a requirement, from least to most confidential
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidentiality {
+    /// Anyone, including external suppliers
+    #[default]
+    Public,
+    /// People within the organization
+    Internal,
+    /// Only people named by the project
+    Restricted,
+}
+
+impl Confidentiality {
+    /// Name as written in requirement files, e.g. `internal`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Confidentiality::Public => "public",
+            Confidentiality::Internal => "internal",
+            Confidentiality::Restricted => "restricted",
+        }
+    }
+}
+
+impl fmt::Display for Confidentiality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The same item in another system, such as a Jira issue or a DOORS object
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ExternalRef {
+    /// Name of the system, e.g. `jira`
+    pub system: String,
+
+    /// ID of the item in that system, e.g. `PROJ-123`
+    pub id: String,
+
+    /// Link to the item, overriding the system's URL template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ExternalRef {
+    /// Link to the item: its own URL, or the URL template of its system
+    /// with `{id}` replaced by the percent-encoded ID
+    pub fn resolve_url(&self, templates: &BTreeMap<String, String>) -> Option<String> {
+        self.url.clone().or_else(|| {
+            templates
+                .get(&self.system)
+                .map(|template| template.replace("{id}", &crate::percent::encode(&self.id)))
+        })
+    }
+}
+
+impl fmt::Display for ExternalRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.system, self.id)
+    }
+}
+
+/// A file or web page attached to a requirement, such as a diagram or a
+/// mockup
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Attachment {
+    /// Path relative to the project root, the directory holding `.rqm`, or
+    /// an http(s) URL
+    pub path: String,
+
+    /// Text shown with the attachment
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+
+    /// Expected digest of the file, as `sha256:<hex>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
+impl Attachment {
+    /// File extensions shown as images rather than linked
+    const IMAGE_EXTENSIONS: &'static [&'static str] = &["png", "jpg", "jpeg", "gif", "svg", "webp"];
+
+    /// Create an attachment without caption or checksum
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            caption: None,
+            checksum: None,
+        }
+    }
+
+    /// Whether the attachment is a web page rather than a file
+    ///
+    /// Only well-formed http(s) URLs count, so attachments such as
+    /// `javascript:...` are never linked.
+    pub fn is_url(&self) -> bool {
+        crate::validator::is_well_formed_url(self.path.trim())
+    }
+
+    /// Whether the path starts with a URL scheme such as `https:`, whether
+    /// or not the URL is one [`is_url`](Self::is_url) accepts
+    pub fn has_scheme(&self) -> bool {
+        self.path.trim().split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        })
+    }
+
+    /// Whether the attachment is an image, judging by its extension
+    pub fn is_image(&self) -> bool {
+        let path = self.path.split(['?', '#']).next().unwrap_or_default();
+        Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                Self::IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            })
+    }
+
+    /// Text to show for the attachment: its caption, or else its path
+    pub fn label(&self) -> &str {
+        self.caption.as_deref().unwrap_or(&self.path)
+    }
+
+    /// The path relative to the project root, with `.` and `..` resolved
+    ///
+    /// `None` for URLs, absolute paths and paths leading out of the root.
+    /// Symlinks are not looked at; use [`resolve`](Self::resolve) to read
+    /// the file.
+    pub fn relative_path(&self) -> Option<PathBuf> {
+        if self.has_scheme() {
+            return None;
+        }
+        let mut relative = PathBuf::new();
+        for component in Path::new(self.path.trim()).components() {
+            match component {
+                Component::Normal(part) => relative.push(part),
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !relative.pop() {
+                        return None;
+                    }
+                }
+                Component::RootDir | Component::Prefix(_) => return None,
+            }
+        }
+        (!relative.as_os_str().is_empty()).then_some(relative)
+    }
+
+    /// The attached file under `root`, with symlinks resolved
+    ///
+    /// `None` if the file does not exist or a symlink leads out of `root`.
+    pub fn resolve(&self, root: &Path) -> Option<PathBuf> {
+        let root = root.canonicalize().ok()?;
+        let file = root.join(self.relative_path()?).canonicalize().ok()?;
+        file.starts_with(&root).then_some(file)
+    }
+}
+
+/// Text of a requirement in another language
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Translation {
+    /// Translated summary
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+
+    /// Translated description
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A sign-off of a requirement, or of a baseline, by a person in a role
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct Approval {
+    /// Who approved: an email, a GitHub username (@user) or an alias
+    pub person: String,
+
+    /// Role the person approved in, e.g. `product-owner`
+    pub role: String,
+
+    /// Day of the approval, YYYY-MM-DD
+    pub date: String,
+
+    /// Label of the baseline the approval was given for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<String>,
+
+    /// Signature of the requirement as approved, `sha256:<hex>`; the
+    /// approval lapses once the requirement says something else
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+impl Approval {
+    pub fn new(
+        person: impl Into<String>,
+        role: impl Into<String>,
+        date: impl Into<String>,
+    ) -> Self {
+        Self {
+            person: person.into(),
+            role: role.into(),
+            date: date.into(),
+            baseline: None,
+            signature: None,
+        }
+    }
+}
+
 /// A single requirement or reference to a requirement
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(untagged)]
 pub enum RequirementReference {
     /// Full requirement definition
@@ -120,8 +528,18 @@ pub enum RequirementReference {
     Reference(String),
 }
 
+impl RequirementReference {
+    /// Get the summary of the referenced requirement
+    pub fn summary(&self) -> &str {
+        match self {
+            RequirementReference::Full(req) => &req.summary,
+            RequirementReference::Reference(summary) => summary,
+        }
+    }
+}
+
 /// A single requirement
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct Requirement {
     /// Short, unique identifier (required)
     pub summary: String,
@@ -150,6 +568,10 @@ pub struct Requirement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<OwnerReference>,
 
+    /// Name of the template this requirement inherits defaults from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+
     /// Child requirements
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub requirements: Vec<RequirementReference>,
@@ -158,6 +580,22 @@ pub struct Requirement {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub further_information: Vec<String>,
 
+    /// The same requirement in other systems
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<ExternalRef>,
+
+    /// Files and web pages attached to the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+
+    /// Summary and description in other languages, by locale such as `de`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+
+    /// Sign-offs of the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<Approval>,
+
     /// Tags for categorization
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
@@ -170,6 +608,34 @@ pub struct Requirement {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<Status>,
 
+    /// Who may read the requirement; unset inherits the parent's level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidentiality: Option<Confidentiality>,
+
+    /// Release this requirement is targeted at (e.g., "2.0")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_release: Option<String>,
+
+    /// Milestone within the release plan
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+
+    /// Effort estimate in the project's unit (story points or hours)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<f64>,
+
+    /// Summary of the requirement that replaces this one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+
+    /// Summaries of requirements this one replaces
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supersedes: Vec<String>,
+
+    /// Previous summary, so metadata carries over when the summary is reworded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<String>,
+
     /// Creation timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<String>,
@@ -177,6 +643,10 @@ pub struct Requirement {
     /// Last update timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<String>,
+
+    /// Who made the last update, as `Name <email>`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
 }
 
 impl Requirement {
@@ -190,37 +660,194 @@ impl Requirement {
             acceptance_test: None,
             acceptance_test_link: None,
             owner: None,
+            template: None,
             requirements: Vec::new(),
             further_information: Vec::new(),
+            external_refs: Vec::new(),
+            attachments: Vec::new(),
+            translations: BTreeMap::new(),
+            approvals: Vec::new(),
             tags: Vec::new(),
             priority: None,
             status: None,
+            confidentiality: None,
+            target_release: None,
+            milestone: None,
+            estimate: None,
+            superseded_by: None,
+            supersedes: Vec::new(),
+            renamed_from: None,
             created_at: None,
             updated_at: None,
+            updated_by: None,
+        }
+    }
+
+    /// Copy this requirement with nested children replaced by references to
+    /// their summaries, without copying the children themselves
+    pub fn shallow_clone(&self) -> Self {
+        // Destructured so a new field cannot be forgotten here
+        let Self {
+            summary,
+            name,
+            description,
+            justification,
+            acceptance_test,
+            acceptance_test_link,
+            owner,
+            template,
+            requirements,
+            further_information,
+            external_refs,
+            attachments,
+            translations,
+            approvals,
+            tags,
+            priority,
+            status,
+            confidentiality,
+            target_release,
+            milestone,
+            estimate,
+            superseded_by,
+            supersedes,
+            renamed_from,
+            created_at,
+            updated_at,
+            updated_by,
+        } = self;
+        Self {
+            summary: summary.clone(),
+            name: name.clone(),
+            description: description.clone(),
+            justification: justification.clone(),
+            acceptance_test: acceptance_test.clone(),
+            acceptance_test_link: acceptance_test_link.clone(),
+            owner: owner.clone(),
+            template: template.clone(),
+            requirements: requirements
+                .iter()
+                .map(|child| RequirementReference::Reference(child.summary().to_string()))
+                .collect(),
+            further_information: further_information.clone(),
+            external_refs: external_refs.clone(),
+            attachments: attachments.clone(),
+            translations: translations.clone(),
+            approvals: approvals.clone(),
+            tags: tags.clone(),
+            priority: *priority,
+            status: *status,
+            confidentiality: *confidentiality,
+            target_release: target_release.clone(),
+            milestone: milestone.clone(),
+            estimate: *estimate,
+            superseded_by: superseded_by.clone(),
+            supersedes: supersedes.clone(),
+            renamed_from: renamed_from.clone(),
+            created_at: created_at.clone(),
+            updated_at: updated_at.clone(),
+            updated_by: updated_by.clone(),
         }
     }
 
     /// Flatten this requirement and all children into a list
     pub fn flatten(&self) -> Vec<&Requirement> {
-        let mut result = vec![self];
-        for child in &self.requirements {
-            if let RequirementReference::Full(req) = child {
-                result.extend(req.flatten());
-            }
+        let mut result = vec![];
+        let mut stack = vec![self];
+        while let Some(req) = stack.pop() {
+            result.push(req);
+            stack.extend(
+                req.requirements
+                    .iter()
+                    .rev()
+                    .filter_map(|child| match child {
+                        RequirementReference::Full(child) => Some(child.as_ref()),
+                        RequirementReference::Reference(_) => None,
+                    }),
+            );
         }
         result
     }
 
+    /// Check whether this requirement is deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.status == Some(Status::Deprecated)
+    }
+
     /// Get the ID (name or summary)
     pub fn id(&self) -> &str {
         self.name.as_deref().unwrap_or(&self.summary)
     }
+
+    /// Apply resolved templates to this requirement and its nested children
+    fn apply_template(&mut self, templates: &HashMap<String, RequirementTemplate>) -> Result<()> {
+        if let Some(name) = &self.template {
+            let template = templates.get(name).ok_or_else(|| {
+                Error::InvalidReference(format!(
+                    "Requirement '{}' uses unknown template '{}'",
+                    self.summary, name
+                ))
+            })?;
+
+            if self.owner.is_none() {
+                self.owner = template.owner.clone();
+            }
+            if self.priority.is_none() {
+                self.priority = template.priority;
+            }
+            if self.status.is_none() {
+                self.status = template.status;
+            }
+            self.tags = merge_tags(&template.tags, &self.tags);
+        }
+
+        for child in &mut self.requirements {
+            if let RequirementReference::Full(req) = child {
+                req.apply_template(templates)?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_names_match_the_file_format() {
+        let serialized = |value: serde_json::Value| value.as_str().unwrap_or_default().to_string();
+        for priority in [
+            Priority::Critical,
+            Priority::High,
+            Priority::Medium,
+            Priority::Low,
+        ] {
+            assert_eq!(serialized(serde_json::json!(priority)), priority.as_str());
+        }
+        for status in [
+            Status::Draft,
+            Status::Proposed,
+            Status::Approved,
+            Status::Implemented,
+            Status::Verified,
+            Status::Deprecated,
+        ] {
+            assert_eq!(serialized(serde_json::json!(status)), status.to_string());
+        }
+        for confidentiality in [
+            Confidentiality::Public,
+            Confidentiality::Internal,
+            Confidentiality::Restricted,
+        ] {
+            assert_eq!(
+                serialized(serde_json::json!(confidentiality)),
+                confidentiality.as_str()
+            );
+        }
+    }
+
     #[test]
     fn test_requirement_new() {
         let req = Requirement::new("Test Requirement");
@@ -256,6 +883,23 @@ mod tests {
         assert_eq!(flattened[1].summary, "Child");
     }
 
+    #[test]
+    fn test_requirement_flatten_preorder() {
+        let mut a = Requirement::new("A");
+        a.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("A1"))));
+        let mut root = Requirement::new("Root");
+        root.requirements
+            .push(RequirementReference::Full(Box::new(a)));
+        root.requirements
+            .push(RequirementReference::Reference("Elsewhere".to_string()));
+        root.requirements
+            .push(RequirementReference::Full(Box::new(Requirement::new("B"))));
+
+        let summaries: Vec<_> = root.flatten().iter().map(|r| r.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["Root", "A", "A1", "B"]);
+    }
+
     #[test]
     fn test_config_alias_map() {
         let config = RequirementConfig {
@@ -266,6 +910,7 @@ mod tests {
                 email: Some("john@example.com".to_string()),
                 github: None,
             }],
+            templates: vec![],
             requirements: vec![],
         };
 
@@ -276,4 +921,120 @@ mod tests {
             Some("john@example.com".to_string())
         );
     }
+
+    fn template_config() -> RequirementConfig {
+        let mut inherits = Requirement::new("Inherits");
+        inherits.template = Some("security".to_string());
+        inherits.tags = vec!["auth".to_string()];
+
+        let mut overrides = Requirement::new("Overrides");
+        overrides.template = Some("security".to_string());
+        overrides.priority = Some(Priority::Low);
+        overrides.requirements = vec![RequirementReference::Full(Box::new({
+            let mut child = Requirement::new("Nested");
+            child.template = Some("base".to_string());
+            child
+        }))];
+
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![
+                RequirementTemplate {
+                    name: "base".to_string(),
+                    extends: None,
+                    owner: Some(OwnerReference::String("team@example.com".to_string())),
+                    tags: vec!["baseline".to_string()],
+                    priority: Some(Priority::Medium),
+                    status: None,
+                },
+                RequirementTemplate {
+                    name: "security".to_string(),
+                    extends: Some("base".to_string()),
+                    owner: None,
+                    tags: vec!["security".to_string()],
+                    priority: Some(Priority::High),
+                    status: Some(Status::Draft),
+                },
+            ],
+            requirements: vec![inherits, overrides],
+        }
+    }
+
+    #[test]
+    fn test_apply_templates_inherits_defaults() {
+        let mut config = template_config();
+        config.apply_templates().unwrap();
+
+        let req = &config.requirements[0];
+        assert_eq!(req.priority, Some(Priority::High));
+        assert_eq!(req.status, Some(Status::Draft));
+        assert_eq!(req.owner.as_ref().unwrap().as_str(), "team@example.com");
+        assert_eq!(req.tags, vec!["baseline", "security", "auth"]);
+    }
+
+    #[test]
+    fn test_apply_templates_requirement_wins_and_nested() {
+        let mut config = template_config();
+        config.apply_templates().unwrap();
+
+        let req = &config.requirements[1];
+        assert_eq!(req.priority, Some(Priority::Low));
+
+        let all = config.all_requirements();
+        let nested = all.iter().find(|r| r.summary == "Nested").unwrap();
+        assert_eq!(nested.priority, Some(Priority::Medium));
+        assert_eq!(nested.tags, vec!["baseline"]);
+    }
+
+    #[test]
+    fn test_apply_templates_unknown_template() {
+        let mut config = template_config();
+        config.requirements[0].template = Some("missing".to_string());
+
+        let result = config.apply_templates();
+        assert!(matches!(result, Err(Error::InvalidReference(_))));
+    }
+
+    #[test]
+    fn test_apply_templates_inheritance_cycle() {
+        let mut config = template_config();
+        config.templates[0].extends = Some("security".to_string());
+
+        let result = config.apply_templates();
+        assert!(matches!(result, Err(Error::CircularReference(_))));
+    }
+
+    #[test]
+    fn test_inherit_priorities_passes_down_nested() {
+        let yaml = r#"
+version: "1.0"
+requirements:
+  - summary: Braking
+    priority: critical
+    requirements:
+      - summary: Sensors
+        requirements:
+          - summary: Redundancy
+      - summary: Logging
+        priority: low
+      - Wipers
+  - summary: Wipers
+"#;
+        let mut config: RequirementConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.inherit_priorities(), 2);
+
+        let priority = |summary: &str| {
+            config
+                .all_requirements()
+                .into_iter()
+                .find(|req| req.summary == summary)
+                .unwrap()
+                .priority
+        };
+        assert_eq!(priority("Sensors"), Some(Priority::Critical));
+        assert_eq!(priority("Redundancy"), Some(Priority::Critical));
+        assert_eq!(priority("Logging"), Some(Priority::Low));
+        assert_eq!(priority("Wipers"), None);
+    }
 }
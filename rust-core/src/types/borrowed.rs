@@ -0,0 +1,615 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Requirement documents borrowing their text from the parsed YAML
+//!
+//! Validation and reporting only read requirements, so copying every
+//! summary, description and tag into its own `String` is wasted work on
+//! large files. These types hold `Cow` strings that point into the source
+//! text wherever YAML allows it; scalars with escapes or folding are the
+//! only ones allocated. [`RequirementConfigRef::into_owned`] converts to
+//! the owned types when a requirement needs to outlive the source.
+
+use super::{
+    merge_tags, Approval, Attachment, Confidentiality, ExternalRef, Priority, Status, Translation,
+};
+use crate::types::RequirementReference;
+use crate::{
+    Error, OwnerReference, PersonAlias, Requirement, RequirementConfig, RequirementTemplate, Result,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+/// A string deserialized without copying when the source allows it
+///
+/// `Cow` on its own always deserializes into an owned string unless the
+/// field is marked `borrow`, which does not reach into `Option` or `Vec`.
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct Text<'a>(#[serde(borrow)] Cow<'a, str>);
+
+fn optional_text<'de: 'a, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<Cow<'a, str>>, D::Error> {
+    Ok(Option::<Text<'a>>::deserialize(deserializer)?.map(|text| text.0))
+}
+
+fn text_list<'de: 'a, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<Cow<'a, str>>, D::Error> {
+    Ok(Vec::<Text<'a>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|text| text.0)
+        .collect())
+}
+
+/// Top-level configuration borrowing from its YAML source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementConfigRef<'a> {
+    /// Schema version
+    #[serde(borrow)]
+    pub version: Cow<'a, str>,
+
+    /// Person aliases for ownership
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<PersonAliasRef<'a>>,
+
+    /// Named partial requirements that can be applied via `template:`
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<RequirementTemplateRef<'a>>,
+
+    /// Top-level requirements
+    #[serde(borrow)]
+    pub requirements: Vec<RequirementRef<'a>>,
+}
+
+impl<'a> RequirementConfigRef<'a> {
+    /// Get a map of aliases for quick lookup
+    pub fn alias_map(&self) -> HashMap<&str, &PersonAliasRef<'a>> {
+        self.aliases
+            .iter()
+            .map(|alias| (alias.alias.as_ref(), alias))
+            .collect()
+    }
+
+    /// Flatten all requirements into a single list
+    pub fn all_requirements(&self) -> Vec<&RequirementRef<'a>> {
+        self.requirements
+            .iter()
+            .flat_map(RequirementRef::flatten)
+            .collect()
+    }
+
+    /// Resolve `template:` references like [`RequirementConfig::apply_templates`]
+    ///
+    /// Inherited owners and tags keep pointing into the source.
+    pub fn apply_templates(&mut self) -> Result<()> {
+        let mut resolved = HashMap::new();
+        for template in &self.templates {
+            let merged = self.resolve_template(&template.name, &mut Vec::new())?;
+            resolved.insert(template.name.to_string(), merged);
+        }
+
+        for req in &mut self.requirements {
+            req.apply_template(&resolved)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_template(
+        &self,
+        name: &str,
+        chain: &mut Vec<String>,
+    ) -> Result<RequirementTemplateRef<'a>> {
+        if chain.iter().any(|n| n == name) {
+            chain.push(name.to_string());
+            return Err(Error::CircularReference(format!(
+                "Template inheritance cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+
+        let template = self
+            .templates
+            .iter()
+            .find(|t| t.name == name)
+            .ok_or_else(|| Error::InvalidReference(format!("Unknown template '{}'", name)))?;
+
+        match &template.extends {
+            Some(parent) => {
+                chain.push(name.to_string());
+                let base = self.resolve_template(parent, chain)?;
+                chain.pop();
+                Ok(template.merged_over(&base))
+            }
+            None => Ok(template.clone()),
+        }
+    }
+
+    /// Copy every string into an owned [`RequirementConfig`]
+    pub fn into_owned(self) -> RequirementConfig {
+        RequirementConfig {
+            version: self.version.into_owned(),
+            aliases: self
+                .aliases
+                .into_iter()
+                .map(PersonAliasRef::into_owned)
+                .collect(),
+            templates: self
+                .templates
+                .into_iter()
+                .map(RequirementTemplateRef::into_owned)
+                .collect(),
+            requirements: self
+                .requirements
+                .into_iter()
+                .map(RequirementRef::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// Person alias borrowing from its YAML source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PersonAliasRef<'a> {
+    /// Short alias identifier
+    #[serde(borrow)]
+    pub alias: Cow<'a, str>,
+
+    /// Full name of the person
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Cow<'a, str>>,
+
+    /// Email address
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<Cow<'a, str>>,
+
+    /// GitHub username
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github: Option<Cow<'a, str>>,
+}
+
+impl PersonAliasRef<'_> {
+    /// Copy every string into an owned [`PersonAlias`]
+    pub fn into_owned(self) -> PersonAlias {
+        PersonAlias {
+            alias: self.alias.into_owned(),
+            name: self.name.map(Cow::into_owned),
+            email: self.email.map(Cow::into_owned),
+            github: self.github.map(Cow::into_owned),
+        }
+    }
+}
+
+/// Requirement template borrowing from its YAML source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementTemplateRef<'a> {
+    /// Template identifier referenced by requirements
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+
+    /// Parent template to inherit defaults from
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extends: Option<Cow<'a, str>>,
+
+    /// Default owner reference
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Cow<'a, str>>,
+
+    /// Tags added to every requirement using this template
+    #[serde(borrow, default, deserialize_with = "text_list")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Cow<'a, str>>,
+
+    /// Default priority level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+
+    /// Default status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+}
+
+impl<'a> RequirementTemplateRef<'a> {
+    /// Merge this template over a base template, with this template's values winning
+    fn merged_over(&self, base: &RequirementTemplateRef<'a>) -> RequirementTemplateRef<'a> {
+        RequirementTemplateRef {
+            name: self.name.clone(),
+            extends: self.extends.clone(),
+            owner: self.owner.clone().or_else(|| base.owner.clone()),
+            tags: merge_tags(&base.tags, &self.tags),
+            priority: self.priority.or(base.priority),
+            status: self.status.or(base.status),
+        }
+    }
+
+    /// Copy every string into an owned [`RequirementTemplate`]
+    pub fn into_owned(self) -> RequirementTemplate {
+        RequirementTemplate {
+            name: self.name.into_owned(),
+            extends: self.extends.map(Cow::into_owned),
+            owner: self
+                .owner
+                .map(|owner| OwnerReference::String(owner.into_owned())),
+            tags: self.tags.into_iter().map(Cow::into_owned).collect(),
+            priority: self.priority,
+            status: self.status,
+        }
+    }
+}
+
+/// A child requirement or reference borrowing from its YAML source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum RequirementReferenceRef<'a> {
+    /// Full requirement definition
+    Full(#[serde(borrow)] Box<RequirementRef<'a>>),
+
+    /// Reference by summary
+    Reference(#[serde(borrow)] Cow<'a, str>),
+}
+
+impl RequirementReferenceRef<'_> {
+    /// Get the summary of the referenced requirement
+    pub fn summary(&self) -> &str {
+        match self {
+            RequirementReferenceRef::Full(req) => &req.summary,
+            RequirementReferenceRef::Reference(summary) => summary,
+        }
+    }
+
+    /// Copy every string into an owned [`RequirementReference`]
+    pub fn into_owned(self) -> RequirementReference {
+        match self {
+            RequirementReferenceRef::Full(req) => {
+                RequirementReference::Full(Box::new(req.into_owned()))
+            }
+            RequirementReferenceRef::Reference(summary) => {
+                RequirementReference::Reference(summary.into_owned())
+            }
+        }
+    }
+}
+
+/// A single requirement borrowing from its YAML source
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequirementRef<'a> {
+    /// Short, unique identifier (required)
+    #[serde(borrow)]
+    pub summary: Cow<'a, str>,
+
+    /// Optional human-friendly name or ID
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Cow<'a, str>>,
+
+    /// Detailed description
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<Cow<'a, str>>,
+
+    /// Rationale for the requirement
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub justification: Option<Cow<'a, str>>,
+
+    /// Acceptance criteria text
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acceptance_test: Option<Cow<'a, str>>,
+
+    /// URL to acceptance test documentation
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acceptance_test_link: Option<Cow<'a, str>>,
+
+    /// Owner reference (email, GitHub username, or alias)
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Cow<'a, str>>,
+
+    /// Name of the template this requirement inherits defaults from
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<Cow<'a, str>>,
+
+    /// Child requirements
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub requirements: Vec<RequirementReferenceRef<'a>>,
+
+    /// Additional information
+    #[serde(borrow, default, deserialize_with = "text_list")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub further_information: Vec<Cow<'a, str>>,
+
+    /// The same requirement in other systems
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_refs: Vec<ExternalRef>,
+
+    /// Files and web pages attached to the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+
+    /// Summary and description in other languages, by locale such as `de`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub translations: BTreeMap<String, Translation>,
+
+    /// Sign-offs of the requirement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<Approval>,
+
+    /// Tags for categorization
+    #[serde(borrow, default, deserialize_with = "text_list")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Cow<'a, str>>,
+
+    /// Priority level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+
+    /// Current status
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+
+    /// Who may read the requirement; unset inherits the parent's level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidentiality: Option<Confidentiality>,
+
+    /// Release this requirement is targeted at (e.g., "2.0")
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_release: Option<Cow<'a, str>>,
+
+    /// Milestone within the release plan
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<Cow<'a, str>>,
+
+    /// Effort estimate in the project's unit (story points or hours)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<f64>,
+
+    /// Summary of the requirement that replaces this one
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<Cow<'a, str>>,
+
+    /// Summaries of requirements this one replaces
+    #[serde(borrow, default, deserialize_with = "text_list")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub supersedes: Vec<Cow<'a, str>>,
+
+    /// Previous summary, so metadata carries over when the summary is reworded
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub renamed_from: Option<Cow<'a, str>>,
+
+    /// Creation timestamp
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<Cow<'a, str>>,
+
+    /// Last update timestamp
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<Cow<'a, str>>,
+
+    /// Who made the last update, as `Name <email>`
+    #[serde(borrow, default, deserialize_with = "optional_text")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<Cow<'a, str>>,
+}
+
+impl<'a> RequirementRef<'a> {
+    /// Flatten this requirement and all children into a list, in pre-order
+    pub fn flatten(&self) -> Vec<&RequirementRef<'a>> {
+        let mut result = vec![];
+        let mut stack = vec![self];
+        while let Some(req) = stack.pop() {
+            result.push(req);
+            stack.extend(
+                req.requirements
+                    .iter()
+                    .rev()
+                    .filter_map(|child| match child {
+                        RequirementReferenceRef::Full(child) => Some(child.as_ref()),
+                        RequirementReferenceRef::Reference(_) => None,
+                    }),
+            );
+        }
+        result
+    }
+
+    /// Check whether this requirement is deprecated
+    pub fn is_deprecated(&self) -> bool {
+        self.status == Some(Status::Deprecated)
+    }
+
+    /// Get the ID (name or summary)
+    pub fn id(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.summary)
+    }
+
+    /// Apply resolved templates to this requirement and its nested children
+    fn apply_template(
+        &mut self,
+        templates: &HashMap<String, RequirementTemplateRef<'a>>,
+    ) -> Result<()> {
+        if let Some(name) = &self.template {
+            let template = templates.get(name.as_ref()).ok_or_else(|| {
+                Error::InvalidReference(format!(
+                    "Requirement '{}' uses unknown template '{}'",
+                    self.summary, name
+                ))
+            })?;
+
+            if self.owner.is_none() {
+                self.owner = template.owner.clone();
+            }
+            if self.priority.is_none() {
+                self.priority = template.priority;
+            }
+            if self.status.is_none() {
+                self.status = template.status;
+            }
+            self.tags = merge_tags(&template.tags, &self.tags);
+        }
+
+        for child in &mut self.requirements {
+            if let RequirementReferenceRef::Full(req) = child {
+                req.apply_template(templates)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy every string into an owned [`Requirement`]
+    pub fn into_owned(self) -> Requirement {
+        // Destructured so a new field cannot be forgotten here
+        let Self {
+            summary,
+            name,
+            description,
+            justification,
+            acceptance_test,
+            acceptance_test_link,
+            owner,
+            template,
+            requirements,
+            further_information,
+            external_refs,
+            attachments,
+            translations,
+            approvals,
+            tags,
+            priority,
+            status,
+            confidentiality,
+            target_release,
+            milestone,
+            estimate,
+            superseded_by,
+            supersedes,
+            renamed_from,
+            created_at,
+            updated_at,
+            updated_by,
+        } = self;
+        let owned = |text: Option<Cow<'_, str>>| text.map(Cow::into_owned);
+        let owned_list = |list: Vec<Cow<'_, str>>| list.into_iter().map(Cow::into_owned).collect();
+        Requirement {
+            summary: summary.into_owned(),
+            name: owned(name),
+            description: owned(description),
+            justification: owned(justification),
+            acceptance_test: owned(acceptance_test),
+            acceptance_test_link: owned(acceptance_test_link),
+            owner: owner.map(|owner| OwnerReference::String(owner.into_owned())),
+            template: owned(template),
+            requirements: requirements
+                .into_iter()
+                .map(RequirementReferenceRef::into_owned)
+                .collect(),
+            further_information: owned_list(further_information),
+            external_refs,
+            attachments,
+            translations,
+            approvals,
+            tags: owned_list(tags),
+            priority,
+            status,
+            confidentiality,
+            target_release: owned(target_release),
+            milestone: owned(milestone),
+            estimate,
+            superseded_by: owned(superseded_by),
+            supersedes: owned_list(supersedes),
+            renamed_from: owned(renamed_from),
+            created_at: owned(created_at),
+            updated_at: owned(updated_at),
+            updated_by: owned(updated_by),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const YAML: &str = r#"
+version: "1.0"
+aliases:
+  - alias: john
+    email: john@example.com
+templates:
+  - name: security
+    owner: john
+    tags: [security]
+    priority: high
+requirements:
+  - summary: Login
+    template: security
+    description: "Users sign in with \"SSO\""
+    tags: [auth]
+    requirements:
+      - summary: Session timeout
+        status: draft
+      - Logout
+"#;
+
+    #[test]
+    fn test_parse_borrowed_points_into_source() {
+        let config = Parser::parse_borrowed(YAML).unwrap();
+        let login = &config.requirements[0];
+
+        assert!(matches!(login.summary, Cow::Borrowed(_)));
+        assert!(matches!(login.tags[1], Cow::Borrowed(_)));
+        // Escapes can only be resolved into a copy
+        assert!(matches!(login.description, Some(Cow::Owned(_))));
+        assert_eq!(
+            login.description.as_deref(),
+            Some("Users sign in with \"SSO\"")
+        );
+        assert_eq!(
+            config.alias_map()["john"].email.as_deref(),
+            Some("john@example.com")
+        );
+    }
+
+    #[test]
+    fn test_parse_borrowed_applies_templates() {
+        let config = Parser::parse_borrowed(YAML).unwrap();
+        let login = &config.requirements[0];
+
+        assert_eq!(login.owner.as_deref(), Some("john"));
+        assert_eq!(login.priority, Some(Priority::High));
+        assert_eq!(login.tags, vec!["security", "auth"]);
+
+        let summaries: Vec<_> = config.all_requirements().iter().map(|r| r.id()).collect();
+        assert_eq!(summaries, vec!["Login", "Session timeout"]);
+        assert_eq!(login.requirements[1].summary(), "Logout");
+    }
+
+    #[test]
+    fn test_into_owned_matches_parse_str() {
+        let borrowed = Parser::parse_borrowed(YAML).unwrap();
+        assert_eq!(borrowed.into_owned(), Parser::parse_str(YAML).unwrap());
+    }
+
+    #[test]
+    fn test_parse_borrowed_unknown_template() {
+        let yaml = "version: \"1.0\"\nrequirements:\n  - summary: A\n    template: missing\n";
+        let result = Parser::parse_borrowed(yaml);
+        assert!(matches!(result, Err(Error::InvalidReference(_))));
+    }
+}
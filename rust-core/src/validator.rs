@@ -2,80 +2,718 @@
 // Copyright (c) 2025
 // SPDX-License-Identifier: MIT
 
-use crate::{Error, RequirementConfig, Result};
+use crate::graph::analysis::last_updated;
+use crate::metadata::canonical::sha256_hex;
+use crate::metadata::{Release, TagRegistry};
+use crate::types::Status;
+use crate::workspace::{map, ProjectScope};
+use crate::{Error, MetadataStore, RequirementConfig, Result};
+use chrono::Utc;
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::paths::{JSONPointer, PathChunk};
 use jsonschema::JSONSchema;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+mod approvals;
+mod attachments;
+mod baseline;
+mod cache;
+mod custom;
+mod glossary;
+mod links;
+#[cfg(any(feature = "owner-directory", feature = "link-check"))]
+mod lookup;
+#[cfg(feature = "owner-directory")]
+mod owners;
+mod patterns;
+#[cfg(feature = "link-check")]
+mod reachability;
+mod rules;
+mod sarif;
+mod translations;
+
+pub use attachments::file_checksum;
+pub use baseline::{Baseline, BaselineEntry};
+pub use cache::ValidationCache;
+pub use custom::{CustomRule, RuleContext};
+pub use glossary::{Glossary, GlossaryEntry};
+pub use links::is_well_formed_url;
+#[cfg(any(feature = "owner-directory", feature = "link-check"))]
+pub use lookup::{CacheEntry, LookupCache};
+#[cfg(feature = "owner-directory")]
+pub use owners::{
+    CachedOwner, CommandDirectory, GitHubDirectory, OwnerCache, OwnerChecker, OwnerDirectory,
+    OWNER_EXISTS_RULE,
+};
+pub use patterns::{PatternField, PatternRule, RequiredField};
+#[cfg(feature = "link-check")]
+pub use reachability::{
+    CachedLink, CurlFetcher, LinkCache, LinkChecker, LinkFetcher, LINK_REACHABLE_RULE,
+};
+pub use rules::{rule, LintConfig, Profile, Rule, RuleLevel, Severity, RULES};
+
+/// Rule ID reported for JSON schema violations
+pub const SCHEMA_RULE: &str = "schema";
+
+/// The JSON schema requirement files are checked against
+const SCHEMA_SOURCE: &str = include_str!("../../schema.json");
+
+/// Rule ID reported when a baseline cannot be applied
+pub const BASELINE_RULE: &str = "baseline";
+
+/// A finding reported by a lint rule
+#[derive(Debug)]
+pub struct Finding {
+    /// ID of the rule that produced the finding
+    pub rule: String,
+
+    /// Severity after applying the project's lint configuration
+    pub severity: Severity,
+
+    /// The underlying error
+    pub error: Error,
+
+    /// Summary of the requirement the finding concerns, when known
+    pub requirement: Option<String>,
+}
+
+impl Finding {
+    /// Human-readable description of the finding
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+
+    /// The message followed by the line of the finding's requirement, e.g.
+    /// `... (line 12)`, if `line` finds it
+    pub fn located(&self, line: impl FnOnce(&str) -> Option<usize>) -> String {
+        match self.requirement.as_deref().and_then(line) {
+            Some(line) => format!("{} (line {})", self.message(), line),
+            None => self.message(),
+        }
+    }
+}
+
+impl Serialize for Finding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Finding", 4)?;
+        state.serialize_field("rule", &self.rule)?;
+        state.serialize_field("severity", &self.severity)?;
+        state.serialize_field("message", &self.message())?;
+        if let Some(requirement) = &self.requirement {
+            state.serialize_field("requirement", requirement)?;
+        }
+        state.end()
+    }
+}
+
+/// Every finding from one validation run, grouped by severity
+#[derive(Debug, Default, Serialize)]
+pub struct ValidationReport {
+    pub errors: Vec<Finding>,
+    pub warnings: Vec<Finding>,
+    pub infos: Vec<Finding>,
+
+    /// Number of known findings suppressed by a baseline
+    #[serde(skip_serializing_if = "is_zero")]
+    pub suppressed: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+impl ValidationReport {
+    /// Check whether validation passed, i.e. there are no errors
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Fail with the first error, or return the report if there is none
+    pub fn into_result(mut self) -> Result<Self> {
+        if self.errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(self.errors.remove(0).error)
+        }
+    }
+
+    /// Every finding, errors first
+    fn findings(&self) -> impl Iterator<Item = &Finding> {
+        self.errors.iter().chain(&self.warnings).chain(&self.infos)
+    }
+
+    /// Move every finding of `other` into this report
+    pub fn append(&mut self, other: ValidationReport) {
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+        self.infos.extend(other.infos);
+    }
+
+    pub(crate) fn push(&mut self, finding: Finding) {
+        match finding.severity {
+            Severity::Error => self.errors.push(finding),
+            Severity::Warning => self.warnings.push(finding),
+            Severity::Info => self.infos.push(finding),
+        }
+    }
+}
+
+/// The embedded schema, compiled once per process
+pub(crate) struct CompiledSchemas {
+    document: JSONSchema,
+    /// The schema's requirement definition, to explain `oneOf` failures
+    requirement: JSONSchema,
+}
+
+impl CompiledSchemas {
+    /// Check a configuration against the schema
+    pub(crate) fn check_config(&self, config: &RequirementConfig) -> Vec<Finding> {
+        match serde_json::to_value(config) {
+            Ok(json) => self.check(&json),
+            Err(e) => vec![Finding {
+                rule: SCHEMA_RULE.to_string(),
+                severity: Severity::Error,
+                error: Error::custom(format!("Failed to convert to JSON: {}", e)),
+                requirement: None,
+            }],
+        }
+    }
+
+    /// See [`Validator::check_schema`]
+    fn check(&self, document: &Value) -> Vec<Finding> {
+        let mut problems = vec![];
+        self.collect_errors(&self.document, document, &[], &mut problems);
+
+        problems
+            .into_iter()
+            .map(|(path, message)| {
+                let (requirement, field) = locate(document, &JSONPointer::from(&path[..]));
+                let message = match (&requirement, field.is_empty()) {
+                    (Some(summary), true) => format!("requirement '{}': {}", summary, message),
+                    (Some(summary), false) => {
+                        format!("requirement '{}' field `{}`: {}", summary, field, message)
+                    }
+                    (None, false) => format!("`{}`: {}", field, message),
+                    (None, true) => message,
+                };
+                Finding {
+                    rule: SCHEMA_RULE.to_string(),
+                    severity: Severity::Error,
+                    error: Error::SchemaValidation(message),
+                    requirement,
+                }
+            })
+            .collect()
+    }
+
+    /// Collect schema errors as (absolute path, message) pairs
+    ///
+    /// A nested requirement that matches neither form of reference only
+    /// yields an opaque `oneOf` error, so it is re-checked against the
+    /// requirement definition to find the offending fields.
+    fn collect_errors(
+        &self,
+        schema: &JSONSchema,
+        instance: &Value,
+        base: &[PathChunk],
+        problems: &mut Vec<(Vec<PathChunk>, String)>,
+    ) {
+        let Err(errors) = schema.validate(instance) else {
+            return;
+        };
+
+        for e in errors {
+            let mut path = base.to_vec();
+            path.extend(e.instance_path.iter().cloned());
+            if matches!(e.kind, ValidationErrorKind::OneOfNotValid) && e.instance.is_object() {
+                self.collect_errors(&self.requirement, &e.instance, &path, problems);
+            } else {
+                problems.push((path, e.to_string()));
+            }
+        }
+    }
+}
+
+static SCHEMAS: OnceLock<std::result::Result<CompiledSchemas, String>> = OnceLock::new();
+
+fn compiled_schemas() -> Result<&'static CompiledSchemas> {
+    SCHEMAS
+        .get_or_init(compile_schemas)
+        .as_ref()
+        .map_err(|e| Error::custom(e.clone()))
+}
+
+fn compile_schemas() -> std::result::Result<CompiledSchemas, String> {
+    let schema: Value = serde_json::from_str(SCHEMA_SOURCE)
+        .map_err(|e| format!("Failed to parse schema: {}", e))?;
+
+    let document =
+        JSONSchema::compile(&schema).map_err(|e| format!("Failed to compile schema: {}", e))?;
+
+    let requirement = serde_json::json!({
+        "$schema": schema["$schema"],
+        "$defs": schema["$defs"],
+        "$ref": "#/$defs/requirement",
+    });
+    let requirement = JSONSchema::compile(&requirement)
+        .map_err(|e| format!("Failed to compile schema: {}", e))?;
+
+    Ok(CompiledSchemas {
+        document,
+        requirement,
+    })
+}
 
 /// Validator for requirement files
+///
+/// Schema validation always runs first; the remaining checks are [`RULES`]
+/// whose levels can be adjusted per project with a [`LintConfig`]. Other
+/// crates can add checks of their own with [`Validator::register_rule`].
 pub struct Validator {
-    schema: JSONSchema,
+    schemas: &'static CompiledSchemas,
+    tag_registry: TagRegistry,
+    releases: Vec<Release>,
+    lint: LintConfig,
+    profile: Option<Profile>,
+    glossary: Option<Glossary>,
+    baseline: Option<Baseline>,
+    /// Requirement UUIDs for matching baseline entries, and timestamps
+    metadata: Option<MetadataStore>,
+    /// Summaries of archived requirements, which references may still name
+    archived: BTreeSet<String>,
+    /// The monorepo project validated, whose references into other
+    /// projects are checked with the whole monorepo
+    project_scope: Option<ProjectScope>,
+    /// URL templates of external systems, by name
+    external_systems: BTreeMap<String, String>,
+    /// Directory attachment paths are relative to
+    attachment_root: Option<PathBuf>,
+    /// Locales every requirement must be translated to
+    locales: Vec<String>,
+    custom_rules: Vec<Box<dyn CustomRule>>,
 }
 
 impl Validator {
     /// Create a new validator with the embedded schema
+    ///
+    /// The schema is compiled by the first call and shared by every
+    /// validator created afterwards.
     pub fn new() -> Result<Self> {
-        let schema_json = include_str!("../../schema.json");
-        let schema: Value = serde_json::from_str(schema_json)
-            .map_err(|e| Error::custom(format!("Failed to parse schema: {}", e)))?;
+        Ok(Self {
+            schemas: compiled_schemas()?,
+            tag_registry: TagRegistry::default(),
+            releases: Vec::new(),
+            lint: LintConfig::default(),
+            profile: None,
+            glossary: None,
+            baseline: None,
+            metadata: None,
+            archived: BTreeSet::new(),
+            project_scope: None,
+            external_systems: BTreeMap::new(),
+            attachment_root: None,
+            locales: Vec::new(),
+            custom_rules: Vec::new(),
+        })
+    }
+
+    /// Create a validator with the settings of the project whose `.rqm`
+    /// directory is `rqm_dir`
+    ///
+    /// Loads `lint.yml` and the glossary, takes external systems and
+    /// locales from the project's metadata, which is kept for UUIDs and
+    /// timestamps, and checks attachments against the directory holding
+    /// `rqm_dir`. Whatever the project does not have keeps its default.
+    /// A project of a monorepo leaves references into the other projects
+    /// to [`Monorepo::validate`](crate::Monorepo::validate).
+    pub fn for_project(rqm_dir: &Path) -> Result<Self> {
+        let mut validator = Self::new()?.with_lint_config(LintConfig::load(rqm_dir)?);
+        if let Some(root) = rqm_dir.parent() {
+            validator = validator.with_attachment_root(root);
+            if let Some(scope) = ProjectScope::find(root)? {
+                validator = validator.with_project_scope(scope);
+            }
+        }
+        if let Some(glossary) = Glossary::load(rqm_dir)? {
+            validator = validator.with_glossary(glossary);
+        }
+        if let Some(store) = MetadataStore::open(rqm_dir)? {
+            let project = store.project_config();
+            validator = validator
+                .with_external_systems(project.external_systems.clone())
+                .with_locales(project.locales.clone())
+                .with_metadata(store);
+        }
+        Ok(validator)
+    }
 
-        let compiled = JSONSchema::compile(&schema)
-            .map_err(|e| Error::custom(format!("Failed to compile schema: {}", e)))?;
+    /// Enforce a project tag registry (unknown tags are rejected in strict mode)
+    pub fn with_tag_registry(mut self, registry: TagRegistry) -> Self {
+        self.tag_registry = registry;
+        self
+    }
 
-        Ok(Self { schema: compiled })
+    /// Use a project release registry to order `target_release` values
+    pub fn with_releases(mut self, releases: Vec<Release>) -> Self {
+        self.releases = releases;
+        self
     }
 
-    /// Validate a RequirementConfig against the schema
-    pub fn validate(&self, config: &RequirementConfig) -> Result<()> {
-        // Convert to JSON for validation
-        let json = serde_json::to_value(config)
-            .map_err(|e| Error::custom(format!("Failed to convert to JSON: {}", e)))?;
+    /// Apply per-project rule levels
+    pub fn with_lint_config(mut self, lint: LintConfig) -> Self {
+        self.lint = lint;
+        self
+    }
 
-        // Validate against schema
-        if let Err(errors) = self.schema.validate(&json) {
-            let error_messages: Vec<String> = errors.map(|e| format!("{}", e)).collect();
-            return Err(Error::SchemaValidation(error_messages.join("; ")));
+    /// Use a rule profile, overriding the one in the lint configuration
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Enable terminology rules backed by a project glossary
+    pub fn with_glossary(mut self, glossary: Glossary) -> Self {
+        self.glossary = Some(glossary);
+        self
+    }
+
+    /// Suppress the known findings recorded in a baseline file
+    ///
+    /// Requirement UUIDs are looked up in the metadata of the `.rqm`
+    /// directory containing the baseline, if it has any.
+    pub fn with_baseline<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        self.baseline = Some(Baseline::load(path)?);
+        if let Some(store) = path
+            .parent()
+            .map(MetadataStore::open)
+            .transpose()?
+            .flatten()
+        {
+            self.metadata = Some(store);
         }
+        Ok(self)
+    }
+
+    /// Look up requirement timestamps in project metadata
+    ///
+    /// A baseline added afterwards uses the metadata next to it instead.
+    pub fn with_metadata(mut self, store: MetadataStore) -> Self {
+        self.metadata = Some(store);
+        self
+    }
+
+    /// Let references name requirements archived with
+    /// [`archive_deprecated`](crate::archive::archive_deprecated)
+    ///
+    /// Archived requirements are not validated themselves; depending on one
+    /// is reported like depending on a deprecated requirement.
+    pub fn with_archive(mut self, archived: &RequirementConfig) -> Self {
+        self.set_archive(Some(archived));
+        self
+    }
+
+    /// Validate one project of a monorepo, accepting references into the
+    /// other projects without checking them
+    pub fn with_project_scope(mut self, scope: ProjectScope) -> Self {
+        self.project_scope = Some(scope);
+        self
+    }
+
+    /// Replace the archived requirements set with
+    /// [`Validator::with_archive`], for sessions outliving one archive
+    pub fn set_archive(&mut self, archived: Option<&RequirementConfig>) {
+        self.archived = archived
+            .map(|archived| {
+                archived
+                    .all_requirements()
+                    .into_iter()
+                    .map(|req| req.summary.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    /// Resolve `external_refs` without a URL with these templates, as in
+    /// [`ProjectConfig::external_systems`](crate::ProjectConfig::external_systems)
+    pub fn with_external_systems(mut self, templates: BTreeMap<String, String>) -> Self {
+        self.external_systems = templates;
+        self
+    }
+
+    /// Check that attached files exist below `root`, usually the directory
+    /// holding `.rqm`, and match their checksums
+    pub fn with_attachment_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.attachment_root = Some(root.into());
+        self
+    }
 
-        // Additional validation
-        self.validate_unique_summaries(config)?;
-        self.validate_owner_references(config)?;
+    /// Require translations to these locales, as in
+    /// [`ProjectConfig::locales`](crate::ProjectConfig::locales), besides
+    /// those used in the requirements
+    pub fn with_locales(mut self, locales: Vec<String>) -> Self {
+        self.locales = locales;
+        self
+    }
+
+    /// Requirement metadata used to identify baseline entries
+    pub fn metadata(&self) -> Option<&MetadataStore> {
+        self.metadata.as_ref()
+    }
 
+    /// Add a rule defined outside this crate
+    ///
+    /// Fails if another rule already uses its ID.
+    pub fn register_rule(&mut self, rule: Box<dyn CustomRule>) -> Result<()> {
+        let id = rule.id();
+        if id == SCHEMA_RULE
+            || rules::rule(id).is_some()
+            || self.lint.patterns.iter().any(|pattern| pattern.id == id)
+            || self.custom_rules.iter().any(|custom| custom.id() == id)
+        {
+            return Err(Error::custom(format!(
+                "Lint rule '{}' is already defined",
+                id
+            )));
+        }
+        self.custom_rules.push(rule);
         Ok(())
     }
 
-    /// Ensure all summaries are unique
-    fn validate_unique_summaries(&self, config: &RequirementConfig) -> Result<()> {
-        let mut seen = HashSet::new();
+    /// Severity a rule reports at, or `None` if it is off
+    pub(crate) fn severity(&self, rule: &Rule) -> Option<Severity> {
+        self.level(rule.id, rule.default_level).severity()
+    }
+
+    /// Configured level of a rule, falling back to the profile's
+    fn level(&self, id: &str, default_level: RuleLevel) -> RuleLevel {
+        let profile = self.profile.or(self.lint.profile).unwrap_or_default();
+        self.lint
+            .rules
+            .get(id)
+            .copied()
+            .unwrap_or_else(|| profile.adjust(id, default_level))
+    }
+
+    /// Check a JSON document against the schema
+    ///
+    /// Each error names the requirement and field it concerns rather than
+    /// an opaque instance path. This also works on raw documents whose
+    /// values are too malformed to parse into a [`RequirementConfig`].
+    pub fn check_schema(&self, document: &Value) -> Vec<Finding> {
+        self.schemas.check(document)
+    }
 
+    /// The compiled schema, which unlike the validator can be shared between threads
+    pub(crate) fn schemas(&self) -> &'static CompiledSchemas {
+        self.schemas
+    }
+
+    /// Validate a RequirementConfig against the schema and every enabled rule
+    ///
+    /// All findings are collected rather than stopping at the first error;
+    /// use [`ValidationReport::into_result`] to fail on errors.
+    pub fn validate(&self, config: &RequirementConfig) -> ValidationReport {
+        let mut report = self.check(config);
+        self.apply_baseline(&mut report);
+        report
+    }
+
+    /// Validate like [`Validator::validate`], reusing the findings cached
+    /// for `source`, the text `config` was parsed from
+    pub fn validate_cached(
+        &self,
+        config: &RequirementConfig,
+        source: &str,
+        cache: &ValidationCache,
+    ) -> ValidationReport {
+        let key = format!("{}{}", source, self.cache_state(config));
+        let mut report = cache.get_or_insert_with(&key, || self.check(config));
+        self.apply_baseline(&mut report);
+        report
+    }
+
+    /// Findings of the schema and every enabled rule, before any baseline
+    fn check(&self, config: &RequirementConfig) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for finding in self.schemas.check_config(config) {
+            report.push(finding);
+        }
+        self.check_rules(config, &mut report);
+        report
+    }
+
+    /// Hash of the schema and configuration that findings depend on,
+    /// besides the requirements themselves
+    pub(crate) fn fingerprint(&self) -> Result<String> {
+        let custom_rules: Vec<(&str, RuleLevel)> = self
+            .custom_rules
+            .iter()
+            .map(|custom| (custom.id(), self.level(custom.id(), custom.default_level())))
+            .collect();
+        let settings = serde_json::to_string(&(
+            env!("CARGO_PKG_VERSION"),
+            &self.tag_registry,
+            &self.releases,
+            &self.lint,
+            &self.profile,
+            &self.glossary,
+            custom_rules,
+            &self.archived,
+            &self.project_scope,
+            &self.external_systems,
+            &self.attachment_root,
+            &self.locales,
+        ))
+        .map_err(|e| Error::custom(format!("Failed to serialize settings: {}", e)))?;
+        Ok(sha256_hex(&format!("{}\n{}", SCHEMA_SOURCE, settings)))
+    }
+
+    /// State outside `config` which cached findings of the rules are only
+    /// valid for: the files attached in it and, for the stale rule, the date
+    /// and when the metadata store last saw undecided requirements change
+    ///
+    /// Requirements go stale by the day without their file changing, so
+    /// findings cached with the stale rule enabled last until midnight UTC.
+    pub(crate) fn cache_state(&self, config: &RequirementConfig) -> String {
+        let mut state = attachments::state(self, config);
+        let stale_enabled = rules::rule(rules::STALE_RULE)
+            .and_then(|rule| self.severity(rule))
+            .is_some();
+        if !stale_enabled {
+            return state;
+        }
+        let _ = write!(state, "\ntoday\t{}", Utc::now().date_naive());
+        let Some(store) = &self.metadata else {
+            return state;
+        };
         for req in config.all_requirements() {
-            if !seen.insert(&req.summary) {
-                return Err(Error::DuplicateSummary(req.summary.clone()));
+            if !matches!(req.status, Some(Status::Draft | Status::Proposed)) {
+                continue;
+            }
+            if let Ok(Some(updated_at)) = last_updated(req, Some(store)) {
+                let _ = write!(state, "\n{}\t{}", req.summary, updated_at.to_rfc3339());
             }
         }
+        state
+    }
 
-        Ok(())
+    /// Add the findings of every enabled rule besides the schema
+    pub(crate) fn check_rules(&self, config: &RequirementConfig, report: &mut ValidationReport) {
+        self.check_rules_where(config, report, |_| true);
+        self.check_extensions(config, report);
+    }
+
+    /// Add the findings of the enabled built-in rules that look at each
+    /// requirement on its own, which can be checked one file at a time
+    pub(crate) fn check_local_rules(
+        &self,
+        config: &RequirementConfig,
+        report: &mut ValidationReport,
+    ) {
+        self.check_rules_where(config, report, |rule| rule.local);
     }
 
-    /// Validate owner references point to valid aliases or are valid formats
-    fn validate_owner_references(&self, config: &RequirementConfig) -> Result<()> {
-        let alias_map = config.alias_map();
+    /// Add the findings of every other enabled rule, which need the
+    /// requirements of all files
+    pub(crate) fn check_global_rules(
+        &self,
+        config: &RequirementConfig,
+        report: &mut ValidationReport,
+    ) {
+        self.check_rules_where(config, report, |rule| !rule.local);
+        self.check_extensions(config, report);
+    }
 
-        for req in config.all_requirements() {
-            if let Some(owner) = &req.owner {
-                let owner_str = owner.as_str();
-
-                // Check if it's an email, GitHub username, or valid alias
-                if !owner.is_email() && !owner.is_github() && !alias_map.contains_key(owner_str) {
-                    return Err(Error::InvalidOwner(format!(
-                        "'{}' is not a valid email, GitHub username, or defined alias",
-                        owner_str
-                    )));
+    fn check_rules_where(
+        &self,
+        config: &RequirementConfig,
+        report: &mut ValidationReport,
+        filter: impl Fn(&Rule) -> bool,
+    ) {
+        let enabled: Vec<(&Rule, Severity)> = RULES
+            .iter()
+            .filter(|rule| filter(rule))
+            .filter_map(|rule| Some((rule, self.severity(rule)?)))
+            .collect();
+        // Rules only read the config, so they run concurrently with the
+        // `parallel` feature; findings keep the order of `RULES`
+        let violations = map(&enabled, |(rule, _)| (rule.check)(self, config));
+        for ((rule, severity), violations) in enabled.iter().zip(violations) {
+            for violation in violations {
+                report.push(Finding {
+                    rule: rule.id.to_string(),
+                    severity: *severity,
+                    error: violation.error,
+                    requirement: Some(violation.requirement),
+                });
+            }
+        }
+    }
+
+    /// Add the findings of lint patterns and custom rules
+    fn check_extensions(&self, config: &RequirementConfig, report: &mut ValidationReport) {
+        for pattern in &self.lint.patterns {
+            let level = self.lint.rules.get(&pattern.id).copied();
+            let Some(severity) = level.unwrap_or(pattern.level).severity() else {
+                continue;
+            };
+            match pattern.check(config) {
+                Ok(violations) => {
+                    for violation in violations {
+                        report.push(Finding {
+                            rule: pattern.id.clone(),
+                            severity,
+                            error: violation.error,
+                            requirement: Some(violation.requirement),
+                        });
+                    }
                 }
+                Err(error) => report.push(Finding {
+                    rule: pattern.id.clone(),
+                    severity,
+                    error,
+                    requirement: None,
+                }),
             }
         }
 
-        Ok(())
+        for custom in &self.custom_rules {
+            let Some(severity) = self.level(custom.id(), custom.default_level()).severity() else {
+                continue;
+            };
+            let ctx = RuleContext {
+                rule: custom.id(),
+                severity,
+                tag_registry: &self.tag_registry,
+                releases: &self.releases,
+                glossary: self.glossary.as_ref(),
+            };
+            for finding in custom.check(config, &ctx) {
+                report.push(finding);
+            }
+        }
+    }
+
+    /// Remove the findings known to the baseline, if there is one
+    pub(crate) fn apply_baseline(&self, report: &mut ValidationReport) {
+        if let Some(baseline) = &self.baseline {
+            match baseline.apply(report, self.metadata.as_ref()) {
+                Ok(suppressed) => report.suppressed = suppressed,
+                Err(e) => report.push(Finding {
+                    rule: BASELINE_RULE.to_string(),
+                    severity: Severity::Error,
+                    error: Error::custom(format!("Failed to apply baseline: {}", e)),
+                    requirement: None,
+                }),
+            }
+        }
     }
 }
 
@@ -85,10 +723,87 @@ impl Default for Validator {
     }
 }
 
+/// Find the innermost requirement a JSON pointer falls within
+///
+/// Returns its summary and the field path below it, e.g. `tags[2]`. Outside
+/// any named requirement the path is relative to the document.
+fn locate(document: &Value, path: &JSONPointer) -> (Option<String>, String) {
+    let mut current = document;
+    let mut summary = None;
+    let mut field = String::new();
+    let mut in_requirements = false;
+
+    for chunk in path.iter() {
+        let next = match chunk {
+            PathChunk::Property(name) => current.get(&**name),
+            PathChunk::Index(index) => current.get(*index),
+            PathChunk::Keyword(_) => None,
+        };
+        let Some(next) = next else {
+            break;
+        };
+
+        let named = next.get("summary").and_then(Value::as_str);
+        match (chunk, named) {
+            (PathChunk::Index(_), Some(name)) if in_requirements => {
+                summary = Some(name.to_string());
+                field.clear();
+            }
+            (PathChunk::Index(index), _) => field.push_str(&format!("[{}]", index)),
+            (PathChunk::Property(name), _) => {
+                if !field.is_empty() {
+                    field.push('.');
+                }
+                field.push_str(name);
+            }
+            (PathChunk::Keyword(_), _) => {}
+        }
+
+        in_requirements = matches!(chunk, PathChunk::Property(name) if &**name == "requirements");
+        current = next;
+    }
+
+    (summary, field)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{Confidentiality, Priority, RequirementReference, Status};
     use crate::{OwnerReference, PersonAlias, Requirement};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_validators_share_the_compiled_schema() {
+        let first = Validator::new().unwrap();
+        let second = Validator::new().unwrap();
+        assert!(std::ptr::eq(first.schemas(), second.schemas()));
+    }
+
+    #[test]
+    fn test_for_project_loads_the_project_settings() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rqm_dir = temp.path().join(".rqm");
+        let mut project = crate::metadata::ProjectConfig::new("PRJ".to_string());
+        project.external_systems.insert(
+            "jira".to_string(),
+            "https://jira.example.com/browse/{id}".to_string(),
+        );
+        project.locales = vec!["de".to_string()];
+        MetadataStore::init_with_config(&rqm_dir, project).unwrap();
+        std::fs::write(rqm_dir.join("lint.yml"), "stale_after_days: 30\n").unwrap();
+
+        let validator = Validator::for_project(&rqm_dir).unwrap();
+        assert!(validator.metadata().is_some());
+        assert_eq!(validator.lint.stale_after_days, Some(30));
+        assert_eq!(validator.locales, vec!["de"]);
+        assert!(validator.external_systems.contains_key("jira"));
+        assert_eq!(validator.attachment_root.as_deref(), Some(temp.path()));
+
+        // A directory without a project gets the defaults
+        let validator = Validator::for_project(&temp.path().join("elsewhere")).unwrap();
+        assert!(validator.metadata().is_none());
+    }
 
     #[test]
     fn test_validate_simple_config() {
@@ -96,10 +811,11 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![Requirement::new("Test")],
         };
 
-        assert!(validator.validate(&config).is_ok());
+        assert!(validator.validate(&config).is_valid());
     }
 
     #[test]
@@ -108,12 +824,16 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![Requirement::new("Test"), Requirement::new("Test")],
         };
 
         let result = validator.validate(&config);
-        assert!(result.is_err());
-        assert!(matches!(result.unwrap_err(), Error::DuplicateSummary(_)));
+        assert!(!result.is_valid());
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::DuplicateSummary(_)
+        ));
     }
 
     #[test]
@@ -127,6 +847,7 @@ mod tests {
                 email: None,
                 github: None,
             }],
+            templates: vec![],
             requirements: vec![{
                 let mut req = Requirement::new("Test");
                 req.owner = Some(OwnerReference::String("john".to_string()));
@@ -134,7 +855,7 @@ mod tests {
             }],
         };
 
-        assert!(validator.validate(&config).is_ok());
+        assert!(validator.validate(&config).is_valid());
     }
 
     #[test]
@@ -143,6 +864,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![{
                 let mut req = Requirement::new("Test");
                 req.owner = Some(OwnerReference::String("nonexistent".to_string()));
@@ -151,7 +873,7 @@ mod tests {
         };
 
         let result = validator.validate(&config);
-        assert!(result.is_err());
+        assert!(!result.is_valid());
     }
 
     #[test]
@@ -160,6 +882,7 @@ mod tests {
         let config = RequirementConfig {
             version: "1.0".to_string(),
             aliases: vec![],
+            templates: vec![],
             requirements: vec![{
                 let mut req = Requirement::new("Test");
                 req.owner = Some(OwnerReference::String("test@example.com".to_string()));
@@ -167,6 +890,679 @@ mod tests {
             }],
         };
 
-        assert!(validator.validate(&config).is_ok());
+        assert!(validator.validate(&config).is_valid());
+    }
+
+    fn tagged_config(tag: &str) -> RequirementConfig {
+        let mut req = Requirement::new("Tagged");
+        req.tags = vec![tag.to_string()];
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        }
+    }
+
+    #[test]
+    fn test_malformed_tag() {
+        let validator = Validator::new().unwrap();
+        let result = validator.validate(&tagged_config("safety//hv"));
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::InvalidTag(_)
+        ));
+    }
+
+    #[test]
+    fn test_strict_tag_registry() {
+        let validator = Validator::new().unwrap().with_tag_registry(TagRegistry {
+            strict: true,
+            registered: vec!["safety/electrical/hv".to_string()],
+        });
+
+        assert!(validator
+            .validate(&tagged_config("safety/electrical"))
+            .is_valid());
+        let result = validator.validate(&tagged_config("safety/mechanical"));
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::InvalidTag(_)
+        ));
+    }
+
+    #[test]
+    fn test_non_strict_tag_registry_allows_unknown() {
+        let validator = Validator::new().unwrap().with_tag_registry(TagRegistry {
+            strict: false,
+            registered: vec!["safety".to_string()],
+        });
+
+        assert!(validator.validate(&tagged_config("performance")).is_valid());
+    }
+
+    fn supersession_config() -> RequirementConfig {
+        let mut old = Requirement::new("Old Login");
+        old.status = Some(crate::types::Status::Deprecated);
+        old.superseded_by = Some("New Login".to_string());
+
+        let mut new = Requirement::new("New Login");
+        new.supersedes = vec!["Old Login".to_string()];
+
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![old, new],
+        }
+    }
+
+    #[test]
+    fn test_valid_supersession() {
+        let validator = Validator::new().unwrap();
+        assert!(validator.validate(&supersession_config()).is_valid());
+    }
+
+    #[test]
+    fn test_deprecated_without_replacement() {
+        let validator = Validator::new().unwrap();
+        let mut config = supersession_config();
+        config.requirements[0].superseded_by = None;
+
+        let result = validator.validate(&config);
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::DeprecatedReference(_)
+        ));
+    }
+
+    #[test]
+    fn test_dangling_supersession_link() {
+        let validator = Validator::new().unwrap();
+        let mut config = supersession_config();
+        config.requirements[1].supersedes = vec!["Missing".to_string()];
+
+        let result = validator.validate(&config);
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::InvalidReference(_)
+        ));
+    }
+
+    #[test]
+    fn test_active_depends_on_deprecated() {
+        let validator = Validator::new().unwrap();
+        let mut config = supersession_config();
+        config.requirements[1]
+            .requirements
+            .push(RequirementReference::Reference("Old Login".to_string()));
+
+        let result = validator.validate(&config);
+        assert!(matches!(
+            result.into_result().unwrap_err(),
+            Error::DeprecatedReference(_)
+        ));
+    }
+
+    fn release_config(parent: &str, child: &str) -> RequirementConfig {
+        let mut child_req = Requirement::new("Child");
+        child_req.target_release = Some(child.to_string());
+        let mut parent_req = Requirement::new("Parent");
+        parent_req.target_release = Some(parent.to_string());
+        parent_req
+            .requirements
+            .push(RequirementReference::Full(Box::new(child_req)));
+
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![parent_req],
+        }
+    }
+
+    #[test]
+    fn test_child_targets_later_release() {
+        let validator = Validator::new().unwrap();
+        let config = release_config("1.0", "2.0");
+
+        assert!(validator.validate(&config).is_valid());
+        let warnings = warning_messages(&validator, &config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("'Child'"));
+
+        assert!(warning_messages(&validator, &release_config("2.0", "1.5")).is_empty());
+    }
+
+    #[test]
+    fn test_release_registry_order_and_unknown() {
+        let validator = Validator::new().unwrap().with_releases(vec![
+            Release {
+                name: "mvp".to_string(),
+                date: None,
+            },
+            Release {
+                name: "ga".to_string(),
+                date: None,
+            },
+        ]);
+
+        assert_eq!(
+            warning_messages(&validator, &release_config("mvp", "ga")).len(),
+            1
+        );
+        assert!(warning_messages(&validator, &release_config("ga", "mvp")).is_empty());
+
+        let warnings = warning_messages(&validator, &release_config("beta", "mvp"));
+        assert!(warnings[0].contains("unregistered release 'beta'"));
+    }
+
+    fn warning_messages(validator: &Validator, config: &RequirementConfig) -> Vec<String> {
+        validator
+            .validate(config)
+            .warnings
+            .iter()
+            .map(Finding::message)
+            .collect()
+    }
+
+    fn lint(rules: &[(&str, RuleLevel)]) -> LintConfig {
+        LintConfig {
+            rules: rules
+                .iter()
+                .map(|(id, level)| (id.to_string(), *level))
+                .collect::<BTreeMap<_, _>>(),
+            ..LintConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_rule_disabled() {
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![Requirement::new("Test"), Requirement::new("Test")],
+        };
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("duplicate-summary", RuleLevel::Off)]));
+
+        assert!(validator.validate(&config).is_valid());
+        assert!(validator.validate(&config).warnings.is_empty());
+    }
+
+    #[test]
+    fn test_rule_downgraded_to_warning() {
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("tag-format", RuleLevel::Warning)]));
+        let config = tagged_config("safety//hv");
+
+        assert!(validator.validate(&config).is_valid());
+        let warnings = warning_messages(&validator, &config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("safety//hv"));
+    }
+
+    #[test]
+    fn test_rule_enabled() {
+        let config = tagged_config("safety");
+        let report = Validator::new().unwrap().validate(&config);
+        assert!(report.errors.is_empty() && report.warnings.is_empty() && report.infos.is_empty());
+
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("missing-description", RuleLevel::Error)]));
+        let report = validator.validate(&config);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, "missing-description");
+        assert_eq!(report.errors[0].severity, Severity::Error);
+        assert!(!validator.validate(&config).is_valid());
+    }
+
+    #[test]
+    fn test_findings_collect_every_violation() {
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![
+                Requirement::new("A"),
+                Requirement::new("A"),
+                Requirement::new("B"),
+                Requirement::new("B"),
+            ],
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        assert_eq!(report.errors.len(), 2);
+        assert!(report
+            .errors
+            .iter()
+            .all(|f| matches!(f.error, Error::DuplicateSummary(_))));
+    }
+
+    #[test]
+    fn test_report_collects_schema_and_rule_errors() {
+        let mut req = Requirement::new("A");
+        req.tags.push("bad//tag".to_string());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![
+                req,
+                Requirement::new("A"),
+                Requirement::new("x".repeat(201)),
+            ],
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        let rules: Vec<&str> = report.errors.iter().map(|f| f.rule.as_str()).collect();
+        assert!(rules.contains(&"duplicate-summary"));
+        assert!(rules.contains(&"tag-format"));
+        assert!(rules.contains(&SCHEMA_RULE));
+        assert!(!report.is_valid());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["errors"][0]["severity"], "error");
+        assert!(json["errors"][0]["message"].is_string());
+        assert!(json["infos"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_report_infos() {
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("missing-description", RuleLevel::Info)]));
+        let report = validator.validate(&tagged_config("safety"));
+
+        assert!(report.is_valid());
+        assert_eq!(report.infos.len(), 1);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_status_rollup_warning() {
+        let mut child = Requirement::new("Child");
+        child.status = Some(Status::Proposed);
+        let mut parent = Requirement::new("Parent");
+        parent.status = Some(Status::Implemented);
+        parent
+            .requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![parent],
+        };
+
+        let report = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("missing-approval", RuleLevel::Off)]))
+            .validate(&config);
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "status-rollup");
+        assert_eq!(report.warnings[0].requirement.as_deref(), Some("Parent"));
+        assert!(report.warnings[0]
+            .message()
+            .contains("is implemented but its children are only proposed"));
+    }
+
+    #[test]
+    fn test_priority_conflict_warning() {
+        let mut grandchild = Requirement::new("Grandchild");
+        grandchild.priority = Some(Priority::Low);
+        let mut child = Requirement::new("Child");
+        child
+            .requirements
+            .push(RequirementReference::Full(Box::new(grandchild)));
+        let mut parent = Requirement::new("Parent");
+        parent.priority = Some(Priority::Critical);
+        parent
+            .requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![parent],
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        assert!(report.warnings.is_empty());
+
+        let validator = Validator::new().unwrap().with_lint_config(LintConfig {
+            inherit_priority: true,
+            ..LintConfig::default()
+        });
+        let report = validator.validate(&config);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "priority-conflict");
+        assert_eq!(
+            report.warnings[0].message(),
+            "Requirement 'Grandchild' has low priority under critical parent 'Child'"
+        );
+    }
+
+    #[test]
+    fn test_confidentiality_conflict_warning() {
+        let mut grandchild = Requirement::new("Grandchild");
+        grandchild.confidentiality = Some(Confidentiality::Public);
+        let mut child = Requirement::new("Child");
+        child
+            .requirements
+            .push(RequirementReference::Full(Box::new(grandchild)));
+        let mut parent = Requirement::new("Parent");
+        parent.confidentiality = Some(Confidentiality::Restricted);
+        parent
+            .requirements
+            .push(RequirementReference::Full(Box::new(child)));
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![parent],
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "confidentiality-conflict");
+        assert_eq!(
+            report.warnings[0].message(),
+            "Requirement 'Grandchild' is marked public but is restricted like its parent 'Child'"
+        );
+    }
+
+    #[test]
+    fn test_references_to_archived_requirements() {
+        let mut login = Requirement::new("Login");
+        login.requirements.push(RequirementReference::Reference(
+            "Password login".to_string(),
+        ));
+        login.supersedes.push("Password login".to_string());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![login],
+        };
+        let mut password = Requirement::new("Password login");
+        password.status = Some(Status::Deprecated);
+        password.superseded_by = Some("Login".to_string());
+        let archived = RequirementConfig {
+            requirements: vec![password],
+            ..config.clone()
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        assert_eq!(report.errors.len(), 2);
+
+        let report = Validator::new()
+            .unwrap()
+            .with_archive(&archived)
+            .validate(&config);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, "deprecated-dependency");
+        assert_eq!(
+            report.errors[0].message(),
+            "Deprecated reference: Active requirement 'Login' depends on archived 'Password login'"
+        );
+    }
+
+    #[test]
+    fn test_stale_requirement_warning() {
+        let mut req = Requirement::new("Old draft");
+        req.status = Some(Status::Draft);
+        req.updated_at = Some((chrono::Utc::now() - chrono::Duration::days(40)).to_rfc3339());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+
+        let report = Validator::new().unwrap().validate(&config);
+        assert!(report.warnings.is_empty());
+        let validator = Validator::new().unwrap().with_lint_config(LintConfig {
+            stale_after_days: Some(30),
+            ..LintConfig::default()
+        });
+        let report = validator.validate(&config);
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "stale-requirement");
+        assert!(report.warnings[0].message().contains("draft for 40 days"));
+    }
+
+    #[test]
+    fn test_pattern_rules_reported_with_their_id() {
+        let validator = Validator::new().unwrap().with_lint_config(LintConfig {
+            patterns: vec![PatternRule {
+                id: "summary-capitalized".to_string(),
+                field: PatternField::Summary,
+                pattern: Some("^[A-Z]".to_string()),
+                min_words: None,
+                level: RuleLevel::Warning,
+                message: None,
+            }],
+            ..LintConfig::default()
+        });
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![Requirement::new("Good"), Requirement::new("bad")],
+        };
+
+        let report = validator.validate(&config);
+        assert!(report.is_valid());
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].rule, "summary-capitalized");
+    }
+
+    #[test]
+    fn test_status_required_fields() {
+        let validator = Validator::new().unwrap().with_lint_config(LintConfig {
+            required_fields: BTreeMap::from([
+                (
+                    Status::Approved,
+                    vec![
+                        RequiredField::Field(PatternField::Owner),
+                        RequiredField::Field(PatternField::Justification),
+                    ],
+                ),
+                (
+                    Status::Verified,
+                    vec![RequiredField::AnyOf(vec![
+                        PatternField::AcceptanceTest,
+                        PatternField::AcceptanceTestLink,
+                    ])],
+                ),
+            ]),
+            ..LintConfig::default()
+        });
+
+        let mut approved = Requirement::new("Approved");
+        approved.status = Some(Status::Approved);
+        approved.owner = Some(OwnerReference::String("dev@example.com".to_string()));
+        let mut verified = Requirement::new("Verified");
+        verified.status = Some(Status::Verified);
+        let mut linked = Requirement::new("Linked");
+        linked.status = Some(Status::Verified);
+        linked.acceptance_test_link = Some("https://example.com/test".to_string());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![approved, verified, linked, Requirement::new("Draft")],
+        };
+
+        let report = validator.validate(&config);
+        let messages: Vec<String> = report.errors.iter().map(Finding::message).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Requirement 'Approved' is approved but has no justification",
+                "Requirement 'Verified' is verified but has no acceptance_test or acceptance_test_link",
+            ]
+        );
+        assert!(report
+            .errors
+            .iter()
+            .all(|f| f.rule == "status-required-fields"));
+    }
+
+    #[test]
+    fn test_schema_errors_name_the_requirement() {
+        let document = serde_json::json!({
+            "version": "1.0",
+            "requirements": [{
+                "summary": "User login",
+                "priority": "urgent",
+                "requirements": [{ "summary": "Audit", "tags": ["ok", 5] }, { "name": "x" }]
+            }]
+        });
+
+        let findings = Validator::new().unwrap().check_schema(&document);
+        let messages: Vec<String> = findings.iter().map(Finding::message).collect();
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("requirement 'User login' field `priority`: \"urgent\"")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("requirement 'Audit' field `tags[1]`: 5")));
+        assert!(messages.iter().any(|m| m.contains(
+            "requirement 'User login' field `requirements[1]`: \"summary\" is a required"
+        )));
+        assert!(findings.iter().all(|f| f
+            .requirement
+            .as_deref()
+            .is_some_and(|s| s == "User login" || s == "Audit")));
+
+        let findings = Validator::new()
+            .unwrap()
+            .check_schema(&serde_json::json!({ "version": 1, "requirements": [] }));
+        assert_eq!(findings[0].requirement, None);
+        assert!(findings[0]
+            .message()
+            .contains("`version`: 1 is not of type"));
+    }
+
+    #[test]
+    fn test_profiles() {
+        let config = release_config("1.0", "2.0");
+
+        let minimal = Validator::new().unwrap().with_profile(Profile::Minimal);
+        assert!(warning_messages(&minimal, &config).is_empty());
+
+        let strict = Validator::new().unwrap().with_profile(Profile::Strict);
+        let report = strict.validate(&config);
+        assert!(report.errors.iter().any(|f| f.rule == "release-order"));
+        assert!(report
+            .errors
+            .iter()
+            .all(|f| f.rule != "missing-description"));
+        assert!(report
+            .warnings
+            .iter()
+            .any(|f| f.rule == "missing-description"));
+
+        // An explicit profile wins over the lint configuration's
+        let lint = LintConfig {
+            profile: Some(Profile::Strict),
+            ..LintConfig::default()
+        };
+        let validator = Validator::new()
+            .unwrap()
+            .with_lint_config(lint)
+            .with_profile(Profile::Minimal);
+        assert!(validator.validate(&config).is_valid());
+    }
+
+    #[test]
+    fn test_baseline_suppresses_known_findings() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path(), "REQ".to_string()).unwrap();
+        let mut login = Requirement::new("Login");
+        login.tags = vec!["ui/".to_string()];
+        store.get_or_create_metadata(&login).unwrap();
+        let mut config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![login],
+        };
+
+        let validator = Validator::new().unwrap();
+        let baseline = Baseline::from_report(&validator.validate(&config), Some(&store)).unwrap();
+        let path = temp.path().join("baseline.json");
+        baseline.save(&path).unwrap();
+
+        let validator = Validator::new().unwrap().with_baseline(&path).unwrap();
+        let report = validator.validate(&config);
+        assert!(report.is_valid());
+        assert_eq!(report.suppressed, 1);
+
+        // A new violation on the same requirement still fails
+        config.requirements[0].superseded_by = Some("Nothing".to_string());
+        let report = validator.validate(&config);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, "supersession-link");
+    }
+
+    struct TicketPrefix;
+
+    impl CustomRule for TicketPrefix {
+        fn id(&self) -> &str {
+            "ticket-prefix"
+        }
+
+        fn check(&self, config: &RequirementConfig, ctx: &RuleContext) -> Vec<Finding> {
+            config
+                .all_requirements()
+                .into_iter()
+                .filter(|req| !req.summary.starts_with("ACME"))
+                .map(|req| {
+                    ctx.finding(
+                        &req.summary,
+                        Error::custom(format!("'{}' lacks the ACME prefix", req.summary)),
+                    )
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![Requirement::new("ACME login"), Requirement::new("Logout")],
+        };
+
+        let mut validator = Validator::new().unwrap();
+        validator.register_rule(Box::new(TicketPrefix)).unwrap();
+        assert!(validator.register_rule(Box::new(TicketPrefix)).is_err());
+
+        let report = validator.validate(&config);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].rule, "ticket-prefix");
+        assert_eq!(report.errors[0].requirement.as_deref(), Some("Logout"));
+
+        // Levels follow the profile and lint configuration like built-in rules
+        let mut minimal = Validator::new().unwrap().with_profile(Profile::Minimal);
+        minimal.register_rule(Box::new(TicketPrefix)).unwrap();
+        assert_eq!(minimal.validate(&config).warnings.len(), 1);
+
+        let mut off = Validator::new()
+            .unwrap()
+            .with_lint_config(lint(&[("ticket-prefix", RuleLevel::Off)]));
+        off.register_rule(Box::new(TicketPrefix)).unwrap();
+        assert!(off.validate(&config).is_valid());
     }
 }
@@ -0,0 +1,195 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Checks on the approvals of requirements
+//!
+//! Which roles must approve is set with `approval_roles` in `lint.yml`;
+//! without it, one approval in any role will do, as for `signoff`.
+
+use super::attachments::is_checksum;
+use super::rules::Violation;
+use super::Validator;
+use crate::graph::{is_current, missing_roles};
+use crate::types::{OwnerReference, Status};
+use crate::{Error, RequirementConfig};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+
+pub(super) fn approval(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let mut seen = HashSet::new();
+        for approval in &req.approvals {
+            let person = OwnerReference::String(approval.person.clone());
+            let message = if !seen.insert((&approval.person, &approval.role)) {
+                format!(
+                    "Requirement '{}' is approved by {} as {} twice",
+                    req.summary, approval.person, approval.role
+                )
+            } else if !person.is_email()
+                && !person.is_github()
+                && !config.aliases.iter().any(|a| a.alias == approval.person)
+            {
+                format!(
+                    "Approval of requirement '{}' names '{}', which is not a valid email, GitHub username, or defined alias",
+                    req.summary, approval.person
+                )
+            } else if NaiveDate::parse_from_str(&approval.date, "%Y-%m-%d").is_err() {
+                format!(
+                    "Approval of requirement '{}' by {} has invalid date '{}'; expected YYYY-MM-DD",
+                    req.summary, approval.person, approval.date
+                )
+            } else if let Some(signature) =
+                approval.signature.as_deref().filter(|s| !is_checksum(s))
+            {
+                format!(
+                    "Approval of requirement '{}' by {} has malformed signature '{}'; expected sha256:<hex>",
+                    req.summary, approval.person, signature
+                )
+            } else {
+                continue;
+            };
+            errors.push(Violation::new(&req.summary, Error::custom(message)));
+        }
+    }
+    errors
+}
+
+pub(super) fn outdated_approval(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        for approval in &req.approvals {
+            let well_formed = approval.signature.as_deref().is_some_and(is_checksum);
+            if well_formed && !is_current(approval, req) {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::custom(format!(
+                        "Approval of requirement '{}' by {} as {} was signed for an earlier version",
+                        req.summary, approval.person, approval.role
+                    )),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+pub(super) fn missing_approval(
+    validator: &Validator,
+    config: &RequirementConfig,
+) -> Vec<Violation> {
+    let roles = &validator.lint.approval_roles;
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let Some(status) = req
+            .status
+            .filter(|status| (Status::Approved..=Status::Verified).contains(status))
+        else {
+            continue;
+        };
+        let missing = missing_roles(req, roles);
+        let status = status.to_string();
+        let message = if roles.is_empty() && !missing.is_empty() {
+            format!(
+                "Requirement '{}' is {} without any approval",
+                req.summary, status
+            )
+        } else if !missing.is_empty() {
+            format!(
+                "Requirement '{}' is {} without approval by {}",
+                req.summary,
+                status,
+                missing.join(", ")
+            )
+        } else {
+            continue;
+        };
+        errors.push(Violation::new(&req.summary, Error::custom(message)));
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::signature;
+    use crate::validator::LintConfig;
+    use crate::Parser;
+
+    #[test]
+    fn test_approvals() {
+        let mut config = Parser::parse_str(
+            r#"
+version: "1.0"
+aliases:
+  - alias: qa
+    email: qa@example.com
+requirements:
+  - summary: Login
+    status: approved
+    approvals:
+      - person: "@alice"
+        role: product-owner
+        date: 2025-03-01
+      - person: qa
+        role: safety
+        date: 2025-02-30
+      - person: Bob
+        role: safety
+        date: 2025-03-01
+      - person: "@alice"
+        role: product-owner
+        date: 2025-03-02
+  - summary: Lockout
+    status: implemented
+    approvals:
+      - person: qa
+        role: safety
+        date: 2025-03-01
+        signature: sha256:abc
+"#,
+        )
+        .unwrap();
+        let messages = |validator: &Validator, config: &RequirementConfig| -> Vec<String> {
+            approval(validator, config)
+                .into_iter()
+                .chain(outdated_approval(validator, config))
+                .chain(missing_approval(validator, config))
+                .map(|v| v.error.to_string())
+                .collect()
+        };
+
+        let validator = Validator::new().unwrap();
+        assert_eq!(
+            messages(&validator, &config),
+            vec![
+                "Approval of requirement 'Login' by qa has invalid date '2025-02-30'; expected YYYY-MM-DD",
+                "Approval of requirement 'Login' names 'Bob', which is not a valid email, GitHub username, or defined alias",
+                "Requirement 'Login' is approved by @alice as product-owner twice",
+                "Approval of requirement 'Lockout' by qa has malformed signature 'sha256:abc'; expected sha256:<hex>",
+                // Like `signoff`, any approval will do without approval_roles
+                "Requirement 'Lockout' is implemented without any approval",
+            ]
+        );
+
+        // A signature for another version lapses the approval
+        let lockout = &mut config.requirements[1];
+        lockout.approvals[0].signature = Some(signature(&crate::Requirement::new("Lockout v1")));
+        let validator = validator.with_lint_config(LintConfig {
+            approval_roles: vec!["safety".to_string()],
+            ..LintConfig::default()
+        });
+        let errors = messages(&validator, &config);
+        assert_eq!(
+            errors[3..],
+            [
+                "Approval of requirement 'Lockout' by qa as safety was signed for an earlier version",
+                "Requirement 'Lockout' is implemented without approval by safety",
+            ]
+        );
+
+        config.requirements[1].approvals[0].signature = Some(signature(&config.requirements[1]));
+        assert_eq!(messages(&validator, &config).len(), 3);
+    }
+}
@@ -0,0 +1,228 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Checks on files attached to requirements
+//!
+//! Paths are resolved against the root set with
+//! [`Validator::with_attachment_root`]; without one only their form is
+//! checked. Attached URLs are checked with the other links.
+
+use super::rules::Violation;
+use super::Validator;
+use crate::{Error, RequirementConfig};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Prefix of attachment checksums
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// Checksum of a file in the form attachments record it, `sha256:<hex>`
+pub fn file_checksum(path: &Path) -> io::Result<String> {
+    let digest = Sha256::digest(fs::read(path)?);
+    Ok(format!("{}{:x}", CHECKSUM_PREFIX, digest))
+}
+
+pub(super) fn is_checksum(checksum: &str) -> bool {
+    checksum
+        .strip_prefix(CHECKSUM_PREFIX)
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub(super) fn attachment(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let mut seen = HashSet::new();
+        for attachment in &req.attachments {
+            let path = attachment.path.trim();
+            let message = if !seen.insert(path) {
+                format!("Requirement '{}' attaches '{}' twice", req.summary, path)
+            } else if attachment
+                .checksum
+                .as_deref()
+                .is_some_and(|checksum| !is_checksum(checksum))
+            {
+                format!(
+                    "Attachment '{}' of requirement '{}' has malformed checksum '{}'; expected sha256:<hex>",
+                    path,
+                    req.summary,
+                    attachment.checksum.as_deref().unwrap_or_default()
+                )
+            } else if attachment.has_scheme() {
+                match attachment.checksum {
+                    Some(_) => format!(
+                        "Requirement '{}' has a checksum for '{}', which only files can have",
+                        req.summary, path
+                    ),
+                    None => continue,
+                }
+            } else if let Some(relative) = attachment.relative_path() {
+                let Some(root) = &validator.attachment_root else {
+                    continue;
+                };
+                if !root.join(relative).is_file() {
+                    format!(
+                        "Requirement '{}' attaches '{}', which does not exist",
+                        req.summary, path
+                    )
+                } else if let Some(file) = attachment.resolve(root) {
+                    let Some(expected) = &attachment.checksum else {
+                        continue;
+                    };
+                    match file_checksum(&file) {
+                        Ok(found) if found.eq_ignore_ascii_case(expected) => continue,
+                        Ok(found) => format!(
+                            "Attachment '{}' of requirement '{}' has changed: expected {}, found {}",
+                            path, req.summary, expected, found
+                        ),
+                        Err(e) => format!(
+                            "Could not read attachment '{}' of requirement '{}': {}",
+                            path, req.summary, e
+                        ),
+                    }
+                } else {
+                    format!(
+                        "Requirement '{}' attaches '{}', which links outside the project",
+                        req.summary, path
+                    )
+                }
+            } else {
+                format!(
+                    "Requirement '{}' attaches '{}', which is outside the project",
+                    req.summary, path
+                )
+            };
+            errors.push(Violation::new(&req.summary, Error::custom(message)));
+        }
+    }
+    errors
+}
+
+/// Size and modification time of every attached file, so that cached
+/// findings are not reused once an attachment changes
+pub(super) fn state(validator: &Validator, config: &RequirementConfig) -> String {
+    let mut state = String::new();
+    let Some(root) = &validator.attachment_root else {
+        return state;
+    };
+    for req in config.all_requirements() {
+        for attachment in &req.attachments {
+            let Some(relative) = attachment.relative_path() else {
+                continue;
+            };
+            let file = attachment.resolve(root);
+            let modified = file.and_then(|file| fs::metadata(file).ok()).map(|meta| {
+                let since = meta
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok());
+                (meta.len(), since.unwrap_or_default().as_nanos())
+            });
+            let _ = write!(state, "\n{}\t{:?}", relative.display(), modified);
+        }
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Attachment, Requirement};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_attachment() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("docs")).unwrap();
+        fs::write(temp.path().join("docs/flow.svg"), "<svg/>").unwrap();
+        let checksum = file_checksum(&temp.path().join("docs/flow.svg")).unwrap();
+
+        let mut req = Requirement::new("Login");
+        req.attachments = vec![
+            Attachment {
+                checksum: Some(checksum.clone()),
+                ..Attachment::new("./docs/../docs/flow.svg")
+            },
+            Attachment::new("https://example.com/mockup.png"),
+            Attachment::new("docs/missing.pdf"),
+            Attachment::new("../secrets.txt"),
+            Attachment::new("https://example.com/mockup.png"),
+            Attachment {
+                checksum: Some("md5:abc".to_string()),
+                ..Attachment::new("docs/flow.svg")
+            },
+        ];
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+        let messages = |validator: &Validator| -> Vec<String> {
+            attachment(validator, &config)
+                .iter()
+                .map(|v| v.error.to_string())
+                .collect()
+        };
+
+        // Without a root, files cannot be looked at
+        assert_eq!(messages(&Validator::new().unwrap()).len(), 3);
+
+        let validator = Validator::new().unwrap().with_attachment_root(temp.path());
+        let errors = messages(&validator);
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].ends_with("attaches 'docs/missing.pdf', which does not exist"));
+        assert!(errors[1].ends_with("attaches '../secrets.txt', which is outside the project"));
+        assert!(errors[2].ends_with("attaches 'https://example.com/mockup.png' twice"));
+        assert!(errors[3].contains("malformed checksum 'md5:abc'"));
+
+        let before = state(&validator, &config);
+        fs::write(temp.path().join("docs/flow.svg"), "<svg></svg>").unwrap();
+        assert_ne!(state(&validator, &config), before);
+        assert!(messages(&validator)[0].contains(&format!("has changed: expected {}", checksum)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_attachment_symlink_outside_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("project");
+        fs::create_dir(&root).unwrap();
+        fs::write(temp.path().join("secrets.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(temp.path().join("secrets.txt"), root.join("notes.txt"))
+            .unwrap();
+
+        let mut req = Requirement::new("Login");
+        req.attachments = vec![
+            Attachment {
+                checksum: Some(file_checksum(&temp.path().join("secrets.txt")).unwrap()),
+                ..Attachment::new("notes.txt")
+            },
+            Attachment::new("javascript://alert(1)"),
+        ];
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+        let validator = Validator::new().unwrap().with_attachment_root(&root);
+        let errors: Vec<String> = attachment(&validator, &config)
+            .iter()
+            .map(|v| v.error.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["Requirement 'Login' attaches 'notes.txt', which links outside the project"]
+        );
+        assert_eq!(config.requirements[0].attachments[0].resolve(&root), None);
+        assert!(!config.requirements[0].attachments[1].is_url());
+        assert_eq!(config.requirements[0].attachments[1].relative_path(), None);
+        assert!(!state(&validator, &config).contains("Some"));
+    }
+}
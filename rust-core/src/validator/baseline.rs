@@ -0,0 +1,213 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Baselines of known findings
+//!
+//! A baseline records the findings a project has accepted for now, so a
+//! stricter rule can be enabled on a legacy specification and only new
+//! violations fail validation. Findings are keyed by rule and requirement
+//! UUID, so several findings of one rule on a requirement share an entry.
+
+use super::{Finding, ValidationReport};
+use crate::{Error, MetadataStore, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// A known finding: a rule violated by a requirement
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BaselineEntry {
+    /// ID of the rule
+    pub rule: String,
+
+    /// UUID of the requirement, or its summary if it has no stored metadata;
+    /// absent for findings that do not concern a requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement: Option<String>,
+}
+
+/// Known findings to suppress, usually stored in `.rqm/baseline.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Baseline {
+    #[serde(default)]
+    pub entries: BTreeSet<BaselineEntry>,
+}
+
+impl Baseline {
+    /// Load a baseline file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::custom(format!("Failed to parse baseline: {}", e)))
+    }
+
+    /// Save the baseline to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::custom(format!("Failed to serialize baseline: {}", e)))?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Record every finding in a report
+    ///
+    /// Requirements are identified by their UUID in `store`, falling back to
+    /// their summary when the store has none.
+    pub fn from_report(report: &ValidationReport, store: Option<&MetadataStore>) -> Result<Self> {
+        let mut keys = Keys::new(store);
+        let entries = report
+            .findings()
+            .map(|finding| keys.entry(finding))
+            .collect::<Result<_>>()?;
+        Ok(Self { entries })
+    }
+
+    /// Remove known findings from a report, returning how many were removed
+    ///
+    /// Requirements are identified as in [`Baseline::from_report`].
+    pub fn apply(
+        &self,
+        report: &mut ValidationReport,
+        store: Option<&MetadataStore>,
+    ) -> Result<usize> {
+        let mut keys = Keys::new(store);
+        let mut suppressed = 0;
+        for findings in [&mut report.errors, &mut report.warnings, &mut report.infos] {
+            let mut kept = vec![];
+            for finding in findings.drain(..) {
+                if self.entries.contains(&keys.entry(&finding)?) {
+                    suppressed += 1;
+                } else {
+                    kept.push(finding);
+                }
+            }
+            *findings = kept;
+        }
+        Ok(suppressed)
+    }
+}
+
+/// Resolves requirement summaries to baseline keys, caching metadata lookups
+struct Keys<'a> {
+    store: Option<&'a MetadataStore>,
+    resolved: HashMap<String, String>,
+}
+
+impl<'a> Keys<'a> {
+    fn new(store: Option<&'a MetadataStore>) -> Self {
+        Self {
+            store,
+            resolved: HashMap::new(),
+        }
+    }
+
+    fn entry(&mut self, finding: &Finding) -> Result<BaselineEntry> {
+        let requirement = match &finding.requirement {
+            Some(summary) => Some(self.key(summary)?),
+            None => None,
+        };
+        Ok(BaselineEntry {
+            rule: finding.rule.clone(),
+            requirement,
+        })
+    }
+
+    fn key(&mut self, summary: &str) -> Result<String> {
+        if let Some(key) = self.resolved.get(summary) {
+            return Ok(key.clone());
+        }
+
+        let key = match self.store {
+            Some(store) => store
+                .find_metadata(summary)?
+                .map(|meta| meta.uuid.to_string()),
+            None => None,
+        }
+        .unwrap_or_else(|| summary.to_string());
+        self.resolved.insert(summary.to_string(), key.clone());
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::Severity;
+    use crate::Requirement;
+    use tempfile::TempDir;
+
+    fn finding(rule: &str, requirement: Option<&str>) -> Finding {
+        Finding {
+            rule: rule.to_string(),
+            severity: Severity::Error,
+            error: Error::custom(format!("{} on {:?}", rule, requirement)),
+            requirement: requirement.map(str::to_string),
+        }
+    }
+
+    fn report(findings: Vec<Finding>) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        for finding in findings {
+            report.push(finding);
+        }
+        report
+    }
+
+    #[test]
+    fn test_apply_suppresses_known_findings() {
+        let baseline = Baseline::from_report(
+            &report(vec![
+                finding("tag-format", Some("Login")),
+                finding("schema", None),
+            ]),
+            None,
+        )
+        .unwrap();
+
+        let mut current = report(vec![
+            finding("tag-format", Some("Login")),
+            finding("tag-format", Some("Login")),
+            finding("tag-format", Some("Logout")),
+            finding("owner-reference", Some("Login")),
+            finding("schema", None),
+        ]);
+        assert_eq!(baseline.apply(&mut current, None).unwrap(), 3);
+
+        let remaining: Vec<_> = current
+            .errors
+            .iter()
+            .map(|f| (f.rule.as_str(), f.requirement.as_deref()))
+            .collect();
+        assert_eq!(
+            remaining,
+            vec![
+                ("tag-format", Some("Logout")),
+                ("owner-reference", Some("Login"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keyed_by_uuid() {
+        let temp = TempDir::new().unwrap();
+        let mut store = MetadataStore::init(temp.path(), "REQ".to_string()).unwrap();
+        let uuid = store
+            .get_or_create_metadata(&Requirement::new("Login"))
+            .unwrap()
+            .uuid;
+
+        let baseline = Baseline::from_report(
+            &report(vec![finding("tag-format", Some("Login"))]),
+            Some(&store),
+        )
+        .unwrap();
+        let entry = baseline.entries.iter().next().unwrap();
+        assert_eq!(entry.requirement, Some(uuid.to_string()));
+
+        let path = temp.path().join("baseline.json");
+        baseline.save(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+    }
+}
@@ -0,0 +1,320 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Findings of earlier validations, keyed by file content
+//!
+//! Each entry in `.rqm/cache/` holds the findings for one file content,
+//! before any baseline is applied. The cache remembers a fingerprint of
+//! the schema and validator configuration it was filled with, and clears
+//! itself when it is opened with a different one, e.g. after `lint.yml`
+//! changes or rqm is upgraded.
+//!
+//! Files are replaced atomically, so concurrent runs never read a partly
+//! written entry. Reading an entry marks it as used; opening the cache
+//! removes entries unused for [`MAX_AGE`], then the least recently used
+//! ones beyond [`MAX_ENTRIES`].
+//!
+//! A cache kept in memory instead serves a long-running process, such as
+//! watch mode, that validates with one validator.
+
+use super::{Finding, Severity, ValidationReport, Validator};
+use crate::metadata::canonical::sha256_hex;
+use crate::metadata::write_atomic;
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+
+/// How long an entry is kept without being used
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How many entries are kept at most
+const MAX_ENTRIES: usize = 1000;
+
+/// Cached findings stored in `.rqm/cache/`, or in memory
+#[derive(Debug, Clone)]
+pub struct ValidationCache {
+    store: Store,
+}
+
+/// Where entries are kept, by hash of their key
+#[derive(Debug, Clone)]
+enum Store {
+    Dir(PathBuf),
+    Memory(Arc<Mutex<HashMap<String, String>>>),
+}
+
+/// A finding as stored in the cache, which keeps only its message
+#[derive(Serialize, Deserialize)]
+struct CachedFinding {
+    rule: String,
+    severity: Severity,
+    message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    requirement: Option<String>,
+}
+
+impl ValidationCache {
+    /// Open the cache in an `.rqm` directory for findings of `validator`
+    ///
+    /// Entries made by a differently configured validator are removed.
+    pub fn open<P: AsRef<Path>>(rqm_dir: P, validator: &Validator) -> Result<Self> {
+        let dir = rqm_dir.as_ref().join("cache");
+        let fingerprint = validator.fingerprint()?;
+        let fingerprint_path = dir.join("fingerprint");
+        if fs::read_to_string(&fingerprint_path).ok().as_deref() != Some(fingerprint.as_str()) {
+            if dir.is_dir() {
+                fs::remove_dir_all(&dir)?;
+            }
+            fs::create_dir_all(&dir)?;
+            // Generated files have no place in version control
+            write_atomic(&dir.join(".gitignore"), b"*\n")?;
+            write_atomic(&fingerprint_path, fingerprint.as_bytes())?;
+        }
+        let cache = Self {
+            store: Store::Dir(dir),
+        };
+        cache.prune(SystemTime::now(), MAX_AGE, MAX_ENTRIES);
+        Ok(cache)
+    }
+
+    /// An empty cache in memory, for findings of a single validator
+    pub fn in_memory() -> Self {
+        Self {
+            store: Store::Memory(Arc::default()),
+        }
+    }
+
+    /// Findings cached for `source`, if any
+    pub fn get(&self, source: &str) -> Option<ValidationReport> {
+        let content = match &self.store {
+            Store::Dir(dir) => {
+                let entry = entry(dir, source);
+                let content = fs::read_to_string(&entry).ok()?;
+                // Only pruning relies on the time, so failing to set it is harmless
+                let _ = File::options()
+                    .append(true)
+                    .open(&entry)
+                    .and_then(|file| file.set_modified(SystemTime::now()));
+                content
+            }
+            Store::Memory(entries) => entries
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .get(&sha256_hex(source))
+                .cloned()?,
+        };
+        let findings: Vec<CachedFinding> = serde_json::from_str(&content).ok()?;
+        let mut report = ValidationReport::default();
+        for cached in findings {
+            report.push(Finding {
+                rule: cached.rule,
+                severity: cached.severity,
+                error: Error::Custom(cached.message),
+                requirement: cached.requirement,
+            });
+        }
+        Some(report)
+    }
+
+    /// Store the findings for `source`
+    pub fn put(&self, source: &str, report: &ValidationReport) -> Result<()> {
+        let findings: Vec<CachedFinding> = report
+            .findings()
+            .map(|finding| CachedFinding {
+                rule: finding.rule.clone(),
+                severity: finding.severity,
+                message: finding.message(),
+                requirement: finding.requirement.clone(),
+            })
+            .collect();
+        let content = serde_json::to_string(&findings)
+            .map_err(|e| Error::custom(format!("Failed to serialize cache entry: {}", e)))?;
+        match &self.store {
+            Store::Dir(dir) => write_atomic(&entry(dir, source), content.as_bytes())?,
+            Store::Memory(entries) => {
+                let mut entries = entries.lock().unwrap_or_else(PoisonError::into_inner);
+                // Entries are not used in any order worth keeping track of
+                if entries.len() >= MAX_ENTRIES {
+                    entries.clear();
+                }
+                entries.insert(sha256_hex(source), content);
+            }
+        }
+        Ok(())
+    }
+
+    /// Findings cached for `source`, or those of `check` after caching them
+    ///
+    /// A cache that cannot be written only costs time, so write errors are
+    /// ignored.
+    pub fn get_or_insert_with(
+        &self,
+        source: &str,
+        check: impl FnOnce() -> ValidationReport,
+    ) -> ValidationReport {
+        self.get(source).unwrap_or_else(|| {
+            let report = check();
+            let _ = self.put(source, &report);
+            report
+        })
+    }
+
+    /// Remove entries last used more than `max_age` before `now`, then the
+    /// least recently used ones beyond `max_entries`
+    ///
+    /// Temporary files left by an interrupted write go the same way. An
+    /// entry that cannot be removed is left for the next run.
+    fn prune(&self, now: SystemTime, max_age: Duration, max_entries: usize) {
+        let Store::Dir(dir) = &self.store else {
+            return;
+        };
+        let Ok(dir) = fs::read_dir(dir) else {
+            return;
+        };
+        let mut entries: Vec<(SystemTime, PathBuf)> = dir
+            .flatten()
+            .filter(|entry| {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                name.ends_with(".json") || name.ends_with(".tmp")
+            })
+            .filter_map(|entry| {
+                let used = entry.metadata().and_then(|meta| meta.modified()).ok()?;
+                Some((used, entry.path()))
+            })
+            .collect();
+        // Most recently used first
+        entries.sort_by_key(|&(used, _)| std::cmp::Reverse(used));
+        for (i, (used, path)) in entries.iter().enumerate() {
+            let expired = now.duration_since(*used).is_ok_and(|age| age > max_age);
+            if expired || i >= max_entries {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// The file of the entry for `source` in a cache directory
+fn entry(dir: &Path, source: &str) -> PathBuf {
+    dir.join(format!("{}.json", sha256_hex(source)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::{LintConfig, RuleLevel};
+    use crate::Parser;
+    use tempfile::TempDir;
+
+    const SOURCE: &str = "version: \"1.0\"\nrequirements:\n  - summary: A\n  - summary: A\n";
+
+    #[test]
+    fn test_cached_findings_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let validator = Validator::new().unwrap();
+        let cache = ValidationCache::open(temp.path(), &validator).unwrap();
+        let config = Parser::parse_str(SOURCE).unwrap();
+        let key = format!("{}{}", SOURCE, validator.cache_state(&config));
+        assert!(cache.get(&key).is_none());
+
+        let report = validator.validate_cached(&config, SOURCE, &cache);
+        let cached = cache.get(&key).unwrap();
+        assert_eq!(cached.errors.len(), report.errors.len());
+        assert_eq!(cached.errors[0].rule, "duplicate-summary");
+        assert_eq!(cached.errors[0].message(), report.errors[0].message());
+        assert_eq!(cached.errors[0].requirement.as_deref(), Some("A"));
+    }
+
+    #[test]
+    fn test_changed_configuration_clears_the_cache() {
+        let temp = TempDir::new().unwrap();
+        let validator = Validator::new().unwrap();
+        let cache = ValidationCache::open(temp.path(), &validator).unwrap();
+        cache.put(SOURCE, &ValidationReport::default()).unwrap();
+
+        let reopened = ValidationCache::open(temp.path(), &validator).unwrap();
+        assert!(reopened.get(SOURCE).is_some());
+
+        let mut lint = LintConfig::default();
+        lint.rules
+            .insert("duplicate-summary".to_string(), RuleLevel::Off);
+        let relaxed = Validator::new().unwrap().with_lint_config(lint);
+        let reopened = ValidationCache::open(temp.path(), &relaxed).unwrap();
+        assert!(reopened.get(SOURCE).is_none());
+        assert!(temp.path().join("cache/.gitignore").exists());
+    }
+
+    #[test]
+    fn test_metadata_timestamps_are_part_of_the_key() {
+        let temp = TempDir::new().unwrap();
+        let config = Parser::parse_str(
+            "version: \"1.0\"\nrequirements:\n  - summary: A\n    status: draft\n",
+        )
+        .unwrap();
+        let mut store = crate::MetadataStore::init(temp.path(), "REQ".to_string()).unwrap();
+        let without = Validator::new()
+            .unwrap()
+            .with_metadata(crate::MetadataStore::new(temp.path()).unwrap());
+        let today = format!("\ntoday\t{}", chrono::Utc::now().date_naive());
+        assert_eq!(without.cache_state(&config), today);
+
+        store
+            .get_or_create_metadata(&config.requirements[0])
+            .unwrap();
+        let with = Validator::new().unwrap().with_metadata(store);
+        let state = with.cache_state(&config);
+        assert!(state.strip_prefix(&today).unwrap().starts_with("\nA\t"));
+
+        // Without the stale rule, neither the date nor timestamps matter
+        let mut lint = LintConfig::default();
+        lint.rules
+            .insert("stale-requirement".to_string(), RuleLevel::Off);
+        assert_eq!(with.with_lint_config(lint).cache_state(&config), "");
+    }
+
+    #[test]
+    fn test_prune_keeps_recently_used_entries() {
+        let temp = TempDir::new().unwrap();
+        let cache = ValidationCache::open(temp.path(), &Validator::new().unwrap()).unwrap();
+        let report = ValidationReport::default();
+        for source in ["a", "b", "c"] {
+            cache.put(source, &report).unwrap();
+        }
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::now();
+        let set_used = |source: &str, used: SystemTime| {
+            let file = File::options()
+                .append(true)
+                .open(entry(&temp.path().join("cache"), source));
+            file.unwrap().set_modified(used).unwrap();
+        };
+        set_used("a", now - 40 * day);
+        set_used("b", now - 2 * day);
+        set_used("c", now - 3 * day);
+
+        cache.prune(now, MAX_AGE, 1);
+        assert!(cache.get("a").is_none());
+        assert!(cache.get("b").is_some());
+        assert!(cache.get("c").is_none());
+        assert!(temp.path().join("cache/fingerprint").exists());
+    }
+
+    #[test]
+    fn test_in_memory_cache() {
+        let validator = Validator::new().unwrap();
+        let cache = ValidationCache::in_memory();
+        let config = Parser::parse_str(SOURCE).unwrap();
+        assert!(cache.get(SOURCE).is_none());
+
+        let report = validator.validate_cached(&config, SOURCE, &cache);
+        let key = format!("{}{}", SOURCE, validator.cache_state(&config));
+        assert_eq!(cache.get(&key).unwrap().errors.len(), report.errors.len());
+        // Clones share their entries
+        assert!(cache.clone().get(&key).is_some());
+    }
+}
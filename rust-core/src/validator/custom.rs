@@ -0,0 +1,60 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Extension point for rules defined outside this crate
+
+use super::rules::{RuleLevel, Severity};
+use super::{Finding, Glossary};
+use crate::metadata::{Release, TagRegistry};
+use crate::{Error, RequirementConfig};
+
+/// An organization-specific check, registered with
+/// [`Validator::register_rule`](super::Validator::register_rule)
+///
+/// Custom rules run after the built-in and pattern rules. Their level
+/// follows the active profile like a built-in rule's and can be overridden
+/// in [`LintConfig::rules`](super::LintConfig::rules).
+pub trait CustomRule: Send + Sync {
+    /// Stable identifier used in reports; must not clash with another rule
+    fn id(&self) -> &str;
+
+    /// One-line description of what the rule checks
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// Level used when the project does not configure the rule
+    fn default_level(&self) -> RuleLevel {
+        RuleLevel::Error
+    }
+
+    /// Check the configuration; [`RuleContext::finding`] builds findings at
+    /// the configured severity
+    fn check(&self, config: &RequirementConfig, ctx: &RuleContext) -> Vec<Finding>;
+}
+
+/// Project settings available to a custom rule
+pub struct RuleContext<'a> {
+    /// ID of the rule being run
+    pub rule: &'a str,
+
+    /// Severity the rule is configured to report at
+    pub severity: Severity,
+
+    pub tag_registry: &'a TagRegistry,
+    pub releases: &'a [Release],
+    pub glossary: Option<&'a Glossary>,
+}
+
+impl RuleContext<'_> {
+    /// A finding of this rule about a requirement
+    pub fn finding(&self, requirement: &str, error: Error) -> Finding {
+        Finding {
+            rule: self.rule.to_string(),
+            severity: self.severity,
+            error,
+            requirement: Some(requirement.to_string()),
+        }
+    }
+}
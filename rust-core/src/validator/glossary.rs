@@ -0,0 +1,451 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Terminology linting backed by a project glossary
+//!
+//! The glossary also defines the terms that exports link to their
+//! definitions.
+
+use super::rules::Violation;
+use super::Validator;
+use crate::{Error, Requirement, RequirementConfig, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Terms that make a requirement hard to verify, used unless the glossary lists its own
+const DEFAULT_VAGUE_TERMS: &[&str] = &[
+    "fast",
+    "quick",
+    "easy",
+    "simple",
+    "user-friendly",
+    "intuitive",
+    "efficient",
+    "flexible",
+    "robust",
+    "seamless",
+    "adequate",
+    "appropriate",
+    "as needed",
+    "as appropriate",
+    "if possible",
+    "etc",
+    "and/or",
+];
+
+/// Project glossary, read from `.rqm/glossary.yml`
+///
+/// ```yaml
+/// banned:
+///   should: shall
+///   recieve: receive
+/// vague: [fast, user-friendly]
+/// acronyms:
+///   API: Application Programming Interface
+/// terms:
+///   - term: Session token
+///     definition: Opaque credential of a login
+///     aliases: [tokens]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Glossary {
+    /// Words or phrases to avoid, mapped to a suggested replacement (may be empty)
+    #[serde(default)]
+    pub banned: BTreeMap<String, String>,
+
+    /// Terms without a measurable meaning
+    #[serde(default = "default_vague_terms")]
+    pub vague: Vec<String>,
+
+    /// Defined acronyms and their expansions
+    #[serde(default)]
+    pub acronyms: BTreeMap<String, String>,
+
+    /// Terms with their definitions, linked from exported text
+    #[serde(default)]
+    pub terms: Vec<GlossaryEntry>,
+}
+
+/// A term used in requirement text, with its definition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GlossaryEntry {
+    /// Term or acronym as written in requirement text
+    pub term: String,
+
+    /// Meaning of the term
+    pub definition: String,
+
+    /// Other spellings of the term, such as plurals or expansions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+}
+
+impl GlossaryEntry {
+    /// Create an entry without aliases
+    pub fn new(term: impl Into<String>, definition: impl Into<String>) -> Self {
+        Self {
+            term: term.into(),
+            definition: definition.into(),
+            aliases: vec![],
+        }
+    }
+
+    /// The term followed by its aliases
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.term.as_str()).chain(self.aliases.iter().map(String::as_str))
+    }
+}
+
+fn default_vague_terms() -> Vec<String> {
+    DEFAULT_VAGUE_TERMS.iter().map(|t| t.to_string()).collect()
+}
+
+impl Default for Glossary {
+    fn default() -> Self {
+        Self {
+            banned: BTreeMap::new(),
+            vague: default_vague_terms(),
+            acronyms: BTreeMap::new(),
+            terms: vec![],
+        }
+    }
+}
+
+impl Glossary {
+    /// Load `glossary.yml` from an `.rqm` directory, if present
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Option<Self>> {
+        let path = rqm_dir.as_ref().join("glossary.yml");
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let glossary: Self = serde_yaml::from_str(&content)?;
+        glossary.check()?;
+        Ok(Some(glossary))
+    }
+
+    /// Fail if a term, alias or acronym is defined more than once, ignoring
+    /// case, as it could only link to one of its definitions
+    pub fn check(&self) -> Result<()> {
+        let mut seen = BTreeSet::new();
+        for name in self.acronyms.keys().map(String::as_str).chain(
+            self.terms
+                .iter()
+                .flat_map(GlossaryEntry::names)
+                .map(str::trim),
+        ) {
+            if !seen.insert(name.to_lowercase()) {
+                return Err(Error::custom(format!(
+                    "Glossary defines '{}' more than once",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Terms followed by the acronyms, as entries defined by their expansion
+    pub fn entries(&self) -> Vec<GlossaryEntry> {
+        let acronyms = self
+            .acronyms
+            .iter()
+            .map(|(acronym, expansion)| GlossaryEntry::new(acronym, expansion));
+        self.terms.iter().cloned().chain(acronyms).collect()
+    }
+}
+
+/// Prose fields checked for terminology, with their names for messages
+fn prose(req: &Requirement) -> Vec<(&'static str, &str)> {
+    [
+        ("summary", Some(req.summary.as_str())),
+        ("description", req.description.as_deref()),
+        ("justification", req.justification.as_deref()),
+        ("acceptance_test", req.acceptance_test.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, text)| text.map(|t| (field, t)))
+    .collect()
+}
+
+/// Match a term as a whole word or phrase, ignoring case
+fn term_regex(term: &str) -> Option<Regex> {
+    Regex::new(&format!(r"(?i)(^|\W){}($|\W)", regex::escape(term))).ok()
+}
+
+/// Report each term once per requirement field
+fn find_terms<'a>(
+    terms: impl Iterator<Item = (&'a String, Option<&'a String>)>,
+    config: &RequirementConfig,
+    describe: impl Fn(&Requirement, &str, &str, Option<&str>) -> String,
+) -> Vec<Violation> {
+    let compiled: Vec<_> = terms
+        .filter_map(|(term, suggestion)| Some((term, suggestion, term_regex(term)?)))
+        .collect();
+
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        for (field, text) in prose(req) {
+            for (term, suggestion, regex) in &compiled {
+                if regex.is_match(text) {
+                    let message = describe(
+                        req,
+                        field,
+                        term,
+                        suggestion.map(String::as_str).filter(|s| !s.is_empty()),
+                    );
+                    errors.push(Violation::new(&req.summary, Error::custom(message)));
+                }
+            }
+        }
+    }
+    errors
+}
+
+pub(super) fn banned_term(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let Some(glossary) = &validator.glossary else {
+        return vec![];
+    };
+
+    find_terms(
+        glossary.banned.iter().map(|(term, s)| (term, Some(s))),
+        config,
+        |req, field, term, suggestion| {
+            let mut message = format!(
+                "Requirement '{}' uses banned term '{}' in {}",
+                req.summary, term, field
+            );
+            if let Some(suggestion) = suggestion {
+                message.push_str(&format!("; use '{}' instead", suggestion));
+            }
+            message
+        },
+    )
+}
+
+pub(super) fn vague_term(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let Some(glossary) = &validator.glossary else {
+        return vec![];
+    };
+
+    find_terms(
+        glossary.vague.iter().map(|term| (term, None)),
+        config,
+        |req, field, term, _| {
+            format!(
+                "Requirement '{}' uses vague term '{}' in {}; state a measurable criterion",
+                req.summary, term, field
+            )
+        },
+    )
+}
+
+pub(super) fn undefined_acronym(
+    validator: &Validator,
+    config: &RequirementConfig,
+) -> Vec<Violation> {
+    let Some(glossary) = &validator.glossary else {
+        return vec![];
+    };
+    let defined: BTreeSet<&str> = glossary
+        .acronyms
+        .keys()
+        .map(String::as_str)
+        .chain(glossary.terms.iter().flat_map(GlossaryEntry::names))
+        .collect();
+    let Ok(acronym) = Regex::new(r"\b[A-Z][A-Z0-9]*[A-Z][A-Z0-9]*s?\b") else {
+        return vec![];
+    };
+
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let mut reported = BTreeSet::new();
+        for (field, text) in prose(req) {
+            for found in acronym.find_iter(text) {
+                let word = found.as_str();
+                let singular = word.strip_suffix('s').unwrap_or(word);
+                if defined.contains(word) || defined.contains(singular) || !reported.insert(word) {
+                    continue;
+                }
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::custom(format!(
+                        "Requirement '{}' uses acronym '{}' in {} that is not defined in the glossary",
+                        req.summary, word, field
+                    )),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn check(
+        rule: fn(&Validator, &RequirementConfig) -> Vec<Violation>,
+        glossary: Glossary,
+        description: &str,
+    ) -> Vec<String> {
+        let validator = Validator::new().unwrap().with_glossary(glossary);
+        let mut req = Requirement::new("Login");
+        req.description = Some(description.to_string());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+        rule(&validator, &config)
+            .iter()
+            .map(|v| v.error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_banned_term() {
+        let glossary = Glossary {
+            banned: BTreeMap::from([
+                ("should".to_string(), "shall".to_string()),
+                ("in order to".to_string(), String::new()),
+            ]),
+            ..Glossary::default()
+        };
+
+        let errors = check(banned_term, glossary.clone(), "The system Should log in");
+        assert_eq!(
+            errors,
+            vec![
+                "Requirement 'Login' uses banned term 'should' in description; use 'shall' instead"
+            ]
+        );
+        assert!(check(banned_term, glossary.clone(), "Shouldering the load").is_empty());
+        assert_eq!(
+            check(banned_term, glossary, "Log in order to audit")[0],
+            "Requirement 'Login' uses banned term 'in order to' in description"
+        );
+    }
+
+    #[test]
+    fn test_vague_term() {
+        let errors = check(
+            vague_term,
+            Glossary::default(),
+            "Login must be fast and user-friendly.",
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("'user-friendly'") || errors[1].contains("'user-friendly'"));
+        assert!(check(vague_term, Glossary::default(), "Login within 200 ms").is_empty());
+    }
+
+    #[test]
+    fn test_undefined_acronym() {
+        let glossary = Glossary {
+            acronyms: BTreeMap::from([(
+                "API".to_string(),
+                "Application Programming Interface".to_string(),
+            )]),
+            ..Glossary::default()
+        };
+
+        let errors = check(
+            undefined_acronym,
+            glossary,
+            "Expose APIs over HTTP2 and HTTP2 with SSO. A user logs in.",
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("'HTTP2'"));
+        assert!(errors[1].contains("'SSO'"));
+    }
+
+    #[test]
+    fn test_acronyms_defined_as_terms() {
+        let glossary = Glossary {
+            terms: vec![
+                GlossaryEntry::new("SSO", "Single sign-on"),
+                GlossaryEntry {
+                    aliases: vec!["OTP".to_string()],
+                    ..GlossaryEntry::new("One-time password", "Code valid for one login")
+                },
+            ],
+            ..Glossary::default()
+        };
+
+        let errors = check(
+            undefined_acronym,
+            glossary,
+            "Sign in with SSO or an OTP over SMS",
+        );
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'SMS'"));
+    }
+
+    #[test]
+    fn test_rules_need_a_glossary() {
+        let validator = Validator::new().unwrap();
+        let mut req = Requirement::new("Fast API");
+        req.description = Some("It should be fast".to_string());
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+        assert!(vague_term(&validator, &config).is_empty());
+        assert!(undefined_acronym(&validator, &config).is_empty());
+    }
+
+    #[test]
+    fn test_glossary_load() {
+        let temp = TempDir::new().unwrap();
+        assert!(Glossary::load(temp.path()).unwrap().is_none());
+
+        fs::write(
+            temp.path().join("glossary.yml"),
+            "banned:\n  should: shall\nacronyms:\n  API: Application Programming Interface\n",
+        )
+        .unwrap();
+        let glossary = Glossary::load(temp.path()).unwrap().unwrap();
+        assert_eq!(glossary.banned["should"], "shall");
+        assert!(glossary.vague.contains(&"fast".to_string()));
+        assert!(glossary.acronyms.contains_key("API"));
+        assert_eq!(
+            glossary.entries(),
+            vec![GlossaryEntry::new(
+                "API",
+                "Application Programming Interface"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_glossary_rejects_duplicate_terms() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("glossary.yml"),
+            "acronyms:\n  SSO: Single sign-on\nterms:\n  - term: Token\n    definition: A credential\n  - term: sso\n    definition: Single sign-on\n",
+        )
+        .unwrap();
+        let error = Glossary::load(temp.path()).unwrap_err();
+        assert!(error.to_string().contains("defines 'sso' more than once"));
+
+        let glossary = Glossary {
+            terms: vec![
+                GlossaryEntry {
+                    aliases: vec!["tokens".to_string()],
+                    ..GlossaryEntry::new("Session token", "Credential of a login")
+                },
+                GlossaryEntry::new("Tokens", "Any credentials"),
+            ],
+            ..Glossary::default()
+        };
+        assert!(glossary.check().is_err());
+    }
+}
@@ -0,0 +1,268 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Checks on URLs in `acceptance_test_link`, `further_information`,
+//! `external_refs` and `attachments`
+
+use super::rules::Violation;
+use super::Validator;
+use crate::{Error, Requirement, RequirementConfig};
+use std::collections::HashSet;
+
+/// URLs on a requirement, with the field each came from
+///
+/// `acceptance_test_link` and the URLs of `external_refs` are always
+/// treated as URLs; `further_information` entries and attachments only when
+/// they start with a URL scheme, since they may be prose or file paths. URLs from templates are checked
+/// by [`external_ref`].
+pub(super) fn urls(req: &Requirement) -> Vec<(&'static str, &str)> {
+    let mut urls = vec![];
+    if let Some(link) = &req.acceptance_test_link {
+        urls.push(("acceptance_test_link", link.trim()));
+    }
+    urls.extend(
+        req.external_refs
+            .iter()
+            .filter_map(|reference| reference.url.as_deref())
+            .map(|url| ("external_refs", url.trim())),
+    );
+    urls.extend(
+        req.attachments
+            .iter()
+            .filter(|attachment| attachment.has_scheme())
+            .map(|attachment| ("attachments", attachment.path.trim())),
+    );
+    urls.extend(
+        req.further_information
+            .iter()
+            .map(|info| info.trim())
+            .filter(|info| scheme(info).is_some())
+            .map(|info| ("further_information", info)),
+    );
+    urls
+}
+
+/// The scheme of a URL-like string, e.g. `https`
+fn scheme(s: &str) -> Option<&str> {
+    let (scheme, rest) = s.split_once("://")?;
+    let valid = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    (valid && !rest.is_empty()).then_some(scheme)
+}
+
+/// Check whether a string is an absolute http(s) URL with a plausible host
+pub fn is_well_formed_url(url: &str) -> bool {
+    let Some(scheme) = scheme(url) else {
+        return false;
+    };
+    if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+        return false;
+    }
+    if url.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    let rest = &url[scheme.len() + 3..];
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host_port = authority.rsplit('@').next().unwrap_or("");
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some((host, port)) => (host, Some(port)),
+        None => (host_port, None),
+    };
+
+    let valid_port = match port {
+        Some(port) => port.parse::<u16>().is_ok(),
+        None => true,
+    };
+    let valid_host = !host.is_empty()
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+
+    valid_host && valid_port
+}
+
+pub(super) fn link_format(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        for (field, url) in urls(req) {
+            if !is_well_formed_url(url) {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::custom(format!(
+                        "Requirement '{}' has malformed URL '{}' in {}",
+                        req.summary, url, field
+                    )),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+pub(super) fn external_ref(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let templates = &validator.external_systems;
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let mut seen = HashSet::new();
+        for reference in &req.external_refs {
+            let message = if reference.system.trim().is_empty() || reference.id.trim().is_empty() {
+                format!(
+                    "External reference '{}' on requirement '{}' needs a system and an ID",
+                    reference, req.summary
+                )
+            } else if !seen.insert((&reference.system, &reference.id)) {
+                format!(
+                    "Requirement '{}' references '{}' twice",
+                    req.summary, reference
+                )
+            } else if reference.url.is_some() {
+                continue;
+            } else {
+                match reference.resolve_url(templates) {
+                    None => format!(
+                        "External reference '{}' on requirement '{}' has no url, and '{}' has no URL template",
+                        reference, req.summary, reference.system
+                    ),
+                    Some(url) if !is_well_formed_url(&url) => format!(
+                        "External reference '{}' on requirement '{}' links to malformed URL '{}'",
+                        reference, req.summary, url
+                    ),
+                    Some(_) => continue,
+                }
+            };
+            errors.push(Violation::new(&req.summary, Error::custom(message)));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ExternalRef;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_is_well_formed_url() {
+        for url in [
+            "https://example.com",
+            "http://localhost:8080/tests?id=1#top",
+            "https://user@ci.example.com/job/42/",
+        ] {
+            assert!(is_well_formed_url(url), "{}", url);
+        }
+        for url in [
+            "example.com/test",
+            "ftp://example.com",
+            "https://",
+            "https://exa mple.com",
+            "https://-bad.com",
+            "https://example..com",
+            "https://example.com:99999",
+        ] {
+            assert!(!is_well_formed_url(url), "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_link_format() {
+        let mut req = Requirement::new("Login");
+        req.acceptance_test_link = Some("not a url".to_string());
+        req.further_information = vec![
+            "See the login design notes".to_string(),
+            "https://wiki.example.com/login".to_string(),
+            "htps//typo".to_string(),
+            "http://bad host/".to_string(),
+        ];
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+
+        let errors: Vec<String> = link_format(&Validator::new().unwrap(), &config)
+            .iter()
+            .map(|v| v.error.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "Requirement 'Login' has malformed URL 'not a url' in acceptance_test_link",
+                "Requirement 'Login' has malformed URL 'http://bad host/' in further_information",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_external_ref() {
+        let reference = |system: &str, id: &str, url: Option<&str>| ExternalRef {
+            system: system.to_string(),
+            id: id.to_string(),
+            url: url.map(str::to_string),
+        };
+        let mut req = Requirement::new("Login");
+        req.external_refs = vec![
+            reference("jira", "AUTH-1", None),
+            reference("jira", "AUTH-1", None),
+            reference("doors", "42", Some("https://doors.example.com/42")),
+            reference("polarion", "PX-7", None),
+            reference("wiki", "Login page?", None),
+            reference("intranet", "7", None),
+            reference("jira", "", None),
+        ];
+        let config = RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![req],
+        };
+        let validator = Validator::new()
+            .unwrap()
+            .with_external_systems(BTreeMap::from([
+                (
+                    "jira".to_string(),
+                    "https://example.atlassian.net/browse/{id}".to_string(),
+                ),
+                (
+                    "wiki".to_string(),
+                    "https://wiki.example.com/{id}".to_string(),
+                ),
+                ("intranet".to_string(), "intranet/{id}".to_string()),
+            ]));
+        assert_eq!(
+            config.requirements[0].external_refs[0]
+                .resolve_url(&validator.external_systems)
+                .as_deref(),
+            Some("https://example.atlassian.net/browse/AUTH-1")
+        );
+        // IDs cannot break out of the URL path
+        assert_eq!(
+            config.requirements[0].external_refs[4]
+                .resolve_url(&validator.external_systems)
+                .as_deref(),
+            Some("https://wiki.example.com/Login%20page%3F")
+        );
+
+        let errors: Vec<String> = external_ref(&validator, &config)
+            .iter()
+            .map(|v| v.error.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "Requirement 'Login' references 'jira:AUTH-1' twice",
+                "External reference 'polarion:PX-7' on requirement 'Login' has no url, and 'polarion' has no URL template",
+                "External reference 'intranet:7' on requirement 'Login' links to malformed URL 'intranet/7'",
+                "External reference 'jira:' on requirement 'Login' needs a system and an ID",
+            ]
+        );
+    }
+}
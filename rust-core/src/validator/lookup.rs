@@ -0,0 +1,127 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! What the online checks of owners and links have in common
+//!
+//! Both look things up with a bounded number of requests in flight, and
+//! keep the results in a JSON file in `.rqm` until they expire.
+
+use crate::metadata::write_atomic;
+use crate::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// A result kept in a [`LookupCache`]
+pub trait CacheEntry: Clone + Serialize + DeserializeOwned {
+    /// File in the `.rqm` directory holding the results
+    const FILE: &'static str;
+
+    /// What is cached, for error messages, e.g. "link"
+    const KIND: &'static str;
+
+    /// When the result was looked up
+    fn checked_at(&self) -> DateTime<Utc>;
+}
+
+/// Lookup results by key, stored in `.rqm/<E::FILE>`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "E: CacheEntry")]
+pub struct LookupCache<E> {
+    #[serde(default)]
+    pub entries: BTreeMap<String, E>,
+}
+
+impl<E> Default for LookupCache<E> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<E: CacheEntry> LookupCache<E> {
+    /// Load the cache from an `.rqm` directory, starting empty if it is absent
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Self> {
+        let path = rqm_dir.as_ref().join(E::FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| Error::custom(format!("Failed to parse {} cache: {}", E::KIND, e)))
+    }
+
+    /// Save the cache to an `.rqm` directory
+    pub fn save<P: AsRef<Path>>(&self, rqm_dir: P) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::custom(format!("Failed to serialize {} cache: {}", E::KIND, e)))?;
+        write_atomic(&rqm_dir.as_ref().join(E::FILE), content.as_bytes())?;
+        Ok(())
+    }
+
+    /// Get a result looked up within `ttl` of `now`
+    pub fn get(&self, key: &str, ttl: Duration, now: DateTime<Utc>) -> Option<&E> {
+        self.entries
+            .get(key)
+            .filter(|entry| now - entry.checked_at() < ttl)
+    }
+}
+
+/// Apply `f` to every item on at most `max_concurrency` threads, keeping
+/// the results in item order
+pub(super) fn bounded<T: Sync, R: Send>(
+    items: &[T],
+    max_concurrency: usize,
+    f: impl Fn(&T) -> R + Sync,
+) -> Vec<R> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..max_concurrency.max(1).min(items.len()) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                let result = f(item);
+                if let Ok(mut results) = results.lock() {
+                    results.push((i, result));
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_default();
+    results.sort_by_key(|&(i, _)| i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounded_keeps_order_and_limit() {
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+        let items: Vec<usize> = (0..20).collect();
+        let results = bounded(&items, 3, |&n| {
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(current, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(2));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            n * 2
+        });
+        assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 3);
+    }
+}
@@ -0,0 +1,488 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Owner existence checks against external directories
+//!
+//! Enabled by the `owner-directory` feature. A bounded number of owners are
+//! looked up at once, and results are cached in `.rqm/owner-cache.json` so
+//! that repeated runs only contact a directory once an entry has expired.
+
+use super::lookup::{bounded, CacheEntry, LookupCache};
+use super::rules::{RuleLevel, Severity};
+use super::{Finding, ValidationReport};
+use crate::{Error, RequirementConfig, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Rule ID reported for owners missing from their directory
+pub const OWNER_EXISTS_RULE: &str = "owner-exists";
+
+/// An external source of truth for people
+pub trait OwnerDirectory: Send + Sync {
+    /// Name of the directory used in findings, e.g. "GitHub"
+    fn name(&self) -> &str;
+
+    /// Check whether this directory is responsible for an identity
+    fn handles(&self, identity: &str) -> bool;
+
+    /// Look up an identity; `Ok(false)` means it definitely does not exist
+    fn exists(&self, identity: &str) -> Result<bool>;
+}
+
+/// Looks up `@user` owners through the GitHub REST API
+///
+/// Requests go through `curl`; a token from `GITHUB_TOKEN` is sent on
+/// standard input so it never appears in the process list.
+#[derive(Debug, Clone)]
+pub struct GitHubDirectory {
+    api_url: String,
+    token: Option<String>,
+}
+
+impl Default for GitHubDirectory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GitHubDirectory {
+    /// Use the public GitHub API, authenticating with `GITHUB_TOKEN` if set
+    pub fn new() -> Self {
+        Self {
+            api_url: "https://api.github.com".to_string(),
+            token: std::env::var("GITHUB_TOKEN").ok(),
+        }
+    }
+
+    /// Use a GitHub Enterprise API endpoint instead
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+}
+
+impl OwnerDirectory for GitHubDirectory {
+    fn name(&self) -> &str {
+        "GitHub"
+    }
+
+    fn handles(&self, identity: &str) -> bool {
+        identity.starts_with('@')
+    }
+
+    fn exists(&self, identity: &str) -> Result<bool> {
+        let user = identity.trim_start_matches('@');
+        let mut child = Command::new("curl")
+            .args(["-s", "-o", "/dev/null", "-w", "%{http_code}", "-H", "@-"])
+            .arg(format!(
+                "{}/users/{}",
+                self.api_url.trim_end_matches('/'),
+                user
+            ))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "Accept: application/vnd.github+json")?;
+            if let Some(token) = &self.token {
+                writeln!(stdin, "Authorization: Bearer {}", token)?;
+            }
+        }
+
+        let output = child.wait_with_output()?;
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "200" => Ok(true),
+            "404" => Ok(false),
+            code => Err(Error::custom(format!(
+                "GitHub lookup of '{}' failed with HTTP status '{}'",
+                identity, code
+            ))),
+        }
+    }
+}
+
+/// Looks up email owners by running an external command
+///
+/// The command receives the email as its last argument and must exit with
+/// 0 if the person exists and 1 if not; any other outcome is an error. This
+/// lets projects plug in `ldapsearch`, `gam`, or a small wrapper script.
+#[derive(Debug, Clone)]
+pub struct CommandDirectory {
+    name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl CommandDirectory {
+    /// Create a directory backed by `program args... <email>`
+    pub fn new(name: impl Into<String>, program: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            program: program.into(),
+            args,
+        }
+    }
+}
+
+impl OwnerDirectory for CommandDirectory {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn handles(&self, identity: &str) -> bool {
+        identity.contains('@') && !identity.starts_with('@')
+    }
+
+    fn exists(&self, identity: &str) -> Result<bool> {
+        let status = Command::new(&self.program)
+            .args(&self.args)
+            .arg(identity)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+
+        match status.code() {
+            Some(0) => Ok(true),
+            Some(1) => Ok(false),
+            _ => Err(Error::custom(format!(
+                "{} lookup of '{}' failed ({})",
+                self.name, identity, status
+            ))),
+        }
+    }
+}
+
+/// A cached lookup result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedOwner {
+    pub exists: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl CacheEntry for CachedOwner {
+    const FILE: &'static str = "owner-cache.json";
+    const KIND: &'static str = "owner";
+
+    fn checked_at(&self) -> DateTime<Utc> {
+        self.checked_at
+    }
+}
+
+/// Lookup results keyed by identity, stored in `.rqm/owner-cache.json`
+pub type OwnerCache = LookupCache<CachedOwner>;
+
+/// Checks that requirement owners exist in their directories
+pub struct OwnerChecker {
+    directories: Vec<Box<dyn OwnerDirectory>>,
+    cache: OwnerCache,
+    ttl: Duration,
+    max_concurrency: usize,
+    level: RuleLevel,
+}
+
+impl Default for OwnerChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OwnerChecker {
+    /// Create a checker with no directories, eight lookups at a time, a
+    /// one-week cache, and warnings
+    pub fn new() -> Self {
+        Self {
+            directories: Vec::new(),
+            cache: OwnerCache::default(),
+            ttl: Duration::days(7),
+            max_concurrency: 8,
+            level: RuleLevel::Warning,
+        }
+    }
+
+    /// Consult a directory; the first one handling an identity is used
+    pub fn with_directory(mut self, directory: impl OwnerDirectory + 'static) -> Self {
+        self.directories.push(Box::new(directory));
+        self
+    }
+
+    /// Start from previously cached results
+    pub fn with_cache(mut self, cache: OwnerCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Re-check cached results older than `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Limit the number of lookups in flight
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Report missing owners at this level
+    pub fn with_level(mut self, level: RuleLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Lookup results, including those made by [`OwnerChecker::check`]
+    pub fn cache(&self) -> &OwnerCache {
+        &self.cache
+    }
+
+    /// Check every owner, resolving aliases to their email and GitHub user
+    ///
+    /// Owners no directory handles are skipped. Failed lookups are reported
+    /// as infos and fall back to the last cached result, if any.
+    pub fn check(&mut self, config: &RequirementConfig) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let Some(severity) = self.level.severity() else {
+            return report;
+        };
+
+        // Identity -> summaries of the requirements it owns
+        let alias_map = config.alias_map();
+        let mut owned: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for req in config.all_requirements() {
+            let Some(owner) = &req.owner else {
+                continue;
+            };
+            let identities = match alias_map.get(owner.as_str()) {
+                Some(alias) => alias
+                    .github
+                    .iter()
+                    .map(|user| format!("@{}", user.trim_start_matches('@')))
+                    .chain(alias.email.iter().cloned())
+                    .collect(),
+                None => vec![owner.as_str().to_string()],
+            };
+            for identity in identities {
+                owned.entry(identity).or_default().push(&req.summary);
+            }
+        }
+
+        let now = Utc::now();
+        let pending: Vec<(&str, &dyn OwnerDirectory)> = owned
+            .keys()
+            .filter(|identity| self.cache.get(identity, self.ttl, now).is_none())
+            .filter_map(|identity| {
+                let directory = self.directories.iter().find(|d| d.handles(identity))?;
+                Some((identity.as_str(), directory.as_ref()))
+            })
+            .collect();
+
+        let results = bounded(&pending, self.max_concurrency, |&(identity, directory)| {
+            (identity, directory.name(), directory.exists(identity))
+        });
+
+        let mut directory_names = BTreeMap::new();
+        for (identity, directory, result) in results {
+            directory_names.insert(identity.to_string(), directory.to_string());
+            match result {
+                Ok(exists) => {
+                    self.cache.entries.insert(
+                        identity.to_string(),
+                        CachedOwner {
+                            exists,
+                            checked_at: now,
+                        },
+                    );
+                }
+                Err(e) => report.push(Finding {
+                    rule: OWNER_EXISTS_RULE.to_string(),
+                    severity: Severity::Info,
+                    error: e,
+                    requirement: None,
+                }),
+            }
+        }
+
+        for (identity, summaries) in &owned {
+            if self.cache.entries.get(identity).map(|entry| entry.exists) != Some(false) {
+                continue;
+            }
+            let directory = match directory_names.get(identity) {
+                Some(name) => format!(" in {}", name),
+                None => String::new(),
+            };
+            for summary in summaries {
+                report.push(Finding {
+                    rule: OWNER_EXISTS_RULE.to_string(),
+                    severity,
+                    error: Error::InvalidOwner(format!(
+                        "'{}' owning requirement '{}' was not found{}",
+                        identity, summary, directory
+                    )),
+                    requirement: Some(summary.to_string()),
+                });
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OwnerReference;
+    use crate::{PersonAlias, Requirement};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    struct FakeDirectory {
+        known: Vec<&'static str>,
+        lookups: Arc<AtomicUsize>,
+    }
+
+    impl OwnerDirectory for FakeDirectory {
+        fn name(&self) -> &str {
+            "Fake"
+        }
+
+        fn handles(&self, _: &str) -> bool {
+            true
+        }
+
+        fn exists(&self, identity: &str) -> Result<bool> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if identity == "offline@example.com" {
+                return Err(Error::custom("directory unavailable"));
+            }
+            Ok(self.known.contains(&identity))
+        }
+    }
+
+    fn owned(summary: &str, owner: &str) -> Requirement {
+        let mut req = Requirement::new(summary);
+        req.owner = Some(OwnerReference::String(owner.to_string()));
+        req
+    }
+
+    fn config() -> RequirementConfig {
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![PersonAlias {
+                alias: "jane".to_string(),
+                name: None,
+                email: Some("jane@example.com".to_string()),
+                github: Some("jane".to_string()),
+            }],
+            templates: vec![],
+            requirements: vec![
+                owned("A", "@alice"),
+                owned("B", "@ghost"),
+                owned("C", "jane"),
+                owned("D", "offline@example.com"),
+                owned("E", "@ghost"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_missing_owners_reported() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let mut checker = OwnerChecker::new().with_directory(FakeDirectory {
+            known: vec!["@alice", "@jane"],
+            lookups: lookups.clone(),
+        });
+
+        let report = checker.check(&config());
+        let warnings: Vec<String> = report.warnings.iter().map(Finding::message).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "Invalid owner reference: '@ghost' owning requirement 'B' was not found in Fake",
+                "Invalid owner reference: '@ghost' owning requirement 'E' was not found in Fake",
+                "Invalid owner reference: 'jane@example.com' owning requirement 'C' was not found in Fake",
+            ]
+        );
+        assert_eq!(report.infos.len(), 1);
+        assert!(report.is_valid());
+        assert_eq!(lookups.load(Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_cached_results_skip_lookups() {
+        let lookups = Arc::new(AtomicUsize::new(0));
+        let mut checker = OwnerChecker::new()
+            .with_directory(FakeDirectory {
+                known: vec!["@alice"],
+                lookups: lookups.clone(),
+            })
+            .with_level(RuleLevel::Error);
+        checker.check(&config());
+        assert_eq!(lookups.load(Ordering::SeqCst), 5);
+
+        // Only the failed lookup is retried
+        let report = checker.check(&config());
+        assert_eq!(lookups.load(Ordering::SeqCst), 6);
+        assert_eq!(report.errors.len(), 4);
+
+        let expired = checker.with_ttl(Duration::zero()).check(&config());
+        assert_eq!(lookups.load(Ordering::SeqCst), 11);
+        assert_eq!(expired.errors.len(), 4);
+    }
+
+    #[test]
+    fn test_owner_cache_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            OwnerCache::load(temp.path()).unwrap(),
+            OwnerCache::default()
+        );
+
+        let mut cache = OwnerCache::default();
+        let now = Utc::now();
+        cache.entries.insert(
+            "@alice".to_string(),
+            CachedOwner {
+                exists: true,
+                checked_at: now - Duration::days(2),
+            },
+        );
+        cache.save(temp.path()).unwrap();
+
+        let loaded = OwnerCache::load(temp.path()).unwrap();
+        assert_eq!(loaded, cache);
+        let exists = |ttl| loaded.get("@alice", ttl, now).map(|entry| entry.exists);
+        assert_eq!(exists(Duration::days(7)), Some(true));
+        assert_eq!(exists(Duration::days(1)), None);
+    }
+
+    #[test]
+    fn test_command_directory() {
+        let exists = CommandDirectory::new(
+            "Shell",
+            "sh",
+            vec!["-c".to_string(), "exit 0".to_string(), "--".to_string()],
+        );
+        assert!(exists.handles("a@example.com"));
+        assert!(!exists.handles("@alice"));
+        assert!(exists.exists("a@example.com").unwrap());
+
+        let missing = CommandDirectory::new(
+            "Shell",
+            "sh",
+            vec!["-c".to_string(), "exit 1".to_string(), "--".to_string()],
+        );
+        assert!(!missing.exists("a@example.com").unwrap());
+
+        let broken = CommandDirectory::new(
+            "Shell",
+            "sh",
+            vec!["-c".to_string(), "exit 3".to_string(), "--".to_string()],
+        );
+        assert!(broken.exists("a@example.com").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Project-defined rules on requirement fields
+
+use super::rules::{RuleLevel, Violation};
+use crate::{Error, Requirement, RequirementConfig, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// A requirement field project-defined rules can inspect
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternField {
+    Summary,
+    Name,
+    Description,
+    Justification,
+    AcceptanceTest,
+    AcceptanceTestLink,
+    Owner,
+    /// Each tag is checked separately
+    Tags,
+}
+
+impl PatternField {
+    fn label(self) -> &'static str {
+        match self {
+            PatternField::Summary => "summary",
+            PatternField::Name => "name",
+            PatternField::Description => "description",
+            PatternField::Justification => "justification",
+            PatternField::AcceptanceTest => "acceptance_test",
+            PatternField::AcceptanceTestLink => "acceptance_test_link",
+            PatternField::Owner => "owner",
+            PatternField::Tags => "tag",
+        }
+    }
+
+    /// Check whether a requirement sets this field to a non-blank value
+    pub(super) fn is_set(self, req: &Requirement) -> bool {
+        self.values(req)
+            .iter()
+            .any(|value| !value.trim().is_empty())
+    }
+
+    /// Values of this field on a requirement; unset fields yield nothing
+    fn values(self, req: &Requirement) -> Vec<&str> {
+        let value = match self {
+            PatternField::Summary => Some(req.summary.as_str()),
+            PatternField::Name => req.name.as_deref(),
+            PatternField::Description => req.description.as_deref(),
+            PatternField::Justification => req.justification.as_deref(),
+            PatternField::AcceptanceTest => req.acceptance_test.as_deref(),
+            PatternField::AcceptanceTestLink => req.acceptance_test_link.as_deref(),
+            PatternField::Owner => req.owner.as_ref().map(|owner| owner.as_str()),
+            PatternField::Tags => return req.tags.iter().map(String::as_str).collect(),
+        };
+        value.into_iter().collect()
+    }
+}
+
+/// A field, or a choice of fields, that must be set
+///
+/// In YAML a single field is written as a string and a choice as a list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum RequiredField {
+    Field(PatternField),
+    AnyOf(Vec<PatternField>),
+}
+
+impl RequiredField {
+    /// Check whether the requirement satisfies this entry
+    pub fn is_satisfied(&self, req: &Requirement) -> bool {
+        match self {
+            RequiredField::Field(field) => field.is_set(req),
+            RequiredField::AnyOf(fields) => fields.iter().any(|field| field.is_set(req)),
+        }
+    }
+
+    /// Describe the entry for use in messages
+    pub fn describe(&self) -> String {
+        match self {
+            RequiredField::Field(field) => field.label().to_string(),
+            RequiredField::AnyOf(fields) => fields
+                .iter()
+                .map(|field| field.label())
+                .collect::<Vec<_>>()
+                .join(" or "),
+        }
+    }
+}
+
+/// A rule on the content of one field, declared under `patterns` in `lint.yml`
+///
+/// ```yaml
+/// patterns:
+///   - id: summary-capitalized
+///     field: summary
+///     matches: "^[A-Z]"
+///   - id: description-length
+///     field: description
+///     min_words: 20
+///     level: warning
+/// ```
+///
+/// Requirements that leave the field unset are not checked.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PatternRule {
+    /// Rule ID reported with each finding; must not clash with a built-in rule
+    pub id: String,
+
+    /// Field the rule inspects
+    pub field: PatternField,
+
+    /// Regular expression the value must match
+    #[serde(default, rename = "matches", skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// Minimum number of whitespace-separated words
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_words: Option<usize>,
+
+    /// Level of findings from this rule
+    #[serde(default = "default_level")]
+    pub level: RuleLevel,
+
+    /// Explanation appended to each finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+fn default_level() -> RuleLevel {
+    RuleLevel::Error
+}
+
+impl PatternRule {
+    /// Compile the rule's regular expression, if it has one
+    pub fn regex(&self) -> Result<Option<Regex>> {
+        self.pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| {
+                Error::custom(format!("Invalid pattern in lint rule '{}': {}", self.id, e))
+            })
+    }
+
+    /// Check every requirement, returning one violation per violating value
+    pub(super) fn check(&self, config: &RequirementConfig) -> Result<Vec<Violation>> {
+        let regex = self.regex()?;
+
+        let mut errors = vec![];
+        for req in config.all_requirements() {
+            for value in self.field.values(req) {
+                let problem = match (&regex, self.min_words) {
+                    (Some(regex), _) if !regex.is_match(value) => {
+                        format!("does not match `{}`", regex.as_str())
+                    }
+                    (_, Some(min)) if value.split_whitespace().count() < min => {
+                        format!("has fewer than {} words", min)
+                    }
+                    _ => continue,
+                };
+
+                let mut text = format!(
+                    "Requirement '{}' violates rule '{}': {} '{}' {}",
+                    req.summary,
+                    self.id,
+                    self.field.label(),
+                    value,
+                    problem
+                );
+                if let Some(message) = &self.message {
+                    text.push_str(&format!(" ({})", message));
+                }
+                errors.push(Violation::new(&req.summary, Error::custom(text)));
+            }
+        }
+        Ok(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(
+        field: PatternField,
+        pattern: Option<&str>,
+        min_words: Option<usize>,
+    ) -> PatternRule {
+        PatternRule {
+            id: "custom".to_string(),
+            field,
+            pattern: pattern.map(str::to_string),
+            min_words,
+            level: RuleLevel::Error,
+            message: None,
+        }
+    }
+
+    fn config(reqs: Vec<Requirement>) -> RequirementConfig {
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: reqs,
+        }
+    }
+
+    #[test]
+    fn test_pattern_match() {
+        let mut good = Requirement::new("Login");
+        good.name = Some("REQ-12".to_string());
+        let mut bad = Requirement::new("logout");
+        bad.name = Some("REQ-x".to_string());
+        let config = config(vec![good, bad, Requirement::new("Unnamed")]);
+
+        let errors = pattern(PatternField::Summary, Some("^[A-Z]"), None)
+            .check(&config)
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].requirement, "logout");
+        assert!(errors[0].error.to_string().contains("'logout'"));
+
+        let errors = pattern(PatternField::Name, Some(r"^REQ-\d+$"), None)
+            .check(&config)
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0]
+            .error
+            .to_string()
+            .contains("violates rule 'custom'"));
+    }
+
+    #[test]
+    fn test_min_words_and_message() {
+        let mut req = Requirement::new("Login");
+        req.description = Some("Too short".to_string());
+        let mut rule = pattern(PatternField::Description, None, Some(3));
+        rule.message = Some("describe the behaviour".to_string());
+
+        let errors = rule.check(&config(vec![req])).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].error.to_string().contains("fewer than 3 words"));
+        assert!(errors[0]
+            .error
+            .to_string()
+            .ends_with("(describe the behaviour)"));
+    }
+
+    #[test]
+    fn test_tags_checked_individually() {
+        let mut req = Requirement::new("Login");
+        req.tags = vec!["ui".to_string(), "Auth".to_string()];
+
+        let errors = pattern(PatternField::Tags, Some("^[a-z]+$"), None)
+            .check(&config(vec![req]))
+            .unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].error.to_string().contains("tag 'Auth'"));
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        let rule = pattern(PatternField::Summary, Some("("), None);
+        assert!(rule.regex().is_err());
+        assert!(rule.check(&config(vec![Requirement::new("A")])).is_err());
+    }
+}
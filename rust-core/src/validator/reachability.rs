@@ -0,0 +1,340 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Online reachability checks for requirement links
+//!
+//! Enabled by the `link-check` feature. A bounded number of requests run at
+//! once, and results are cached in `.rqm/link-cache.json` so CI only fetches
+//! links whose cached result has expired.
+
+use super::links::{is_well_formed_url, urls};
+use super::lookup::{bounded, CacheEntry, LookupCache};
+use super::rules::{RuleLevel, Severity};
+use super::{Finding, ValidationReport};
+use crate::{Error, RequirementConfig, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Command;
+
+/// Rule ID reported for links that cannot be reached
+pub const LINK_REACHABLE_RULE: &str = "link-reachable";
+
+/// Fetches a URL and reports its HTTP status
+pub trait LinkFetcher: Send + Sync {
+    /// Get the final HTTP status, or `None` if no response was received
+    fn status(&self, url: &str) -> Result<Option<u16>>;
+}
+
+/// Fetches links with `curl`, following redirects
+#[derive(Debug, Clone)]
+pub struct CurlFetcher {
+    timeout_secs: u64,
+}
+
+impl Default for CurlFetcher {
+    fn default() -> Self {
+        Self { timeout_secs: 10 }
+    }
+}
+
+impl CurlFetcher {
+    /// Give up on a request after this many seconds
+    pub fn with_timeout(mut self, timeout_secs: u64) -> Self {
+        self.timeout_secs = timeout_secs;
+        self
+    }
+}
+
+impl LinkFetcher for CurlFetcher {
+    fn status(&self, url: &str) -> Result<Option<u16>> {
+        let output = Command::new("curl")
+            .args(["-s", "-L", "-o", "/dev/null", "-w", "%{http_code}"])
+            .arg("--max-time")
+            .arg(self.timeout_secs.to_string())
+            .arg("--")
+            .arg(url)
+            .output()?;
+
+        // curl reports 000 when no response arrived
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse::<u16>()
+            .ok()
+            .filter(|&code| code != 0))
+    }
+}
+
+/// A cached fetch result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedLink {
+    /// Final HTTP status, or `None` if unreachable
+    pub status: Option<u16>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl CachedLink {
+    fn is_reachable(&self) -> bool {
+        matches!(self.status, Some(200..=399))
+    }
+}
+
+impl CacheEntry for CachedLink {
+    const FILE: &'static str = "link-cache.json";
+    const KIND: &'static str = "link";
+
+    fn checked_at(&self) -> DateTime<Utc> {
+        self.checked_at
+    }
+}
+
+/// Fetch results keyed by URL, stored in `.rqm/link-cache.json`
+pub type LinkCache = LookupCache<CachedLink>;
+
+/// Checks that requirement links are reachable
+pub struct LinkChecker {
+    fetcher: Box<dyn LinkFetcher>,
+    cache: LinkCache,
+    ttl: Duration,
+    max_concurrency: usize,
+    level: RuleLevel,
+}
+
+impl Default for LinkChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinkChecker {
+    /// Create a checker using `curl`, eight requests at a time, a one-day
+    /// cache, and warnings
+    pub fn new() -> Self {
+        Self {
+            fetcher: Box::new(CurlFetcher::default()),
+            cache: LinkCache::default(),
+            ttl: Duration::days(1),
+            max_concurrency: 8,
+            level: RuleLevel::Warning,
+        }
+    }
+
+    /// Fetch links with a different client
+    pub fn with_fetcher(mut self, fetcher: impl LinkFetcher + 'static) -> Self {
+        self.fetcher = Box::new(fetcher);
+        self
+    }
+
+    /// Start from previously cached results
+    pub fn with_cache(mut self, cache: LinkCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Re-fetch cached results older than `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Limit the number of requests in flight
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Report unreachable links at this level
+    pub fn with_level(mut self, level: RuleLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Fetch results, including those made by [`LinkChecker::check`]
+    pub fn cache(&self) -> &LinkCache {
+        &self.cache
+    }
+
+    /// Check every well-formed link; malformed ones are left to `link-format`
+    pub fn check(&mut self, config: &RequirementConfig) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let Some(severity) = self.level.severity() else {
+            return report;
+        };
+
+        // URL -> (requirement summary, field) pairs that use it
+        let mut used: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+        for req in config.all_requirements() {
+            for (field, url) in urls(req) {
+                if is_well_formed_url(url) {
+                    used.entry(url).or_default().push((&req.summary, field));
+                }
+            }
+        }
+
+        let now = Utc::now();
+        let pending: Vec<&str> = used
+            .keys()
+            .copied()
+            .filter(|url| self.cache.get(url, self.ttl, now).is_none())
+            .collect();
+
+        let results = bounded(&pending, self.max_concurrency, |&url| {
+            (url, self.fetcher.status(url))
+        });
+        for (url, result) in results {
+            match result {
+                Ok(status) => {
+                    self.cache.entries.insert(
+                        url.to_string(),
+                        CachedLink {
+                            status,
+                            checked_at: now,
+                        },
+                    );
+                }
+                Err(e) => report.push(Finding {
+                    rule: LINK_REACHABLE_RULE.to_string(),
+                    severity: Severity::Info,
+                    error: Error::custom(format!("Could not check '{}': {}", url, e)),
+                    requirement: None,
+                }),
+            }
+        }
+
+        for (url, uses) in &used {
+            let Some(entry) = self.cache.entries.get(*url) else {
+                continue;
+            };
+            if entry.is_reachable() {
+                continue;
+            }
+            let problem = match entry.status {
+                Some(status) => format!("returned HTTP {}", status),
+                None => "is unreachable".to_string(),
+            };
+            for (summary, field) in uses {
+                report.push(Finding {
+                    rule: LINK_REACHABLE_RULE.to_string(),
+                    severity,
+                    error: Error::custom(format!(
+                        "Requirement '{}' links to '{}' in {}, which {}",
+                        summary, url, field, problem
+                    )),
+                    requirement: Some(summary.to_string()),
+                });
+            }
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Requirement;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::TempDir;
+
+    struct FakeFetcher {
+        fetches: Arc<AtomicUsize>,
+        in_flight: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl LinkFetcher for FakeFetcher {
+        fn status(&self, url: &str) -> Result<Option<u16>> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(current, Ordering::SeqCst);
+            thread::sleep(std::time::Duration::from_millis(5));
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(match url {
+                u if u.ends_with("/missing") => Some(404),
+                u if u.contains("offline") => None,
+                _ => Some(200),
+            })
+        }
+    }
+
+    fn config() -> RequirementConfig {
+        let mut a = Requirement::new("A");
+        a.acceptance_test_link = Some("https://ci.example.com/missing".to_string());
+        a.further_information = (0..6)
+            .map(|i| format!("https://docs.example.com/{}", i))
+            .collect();
+        let mut b = Requirement::new("B");
+        b.acceptance_test_link = Some("https://offline.example.com/".to_string());
+        b.further_information = vec![
+            "https://ci.example.com/missing".to_string(),
+            "https://bad host".to_string(),
+        ];
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![a, b],
+        }
+    }
+
+    #[test]
+    fn test_unreachable_links_reported() {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let mut checker = LinkChecker::new()
+            .with_fetcher(FakeFetcher {
+                fetches: fetches.clone(),
+                in_flight: Arc::new(AtomicUsize::new(0)),
+                max_in_flight: max_in_flight.clone(),
+            })
+            .with_max_concurrency(2);
+
+        let report = checker.check(&config());
+        let warnings: Vec<String> = report.warnings.iter().map(Finding::message).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "Requirement 'A' links to 'https://ci.example.com/missing' in acceptance_test_link, which returned HTTP 404",
+                "Requirement 'B' links to 'https://ci.example.com/missing' in further_information, which returned HTTP 404",
+                "Requirement 'B' links to 'https://offline.example.com/' in acceptance_test_link, which is unreachable",
+            ]
+        );
+        assert_eq!(fetches.load(Ordering::SeqCst), 8);
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 2);
+
+        // Everything is cached now
+        let report = checker.check(&config());
+        assert_eq!(fetches.load(Ordering::SeqCst), 8);
+        assert_eq!(report.warnings.len(), 3);
+    }
+
+    #[test]
+    fn test_link_cache_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(LinkCache::load(temp.path()).unwrap(), LinkCache::default());
+
+        let now = Utc::now();
+        let mut cache = LinkCache::default();
+        cache.entries.insert(
+            "https://example.com".to_string(),
+            CachedLink {
+                status: Some(200),
+                checked_at: now - Duration::hours(2),
+            },
+        );
+        cache.save(temp.path()).unwrap();
+
+        let loaded = LinkCache::load(temp.path()).unwrap();
+        assert_eq!(loaded, cache);
+        assert!(loaded
+            .get("https://example.com", Duration::days(1), now)
+            .is_some());
+        assert!(loaded
+            .get("https://example.com", Duration::hours(1), now)
+            .is_none());
+    }
+}
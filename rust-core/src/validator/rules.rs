@@ -0,0 +1,1047 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Built-in lint rules and their per-project configuration
+
+use super::approvals::{approval, missing_approval, outdated_approval};
+use super::attachments::attachment;
+use super::glossary::{banned_term, undefined_acronym, vague_term};
+use super::links::{external_ref, link_format};
+use super::patterns::{PatternRule, RequiredField};
+use super::translations::untranslated;
+use super::{Validator, SCHEMA_RULE};
+use crate::graph::analysis::{stale_requirements, DEFAULT_STALE_DAYS};
+use crate::metadata::{compare_releases, is_valid_tag};
+use crate::types::{RequirementReference, Status};
+use crate::{redact, Error, RequirementConfig, RequirementGraph, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// Severity of a validation finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Name as written in `lint.yml`, e.g. `warning`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// Configured level of a rule
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// Do not run the rule
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl RuleLevel {
+    /// Severity of findings at this level, or `None` when the rule is off
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Warning => Some(Severity::Warning),
+            RuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+/// A named bundle of rule levels
+///
+/// Projects can start from `minimal` and ratchet up to `strict`; levels set
+/// explicitly in `lint.yml` always take precedence over the profile.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    /// Only integrity rules fail; other errors become warnings and the rest is off
+    Minimal,
+
+    /// Each rule at its default level
+    #[default]
+    Standard,
+
+    /// Every rule on; warnings become errors and infos become warnings
+    Strict,
+}
+
+/// Rules that keep their default level in the minimal profile
+const INTEGRITY_RULES: [&str; 5] = [
+    "duplicate-summary",
+    "duplicate-name",
+    "owner-reference",
+    "child-reference",
+    "supersession-link",
+];
+
+impl Profile {
+    /// Level of a rule under this profile
+    pub fn level(self, rule: &Rule) -> RuleLevel {
+        self.adjust(rule.id, rule.default_level)
+    }
+
+    /// Level under this profile of a rule with the given default
+    pub(super) fn adjust(self, id: &str, default_level: RuleLevel) -> RuleLevel {
+        match (self, default_level) {
+            (Profile::Standard, level) => level,
+            (Profile::Minimal, level) if INTEGRITY_RULES.contains(&id) => level,
+            (Profile::Minimal, RuleLevel::Error) => RuleLevel::Warning,
+            (Profile::Minimal, _) => RuleLevel::Off,
+            (Profile::Strict, RuleLevel::Off | RuleLevel::Info) => RuleLevel::Warning,
+            (Profile::Strict, _) => RuleLevel::Error,
+        }
+    }
+}
+
+impl std::str::FromStr for Profile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "minimal" => Ok(Profile::Minimal),
+            "standard" => Ok(Profile::Standard),
+            "strict" => Ok(Profile::Strict),
+            _ => Err(Error::custom(format!(
+                "Unknown profile '{}' (expected minimal, standard, or strict)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Per-project lint configuration, read from `.rqm/lint.yml`
+///
+/// ```yaml
+/// profile: minimal
+/// rules:
+///   missing-description: warning
+///   owner-reference: off
+/// required_fields:
+///   approved: [owner, justification]
+///   verified:
+///     - [acceptance_test, acceptance_test_link]
+/// patterns:
+///   - id: name-format
+///     field: name
+///     matches: "^REQ-\\d+$"
+/// stale_after_days: 60
+/// inherit_priority: true
+/// approval_roles: [product-owner, safety]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct LintConfig {
+    /// Profile supplying the level of rules not listed in `rules`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+
+    /// Level overrides keyed by rule ID, including the IDs of `patterns`;
+    /// unlisted rules keep their default
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleLevel>,
+
+    /// Fields that requirements in a given status must set
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub required_fields: BTreeMap<Status, Vec<RequiredField>>,
+
+    /// Project-defined rules on field content
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<PatternRule>,
+
+    /// Days a draft or proposed requirement may go unchanged before it is
+    /// stale, 90 when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_after_days: Option<u32>,
+
+    /// Let children without a priority inherit their parent's when checking
+    /// for priority conflicts
+    #[serde(default)]
+    pub inherit_priority: bool,
+
+    /// Roles that must each approve a requirement before it is approved
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approval_roles: Vec<String>,
+}
+
+impl LintConfig {
+    /// Load `lint.yml` from an `.rqm` directory, using defaults if it is absent
+    pub fn load<P: AsRef<Path>>(rqm_dir: P) -> Result<Self> {
+        let path = rqm_dir.as_ref().join("lint.yml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let config: Self = serde_yaml::from_str(&content)?;
+        let declared = |id: &String| config.patterns.iter().any(|pattern| &pattern.id == id);
+        if let Some(id) = config
+            .rules
+            .keys()
+            .find(|id| rule(id).is_none() && !declared(id))
+        {
+            return Err(Error::custom(format!(
+                "Unknown lint rule '{}' in {}",
+                id,
+                path.display()
+            )));
+        }
+
+        let mut ids = HashSet::new();
+        for pattern in &config.patterns {
+            if pattern.id == SCHEMA_RULE || rule(&pattern.id).is_some() || !ids.insert(&pattern.id)
+            {
+                return Err(Error::custom(format!(
+                    "Lint rule '{}' in {} is already defined",
+                    pattern.id,
+                    path.display()
+                )));
+            }
+            if pattern.pattern.is_none() && pattern.min_words.is_none() {
+                return Err(Error::custom(format!(
+                    "Lint rule '{}' in {} needs 'matches' or 'min_words'",
+                    pattern.id,
+                    path.display()
+                )));
+            }
+            pattern.regex()?;
+        }
+
+        Ok(config)
+    }
+
+    /// Get the effective level of a rule
+    pub fn level(&self, rule: &Rule) -> RuleLevel {
+        self.level_with(rule, self.profile.unwrap_or_default())
+    }
+
+    /// Get the level of a rule, using `profile` for rules not listed
+    pub fn level_with(&self, rule: &Rule, profile: Profile) -> RuleLevel {
+        self.rules
+            .get(rule.id)
+            .copied()
+            .unwrap_or_else(|| profile.level(rule))
+    }
+}
+
+/// A built-in validation rule
+#[derive(Debug, Clone, Copy)]
+pub struct Rule {
+    /// Stable identifier used in `lint.yml` and reports
+    pub id: &'static str,
+
+    /// One-line description of what the rule checks
+    pub description: &'static str,
+
+    /// Level used when the project does not configure the rule
+    pub default_level: RuleLevel,
+
+    pub(super) check: fn(&Validator, &RequirementConfig) -> Vec<Violation>,
+
+    /// Whether the violations of each requirement depend only on the
+    /// requirement itself, so that files can be checked one by one
+    pub(super) local: bool,
+}
+
+/// A rule violation and the requirement it was found on
+#[derive(Debug)]
+pub(super) struct Violation {
+    pub(super) requirement: String,
+    pub(super) error: Error,
+}
+
+impl Violation {
+    pub(super) fn new(requirement: &str, error: Error) -> Self {
+        Self {
+            requirement: requirement.to_string(),
+            error,
+        }
+    }
+}
+
+/// Rule ID reported for requirements left in draft or proposed too long
+pub(super) const STALE_RULE: &str = "stale-requirement";
+
+/// All built-in rules, in the order they run
+pub const RULES: &[Rule] = &[
+    Rule {
+        id: "duplicate-summary",
+        description: "Requirement summaries must be unique",
+        default_level: RuleLevel::Error,
+        check: duplicate_summary,
+        local: false,
+    },
+    Rule {
+        id: "duplicate-name",
+        description: "Requirement names must be unique",
+        default_level: RuleLevel::Error,
+        check: duplicate_name,
+        local: false,
+    },
+    Rule {
+        id: "owner-reference",
+        description: "Owners must be an email, a GitHub username, or a defined alias",
+        default_level: RuleLevel::Error,
+        check: owner_reference,
+        local: false,
+    },
+    Rule {
+        id: "child-reference",
+        description: "Child requirements referenced by summary must exist",
+        default_level: RuleLevel::Error,
+        check: child_reference,
+        local: false,
+    },
+    Rule {
+        id: "tag-format",
+        description: "Tags must be well-formed hierarchical tags",
+        default_level: RuleLevel::Error,
+        check: tag_format,
+        local: true,
+    },
+    Rule {
+        id: "tag-registry",
+        description: "Tags must be registered when the tag registry is strict",
+        default_level: RuleLevel::Error,
+        check: tag_registry,
+        local: true,
+    },
+    Rule {
+        id: "supersession-link",
+        description: "superseded_by and supersedes must name existing requirements",
+        default_level: RuleLevel::Error,
+        check: supersession_link,
+        local: false,
+    },
+    Rule {
+        id: "deprecated-replacement",
+        description: "Deprecated requirements must declare superseded_by",
+        default_level: RuleLevel::Error,
+        check: deprecated_replacement,
+        local: false,
+    },
+    Rule {
+        id: "deprecated-dependency",
+        description: "Active requirements must not depend on deprecated ones",
+        default_level: RuleLevel::Error,
+        check: deprecated_dependency,
+        local: false,
+    },
+    Rule {
+        id: "release-registry",
+        description: "target_release must be in the release registry, if one is defined",
+        default_level: RuleLevel::Warning,
+        check: release_registry,
+        local: true,
+    },
+    Rule {
+        id: "release-order",
+        description: "Children must not target a later release than their parent",
+        default_level: RuleLevel::Warning,
+        check: release_order,
+        local: false,
+    },
+    Rule {
+        id: "priority-conflict",
+        description: "Children must not have a lower priority than a critical parent",
+        default_level: RuleLevel::Warning,
+        check: priority_conflict,
+        local: false,
+    },
+    Rule {
+        id: "confidentiality-conflict",
+        description: "Children should not be marked less confidential than their parent",
+        default_level: RuleLevel::Warning,
+        check: confidentiality_conflict,
+        local: false,
+    },
+    Rule {
+        id: "status-rollup",
+        description: "Parents must not be further along than their children",
+        default_level: RuleLevel::Warning,
+        check: status_rollup,
+        local: false,
+    },
+    Rule {
+        id: "status-required-fields",
+        description: "Requirements must set the fields required for their status",
+        default_level: RuleLevel::Error,
+        check: status_required_fields,
+        local: true,
+    },
+    Rule {
+        id: STALE_RULE,
+        description: "Draft and proposed requirements should not go unchanged for long",
+        default_level: RuleLevel::Warning,
+        check: stale_requirement,
+        local: true,
+    },
+    Rule {
+        id: "banned-term",
+        description: "Text must not use terms the glossary bans",
+        default_level: RuleLevel::Warning,
+        check: banned_term,
+        local: true,
+    },
+    Rule {
+        id: "vague-term",
+        description: "Text should avoid vague, unverifiable terms",
+        default_level: RuleLevel::Warning,
+        check: vague_term,
+        local: true,
+    },
+    Rule {
+        id: "undefined-acronym",
+        description: "Acronyms should be defined in the glossary",
+        default_level: RuleLevel::Info,
+        check: undefined_acronym,
+        local: false,
+    },
+    Rule {
+        id: "link-format",
+        description: "Links must be well-formed http(s) URLs",
+        default_level: RuleLevel::Off,
+        check: link_format,
+        local: true,
+    },
+    Rule {
+        id: "external-ref",
+        description: "External references must name a system and an ID, and resolve to a URL",
+        default_level: RuleLevel::Warning,
+        check: external_ref,
+        local: true,
+    },
+    Rule {
+        id: "attachment",
+        description: "Attached files must exist in the project and match their checksums",
+        default_level: RuleLevel::Error,
+        check: attachment,
+        local: true,
+    },
+    Rule {
+        id: "untranslated-requirement",
+        description: "Requirements should be translated to every locale in use",
+        default_level: RuleLevel::Warning,
+        check: untranslated,
+        local: false,
+    },
+    Rule {
+        id: "approval",
+        description: "Approvals must name a known person, a valid date and a well-formed signature",
+        default_level: RuleLevel::Error,
+        check: approval,
+        local: false,
+    },
+    Rule {
+        id: "outdated-approval",
+        description: "Signed approvals should match what the requirement says now",
+        default_level: RuleLevel::Warning,
+        check: outdated_approval,
+        local: true,
+    },
+    Rule {
+        id: "missing-approval",
+        description: "Approved requirements need an approval from every role in approval_roles, or any approval without them",
+        default_level: RuleLevel::Warning,
+        check: missing_approval,
+        local: true,
+    },
+    Rule {
+        id: "missing-description",
+        description: "Requirements should have a description",
+        default_level: RuleLevel::Off,
+        check: missing_description,
+        local: true,
+    },
+];
+
+/// Look up a built-in rule by ID
+pub fn rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+fn duplicate_summary(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut seen = HashSet::new();
+    config
+        .all_requirements()
+        .into_iter()
+        .filter(|req| !seen.insert(&req.summary))
+        .map(|req| Violation::new(&req.summary, Error::DuplicateSummary(req.summary.clone())))
+        .collect()
+}
+
+fn duplicate_name(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut seen: HashMap<&str, &str> = HashMap::new();
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        let Some(name) = req.name.as_deref() else {
+            continue;
+        };
+        if let Some(first) = seen.insert(name, &req.summary) {
+            errors.push(Violation::new(
+                &req.summary,
+                Error::custom(format!(
+                    "Requirement '{}' reuses the name '{}' of '{}'",
+                    req.summary, name, first
+                )),
+            ));
+        }
+    }
+    errors
+}
+
+fn owner_reference(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let alias_map = config.alias_map();
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        if let Some(owner) = &req.owner {
+            let owner_str = owner.as_str();
+
+            // Check if it's an email, GitHub username, or valid alias
+            if !owner.is_email() && !owner.is_github() && !alias_map.contains_key(owner_str) {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::InvalidOwner(format!(
+                        "'{}' is not a valid email, GitHub username, or defined alias",
+                        owner_str
+                    )),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn child_reference(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let summaries = known_summaries(validator, config);
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        for child in &req.requirements {
+            if let RequirementReference::Reference(summary) = child {
+                if !resolves(validator, &summaries, summary) {
+                    errors.push(Violation::new(
+                        &req.summary,
+                        Error::InvalidReference(format!(
+                            "Requirement '{}' references non-existent '{}'",
+                            req.summary, summary
+                        )),
+                    ));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Summaries of `config` and of the requirements archived from it
+fn known_summaries<'a>(
+    validator: &'a Validator,
+    config: &'a RequirementConfig,
+) -> HashSet<&'a str> {
+    config
+        .all_requirements()
+        .into_iter()
+        .map(|req| req.summary.as_str())
+        .chain(validator.archived.iter().map(String::as_str))
+        .collect()
+}
+
+/// Whether `reference` names one of `summaries`, or a requirement of
+/// another project of the monorepo, which is checked with the monorepo
+fn resolves(validator: &Validator, summaries: &HashSet<&str>, reference: &str) -> bool {
+    summaries.contains(reference)
+        || validator.project_scope.as_ref().is_some_and(|scope| {
+            scope
+                .local(reference)
+                .is_none_or(|local| summaries.contains(local))
+        })
+}
+
+fn tag_format(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        for tag in req.tags.iter().filter(|tag| !is_valid_tag(tag)) {
+            errors.push(Violation::new(
+                &req.summary,
+                Error::InvalidTag(format!(
+                    "'{}' on requirement '{}' is not a well-formed hierarchical tag",
+                    tag, req.summary
+                )),
+            ));
+        }
+    }
+    errors
+}
+
+fn tag_registry(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let registry = &validator.tag_registry;
+    if !registry.strict {
+        return vec![];
+    }
+
+    let mut errors = vec![];
+    for req in config.all_requirements() {
+        for tag in &req.tags {
+            if is_valid_tag(tag) && !registry.is_known(tag) {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::InvalidTag(format!(
+                        "'{}' on requirement '{}' is not in the tag registry",
+                        tag, req.summary
+                    )),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+fn supersession_link(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let summaries = known_summaries(validator, config);
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        for target in req.superseded_by.iter().chain(&req.supersedes) {
+            if !resolves(validator, &summaries, target) {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::InvalidReference(format!(
+                        "Requirement '{}' has supersession link to non-existent '{}'",
+                        req.summary, target
+                    )),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn deprecated_replacement(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    config
+        .all_requirements()
+        .into_iter()
+        .filter(|req| req.is_deprecated() && req.superseded_by.is_none())
+        .map(|req| {
+            Violation::new(
+                &req.summary,
+                Error::DeprecatedReference(format!(
+                    "'{}' is deprecated but does not declare superseded_by",
+                    req.summary
+                )),
+            )
+        })
+        .collect()
+}
+
+fn deprecated_dependency(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let by_summary: HashMap<&str, _> = config
+        .all_requirements()
+        .into_iter()
+        .map(|req| (req.summary.as_str(), req))
+        .collect();
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        if req.is_deprecated() {
+            continue;
+        }
+
+        for child in &req.requirements {
+            let child_summary = child.summary();
+            if by_summary
+                .get(child_summary)
+                .is_some_and(|child| child.is_deprecated())
+            {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::DeprecatedReference(format!(
+                        "Active requirement '{}' depends on deprecated '{}'",
+                        req.summary, child_summary
+                    )),
+                ));
+            } else if !by_summary.contains_key(child_summary)
+                && validator.archived.contains(child_summary)
+            {
+                errors.push(Violation::new(
+                    &req.summary,
+                    Error::DeprecatedReference(format!(
+                        "Active requirement '{}' depends on archived '{}'",
+                        req.summary, child_summary
+                    )),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn release_registry(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let releases = &validator.releases;
+    if releases.is_empty() {
+        return vec![];
+    }
+
+    config
+        .all_requirements()
+        .into_iter()
+        .filter_map(|req| {
+            let release = req.target_release.as_ref()?;
+            (!releases.iter().any(|r| &r.name == release)).then(|| {
+                Violation::new(
+                    &req.summary,
+                    Error::custom(format!(
+                        "Requirement '{}' targets unregistered release '{}'",
+                        req.summary, release
+                    )),
+                )
+            })
+        })
+        .collect()
+}
+
+fn release_order(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let by_summary: HashMap<&str, _> = config
+        .all_requirements()
+        .into_iter()
+        .map(|req| (req.summary.as_str(), req))
+        .collect();
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        let Some(release) = &req.target_release else {
+            continue;
+        };
+
+        for child in &req.requirements {
+            let child_summary = child.summary();
+            let Some(child_release) = by_summary
+                .get(child_summary)
+                .and_then(|child| child.target_release.as_deref())
+            else {
+                continue;
+            };
+
+            if compare_releases(&validator.releases, child_release, release)
+                == Some(std::cmp::Ordering::Greater)
+            {
+                errors.push(Violation::new(
+                    child_summary,
+                    Error::custom(format!(
+                        "Requirement '{}' targets release '{}' after its parent '{}' ('{}')",
+                        child_summary, child_release, req.summary, release
+                    )),
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+fn priority_conflict(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let graph = if validator.lint.inherit_priority {
+        let mut inherited = config.clone();
+        inherited.inherit_priorities();
+        RequirementGraph::from_config(&inherited)
+    } else {
+        RequirementGraph::from_config(config)
+    };
+    // Dangling references are reported by child-reference
+    let Ok(graph) = graph else {
+        return vec![];
+    };
+    graph
+        .priority_conflicts()
+        .into_iter()
+        .map(|conflict| {
+            Violation::new(
+                &conflict.child,
+                Error::custom(format!(
+                    "Requirement '{}' has {} priority under {} parent '{}'",
+                    conflict.child,
+                    conflict.child_priority,
+                    conflict.parent_priority,
+                    conflict.parent
+                )),
+            )
+        })
+        .collect()
+}
+
+fn confidentiality_conflict(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let levels = redact::levels(config);
+    let mut errors = vec![];
+    for parent in config.all_requirements() {
+        for child in &parent.requirements {
+            let RequirementReference::Full(child) = child else {
+                continue;
+            };
+            let (Some(marked), Some(&inherited)) =
+                (child.confidentiality, levels.get(parent.summary.as_str()))
+            else {
+                continue;
+            };
+            if marked < inherited {
+                errors.push(Violation::new(
+                    &child.summary,
+                    Error::custom(format!(
+                        "Requirement '{}' is marked {} but is {} like its parent '{}'",
+                        child.summary, marked, inherited, parent.summary
+                    )),
+                ));
+            }
+        }
+    }
+    errors
+}
+
+fn status_rollup(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    // Dangling references are reported by child-reference
+    let Ok(graph) = RequirementGraph::from_config(config) else {
+        return vec![];
+    };
+    graph
+        .statuses_ahead_of_children()
+        .into_iter()
+        .filter_map(|(req, derived)| {
+            let declared = req.status?;
+            Some(Violation::new(
+                &req.summary,
+                Error::custom(format!(
+                    "Requirement '{}' is {} but its children are only {}",
+                    req.summary, declared, derived
+                )),
+            ))
+        })
+        .collect()
+}
+
+fn status_required_fields(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let policies = &validator.lint.required_fields;
+    let mut errors = vec![];
+
+    for req in config.all_requirements() {
+        let Some((status, required)) = req
+            .status
+            .and_then(|status| Some((status, policies.get(&status)?)))
+        else {
+            continue;
+        };
+        for field in required.iter().filter(|field| !field.is_satisfied(req)) {
+            errors.push(Violation::new(
+                &req.summary,
+                Error::custom(format!(
+                    "Requirement '{}' is {} but has no {}",
+                    req.summary,
+                    status,
+                    field.describe()
+                )),
+            ));
+        }
+    }
+
+    errors
+}
+
+fn stale_requirement(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let max_days = validator
+        .lint
+        .stale_after_days
+        .unwrap_or(DEFAULT_STALE_DAYS);
+    // Without readable metadata only timestamps in the file itself count
+    let stale = stale_requirements(config, validator.metadata.as_ref(), max_days, Utc::now())
+        .or_else(|_| stale_requirements(config, None, max_days, Utc::now()))
+        .unwrap_or_default();
+    stale
+        .into_iter()
+        .map(|stale| {
+            Violation::new(
+                &stale.summary,
+                Error::custom(format!(
+                    "Requirement '{}' has been {} for {} days without changes (stale after {})",
+                    stale.summary, stale.status, stale.days, max_days
+                )),
+            )
+        })
+        .collect()
+}
+
+fn missing_description(_: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    config
+        .all_requirements()
+        .into_iter()
+        .filter(|req| req.description.as_deref().unwrap_or("").trim().is_empty())
+        .map(|req| {
+            Violation::new(
+                &req.summary,
+                Error::custom(format!("Requirement '{}' has no description", req.summary)),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rule_ids_are_unique() {
+        let ids: HashSet<&str> = RULES.iter().map(|rule| rule.id).collect();
+        assert_eq!(ids.len(), RULES.len());
+        assert!(rule("duplicate-summary").is_some());
+        assert!(rule("nonexistent").is_none());
+    }
+
+    #[test]
+    fn test_lint_config_load() {
+        let temp = TempDir::new().unwrap();
+        assert_eq!(
+            LintConfig::load(temp.path()).unwrap(),
+            LintConfig::default()
+        );
+
+        fs::write(
+            temp.path().join("lint.yml"),
+            "rules:\n  missing-description: warning\n  owner-reference: off\n",
+        )
+        .unwrap();
+        let config = LintConfig::load(temp.path()).unwrap();
+        assert_eq!(
+            config.level(rule("missing-description").unwrap()),
+            RuleLevel::Warning
+        );
+        assert_eq!(
+            config.level(rule("owner-reference").unwrap()),
+            RuleLevel::Off
+        );
+        assert_eq!(
+            config.level(rule("duplicate-summary").unwrap()),
+            RuleLevel::Error
+        );
+    }
+
+    #[test]
+    fn test_lint_config_rejects_unknown_rule() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("lint.yml"),
+            "rules:\n  no-such-rule: error\n",
+        )
+        .unwrap();
+
+        let err = LintConfig::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("no-such-rule"));
+    }
+
+    #[test]
+    fn test_lint_config_patterns() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("lint.yml"),
+            "patterns:\n  - id: name-format\n    field: name\n    matches: '^REQ-\\d+$'\n    level: warning\n",
+        )
+        .unwrap();
+        let config = LintConfig::load(temp.path()).unwrap();
+        assert_eq!(config.patterns[0].id, "name-format");
+        assert_eq!(config.patterns[0].pattern.as_deref(), Some("^REQ-\\d+$"));
+        assert_eq!(config.patterns[0].level, RuleLevel::Warning);
+
+        // `rules` may override the level of a declared pattern
+        fs::write(
+            temp.path().join("lint.yml"),
+            "rules:\n  name-format: off\npatterns:\n  - id: name-format\n    field: name\n    matches: '^REQ-\\d+$'\n",
+        )
+        .unwrap();
+        let mut config = LintConfig::load(temp.path()).unwrap();
+        let requirements = crate::Parser::parse_str(
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    name: login\n",
+        )
+        .unwrap();
+        let flagged = |config: &LintConfig| {
+            let validator = Validator::new().unwrap().with_lint_config(config.clone());
+            let report = validator.validate(&requirements);
+            let flagged = report.findings().any(|f| f.rule == "name-format");
+            flagged
+        };
+        assert!(!flagged(&config));
+        config.rules.clear();
+        assert!(flagged(&config));
+
+        for bad in [
+            "patterns:\n  - id: tag-format\n    field: tags\n    matches: x\n",
+            "patterns:\n  - id: empty\n    field: summary\n",
+            "patterns:\n  - id: broken\n    field: summary\n    matches: '('\n",
+        ] {
+            fs::write(temp.path().join("lint.yml"), bad).unwrap();
+            assert!(LintConfig::load(temp.path()).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_lint_config_required_fields() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("lint.yml"),
+            "required_fields:\n  approved: [owner, justification]\n  verified:\n    - [acceptance_test, acceptance_test_link]\n",
+        )
+        .unwrap();
+
+        let config = LintConfig::load(temp.path()).unwrap();
+        assert_eq!(config.required_fields[&Status::Approved].len(), 2);
+        assert_eq!(
+            config.required_fields[&Status::Verified][0].describe(),
+            "acceptance_test or acceptance_test_link"
+        );
+    }
+
+    #[test]
+    fn test_profile_levels() {
+        let duplicate = rule("duplicate-summary").unwrap();
+        let tag_format = rule("tag-format").unwrap();
+        let release_order = rule("release-order").unwrap();
+        let description = rule("missing-description").unwrap();
+
+        assert_eq!(Profile::Minimal.level(duplicate), RuleLevel::Error);
+        assert_eq!(Profile::Minimal.level(tag_format), RuleLevel::Warning);
+        assert_eq!(Profile::Minimal.level(release_order), RuleLevel::Off);
+        assert_eq!(Profile::Standard.level(release_order), RuleLevel::Warning);
+        assert_eq!(Profile::Strict.level(release_order), RuleLevel::Error);
+        assert_eq!(Profile::Strict.level(description), RuleLevel::Warning);
+
+        let config = LintConfig {
+            profile: Some(Profile::Strict),
+            rules: BTreeMap::from([("release-order".to_string(), RuleLevel::Off)]),
+            ..LintConfig::default()
+        };
+        assert_eq!(config.level(release_order), RuleLevel::Off);
+        assert_eq!(config.level(description), RuleLevel::Warning);
+        assert_eq!(
+            config.level_with(description, Profile::Minimal),
+            RuleLevel::Off
+        );
+        assert_eq!("strict".parse::<Profile>().unwrap(), Profile::Strict);
+        assert!("lax".parse::<Profile>().is_err());
+    }
+}
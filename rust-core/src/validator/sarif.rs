@@ -0,0 +1,250 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! SARIF output for validation reports
+//!
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! is understood by GitHub code scanning and most CI systems, which use it to
+//! annotate the offending lines of a pull request.
+
+use super::rules::{Severity, RULES};
+use super::{ValidationReport, SCHEMA_RULE};
+use crate::workspace::WorkspaceReport;
+use crate::Parser;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+impl ValidationReport {
+    /// Render the report as a SARIF log
+    ///
+    /// `artifact_uri` is the path of the validated file as CI should report
+    /// it, usually relative to the repository root. `source` is the file's
+    /// content, used to find the line of each finding's requirement; findings
+    /// that cannot be placed are reported on line 1.
+    pub fn to_sarif(&self, artifact_uri: &str, source: &str) -> Value {
+        sarif_log(&[(artifact_uri, source, self)])
+    }
+}
+
+impl WorkspaceReport {
+    /// Render the findings of every file as one SARIF log
+    ///
+    /// `artifacts` holds the URI and content of each file, in workspace
+    /// order, as for [`ValidationReport::to_sarif`]. Findings that concern
+    /// no requirement are reported on line 1 of the first file.
+    pub fn to_sarif(&self, artifacts: &[(&str, &str)]) -> Value {
+        let mut reports = vec![];
+        if let Some(&(uri, _)) = artifacts.first() {
+            reports.push((uri, "", &self.unattributed));
+        }
+        reports.extend(
+            artifacts
+                .iter()
+                .zip(&self.files)
+                .map(|(&(uri, source), file)| (uri, source, &file.report)),
+        );
+        sarif_log(&reports)
+    }
+}
+
+/// A SARIF log of the reports of files, given with their URI and content
+fn sarif_log(reports: &[(&str, &str, &ValidationReport)]) -> Value {
+    let mut rules: Vec<Value> = std::iter::once(json!({
+        "id": SCHEMA_RULE,
+        "shortDescription": { "text": "Requirements file matches the JSON schema" },
+        "defaultConfiguration": { "level": "error" },
+    }))
+    .chain(RULES.iter().map(|rule| {
+        json!({
+            "id": rule.id,
+            "shortDescription": { "text": rule.description },
+            "defaultConfiguration": {
+                "level": rule.default_level.severity().map_or("none", level)
+            },
+        })
+    }))
+    .collect();
+    let mut ids: Vec<String> = rules
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap_or_default().to_string())
+        .collect();
+
+    // Project pattern rules and optional checks are only known from their findings
+    let custom: BTreeSet<&str> = reports
+        .iter()
+        .flat_map(|(_, _, report)| report.findings())
+        .map(|finding| finding.rule.as_str())
+        .filter(|id| !ids.iter().any(|known| known == id))
+        .collect();
+    for id in custom {
+        rules.push(json!({ "id": id }));
+        ids.push(id.to_string());
+    }
+
+    let mut artifacts: Vec<&str> = vec![];
+    let mut results: Vec<Value> = vec![];
+    for &(artifact_uri, source, report) in reports {
+        if !artifacts.contains(&artifact_uri) {
+            artifacts.push(artifact_uri);
+        }
+        results.extend(report.findings().map(|finding| {
+            let line = finding
+                .requirement
+                .as_deref()
+                .and_then(|summary| Parser::summary_line(source, summary))
+                .unwrap_or(1);
+            json!({
+                "ruleId": finding.rule,
+                "ruleIndex": ids.iter().position(|id| *id == finding.rule),
+                "level": level(finding.severity),
+                "message": { "text": finding.message() },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": artifact_uri },
+                        "region": { "startLine": line },
+                    },
+                }],
+            })
+        }));
+    }
+    let artifacts: Vec<Value> = artifacts
+        .into_iter()
+        .map(|uri| json!({ "location": { "uri": uri } }))
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "rqm-validator",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/238855/rqm",
+                    "rules": rules,
+                },
+            },
+            "artifacts": artifacts,
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::Finding;
+    use crate::{Error, Validator};
+
+    const SOURCE: &str = r#"version: "1.0"
+requirements:
+  - summary: Login
+    owner: "not an owner"
+  - summary: Logout
+    owner: alice@example.com
+    superseded_by: Nothing
+"#;
+
+    #[test]
+    fn test_to_sarif() {
+        let config = Parser::parse_str(SOURCE).unwrap();
+        let report = Validator::new().unwrap().validate(&config);
+        let sarif = report.to_sarif("requirements.yml", SOURCE);
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "rqm-validator");
+        assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "schema");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "owner-reference");
+        assert_eq!(results[0]["level"], "error");
+        let location = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "requirements.yml");
+        assert_eq!(location["region"]["startLine"], 3);
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["region"]["startLine"],
+            5
+        );
+
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let index = results[1]["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(rules[index]["id"], results[1]["ruleId"]);
+    }
+
+    #[test]
+    fn test_workspace_to_sarif() {
+        use crate::workspace::{Workspace, WorkspaceFile};
+
+        let other =
+            "version: \"1.0\"\nrequirements:\n  - summary: Other\n    owner: \"bad owner\"\n";
+        let workspace = Workspace {
+            files: vec![
+                WorkspaceFile::parse("a.yml", SOURCE.to_string()).unwrap(),
+                WorkspaceFile::parse("b.yml", other.to_string()).unwrap(),
+            ],
+        };
+        let report = workspace.validate(&Validator::new().unwrap());
+        let sarif = report.to_sarif(&[("a.yml", SOURCE), ("b.yml", other)]);
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["artifacts"].as_array().unwrap().len(), 2);
+        let locations: Vec<(String, u64)> = run["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|result| {
+                let location = &result["locations"][0]["physicalLocation"];
+                (
+                    location["artifactLocation"]["uri"]
+                        .as_str()
+                        .unwrap()
+                        .to_string(),
+                    location["region"]["startLine"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                ("a.yml".to_string(), 3),
+                ("a.yml".to_string(), 5),
+                ("b.yml".to_string(), 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_rules_listed() {
+        let mut report = ValidationReport::default();
+        report.push(Finding {
+            rule: "summary-case".to_string(),
+            severity: Severity::Info,
+            error: Error::custom("Requirement 'x' violates rule 'summary-case'"),
+            requirement: Some("missing".to_string()),
+        });
+
+        let sarif = report.to_sarif("reqs.yml", "");
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "note");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            1
+        );
+        let index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(run["tool"]["driver"]["rules"][index]["id"], "summary-case");
+    }
+}
@@ -0,0 +1,119 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Requirements missing translations
+//!
+//! Every locale a requirement of the file is translated to counts, together
+//! with those the project requires with [`Validator::with_locales`].
+
+use super::rules::Violation;
+use super::Validator;
+use crate::locale::locales;
+use crate::{Error, RequirementConfig};
+use std::collections::HashMap;
+
+pub(super) fn untranslated(validator: &Validator, config: &RequirementConfig) -> Vec<Violation> {
+    let mut required = locales(config);
+    required.extend(validator.locales.iter().cloned());
+
+    let mut errors = vec![];
+    for locale in &required {
+        let mut translated: HashMap<&str, &str> = HashMap::new();
+        for req in config.all_requirements() {
+            let translation = req.translations.get(locale);
+            let summary = translation.and_then(|t| t.summary.as_deref());
+            let message = if translation.is_none() {
+                format!(
+                    "Requirement '{}' is not translated to '{}'",
+                    req.summary, locale
+                )
+            } else if summary.is_none() {
+                format!(
+                    "The '{}' translation of requirement '{}' has no summary",
+                    locale, req.summary
+                )
+            } else if req.description.is_some()
+                && translation.is_some_and(|t| t.description.is_none())
+            {
+                format!(
+                    "The '{}' translation of requirement '{}' has no description",
+                    locale, req.summary
+                )
+            } else {
+                let summary = summary.unwrap_or_default();
+                match translated.insert(summary, &req.summary) {
+                    Some(other) => format!(
+                        "Requirement '{}' has the same '{}' summary '{}' as '{}'",
+                        req.summary, locale, summary, other
+                    ),
+                    None => continue,
+                }
+            };
+            errors.push(Violation::new(&req.summary, Error::custom(message)));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn test_untranslated() {
+        let config = Parser::parse_str(
+            r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    description: Users sign in
+    translations:
+      de:
+        summary: Anmeldung
+        description: Benutzer melden sich an
+    requirements:
+      - summary: Logout
+        description: Users sign out
+        translations:
+          de:
+            summary: Abmeldung
+      - summary: Lockout
+        translations:
+          de:
+            summary: Anmeldung
+          fr: {}
+"#,
+        )
+        .unwrap();
+        let messages = |validator: &Validator| -> Vec<String> {
+            untranslated(validator, &config)
+                .iter()
+                .map(|v| v.error.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            messages(&Validator::new().unwrap()),
+            vec![
+                "The 'de' translation of requirement 'Logout' has no description",
+                "Requirement 'Lockout' has the same 'de' summary 'Anmeldung' as 'Login'",
+                "Requirement 'Login' is not translated to 'fr'",
+                "Requirement 'Logout' is not translated to 'fr'",
+                "The 'fr' translation of requirement 'Lockout' has no summary",
+            ]
+        );
+
+        let validator = Validator::new()
+            .unwrap()
+            .with_locales(vec!["es".to_string()]);
+        assert_eq!(
+            messages(&validator)
+                .iter()
+                .filter(|message| message.contains("'es'"))
+                .count(),
+            3
+        );
+    }
+}
@@ -0,0 +1,158 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! JavaScript bindings for WebAssembly
+//!
+//! Enabled by the `wasm` feature, for validating requirements in the
+//! browser without the native binaries:
+//!
+//! ```sh
+//! wasm-pack build rust-core --target web -- --features wasm
+//! ```
+//!
+//! Every function takes YAML source and returns JSON text in the same
+//! format as `rqm-validator --output json`, so results can be handled the
+//! same way in JavaScript and on the command line:
+//!
+//! ```js
+//! import init, { validate } from './pkg/rqm_core.js';
+//! await init();
+//! const result = JSON.parse(validate(source));
+//! ```
+//!
+//! Functions throw an `Error` if the YAML cannot be parsed, except
+//! [`validate`], which reports that as a validation error.
+
+use crate::error::{Error, Result};
+use crate::graph::{self, RequirementGraph};
+use crate::output::{DiffResult, ValidationResult};
+use crate::parser::Parser;
+use crate::validator::{ValidationReport, Validator};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Parse requirements, returning them as JSON with templates applied
+#[wasm_bindgen]
+pub fn parse(yaml: &str) -> std::result::Result<String, JsError> {
+    Ok(parse_json(yaml)?)
+}
+
+/// Validate requirements, returning a `ValidationResult`
+///
+/// `profile` selects a lint profile such as `strict`; findings are located
+/// by line where their requirement is known.
+#[wasm_bindgen]
+pub fn validate(yaml: &str, profile: Option<String>) -> std::result::Result<String, JsError> {
+    Ok(validate_json(yaml, profile.as_deref())?)
+}
+
+/// The requirement graph as a JSON Graph Format document
+#[wasm_bindgen]
+pub fn graph(yaml: &str) -> std::result::Result<String, JsError> {
+    Ok(graph_json(yaml)?)
+}
+
+/// Changes from `old` to `new`, returning a `DiffResult`
+#[wasm_bindgen]
+pub fn diff(old: &str, new: &str) -> std::result::Result<String, JsError> {
+    Ok(diff_json(old, new)?)
+}
+
+fn parse_json(yaml: &str) -> Result<String> {
+    to_json(&Parser::parse_str(yaml)?)
+}
+
+fn validate_json(yaml: &str, profile: Option<&str>) -> Result<String> {
+    let mut validator = Validator::new()?;
+    if let Some(profile) = profile {
+        validator = validator.with_profile(profile.parse()?);
+    }
+    let result = match Parser::parse_str(yaml) {
+        Ok(config) => ValidationResult::from_report(&validator.validate(&config), yaml),
+        Err(e) => {
+            // Values that don't fit the data model are better explained by the schema
+            let schema_errors = serde_yaml::from_str::<serde_json::Value>(yaml)
+                .map(|document| validator.check_schema(&document))
+                .unwrap_or_default();
+            if schema_errors.is_empty() {
+                ValidationResult::error(format!("Parse error: {}", e))
+            } else {
+                let report = ValidationReport {
+                    errors: schema_errors,
+                    ..ValidationReport::default()
+                };
+                ValidationResult::from_report(&report, yaml)
+            }
+        }
+    };
+    to_json(&result)
+}
+
+fn graph_json(yaml: &str) -> Result<String> {
+    let graph = RequirementGraph::from_config(&Parser::parse_str(yaml)?)?;
+    to_json(&graph.to_json_graph())
+}
+
+fn diff_json(old: &str, new: &str) -> Result<String> {
+    let old = RequirementGraph::from_config(&Parser::parse_str(old)?)?;
+    let new = RequirementGraph::from_config(&Parser::parse_str(new)?)?;
+    to_json(&DiffResult::new(graph::diff(&old, &new)))
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(|e| Error::custom(format!("Failed to serialize: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const YAML: &str = r#"version: "1.0"
+requirements:
+  - summary: Login
+    status: draft
+    requirements:
+      - summary: Lockout
+"#;
+
+    fn json(text: &str) -> Value {
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_graph() {
+        assert_eq!(
+            json(&parse_json(YAML).unwrap())["requirements"][0]["summary"],
+            "Login"
+        );
+        assert!(parse_json("requirements: [").is_err());
+
+        let graph = json(&graph_json(YAML).unwrap());
+        assert_eq!(graph["graph"]["edges"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_validate_reports_errors_as_results() {
+        let result = json(&validate_json(YAML, None).unwrap());
+        assert_eq!(result["valid"], true);
+        assert_eq!(result["schema_version"], 1);
+
+        let duplicate = "version: \"1.0\"\nrequirements:\n  - summary: A\n  - summary: A\n";
+        let result = json(&validate_json(duplicate, None).unwrap());
+        assert_eq!(result["valid"], false);
+
+        let result = json(&validate_json("requirements: [", None).unwrap());
+        assert_eq!(result["valid"], false);
+        assert!(validate_json(YAML, Some("nope")).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let changed = YAML.replace("status: draft", "status: approved");
+        let result = json(&diff_json(YAML, &changed).unwrap());
+        assert_eq!(result["modified"][0]["summary"], "Login");
+        assert_eq!(result["status_transitions"][0]["to"], "approved");
+    }
+}
@@ -0,0 +1,416 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Revalidation of requirements files as they are edited
+//!
+//! Enabled by the `watch` feature. [`WatchSession::run`] waits for file
+//! system events in the directories of its files and their archives, and
+//! only files whose content changed are parsed again. Findings are kept in
+//! memory by file content, so the schema and the rules looking at each
+//! requirement on its own only run again on the changed files; the rules
+//! spanning files run on the whole [`Workspace`]. The findings are compared
+//! with the previous run, so the editor sees which are new and which were
+//! fixed. References to archived requirements resolve as they are archived.
+
+use crate::archive::{archive_path, load_archives};
+use crate::validator::{Finding, ValidationCache, Validator};
+use crate::workspace::{Workspace, WorkspaceFile};
+use crate::{Error, Result};
+use notify::{Event, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How long to wait for more events after one arrived, as editors often
+/// write a file in several steps
+const DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Findings that appeared or disappeared since the previous run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FindingDelta {
+    /// Files read and parsed again
+    pub reparsed: Vec<PathBuf>,
+
+    pub new: Vec<String>,
+    pub fixed: Vec<String>,
+
+    /// Number of findings after this run
+    pub total: usize,
+
+    /// Time spent parsing and validating
+    pub elapsed: Duration,
+}
+
+impl FindingDelta {
+    /// Check whether any finding appeared or disappeared
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.fixed.is_empty()
+    }
+}
+
+/// A watched file as of its last read
+struct Watched {
+    path: PathBuf,
+
+    /// The path as file system events name it, in its resolved directory
+    resolved: PathBuf,
+
+    /// Content as last read, `None` if the file could not be read
+    source: Option<String>,
+
+    /// The parsed file, or why it could not be parsed
+    parsed: std::result::Result<WorkspaceFile, String>,
+
+    /// Whether the file may have changed since it was last read
+    dirty: bool,
+}
+
+/// A path with its directory resolved, so that relative and symlinked
+/// paths compare equal to those in file system events
+fn resolve(path: &Path) -> PathBuf {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let parent = fs::canonicalize(parent).unwrap_or_else(|_| parent.to_path_buf());
+    match path.file_name() {
+        Some(name) => parent.join(name),
+        None => parent,
+    }
+}
+
+/// Keeps the files of a workspace and their findings between runs
+pub struct WatchSession {
+    files: Vec<Watched>,
+    findings: BTreeSet<String>,
+
+    /// Findings of earlier runs by file content
+    cache: ValidationCache,
+
+    /// Whether an archive may have changed since the last run
+    archives_changed: bool,
+
+    /// Why the archives could not be loaded, as of the last load
+    archive_error: Option<String>,
+}
+
+impl WatchSession {
+    /// Watch `paths`; nothing is read until the first
+    /// [`WatchSession::revalidate`]
+    pub fn new<I, P>(paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let files = paths
+            .into_iter()
+            .map(|path| Watched {
+                path: path.as_ref().to_path_buf(),
+                resolved: resolve(path.as_ref()),
+                source: None,
+                parsed: Err("not read yet".to_string()),
+                dirty: true,
+            })
+            .collect();
+        Self {
+            files,
+            findings: BTreeSet::new(),
+            cache: ValidationCache::in_memory(),
+            archives_changed: true,
+            archive_error: None,
+        }
+    }
+
+    /// Current findings, each as `severity: file: message`
+    pub fn findings(&self) -> &BTreeSet<String> {
+        &self.findings
+    }
+
+    /// Note that `path` changed, returning whether it is one of the files,
+    /// their archives or the archive directories
+    pub fn changed(&mut self, path: &Path) -> bool {
+        let path = resolve(path);
+        let mut watched = false;
+        for file in &mut self.files {
+            let archive = archive_path(&file.resolved);
+            if path == file.resolved {
+                file.dirty = true;
+                watched = true;
+            } else if path == archive || Some(path.as_path()) == archive.parent() {
+                self.archives_changed = true;
+                watched = true;
+            }
+        }
+        watched
+    }
+
+    /// Revalidate if any file changed since the last run
+    ///
+    /// Returns `None` when no content changed. The first run reads every
+    /// file and reports all findings as new. The validator is given the
+    /// archives of the files whenever they change.
+    pub fn revalidate(&mut self, validator: &mut Validator) -> Option<FindingDelta> {
+        let start = Instant::now();
+        let archives_changed = std::mem::take(&mut self.archives_changed);
+        if archives_changed {
+            self.archive_error = match load_archives(self.files.iter().map(|file| &file.path)) {
+                Ok(archived) => {
+                    validator.set_archive(archived.as_ref());
+                    None
+                }
+                Err(e) => Some(format!("error: archive: {}", e)),
+            };
+            // Cached findings depend on the archived requirements
+            self.cache = ValidationCache::in_memory();
+        }
+
+        let mut reparsed = vec![];
+        for file in self.files.iter_mut().filter(|file| file.dirty) {
+            file.dirty = false;
+            let source = fs::read_to_string(&file.path);
+            if file.source.is_some() && file.source.as_ref() == source.as_ref().ok() {
+                continue;
+            }
+            file.source = source.as_ref().ok().cloned();
+            file.parsed = source
+                .map_err(|e| format!("{}: {}", file.path.display(), e))
+                .and_then(|source| {
+                    WorkspaceFile::parse(&file.path, source).map_err(|e| e.to_string())
+                });
+            reparsed.push(file.path.clone());
+        }
+        if reparsed.is_empty() && !archives_changed {
+            return None;
+        }
+
+        let mut findings = self.validate(validator);
+        findings.extend(self.archive_error.clone());
+        let delta = FindingDelta {
+            reparsed,
+            new: findings.difference(&self.findings).cloned().collect(),
+            fixed: self.findings.difference(&findings).cloned().collect(),
+            total: findings.len(),
+            elapsed: start.elapsed(),
+        };
+        self.findings = findings;
+        Some(delta)
+    }
+
+    /// Validate, then revalidate whenever the files or their archives
+    /// change, passing each delta to `on_change` until it returns false
+    pub fn run(
+        &mut self,
+        validator: &mut Validator,
+        mut on_change: impl FnMut(&FindingDelta) -> bool,
+    ) -> Result<()> {
+        let watch_error = |e: notify::Error| Error::custom(format!("Failed to watch files: {}", e));
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+        let mut directories = BTreeSet::new();
+        loop {
+            // Directories are watched rather than files, as editors often
+            // replace a file on saving; archive directories once they exist
+            for file in &self.files {
+                let archive = archive_path(&file.resolved);
+                for directory in [file.resolved.parent(), archive.parent()]
+                    .into_iter()
+                    .flatten()
+                {
+                    if directory.is_dir() && !directories.contains(directory) {
+                        watcher
+                            .watch(directory, RecursiveMode::NonRecursive)
+                            .map_err(watch_error)?;
+                        directories.insert(directory.to_path_buf());
+                    }
+                }
+            }
+
+            if let Some(delta) = self.revalidate(validator) {
+                if !on_change(&delta) {
+                    return Ok(());
+                }
+            }
+
+            let Ok(event) = events.recv() else {
+                return Ok(());
+            };
+            self.handle(event.map_err(watch_error)?);
+            while let Ok(event) = events.recv_timeout(DEBOUNCE) {
+                self.handle(event.map_err(watch_error)?);
+            }
+        }
+    }
+
+    fn handle(&mut self, event: Event) {
+        for path in &event.paths {
+            self.changed(path);
+        }
+    }
+
+    /// Findings of every parsed file validated together, plus parse errors
+    fn validate(&self, validator: &Validator) -> BTreeSet<String> {
+        let mut findings = BTreeSet::new();
+        let mut workspace = Workspace::default();
+        for file in &self.files {
+            match &file.parsed {
+                Ok(parsed) => workspace.files.push(parsed.clone()),
+                Err(e) => {
+                    findings.insert(format!("error: {}", e));
+                }
+            }
+        }
+
+        let report = workspace.validate_cached(validator, &self.cache);
+        let describe = |finding: &Finding, file: Option<&WorkspaceFile>| {
+            let severity = finding.severity.as_str();
+            match file {
+                Some(file) => format!("{}: {}", severity, file.locate(finding)),
+                None => format!("{}: {}", severity, finding.message()),
+            }
+        };
+        let files = report
+            .files
+            .iter()
+            .zip(&workspace.files)
+            .map(|(file, parsed)| (&file.report, Some(parsed)));
+        for (report, file) in std::iter::once((&report.unattributed, None)).chain(files) {
+            for finding in report
+                .errors
+                .iter()
+                .chain(&report.warnings)
+                .chain(&report.infos)
+            {
+                findings.insert(describe(finding, file));
+            }
+        }
+        findings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const VALID: &str = r#"
+version: "1.0"
+requirements:
+  - summary: Login
+    requirements:
+      - Audit log
+"#;
+
+    #[test]
+    fn test_revalidate_reports_new_and_fixed_findings() {
+        let temp = TempDir::new().unwrap();
+        let auth = temp.path().join("auth.yml");
+        let audit = temp.path().join("audit.yml");
+        fs::write(&auth, VALID).unwrap();
+        fs::write(
+            &audit,
+            "version: \"1.0\"\nrequirements:\n  - summary: Audit logs\n",
+        )
+        .unwrap();
+        let mut validator = Validator::new().unwrap();
+        let mut session = WatchSession::new([&auth, &audit]);
+
+        let first = session.revalidate(&mut validator).unwrap();
+        assert_eq!(first.reparsed.len(), 2);
+        assert!(first
+            .new
+            .iter()
+            .any(|f| f.starts_with("error: ") && f.contains("Audit log")));
+        assert!(session.revalidate(&mut validator).is_none());
+
+        // Events without a change of content are ignored
+        assert!(session.changed(&audit));
+        assert!(!session.changed(&temp.path().join("notes.txt")));
+        assert!(session.revalidate(&mut validator).is_none());
+
+        // Fixing the summary in one file only rereads that file
+        fs::write(
+            &audit,
+            "version: \"1.0\"\nrequirements:\n  - summary: Audit log\n",
+        )
+        .unwrap();
+        session.changed(&audit);
+        let fixed = session.revalidate(&mut validator).unwrap();
+        assert_eq!(fixed.reparsed, vec![audit.clone()]);
+        assert!(fixed.new.is_empty());
+        assert!(!fixed.fixed.is_empty());
+        assert_eq!(fixed.total, session.findings().len());
+
+        // Syntax errors show up as findings of their own
+        fs::write(&audit, "requirements: [").unwrap();
+        session.changed(&audit);
+        let broken = session.revalidate(&mut validator).unwrap();
+        assert!(broken
+            .new
+            .iter()
+            .any(|f| f.starts_with("error: ") && f.contains("audit.yml")));
+    }
+
+    #[test]
+    fn test_revalidate_follows_the_archive() {
+        let temp = TempDir::new().unwrap();
+        let auth = temp.path().join("auth.yml");
+        fs::write(&auth, VALID).unwrap();
+        let mut validator = Validator::new().unwrap();
+        let mut session = WatchSession::new([&auth]);
+        let dangling = |delta: &FindingDelta| {
+            delta
+                .new
+                .iter()
+                .any(|f| f.starts_with("error: ") && f.contains("Audit log"))
+        };
+        assert!(dangling(&session.revalidate(&mut validator).unwrap()));
+
+        // Archiving the referenced requirement resolves the reference
+        fs::create_dir(temp.path().join("archive")).unwrap();
+        fs::write(
+            temp.path().join("archive/auth.yml"),
+            "version: \"1.0\"\nrequirements:\n  - summary: Audit log\n    status: deprecated\n",
+        )
+        .unwrap();
+        assert!(session.changed(&temp.path().join("archive/auth.yml")));
+        let archived = session.revalidate(&mut validator).unwrap();
+        assert!(archived.reparsed.is_empty());
+        assert!(archived.fixed.iter().any(|f| f.contains("non-existent")));
+    }
+
+    #[test]
+    fn test_run_follows_file_events() {
+        let temp = TempDir::new().unwrap();
+        let auth = temp.path().join("auth.yml");
+        fs::write(&auth, VALID).unwrap();
+        let mut validator = Validator::new().unwrap();
+        let mut session = WatchSession::new([&auth]);
+
+        let writer = {
+            let auth = auth.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(200));
+                fs::write(
+                    &auth,
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n",
+                )
+                .unwrap();
+            })
+        };
+        let mut deltas = vec![];
+        session
+            .run(&mut validator, |delta| {
+                deltas.push(delta.clone());
+                deltas.len() < 2
+            })
+            .unwrap();
+        writer.join().unwrap();
+
+        assert!(!deltas[0].new.is_empty());
+        assert_eq!(deltas[1].reparsed, vec![auth]);
+        assert!(deltas[1].fixed.iter().any(|f| f.contains("Audit log")));
+    }
+}
@@ -0,0 +1,571 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Validation of requirements spread over several files
+//!
+//! Each file is parsed on its own, but rules run over the requirements of
+//! every file together: references resolve across files, and summaries and
+//! names must be unique in the whole workspace.
+//!
+//! With the `parallel` feature, files are parsed, checked against the
+//! schema and checked by the rules looking at each requirement on its own
+//! concurrently, and the rules needing every file run concurrently with one
+//! another. Findings are reported in the same order either way.
+//!
+//! A [`Monorepo`] holds several projects, each with its own `.rqm`
+//! directory, and turns them into one workspace.
+
+use crate::validator::{rule, Finding, Severity, ValidationCache, ValidationReport, Validator};
+use crate::{Error, Parser, RequirementConfig, Result};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod projects;
+
+pub use projects::{
+    Monorepo, Project, ProjectEntry, ProjectManifest, ProjectScope, PROJECTS_FILE,
+    PROJECT_SEPARATOR,
+};
+
+const DUPLICATE_SUMMARY_RULE: &str = "duplicate-summary";
+const DUPLICATE_NAME_RULE: &str = "duplicate-name";
+
+/// A parsed requirements file
+#[derive(Debug, Clone)]
+pub struct WorkspaceFile {
+    pub path: PathBuf,
+
+    /// Raw file content, used to locate findings
+    pub source: String,
+
+    pub config: RequirementConfig,
+
+    /// Project qualifying the summaries of this file in a [`Monorepo`]
+    pub namespace: Option<String>,
+}
+
+impl WorkspaceFile {
+    /// Parse a file's content, naming the file in any error
+    pub fn parse(path: impl Into<PathBuf>, source: String) -> Result<Self> {
+        let path = path.into();
+        let config = Parser::parse_str(&source)
+            .map_err(|e| Error::custom(format!("{}: {}", path.display(), e)))?;
+        Ok(Self {
+            path,
+            source,
+            config,
+            namespace: None,
+        })
+    }
+
+    /// 1-based line of a requirement's summary in this file
+    pub fn line(&self, summary: &str) -> Option<usize> {
+        let summary = match &self.namespace {
+            Some(namespace) => summary
+                .strip_prefix(namespace.as_str())
+                .and_then(|rest| rest.strip_prefix(PROJECT_SEPARATOR))
+                .unwrap_or(summary),
+            None => summary,
+        };
+        Parser::summary_line(&self.source, summary)
+    }
+
+    /// A finding's message prefixed by this file and located by line, e.g.
+    /// `auth.yml: ... (line 12)`
+    pub fn locate(&self, finding: &Finding) -> String {
+        format!(
+            "{}: {}",
+            self.path.display(),
+            finding.located(|summary| self.line(summary))
+        )
+    }
+
+    /// Describe where a requirement is, e.g. `auth.yml line 12`
+    fn location(&self, summary: &str) -> String {
+        match self.line(summary) {
+            Some(line) => format!("{} line {}", self.path.display(), line),
+            None => self.path.display().to_string(),
+        }
+    }
+}
+
+/// Findings for one file of a workspace
+#[derive(Debug)]
+pub struct FileReport {
+    pub path: PathBuf,
+    pub report: ValidationReport,
+}
+
+/// Findings of a workspace validation, grouped by file
+#[derive(Debug, Default)]
+pub struct WorkspaceReport {
+    /// One report per file, in workspace order
+    pub files: Vec<FileReport>,
+
+    /// Findings that do not concern a particular requirement
+    pub unattributed: ValidationReport,
+}
+
+impl WorkspaceReport {
+    /// Check whether validation passed in every file
+    pub fn is_valid(&self) -> bool {
+        self.unattributed.is_valid() && self.files.iter().all(|file| file.report.is_valid())
+    }
+
+    /// Every finding in one report, e.g. to record a baseline
+    pub fn into_report(self) -> ValidationReport {
+        let mut report = self.unattributed;
+        for file in self.files {
+            report.suppressed += file.report.suppressed;
+            report.append(file.report);
+        }
+        report
+    }
+}
+
+/// Apply `f` to every item, concurrently with the `parallel` feature,
+/// keeping the results in item order
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T: Sync, U: Send>(items: &[T], f: impl Fn(&T) -> U + Sync + Send) -> Vec<U> {
+    items.par_iter().map(f).collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T, U>(items: &[T], f: impl Fn(&T) -> U) -> Vec<U> {
+    items.iter().map(f).collect()
+}
+
+/// A set of requirements files validated together
+#[derive(Debug, Clone, Default)]
+pub struct Workspace {
+    pub files: Vec<WorkspaceFile>,
+}
+
+impl Workspace {
+    /// Read and parse every file
+    pub fn load<I, P>(paths: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|path| path.as_ref().to_path_buf())
+            .collect();
+        // Collected before failing, so the error is always the first file's
+        let files = map(&paths, |path| {
+            let source = fs::read_to_string(path)
+                .map_err(|e| Error::custom(format!("{}: {}", path.display(), e)))?;
+            WorkspaceFile::parse(path, source)
+        });
+        Ok(Self {
+            files: files.into_iter().collect::<Result<_>>()?,
+        })
+    }
+
+    /// Combine every file into one configuration
+    ///
+    /// The version is taken from the first file.
+    pub fn merged(&self) -> RequirementConfig {
+        let mut merged = RequirementConfig {
+            version: self
+                .files
+                .first()
+                .map_or_else(|| "1.0".to_string(), |file| file.config.version.clone()),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![],
+        };
+        for file in &self.files {
+            merged.aliases.extend(file.config.aliases.iter().cloned());
+            merged
+                .templates
+                .extend(file.config.templates.iter().cloned());
+            merged
+                .requirements
+                .extend(file.config.requirements.iter().cloned());
+        }
+        merged
+    }
+
+    /// Index of the first file defining a requirement
+    fn file_of(&self) -> HashMap<&str, usize> {
+        let mut owners = HashMap::new();
+        for (i, file) in self.files.iter().enumerate() {
+            for req in file.config.all_requirements() {
+                owners.entry(req.summary.as_str()).or_insert(i);
+            }
+        }
+        owners
+    }
+
+    /// Validate every file against the requirements of the whole workspace
+    ///
+    /// Each finding is reported in the file defining its requirement;
+    /// duplicates are reported in the file of the later definition and name
+    /// both locations.
+    pub fn validate(&self, validator: &Validator) -> WorkspaceReport {
+        self.validate_with(validator, None)
+    }
+
+    /// Validate like [`Workspace::validate`], reusing cached findings
+    ///
+    /// The schema and the rules that look at each requirement on its own
+    /// are cached per file, so only changed files are checked again. The
+    /// other rules span files, so they run again whenever any file changed.
+    pub fn validate_cached(
+        &self,
+        validator: &Validator,
+        cache: &ValidationCache,
+    ) -> WorkspaceReport {
+        self.validate_with(validator, Some(cache))
+    }
+
+    fn validate_with(
+        &self,
+        validator: &Validator,
+        cache: Option<&ValidationCache>,
+    ) -> WorkspaceReport {
+        let cached = |key: String, check: &dyn Fn() -> ValidationReport| match cache {
+            Some(cache) => cache.get_or_insert_with(&key, check),
+            None => check(),
+        };
+        let mut report = WorkspaceReport {
+            files: self
+                .files
+                .iter()
+                .map(|file| FileReport {
+                    path: file.path.clone(),
+                    report: ValidationReport::default(),
+                })
+                .collect(),
+            unattributed: ValidationReport::default(),
+        };
+
+        // Files are checked against the schema and the local rules one by
+        // one, which needs no other file
+        let schemas = validator.schemas();
+        let mut merged = ValidationReport::default();
+        let checked = map(&self.files, |file| {
+            let key = format!(
+                "file\n{}{}",
+                file.source,
+                validator.cache_state(&file.config)
+            );
+            cached(key, &|| {
+                let mut report = ValidationReport::default();
+                for finding in schemas.check_config(&file.config) {
+                    report.push(finding);
+                }
+                validator.check_local_rules(&file.config, &mut report);
+                report
+            })
+        });
+        for file_report in checked {
+            merged.append(file_report);
+        }
+        let sources: Vec<&str> = self.files.iter().map(|file| file.source.as_str()).collect();
+        let config = self.merged();
+        let key = format!(
+            "rules\n{}{}",
+            sources.join("\0"),
+            validator.cache_state(&config)
+        );
+        let rules = cached(key, &|| {
+            let mut report = ValidationReport::default();
+            validator.check_global_rules(&config, &mut report);
+            report
+        });
+        // Duplicates are reported with the files involved instead
+        for finding in rules
+            .errors
+            .into_iter()
+            .chain(rules.warnings)
+            .chain(rules.infos)
+        {
+            if finding.rule != DUPLICATE_SUMMARY_RULE && finding.rule != DUPLICATE_NAME_RULE {
+                merged.push(finding);
+            }
+        }
+        let mut duplicate_files = HashMap::new();
+        for (i, finding) in self.duplicates(validator) {
+            duplicate_files.insert((finding.rule.clone(), finding.message()), i);
+            merged.push(finding);
+        }
+
+        validator.apply_baseline(&mut merged);
+        report.unattributed.suppressed = merged.suppressed;
+        let owners = self.file_of();
+        let findings = merged
+            .errors
+            .into_iter()
+            .chain(merged.warnings)
+            .chain(merged.infos);
+        for finding in findings {
+            let file = match duplicate_files.get(&(finding.rule.clone(), finding.message())) {
+                Some(&i) => Some(i),
+                None => finding
+                    .requirement
+                    .as_deref()
+                    .and_then(|s| owners.get(s).copied()),
+            };
+            match file {
+                Some(i) => report.files[i].report.push(finding),
+                None => report.unattributed.push(finding),
+            }
+        }
+
+        report
+    }
+
+    /// Duplicate summaries and names, with the index of the file to report each in
+    fn duplicates(&self, validator: &Validator) -> Vec<(usize, Finding)> {
+        let severity = |id| rule(id).and_then(|rule| validator.severity(rule));
+        let mut duplicates = vec![];
+
+        if let Some(severity) = severity(DUPLICATE_SUMMARY_RULE) {
+            let mut seen: HashMap<&str, usize> = HashMap::new();
+            for (i, file) in self.files.iter().enumerate() {
+                for req in file.config.all_requirements() {
+                    let Some(&first) = seen.get(req.summary.as_str()) else {
+                        seen.insert(&req.summary, i);
+                        continue;
+                    };
+                    let message = if first == i {
+                        format!(
+                            "'{}' is defined twice in {}",
+                            req.summary,
+                            file.path.display()
+                        )
+                    } else {
+                        format!(
+                            "'{}' in {} is already defined in {}",
+                            req.summary,
+                            file.location(&req.summary),
+                            self.files[first].location(&req.summary)
+                        )
+                    };
+                    duplicates.push((
+                        i,
+                        finding(
+                            DUPLICATE_SUMMARY_RULE,
+                            severity,
+                            Error::DuplicateSummary(message),
+                            &req.summary,
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if let Some(severity) = severity(DUPLICATE_NAME_RULE) {
+            let mut seen: HashMap<&str, (usize, &str)> = HashMap::new();
+            for (i, file) in self.files.iter().enumerate() {
+                for req in file.config.all_requirements() {
+                    let Some(name) = req.name.as_deref() else {
+                        continue;
+                    };
+                    let Some(&(first, first_summary)) = seen.get(name) else {
+                        seen.insert(name, (i, &req.summary));
+                        continue;
+                    };
+                    let message = format!(
+                        "Requirement '{}' in {} reuses the name '{}' of '{}' in {}",
+                        req.summary,
+                        file.location(&req.summary),
+                        name,
+                        first_summary,
+                        self.files[first].location(first_summary)
+                    );
+                    duplicates.push((
+                        i,
+                        finding(
+                            DUPLICATE_NAME_RULE,
+                            severity,
+                            Error::custom(message),
+                            &req.summary,
+                        ),
+                    ));
+                }
+            }
+        }
+
+        duplicates
+    }
+}
+
+fn finding(rule: &str, severity: Severity, error: Error, requirement: &str) -> Finding {
+    Finding {
+        rule: rule.to_string(),
+        severity,
+        error,
+        requirement: Some(requirement.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::Baseline;
+    use tempfile::TempDir;
+
+    fn file(path: &str, source: &str) -> WorkspaceFile {
+        WorkspaceFile::parse(path, source.to_string()).unwrap()
+    }
+
+    fn messages(report: &ValidationReport) -> Vec<String> {
+        report.errors.iter().map(Finding::message).collect()
+    }
+
+    #[test]
+    fn test_references_resolve_across_files() {
+        let workspace = Workspace {
+            files: vec![
+                file(
+                    "auth.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements: [Audit log]\n    superseded_by: Single sign-on\n",
+                ),
+                file(
+                    "audit.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Audit log\n  - summary: Single sign-on\n  - summary: Export\n    requirements: [Missing]\n",
+                ),
+            ],
+        };
+
+        // Each file alone has dangling references
+        let validator = Validator::new().unwrap();
+        assert!(!validator.validate(&workspace.files[0].config).is_valid());
+
+        let report = workspace.validate(&validator);
+        assert!(messages(&report.files[0].report).is_empty());
+        assert_eq!(
+            messages(&report.files[1].report),
+            vec!["Invalid reference: Requirement 'Export' references non-existent 'Missing'"]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn test_duplicates_name_both_files() {
+        let workspace = Workspace {
+            files: vec![
+                file(
+                    "a.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n    name: REQ-1\n",
+                ),
+                file(
+                    "b.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Logout\n    name: REQ-1\n  - summary: Login\n",
+                ),
+            ],
+        };
+
+        let report = workspace.validate(&Validator::new().unwrap());
+        assert!(report.files[0].report.is_valid());
+        assert_eq!(
+            messages(&report.files[1].report),
+            vec![
+                "Duplicate summary: 'Login' in b.yml line 5 is already defined in a.yml line 3",
+                "Requirement 'Logout' in b.yml line 3 reuses the name 'REQ-1' of 'Login' in a.yml line 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_baseline_hides_duplicates() {
+        let temp = TempDir::new().unwrap();
+        let workspace = Workspace {
+            files: vec![
+                file(
+                    "a.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n",
+                ),
+                file(
+                    "b.yml",
+                    "version: \"1.0\"\nrequirements:\n  - summary: Login\n",
+                ),
+            ],
+        };
+        let report = workspace.validate(&Validator::new().unwrap());
+        assert_eq!(messages(&report.files[1].report).len(), 1);
+
+        let path = temp.path().join("baseline.json");
+        Baseline::from_report(&report.files[1].report, None)
+            .unwrap()
+            .save(&path)
+            .unwrap();
+        let validator = Validator::new().unwrap().with_baseline(&path).unwrap();
+        let report = workspace.validate(&validator);
+        assert!(report.is_valid());
+        assert_eq!(report.unattributed.suppressed, 1);
+    }
+
+    #[test]
+    fn test_cached_validation_matches_uncached() {
+        let temp = TempDir::new().unwrap();
+        let workspace = Workspace {
+            files: vec![
+                file("a.yml", "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements: [Missing]\n"),
+                file("b.yml", &format!("version: \"1.0\"\nrequirements:\n  - summary: {}\n", "x".repeat(201))),
+            ],
+        };
+        let validator = Validator::new().unwrap();
+        let cache = ValidationCache::open(temp.path(), &validator).unwrap();
+
+        let expected = workspace.validate(&validator);
+        for _ in 0..2 {
+            let report = workspace.validate_cached(&validator, &cache);
+            for (cached, uncached) in report.files.iter().zip(&expected.files) {
+                assert_eq!(messages(&cached.report), messages(&uncached.report));
+            }
+        }
+        assert_eq!(messages(&expected.files[0].report).len(), 1);
+        assert_eq!(messages(&expected.files[1].report).len(), 1);
+        assert_eq!(fs::read_dir(temp.path().join("cache")).unwrap().count(), 5);
+    }
+
+    #[test]
+    fn test_load_names_failing_file() {
+        let temp = TempDir::new().unwrap();
+        let good = temp.path().join("good.yml");
+        let bad = temp.path().join("bad.yml");
+        fs::write(&good, "version: \"1.0\"\nrequirements:\n  - summary: A\n").unwrap();
+        fs::write(&bad, "version: \"1.0\"\nrequirements: [[\n").unwrap();
+
+        assert_eq!(Workspace::load([&good]).unwrap().files.len(), 1);
+        let error = Workspace::load([&good, &bad]).unwrap_err().to_string();
+        assert!(error.contains("bad.yml"));
+
+        let worse = temp.path().join("worse.yml");
+        fs::write(&worse, "requirements: {\n").unwrap();
+        let error = Workspace::load([&good, &bad, &worse])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("bad.yml"));
+    }
+
+    #[test]
+    fn test_schema_findings_keep_file_order() {
+        let files = (0..16)
+            .map(|i| {
+                let source = format!(
+                    "version: \"1.0\"\nrequirements:\n  - summary: R{}{}\n",
+                    i,
+                    "x".repeat(200)
+                );
+                file(&format!("{}.yml", i), &source)
+            })
+            .collect();
+
+        let report = Workspace { files }.validate(&Validator::new().unwrap());
+        for (i, file) in report.files.iter().enumerate() {
+            let errors = messages(&file.report);
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].contains(&format!("'R{}x", i)), "{}", errors[0]);
+        }
+    }
+}
@@ -0,0 +1,705 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Several projects in one repository
+//!
+//! A monorepo lists its projects in `rqm-projects.yml` at its root. Each
+//! project is a directory with its own `.rqm` metadata, ID prefix and
+//! requirements files:
+//!
+//! ```yaml
+//! projects:
+//!   - path: services/auth
+//!   - path: services/billing
+//!     name: billing
+//!     files: [requirements.yml, invoices.yml]
+//! ```
+//!
+//! Requirements name those of another project as `project:summary`, or
+//! `project:REQ-ID` by `name` or generated ID. [`Monorepo::validate`]
+//! checks each project with its own `.rqm` settings and then the references
+//! between projects. A project validated on its own finds the manifest
+//! above it and leaves references into other projects to that check; see
+//! [`ProjectScope`].
+
+use super::{finding, FileReport, Workspace, WorkspaceReport};
+use crate::metadata::MetadataStore;
+use crate::types::{Requirement, RequirementConfig, RequirementReference};
+use crate::validator::{rule, ValidationReport, Validator};
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Manifest listing the projects of a monorepo
+pub const PROJECTS_FILE: &str = "rqm-projects.yml";
+
+/// Separator between a project and the requirement it contains
+pub const PROJECT_SEPARATOR: char = ':';
+
+const CHILD_RULE: &str = "child-reference";
+const SUPERSESSION_RULE: &str = "supersession-link";
+
+/// Contents of [`PROJECTS_FILE`]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectManifest {
+    pub projects: Vec<ProjectEntry>,
+}
+
+/// A project listed in the manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectEntry {
+    /// Directory of the project, relative to the manifest
+    pub path: PathBuf,
+
+    /// Name other projects use in references; defaults to the directory name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Requirements files, relative to the project directory
+    #[serde(default = "default_files")]
+    pub files: Vec<PathBuf>,
+}
+
+fn default_files() -> Vec<PathBuf> {
+    vec![PathBuf::from("requirements.yml")]
+}
+
+impl ProjectEntry {
+    /// The project's name, or that of its directory
+    pub fn name(&self) -> Option<String> {
+        self.name.clone().or_else(|| {
+            let name = self.path.file_name()?.to_str()?;
+            (name != "." && name != "..").then(|| name.to_string())
+        })
+    }
+}
+
+/// Where a project sits among the others of its monorepo
+///
+/// References into other projects are left to [`Monorepo::validate`], so
+/// validating or exporting one project does not report them as dangling.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ProjectScope {
+    /// Name of the project
+    pub name: String,
+
+    /// Names of the other projects
+    pub others: Vec<String>,
+}
+
+impl ProjectScope {
+    /// The scope of the project in `root`, if a manifest in it or one of
+    /// its parents lists it
+    pub fn find(root: &Path) -> Result<Option<Self>> {
+        let root = if root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            root
+        };
+        let Ok(root) = root.canonicalize() else {
+            return Ok(None);
+        };
+        let Some(manifest) = root
+            .ancestors()
+            .map(|dir| dir.join(PROJECTS_FILE))
+            .find(|manifest| manifest.is_file())
+        else {
+            return Ok(None);
+        };
+        let base = manifest.parent().unwrap_or_else(|| Path::new("."));
+        let content = fs::read_to_string(&manifest)?;
+        let entries: ProjectManifest = serde_yaml::from_str(&content)?;
+
+        let mut name = None;
+        let mut others = vec![];
+        for entry in &entries.projects {
+            let Some(entry_name) = entry.name() else {
+                continue;
+            };
+            let path = base.join(&entry.path);
+            if name.is_none() && path.canonicalize().is_ok_and(|path| path == root) {
+                name = Some(entry_name);
+            } else {
+                others.push(entry_name);
+            }
+        }
+        Ok(name.map(|name| Self { name, others }))
+    }
+
+    /// The requirement of this project that `reference` names, without the
+    /// project's own prefix, or `None` if it names one of another project
+    pub fn local<'a>(&self, reference: &'a str) -> Option<&'a str> {
+        match reference.split_once(PROJECT_SEPARATOR) {
+            Some((project, target)) if project == self.name => Some(target),
+            Some((project, _)) if self.others.iter().any(|other| other == project) => None,
+            _ => Some(reference),
+        }
+    }
+
+    /// `config` on its own: references with the project's prefix lose it,
+    /// and those into other projects are dropped
+    ///
+    /// Summaries are kept as they are, so one containing the separator is
+    /// never mistaken for a reference.
+    pub fn localize(&self, mut config: RequirementConfig) -> RequirementConfig {
+        let summaries: HashSet<String> = config
+            .all_requirements()
+            .into_iter()
+            .map(|req| req.summary.clone())
+            .collect();
+        let local = |reference: &str| -> Option<String> {
+            if summaries.contains(reference) {
+                return Some(reference.to_string());
+            }
+            self.local(reference).map(str::to_string)
+        };
+
+        let mut stack: Vec<&mut Requirement> = config.requirements.iter_mut().collect();
+        while let Some(req) = stack.pop() {
+            req.superseded_by = req.superseded_by.as_deref().and_then(local);
+            req.supersedes = req.supersedes.iter().filter_map(|s| local(s)).collect();
+            let children = std::mem::take(&mut req.requirements);
+            req.requirements = children
+                .into_iter()
+                .filter_map(|child| match child {
+                    RequirementReference::Reference(summary) => {
+                        local(&summary).map(RequirementReference::Reference)
+                    }
+                    full => Some(full),
+                })
+                .collect();
+            for child in &mut req.requirements {
+                if let RequirementReference::Full(child) = child {
+                    stack.push(child);
+                }
+            }
+        }
+        config
+    }
+}
+
+/// A project of a monorepo
+pub struct Project {
+    pub name: String,
+
+    /// Directory holding the project's `.rqm` directory
+    pub root: PathBuf,
+
+    pub workspace: Workspace,
+
+    /// Metadata of the project, if it has a `.rqm/config.yml`
+    pub store: Option<MetadataStore>,
+}
+
+impl Project {
+    /// The project's `.rqm` directory, holding its settings
+    pub fn rqm_dir(&self) -> PathBuf {
+        self.root.join(".rqm")
+    }
+
+    /// Prefix of the project's generated IDs
+    pub fn prefix(&self) -> Option<&str> {
+        self.store
+            .as_ref()
+            .map(|store| store.project_config().project_prefix.as_str())
+    }
+
+    /// `summary` qualified by the project, e.g. `auth:Login`
+    pub fn qualify(&self, summary: &str) -> String {
+        format!("{}{}{}", self.name, PROJECT_SEPARATOR, summary)
+    }
+}
+
+/// The projects of a repository, loaded together
+pub struct Monorepo {
+    pub projects: Vec<Project>,
+}
+
+impl Monorepo {
+    /// Load every project listed in a manifest
+    ///
+    /// Fails if two projects share a name or ID prefix, since references
+    /// and generated IDs would then be ambiguous.
+    pub fn load(manifest: impl AsRef<Path>) -> Result<Self> {
+        let manifest = manifest.as_ref();
+        let base = manifest
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let content = fs::read_to_string(manifest)?;
+        let entries: ProjectManifest = serde_yaml::from_str(&content)?;
+
+        let mut projects: Vec<Project> = vec![];
+        for entry in entries.projects {
+            let root = base.join(&entry.path);
+            let name = entry.name().ok_or_else(|| {
+                Error::custom(format!(
+                    "Project '{}' in {} needs a name",
+                    entry.path.display(),
+                    manifest.display()
+                ))
+            })?;
+            if name.is_empty()
+                || name.contains(PROJECT_SEPARATOR)
+                || name.contains(char::is_whitespace)
+            {
+                return Err(Error::custom(format!(
+                    "Project name '{}' must not be empty or contain '{}' or spaces",
+                    name, PROJECT_SEPARATOR
+                )));
+            }
+            if projects.iter().any(|project| project.name == name) {
+                return Err(Error::custom(format!(
+                    "Project '{}' is listed twice in {}",
+                    name,
+                    manifest.display()
+                )));
+            }
+
+            let rqm_dir = root.join(".rqm");
+            let store = if rqm_dir.join("config.yml").exists() {
+                Some(MetadataStore::new(&rqm_dir)?)
+            } else {
+                None
+            };
+            let workspace = Workspace::load(entry.files.iter().map(|file| root.join(file)))?;
+            let project = Project {
+                name,
+                root,
+                workspace,
+                store,
+            };
+            if let Some(other) = projects
+                .iter()
+                .find(|other| other.prefix().is_some() && other.prefix() == project.prefix())
+            {
+                return Err(Error::custom(format!(
+                    "Projects '{}' and '{}' both use the ID prefix '{}'",
+                    other.name,
+                    project.name,
+                    project.prefix().unwrap_or_default()
+                )));
+            }
+            projects.push(project);
+        }
+        Ok(Self { projects })
+    }
+
+    /// Find a project by name
+    pub fn project(&self, name: &str) -> Option<&Project> {
+        self.projects.iter().find(|project| project.name == name)
+    }
+
+    /// Where `project` sits among the others
+    pub fn scope(&self, project: &Project) -> ProjectScope {
+        ProjectScope {
+            name: project.name.clone(),
+            others: self
+                .projects
+                .iter()
+                .filter(|other| other.name != project.name)
+                .map(|other| other.name.clone())
+                .collect(),
+        }
+    }
+
+    /// Resolve a reference made in the project named `from`
+    ///
+    /// `Login` names a requirement of `from` itself, `billing:Invoice` or
+    /// `billing:BIL-007` one of the `billing` project. A requirement of
+    /// `from` whose summary contains the separator, such as `billing: fees`,
+    /// is found before any project. Returns the qualified summary of the
+    /// requirement, or `None` if there is no such project or requirement.
+    pub fn resolve(&self, from: &str, reference: &str) -> Result<Option<String>> {
+        self.resolve_in(&self.indexes(), from, reference)
+    }
+
+    fn resolve_in(
+        &self,
+        indexes: &HashMap<&str, ProjectIndex>,
+        from: &str,
+        reference: &str,
+    ) -> Result<Option<String>> {
+        if let Some(summary) = indexes.get(from).map(|own| own.resolve(reference)) {
+            if let Some(summary) = summary? {
+                return Ok(Some(summary));
+            }
+        }
+        let (project, target) = self.split(from, reference);
+        match indexes.get(project) {
+            Some(index) => index.resolve(target),
+            None => Ok(None),
+        }
+    }
+
+    /// Validate every project with its own settings, then the references
+    /// between projects
+    ///
+    /// Each project is checked by a [`Validator::for_project`] on its `.rqm`
+    /// directory, passed through `configure`, e.g. to add a profile, so its
+    /// lint settings, metadata and baseline apply to its summaries as
+    /// written. Files are reported in project order.
+    pub fn validate(
+        &self,
+        mut configure: impl FnMut(&Project, Validator) -> Result<Validator>,
+    ) -> Result<WorkspaceReport> {
+        let indexes = self.indexes();
+        let mut report = WorkspaceReport::default();
+        for project in &self.projects {
+            let validator =
+                Validator::for_project(&project.rqm_dir())?.with_project_scope(self.scope(project));
+            let validator = configure(project, validator)?;
+            let mut checked = project.workspace.validate(&validator);
+            self.check_links(project, &indexes, &validator, &mut checked.files)?;
+            report.unattributed.suppressed += checked.unattributed.suppressed;
+            report.unattributed.append(checked.unattributed);
+            report.files.extend(checked.files);
+        }
+        Ok(report)
+    }
+
+    /// Report the references of `project` into other projects that do not
+    /// resolve, with the project's rule levels and baseline
+    fn check_links(
+        &self,
+        project: &Project,
+        indexes: &HashMap<&str, ProjectIndex>,
+        validator: &Validator,
+        reports: &mut [FileReport],
+    ) -> Result<()> {
+        for (file, file_report) in project.workspace.files.iter().zip(reports) {
+            let mut dangling = ValidationReport::default();
+            for req in file.config.all_requirements() {
+                let children = req.requirements.iter().filter_map(|child| match child {
+                    RequirementReference::Reference(summary) => Some((CHILD_RULE, summary)),
+                    RequirementReference::Full(_) => None,
+                });
+                let links = req
+                    .superseded_by
+                    .iter()
+                    .chain(&req.supersedes)
+                    .map(|target| (SUPERSESSION_RULE, target));
+                for (id, reference) in children.chain(links) {
+                    // References within the project were checked with it
+                    let other = self.split(&project.name, reference).0;
+                    if other == project.name
+                        || self
+                            .resolve_in(indexes, &project.name, reference)?
+                            .is_some()
+                    {
+                        continue;
+                    }
+                    let Some(severity) = rule(id).and_then(|rule| validator.severity(rule)) else {
+                        continue;
+                    };
+                    let message = if id == CHILD_RULE {
+                        format!(
+                            "Requirement '{}' references non-existent '{}'",
+                            req.summary, reference
+                        )
+                    } else {
+                        format!(
+                            "Requirement '{}' has supersession link to non-existent '{}'",
+                            req.summary, reference
+                        )
+                    };
+                    dangling.push(finding(
+                        id,
+                        severity,
+                        Error::InvalidReference(message),
+                        &req.summary,
+                    ));
+                }
+            }
+            validator.apply_baseline(&mut dangling);
+            file_report.report.suppressed += dangling.suppressed;
+            file_report.report.append(dangling);
+        }
+        Ok(())
+    }
+
+    /// Every project as one workspace, with summaries and references
+    /// qualified by project
+    ///
+    /// References that do not resolve are qualified as written. Qualified
+    /// summaries match neither the projects' metadata nor their baselines,
+    /// so use [`Monorepo::validate`] to check the projects.
+    pub fn workspace(&self) -> Result<Workspace> {
+        let indexes = self.indexes();
+        let qualify = |from: &Project, reference: &str| -> Result<String> {
+            let resolved = self.resolve_in(&indexes, &from.name, reference)?;
+            Ok(resolved.unwrap_or_else(|| {
+                let (name, target) = self.split(&from.name, reference);
+                format!("{}{}{}", name, PROJECT_SEPARATOR, target)
+            }))
+        };
+
+        let mut files = vec![];
+        for project in &self.projects {
+            for file in &project.workspace.files {
+                let mut file = file.clone();
+                file.namespace = Some(project.name.clone());
+                let mut stack: Vec<&mut Requirement> =
+                    file.config.requirements.iter_mut().collect();
+                while let Some(req) = stack.pop() {
+                    req.summary = project.qualify(&req.summary);
+                    if let Some(target) = &req.superseded_by {
+                        req.superseded_by = Some(qualify(project, target)?);
+                    }
+                    for target in &mut req.supersedes {
+                        *target = qualify(project, target)?;
+                    }
+                    for child in &mut req.requirements {
+                        match child {
+                            RequirementReference::Reference(summary) => {
+                                *summary = qualify(project, summary)?;
+                            }
+                            RequirementReference::Full(child) => stack.push(child),
+                        }
+                    }
+                }
+                files.push(file);
+            }
+        }
+        Ok(Workspace { files })
+    }
+
+    fn indexes(&self) -> HashMap<&str, ProjectIndex<'_>> {
+        self.projects
+            .iter()
+            .map(|project| (project.name.as_str(), ProjectIndex::new(project)))
+            .collect()
+    }
+
+    /// Split a reference into the project it names and the rest, defaulting
+    /// to `from` when it names no known project
+    fn split<'a>(&self, from: &'a str, reference: &'a str) -> (&'a str, &'a str) {
+        match reference.split_once(PROJECT_SEPARATOR) {
+            Some((project, target)) if self.project(project).is_some() => (project, target),
+            _ => (from, reference),
+        }
+    }
+}
+
+/// Lookups of the requirements of one project
+struct ProjectIndex<'a> {
+    project: &'a Project,
+    summaries: HashSet<&'a str>,
+    names: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> ProjectIndex<'a> {
+    fn new(project: &'a Project) -> Self {
+        let mut summaries = HashSet::new();
+        let mut names = HashMap::new();
+        for file in &project.workspace.files {
+            for req in file.config.all_requirements() {
+                summaries.insert(req.summary.as_str());
+                if let Some(name) = &req.name {
+                    names.entry(name.as_str()).or_insert(req.summary.as_str());
+                }
+            }
+        }
+        Self {
+            project,
+            summaries,
+            names,
+        }
+    }
+
+    /// Qualified summary of the requirement with this summary, name or
+    /// generated ID
+    fn resolve(&self, target: &str) -> Result<Option<String>> {
+        let summary = if self.summaries.contains(target) {
+            Some(target.to_string())
+        } else if let Some(summary) = self.names.get(target) {
+            Some(summary.to_string())
+        } else {
+            match &self.project.store {
+                Some(store) => store
+                    .find_by_generated_id(target)?
+                    .map(|meta| meta.summary)
+                    .filter(|summary| self.summaries.contains(summary.as_str())),
+                None => None,
+            }
+        };
+        Ok(summary.map(|summary| self.project.qualify(&summary)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Parser, Validator};
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn monorepo(temp: &TempDir) -> Monorepo {
+        write(
+            temp.path(),
+            PROJECTS_FILE,
+            "projects:\n  - path: services/auth\n  - path: services/billing\n    name: pay\n    files: [billing.yml]\n",
+        );
+        write(
+            temp.path(),
+            "services/auth/requirements.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements: [Session, pay:PAY-001, pay:Refunds]\n  - summary: Session\n",
+        );
+        write(
+            temp.path(),
+            "services/billing/billing.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Invoice\n    requirements: [auth:Login]\n  - summary: Login\n",
+        );
+        let mut store =
+            MetadataStore::init(temp.path().join("services/billing/.rqm"), "PAY".to_string())
+                .unwrap();
+        let invoice = Parser::parse_str("version: \"1.0\"\nrequirements:\n  - summary: Invoice\n")
+            .unwrap()
+            .requirements
+            .remove(0);
+        store.get_or_create_metadata(&invoice).unwrap();
+        Monorepo::load(temp.path().join(PROJECTS_FILE)).unwrap()
+    }
+
+    #[test]
+    fn test_cross_project_references_resolve() {
+        let temp = TempDir::new().unwrap();
+        let repo = monorepo(&temp);
+        assert_eq!(repo.projects[0].name, "auth");
+        assert_eq!(repo.projects[1].prefix(), Some("PAY"));
+        assert_eq!(
+            repo.resolve("auth", "pay:PAY-001").unwrap().as_deref(),
+            Some("pay:Invoice")
+        );
+        assert_eq!(
+            repo.resolve("pay", "Login").unwrap().as_deref(),
+            Some("pay:Login")
+        );
+        assert_eq!(repo.resolve("auth", "pay:Refunds").unwrap(), None);
+
+        let workspace = repo.workspace().unwrap();
+        let login = &workspace.files[0].config.requirements[0];
+        assert_eq!(login.summary, "auth:Login");
+        let children: Vec<_> = login.requirements.iter().map(|c| c.summary()).collect();
+        assert_eq!(children, vec!["auth:Session", "pay:Invoice", "pay:Refunds"]);
+        assert_eq!(workspace.files[1].line("pay:Invoice"), Some(3));
+
+        // Summaries repeat across projects, but only the missing one is reported
+        let report = repo.validate(|_, validator| Ok(validator)).unwrap();
+        let errors: Vec<String> = report
+            .files
+            .iter()
+            .flat_map(|file| file.report.errors.iter().map(|f| f.message()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["Invalid reference: Requirement 'Login' references non-existent 'pay:Refunds'"]
+        );
+    }
+
+    #[test]
+    fn test_projects_are_validated_with_their_own_settings() {
+        let temp = TempDir::new().unwrap();
+        monorepo(&temp);
+        write(
+            temp.path(),
+            "services/billing/.rqm/lint.yml",
+            "rules:\n  child-reference: off\n",
+        );
+        write(
+            temp.path(),
+            "services/billing/billing.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Invoice\n    requirements: [auth:Logout, Missing]\n",
+        );
+        let repo = Monorepo::load(temp.path().join(PROJECTS_FILE)).unwrap();
+        let report = repo.validate(|_, validator| Ok(validator)).unwrap();
+        let errors: Vec<String> = report.files[1]
+            .report
+            .errors
+            .iter()
+            .map(|f| f.message())
+            .collect();
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        // Validated on its own, auth leaves references into pay to the monorepo
+        let auth = &repo.projects[0];
+        let validator = Validator::for_project(&auth.rqm_dir()).unwrap();
+        let report = auth.workspace.validate(&validator);
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_summaries_may_contain_the_separator() {
+        let temp = TempDir::new().unwrap();
+        monorepo(&temp);
+        write(
+            temp.path(),
+            "services/auth/requirements.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    requirements: [\"pay: fees\", \"auth:pay: fees\"]\n  - summary: \"pay: fees\"\n",
+        );
+        let repo = Monorepo::load(temp.path().join(PROJECTS_FILE)).unwrap();
+        assert_eq!(
+            repo.resolve("auth", "pay: fees").unwrap().as_deref(),
+            Some("auth:pay: fees")
+        );
+        assert!(repo
+            .validate(|_, validator| Ok(validator))
+            .unwrap()
+            .is_valid());
+
+        let scope = repo.scope(&repo.projects[0]);
+        assert_eq!(scope.local("pay:Invoice"), None);
+        assert_eq!(scope.local("auth:Login"), Some("Login"));
+        let config = scope.localize(repo.projects[0].workspace.files[0].config.clone());
+        let children: Vec<_> = config.requirements[0]
+            .requirements
+            .iter()
+            .map(|c| c.summary())
+            .collect();
+        assert_eq!(children, vec!["pay: fees", "pay: fees"]);
+    }
+
+    #[test]
+    fn test_ambiguous_projects_are_rejected() {
+        let temp = TempDir::new().unwrap();
+        write(
+            temp.path(),
+            PROJECTS_FILE,
+            "projects:\n  - path: a\n    name: core\n  - path: b\n    name: core\n",
+        );
+        write(
+            temp.path(),
+            "a/requirements.yml",
+            "version: \"1.0\"\nrequirements: []\n",
+        );
+        write(
+            temp.path(),
+            "b/requirements.yml",
+            "version: \"1.0\"\nrequirements: []\n",
+        );
+        let err = Monorepo::load(temp.path().join(PROJECTS_FILE))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("listed twice"));
+
+        write(
+            temp.path(),
+            PROJECTS_FILE,
+            "projects:\n  - path: a\n  - path: b\n",
+        );
+        MetadataStore::init(temp.path().join("a/.rqm"), "REQ".to_string()).unwrap();
+        MetadataStore::init(temp.path().join("b/.rqm"), "REQ".to_string()).unwrap();
+        let err = Monorepo::load(temp.path().join(PROJECTS_FILE))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("both use the ID prefix 'REQ'"));
+    }
+}
@@ -0,0 +1,333 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! A small XML reader for the formats rqm imports
+//!
+//! Builds a tree of elements without validating against any schema.
+//! Namespace prefixes are dropped from element and attribute names. Text is
+//! kept as written, whitespace included; readers of a format trim it where
+//! the format makes whitespace insignificant.
+
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+
+/// Deepest nesting of elements accepted, so that walking the tree of an
+/// untrusted document cannot overflow the stack
+const MAX_DEPTH: usize = 256;
+
+/// An XML element with its attributes and children
+///
+/// Text is kept as unnamed children, so mixed content stays in order.
+#[derive(Debug, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: HashMap<String, String>,
+    pub(crate) children: Vec<Element>,
+    pub(crate) text: Option<String>,
+}
+
+impl Element {
+    fn text(text: &str) -> Self {
+        Self {
+            text: Some(text.to_string()),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Child elements, without the text between them
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter(|child| child.text.is_none())
+    }
+
+    pub(crate) fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    pub(crate) fn path(&self, names: &[&str]) -> Option<&Element> {
+        names
+            .iter()
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    /// All text below this element, as written
+    pub(crate) fn text_content(&self) -> String {
+        let mut parts = vec![];
+        self.collect_text(&mut parts);
+        parts.concat()
+    }
+
+    fn collect_text<'a>(&'a self, parts: &mut Vec<&'a str>) {
+        parts.extend(self.text.as_deref());
+        for child in &self.children {
+            child.collect_text(parts);
+        }
+    }
+
+    pub(crate) fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Element)) {
+        f(self);
+        for child in &self.children {
+            child.visit(f);
+        }
+    }
+}
+
+fn xml_error(position: usize, message: &str) -> Error {
+    Error::custom(format!("Invalid XML at byte {}: {}", position, message))
+}
+
+/// Drop a namespace prefix such as `xhtml:`
+pub(crate) fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Resolve the predefined entities and character references
+fn unescape(text: &str, position: usize) -> Result<String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find(';')
+            .ok_or_else(|| xml_error(position, "unterminated entity"))?;
+        let entity = &rest[start + 1..start + end];
+        let c = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix("#x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => entity.strip_prefix('#').and_then(|dec| dec.parse().ok()),
+            }
+            .and_then(char::from_u32),
+        };
+        let c = c.ok_or_else(|| xml_error(position, &format!("unknown entity '&{};'", entity)))?;
+        out.push(c);
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Parse a start tag without its angle brackets
+fn element(tag: &str, position: usize) -> Result<Element> {
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = Element {
+        name: local_name(&tag[..name_end]).to_string(),
+        ..Element::default()
+    };
+
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let invalid = || xml_error(position, "malformed attribute");
+        let eq = rest.find('=').ok_or_else(invalid)?;
+        let key = local_name(rest[..eq].trim()).to_string();
+        let value = rest[eq + 1..].trim_start();
+        let quote = value
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(invalid)?;
+        let close = value[1..].find(quote).ok_or_else(invalid)?;
+        // Literal line breaks in attributes are normalized to spaces
+        let raw = value[1..close + 1].replace(['\n', '\r', '\t'], " ");
+        element.attributes.insert(key, unescape(&raw, position)?);
+        rest = value[close + 2..].trim_start();
+    }
+    Ok(element)
+}
+
+/// Parse the elements, attributes and text of an XML document
+///
+/// Processing instructions, comments and document type declarations are
+/// skipped, and custom entities are not supported. Returns an unnamed
+/// element holding the top-level elements.
+pub(crate) fn parse_xml(xml: &str) -> Result<Element> {
+    let mut stack = vec![Element::default()];
+    let mut pos = 0;
+    while pos < xml.len() {
+        let tail = &xml[pos..];
+        let find = |pattern: &str| {
+            tail.find(pattern)
+                .ok_or_else(|| xml_error(pos, &format!("missing '{}'", pattern)))
+        };
+
+        if tail.starts_with("<?") {
+            pos += find("?>")? + 2;
+        } else if tail.starts_with("<!--") {
+            pos += find("-->")? + 3;
+        } else if let Some(data) = tail.strip_prefix("<![CDATA[") {
+            let end = find("]]>")?;
+            if let Some(element) = stack.last_mut() {
+                element.children.push(Element::text(&data[..end - 9]));
+            }
+            pos += end + 3;
+        } else if tail.starts_with("<!") {
+            pos += find(">")? + 1;
+        } else if let Some(closing) = tail.strip_prefix("</") {
+            let end = find(">")?;
+            let name = local_name(closing[..end - 2].trim());
+            let element = stack.pop().filter(|_| !stack.is_empty());
+            match (element, stack.last_mut()) {
+                (Some(element), Some(parent)) if element.name == name => {
+                    parent.children.push(element)
+                }
+                _ => return Err(xml_error(pos, &format!("unexpected '</{}>'", name))),
+            }
+            pos += end + 1;
+        } else if tail.starts_with('<') {
+            if stack.len() > MAX_DEPTH {
+                return Err(xml_error(
+                    pos,
+                    &format!("elements nested deeper than {}", MAX_DEPTH),
+                ));
+            }
+            let mut quote = None;
+            let end = tail
+                .char_indices()
+                .find(|&(_, c)| match quote {
+                    Some(q) if c == q => {
+                        quote = None;
+                        false
+                    }
+                    Some(_) => false,
+                    None if c == '"' || c == '\'' => {
+                        quote = Some(c);
+                        false
+                    }
+                    None => c == '>',
+                })
+                .map(|(i, _)| i)
+                .ok_or_else(|| xml_error(pos, "unterminated tag"))?;
+            let tag = &tail[1..end];
+            let empty = tag.ends_with('/');
+            let element = element(tag.trim_end_matches('/').trim(), pos)?;
+            if empty {
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(element);
+                }
+            } else {
+                stack.push(element);
+            }
+            pos += end + 1;
+        } else {
+            let end = tail.find('<').unwrap_or(tail.len());
+            let text = unescape(&tail[..end], pos)?;
+            if let Some(element) = stack.last_mut() {
+                element.children.push(Element::text(&text));
+            }
+            pos += end;
+        }
+    }
+
+    match stack.pop() {
+        Some(document) if stack.is_empty() => Ok(document),
+        _ => Err(xml_error(xml.len(), "unclosed element")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(xml: &str) -> Element {
+        parse_xml(xml).unwrap().children.remove(0)
+    }
+
+    #[test]
+    fn test_entities() {
+        let element = root("<p>&lt;a&gt; &amp; &quot;b&quot; &apos;c&apos; &#65;&#x42;</p>");
+        assert_eq!(element.text_content(), "<a> & \"b\" 'c' AB");
+
+        let err = parse_xml("<p>&nbsp;</p>").unwrap_err().to_string();
+        assert!(err.contains("unknown entity '&nbsp;'"), "{}", err);
+        assert!(parse_xml("<p>&amp</p>").is_err());
+        assert!(parse_xml("<p>&#xD800;</p>").is_err());
+    }
+
+    #[test]
+    fn test_cdata() {
+        let element = root("<p><![CDATA[<b>bold</b> & ]]>plain</p>");
+        assert_eq!(element.text_content(), "<b>bold</b> & plain");
+        assert!(element.elements().next().is_none());
+        assert!(parse_xml("<p><![CDATA[open</p>").is_err());
+    }
+
+    #[test]
+    fn test_attributes() {
+        let element = root(
+            "<x:item x:id=\"a&amp;b\" name='say \"hi\"' note=\"two\nlines\" empty=\"\" flag='a>b'/>",
+        );
+        assert_eq!(element.name, "item");
+        assert_eq!(element.attr("id"), Some("a&b"));
+        assert_eq!(element.attr("name"), Some("say \"hi\""));
+        assert_eq!(element.attr("note"), Some("two lines"));
+        assert_eq!(element.attr("empty"), Some(""));
+        assert_eq!(element.attr("flag"), Some("a>b"));
+        assert_eq!(element.attr("missing"), None);
+    }
+
+    #[test]
+    fn test_text_is_kept_as_written() {
+        let element = root("<p>  Press <b>Enter</b>\n  to\tsubmit  <i>now</i>ish </p>");
+        assert_eq!(
+            element.text_content(),
+            "  Press Enter\n  to\tsubmit  nowish "
+        );
+        let names: Vec<&str> = element.elements().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["b", "i"]);
+    }
+
+    #[test]
+    fn test_skips_declarations_and_comments() {
+        let document =
+            parse_xml("<?xml version=\"1.0\"?>\n<!DOCTYPE r>\n<!-- <ignored/> -->\n<r><a/></r>")
+                .unwrap();
+        let root = document.elements().next().unwrap();
+        assert_eq!(root.name, "r");
+        assert!(root.path(&["a"]).is_some());
+    }
+
+    #[test]
+    fn test_malformed() {
+        for xml in [
+            "<a>",
+            "<a></b>",
+            "</a>",
+            "<a><b></a></b>",
+            "<a",
+            "<a b></a>",
+            "<a b=c></a>",
+            "<a b=\"c></a>",
+            "<!-- open",
+            "<?xml",
+        ] {
+            assert!(parse_xml(xml).is_err(), "{}", xml);
+        }
+    }
+
+    #[test]
+    fn test_nesting_depth_is_limited() {
+        let nested = |depth: usize| "<a>".repeat(depth) + &"</a>".repeat(depth);
+        let document = parse_xml(&nested(MAX_DEPTH)).unwrap();
+        let mut depth = 0;
+        document.visit(&mut |_| depth += 1);
+        assert_eq!(depth, MAX_DEPTH + 1);
+
+        let err = parse_xml(&nested(MAX_DEPTH + 1)).unwrap_err().to_string();
+        assert!(err.contains("nested deeper than"), "{}", err);
+        // Deep enough to overflow the stack if it were walked recursively
+        assert!(parse_xml(&nested(1_000_000)).is_err());
+        assert!(parse_xml(&"<a/>".repeat(1_000)).is_ok());
+    }
+}
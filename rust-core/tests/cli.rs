@@ -0,0 +1,405 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Exit codes and output routing of `rqm-validator`
+//!
+//! Results go to stdout in the `--output` format and exit with 0, or 1 for
+//! invalid requirements; errors go to stderr and exit with 2. Commands that
+//! talk to other services (`notify`, `sync`, `pdf`, `hook`) are left to the
+//! acceptance tests.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use predicates::str::contains;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::process::Stdio;
+use tempfile::TempDir;
+
+const VALID: &str = r#"version: "1.0"
+requirements:
+  - summary: Login
+    description: Users sign in with a password
+    status: proposed
+  - summary: Logout
+    description: Users sign out
+    status: proposed
+    requirements:
+      - summary: Session expiry
+        status: draft
+"#;
+
+const DUPLICATE: &str = r#"version: "1.0"
+requirements:
+  - summary: First
+  - summary: First
+"#;
+
+const CYCLE: &str = r#"version: "1.0"
+requirements:
+  - summary: A
+    requirements:
+      - B
+  - summary: B
+    requirements:
+      - A
+"#;
+
+fn rqm(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("rqm-validator").unwrap();
+    cmd.current_dir(dir.path());
+    cmd
+}
+
+fn project(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for (name, content) in files {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+fn json(stdout: &[u8]) -> serde_json::Value {
+    serde_json::from_slice(stdout).unwrap()
+}
+
+#[test]
+fn test_validate_exit_codes() {
+    let dir = project(&[("valid.yml", VALID), ("duplicate.yml", DUPLICATE)]);
+
+    let valid = rqm(&dir).args(["validate", "valid.yml"]).assert().code(0);
+    assert_eq!(json(&valid.get_output().stdout)["valid"], true);
+
+    let invalid = rqm(&dir)
+        .args(["validate", "duplicate.yml"])
+        .assert()
+        .code(1)
+        .stderr("");
+    let result = json(&invalid.get_output().stdout);
+    assert_eq!(result["valid"], false);
+    assert_eq!(result["errors"][0], "Duplicate summary: First (line 3)");
+
+    // A file that cannot be read is an invalid result, not a crash
+    rqm(&dir)
+        .args(["validate", "missing.yml"])
+        .assert()
+        .code(1)
+        .stdout(contains("\"valid\": false"));
+}
+
+#[test]
+fn test_validate_output_formats() {
+    let dir = project(&[("duplicate.yml", DUPLICATE)]);
+
+    rqm(&dir)
+        .args(["--output", "text", "validate", "duplicate.yml"])
+        .assert()
+        .code(1)
+        .stdout(contains("error: Duplicate summary: First (line 3)\n"));
+    rqm(&dir)
+        .args(["--output", "yaml", "validate", "duplicate.yml"])
+        .assert()
+        .code(1)
+        .stdout(contains("valid: false\n"));
+
+    let sarif = rqm(&dir)
+        .args(["validate", "--sarif", "duplicate.yml"])
+        .assert()
+        .code(1);
+    let log = json(&sarif.get_output().stdout);
+    assert_eq!(log["version"], "2.1.0");
+    assert_eq!(
+        log["runs"][0]["artifacts"][0]["location"]["uri"],
+        "duplicate.yml"
+    );
+}
+
+#[test]
+fn test_baseline_hides_analysis_warnings() {
+    let dir = project(&[("valid.yml", VALID)]);
+
+    rqm(&dir)
+        .args(["validate", "valid.yml", "--baseline", "baseline.json"])
+        .arg("--update-baseline")
+        .assert()
+        .code(0)
+        .stdout("")
+        .stderr(contains("Recorded 4 baseline entries"));
+
+    // Includes the warnings of the analyses that run after the rules
+    let checked = rqm(&dir)
+        .args(["validate", "valid.yml", "--baseline", "baseline.json"])
+        .assert()
+        .code(0);
+    let result = json(&checked.get_output().stdout);
+    assert_eq!(result["warnings"], serde_json::json!([]));
+    assert_eq!(result["suppressed"], 4);
+
+    rqm(&dir)
+        .args(["validate", "valid.yml", "--baseline", "missing.json"])
+        .assert()
+        .code(2)
+        .stdout(contains("Baseline error"));
+}
+
+#[test]
+fn test_validate_workspace() {
+    let renamed = VALID
+        .replace("Login", "Sign in")
+        .replace("Logout", "Sign out")
+        .replace("Session expiry", "Idle timeout");
+    let dir = project(&[
+        ("a.yml", VALID),
+        ("b.yml", &renamed),
+        ("duplicate.yml", DUPLICATE),
+    ]);
+
+    let valid = rqm(&dir)
+        .args(["validate", "a.yml", "b.yml"])
+        .assert()
+        .code(0);
+    let warnings = json(&valid.get_output().stdout)["warnings"].clone();
+    assert!(warnings
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|warning| warning.as_str().unwrap().starts_with("b.yml: ")));
+
+    rqm(&dir)
+        .args(["validate", "a.yml", "duplicate.yml"])
+        .assert()
+        .code(1)
+        .stdout(contains("duplicate.yml: Duplicate summary: 'First'"));
+}
+
+#[test]
+fn test_errors_go_to_stderr() {
+    let dir = project(&[]);
+    rqm(&dir)
+        .args(["export", "missing.yml", "--format", "csv"])
+        .assert()
+        .code(2)
+        .stdout("")
+        .stderr(contains("Error: "));
+    rqm(&dir).assert().code(2);
+}
+
+#[test]
+fn test_cycles() {
+    let dir = project(&[("valid.yml", VALID), ("cycle.yml", CYCLE)]);
+    rqm(&dir)
+        .args(["--output", "text", "cycles", "valid.yml"])
+        .assert()
+        .code(0)
+        .stdout("No circular references\n");
+    rqm(&dir)
+        .args(["--output", "text", "cycles", "cycle.yml"])
+        .assert()
+        .code(1)
+        .stdout("cycle: A -> B\n");
+}
+
+#[test]
+fn test_graph_and_export() {
+    let dir = project(&[("valid.yml", VALID)]);
+    rqm(&dir)
+        .args(["graph", "valid.yml", "--format", "dot"])
+        .assert()
+        .code(0)
+        .stdout(predicate::str::starts_with("digraph requirements {"));
+    rqm(&dir)
+        .args(["export", "valid.yml", "--format", "csv"])
+        .assert()
+        .code(0)
+        .stdout(contains("Session expiry"));
+    rqm(&dir)
+        .args([
+            "--output",
+            "json",
+            "export",
+            "valid.yml",
+            "--format",
+            "config",
+        ])
+        .assert()
+        .code(0)
+        .stdout(contains("\"summary\": \"Login\""));
+}
+
+#[test]
+fn test_query_and_search() {
+    let dir = project(&[("valid.yml", VALID)]);
+    let query = rqm(&dir)
+        .args(["query", "valid.yml", "status==draft"])
+        .assert()
+        .code(0);
+    let requirements = json(&query.get_output().stdout)["requirements"].clone();
+    assert_eq!(requirements.as_array().unwrap().len(), 1);
+    assert_eq!(requirements[0]["summary"], "Session expiry");
+
+    rqm(&dir)
+        .args(["query", "valid.yml", "status=="])
+        .assert()
+        .code(2)
+        .stdout("");
+
+    let search = rqm(&dir)
+        .args(["search", "password", "valid.yml"])
+        .assert()
+        .code(0);
+    assert_eq!(
+        json(&search.get_output().stdout)["hits"][0]["summary"],
+        "Login"
+    );
+}
+
+#[test]
+fn test_diff_and_merge() {
+    let dir = project(&[
+        (
+            "base.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n",
+        ),
+        (
+            "ours.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: draft\n",
+        ),
+        (
+            "theirs.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    status: approved\n",
+        ),
+        (
+            "priority.yml",
+            "version: \"1.0\"\nrequirements:\n  - summary: Login\n    priority: high\n",
+        ),
+    ]);
+
+    let diff = rqm(&dir)
+        .args(["diff", "base.yml", "theirs.yml"])
+        .assert()
+        .code(0);
+    assert_eq!(
+        json(&diff.get_output().stdout)["modified"][0]["summary"],
+        "Login"
+    );
+
+    rqm(&dir)
+        .args(["merge", "base.yml", "base.yml", "priority.yml"])
+        .args(["--out", "merged.yml"])
+        .assert()
+        .code(0);
+    assert!(read(&dir, "merged.yml").contains("priority: high"));
+
+    // Conflicts are written between markers and exit with 1
+    let conflict = rqm(&dir)
+        .args(["merge", "base.yml", "ours.yml", "theirs.yml"])
+        .args(["--out", "conflict.yml"])
+        .assert()
+        .code(1);
+    assert_eq!(
+        json(&conflict.get_output().stdout)["conflicts"][0]["field"],
+        "status"
+    );
+    assert!(read(&dir, "conflict.yml").contains("<<<<<<< ours"));
+}
+
+#[test]
+fn test_project_commands() {
+    let dir = project(&[]);
+    rqm(&dir).args(["init", "proj"]).assert().code(0);
+    assert!(dir.path().join("proj/.rqm/config.yml").is_file());
+
+    rqm(&dir)
+        .args(["--output", "text", "baseline", "create"])
+        .args(["proj/requirements.yml", "Release 1.0"])
+        .assert()
+        .code(0)
+        .stdout(contains("Baseline 'Release 1.0'"));
+    rqm(&dir)
+        .args(["--output", "text", "new", "Audit log"])
+        .args(["--file", "proj/requirements.yml"])
+        .assert()
+        .code(0)
+        .stdout(contains("'Audit log'"));
+    rqm(&dir)
+        .args(["--output", "text", "changelog", "proj/requirements.yml"])
+        .args(["--from", "Release 1.0"])
+        .assert()
+        .code(0)
+        .stdout(contains("## Changes since Release 1.0").and(contains("Audit log")));
+    rqm(&dir)
+        .args(["changelog", "proj/requirements.yml", "--from", "Release 9"])
+        .assert()
+        .code(2);
+
+    let deprecated = "  - summary: Legacy export\n    status: deprecated\n";
+    let file = dir.path().join("proj/requirements.yml");
+    fs::write(&file, read(&dir, "proj/requirements.yml") + deprecated).unwrap();
+    rqm(&dir)
+        .args(["archive", "proj/requirements.yml"])
+        .assert()
+        .code(0)
+        .stdout(contains("Legacy export"));
+    assert!(!read(&dir, "proj/requirements.yml").contains("Legacy export"));
+}
+
+#[test]
+fn test_workload_signoff_and_site() {
+    let dir = project(&[("valid.yml", VALID)]);
+    rqm(&dir)
+        .args(["--output", "text", "workload", "valid.yml"])
+        .assert()
+        .code(0)
+        .stdout(contains("unassigned: 3 open"));
+
+    // Proposed requirements still lack their approvals
+    rqm(&dir)
+        .args(["--output", "text", "signoff", "valid.yml"])
+        .assert()
+        .code(1)
+        .stdout(contains("Login (proposed)"));
+
+    rqm(&dir)
+        .args(["site", "build", "valid.yml", "--out", "site"])
+        .assert()
+        .code(0);
+    assert!(dir.path().join("site/index.html").is_file());
+}
+
+#[test]
+fn test_closed_stdout_is_not_an_error() {
+    let requirements: String = (0..2000)
+        .map(|i| format!("  - summary: Requirement {}\n", i))
+        .collect();
+    let dir = project(&[(
+        "large.yml",
+        &format!("version: \"1.0\"\nrequirements:\n{}", requirements),
+    )]);
+
+    // As in `rqm-validator export ... | head -1`
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_rqm-validator"))
+        .current_dir(dir.path())
+        .args(["export", "large.yml", "--format", "csv"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    drop(child.stdout.take());
+    let mut stderr = String::new();
+    child
+        .stderr
+        .take()
+        .unwrap()
+        .read_to_string(&mut stderr)
+        .unwrap();
+    assert!(child.wait().unwrap().success(), "{}", stderr);
+    assert_eq!(stderr, "");
+}
+
+fn read(dir: &TempDir, name: &str) -> String {
+    fs::read_to_string(Path::new(dir.path()).join(name)).unwrap()
+}
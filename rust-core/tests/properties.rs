@@ -0,0 +1,297 @@
+// RQM - Requirements Management in Code
+// Copyright (c) 2025
+// SPDX-License-Identifier: MIT
+
+//! Property tests over generated requirement documents
+//!
+//! The strategies build arbitrary `RequirementConfig`s: forests of nested
+//! requirements with references by summary that either never close a cycle
+//! or close exactly one, and single chains nested deeply. Malformed input
+//! is covered by the fuzz targets in `fuzz/`.
+
+use proptest::prelude::*;
+use rqm_core::types::{Priority, RequirementReference, Status};
+use rqm_core::{OwnerReference, Parser, Requirement, RequirementConfig, RequirementGraph};
+use std::ffi::{CStr, CString};
+
+/// Deepest nesting generated; serde_yaml refuses documents nested past 128
+const MAX_DEPTH: usize = 40;
+
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[A-Za-z0-9 ,.:#'\"-]{0,40}",
+        any::<String>(),
+        // Scalars YAML would read as something other than a string
+        Just("null".to_string()),
+        Just("~".to_string()),
+        Just("true".to_string()),
+        Just("1.0".to_string()),
+        Just("- item".to_string()),
+        Just("key: value".to_string()),
+    ]
+}
+
+fn status() -> impl Strategy<Value = Status> {
+    prop_oneof![
+        Just(Status::Draft),
+        Just(Status::Proposed),
+        Just(Status::Approved),
+        Just(Status::Implemented),
+        Just(Status::Verified),
+        Just(Status::Deprecated),
+    ]
+}
+
+fn priority() -> impl Strategy<Value = Priority> {
+    prop_oneof![
+        Just(Priority::Critical),
+        Just(Priority::High),
+        Just(Priority::Medium),
+        Just(Priority::Low),
+    ]
+}
+
+/// A requirement without children; `summary` is replaced by a unique one
+fn leaf() -> impl Strategy<Value = Requirement> {
+    (
+        (
+            text(),
+            proptest::option::of(text()),
+            proptest::option::of(text()),
+            proptest::option::of("[a-z]{1,8}@example\\.com|@[a-z]{1,8}"),
+        ),
+        (
+            proptest::collection::vec("[a-z][a-z0-9-]{0,10}", 0..4),
+            proptest::option::of(priority()),
+            proptest::option::of(status()),
+            proptest::option::of(0u32..1000),
+            proptest::collection::vec(text(), 0..3),
+        ),
+    )
+        .prop_map(
+            |((summary, name, description, owner), (tags, priority, status, estimate, info))| {
+                let mut req = Requirement::new(summary);
+                req.name = name;
+                req.description = description;
+                req.owner = owner.map(OwnerReference::String);
+                req.tags = tags;
+                req.priority = priority;
+                req.status = status;
+                req.estimate = estimate.map(|points| f64::from(points) / 4.0);
+                req.further_information = info;
+                req
+            },
+        )
+}
+
+/// Shape of a generated config: a parent (or none, for top level) for each
+/// requirement after the first, and references `(from, to)` by index
+#[derive(Debug, Clone)]
+struct Shape {
+    leaves: Vec<Requirement>,
+    parents: Vec<Option<usize>>,
+    references: Vec<(usize, usize)>,
+}
+
+/// Requirements only ever reference later ones, so no cycle can form
+fn acyclic_shape(max: usize) -> impl Strategy<Value = Shape> {
+    proptest::collection::vec(leaf(), 1..max).prop_flat_map(|leaves| {
+        let count = leaves.len();
+        let parents = (1..count)
+            .map(|index| proptest::option::weighted(0.8, 0..index))
+            .collect::<Vec<_>>();
+        let references =
+            proptest::collection::vec((0..count, 0..count), 0..count).prop_map(|pairs| {
+                pairs
+                    .into_iter()
+                    .filter(|(from, to)| from < to)
+                    .collect::<Vec<_>>()
+            });
+        (Just(leaves), parents, references).prop_map(|(leaves, parents, references)| Shape {
+            leaves,
+            parents: std::iter::once(None).chain(parents).collect(),
+            references,
+        })
+    })
+}
+
+/// An acyclic shape plus one reference back to an earlier requirement
+fn cyclic_shape(max: usize) -> impl Strategy<Value = Shape> {
+    acyclic_shape(max)
+        .prop_filter("a cycle needs two requirements", |shape| {
+            shape.leaves.len() > 1
+        })
+        .prop_flat_map(|shape| {
+            let count = shape.leaves.len();
+            (Just(shape), (1..count))
+                .prop_flat_map(|(shape, from)| (Just(shape), Just(from), 0..from))
+        })
+        .prop_map(|(mut shape, from, to)| {
+            // `to` reaches `from` and `from` refers back to `to`
+            shape.references.push((to, from));
+            shape.references.push((from, to));
+            shape
+        })
+}
+
+fn summary(index: usize, text: &str) -> String {
+    format!("R{} {}", index, text)
+}
+
+impl Shape {
+    fn build(self) -> RequirementConfig {
+        let summaries: Vec<String> = self
+            .leaves
+            .iter()
+            .enumerate()
+            .map(|(index, req)| summary(index, &req.summary))
+            .collect();
+        let mut slots: Vec<Option<Requirement>> = self
+            .leaves
+            .into_iter()
+            .zip(&summaries)
+            .map(|(mut req, summary)| {
+                req.summary = summary.clone();
+                Some(req)
+            })
+            .collect();
+
+        for (from, to) in self.references {
+            if let Some(req) = slots[from].as_mut() {
+                req.requirements
+                    .push(RequirementReference::Reference(summaries[to].clone()));
+            }
+        }
+
+        // Parents precede their children, so attaching from the back moves
+        // complete subtrees
+        for index in (0..slots.len()).rev() {
+            if let Some(parent) = self.parents[index] {
+                let child = slots[index].take().unwrap();
+                slots[parent]
+                    .as_mut()
+                    .unwrap()
+                    .requirements
+                    .insert(0, RequirementReference::Full(Box::new(child)));
+            }
+        }
+
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: slots.into_iter().flatten().collect(),
+        }
+    }
+}
+
+fn acyclic_config() -> impl Strategy<Value = RequirementConfig> {
+    acyclic_shape(24).prop_map(Shape::build)
+}
+
+fn cyclic_config() -> impl Strategy<Value = RequirementConfig> {
+    cyclic_shape(24).prop_map(Shape::build)
+}
+
+/// A single chain of requirements nested up to `MAX_DEPTH` levels deep
+fn deep_config() -> impl Strategy<Value = RequirementConfig> {
+    proptest::collection::vec(leaf(), 1..MAX_DEPTH).prop_map(|leaves| {
+        let mut chain = leaves
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut req)| {
+                req.summary = summary(index, &req.summary);
+                req
+            })
+            .rev();
+        let mut deepest = chain.next().unwrap();
+        for mut parent in chain {
+            parent
+                .requirements
+                .push(RequirementReference::Full(Box::new(deepest)));
+            deepest = parent;
+        }
+        RequirementConfig {
+            version: "1.0".to_string(),
+            aliases: vec![],
+            templates: vec![],
+            requirements: vec![deepest],
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn parse_str_never_panics(yaml in any::<String>()) {
+        let _ = Parser::parse_str(&yaml);
+    }
+
+    #[test]
+    fn parse_str_never_panics_on_yaml_like_input(
+        yaml in "(version: \"1.0\"\n)?requirements:\n(  - (summary: )?[a-z\\[\\]{}:&*!|>-]{0,12}\n( {4,6}(requirements|summary|status|owner): [a-z\\[\\]{}:&*-]{0,8}\n){0,4}){0,4}"
+    ) {
+        let _ = Parser::parse_str(&yaml);
+        let _ = Parser::parse_borrowed(&yaml);
+    }
+
+    #[test]
+    fn yaml_roundtrip_preserves_config(config in acyclic_config()) {
+        let yaml = Parser::to_yaml(&config).unwrap();
+        prop_assert_eq!(Parser::parse_str(&yaml).unwrap(), config);
+    }
+
+    #[test]
+    fn borrowed_parse_matches_owned(config in prop_oneof![acyclic_config(), deep_config()]) {
+        let yaml = Parser::to_yaml(&config).unwrap();
+        let borrowed = Parser::parse_borrowed(&yaml).unwrap().into_owned();
+        prop_assert_eq!(borrowed, Parser::parse_str(&yaml).unwrap());
+    }
+
+    #[test]
+    fn deep_nesting_roundtrips(config in deep_config()) {
+        let yaml = Parser::to_yaml(&config).unwrap();
+        let parsed = Parser::parse_str(&yaml).unwrap();
+        prop_assert_eq!(parsed.all_requirements().len(), config.all_requirements().len());
+        prop_assert!(!RequirementGraph::from_config(&parsed).unwrap().has_cycles());
+    }
+
+    #[test]
+    fn forward_references_never_form_cycles(config in acyclic_config()) {
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        prop_assert!(!graph.has_cycles());
+        prop_assert!(graph.check_acyclic().is_ok());
+        prop_assert_eq!(graph.topological_sort().unwrap().len(), graph.len());
+    }
+
+    #[test]
+    fn back_reference_is_reported_as_cycle(config in cyclic_config()) {
+        let graph = RequirementGraph::from_config(&config).unwrap();
+        prop_assert!(graph.has_cycles());
+        prop_assert!(graph.check_acyclic().is_err());
+        prop_assert!(!graph.cycles().is_empty());
+
+        // Traversal cuts cycles, so it visits every requirement at most once
+        for req in config.all_requirements() {
+            let mut visited = 0;
+            graph.traverse(&req.summary, |_, _| { visited += 1; Ok(()) }).unwrap();
+            prop_assert!(visited <= graph.len());
+        }
+    }
+
+    #[test]
+    fn ffi_returns_json_for_any_input(yaml in any::<String>()) {
+        let yaml = CString::new(yaml.replace('\0', "")).unwrap();
+        for call in [
+            rqm_core::ffi::validate_yaml,
+            rqm_core::ffi::rqm_parse_to_json,
+            rqm_core::ffi::rqm_check_cycles,
+            rqm_core::ffi::rqm_graph_json,
+        ] {
+            let result = unsafe { call(yaml.as_ptr()) };
+            prop_assert!(!result.is_null());
+            let json = unsafe { CStr::from_ptr(result) }.to_str().unwrap().to_owned();
+            unsafe { rqm_core::ffi::free_string(result) };
+            prop_assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+#!/usr/bin/env bash
+set -e
+
+# Fail when rust-core benchmarks regress against a saved baseline
+#
+# Usage:
+#   scripts/bench-gate.sh save [name]     Record a baseline (default: main)
+#   scripts/bench-gate.sh check [name]    Compare against it and gate
+#
+# RQM_BENCH_THRESHOLD is the allowed slowdown as a fraction (default 0.10).
+# RQM_BENCH_SIZES limits the corpus sizes, e.g. "1000,10000" in CI.
+
+SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+PROJECT_ROOT="$(cd "$SCRIPT_DIR/.." && pwd)"
+CRITERION_DIR="$PROJECT_ROOT/rust-core/target/criterion"
+
+# Colors
+GREEN='\033[0;32m'
+YELLOW='\033[1;33m'
+RED='\033[0;31m'
+NC='\033[0m'
+
+ACTION="${1:-check}"
+BASELINE="${2:-main}"
+THRESHOLD="${RQM_BENCH_THRESHOLD:-0.10}"
+
+cd "$PROJECT_ROOT/rust-core"
+
+case $ACTION in
+  save)
+    echo -e "${YELLOW}Recording benchmark baseline '$BASELINE'...${NC}"
+    cargo bench --bench core -- --save-baseline "$BASELINE"
+    echo -e "${GREEN}✓ Baseline '$BASELINE' saved${NC}"
+    exit 0
+    ;;
+  check)
+    ;;
+  *)
+    echo "Usage: $0 [save|check] [baseline]"
+    exit 1
+    ;;
+esac
+
+if ! command -v jq >/dev/null; then
+  echo -e "${RED}jq is required to read benchmark results${NC}"
+  exit 1
+fi
+
+echo -e "${YELLOW}Comparing benchmarks against baseline '$BASELINE'...${NC}"
+find "$CRITERION_DIR" -path '*/change/estimates.json' -delete 2>/dev/null || true
+# Lenient, so benchmarks the baseline does not have yet are measured, not failed
+cargo bench --bench core -- --baseline-lenient "$BASELINE"
+
+# Criterion records the relative change of each benchmark under change/
+REGRESSIONS=0
+while IFS= read -r estimates; do
+  bench="${estimates#"$CRITERION_DIR"/}"
+  bench="${bench%/change/estimates.json}"
+  change=$(jq '.mean.point_estimate' "$estimates")
+  if jq -e --argjson change "$change" --argjson limit "$THRESHOLD" -n '$change > $limit' >/dev/null; then
+    printf "${RED}✗ %s is %.1f%% slower${NC}\n" "$bench" "$(jq -n "$change * 100")"
+    REGRESSIONS=$((REGRESSIONS + 1))
+  else
+    printf "${GREEN}✓ %s (%+.1f%%)${NC}\n" "$bench" "$(jq -n "$change * 100")"
+  fi
+done < <(find "$CRITERION_DIR" -path '*/change/estimates.json' | sort)
+
+if [ "$REGRESSIONS" -gt 0 ]; then
+  echo -e "${RED}$REGRESSIONS benchmark(s) regressed by more than $(jq -n "$THRESHOLD * 100")%${NC}"
+  exit 1
+fi
+
+echo -e "${GREEN}No benchmark regressed by more than $(jq -n "$THRESHOLD * 100")%${NC}"
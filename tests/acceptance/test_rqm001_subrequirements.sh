@@ -119,7 +119,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/schema_test.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/schema_test.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Validator successfully loads and applies schema"
 else
@@ -153,7 +153,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/duplicate_summary.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/duplicate_summary.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == false' > /dev/null 2>&1; then
     if echo "$OUTPUT" | jq -r '.errors[]' | grep -i "duplicate\|unique" > /dev/null; then
         test_passed "Duplicate summaries correctly detected and rejected"
@@ -189,7 +189,7 @@ requirements:
         owner: test@example.com
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/nested_duplicate.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/nested_duplicate.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == false' > /dev/null 2>&1; then
     test_passed "Nested duplicate summaries detected"
 else
@@ -224,7 +224,7 @@ requirements:
     status: proposed
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/unique_summaries.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/unique_summaries.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "File with unique summaries passes validation"
 else
@@ -251,7 +251,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/owner_email.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/owner_email.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Email address accepted as owner"
 else
@@ -272,7 +272,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/owner_github.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/owner_github.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "GitHub username accepted as owner"
 else
@@ -300,7 +300,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/owner_alias.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/owner_alias.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Alias owner accepted when defined"
 else
@@ -321,7 +321,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/owner_invalid.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/owner_invalid.yml" 2>&1)
 # This might pass or fail depending on schema strictness
 # For now, just verify validator runs
 if [ -n "$OUTPUT" ]; then
@@ -344,7 +344,7 @@ requirements:
     status: draft
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/owner_undefined_alias.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/owner_undefined_alias.yml" 2>&1)
 # This should ideally fail, but depends on validation implementation
 if echo "$OUTPUT" | jq -e '.valid == false' > /dev/null 2>&1; then
     test_passed "Undefined alias rejected"
@@ -6,7 +6,10 @@ set +e
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 PROJECT_ROOT="$(cd "$SCRIPT_DIR/../.." && pwd)"
-TEST_DIR="$PROJECT_ROOT/tests/acceptance/fixtures"
+# Files written by the tests go to a scratch directory, keeping the
+# tracked fixtures as they are
+TEST_DIR="$(mktemp -d)"
+trap 'rm -rf "$TEST_DIR"' EXIT
 
 # Colors for output
 GREEN='\033[0;32m'
@@ -14,8 +17,6 @@ RED='\033[0;31m'
 YELLOW='\033[1;33m'
 NC='\033[0m' # No Color
 
-mkdir -p "$TEST_DIR"
-
 # Test counter
 TESTS_RUN=0
 TESTS_PASSED=0
@@ -194,7 +195,7 @@ fi
 # Test 9: Validator produces JSON output
 test_header "Test 9: Validator produces structured JSON output"
 if [ -f "$VALIDATOR_DEBUG" ]; then
-    OUTPUT=$("$VALIDATOR_DEBUG" "$TEST_DIR/relative_test.yml" 2>&1)
+    OUTPUT=$("$VALIDATOR_DEBUG" validate "$TEST_DIR/relative_test.yml" 2>&1)
     
     if echo "$OUTPUT" | jq empty 2>/dev/null; then
         test_passed "Validator produces valid JSON output"
@@ -82,7 +82,7 @@ requirements:
     acceptance_test_link: https://github.com/example/rqm/blob/main/tests/test_feature.sh
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/test_link.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/test_link.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "acceptance_test_link field validates successfully"
 else
@@ -113,7 +113,7 @@ requirements:
     owner: test@example.com
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/multi_links.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/multi_links.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Multiple requirements with varying test link configurations accepted"
 else
@@ -139,7 +139,7 @@ requirements:
     acceptance_test_link: https://example.com/tests/test.sh
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/url_formats.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/url_formats.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Various URI formats accepted for test links (https://, file://)"
 else
@@ -166,7 +166,7 @@ requirements:
         acceptance_test_link: https://github.com/example/tests/child.sh
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/nested_links.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/nested_links.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Test links in nested requirements accepted"
 else
@@ -209,7 +209,7 @@ requirements:
     acceptance_test_link: https://github.com/238855/rqm/blob/main/tests/acceptance/test_validation.sh
 YAML
 
-OUTPUT=$("$VALIDATOR_PATH" "$TEST_DIR/real_test_link.yml" 2>&1)
+OUTPUT=$("$VALIDATOR_PATH" validate "$TEST_DIR/real_test_link.yml" 2>&1)
 if echo "$OUTPUT" | jq -e '.valid == true' > /dev/null 2>&1; then
     test_passed "Test link to actual test file validates (link validation is optional)"
 else